@@ -0,0 +1,3 @@
+-- Periodic (Monthly/Quarterly/Yearly) plans now pay out in tranches; index
+-- each tranche claim alongside the other structured events.
+ALTER TYPE event_type ADD VALUE IF NOT EXISTS 'tranche_claimed';
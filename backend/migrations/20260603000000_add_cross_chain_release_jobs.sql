@@ -0,0 +1,57 @@
+-- ──────────────────────────────────────────────────────────────────────────────
+-- Cross-Chain Release Relayer
+-- Owner wallets on external chains, one release job per discovered asset of a
+-- triggered plan (pending → submitted → confirmed | failed), and the worker's
+-- contract event cursor.
+-- ──────────────────────────────────────────────────────────────────────────────
+
+CREATE TABLE IF NOT EXISTS cross_chain_wallets (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id     UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    chain       VARCHAR(20)  NOT NULL,
+    address     VARCHAR(255) NOT NULL,
+    created_at  TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, chain)
+);
+
+CREATE TABLE IF NOT EXISTS cross_chain_release_jobs (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    plan_id             UUID REFERENCES plans(id) ON DELETE SET NULL,
+    contract_plan_id    BIGINT       NOT NULL,
+    asset_index         INTEGER      NOT NULL CHECK (asset_index >= 0),
+
+    -- Asset details
+    chain               VARCHAR(20)  NOT NULL,
+    asset_symbol        VARCHAR(20)  NOT NULL,
+    contract_address    VARCHAR(255),
+    source_address      VARCHAR(255) NOT NULL,
+    -- Smallest-unit amount, kept as a string for precision
+    amount              VARCHAR(78)  NOT NULL,
+
+    -- Status tracking
+    status              VARCHAR(20)  NOT NULL DEFAULT 'pending'
+                        CHECK (status IN ('pending', 'submitted', 'confirmed', 'failed')),
+    remote_tx_hash      VARCHAR(255),
+    attempts            INTEGER      NOT NULL DEFAULT 0,
+    last_error          TEXT,
+    trigger_ledger      BIGINT,
+    reported_at         TIMESTAMP WITH TIME ZONE,
+
+    -- Timestamps
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    submitted_at        TIMESTAMP WITH TIME ZONE,
+    confirmed_at        TIMESTAMP WITH TIME ZONE,
+
+    UNIQUE (contract_plan_id, asset_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_cc_release_jobs_plan_id ON cross_chain_release_jobs(plan_id);
+CREATE INDEX IF NOT EXISTS idx_cc_release_jobs_status  ON cross_chain_release_jobs(status);
+
+CREATE TABLE IF NOT EXISTS cross_chain_relayer_cursors (
+    name            VARCHAR(64) PRIMARY KEY,
+    last_ledger     BIGINT NOT NULL DEFAULT 0,
+    paging_token    VARCHAR(64),
+    updated_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,26 @@
+-- ──────────────────────────────────────────────────────────────────────────────
+-- Dead-Man's-Switch Check-Ins
+-- Per-plan check-in cadence, grace window and reminder escalation state.
+-- escalation_level: 0 none, 1 in-app, 2 email, 3 SMS, 4 emergency contacts.
+-- ──────────────────────────────────────────────────────────────────────────────
+
+CREATE TABLE IF NOT EXISTS plan_check_ins (
+    plan_id                 UUID PRIMARY KEY REFERENCES plans(id) ON DELETE CASCADE,
+    user_id                 UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    cadence_seconds         BIGINT   NOT NULL CHECK (cadence_seconds > 0),
+    grace_period_seconds    BIGINT   NOT NULL DEFAULT 0 CHECK (grace_period_seconds >= 0),
+    sms_phone               VARCHAR(50),
+
+    last_check_in_at        TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    next_due_at             TIMESTAMP WITH TIME ZONE NOT NULL,
+    escalation_level        SMALLINT NOT NULL DEFAULT 0 CHECK (escalation_level BETWEEN 0 AND 4),
+    last_reminder_at        TIMESTAMP WITH TIME ZONE,
+    -- Hash of the last on-chain record_activity call
+    last_tx_hash            VARCHAR(255),
+
+    created_at              TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at              TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_plan_check_ins_user_id     ON plan_check_ins(user_id);
+CREATE INDEX IF NOT EXISTS idx_plan_check_ins_next_due_at ON plan_check_ins(next_due_at);
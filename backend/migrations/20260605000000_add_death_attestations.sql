@@ -0,0 +1,26 @@
+-- ──────────────────────────────────────────────────────────────────────────────
+-- Death-Certificate Attestation
+-- Registered attestors (registrars, notaries, hospitals) and the encrypted
+-- death certificates they upload. document_hash is the hex SHA-256 of the
+-- plaintext certificate, as submitted to the contract.
+-- ──────────────────────────────────────────────────────────────────────────────
+
+CREATE TABLE IF NOT EXISTS death_attestors (
+    user_id         UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    kind            VARCHAR(20) NOT NULL CHECK (kind IN ('registrar', 'notary', 'hospital')),
+    registered_by   UUID NOT NULL REFERENCES admins(id),
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS attestation_documents (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    plan_id             UUID NOT NULL REFERENCES plans(id) ON DELETE CASCADE,
+    attestor_user_id    UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    document_hash       VARCHAR(64) NOT NULL,
+    encrypted_content   BYTEA NOT NULL,
+    encryption_nonce    BYTEA NOT NULL,
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_attestation_documents_plan_id ON attestation_documents(plan_id);
+CREATE INDEX IF NOT EXISTS idx_attestation_documents_hash    ON attestation_documents(plan_id, document_hash);
@@ -0,0 +1,18 @@
+-- ──────────────────────────────────────────────────────────────────────────────
+-- Post-Trigger Contest Windows
+-- Mirrors the contract's CONTEST/START and CONTEST/END events so plan APIs can
+-- show a countdown. Keyed by the on-chain plan id; one row per plan, replaced
+-- if the plan is triggered again after a cancellation.
+-- ──────────────────────────────────────────────────────────────────────────────
+
+CREATE TABLE IF NOT EXISTS plan_contest_windows (
+    contract_plan_id    BIGINT PRIMARY KEY,
+    started_at          TIMESTAMP WITH TIME ZONE NOT NULL,
+    ends_at             TIMESTAMP WITH TIME ZONE NOT NULL,
+    ended_at            TIMESTAMP WITH TIME ZONE,
+    cancelled           BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_plan_contest_windows_ends_at ON plan_contest_windows(ends_at);
@@ -0,0 +1,23 @@
+-- ──────────────────────────────────────────────────────────────────────────────
+-- Bank Account References
+-- The inheritance contract stores an opaque `bank_account_ref` per beneficiary
+-- instead of the plaintext account number. The account number lives here,
+-- AES-256-GCM encrypted, and is resolved when settling a fiat payout.
+--
+-- `reference` is the lowercase hex of the bytes stored on-chain: 32 random
+-- bytes for new accounts, or the SHA-256 of the plaintext for accounts
+-- imported from plans written before contract v2.
+-- ──────────────────────────────────────────────────────────────────────────────
+
+CREATE TABLE IF NOT EXISTS bank_account_refs (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    reference           VARCHAR(64) NOT NULL UNIQUE,
+    user_id             UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    masked_account      VARCHAR(64) NOT NULL,
+    encrypted_account   BYTEA NOT NULL,
+    encryption_nonce    BYTEA NOT NULL,
+    is_legacy           BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_bank_account_refs_user_id ON bank_account_refs(user_id);
@@ -0,0 +1,24 @@
+-- ──────────────────────────────────────────────────────────────────────────────
+-- Multi-Asset Collateral
+-- A loan's `collateral_asset`/`collateral_amount` remain its primary
+-- collateral. Further assets posted against the same loan are tracked here,
+-- and each asset counts toward the borrowing limit at its own collateral
+-- ratio, mirroring the contracts' collateral baskets.
+-- ──────────────────────────────────────────────────────────────────────────────
+
+ALTER TABLE price_feeds
+    ADD COLUMN IF NOT EXISTS collateral_ratio_bps INTEGER NOT NULL DEFAULT 15000
+        CHECK (collateral_ratio_bps >= 10000);
+
+CREATE TABLE IF NOT EXISTS loan_collateral_positions (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    loan_id             UUID NOT NULL REFERENCES loan_lifecycle(id) ON DELETE CASCADE,
+    asset_code          VARCHAR(20) NOT NULL,
+    amount              NUMERIC(30, 8) NOT NULL CHECK (amount >= 0),
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE (loan_id, asset_code)
+);
+
+CREATE INDEX IF NOT EXISTS idx_loan_collateral_positions_loan_id
+    ON loan_collateral_positions(loan_id);
@@ -0,0 +1,11 @@
+-- ──────────────────────────────────────────────────────────────────────────────
+-- Loan Rate Modes
+-- A loan either follows the pool's variable borrow rate or pays a fixed rate
+-- locked when it was opened, mirroring the lending contract's rate modes.
+-- `interest_rate_bps` holds the fixed rate of a fixed loan and the variable
+-- rate last observed for a variable one.
+-- ──────────────────────────────────────────────────────────────────────────────
+
+ALTER TABLE loan_lifecycle
+    ADD COLUMN IF NOT EXISTS rate_mode VARCHAR(16) NOT NULL DEFAULT 'variable'
+        CHECK (rate_mode IN ('variable', 'fixed'));
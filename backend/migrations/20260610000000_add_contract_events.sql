@@ -0,0 +1,27 @@
+-- On-chain contract events mirrored by the contract event indexer.
+CREATE TABLE IF NOT EXISTS contract_events (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Soroban RPC event id; replays of the same event are ignored.
+    event_id            VARCHAR(64) NOT NULL UNIQUE,
+    contract_kind       VARCHAR(20) NOT NULL,
+    contract_id         VARCHAR(56) NOT NULL,
+    -- Leading symbol topics joined with '/', e.g. 'POOL/DEPOSIT'.
+    event_name          VARCHAR(64) NOT NULL,
+    ledger              BIGINT NOT NULL,
+    ledger_closed_at    TIMESTAMP WITH TIME ZONE,
+    tx_hash             VARCHAR(64),
+    topics              JSONB NOT NULL,
+    payload             JSONB NOT NULL,
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_contract_events_contract_ledger ON contract_events(contract_id, ledger);
+CREATE INDEX IF NOT EXISTS idx_contract_events_name            ON contract_events(event_name);
+CREATE INDEX IF NOT EXISTS idx_contract_events_tx_hash         ON contract_events(tx_hash);
+
+CREATE TABLE IF NOT EXISTS contract_indexer_cursors (
+    contract_id     VARCHAR(56) PRIMARY KEY,
+    last_ledger     BIGINT NOT NULL DEFAULT 0,
+    paging_token    VARCHAR(64),
+    updated_at      TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
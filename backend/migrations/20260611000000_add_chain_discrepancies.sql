@@ -0,0 +1,39 @@
+-- ──────────────────────────────────────────────────────────────────────────────
+-- Chain Reconciliation
+-- Differences found between contract storage and the backend's own tables.
+-- A discrepancy stays open (resolved_at IS NULL) until a later pass no longer
+-- sees it; healed discrepancies are recorded already resolved.
+-- ──────────────────────────────────────────────────────────────────────────────
+
+-- Links the backend rows to the contract state they mirror.
+ALTER TABLE loan_lifecycle
+    ADD COLUMN IF NOT EXISTS contract_loan_id BIGINT;
+
+ALTER TABLE pools
+    ADD COLUMN IF NOT EXISTS token_contract_id VARCHAR(56);
+
+CREATE INDEX IF NOT EXISTS idx_loan_lifecycle_contract_loan_id
+    ON loan_lifecycle(contract_loan_id);
+
+CREATE TABLE IF NOT EXISTS chain_discrepancies (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    entity_type         VARCHAR(16)  NOT NULL CHECK (entity_type IN ('plan', 'loan', 'pool')),
+    entity_id           UUID,
+    -- Contract plan id, contract loan id or pool asset code.
+    entity_ref          VARCHAR(128) NOT NULL,
+    field               VARCHAR(128) NOT NULL,
+    on_chain_value      TEXT,
+    database_value      TEXT,
+    severity            VARCHAR(16)  NOT NULL CHECK (severity IN ('info', 'warning', 'critical')),
+    healed              BOOLEAN      NOT NULL DEFAULT FALSE,
+    first_seen_at       TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    last_seen_at        TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    resolved_at         TIMESTAMP WITH TIME ZONE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_chain_discrepancies_open
+    ON chain_discrepancies(entity_type, entity_ref, field)
+    WHERE resolved_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_chain_discrepancies_severity
+    ON chain_discrepancies(severity, first_seen_at DESC);
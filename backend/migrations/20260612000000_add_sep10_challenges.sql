@@ -0,0 +1,16 @@
+-- ──────────────────────────────────────────────────────────────────────────────
+-- SEP-10 Web Authentication
+-- Challenge transactions already exchanged for a token, keyed by transaction
+-- hash, so a signed challenge cannot be replayed within its time bounds.
+-- Rows are pruned once the challenge has expired.
+-- ──────────────────────────────────────────────────────────────────────────────
+
+CREATE TABLE IF NOT EXISTS sep10_used_challenges (
+    tx_hash             VARCHAR(64) PRIMARY KEY,
+    account             VARCHAR(56) NOT NULL,
+    expires_at          TIMESTAMP WITH TIME ZONE NOT NULL,
+    used_at             TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_sep10_used_challenges_expires_at
+    ON sep10_used_challenges(expires_at);
@@ -0,0 +1,42 @@
+-- ──────────────────────────────────────────────────────────────────────────────
+-- Authenticator-app (TOTP) second factor, recovery codes and per-user policy
+-- for which sensitive actions need a second factor.
+-- ──────────────────────────────────────────────────────────────────────────────
+
+-- send_2fa upserts on user_id, which needs a unique index rather than the
+-- plain one the table was created with.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_user_2fa_user_id_unique ON user_2fa(user_id);
+
+CREATE TABLE IF NOT EXISTS user_totp (
+    user_id             UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    -- RFC 6238 shared secret, encrypted with DOCUMENT_ENCRYPTION_KEY.
+    encrypted_secret    BYTEA NOT NULL,
+    encryption_nonce    BYTEA NOT NULL,
+    -- NULL until the user proves the authenticator app is set up.
+    confirmed_at        TIMESTAMP WITH TIME ZONE,
+    -- Last accepted time step; codes from it or earlier are not accepted again.
+    last_used_step      BIGINT,
+    failed_attempts     INTEGER NOT NULL DEFAULT 0,
+    locked_until        TIMESTAMP WITH TIME ZONE,
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS user_recovery_codes (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id             UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- SHA-256 hex of the normalized code.
+    code_hash           VARCHAR(64) NOT NULL,
+    used_at             TIMESTAMP WITH TIME ZONE,
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_user_recovery_codes_user_hash
+    ON user_recovery_codes(user_id, code_hash);
+
+CREATE TABLE IF NOT EXISTS user_2fa_policies (
+    user_id                 UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    require_for_claims      BOOLEAN NOT NULL DEFAULT FALSE,
+    require_for_withdrawals BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at              TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,15 @@
+-- ──────────────────────────────────────────────────────────────────────────────
+-- Legacy Bank Account Targets
+-- Accounts imported from plans written before contract v2 now get a random
+-- reference, like new accounts, which the admin records on-chain with the
+-- contract's `assign_legacy_bank_account_ref`. The plan and beneficiary the
+-- reference belongs to make the import idempotent.
+-- ──────────────────────────────────────────────────────────────────────────────
+
+ALTER TABLE bank_account_refs
+    ADD COLUMN IF NOT EXISTS contract_plan_id  BIGINT,
+    ADD COLUMN IF NOT EXISTS beneficiary_index INTEGER CHECK (beneficiary_index >= 0);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_bank_account_refs_legacy_target
+    ON bank_account_refs(contract_plan_id, beneficiary_index)
+    WHERE is_legacy;
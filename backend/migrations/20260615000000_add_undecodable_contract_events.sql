@@ -0,0 +1,18 @@
+-- Contract events the indexer could not decode, kept as raw XDR so they can
+-- be inspected and replayed once the decoder handles them.
+CREATE TABLE IF NOT EXISTS undecodable_contract_events (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Soroban RPC event id; replays of the same event are ignored.
+    event_id            VARCHAR(64) NOT NULL UNIQUE,
+    contract_kind       VARCHAR(20) NOT NULL,
+    contract_id         VARCHAR(56) NOT NULL,
+    ledger              BIGINT NOT NULL,
+    tx_hash             VARCHAR(64),
+    -- Base64 XDR topics and body, as returned by getEvents.
+    topics              JSONB NOT NULL,
+    value               TEXT NOT NULL,
+    created_at          TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_undecodable_contract_events_contract_ledger
+    ON undecodable_contract_events(contract_id, ledger);
@@ -19,6 +19,9 @@ use tower_http::{
 };
 
 use crate::cache;
+use crate::check_in::{
+    ActivityRecorder, CheckInService, SorobanActivityRecorder, UpdateCheckInSettingsRequest,
+};
 use crate::cross_chain_asset_discovery::CrossChainAssetDiscoveryService;
 use crate::middleware::{
     cache_headers_middleware, enforce_max_request_size, request_id_middleware,
@@ -30,17 +33,24 @@ use crate::analytics::analytics_router;
 use crate::api_error::ApiError;
 use crate::api_versioning::{list_api_versions, versioning_middleware};
 use crate::auth::{AuthenticatedAdmin, AuthenticatedUser};
+use crate::bank_account_vault::{
+    BankAccountVault, ImportLegacyBankAccountRequest, StoreBankAccountRequest,
+};
 use crate::beneficiary_sync::{BeneficiarySyncService, DocumentBeneficiary};
 use crate::collateral_management::{
-    AddCollateralRequest, CollateralManagementService, RemoveCollateralRequest,
-    SwapCollateralRequest,
+    AddCollateralRequest, CollateralAssetQuery, CollateralManagementService,
+    RemoveCollateralRequest, SwapCollateralRequest,
 };
 use crate::config::Config;
+use crate::contest_window::ContestWindowService;
 use crate::contingent_beneficiary::{
     AddContingentBeneficiaryRequest, ContingentBeneficiaryService, PromoteContingentRequest,
     RemoveContingentBeneficiaryRequest, SetContingencyConditionsRequest,
 };
 use crate::csrf::{csrf_protection_middleware, get_csrf_token};
+use crate::death_attestation::{
+    DeathAttestationService, RegisterAttestorRequest, VerifyCertificateRequest,
+};
 use crate::document_storage::DocumentStorageService;
 use crate::governance::{
     CreateProposalRequest, DelegateVotesRequest, DelegationResponse, GovernanceDelegation,
@@ -48,7 +58,7 @@ use crate::governance::{
 };
 use crate::insurance_fund::{CreateInsuranceClaimRequest, ProcessInsuranceClaimRequest};
 use crate::legacy_content::{ContentListFilters, LegacyContentService};
-use crate::loan_lifecycle::{CreateLoanRequest, LoanLifecycleService, LoanListFilters};
+use crate::loan_lifecycle::{CreateLoanRequest, LoanLifecycleService, LoanListFilters, RateMode};
 use crate::message_access_audit::{MessageAccessAuditService, MessageAuditFilters};
 use crate::pagination::PaginationQuery;
 use crate::secure_messages::{
@@ -65,6 +75,8 @@ use crate::service::{
 };
 use crate::session::{list_sessions, logout, logout_all, revoke_session, session_guard_middleware};
 use crate::stress_testing::StressTestingEngine;
+use crate::trigger_expression::PreviewTriggerExpressionRequest;
+use crate::two_factor::{TwoFactorCodeRequest, TwoFactorService, UpdateTwoFactorPolicyRequest};
 use crate::webhook::{delete_webhook, get_webhooks, register_webhook, WebhookService};
 use crate::will_compliance::{ValidationResult, WillComplianceService};
 use crate::will_pdf::{WillDocumentInput, WillPdfService, WillTemplate};
@@ -85,6 +97,9 @@ pub struct AppState {
     pub insurance_fund_service: Arc<crate::insurance_fund::InsuranceFundService>,
     pub webhook_service: Arc<WebhookService>,
     pub asset_discovery_service: Arc<CrossChainAssetDiscoveryService>,
+    pub check_in_service: Arc<CheckInService>,
+    /// `None` when SEP-10 web authentication is not configured.
+    pub sep10: Option<Arc<crate::sep10::Sep10Service>>,
 }
 
 pub async fn create_app(
@@ -123,6 +138,20 @@ pub async fn create_app(
     let cache = Arc::new(crate::cache::CacheService::from_env().await);
     let asset_discovery_service = Arc::new(CrossChainAssetDiscoveryService::from_env());
 
+    // Check-in links are signed with their own secret when one is configured.
+    let check_in_secret =
+        std::env::var("CHECK_IN_SIGNING_SECRET").unwrap_or_else(|_| config.jwt_secret.clone());
+    let check_in_service = Arc::new(CheckInService::new(
+        db.clone(),
+        Arc::new(crate::alert_provider::MockAlertProvider),
+        SorobanActivityRecorder::from_env()?
+            .map(|recorder| Arc::new(recorder) as Arc<dyn ActivityRecorder>),
+        &check_in_secret,
+    ));
+    check_in_service.clone().start();
+
+    let sep10 = crate::sep10::Sep10Service::from_env()?.map(Arc::new);
+
     let state = Arc::new(AppState {
         db: db.clone(),
         config: config.clone(),
@@ -132,6 +161,8 @@ pub async fn create_app(
         insurance_fund_service,
         webhook_service,
         asset_discovery_service,
+        check_in_service,
+        sep10,
     });
 
     let graphql_schema = crate::graphql::create_schema(db.clone(), config.clone());
@@ -219,6 +250,23 @@ pub async fn create_app(
         .route("/api/v1/auth/logout-all", post(logout_all))
         .route("/api/v1/auth/sessions", get(list_sessions))
         .route("/api/v1/auth/sessions/:session_id", delete(revoke_session))
+        // ── Second factor ─────────────────────────────────────────────────────
+        .route("/api/v1/auth/2fa", get(get_two_factor_status))
+        .route(
+            "/api/v1/auth/2fa/totp",
+            post(enroll_totp).delete(disable_totp),
+        )
+        .route("/api/v1/auth/2fa/totp/confirm", post(confirm_totp))
+        .route(
+            "/api/v1/auth/2fa/recovery-codes",
+            post(regenerate_recovery_codes),
+        )
+        .route("/api/v1/auth/2fa/policy", put(update_two_factor_policy))
+        // ── SEP-10 Stellar web authentication ─────────────────────────────────
+        .route(
+            crate::sep10::SEP10_PATH,
+            get(crate::sep10::get_challenge).post(crate::sep10::post_token),
+        )
         // Prometheus metrics scrape endpoint (Issue #423).
         // Restrict access at the network/ingress layer in production.
         .route("/metrics", get(crate::metrics::metrics_handler))
@@ -236,6 +284,52 @@ pub async fn create_app(
         )
         .route("/api/plans/:plan_id/claim", post(claim_plan))
         .route("/api/plans/:plan_id", get(get_plan))
+        // ── Dead-man's-switch check-ins ───────────────────────────────────────
+        .route(
+            "/api/plans/:plan_id/check-in",
+            get(get_check_in_status)
+                .put(configure_check_in)
+                .post(check_in_plan),
+        )
+        .route("/api/check-in/confirm", post(confirm_check_in))
+        .route(
+            "/api/plans/trigger-expression/preview",
+            post(preview_trigger_expression),
+        )
+        // ── Death-certificate attestation ─────────────────────────────────────
+        .route(
+            "/api/plans/:plan_id/death-certificates",
+            post(upload_death_certificate)
+                .get(list_death_certificates)
+                .layer(axum::extract::DefaultBodyLimit::max(
+                    crate::validation::MAX_DOCUMENT_UPLOAD_BYTES,
+                )),
+        )
+        .route(
+            "/api/death-certificates/:document_id/verify",
+            post(verify_death_certificate),
+        )
+        .route(
+            "/api/admin/death-attestors",
+            post(register_death_attestor).get(list_death_attestors),
+        )
+        .route(
+            "/api/admin/death-attestors/:user_id",
+            delete(remove_death_attestor),
+        )
+        // ── Bank account references ───────────────────────────────────────────
+        .route(
+            "/api/bank-accounts",
+            post(store_bank_account).get(list_bank_accounts),
+        )
+        .route(
+            "/api/admin/bank-accounts/legacy",
+            post(import_legacy_bank_account),
+        )
+        .route(
+            "/api/admin/bank-accounts/:reference",
+            get(resolve_bank_account),
+        )
         .route("/api/plans", post(create_plan))
         .route(
             "/api/messages/legacy",
@@ -321,6 +415,10 @@ pub async fn create_app(
         .route("/api/loans/lifecycle/summary", get(get_lifecycle_summary))
         .route("/api/loans/lifecycle/:id", get(get_lifecycle_loan))
         .route("/api/loans/lifecycle/:id/repay", post(repay_lifecycle_loan))
+        .route(
+            "/api/loans/lifecycle/:id/rate-mode",
+            post(switch_lifecycle_loan_rate_mode),
+        )
         .route(
             "/api/admin/loans/lifecycle/:id/liquidate",
             post(liquidate_lifecycle_loan),
@@ -868,6 +966,309 @@ async fn create_plan(
     })))
 }
 
+/// Current check-in schedule and escalation stage for a plan.
+///
+/// `GET /api/plans/:plan_id/check-in`
+async fn get_check_in_status(
+    State(state): State<Arc<AppState>>,
+    Path(plan_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let status = state
+        .check_in_service
+        .get_status(user.user_id, plan_id)
+        .await?;
+    Ok(Json(json!({ "status": "success", "data": status })))
+}
+
+/// Set the check-in cadence and grace window for a plan.
+///
+/// `PUT /api/plans/:plan_id/check-in`
+async fn configure_check_in(
+    State(state): State<Arc<AppState>>,
+    Path(plan_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<UpdateCheckInSettingsRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let status = state
+        .check_in_service
+        .configure(user.user_id, plan_id, &req)
+        .await?;
+    Ok(Json(json!({ "status": "success", "data": status })))
+}
+
+/// Check in as the authenticated owner.
+///
+/// `POST /api/plans/:plan_id/check-in`
+async fn check_in_plan(
+    State(state): State<Arc<AppState>>,
+    Path(plan_id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let status = state
+        .check_in_service
+        .check_in(user.user_id, plan_id, "app")
+        .await?;
+    Ok(Json(json!({ "status": "success", "data": status })))
+}
+
+#[derive(serde::Deserialize)]
+struct ConfirmCheckInRequest {
+    token: String,
+}
+
+/// One-click check-in from a signed reminder link; the token is the
+/// credential.
+///
+/// `POST /api/check-in/confirm`
+async fn confirm_check_in(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<ConfirmCheckInRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let status = state
+        .check_in_service
+        .check_in_with_token(&req.token)
+        .await?;
+    Ok(Json(json!({ "status": "success", "data": status })))
+}
+
+/// Validate a composable trigger expression and preview its behaviour
+/// before it is submitted to `set_trigger_expression` on-chain.
+///
+/// `POST /api/plans/trigger-expression/preview`
+async fn preview_trigger_expression(
+    AuthenticatedUser(_user): AuthenticatedUser,
+    Json(req): Json<PreviewTriggerExpressionRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let preview = req.expression.preview(req.state.as_ref())?;
+    Ok(Json(json!({ "status": "success", "data": preview })))
+}
+
+#[derive(serde::Deserialize)]
+struct UploadDeathCertificateQuery {
+    document_hash: Option<String>,
+}
+
+/// Attestor: upload a death certificate for a plan as the raw request body.
+/// If `document_hash` is given the upload is rejected unless it matches.
+/// Returns the hash to submit to `submit_death_attestation` on-chain.
+///
+/// `POST /api/plans/:plan_id/death-certificates?document_hash=<hex>`
+async fn upload_death_certificate(
+    State(state): State<Arc<AppState>>,
+    Path(plan_id): Path<Uuid>,
+    Query(query): Query<UploadDeathCertificateQuery>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    body: axum::body::Bytes,
+) -> Result<Json<Value>, ApiError> {
+    let doc = DeathAttestationService::upload_certificate(
+        &state.db,
+        user.user_id,
+        plan_id,
+        &body,
+        query.document_hash.as_deref(),
+    )
+    .await?;
+    Ok(Json(json!({ "status": "success", "data": doc })))
+}
+
+/// List the death certificates uploaded for a plan.
+///
+/// `GET /api/plans/:plan_id/death-certificates`
+async fn list_death_certificates(
+    State(state): State<Arc<AppState>>,
+    Path(plan_id): Path<Uuid>,
+    AuthenticatedUser(_user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let docs = DeathAttestationService::list_plan_certificates(&state.db, plan_id).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": docs, "count": docs.len() }),
+    ))
+}
+
+/// Check that a stored certificate hashes to the given value, e.g. the hash
+/// attested on-chain.
+///
+/// `POST /api/death-certificates/:document_id/verify`
+async fn verify_death_certificate(
+    State(state): State<Arc<AppState>>,
+    Path(document_id): Path<Uuid>,
+    AuthenticatedUser(_user): AuthenticatedUser,
+    Json(req): Json<VerifyCertificateRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let matches =
+        DeathAttestationService::verify_certificate(&state.db, document_id, &req.document_hash)
+            .await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": { "document_id": document_id, "matches": matches }
+    })))
+}
+
+/// Admin: register a user as a death attestor.
+///
+/// `POST /api/admin/death-attestors`
+async fn register_death_attestor(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    Json(req): Json<RegisterAttestorRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let attestor =
+        DeathAttestationService::register_attestor(&state.db, admin.admin_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": attestor })))
+}
+
+/// Admin: list registered death attestors.
+///
+/// `GET /api/admin/death-attestors`
+async fn list_death_attestors(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedAdmin(_admin): AuthenticatedAdmin,
+) -> Result<Json<Value>, ApiError> {
+    let attestors = DeathAttestationService::list_attestors(&state.db).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": attestors, "count": attestors.len() }),
+    ))
+}
+
+/// Admin: remove a death attestor.
+///
+/// `DELETE /api/admin/death-attestors/:user_id`
+async fn remove_death_attestor(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+) -> Result<Json<Value>, ApiError> {
+    DeathAttestationService::remove_attestor(&state.db, admin.admin_id, user_id).await?;
+    Ok(Json(json!({ "status": "success" })))
+}
+
+/// Store a beneficiary bank account encrypted and return the reference to
+/// pass as `bank_account_ref` when creating the plan on-chain.
+///
+/// `POST /api/bank-accounts`
+async fn store_bank_account(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<StoreBankAccountRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let account = BankAccountVault::store(&state.db, user.user_id, &req.account_number).await?;
+    Ok(Json(json!({ "status": "success", "data": account })))
+}
+
+/// List the caller's stored bank accounts (masked).
+///
+/// `GET /api/bank-accounts`
+async fn list_bank_accounts(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let accounts = BankAccountVault::list_for_user(&state.db, user.user_id).await?;
+    Ok(Json(
+        json!({ "status": "success", "data": accounts, "count": accounts.len() }),
+    ))
+}
+
+/// Admin: import an account number from a plan written before contract v2.
+/// The returned reference is recorded on-chain with the contract's
+/// `assign_legacy_bank_account_ref`.
+///
+/// `POST /api/admin/bank-accounts/legacy`
+async fn import_legacy_bank_account(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+    Json(req): Json<ImportLegacyBankAccountRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let account = BankAccountVault::import_legacy(&state.db, admin.admin_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": account })))
+}
+
+/// Admin: resolve a bank account reference for fiat settlement.
+///
+/// `GET /api/admin/bank-accounts/:reference`
+async fn resolve_bank_account(
+    State(state): State<Arc<AppState>>,
+    Path(reference): Path<String>,
+    AuthenticatedAdmin(admin): AuthenticatedAdmin,
+) -> Result<Json<Value>, ApiError> {
+    let account_number = BankAccountVault::resolve(&state.db, admin.admin_id, &reference).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "data": { "reference": reference, "account_number": account_number }
+    })))
+}
+
+/// The caller's second factors and which actions require one.
+///
+/// `GET /api/v1/auth/2fa`
+async fn get_two_factor_status(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let status = TwoFactorService::status(&state.db, user.user_id).await?;
+    Ok(Json(json!({ "status": "success", "data": status })))
+}
+
+/// Start enrolling an authenticator app; returns the secret and its
+/// `otpauth://` URI.
+///
+/// `POST /api/v1/auth/2fa/totp`
+async fn enroll_totp(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<Value>, ApiError> {
+    let enrollment = TwoFactorService::enroll_totp(&state.db, user.user_id, &user.email).await?;
+    Ok(Json(json!({ "status": "success", "data": enrollment })))
+}
+
+/// Confirm the authenticator app with its first code; returns the recovery
+/// codes, which are not shown again.
+///
+/// `POST /api/v1/auth/2fa/totp/confirm`
+async fn confirm_totp(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<TwoFactorCodeRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let codes = TwoFactorService::confirm_totp(&state.db, user.user_id, &req.code).await?;
+    Ok(Json(json!({ "status": "success", "data": codes })))
+}
+
+/// `DELETE /api/v1/auth/2fa/totp`
+async fn disable_totp(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<TwoFactorCodeRequest>,
+) -> Result<Json<Value>, ApiError> {
+    TwoFactorService::disable_totp(&state.db, user.user_id, &req.code).await?;
+    Ok(Json(json!({ "status": "success" })))
+}
+
+/// Replace the caller's recovery codes.
+///
+/// `POST /api/v1/auth/2fa/recovery-codes`
+async fn regenerate_recovery_codes(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<TwoFactorCodeRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let codes =
+        TwoFactorService::regenerate_recovery_codes(&state.db, user.user_id, &req.code).await?;
+    Ok(Json(json!({ "status": "success", "data": codes })))
+}
+
+/// Choose whether claims and withdrawals require a second factor.
+///
+/// `PUT /api/v1/auth/2fa/policy`
+async fn update_two_factor_policy(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<UpdateTwoFactorPolicyRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let policy = TwoFactorService::update_policy(&state.db, user.user_id, &req).await?;
+    Ok(Json(json!({ "status": "success", "data": policy })))
+}
+
 async fn get_plan(
     State(state): State<Arc<AppState>>,
     Path(plan_id): Path<Uuid>,
@@ -877,7 +1278,8 @@ async fn get_plan(
     let plan = PlanService::get_plan_by_id(&state.db, plan_id, user.user_id).await?;
     match plan {
         Some(p) => {
-            let body = json!({ "status": "success", "data": p });
+            let contest_window = ContestWindowService::get_for_plan(&state.db, plan_id).await?;
+            let body = json!({ "status": "success", "data": p, "contest_window": contest_window });
             let etag = cache::compute_etag(&body);
             if cache::is_not_modified(&headers, &etag) {
                 return Ok(cache::not_modified_response_with_cc(
@@ -917,10 +1319,14 @@ async fn get_due_for_claim_plan(
     let plan = PlanService::get_due_for_claim_plan_by_id(&state.db, plan_id, user.user_id).await?;
 
     match plan {
-        Some(plan) => Ok(Json(json!({
-            "status": "success",
-            "data": plan
-        }))),
+        Some(plan) => {
+            let contest_window = ContestWindowService::get_for_plan(&state.db, plan_id).await?;
+            Ok(Json(json!({
+                "status": "success",
+                "data": plan,
+                "contest_window": contest_window
+            })))
+        }
         None => Err(ApiError::NotFound(format!(
             "Plan {plan_id} not found or not due for claim"
         ))),
@@ -1469,6 +1875,34 @@ async fn repay_lifecycle_loan(
     Ok(Json(json!({ "status": "success", "data": record })))
 }
 
+/// Record a loan's switch between variable and fixed rates, or a fixed-rate
+/// rebalance, at the rate the lending contract reported.
+///
+/// `POST /api/loans/lifecycle/:id/rate-mode`
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SwitchRateModeRequest {
+    rate_mode: RateMode,
+    interest_rate_bps: i32,
+}
+
+async fn switch_lifecycle_loan_rate_mode(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(req): Json<SwitchRateModeRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let record = LoanLifecycleService::switch_rate_mode(
+        &state.db,
+        id,
+        user.user_id,
+        req.rate_mode,
+        req.interest_rate_bps,
+    )
+    .await?;
+    Ok(Json(json!({ "status": "success", "data": record })))
+}
+
 /// Admin: forcefully liquidate a loan.
 ///
 /// `POST /api/admin/loans/lifecycle/:id/liquidate`
@@ -3001,13 +3435,16 @@ async fn get_collateral_value(
     Ok(Json(json!({ "status": "success", "data": info })))
 }
 
-/// Get maximum withdrawable collateral amount while maintaining health factor >= 150%.
+/// Get the maximum withdrawable amount of one collateral asset (`?asset=`,
+/// the primary collateral by default) while the rest of the basket still
+/// backs the debt.
 ///
 /// `GET /api/loans/lifecycle/:id/collateral/max-withdrawable`
 async fn get_max_withdrawable_collateral(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
     AuthenticatedUser(user): AuthenticatedUser,
+    Query(query): Query<CollateralAssetQuery>,
 ) -> Result<Json<Value>, ApiError> {
     let price_feed = Arc::new(crate::price_feed::DefaultPriceFeedService::new(
         state.db.clone(),
@@ -3018,6 +3455,7 @@ async fn get_max_withdrawable_collateral(
         price_feed,
         id,
         user.user_id,
+        query.asset.as_deref(),
     )
     .await?;
     Ok(Json(json!({ "status": "success", "data": info })))
@@ -2,6 +2,7 @@ use crate::api_error::ApiError;
 use crate::app::AppState;
 use crate::config::Config;
 use crate::notifications::{audit_action, entity_type, AuditLogService};
+use crate::two_factor::TwoFactorService;
 use axum::{extract::State, Json};
 use bcrypt::verify;
 use chrono::{DateTime, Duration, Utc};
@@ -139,29 +140,7 @@ pub async fn web3_login(
         .map_err(|_| ApiError::Unauthorized)?;
 
     // 4. Find or create user
-    let user_row: Option<UserRow> =
-        sqlx::query_as("SELECT id, email FROM users WHERE wallet_address = $1")
-            .bind(&payload.wallet_address)
-            .fetch_optional(&state.db)
-            .await?;
-
-    let (user_id, email) = match user_row {
-        Some(row) => (row.id, row.email),
-        None => {
-            let email = format!("{}@inheritx.auth", payload.wallet_address);
-            let id = Uuid::new_v4();
-            sqlx::query(
-                "INSERT INTO users (id, email, password_hash, wallet_address) VALUES ($1, $2, $3, $4)"
-            )
-            .bind(id)
-            .bind(&email)
-            .bind("web3-auth-none")
-            .bind(&payload.wallet_address)
-            .execute(&state.db)
-            .await?;
-            (id, email)
-        }
-    };
+    let (user_id, email) = find_or_create_wallet_user(&state.db, &payload.wallet_address).await?;
 
     // 5. Generate JWT
     let expiration = Utc::now()
@@ -198,6 +177,37 @@ pub async fn web3_login(
     Ok(Json(LoginResponse { token }))
 }
 
+/// The user owning `wallet_address`, created with a placeholder email and no
+/// password on its first wallet login.
+pub(crate) async fn find_or_create_wallet_user(
+    db: &PgPool,
+    wallet_address: &str,
+) -> Result<(Uuid, String), ApiError> {
+    let user_row: Option<UserRow> =
+        sqlx::query_as("SELECT id, email FROM users WHERE wallet_address = $1")
+            .bind(wallet_address)
+            .fetch_optional(db)
+            .await?;
+
+    match user_row {
+        Some(row) => Ok((row.id, row.email)),
+        None => {
+            let email = format!("{wallet_address}@inheritx.auth");
+            let id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO users (id, email, password_hash, wallet_address) VALUES ($1, $2, $3, $4)"
+            )
+            .bind(id)
+            .bind(&email)
+            .bind("web3-auth-none")
+            .bind(wallet_address)
+            .execute(db)
+            .await?;
+            Ok((id, email))
+        }
+    }
+}
+
 #[derive(Debug, FromRow)]
 struct Admin {
     id: uuid::Uuid,
@@ -411,7 +421,24 @@ pub async fn verify_2fa(
     }))
 }
 
+/// Verify a second factor: an authenticator-app code, a recovery code or the
+/// OTP sent by [`send_2fa`].
 pub async fn verify_2fa_internal(db: &PgPool, user_id: Uuid, otp: &str) -> Result<(), ApiError> {
+    if TwoFactorService::verify_second_factor(db, user_id, otp).await? {
+        return Ok(());
+    }
+
+    let result = verify_delivered_otp(db, user_id, otp).await;
+    if result.is_err() {
+        // A code that was handed on from the authenticator check and missed
+        // here too still counts towards the authenticator lockout.
+        TwoFactorService::record_missed_code(db, user_id, otp).await?;
+    }
+    result
+}
+
+/// Check `otp` against the OTP sent by [`send_2fa`].
+async fn verify_delivered_otp(db: &PgPool, user_id: Uuid, otp: &str) -> Result<(), ApiError> {
     let mut tx = db.begin().await?;
 
     // 1. Retrieve OTP record
@@ -0,0 +1,307 @@
+//! # Bank Account References
+//!
+//! Beneficiary bank accounts are not stored on-chain. The owner registers the
+//! account number here, where it is encrypted at rest, and receives an opaque
+//! reference to pass as the beneficiary's `bank_account_ref` when creating the
+//! plan. Admins resolve the reference back to the account number when
+//! settling a fiat payout.
+//!
+//! Plans created before contract v2 held the plaintext account on-chain;
+//! the contract's `migrate` clears it, leaving the reference empty. Importing
+//! such an account with [`BankAccountVault::import_legacy`] stores it under a
+//! fresh random reference for the admin to record with the contract's
+//! `assign_legacy_bank_account_ref`.
+
+use crate::api_error::ApiError;
+use crate::document_storage::{decrypt_bytes, encrypt_bytes, load_encryption_secret};
+use crate::notifications::{audit_action, entity_type, AuditLogService};
+use chrono::{DateTime, Utc};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Longest account number accepted (the IBAN maximum).
+pub const MAX_ACCOUNT_NUMBER_LEN: usize = 34;
+
+const REFERENCE_BYTES: usize = 32;
+
+/// A stored bank account, as shown to its owner. Never carries the full
+/// account number.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BankAccountRef {
+    pub id: Uuid,
+    /// Hex of the bytes to store on-chain as `bank_account_ref`.
+    pub reference: String,
+    pub user_id: Uuid,
+    pub masked_account: String,
+    pub is_legacy: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StoreBankAccountRequest {
+    pub account_number: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportLegacyBankAccountRequest {
+    pub user_id: Uuid,
+    pub account_number: String,
+    /// Plan and beneficiary whose migrated reference this account fills.
+    pub contract_plan_id: i64,
+    pub beneficiary_index: i32,
+}
+
+/// Trim and validate an account number.
+pub fn normalize_account_number(account_number: &str) -> Result<String, ApiError> {
+    let account = account_number.trim();
+    if account.is_empty() {
+        return Err(ApiError::BadRequest(
+            "account_number must not be empty".to_string(),
+        ));
+    }
+    if account.len() > MAX_ACCOUNT_NUMBER_LEN {
+        return Err(ApiError::BadRequest(format!(
+            "account_number exceeds {MAX_ACCOUNT_NUMBER_LEN} characters"
+        )));
+    }
+    if !account
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == ' ' || c == '-')
+    {
+        return Err(ApiError::BadRequest(
+            "account_number may only contain letters, digits, spaces and dashes".to_string(),
+        ));
+    }
+    Ok(account.to_string())
+}
+
+/// Hide all but the last four characters.
+pub fn mask_account_number(account_number: &str) -> String {
+    let chars: Vec<char> = account_number.chars().collect();
+    let visible = chars.len().min(4);
+    let hidden = chars.len() - visible;
+    let tail: String = chars[hidden..].iter().collect();
+    format!("{}{}", "*".repeat(hidden), tail)
+}
+
+fn new_reference() -> Result<String, ApiError> {
+    let mut bytes = [0u8; REFERENCE_BYTES];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Failed to generate reference")))?;
+    Ok(hex::encode(bytes))
+}
+
+fn encryption_secret() -> Result<Vec<u8>, ApiError> {
+    let secret = load_encryption_secret();
+    if secret.is_empty() {
+        return Err(ApiError::Internal(anyhow::anyhow!(
+            "DOCUMENT_ENCRYPTION_KEY is not configured"
+        )));
+    }
+    Ok(secret)
+}
+
+pub struct BankAccountVault;
+
+impl BankAccountVault {
+    /// Encrypt and store an account number for `user_id`, returning a fresh
+    /// reference for the beneficiary's `bank_account_ref`.
+    pub async fn store(
+        db: &PgPool,
+        user_id: Uuid,
+        account_number: &str,
+    ) -> Result<BankAccountRef, ApiError> {
+        let account = normalize_account_number(account_number)?;
+        let reference = new_reference()?;
+        let row = Self::insert(db, user_id, &reference, &account, None).await?;
+
+        AuditLogService::log(
+            db,
+            Some(user_id),
+            None,
+            audit_action::BANK_ACCOUNT_STORED,
+            Some(row.id),
+            Some(entity_type::BANK_ACCOUNT),
+            None,
+            None,
+            None,
+        )
+        .await?;
+        Ok(row)
+    }
+
+    /// Admin: store a pre-v2 plaintext account under a fresh reference for
+    /// the given plan beneficiary. Importing for the same beneficiary twice
+    /// returns the existing record.
+    pub async fn import_legacy(
+        db: &PgPool,
+        admin_id: Uuid,
+        req: &ImportLegacyBankAccountRequest,
+    ) -> Result<BankAccountRef, ApiError> {
+        let account = normalize_account_number(&req.account_number)?;
+        if req.contract_plan_id < 0 || req.beneficiary_index < 0 {
+            return Err(ApiError::BadRequest(
+                "contract_plan_id and beneficiary_index must not be negative".to_string(),
+            ));
+        }
+        let target = (req.contract_plan_id, req.beneficiary_index);
+        if let Some(existing) = Self::find_legacy(db, target).await? {
+            return Ok(existing);
+        }
+        let reference = new_reference()?;
+        let row = Self::insert(db, req.user_id, &reference, &account, Some(target)).await?;
+
+        AuditLogService::log(
+            db,
+            Some(req.user_id),
+            Some(admin_id),
+            audit_action::BANK_ACCOUNT_IMPORTED,
+            Some(row.id),
+            Some(entity_type::BANK_ACCOUNT),
+            None,
+            None,
+            None,
+        )
+        .await?;
+        Ok(row)
+    }
+
+    /// Admin: decrypt the account number behind a reference for fiat
+    /// settlement. Every resolution is audited.
+    pub async fn resolve(db: &PgPool, admin_id: Uuid, reference: &str) -> Result<String, ApiError> {
+        #[derive(sqlx::FromRow)]
+        struct Row {
+            id: Uuid,
+            user_id: Uuid,
+            encrypted_account: Vec<u8>,
+            encryption_nonce: Vec<u8>,
+        }
+
+        let reference = reference.trim().to_ascii_lowercase();
+        let row = sqlx::query_as::<_, Row>(
+            "SELECT id, user_id, encrypted_account, encryption_nonce \
+             FROM bank_account_refs WHERE reference = $1",
+        )
+        .bind(&reference)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Bank account {reference} not found")))?;
+
+        let plaintext = decrypt_bytes(
+            &row.encrypted_account,
+            &row.encryption_nonce,
+            &encryption_secret()?,
+        )?;
+        let account = String::from_utf8(plaintext)
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("Stored account is not UTF-8")))?;
+
+        AuditLogService::log(
+            db,
+            Some(row.user_id),
+            Some(admin_id),
+            audit_action::BANK_ACCOUNT_RESOLVED,
+            Some(row.id),
+            Some(entity_type::BANK_ACCOUNT),
+            None,
+            None,
+            None,
+        )
+        .await?;
+        Ok(account)
+    }
+
+    pub async fn list_for_user(
+        db: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<BankAccountRef>, ApiError> {
+        let rows = sqlx::query_as::<_, BankAccountRef>(
+            "SELECT id, reference, user_id, masked_account, is_legacy, created_at \
+             FROM bank_account_refs WHERE user_id = $1 ORDER BY created_at",
+        )
+        .bind(user_id)
+        .fetch_all(db)
+        .await?;
+        Ok(rows)
+    }
+
+    async fn find_legacy(
+        db: &PgPool,
+        (contract_plan_id, beneficiary_index): (i64, i32),
+    ) -> Result<Option<BankAccountRef>, ApiError> {
+        let row = sqlx::query_as::<_, BankAccountRef>(
+            "SELECT id, reference, user_id, masked_account, is_legacy, created_at \
+             FROM bank_account_refs \
+             WHERE is_legacy AND contract_plan_id = $1 AND beneficiary_index = $2",
+        )
+        .bind(contract_plan_id)
+        .bind(beneficiary_index)
+        .fetch_optional(db)
+        .await?;
+        Ok(row)
+    }
+
+    /// `legacy_target` is the `(contract_plan_id, beneficiary_index)` of an
+    /// imported pre-v2 account.
+    async fn insert(
+        db: &PgPool,
+        user_id: Uuid,
+        reference: &str,
+        account: &str,
+        legacy_target: Option<(i64, i32)>,
+    ) -> Result<BankAccountRef, ApiError> {
+        let (ciphertext, nonce) = encrypt_bytes(account.as_bytes(), &encryption_secret()?)?;
+        let row = sqlx::query_as::<_, BankAccountRef>(
+            r#"
+            INSERT INTO bank_account_refs
+                (reference, user_id, masked_account, encrypted_account, encryption_nonce,
+                 is_legacy, contract_plan_id, beneficiary_index)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, reference, user_id, masked_account, is_legacy, created_at
+            "#,
+        )
+        .bind(reference)
+        .bind(user_id)
+        .bind(mask_account_number(account))
+        .bind(&ciphertext)
+        .bind(&nonce)
+        .bind(legacy_target.is_some())
+        .bind(legacy_target.map(|(plan, _)| plan))
+        .bind(legacy_target.map(|(_, index)| index))
+        .fetch_one(db)
+        .await?;
+        Ok(row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_all_but_last_four() {
+        assert_eq!(mask_account_number("1234567890"), "******7890");
+        assert_eq!(mask_account_number("123"), "123");
+    }
+
+    #[test]
+    fn rejects_malformed_account_numbers() {
+        assert_eq!(
+            normalize_account_number("  GB29 NWBK 6016 ").unwrap(),
+            "GB29 NWBK 6016"
+        );
+        assert!(normalize_account_number("   ").is_err());
+        assert!(normalize_account_number("12345;DROP").is_err());
+        assert!(normalize_account_number(&"1".repeat(MAX_ACCOUNT_NUMBER_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn new_references_are_random_32_bytes() {
+        let a = new_reference().unwrap();
+        let b = new_reference().unwrap();
+        assert_eq!(a.len(), REFERENCE_BYTES * 2);
+        assert_ne!(a, b);
+    }
+}
@@ -0,0 +1,1268 @@
+//! # Chain Reconciliation
+//!
+//! Compares the state the contracts hold with the backend's copy of it. Each
+//! pass reads, straight from contract storage through
+//! [`SorobanRpcClient::get_contract_data`]:
+//!
+//! | Contract storage                 | Compared with                          |
+//! |----------------------------------|----------------------------------------|
+//! | inheritance `Plan(id)`           | `plans` with that `contract_plan_id`   |
+//! | `Plan(id).beneficiaries`         | `plan_beneficiaries` of the plan       |
+//! | lending `LoanById(id)`           | open `loan_lifecycle` rows             |
+//! | lending `PoolState(token)`       | `pools` with that `token_contract_id`  |
+//!
+//! Every difference is recorded in `chain_discrepancies` with a [`Severity`].
+//! Fields the backend only mirrors, where the chain is authoritative and
+//! nothing downstream is decided from the copy, are healed in place: a plan's
+//! `net_amount` and a pool's liquidity totals. Everything else, such as
+//! beneficiary allocations, plan status or a loan the chain has closed, is
+//! left for a person and raised through the audit log and error tracking the
+//! first time it is seen. Open discrepancies that a later pass no longer
+//! sees are marked resolved.
+
+use crate::api_error::ApiError;
+use crate::contract_indexer::{asset_amount, ContractKind, STELLAR_ASSET_DECIMALS};
+use crate::notifications::AuditLogService;
+use crate::stellar::{
+    contract_enum_key, map_address, map_bool, map_field, map_u32, map_u64, parse_sc_address,
+    SorobanRpcClient,
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use stellar_xdr::curr::{ContractDataDurability, ScVal};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Seconds between reconciliation passes.
+const RECONCILE_INTERVAL_SECS: u64 = 600;
+
+/// Smallest amount a contract can represent; smaller differences come from
+/// the extra decimal place Postgres stores and are not drift.
+fn one_stroop() -> Decimal {
+    Decimal::new(1, STELLAR_ASSET_DECIMALS)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Discrepancies
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityType {
+    Plan,
+    Loan,
+    Pool,
+}
+
+impl EntityType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntityType::Plan => "plan",
+            EntityType::Loan => "loan",
+            EntityType::Pool => "pool",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// A mirrored value lagged behind the chain and has been healed.
+    Info,
+    /// Needs a look, but the backend cannot move funds because of it.
+    Warning,
+    /// The backend could route or release funds differently from the chain.
+    Critical,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+
+    fn sentry_level(&self) -> sentry::Level {
+        match self {
+            Severity::Info => sentry::Level::Info,
+            Severity::Warning => sentry::Level::Warning,
+            Severity::Critical => sentry::Level::Error,
+        }
+    }
+}
+
+/// A correction applied to a backend row. Each carries the value it was
+/// computed against and is only applied if the row still holds it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Heal {
+    PlanNetAmount {
+        plan_id: Uuid,
+        from: Decimal,
+        to: Decimal,
+    },
+    PoolTotalLiquidity {
+        pool_id: Uuid,
+        from: Decimal,
+        to: Decimal,
+    },
+    PoolUtilizedLiquidity {
+        pool_id: Uuid,
+        from: Decimal,
+        to: Decimal,
+    },
+}
+
+/// One field on which the chain and Postgres disagree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Discrepancy {
+    pub entity_type: EntityType,
+    /// The backend row, when there is one.
+    pub entity_id: Option<Uuid>,
+    /// Contract plan id, contract loan id or pool asset code.
+    pub entity_ref: String,
+    pub field: String,
+    pub on_chain: Option<String>,
+    pub database: Option<String>,
+    pub severity: Severity,
+    pub heal: Option<Heal>,
+}
+
+/// Outcome of one reconciliation pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReconciliationReport {
+    /// Plans, loans and pools compared with the chain.
+    pub checked: usize,
+    pub healed: usize,
+    /// Unhealed discrepancies seen in this pass, new or still open.
+    pub open: usize,
+    /// Unhealed discrepancies seen for the first time, and alerted on.
+    pub alerted: usize,
+    pub resolved: u64,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Contract state
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OnChainBeneficiary {
+    pub wallet: Option<String>,
+    pub allocation_bp: u32,
+}
+
+/// The fields of an inheritance contract `InheritancePlan` that are
+/// reconciled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OnChainPlan {
+    pub asset_code: String,
+    pub total_amount: u64,
+    pub is_active: bool,
+    pub beneficiaries: Vec<OnChainBeneficiary>,
+}
+
+/// The fields of a lending contract `LoanRecord` that are reconciled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OnChainLoan {
+    pub borrower: String,
+    /// Outstanding debt, including interest accrued at the last touch.
+    pub principal: u64,
+}
+
+/// The fields of a lending contract `PoolState` that are reconciled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OnChainPool {
+    pub total_deposits: u64,
+    pub total_borrowed: u64,
+}
+
+fn map_symbol(val: &ScVal, field: &str) -> Option<String> {
+    match map_field(val, field)? {
+        ScVal::Symbol(sym) => String::from_utf8(sym.0.to_vec()).ok(),
+        _ => None,
+    }
+}
+
+pub fn parse_plan(val: &ScVal) -> Option<OnChainPlan> {
+    let ScVal::Vec(Some(items)) = map_field(val, "beneficiaries")? else {
+        return None;
+    };
+    let beneficiaries = items
+        .iter()
+        .map(|b| {
+            Some(OnChainBeneficiary {
+                wallet: map_address(b, "wallet"),
+                allocation_bp: map_u32(b, "allocation_bp")?,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(OnChainPlan {
+        asset_code: map_symbol(val, "asset_type")?,
+        total_amount: map_u64(val, "total_amount")?,
+        is_active: map_bool(val, "is_active")?,
+        beneficiaries,
+    })
+}
+
+pub fn parse_loan(val: &ScVal) -> Option<OnChainLoan> {
+    Some(OnChainLoan {
+        borrower: map_address(val, "borrower")?,
+        principal: map_u64(val, "principal")?,
+    })
+}
+
+pub fn parse_pool(val: &ScVal) -> Option<OnChainPool> {
+    Some(OnChainPool {
+        total_deposits: map_u64(val, "total_deposits")?,
+        total_borrowed: map_u64(val, "total_borrowed")?,
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Backend state
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PlanRow {
+    pub id: Uuid,
+    pub contract_plan_id: i64,
+    pub net_amount: Decimal,
+    pub status: String,
+    pub asset_code: Option<String>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct BeneficiaryRow {
+    pub plan_id: Uuid,
+    pub wallet_address: String,
+    pub allocation_percent: Decimal,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct LoanRow {
+    pub id: Uuid,
+    pub contract_loan_id: i64,
+    pub principal: Decimal,
+    pub amount_repaid: Decimal,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PoolRow {
+    pub id: Uuid,
+    pub asset_code: String,
+    pub token_contract_id: String,
+    pub total_liquidity: Decimal,
+    pub utilized_liquidity: Decimal,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Diffing
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn plan_discrepancy(
+    plan: &PlanRow,
+    field: impl Into<String>,
+    on_chain: Option<String>,
+    database: Option<String>,
+    severity: Severity,
+) -> Discrepancy {
+    Discrepancy {
+        entity_type: EntityType::Plan,
+        entity_id: Some(plan.id),
+        entity_ref: plan.contract_plan_id.to_string(),
+        field: field.into(),
+        on_chain,
+        database,
+        severity,
+        heal: None,
+    }
+}
+
+/// Differences between an open backend plan, its beneficiaries and the
+/// contract's copy of the plan, `None` when the contract has no such plan.
+///
+/// Beneficiaries are matched by wallet, so only those bound to a wallet on
+/// chain are compared, and only for plans whose beneficiaries the backend
+/// tracks at all.
+pub fn diff_plan(
+    plan: &PlanRow,
+    beneficiaries: &[BeneficiaryRow],
+    on_chain: Option<&OnChainPlan>,
+) -> Vec<Discrepancy> {
+    let Some(chain) = on_chain else {
+        return vec![plan_discrepancy(
+            plan,
+            "presence",
+            None,
+            Some(plan.status.clone()),
+            Severity::Critical,
+        )];
+    };
+    let mut found = Vec::new();
+
+    if let Some(asset_code) = &plan.asset_code {
+        if !asset_code.eq_ignore_ascii_case(&chain.asset_code) {
+            found.push(plan_discrepancy(
+                plan,
+                "asset_code",
+                Some(chain.asset_code.clone()),
+                Some(asset_code.clone()),
+                Severity::Warning,
+            ));
+        }
+    }
+
+    if !chain.is_active {
+        found.push(plan_discrepancy(
+            plan,
+            "status",
+            Some("inactive".to_string()),
+            Some(plan.status.clone()),
+            Severity::Warning,
+        ));
+    }
+
+    let chain_amount = asset_amount(chain.total_amount);
+    if (plan.net_amount - chain_amount).abs() >= one_stroop() {
+        let mut healed = plan_discrepancy(
+            plan,
+            "net_amount",
+            Some(chain_amount.to_string()),
+            Some(plan.net_amount.to_string()),
+            Severity::Info,
+        );
+        healed.heal = Some(Heal::PlanNetAmount {
+            plan_id: plan.id,
+            from: plan.net_amount,
+            to: chain_amount,
+        });
+        found.push(healed);
+    }
+
+    if !beneficiaries.is_empty() {
+        let chain_allocations: HashMap<&str, u32> = chain
+            .beneficiaries
+            .iter()
+            .filter_map(|b| Some((b.wallet.as_deref()?, b.allocation_bp)))
+            .collect();
+        for row in beneficiaries {
+            let allocation_bp = chain_allocations.get(row.wallet_address.as_str());
+            // allocation_percent carries two more decimals than basis points.
+            let database_bp = row.allocation_percent * Decimal::from(100);
+            if allocation_bp.map(|bp| Decimal::from(*bp)) != Some(database_bp) {
+                found.push(plan_discrepancy(
+                    plan,
+                    format!("beneficiaries.{}.allocation_bp", row.wallet_address),
+                    allocation_bp.map(|bp| bp.to_string()),
+                    Some(database_bp.normalize().to_string()),
+                    Severity::Critical,
+                ));
+            }
+        }
+        for (wallet, allocation_bp) in chain_allocations {
+            if !beneficiaries.iter().any(|row| row.wallet_address == wallet) {
+                found.push(plan_discrepancy(
+                    plan,
+                    format!("beneficiaries.{wallet}.allocation_bp"),
+                    Some(allocation_bp.to_string()),
+                    None,
+                    Severity::Critical,
+                ));
+            }
+        }
+    }
+
+    found
+}
+
+/// Differences between an open backend loan and the contract's loan record,
+/// `None` when the contract has closed the loan.
+///
+/// The contract folds accrued interest into `principal`, so the chain owing
+/// more than the backend's outstanding amount is expected. Owing less means
+/// repayments or liquidations the backend never recorded.
+pub fn diff_loan(loan: &LoanRow, on_chain: Option<&OnChainLoan>) -> Vec<Discrepancy> {
+    let outstanding = loan.principal - loan.amount_repaid;
+    let (field, chain_value, database_value) = match on_chain {
+        None => ("presence", None, loan.status.clone()),
+        Some(chain) => {
+            let chain_amount = asset_amount(chain.principal);
+            if outstanding - chain_amount < one_stroop() {
+                return Vec::new();
+            }
+            (
+                "principal",
+                Some(chain_amount.to_string()),
+                outstanding.to_string(),
+            )
+        }
+    };
+    vec![Discrepancy {
+        entity_type: EntityType::Loan,
+        entity_id: Some(loan.id),
+        entity_ref: loan.contract_loan_id.to_string(),
+        field: field.to_string(),
+        on_chain: chain_value,
+        database: Some(database_value),
+        severity: Severity::Critical,
+        heal: None,
+    }]
+}
+
+/// Differences between a backend pool and the contract's pool state, `None`
+/// when the contract has no pool for the token.
+pub fn diff_pool(pool: &PoolRow, on_chain: Option<&OnChainPool>) -> Vec<Discrepancy> {
+    let discrepancy = |field: &str, on_chain: Option<Decimal>, database: Decimal| Discrepancy {
+        entity_type: EntityType::Pool,
+        entity_id: Some(pool.id),
+        entity_ref: pool.asset_code.clone(),
+        field: field.to_string(),
+        on_chain: on_chain.map(|v| v.to_string()),
+        database: Some(database.to_string()),
+        severity: Severity::Info,
+        heal: None,
+    };
+    let Some(chain) = on_chain else {
+        return vec![Discrepancy {
+            severity: Severity::Warning,
+            ..discrepancy("presence", None, pool.total_liquidity)
+        }];
+    };
+
+    let mut found = Vec::new();
+    let deposits = asset_amount(chain.total_deposits);
+    if (pool.total_liquidity - deposits).abs() >= one_stroop() {
+        found.push(Discrepancy {
+            heal: Some(Heal::PoolTotalLiquidity {
+                pool_id: pool.id,
+                from: pool.total_liquidity,
+                to: deposits,
+            }),
+            ..discrepancy("total_liquidity", Some(deposits), pool.total_liquidity)
+        });
+    }
+    let borrowed = asset_amount(chain.total_borrowed);
+    if (pool.utilized_liquidity - borrowed).abs() >= one_stroop() {
+        found.push(Discrepancy {
+            heal: Some(Heal::PoolUtilizedLiquidity {
+                pool_id: pool.id,
+                from: pool.utilized_liquidity,
+                to: borrowed,
+            }),
+            ..discrepancy(
+                "utilized_liquidity",
+                Some(borrowed),
+                pool.utilized_liquidity,
+            )
+        });
+    }
+    found
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Reconciler
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Everything one pass found, recorded in a single transaction.
+#[derive(Default)]
+struct Pass {
+    /// Entities read from the chain; only their open discrepancies can be
+    /// resolved by this pass.
+    checked: Vec<(EntityType, String)>,
+    found: Vec<Discrepancy>,
+}
+
+impl Pass {
+    fn add(&mut self, entity_type: EntityType, entity_ref: String, found: Vec<Discrepancy>) {
+        self.checked.push((entity_type, entity_ref));
+        self.found.extend(found);
+    }
+}
+
+pub struct ChainReconciler {
+    db: PgPool,
+    rpc: SorobanRpcClient,
+    inheritance_contract: Option<String>,
+    lending_contract: Option<String>,
+}
+
+impl ChainReconciler {
+    pub fn new(db: PgPool, rpc: SorobanRpcClient) -> Self {
+        Self {
+            db,
+            rpc,
+            inheritance_contract: None,
+            lending_contract: None,
+        }
+    }
+
+    pub fn with_inheritance_contract(mut self, contract_id: impl Into<String>) -> Self {
+        self.inheritance_contract = Some(contract_id.into());
+        self
+    }
+
+    pub fn with_lending_contract(mut self, contract_id: impl Into<String>) -> Self {
+        self.lending_contract = Some(contract_id.into());
+        self
+    }
+
+    /// Reconcile the contracts configured through the same variables the
+    /// contract event indexer reads. `None` when neither the inheritance nor
+    /// the lending contract is configured.
+    pub fn from_env(db: PgPool, rpc: SorobanRpcClient) -> Option<Self> {
+        let contract = |kind: ContractKind| {
+            std::env::var(kind.env_var())
+                .ok()
+                .filter(|id| !id.is_empty())
+        };
+        let mut reconciler = Self::new(db, rpc);
+        reconciler.inheritance_contract = contract(ContractKind::Inheritance);
+        reconciler.lending_contract = contract(ContractKind::Lending);
+        (reconciler.inheritance_contract.is_some() || reconciler.lending_contract.is_some())
+            .then_some(reconciler)
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(RECONCILE_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.reconcile_once().await {
+                    error!("Chain reconciliation error: {}", e);
+                    crate::error_tracking::capture_message(
+                        &format!("ChainReconciler::reconcile_once failed: {e}"),
+                        sentry::Level::Error,
+                    );
+                }
+            }
+        });
+    }
+
+    /// Compare every open plan, open loan and linked pool with the chain,
+    /// heal what is safe to heal and record the rest.
+    pub async fn reconcile_once(&self) -> Result<ReconciliationReport, ApiError> {
+        let mut pass = Pass::default();
+        if let Some(contract_id) = &self.inheritance_contract {
+            self.reconcile_plans(contract_id, &mut pass).await?;
+        }
+        if let Some(contract_id) = &self.lending_contract {
+            self.reconcile_loans(contract_id, &mut pass).await?;
+            self.reconcile_pools(contract_id, &mut pass).await?;
+        }
+
+        let mut report = ReconciliationReport {
+            checked: pass.checked.len(),
+            ..Default::default()
+        };
+        let mut alerts = Vec::new();
+        let mut seen = Vec::new();
+        let mut tx = self.db.begin().await?;
+        for discrepancy in &pass.found {
+            if discrepancy.heal.is_some() {
+                if heal(&mut tx, discrepancy).await? {
+                    report.healed += 1;
+                }
+                continue;
+            }
+            let (id, inserted) = record_open(&mut tx, discrepancy).await?;
+            seen.push(id);
+            report.open += 1;
+            if inserted {
+                report.alerted += 1;
+                alerts.push(discrepancy);
+            }
+        }
+        report.resolved = resolve_cleared(&mut tx, &pass.checked, &seen).await?;
+        tx.commit().await?;
+
+        for discrepancy in alerts {
+            let message = format!(
+                "Chain discrepancy on {} {} {}: chain {}, database {}",
+                discrepancy.entity_type.as_str(),
+                discrepancy.entity_ref,
+                discrepancy.field,
+                discrepancy.on_chain.as_deref().unwrap_or("missing"),
+                discrepancy.database.as_deref().unwrap_or("missing"),
+            );
+            warn!("{}", message);
+            crate::error_tracking::capture_message(&message, discrepancy.severity.sentry_level());
+        }
+        info!(?report, "Chain reconciliation pass complete");
+        Ok(report)
+    }
+
+    async fn reconcile_plans(&self, contract_id: &str, pass: &mut Pass) -> Result<(), ApiError> {
+        let plans = sqlx::query_as::<_, PlanRow>(
+            r#"
+            SELECT id, contract_plan_id, net_amount, status, asset_code
+            FROM plans
+            WHERE contract_plan_id IS NOT NULL
+              AND status NOT IN ('claimed', 'deactivated')
+            "#,
+        )
+        .fetch_all(&self.db)
+        .await?;
+        let plan_ids: Vec<Uuid> = plans.iter().map(|p| p.id).collect();
+        let beneficiaries = sqlx::query_as::<_, BeneficiaryRow>(
+            r#"
+            SELECT plan_id, wallet_address, allocation_percent
+            FROM plan_beneficiaries
+            WHERE plan_id = ANY($1)
+            "#,
+        )
+        .bind(&plan_ids)
+        .fetch_all(&self.db)
+        .await?;
+
+        for plan in &plans {
+            let on_chain = match self.read_plan(contract_id, plan.contract_plan_id).await {
+                Ok(on_chain) => on_chain,
+                Err(e) => {
+                    warn!(
+                        "Failed to read plan {} from chain: {}",
+                        plan.contract_plan_id, e
+                    );
+                    continue;
+                }
+            };
+            let plan_beneficiaries: Vec<BeneficiaryRow> = beneficiaries
+                .iter()
+                .filter(|b| b.plan_id == plan.id)
+                .cloned()
+                .collect();
+            pass.add(
+                EntityType::Plan,
+                plan.contract_plan_id.to_string(),
+                diff_plan(plan, &plan_beneficiaries, on_chain.as_ref()),
+            );
+        }
+        Ok(())
+    }
+
+    async fn reconcile_loans(&self, contract_id: &str, pass: &mut Pass) -> Result<(), ApiError> {
+        let loans = sqlx::query_as::<_, LoanRow>(
+            r#"
+            SELECT id, contract_loan_id, principal, amount_repaid, status::TEXT AS status
+            FROM loan_lifecycle
+            WHERE contract_loan_id IS NOT NULL
+              AND status IN ('active', 'overdue')
+            "#,
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        for loan in &loans {
+            let on_chain = match self.read_loan(contract_id, loan.contract_loan_id).await {
+                Ok(on_chain) => on_chain,
+                Err(e) => {
+                    warn!(
+                        "Failed to read loan {} from chain: {}",
+                        loan.contract_loan_id, e
+                    );
+                    continue;
+                }
+            };
+            pass.add(
+                EntityType::Loan,
+                loan.contract_loan_id.to_string(),
+                diff_loan(loan, on_chain.as_ref()),
+            );
+        }
+        Ok(())
+    }
+
+    async fn reconcile_pools(&self, contract_id: &str, pass: &mut Pass) -> Result<(), ApiError> {
+        let pools = sqlx::query_as::<_, PoolRow>(
+            r#"
+            SELECT id, asset_code, token_contract_id, total_liquidity, utilized_liquidity
+            FROM pools
+            WHERE token_contract_id IS NOT NULL
+            "#,
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        for pool in &pools {
+            let on_chain = match self.read_pool(contract_id, &pool.token_contract_id).await {
+                Ok(on_chain) => on_chain,
+                Err(e) => {
+                    warn!("Failed to read {} pool from chain: {}", pool.asset_code, e);
+                    continue;
+                }
+            };
+            pass.add(
+                EntityType::Pool,
+                pool.asset_code.clone(),
+                diff_pool(pool, on_chain.as_ref()),
+            );
+        }
+        Ok(())
+    }
+
+    /// The inheritance contract's `Plan(plan_id)` entry.
+    pub async fn read_plan(
+        &self,
+        contract_id: &str,
+        plan_id: i64,
+    ) -> Result<Option<OnChainPlan>, ApiError> {
+        let key = contract_enum_key("Plan", vec![ScVal::U64(plan_id as u64)])?;
+        let val = self
+            .rpc
+            .get_contract_data(contract_id, &key, ContractDataDurability::Persistent)
+            .await?;
+        val.map(|v| parse_plan(&v).ok_or_else(|| malformed("plan", plan_id)))
+            .transpose()
+    }
+
+    /// The lending contract's `LoanById(loan_id)` entry.
+    pub async fn read_loan(
+        &self,
+        contract_id: &str,
+        loan_id: i64,
+    ) -> Result<Option<OnChainLoan>, ApiError> {
+        let key = contract_enum_key("LoanById", vec![ScVal::U64(loan_id as u64)])?;
+        let val = self
+            .rpc
+            .get_contract_data(contract_id, &key, ContractDataDurability::Persistent)
+            .await?;
+        val.map(|v| parse_loan(&v).ok_or_else(|| malformed("loan", loan_id)))
+            .transpose()
+    }
+
+    /// The lending contract's `PoolState(token)`, kept in instance storage.
+    pub async fn read_pool(
+        &self,
+        contract_id: &str,
+        token_contract_id: &str,
+    ) -> Result<Option<OnChainPool>, ApiError> {
+        let token = parse_sc_address(token_contract_id)?;
+        let key = contract_enum_key("PoolState", vec![ScVal::Address(token)])?;
+        let val = self.rpc.get_instance_data(contract_id, &key).await?;
+        val.map(|v| parse_pool(&v).ok_or_else(|| malformed("pool", token_contract_id)))
+            .transpose()
+    }
+}
+
+fn malformed(entity: &str, id: impl std::fmt::Display) -> ApiError {
+    ApiError::ExternalService(format!(
+        "Unexpected contract storage layout for {entity} {id}"
+    ))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Persistence
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn audit_metadata(discrepancy: &Discrepancy) -> serde_json::Value {
+    json!({
+        "entity_ref": discrepancy.entity_ref,
+        "field": discrepancy.field,
+        "severity": discrepancy.severity.as_str(),
+    })
+}
+
+/// Apply a discrepancy's heal and record it as resolved. Returns `false`
+/// when the row changed since it was read; the next pass looks again.
+async fn heal(
+    tx: &mut Transaction<'_, Postgres>,
+    discrepancy: &Discrepancy,
+) -> Result<bool, ApiError> {
+    let update = match discrepancy.heal.as_ref() {
+        Some(Heal::PlanNetAmount { plan_id, from, to }) => sqlx::query(
+            "UPDATE plans SET net_amount = $1, updated_at = NOW() WHERE id = $2 AND net_amount = $3",
+        )
+        .bind(to)
+        .bind(plan_id)
+        .bind(from),
+        Some(Heal::PoolTotalLiquidity { pool_id, from, to }) => sqlx::query(
+            "UPDATE pools SET total_liquidity = $1, updated_at = NOW() WHERE id = $2 AND total_liquidity = $3",
+        )
+        .bind(to)
+        .bind(pool_id)
+        .bind(from),
+        Some(Heal::PoolUtilizedLiquidity { pool_id, from, to }) => sqlx::query(
+            "UPDATE pools SET utilized_liquidity = $1, updated_at = NOW() WHERE id = $2 AND utilized_liquidity = $3",
+        )
+        .bind(to)
+        .bind(pool_id)
+        .bind(from),
+        None => return Ok(false),
+    };
+    if update.execute(&mut **tx).await?.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO chain_discrepancies (
+            entity_type, entity_id, entity_ref, field,
+            on_chain_value, database_value, severity, healed, resolved_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, TRUE, NOW())
+        "#,
+    )
+    .bind(discrepancy.entity_type.as_str())
+    .bind(discrepancy.entity_id)
+    .bind(&discrepancy.entity_ref)
+    .bind(&discrepancy.field)
+    .bind(&discrepancy.on_chain)
+    .bind(&discrepancy.database)
+    .bind(discrepancy.severity.as_str())
+    .execute(&mut **tx)
+    .await?;
+
+    AuditLogService::log(
+        &mut **tx,
+        None,
+        None,
+        "chain_discrepancy_healed",
+        discrepancy.entity_id,
+        Some(discrepancy.entity_type.as_str()),
+        discrepancy.database.as_deref(),
+        discrepancy.on_chain.as_deref(),
+        Some(audit_metadata(discrepancy)),
+    )
+    .await?;
+    Ok(true)
+}
+
+/// Record an unhealed discrepancy, or refresh it if it is already open.
+/// Returns its id and whether it is new; only new ones are audit-logged.
+async fn record_open(
+    tx: &mut Transaction<'_, Postgres>,
+    discrepancy: &Discrepancy,
+) -> Result<(Uuid, bool), ApiError> {
+    let (id, inserted) = sqlx::query_as::<_, (Uuid, bool)>(
+        r#"
+        INSERT INTO chain_discrepancies (
+            entity_type, entity_id, entity_ref, field,
+            on_chain_value, database_value, severity
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (entity_type, entity_ref, field) WHERE resolved_at IS NULL
+        DO UPDATE SET
+            on_chain_value = EXCLUDED.on_chain_value,
+            database_value = EXCLUDED.database_value,
+            severity = EXCLUDED.severity,
+            last_seen_at = NOW()
+        RETURNING id, (xmax = 0) AS inserted
+        "#,
+    )
+    .bind(discrepancy.entity_type.as_str())
+    .bind(discrepancy.entity_id)
+    .bind(&discrepancy.entity_ref)
+    .bind(&discrepancy.field)
+    .bind(&discrepancy.on_chain)
+    .bind(&discrepancy.database)
+    .bind(discrepancy.severity.as_str())
+    .fetch_one(&mut **tx)
+    .await?;
+
+    if inserted {
+        AuditLogService::log(
+            &mut **tx,
+            None,
+            None,
+            "chain_discrepancy_detected",
+            discrepancy.entity_id,
+            Some(discrepancy.entity_type.as_str()),
+            discrepancy.database.as_deref(),
+            discrepancy.on_chain.as_deref(),
+            Some(audit_metadata(discrepancy)),
+        )
+        .await?;
+    }
+    Ok((id, inserted))
+}
+
+/// Resolve the open discrepancies of the checked entities that this pass
+/// did not see again.
+async fn resolve_cleared(
+    tx: &mut Transaction<'_, Postgres>,
+    checked: &[(EntityType, String)],
+    seen: &[Uuid],
+) -> Result<u64, ApiError> {
+    let (types, refs): (Vec<&str>, Vec<&str>) = checked
+        .iter()
+        .map(|(entity_type, entity_ref)| (entity_type.as_str(), entity_ref.as_str()))
+        .unzip();
+    let result = sqlx::query(
+        r#"
+        UPDATE chain_discrepancies
+        SET resolved_at = NOW()
+        WHERE resolved_at IS NULL
+          AND id <> ALL($3)
+          AND (entity_type, entity_ref) IN (
+              SELECT * FROM UNNEST($1::TEXT[], $2::TEXT[])
+          )
+        "#,
+    )
+    .bind(&types)
+    .bind(&refs)
+    .bind(seen)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Unit tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stellar::{account_strkey, contract_data_key, StellarConfig};
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+    use httpmock::prelude::*;
+    use rust_decimal_macros::dec;
+    use stellar_strkey::Strkey;
+    use stellar_xdr::curr::{
+        ContractDataEntry, ContractExecutable, ExtensionPoint, Hash, LedgerEntryData, Limits,
+        ScContractInstance, ScMap, ScMapEntry, ScSymbol, ScVec, StringM, WriteXdr,
+    };
+
+    fn beneficiary() -> String {
+        account_strkey(&[9; 32])
+    }
+
+    fn contract_id(byte: u8) -> String {
+        format!("{}", Strkey::Contract(stellar_strkey::Contract([byte; 32])))
+    }
+
+    fn symbol(s: &str) -> ScVal {
+        ScVal::Symbol(ScSymbol(StringM::try_from(s).unwrap()))
+    }
+
+    fn map(fields: Vec<(&str, ScVal)>) -> ScVal {
+        let entries: Vec<ScMapEntry> = fields
+            .into_iter()
+            .map(|(key, val)| ScMapEntry {
+                key: symbol(key),
+                val,
+            })
+            .collect();
+        ScVal::Map(Some(ScMap(entries.try_into().unwrap())))
+    }
+
+    fn address(id: &str) -> ScVal {
+        ScVal::Address(parse_sc_address(id).unwrap())
+    }
+
+    fn plan_val(total_amount: u64, is_active: bool, allocation_bp: u32) -> ScVal {
+        let beneficiary = map(vec![
+            ("allocation_bp", ScVal::U32(allocation_bp)),
+            ("wallet", address(&beneficiary())),
+        ]);
+        map(vec![
+            ("asset_type", symbol("USDC")),
+            (
+                "beneficiaries",
+                ScVal::Vec(Some(ScVec(vec![beneficiary].try_into().unwrap()))),
+            ),
+            ("is_active", ScVal::Bool(is_active)),
+            ("total_amount", ScVal::U64(total_amount)),
+        ])
+    }
+
+    fn pool_val(total_deposits: u64, total_borrowed: u64) -> ScVal {
+        map(vec![
+            ("total_borrowed", ScVal::U64(total_borrowed)),
+            ("total_deposits", ScVal::U64(total_deposits)),
+        ])
+    }
+
+    fn plan_row(net_amount: Decimal) -> PlanRow {
+        PlanRow {
+            id: Uuid::new_v4(),
+            contract_plan_id: 7,
+            net_amount,
+            status: "pending".to_string(),
+            asset_code: Some("USDC".to_string()),
+        }
+    }
+
+    fn beneficiary_row(plan: &PlanRow, allocation_percent: Decimal) -> BeneficiaryRow {
+        BeneficiaryRow {
+            plan_id: plan.id,
+            wallet_address: beneficiary(),
+            allocation_percent,
+        }
+    }
+
+    /// A Soroban RPC endpoint serving fixed contract storage entries.
+    struct FakeSorobanRpc {
+        server: MockServer,
+    }
+
+    impl FakeSorobanRpc {
+        async fn start() -> Self {
+            Self {
+                server: MockServer::start_async().await,
+            }
+        }
+
+        fn client(&self) -> SorobanRpcClient {
+            SorobanRpcClient::new(StellarConfig {
+                network_passphrase: "Test SDF Network ; September 2015".to_string(),
+                horizon_url: self.server.base_url(),
+                rpc_url: self.server.base_url(),
+                request_timeout_secs: 5,
+                max_retries: 1,
+            })
+        }
+
+        /// Answer `getLedgerEntries` for `key` with `val`, or with no entry.
+        async fn entry(
+            &self,
+            contract: &str,
+            key: ScVal,
+            durability: ContractDataDurability,
+            val: Option<ScVal>,
+        ) {
+            let key_xdr = contract_data_key(contract, &key, durability).unwrap();
+            let entries = match val {
+                Some(val) => {
+                    let data = LedgerEntryData::ContractData(ContractDataEntry {
+                        ext: ExtensionPoint::V0,
+                        contract: parse_sc_address(contract).unwrap(),
+                        key,
+                        durability,
+                        val,
+                    });
+                    let xdr = BASE64.encode(data.to_xdr(Limits::none()).unwrap());
+                    json!([{ "key": key_xdr, "xdr": xdr, "lastModifiedLedgerSeq": 90 }])
+                }
+                None => json!([]),
+            };
+            self.server
+                .mock_async(|when, then| {
+                    when.method(POST)
+                        .json_body_partial(r#"{ "method": "getLedgerEntries" }"#)
+                        .body_contains(key_xdr.as_str());
+                    then.status(200).json_body(json!({
+                        "jsonrpc": "2.0",
+                        "id": 1,
+                        "result": { "entries": entries, "latestLedger": 100 }
+                    }));
+                })
+                .await;
+        }
+
+        /// Serve a contract instance whose instance storage holds `storage`.
+        async fn instance(&self, contract: &str, storage: Vec<(ScVal, ScVal)>) {
+            let storage: Vec<ScMapEntry> = storage
+                .into_iter()
+                .map(|(key, val)| ScMapEntry { key, val })
+                .collect();
+            let instance = ScVal::ContractInstance(ScContractInstance {
+                executable: ContractExecutable::Wasm(Hash([0; 32])),
+                storage: Some(ScMap(storage.try_into().unwrap())),
+            });
+            self.entry(
+                contract,
+                ScVal::LedgerKeyContractInstance,
+                ContractDataDurability::Persistent,
+                Some(instance),
+            )
+            .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_plans_loans_and_pools_from_contract_storage() {
+        let rpc = FakeSorobanRpc::start().await;
+        let inheritance = contract_id(1);
+        let lending = contract_id(2);
+        let token = contract_id(3);
+        let borrower = contract_id(4);
+
+        rpc.entry(
+            &inheritance,
+            contract_enum_key("Plan", vec![ScVal::U64(7)]).unwrap(),
+            ContractDataDurability::Persistent,
+            Some(plan_val(1_000_000_000, true, 10_000)),
+        )
+        .await;
+        rpc.entry(
+            &inheritance,
+            contract_enum_key("Plan", vec![ScVal::U64(8)]).unwrap(),
+            ContractDataDurability::Persistent,
+            None,
+        )
+        .await;
+        rpc.entry(
+            &lending,
+            contract_enum_key("LoanById", vec![ScVal::U64(3)]).unwrap(),
+            ContractDataDurability::Persistent,
+            Some(map(vec![
+                ("borrower", address(&borrower)),
+                ("principal", ScVal::U64(25_000_000)),
+            ])),
+        )
+        .await;
+        rpc.instance(
+            &lending,
+            vec![(
+                contract_enum_key("PoolState", vec![address(&token)]).unwrap(),
+                pool_val(500_000_000, 120_000_000),
+            )],
+        )
+        .await;
+
+        let reconciler = ChainReconciler::new(
+            PgPool::connect_lazy("postgres://localhost/unused").unwrap(),
+            rpc.client(),
+        );
+
+        let plan = reconciler
+            .read_plan(&inheritance, 7)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(plan.asset_code, "USDC");
+        assert_eq!(plan.total_amount, 1_000_000_000);
+        assert!(plan.is_active);
+        assert_eq!(
+            plan.beneficiaries,
+            vec![OnChainBeneficiary {
+                wallet: Some(beneficiary()),
+                allocation_bp: 10_000,
+            }]
+        );
+        assert_eq!(reconciler.read_plan(&inheritance, 8).await.unwrap(), None);
+
+        let loan = reconciler.read_loan(&lending, 3).await.unwrap().unwrap();
+        assert_eq!(loan.borrower, borrower);
+        assert_eq!(loan.principal, 25_000_000);
+
+        let pool = reconciler
+            .read_pool(&lending, &token)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            pool,
+            OnChainPool {
+                total_deposits: 500_000_000,
+                total_borrowed: 120_000_000,
+            }
+        );
+        assert_eq!(
+            reconciler
+                .read_pool(&lending, &contract_id(5))
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn plan_amount_drift_is_healed_and_allocation_drift_is_critical() {
+        let chain = parse_plan(&plan_val(1_000_000_000, true, 6_000)).unwrap();
+
+        let plan = plan_row(dec!(100.00000000));
+        let beneficiaries = [beneficiary_row(&plan, dec!(60.0000))];
+        assert!(diff_plan(&plan, &beneficiaries, Some(&chain)).is_empty());
+
+        let plan = plan_row(dec!(90));
+        let beneficiaries = [beneficiary_row(&plan, dec!(50.0000))];
+        let found = diff_plan(&plan, &beneficiaries, Some(&chain));
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].field, "net_amount");
+        assert_eq!(found[0].severity, Severity::Info);
+        assert_eq!(
+            found[0].heal,
+            Some(Heal::PlanNetAmount {
+                plan_id: plan.id,
+                from: dec!(90),
+                to: dec!(100),
+            })
+        );
+        assert_eq!(
+            found[1].field,
+            format!("beneficiaries.{}.allocation_bp", beneficiary())
+        );
+        assert_eq!(found[1].on_chain.as_deref(), Some("6000"));
+        assert_eq!(found[1].database.as_deref(), Some("5000"));
+        assert_eq!(found[1].severity, Severity::Critical);
+        assert!(found[1].heal.is_none());
+
+        let found = diff_plan(&plan, &[], None);
+        assert_eq!(found[0].field, "presence");
+        assert_eq!(found[0].severity, Severity::Critical);
+    }
+
+    #[test]
+    fn inactive_plan_on_chain_is_flagged_not_healed() {
+        let chain = parse_plan(&plan_val(1_000_000_000, false, 10_000)).unwrap();
+        let found = diff_plan(&plan_row(dec!(100)), &[], Some(&chain));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].field, "status");
+        assert_eq!(found[0].severity, Severity::Warning);
+        assert!(found[0].heal.is_none());
+    }
+
+    #[test]
+    fn loan_owing_less_on_chain_is_critical() {
+        let loan = LoanRow {
+            id: Uuid::new_v4(),
+            contract_loan_id: 3,
+            principal: dec!(100),
+            amount_repaid: dec!(20),
+            status: "active".to_string(),
+        };
+        let accrued = OnChainLoan {
+            borrower: contract_id(4),
+            principal: 810_000_000,
+        };
+        assert!(diff_loan(&loan, Some(&accrued)).is_empty());
+
+        let repaid = OnChainLoan {
+            principal: 500_000_000,
+            ..accrued
+        };
+        let found = diff_loan(&loan, Some(&repaid));
+        assert_eq!(found[0].field, "principal");
+        assert_eq!(found[0].on_chain.as_deref(), Some("50.0000000"));
+        assert_eq!(found[0].severity, Severity::Critical);
+
+        let found = diff_loan(&loan, None);
+        assert_eq!(found[0].field, "presence");
+        assert_eq!(found[0].database.as_deref(), Some("active"));
+    }
+
+    #[test]
+    fn pool_totals_are_healed() {
+        let pool = PoolRow {
+            id: Uuid::new_v4(),
+            asset_code: "USDC".to_string(),
+            token_contract_id: contract_id(3),
+            total_liquidity: dec!(50),
+            utilized_liquidity: dec!(12),
+        };
+        let chain = parse_pool(&pool_val(500_000_000, 120_000_000)).unwrap();
+        assert!(diff_pool(&pool, Some(&chain)).is_empty());
+
+        let chain = parse_pool(&pool_val(600_000_000, 120_000_000)).unwrap();
+        let found = diff_pool(&pool, Some(&chain));
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].heal,
+            Some(Heal::PoolTotalLiquidity {
+                pool_id: pool.id,
+                from: dec!(50),
+                to: dec!(60),
+            })
+        );
+
+        assert_eq!(diff_pool(&pool, None)[0].severity, Severity::Warning);
+    }
+}
@@ -0,0 +1,793 @@
+//! # Dead-Man's-Switch Check-Ins
+//!
+//! Owners of plans with an on-chain inactivity trigger must periodically
+//! prove they are alive. Each plan has a check-in cadence and a grace window;
+//! as the due date approaches and passes, reminders escalate:
+//!
+//! | Stage                | When                                   | Channel              |
+//! |----------------------|----------------------------------------|----------------------|
+//! | `InApp`              | `REMINDER_LEAD_SECONDS` before due     | in-app notification  |
+//! | `Email`              | at the due date                        | email to the owner   |
+//! | `Sms`                | after a third of the grace window      | SMS to the owner     |
+//! | `EmergencyContacts`  | after two thirds of the grace window   | email/SMS to contacts|
+//!
+//! Every reminder carries a signed one-click link. Checking in (via the link
+//! or an authenticated request) resets the cadence and records the activity
+//! on-chain through an [`ActivityRecorder`]. The contract's own
+//! `set_inactivity_grace_period` keeps `auto_trigger_check` from firing until
+//! the grace window has also elapsed.
+//!
+//! The backend never holds owner keys. [`SorobanActivityRecorder`] calls
+//! `record_activity_as_delegate` from the backend account, which each owner
+//! authorizes once with `set_activity_delegate`. Without a configured
+//! recorder, check-ins on deployed plans are refused rather than recorded
+//! off-chain only.
+
+use crate::alert_provider::AlertProvider;
+use crate::api_error::ApiError;
+use crate::contract_indexer::ContractKind;
+use crate::notifications::{
+    audit_action, entity_type, notif_type, AuditLogService, NotificationService,
+};
+use crate::stellar::{ContractCall, ContractInvoker, StellarClient};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// First (in-app) reminder is sent this long before the due date.
+pub const REMINDER_LEAD_SECONDS: i64 = 3 * 24 * 60 * 60;
+
+/// One-click check-in links stay valid for this long.
+pub const CHECK_IN_LINK_TTL_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+const MAX_CADENCE_DAYS: i64 = 365;
+const MAX_GRACE_DAYS: i64 = 90;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Escalation
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// How far a plan's reminders have escalated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EscalationStage {
+    None = 0,
+    InApp = 1,
+    Email = 2,
+    Sms = 3,
+    EmergencyContacts = 4,
+}
+
+impl EscalationStage {
+    pub fn level(self) -> i16 {
+        self as i16
+    }
+
+    pub fn from_level(level: i16) -> Self {
+        match level {
+            i16::MIN..=0 => EscalationStage::None,
+            1 => EscalationStage::InApp,
+            2 => EscalationStage::Email,
+            3 => EscalationStage::Sms,
+            _ => EscalationStage::EmergencyContacts,
+        }
+    }
+}
+
+/// Stage a plan should be at, given its due date and grace window.
+pub fn escalation_stage(
+    now: DateTime<Utc>,
+    due_at: DateTime<Utc>,
+    grace_period_seconds: i64,
+) -> EscalationStage {
+    if now < due_at - ChronoDuration::seconds(REMINDER_LEAD_SECONDS) {
+        return EscalationStage::None;
+    }
+    if now < due_at {
+        return EscalationStage::InApp;
+    }
+    let overdue = (now - due_at).num_seconds();
+    let grace = grace_period_seconds.max(0);
+    if overdue * 3 < grace {
+        EscalationStage::Email
+    } else if overdue * 3 < grace * 2 {
+        EscalationStage::Sms
+    } else {
+        EscalationStage::EmergencyContacts
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Signed one-click links
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Issues and verifies `plan_id.user_id.expires_at.signature` check-in tokens
+/// (HMAC-SHA256, URL-safe base64 signature).
+#[derive(Clone)]
+pub struct CheckInTokenSigner {
+    key: hmac::Key,
+}
+
+impl CheckInTokenSigner {
+    pub fn new(secret: &str) -> Self {
+        Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes()),
+        }
+    }
+
+    pub fn sign(&self, plan_id: Uuid, user_id: Uuid, expires_at: i64) -> String {
+        let payload = format!("{plan_id}.{user_id}.{expires_at}");
+        let tag = hmac::sign(&self.key, payload.as_bytes());
+        format!("{payload}.{}", URL_SAFE_NO_PAD.encode(tag.as_ref()))
+    }
+
+    /// Returns `(plan_id, user_id)` for a valid, unexpired token.
+    pub fn verify(&self, token: &str, now: DateTime<Utc>) -> Result<(Uuid, Uuid), ApiError> {
+        let (payload, signature) = token.rsplit_once('.').ok_or(ApiError::Unauthorized)?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| ApiError::Unauthorized)?;
+        hmac::verify(&self.key, payload.as_bytes(), &signature)
+            .map_err(|_| ApiError::Unauthorized)?;
+
+        let mut parts = payload.splitn(3, '.');
+        let plan_id = parts.next().and_then(|p| Uuid::parse_str(p).ok());
+        let user_id = parts.next().and_then(|p| Uuid::parse_str(p).ok());
+        let expires_at = parts.next().and_then(|p| p.parse::<i64>().ok());
+        match (plan_id, user_id, expires_at) {
+            (Some(plan_id), Some(user_id), Some(expires_at)) if now.timestamp() < expires_at => {
+                Ok((plan_id, user_id))
+            }
+            _ => Err(ApiError::Unauthorized),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// On-chain activity
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Resets a plan's on-chain inactivity timer.
+#[async_trait]
+pub trait ActivityRecorder: Send + Sync {
+    /// Returns the transaction hash of the submitted call.
+    async fn record_activity(
+        &self,
+        owner_wallet: &str,
+        contract_plan_id: u64,
+    ) -> anyhow::Result<String>;
+}
+
+/// Records activity as the plan's activity delegate: the owner must have
+/// called `set_activity_delegate` with [`ContractInvoker::source_account`].
+pub struct SorobanActivityRecorder {
+    invoker: ContractInvoker,
+    contract_id: String,
+}
+
+impl SorobanActivityRecorder {
+    pub fn new(invoker: ContractInvoker, contract_id: impl Into<String>) -> Self {
+        Self {
+            invoker,
+            contract_id: contract_id.into(),
+        }
+    }
+
+    /// Record against the contract in `INHERITANCE_CONTRACT_ID` from the
+    /// [`ContractInvoker::from_env`] account. `None` unless both are set.
+    pub fn from_env() -> Result<Option<Self>, ApiError> {
+        let Some(contract_id) = std::env::var(ContractKind::Inheritance.env_var())
+            .ok()
+            .filter(|id| !id.is_empty())
+        else {
+            return Ok(None);
+        };
+        let Some(invoker) = ContractInvoker::from_env(StellarClient::from_env())? else {
+            return Ok(None);
+        };
+        Ok(Some(Self::new(invoker, contract_id)))
+    }
+}
+
+#[async_trait]
+impl ActivityRecorder for SorobanActivityRecorder {
+    async fn record_activity(
+        &self,
+        owner_wallet: &str,
+        contract_plan_id: u64,
+    ) -> anyhow::Result<String> {
+        let call = ContractCall::record_activity_as_delegate(
+            &self.contract_id,
+            &self.invoker.source_account(),
+            contract_plan_id,
+        )?;
+        let sent = self.invoker.invoke(call).await?;
+        let result = self.invoker.confirm(&sent.hash).await?;
+        info!(
+            owner = owner_wallet,
+            plan_id = contract_plan_id,
+            ledger = ?result.ledger,
+            "Check-in recorded on-chain"
+        );
+        Ok(sent.hash)
+    }
+}
+
+/// In-process fake that logs and remembers every call.
+#[derive(Default)]
+pub struct MockActivityRecorder {
+    calls: Mutex<Vec<(String, u64)>>,
+}
+
+impl MockActivityRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn calls(&self) -> Vec<(String, u64)> {
+        self.calls.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl ActivityRecorder for MockActivityRecorder {
+    async fn record_activity(
+        &self,
+        owner_wallet: &str,
+        contract_plan_id: u64,
+    ) -> anyhow::Result<String> {
+        info!(
+            "--- [MOCK RECORD_ACTIVITY] owner {} plan {}",
+            owner_wallet, contract_plan_id
+        );
+        let mut calls = self.calls.lock().unwrap();
+        calls.push((owner_wallet.to_string(), contract_plan_id));
+        Ok(format!(
+            "mock-record-activity-{contract_plan_id}-{}",
+            calls.len()
+        ))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Models
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CheckInSettings {
+    pub plan_id: Uuid,
+    pub user_id: Uuid,
+    pub cadence_seconds: i64,
+    pub grace_period_seconds: i64,
+    pub sms_phone: Option<String>,
+    pub last_check_in_at: DateTime<Utc>,
+    pub next_due_at: DateTime<Utc>,
+    pub escalation_level: i16,
+    pub last_reminder_at: Option<DateTime<Utc>>,
+    pub last_tx_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCheckInSettingsRequest {
+    pub cadence_days: i64,
+    pub grace_period_days: i64,
+    pub sms_phone: Option<String>,
+}
+
+impl UpdateCheckInSettingsRequest {
+    pub fn validate(&self) -> Result<(), ApiError> {
+        if !(1..=MAX_CADENCE_DAYS).contains(&self.cadence_days) {
+            return Err(ApiError::Validation(format!(
+                "cadence_days must be between 1 and {MAX_CADENCE_DAYS}"
+            )));
+        }
+        if !(0..=MAX_GRACE_DAYS).contains(&self.grace_period_days) {
+            return Err(ApiError::Validation(format!(
+                "grace_period_days must be between 0 and {MAX_GRACE_DAYS}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckInStatus {
+    pub settings: CheckInSettings,
+    pub stage: EscalationStage,
+    /// When `auto_trigger_check` may fire if the owner does not check in.
+    pub trigger_eligible_at: DateTime<Utc>,
+    pub seconds_until_due: i64,
+}
+
+impl CheckInStatus {
+    fn from_settings(settings: CheckInSettings, now: DateTime<Utc>) -> Self {
+        let stage = escalation_stage(now, settings.next_due_at, settings.grace_period_seconds);
+        let trigger_eligible_at =
+            settings.next_due_at + ChronoDuration::seconds(settings.grace_period_seconds);
+        let seconds_until_due = (settings.next_due_at - now).num_seconds();
+        Self {
+            settings,
+            stage,
+            trigger_eligible_at,
+            seconds_until_due,
+        }
+    }
+}
+
+const SETTINGS_COLUMNS: &str = "plan_id, user_id, cadence_seconds, grace_period_seconds, \
+     sms_phone, last_check_in_at, next_due_at, escalation_level, last_reminder_at, last_tx_hash, \
+     created_at, updated_at";
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Service
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub struct CheckInService {
+    db: PgPool,
+    alerts: Arc<dyn AlertProvider>,
+    recorder: Option<Arc<dyn ActivityRecorder>>,
+    signer: CheckInTokenSigner,
+    check_in_url: String,
+}
+
+impl CheckInService {
+    pub fn new(
+        db: PgPool,
+        alerts: Arc<dyn AlertProvider>,
+        recorder: Option<Arc<dyn ActivityRecorder>>,
+        signing_secret: &str,
+    ) -> Self {
+        let check_in_url = std::env::var("CHECK_IN_BASE_URL")
+            .unwrap_or_else(|_| "http://localhost:3000/check-in".to_string());
+        Self {
+            db,
+            alerts,
+            recorder,
+            signer: CheckInTokenSigner::new(signing_secret),
+            check_in_url,
+        }
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(900));
+            loop {
+                interval.tick().await;
+                match self.process_reminders().await {
+                    Ok(sent) if sent > 0 => info!("Check-in reminders sent: {}", sent),
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("Check-in reminder error: {}", e);
+                        crate::error_tracking::capture_message(
+                            &format!("CheckInService::process_reminders failed: {e}"),
+                            sentry::Level::Error,
+                        );
+                    }
+                }
+            }
+        });
+    }
+
+    /// Create or replace the check-in schedule for a plan the user owns. The
+    /// cadence restarts from now.
+    pub async fn configure(
+        &self,
+        user_id: Uuid,
+        plan_id: Uuid,
+        req: &UpdateCheckInSettingsRequest,
+    ) -> Result<CheckInStatus, ApiError> {
+        req.validate()?;
+        self.require_owner(user_id, plan_id).await?;
+
+        let cadence_seconds = req.cadence_days * 24 * 60 * 60;
+        let grace_period_seconds = req.grace_period_days * 24 * 60 * 60;
+        let settings = sqlx::query_as::<_, CheckInSettings>(&format!(
+            r#"
+            INSERT INTO plan_check_ins (
+                plan_id, user_id, cadence_seconds, grace_period_seconds, sms_phone,
+                last_check_in_at, next_due_at
+            )
+            VALUES ($1, $2, $3, $4, $5, NOW(), NOW() + $3 * INTERVAL '1 second')
+            ON CONFLICT (plan_id) DO UPDATE
+            SET cadence_seconds = EXCLUDED.cadence_seconds,
+                grace_period_seconds = EXCLUDED.grace_period_seconds,
+                sms_phone = EXCLUDED.sms_phone,
+                last_check_in_at = EXCLUDED.last_check_in_at,
+                next_due_at = EXCLUDED.next_due_at,
+                escalation_level = 0,
+                last_reminder_at = NULL,
+                updated_at = NOW()
+            RETURNING {SETTINGS_COLUMNS}
+            "#
+        ))
+        .bind(plan_id)
+        .bind(user_id)
+        .bind(cadence_seconds)
+        .bind(grace_period_seconds)
+        .bind(&req.sms_phone)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(CheckInStatus::from_settings(settings, Utc::now()))
+    }
+
+    pub async fn get_status(
+        &self,
+        user_id: Uuid,
+        plan_id: Uuid,
+    ) -> Result<CheckInStatus, ApiError> {
+        let settings = sqlx::query_as::<_, CheckInSettings>(&format!(
+            "SELECT {SETTINGS_COLUMNS} FROM plan_check_ins WHERE plan_id = $1 AND user_id = $2"
+        ))
+        .bind(plan_id)
+        .bind(user_id)
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("No check-in schedule for plan {plan_id}")))?;
+
+        Ok(CheckInStatus::from_settings(settings, Utc::now()))
+    }
+
+    /// Check in from a signed reminder link.
+    pub async fn check_in_with_token(&self, token: &str) -> Result<CheckInStatus, ApiError> {
+        let (plan_id, user_id) = self.signer.verify(token, Utc::now())?;
+        self.check_in(user_id, plan_id, "link").await
+    }
+
+    /// Record that the owner is alive: record the activity on-chain if the
+    /// plan is deployed, then reset the schedule and escalation. Fails without
+    /// touching the schedule if the on-chain call cannot be made.
+    pub async fn check_in(
+        &self,
+        user_id: Uuid,
+        plan_id: Uuid,
+        source: &str,
+    ) -> Result<CheckInStatus, ApiError> {
+        #[derive(sqlx::FromRow)]
+        struct OnChainRow {
+            contract_plan_id: Option<i64>,
+            wallet_address: Option<String>,
+        }
+
+        let on_chain = sqlx::query_as::<_, OnChainRow>(
+            r#"
+            SELECT p.contract_plan_id, u.wallet_address
+            FROM plans p
+            JOIN users u ON u.id = p.user_id
+            WHERE p.id = $1 AND p.user_id = $2
+            "#,
+        )
+        .bind(plan_id)
+        .bind(user_id)
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Plan {plan_id} not found")))?;
+
+        let tx_hash = match (
+            on_chain.contract_plan_id,
+            on_chain.wallet_address.as_deref(),
+        ) {
+            (Some(contract_plan_id), Some(wallet)) => {
+                let recorder = self.recorder.as_ref().ok_or_else(|| {
+                    ApiError::ServiceUnavailable(
+                        "On-chain check-ins are not configured".to_string(),
+                    )
+                })?;
+                Some(
+                    recorder
+                        .record_activity(wallet, contract_plan_id as u64)
+                        .await
+                        .map_err(|e| ApiError::ExternalService(e.to_string()))?,
+                )
+            }
+            _ => {
+                warn!("Check-in for plan {} has no on-chain counterpart", plan_id);
+                None
+            }
+        };
+
+        let mut tx = self.db.begin().await?;
+        let settings = sqlx::query_as::<_, CheckInSettings>(&format!(
+            r#"
+            UPDATE plan_check_ins
+            SET last_check_in_at = NOW(),
+                next_due_at = NOW() + cadence_seconds * INTERVAL '1 second',
+                escalation_level = 0,
+                last_reminder_at = NULL,
+                last_tx_hash = COALESCE($3, last_tx_hash),
+                updated_at = NOW()
+            WHERE plan_id = $1 AND user_id = $2
+            RETURNING {SETTINGS_COLUMNS}
+            "#
+        ))
+        .bind(plan_id)
+        .bind(user_id)
+        .bind(&tx_hash)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("No check-in schedule for plan {plan_id}")))?;
+
+        AuditLogService::log(
+            &mut *tx,
+            Some(user_id),
+            None,
+            audit_action::OWNER_CHECKED_IN,
+            Some(plan_id),
+            Some(entity_type::PLAN),
+            None,
+            None,
+            Some(serde_json::json!({ "source": source, "tx_hash": tx_hash })),
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(CheckInStatus::from_settings(settings, Utc::now()))
+    }
+
+    /// Escalate every schedule whose stage has advanced since the last
+    /// reminder. Returns the number of reminders sent.
+    pub async fn process_reminders(&self) -> Result<usize, ApiError> {
+        let lead = ChronoDuration::seconds(REMINDER_LEAD_SECONDS);
+        let due_soon = sqlx::query_as::<_, CheckInSettings>(&format!(
+            r#"
+            SELECT {SETTINGS_COLUMNS}
+            FROM plan_check_ins
+            WHERE next_due_at <= $1 AND escalation_level < $2
+            ORDER BY next_due_at
+            "#
+        ))
+        .bind(Utc::now() + lead)
+        .bind(EscalationStage::EmergencyContacts.level())
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut sent = 0;
+        for settings in due_soon {
+            let now = Utc::now();
+            let stage = escalation_stage(now, settings.next_due_at, settings.grace_period_seconds);
+            if stage <= EscalationStage::from_level(settings.escalation_level) {
+                continue;
+            }
+            if let Err(e) = self.send_reminder(&settings, stage).await {
+                error!(
+                    "Check-in reminder for plan {} ({:?}) failed: {}",
+                    settings.plan_id, stage, e
+                );
+                continue;
+            }
+            sqlx::query(
+                r#"
+                UPDATE plan_check_ins
+                SET escalation_level = $2, last_reminder_at = NOW(), updated_at = NOW()
+                WHERE plan_id = $1
+                "#,
+            )
+            .bind(settings.plan_id)
+            .bind(stage.level())
+            .execute(&self.db)
+            .await?;
+            sent += 1;
+        }
+        Ok(sent)
+    }
+
+    async fn send_reminder(
+        &self,
+        settings: &CheckInSettings,
+        stage: EscalationStage,
+    ) -> Result<(), ApiError> {
+        let expires_at = Utc::now().timestamp() + CHECK_IN_LINK_TTL_SECONDS;
+        let link = format!(
+            "{}?token={}",
+            self.check_in_url,
+            self.signer
+                .sign(settings.plan_id, settings.user_id, expires_at)
+        );
+        let deadline =
+            settings.next_due_at + ChronoDuration::seconds(settings.grace_period_seconds);
+        let message = format!(
+            "Please confirm you are still active. Check in before {} to keep your inheritance plan from triggering: {}",
+            deadline.format("%Y-%m-%d %H:%M UTC"),
+            link
+        );
+
+        match stage {
+            EscalationStage::None => {}
+            EscalationStage::InApp => {
+                let mut conn = self.db.acquire().await?;
+                NotificationService::create(
+                    &mut conn,
+                    settings.user_id,
+                    notif_type::CHECK_IN_REMINDER,
+                    message,
+                )
+                .await?;
+            }
+            EscalationStage::Email => {
+                let email: String = sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
+                    .bind(settings.user_id)
+                    .fetch_one(&self.db)
+                    .await?;
+                self.alerts
+                    .send_email(&email, "Inheritance plan check-in due", &message)
+                    .await
+                    .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))?;
+            }
+            EscalationStage::Sms => match settings.sms_phone.as_deref() {
+                Some(phone) => self
+                    .alerts
+                    .send_sms(phone, &message)
+                    .await
+                    .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))?,
+                None => warn!(
+                    "Plan {} has no SMS number; skipping SMS check-in reminder",
+                    settings.plan_id
+                ),
+            },
+            EscalationStage::EmergencyContacts => {
+                let contacts: Vec<(Option<String>, Option<String>)> = sqlx::query_as(
+                    "SELECT email, phone FROM emergency_contacts WHERE user_id = $1",
+                )
+                .bind(settings.user_id)
+                .fetch_all(&self.db)
+                .await?;
+                let contact_message = format!(
+                    "Your contact has missed their InheritX check-in. Their inheritance plan may trigger after {} unless they check in.",
+                    deadline.format("%Y-%m-%d %H:%M UTC")
+                );
+                for (email, phone) in contacts {
+                    if let Some(email) = email {
+                        self.alerts
+                            .send_email(
+                                &email,
+                                "Missed check-in for your contact",
+                                &contact_message,
+                            )
+                            .await
+                            .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))?;
+                    }
+                    if let Some(phone) = phone {
+                        self.alerts
+                            .send_sms(&phone, &contact_message)
+                            .await
+                            .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))?;
+                    }
+                }
+            }
+        }
+
+        AuditLogService::log(
+            &self.db,
+            Some(settings.user_id),
+            None,
+            audit_action::CHECK_IN_REMINDER_SENT,
+            Some(settings.plan_id),
+            Some(entity_type::PLAN),
+            None,
+            None,
+            Some(serde_json::json!({ "stage": stage })),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn require_owner(&self, user_id: Uuid, plan_id: Uuid) -> Result<(), ApiError> {
+        let owned: Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM plans WHERE id = $1 AND user_id = $2")
+                .bind(plan_id)
+                .bind(user_id)
+                .fetch_optional(&self.db)
+                .await?;
+        owned
+            .map(|_| ())
+            .ok_or_else(|| ApiError::NotFound(format!("Plan {plan_id} not found")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + seconds, 0).unwrap()
+    }
+
+    #[test]
+    fn reminders_escalate_through_the_grace_window() {
+        let due = at(10 * DAY);
+        let grace = 9 * DAY;
+        assert_eq!(escalation_stage(at(0), due, grace), EscalationStage::None);
+        assert_eq!(
+            escalation_stage(at(8 * DAY), due, grace),
+            EscalationStage::InApp
+        );
+        assert_eq!(
+            escalation_stage(at(10 * DAY), due, grace),
+            EscalationStage::Email
+        );
+        assert_eq!(
+            escalation_stage(at(13 * DAY), due, grace),
+            EscalationStage::Sms
+        );
+        assert_eq!(
+            escalation_stage(at(16 * DAY), due, grace),
+            EscalationStage::EmergencyContacts
+        );
+    }
+
+    #[test]
+    fn zero_grace_goes_straight_to_emergency_contacts() {
+        let due = at(0);
+        assert_eq!(
+            escalation_stage(at(1), due, 0),
+            EscalationStage::EmergencyContacts
+        );
+    }
+
+    #[test]
+    fn stage_levels_round_trip() {
+        for stage in [
+            EscalationStage::None,
+            EscalationStage::InApp,
+            EscalationStage::Email,
+            EscalationStage::Sms,
+            EscalationStage::EmergencyContacts,
+        ] {
+            assert_eq!(EscalationStage::from_level(stage.level()), stage);
+        }
+    }
+
+    #[test]
+    fn signed_token_round_trips() {
+        let signer = CheckInTokenSigner::new("secret");
+        let (plan_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let token = signer.sign(plan_id, user_id, at(DAY).timestamp());
+        assert_eq!(signer.verify(&token, at(0)).unwrap(), (plan_id, user_id));
+    }
+
+    #[test]
+    fn expired_or_tampered_tokens_are_rejected() {
+        let signer = CheckInTokenSigner::new("secret");
+        let (plan_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let token = signer.sign(plan_id, user_id, at(DAY).timestamp());
+
+        assert!(signer.verify(&token, at(2 * DAY)).is_err());
+        assert!(CheckInTokenSigner::new("other")
+            .verify(&token, at(0))
+            .is_err());
+        let forged = token.replacen(&plan_id.to_string(), &Uuid::new_v4().to_string(), 1);
+        assert!(signer.verify(&forged, at(0)).is_err());
+    }
+
+    #[test]
+    fn settings_request_bounds() {
+        let req = |cadence_days, grace_period_days| UpdateCheckInSettingsRequest {
+            cadence_days,
+            grace_period_days,
+            sms_phone: None,
+        };
+        assert!(req(30, 7).validate().is_ok());
+        assert!(req(0, 7).validate().is_err());
+        assert!(req(30, -1).validate().is_err());
+        assert!(req(30, MAX_GRACE_DAYS + 1).validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn mock_recorder_tracks_calls() {
+        let recorder = MockActivityRecorder::new();
+        let hash = recorder.record_activity("GOWNER", 4).await.unwrap();
+        assert_eq!(hash, "mock-record-activity-4-1");
+        assert_eq!(recorder.calls(), vec![("GOWNER".to_string(), 4)]);
+    }
+}
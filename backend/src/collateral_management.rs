@@ -6,18 +6,28 @@
 //! - Swap collateral types
 //! - Calculate collateral values and requirements
 //! - Determine safe withdrawal amounts
+//!
+//! A loan's `collateral_asset` is its primary collateral. Other assets posted
+//! against the loan form its collateral basket (`loan_collateral_positions`).
+//! Each asset counts toward the borrowing limit at its own collateral ratio
+//! (`price_feeds.collateral_ratio_bps`), and the health factor compares the
+//! value of the whole basket with the outstanding debt.
 
 use crate::api_error::ApiError;
 use crate::events::{DepositMetadata, EventService};
 use crate::loan_lifecycle::{LoanLifecycleRecord, LoanLifecycleService};
 use crate::notifications::{audit_action, entity_type, AuditLogService};
 use crate::price_feed::PriceFeedService;
+use crate::two_factor::{SensitiveAction, TwoFactorService};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Collateral ratio of assets without their own, in basis points (150%).
+pub const DEFAULT_COLLATERAL_RATIO_BPS: i32 = 15000;
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Request / Response Types
 // ─────────────────────────────────────────────────────────────────────────────
@@ -28,6 +38,9 @@ use uuid::Uuid;
 pub struct AddCollateralRequest {
     pub loan_id: Uuid,
     pub user_id: Uuid,
+    /// Asset to post; the loan's primary collateral asset when omitted.
+    #[serde(default)]
+    pub asset: Option<String>,
     pub amount: Decimal,
     pub transaction_hash: Option<String>,
 }
@@ -38,8 +51,14 @@ pub struct AddCollateralRequest {
 pub struct RemoveCollateralRequest {
     pub loan_id: Uuid,
     pub user_id: Uuid,
+    /// Asset to withdraw; the loan's primary collateral asset when omitted.
+    #[serde(default)]
+    pub asset: Option<String>,
     pub amount: Decimal,
     pub transaction_hash: Option<String>,
+    /// Second factor, when the user's policy requires one for withdrawals.
+    #[serde(default)]
+    pub two_fa_code: Option<String>,
 }
 
 /// Request to swap collateral type
@@ -53,7 +72,33 @@ pub struct SwapCollateralRequest {
     pub transaction_hash: Option<String>,
 }
 
-/// Collateral information response
+/// Query selecting one collateral asset of a loan
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CollateralAssetQuery {
+    pub asset: Option<String>,
+}
+
+/// One asset posted as collateral against a loan
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct CollateralPosition {
+    pub asset_code: String,
+    pub amount: Decimal,
+}
+
+/// A collateral position valued at the current price
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollateralHolding {
+    pub asset_code: String,
+    pub amount: Decimal,
+    pub price: Decimal,
+    pub collateral_ratio_bps: i32,
+    pub value_usd: Decimal,
+}
+
+/// Collateral information response. The `collateral_*` fields describe the
+/// primary collateral; `positions` lists every asset, primary first.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CollateralInfo {
@@ -62,6 +107,9 @@ pub struct CollateralInfo {
     pub collateral_amount: Decimal,
     pub collateral_value_usd: Decimal,
     pub current_price: Decimal,
+    pub positions: Vec<CollateralHolding>,
+    pub total_value_usd: Decimal,
+    pub health_factor: Decimal,
 }
 
 /// Collateral requirements response
@@ -84,12 +132,81 @@ pub struct CollateralRequirements {
 #[serde(rename_all = "camelCase")]
 pub struct SafeWithdrawalInfo {
     pub loan_id: Uuid,
+    pub collateral_asset: String,
     pub current_collateral_amount: Decimal,
     pub max_withdrawable_amount: Decimal,
     pub health_factor_after_withdrawal: Decimal,
     pub min_health_factor: Decimal,
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Basket Valuation
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A collateral ratio in basis points as a multiplier (15000 → 1.5).
+pub fn ratio_multiplier(ratio_bps: i32) -> Decimal {
+    Decimal::from(ratio_bps) / Decimal::from(10_000)
+}
+
+/// Debt value (USD) the holdings can back: each holding's value divided by
+/// its collateral ratio.
+pub fn borrowing_capacity(holdings: &[CollateralHolding]) -> Decimal {
+    holdings
+        .iter()
+        .map(|h| h.value_usd / ratio_multiplier(h.collateral_ratio_bps))
+        .sum()
+}
+
+/// Total value of the holdings over the debt value; `Decimal::MAX` when there
+/// is no debt.
+pub fn aggregate_health_factor(holdings: &[CollateralHolding], debt_value_usd: Decimal) -> Decimal {
+    if debt_value_usd <= Decimal::ZERO {
+        return Decimal::MAX;
+    }
+    holdings.iter().map(|h| h.value_usd).sum::<Decimal>() / debt_value_usd
+}
+
+/// Amount of `asset` that must stay posted for the holdings to keep backing
+/// `debt_value_usd`, after counting every other holding at its own ratio.
+pub fn required_amount(
+    holdings: &[CollateralHolding],
+    asset: &str,
+    price: Decimal,
+    collateral_ratio_bps: i32,
+    debt_value_usd: Decimal,
+) -> Decimal {
+    let others: Vec<CollateralHolding> = holdings
+        .iter()
+        .filter(|h| h.asset_code != asset)
+        .cloned()
+        .collect();
+    let shortfall = debt_value_usd - borrowing_capacity(&others);
+    if shortfall <= Decimal::ZERO || price <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+    shortfall * ratio_multiplier(collateral_ratio_bps) / price
+}
+
+/// Largest amount of `asset` that can be withdrawn while the remaining
+/// holdings still back `debt_value_usd`.
+pub fn max_withdrawable(
+    holdings: &[CollateralHolding],
+    asset: &str,
+    debt_value_usd: Decimal,
+) -> Decimal {
+    let Some(holding) = holdings.iter().find(|h| h.asset_code == asset) else {
+        return Decimal::ZERO;
+    };
+    let required = required_amount(
+        holdings,
+        asset,
+        holding.price,
+        holding.collateral_ratio_bps,
+        debt_value_usd,
+    );
+    (holding.amount - required).max(Decimal::ZERO)
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Service
 // ─────────────────────────────────────────────────────────────────────────────
@@ -106,9 +223,11 @@ impl CollateralManagementService {
     /// - Loan must be in active status
     /// - Amount must be positive
     /// - Plan must not be paused
+    /// - An asset other than the primary collateral must have an active price feed
     ///
     /// # Effects
-    /// - Increases collateral_amount in loan_lifecycle table
+    /// - Increases collateral_amount in loan_lifecycle table, or the loan's
+    ///   basket position when another asset is posted
     /// - Emits a deposit event
     /// - Logs audit trail
     /// - Recalculates health factor
@@ -128,7 +247,7 @@ impl CollateralManagementService {
         let loan = sqlx::query_as::<_, crate::loan_lifecycle::LoanLifecycleRow>(
             r#"
             SELECT ll.id, ll.user_id, ll.plan_id, ll.borrow_asset, ll.collateral_asset,
-                   ll.principal, ll.interest_rate_bps, ll.collateral_amount, ll.amount_repaid,
+                   ll.principal, ll.interest_rate_bps, ll.rate_mode, ll.collateral_amount, ll.amount_repaid,
                    ll.status, ll.due_date, ll.transaction_hash,
                    ll.created_at, ll.updated_at, ll.repaid_at, ll.liquidated_at
             FROM loan_lifecycle ll
@@ -158,35 +277,65 @@ impl CollateralManagementService {
             )));
         }
 
-        // Update collateral amount
-        let updated = sqlx::query_as::<_, crate::loan_lifecycle::LoanLifecycleRow>(
-            r#"
-            UPDATE loan_lifecycle
-            SET collateral_amount = collateral_amount + $1
-            WHERE id = $2
-            RETURNING id, user_id, plan_id, borrow_asset, collateral_asset,
-                      principal, interest_rate_bps, collateral_amount, amount_repaid,
-                      status, due_date, transaction_hash,
-                      created_at, updated_at, repaid_at, liquidated_at
-            "#,
-        )
-        .bind(req.amount)
-        .bind(req.loan_id)
-        .fetch_one(&mut *tx)
-        .await?;
-
-        let updated_record: LoanLifecycleRecord = updated.into();
+        let asset = req
+            .asset
+            .clone()
+            .unwrap_or_else(|| loan_record.collateral_asset.clone());
+
+        let (updated_record, total_deposited) = if asset == loan_record.collateral_asset {
+            // Update collateral amount
+            let updated = sqlx::query_as::<_, crate::loan_lifecycle::LoanLifecycleRow>(
+                r#"
+                UPDATE loan_lifecycle
+                SET collateral_amount = collateral_amount + $1
+                WHERE id = $2
+                RETURNING id, user_id, plan_id, borrow_asset, collateral_asset,
+                          principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
+                          status, due_date, transaction_hash,
+                          created_at, updated_at, repaid_at, liquidated_at
+                "#,
+            )
+            .bind(req.amount)
+            .bind(req.loan_id)
+            .fetch_one(&mut *tx)
+            .await?;
+            let updated: LoanLifecycleRecord = updated.into();
+            let total = updated.collateral_amount;
+            (updated, total)
+        } else {
+            if !Self::is_collateral_asset(&mut tx, &asset).await? {
+                return Err(ApiError::BadRequest(format!(
+                    "collateral asset {asset} is not supported"
+                )));
+            }
+            let total: Decimal = sqlx::query_scalar(
+                r#"
+                INSERT INTO loan_collateral_positions (loan_id, asset_code, amount)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (loan_id, asset_code)
+                DO UPDATE SET amount = loan_collateral_positions.amount + EXCLUDED.amount,
+                              updated_at = NOW()
+                RETURNING amount
+                "#,
+            )
+            .bind(req.loan_id)
+            .bind(&asset)
+            .bind(req.amount)
+            .fetch_one(&mut *tx)
+            .await?;
+            (loan_record.clone(), total)
+        };
 
         // Emit deposit event
         EventService::emit_deposit(
             &mut tx,
             req.user_id,
             loan_record.plan_id,
-            &loan_record.collateral_asset,
+            &asset,
             req.amount,
             DepositMetadata {
                 collateral_ratio: None,
-                total_deposited: updated_record.collateral_amount,
+                total_deposited,
             },
             req.transaction_hash.clone(),
             None,
@@ -203,7 +352,7 @@ impl CollateralManagementService {
             Some(entity_type::LOAN),
             None,
             None,
-            None,
+            Some(serde_json::json!({ "asset": asset, "amount": req.amount })),
         )
         .await?;
 
@@ -218,12 +367,14 @@ impl CollateralManagementService {
     /// # Validation
     /// - Loan must exist and belong to the user
     /// - Loan must be in active status
-    /// - Amount must be positive and not exceed current collateral
-    /// - Health factor must remain >= 150% (1.5) after removal
+    /// - Amount must be positive and not exceed the posted amount of the asset
+    /// - The remaining basket must still back the debt, each asset at its own
+    ///   collateral ratio
     /// - Plan must not be paused
     ///
     /// # Effects
-    /// - Decreases collateral_amount in loan_lifecycle table
+    /// - Decreases collateral_amount in loan_lifecycle table, or the loan's
+    ///   basket position for another asset
     /// - Emits a deposit event (negative amount)
     /// - Logs audit trail
     /// - Recalculates health factor
@@ -238,13 +389,21 @@ impl CollateralManagementService {
             ));
         }
 
+        TwoFactorService::enforce(
+            pool,
+            req.user_id,
+            SensitiveAction::Withdrawal,
+            req.two_fa_code.as_deref(),
+        )
+        .await?;
+
         let mut tx = pool.begin().await?;
 
         // Fetch the loan with row lock
         let loan = sqlx::query_as::<_, crate::loan_lifecycle::LoanLifecycleRow>(
             r#"
             SELECT ll.id, ll.user_id, ll.plan_id, ll.borrow_asset, ll.collateral_asset,
-                   ll.principal, ll.interest_rate_bps, ll.collateral_amount, ll.amount_repaid,
+                   ll.principal, ll.interest_rate_bps, ll.rate_mode, ll.collateral_amount, ll.amount_repaid,
                    ll.status, ll.due_date, ll.transaction_hash,
                    ll.created_at, ll.updated_at, ll.repaid_at, ll.liquidated_at
             FROM loan_lifecycle ll
@@ -274,67 +433,85 @@ impl CollateralManagementService {
             )));
         }
 
-        // Verify amount doesn't exceed current collateral
-        if req.amount > loan_record.collateral_amount {
+        let asset = req
+            .asset
+            .clone()
+            .unwrap_or_else(|| loan_record.collateral_asset.clone());
+
+        // Verify amount doesn't exceed the posted collateral
+        let mut positions = Self::positions(&mut tx, &loan_record).await?;
+        let Some(position) = positions.iter_mut().find(|p| p.asset_code == asset) else {
+            return Err(ApiError::BadRequest(format!(
+                "no {asset} collateral is posted against this loan"
+            )));
+        };
+        if req.amount > position.amount {
             return Err(ApiError::BadRequest(format!(
                 "cannot remove {} of collateral; only {} available",
-                req.amount, loan_record.collateral_amount
+                req.amount, position.amount
             )));
         }
+        position.amount -= req.amount;
+        let total_deposited = position.amount;
 
-        // Calculate health factor after removal
-        let collateral_after = loan_record.collateral_amount - req.amount;
-        let health_factor_after = Self::calculate_health_factor(
-            &price_feed,
-            &loan_record.borrow_asset,
-            &loan_record.collateral_asset,
-            loan_record.principal - loan_record.amount_repaid,
-            collateral_after,
-        )
-        .await?;
-
-        // Verify health factor remains >= 150% (1.5)
-        let min_health_factor = Decimal::from_str_exact("1.5").map_err(|e| {
-            ApiError::Internal(anyhow::anyhow!("Failed to parse min health factor: {e}"))
-        })?;
-
-        if health_factor_after < min_health_factor {
+        // Verify the remaining basket still backs the debt
+        let holdings = Self::price_positions(&mut tx, &price_feed, positions).await?;
+        let debt_value_usd = Self::debt_value(&price_feed, &loan_record).await?;
+        if borrowing_capacity(&holdings) < debt_value_usd {
             return Err(ApiError::BadRequest(format!(
-                "cannot remove collateral; health factor would drop to {:.2}%, minimum is {:.2}%",
-                health_factor_after * Decimal::from(100),
-                min_health_factor * Decimal::from(100)
+                "cannot remove collateral; health factor would drop to {:.2}%, below the collateral requirement",
+                aggregate_health_factor(&holdings, debt_value_usd) * Decimal::from(100)
             )));
         }
 
-        // Update collateral amount
-        let updated = sqlx::query_as::<_, crate::loan_lifecycle::LoanLifecycleRow>(
-            r#"
-            UPDATE loan_lifecycle
-            SET collateral_amount = collateral_amount - $1
-            WHERE id = $2
-            RETURNING id, user_id, plan_id, borrow_asset, collateral_asset,
-                      principal, interest_rate_bps, collateral_amount, amount_repaid,
-                      status, due_date, transaction_hash,
-                      created_at, updated_at, repaid_at, liquidated_at
-            "#,
-        )
-        .bind(req.amount)
-        .bind(req.loan_id)
-        .fetch_one(&mut *tx)
-        .await?;
-
-        let updated_record: LoanLifecycleRecord = updated.into();
+        let updated_record = if asset == loan_record.collateral_asset {
+            // Update collateral amount
+            let updated = sqlx::query_as::<_, crate::loan_lifecycle::LoanLifecycleRow>(
+                r#"
+                UPDATE loan_lifecycle
+                SET collateral_amount = collateral_amount - $1
+                WHERE id = $2
+                RETURNING id, user_id, plan_id, borrow_asset, collateral_asset,
+                          principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
+                          status, due_date, transaction_hash,
+                          created_at, updated_at, repaid_at, liquidated_at
+                "#,
+            )
+            .bind(req.amount)
+            .bind(req.loan_id)
+            .fetch_one(&mut *tx)
+            .await?;
+            updated.into()
+        } else {
+            sqlx::query(
+                r#"
+                UPDATE loan_collateral_positions
+                SET amount = amount - $1, updated_at = NOW()
+                WHERE loan_id = $2 AND asset_code = $3
+                "#,
+            )
+            .bind(req.amount)
+            .bind(req.loan_id)
+            .bind(&asset)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query("DELETE FROM loan_collateral_positions WHERE loan_id = $1 AND amount = 0")
+                .bind(req.loan_id)
+                .execute(&mut *tx)
+                .await?;
+            loan_record.clone()
+        };
 
         // Emit deposit event with negative amount to represent withdrawal
         EventService::emit_deposit(
             &mut tx,
             req.user_id,
             loan_record.plan_id,
-            &loan_record.collateral_asset,
+            &asset,
             -req.amount,
             DepositMetadata {
                 collateral_ratio: None,
-                total_deposited: updated_record.collateral_amount,
+                total_deposited,
             },
             req.transaction_hash.clone(),
             None,
@@ -351,7 +528,7 @@ impl CollateralManagementService {
             Some(entity_type::LOAN),
             None,
             None,
-            None,
+            Some(serde_json::json!({ "asset": asset, "amount": req.amount })),
         )
         .await?;
 
@@ -366,12 +543,14 @@ impl CollateralManagementService {
     /// # Validation
     /// - Loan must exist and belong to the user
     /// - Loan must be in active status
-    /// - New collateral asset must be whitelisted
-    /// - New collateral amount must maintain health factor >= 150%
+    /// - New collateral asset must be whitelisted and not already in the basket
+    /// - New collateral amount, together with the rest of the basket, must
+    ///   still back the debt
     /// - Plan must not be paused
     ///
     /// # Effects
-    /// - Updates collateral_asset and collateral_amount
+    /// - Updates collateral_asset and collateral_amount; the rest of the
+    ///   basket is unchanged
     /// - Emits a deposit event for old collateral (negative)
     /// - Emits a deposit event for new collateral (positive)
     /// - Logs audit trail
@@ -392,7 +571,7 @@ impl CollateralManagementService {
         let loan = sqlx::query_as::<_, crate::loan_lifecycle::LoanLifecycleRow>(
             r#"
             SELECT ll.id, ll.user_id, ll.plan_id, ll.borrow_asset, ll.collateral_asset,
-                   ll.principal, ll.interest_rate_bps, ll.collateral_amount, ll.amount_repaid,
+                   ll.principal, ll.interest_rate_bps, ll.rate_mode, ll.collateral_amount, ll.amount_repaid,
                    ll.status, ll.due_date, ll.transaction_hash,
                    ll.created_at, ll.updated_at, ll.repaid_at, ll.liquidated_at
             FROM loan_lifecycle ll
@@ -433,26 +612,29 @@ impl CollateralManagementService {
                 ))
             })?;
 
-        // Calculate health factor with new collateral
-        let health_factor_after = Self::calculate_health_factor(
-            &price_feed,
-            &loan_record.borrow_asset,
-            &req.new_collateral_asset,
-            loan_record.principal - loan_record.amount_repaid,
-            req.new_collateral_amount,
-        )
-        .await?;
-
-        // Verify health factor remains >= 150% (1.5)
-        let min_health_factor = Decimal::from_str_exact("1.5").map_err(|e| {
-            ApiError::Internal(anyhow::anyhow!("Failed to parse min health factor: {e}"))
-        })?;
+        // Replace the primary position and value the resulting basket
+        let mut positions = Self::positions(&mut tx, &loan_record).await?;
+        if positions
+            .iter()
+            .skip(1)
+            .any(|p| p.asset_code == req.new_collateral_asset)
+        {
+            return Err(ApiError::BadRequest(format!(
+                "{} is already posted in this loan's collateral basket",
+                req.new_collateral_asset
+            )));
+        }
+        positions[0] = CollateralPosition {
+            asset_code: req.new_collateral_asset.clone(),
+            amount: req.new_collateral_amount,
+        };
+        let holdings = Self::price_positions(&mut tx, &price_feed, positions).await?;
+        let debt_value_usd = Self::debt_value(&price_feed, &loan_record).await?;
 
-        if health_factor_after < min_health_factor {
+        if borrowing_capacity(&holdings) < debt_value_usd {
             return Err(ApiError::BadRequest(format!(
-                "new collateral configuration would result in health factor of {:.2}%, minimum is {:.2}%",
-                health_factor_after * Decimal::from(100),
-                min_health_factor * Decimal::from(100)
+                "new collateral configuration would result in health factor of {:.2}%, below the collateral requirement",
+                aggregate_health_factor(&holdings, debt_value_usd) * Decimal::from(100)
             )));
         }
 
@@ -463,7 +645,7 @@ impl CollateralManagementService {
             SET collateral_asset = $1, collateral_amount = $2
             WHERE id = $3
             RETURNING id, user_id, plan_id, borrow_asset, collateral_asset,
-                      principal, interest_rate_bps, collateral_amount, amount_repaid,
+                      principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
                       status, due_date, transaction_hash,
                       created_at, updated_at, repaid_at, liquidated_at
             "#,
@@ -527,7 +709,7 @@ impl CollateralManagementService {
 
     // ── Query Functions ───────────────────────────────────────────────────────
 
-    /// Get current collateral value in USD for a loan
+    /// Get current collateral value in USD for a loan, per asset and in total
     pub async fn get_collateral_value(
         pool: &PgPool,
         price_feed: Arc<dyn PriceFeedService>,
@@ -535,82 +717,76 @@ impl CollateralManagementService {
         user_id: Uuid,
     ) -> Result<CollateralInfo, ApiError> {
         let loan = LoanLifecycleService::get_loan_for_user(pool, loan_id, user_id).await?;
+        let mut conn = pool.acquire().await?;
+        let positions = Self::positions(&mut conn, &loan).await?;
+        let holdings = Self::price_positions(&mut conn, &price_feed, positions).await?;
+        let debt_value_usd = Self::debt_value(&price_feed, &loan).await?;
 
-        let price = price_feed.get_price(&loan.collateral_asset).await?;
-        let collateral_value_usd = loan.collateral_amount * price.price;
-
+        let primary = holdings[0].clone();
         Ok(CollateralInfo {
             loan_id,
             collateral_asset: loan.collateral_asset,
             collateral_amount: loan.collateral_amount,
-            collateral_value_usd,
-            current_price: price.price,
+            collateral_value_usd: primary.value_usd,
+            current_price: primary.price,
+            total_value_usd: holdings.iter().map(|h| h.value_usd).sum(),
+            health_factor: aggregate_health_factor(&holdings, debt_value_usd),
+            positions: holdings,
         })
     }
 
-    /// Get maximum withdrawable collateral amount while maintaining health factor >= 150%
+    /// Get the maximum amount of one collateral asset (the primary collateral
+    /// by default) that can be withdrawn while the rest of the basket still
+    /// backs the debt
     pub async fn get_max_withdrawable_collateral(
         pool: &PgPool,
         price_feed: Arc<dyn PriceFeedService>,
         loan_id: Uuid,
         user_id: Uuid,
+        asset: Option<&str>,
     ) -> Result<SafeWithdrawalInfo, ApiError> {
         let loan = LoanLifecycleService::get_loan_for_user(pool, loan_id, user_id).await?;
-
-        let borrow_price = price_feed.get_price(&loan.borrow_asset).await?;
-        let collateral_price = price_feed.get_price(&loan.collateral_asset).await?;
-
-        let remaining_debt = loan.principal - loan.amount_repaid;
-        let debt_value_usd = remaining_debt * borrow_price.price;
-
-        // Health factor = collateral_value / debt_value
-        // We want: health_factor >= 1.5
-        // So: collateral_value >= debt_value * 1.5
-        // max_collateral_value = debt_value * 1.5
-        // max_collateral_amount = max_collateral_value / collateral_price
-
-        let min_health_factor = Decimal::from_str_exact("1.5").map_err(|e| {
-            ApiError::Internal(anyhow::anyhow!("Failed to parse min health factor: {e}"))
-        })?;
-
-        let min_collateral_value = debt_value_usd * min_health_factor;
-        let max_collateral_amount = min_collateral_value / collateral_price.price;
-
-        let max_withdrawable = if loan.collateral_amount > max_collateral_amount {
-            loan.collateral_amount - max_collateral_amount
-        } else {
-            Decimal::ZERO
-        };
-
-        let health_factor_after = if max_withdrawable > Decimal::ZERO {
-            let collateral_after = loan.collateral_amount - max_withdrawable;
-            let collateral_value = collateral_after * collateral_price.price;
-            if debt_value_usd > Decimal::ZERO {
-                collateral_value / debt_value_usd
-            } else {
-                Decimal::MAX
-            }
-        } else {
-            Self::calculate_health_factor(
-                &price_feed,
-                &loan.borrow_asset,
-                &loan.collateral_asset,
-                remaining_debt,
-                loan.collateral_amount,
-            )
-            .await?
-        };
+        let asset = asset.unwrap_or(&loan.collateral_asset).to_string();
+        let mut conn = pool.acquire().await?;
+        let positions = Self::positions(&mut conn, &loan).await?;
+        let holdings = Self::price_positions(&mut conn, &price_feed, positions).await?;
+        let debt_value_usd = Self::debt_value(&price_feed, &loan).await?;
+
+        let holding = holdings
+            .iter()
+            .find(|h| h.asset_code == asset)
+            .cloned()
+            .ok_or_else(|| {
+                ApiError::NotFound(format!(
+                    "no {asset} collateral is posted against loan {loan_id}"
+                ))
+            })?;
+        let max_withdrawable = max_withdrawable(&holdings, &asset, debt_value_usd);
+
+        let after: Vec<CollateralHolding> = holdings
+            .iter()
+            .cloned()
+            .map(|mut h| {
+                if h.asset_code == asset {
+                    h.amount -= max_withdrawable;
+                    h.value_usd = h.amount * h.price;
+                }
+                h
+            })
+            .collect();
 
         Ok(SafeWithdrawalInfo {
             loan_id,
-            current_collateral_amount: loan.collateral_amount,
+            collateral_asset: asset,
+            current_collateral_amount: holding.amount,
             max_withdrawable_amount: max_withdrawable,
-            health_factor_after_withdrawal: health_factor_after,
-            min_health_factor,
+            health_factor_after_withdrawal: aggregate_health_factor(&after, debt_value_usd),
+            min_health_factor: ratio_multiplier(holding.collateral_ratio_bps),
         })
     }
 
-    /// Get required collateral amount for a given loan amount
+    /// Get the primary collateral amount a loan requires, after counting the
+    /// rest of its basket
     pub async fn get_required_collateral(
         pool: &PgPool,
         price_feed: Arc<dyn PriceFeedService>,
@@ -618,31 +794,19 @@ impl CollateralManagementService {
         user_id: Uuid,
     ) -> Result<CollateralRequirements, ApiError> {
         let loan = LoanLifecycleService::get_loan_for_user(pool, loan_id, user_id).await?;
-
-        let borrow_price = price_feed.get_price(&loan.borrow_asset).await?;
-        let collateral_price = price_feed.get_price(&loan.collateral_asset).await?;
-
-        let remaining_debt = loan.principal - loan.amount_repaid;
-        let debt_value_usd = remaining_debt * borrow_price.price;
-
-        // Required collateral value = debt_value * 1.5 (for 150% health factor)
-        let min_health_factor = Decimal::from_str_exact("1.5").map_err(|e| {
-            ApiError::Internal(anyhow::anyhow!("Failed to parse min health factor: {e}"))
-        })?;
-
-        let required_collateral_value = debt_value_usd * min_health_factor;
-        let required_collateral_amount = required_collateral_value / collateral_price.price;
-
-        let collateral_surplus = loan.collateral_amount - required_collateral_amount;
-
-        let health_factor = Self::calculate_health_factor(
-            &price_feed,
-            &loan.borrow_asset,
-            &loan.collateral_asset,
-            remaining_debt,
-            loan.collateral_amount,
-        )
-        .await?;
+        let mut conn = pool.acquire().await?;
+        let positions = Self::positions(&mut conn, &loan).await?;
+        let holdings = Self::price_positions(&mut conn, &price_feed, positions).await?;
+        let debt_value_usd = Self::debt_value(&price_feed, &loan).await?;
+
+        let primary = &holdings[0];
+        let required_collateral_amount = required_amount(
+            &holdings,
+            &primary.asset_code,
+            primary.price,
+            primary.collateral_ratio_bps,
+            debt_value_usd,
+        );
 
         Ok(CollateralRequirements {
             loan_id,
@@ -651,38 +815,93 @@ impl CollateralManagementService {
             collateral_asset: loan.collateral_asset,
             required_collateral_amount,
             current_collateral_amount: loan.collateral_amount,
-            collateral_surplus,
-            health_factor,
-            min_health_factor,
+            collateral_surplus: loan.collateral_amount - required_collateral_amount,
+            health_factor: aggregate_health_factor(&holdings, debt_value_usd),
+            min_health_factor: ratio_multiplier(primary.collateral_ratio_bps),
         })
     }
 
     // ── Helper Functions ──────────────────────────────────────────────────────
 
-    /// Calculate health factor for a loan
-    /// health_factor = collateral_value_usd / debt_value_usd
-    async fn calculate_health_factor(
+    /// Every collateral position of a loan, primary collateral first
+    async fn positions(
+        conn: &mut PgConnection,
+        loan: &LoanLifecycleRecord,
+    ) -> Result<Vec<CollateralPosition>, ApiError> {
+        let basket = sqlx::query_as::<_, CollateralPosition>(
+            r#"
+            SELECT asset_code, amount
+            FROM loan_collateral_positions
+            WHERE loan_id = $1 AND amount > 0
+            ORDER BY created_at
+            "#,
+        )
+        .bind(loan.id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let mut positions = vec![CollateralPosition {
+            asset_code: loan.collateral_asset.clone(),
+            amount: loan.collateral_amount,
+        }];
+        positions.extend(basket);
+        Ok(positions)
+    }
+
+    /// Value each position at its current price and collateral ratio
+    async fn price_positions(
+        conn: &mut PgConnection,
         price_feed: &Arc<dyn PriceFeedService>,
-        borrow_asset: &str,
-        collateral_asset: &str,
-        debt_amount: Decimal,
-        collateral_amount: Decimal,
-    ) -> Result<Decimal, ApiError> {
-        if debt_amount <= Decimal::ZERO {
-            return Ok(Decimal::MAX);
+        positions: Vec<CollateralPosition>,
+    ) -> Result<Vec<CollateralHolding>, ApiError> {
+        let mut holdings = Vec::with_capacity(positions.len());
+        for position in positions {
+            let price = price_feed.get_price(&position.asset_code).await?.price;
+            let collateral_ratio_bps =
+                Self::collateral_ratio_bps(&mut *conn, &position.asset_code).await?;
+            holdings.push(CollateralHolding {
+                value_usd: position.amount * price,
+                asset_code: position.asset_code,
+                amount: position.amount,
+                price,
+                collateral_ratio_bps,
+            });
         }
+        Ok(holdings)
+    }
 
-        let borrow_price = price_feed.get_price(borrow_asset).await?;
-        let collateral_price = price_feed.get_price(collateral_asset).await?;
+    /// Collateral ratio of an asset, in basis points
+    async fn collateral_ratio_bps(conn: &mut PgConnection, asset: &str) -> Result<i32, ApiError> {
+        let ratio: Option<i32> = sqlx::query_scalar(
+            "SELECT collateral_ratio_bps FROM price_feeds WHERE asset_code = $1",
+        )
+        .bind(asset)
+        .fetch_optional(&mut *conn)
+        .await?;
+        Ok(ratio.unwrap_or(DEFAULT_COLLATERAL_RATIO_BPS))
+    }
 
-        let debt_value_usd = debt_amount * borrow_price.price;
-        let collateral_value_usd = collateral_amount * collateral_price.price;
+    /// Whether an asset has an active price feed and can be posted as collateral
+    async fn is_collateral_asset(conn: &mut PgConnection, asset: &str) -> Result<bool, ApiError> {
+        let active: Option<bool> =
+            sqlx::query_scalar("SELECT is_active FROM price_feeds WHERE asset_code = $1")
+                .bind(asset)
+                .fetch_optional(&mut *conn)
+                .await?;
+        Ok(active.unwrap_or(false))
+    }
 
-        if debt_value_usd > Decimal::ZERO {
-            Ok(collateral_value_usd / debt_value_usd)
-        } else {
-            Ok(Decimal::MAX)
+    /// Outstanding debt of a loan in USD
+    async fn debt_value(
+        price_feed: &Arc<dyn PriceFeedService>,
+        loan: &LoanLifecycleRecord,
+    ) -> Result<Decimal, ApiError> {
+        let remaining_debt = loan.principal - loan.amount_repaid;
+        if remaining_debt <= Decimal::ZERO {
+            return Ok(Decimal::ZERO);
         }
+        let borrow_price = price_feed.get_price(&loan.borrow_asset).await?;
+        Ok(remaining_debt * borrow_price.price)
     }
 }
 
@@ -696,6 +915,7 @@ mod tests {
         let req = AddCollateralRequest {
             loan_id: Uuid::new_v4(),
             user_id: Uuid::new_v4(),
+            asset: None,
             amount: dec!(100.00),
             transaction_hash: None,
         };
@@ -707,8 +927,10 @@ mod tests {
         let req = RemoveCollateralRequest {
             loan_id: Uuid::new_v4(),
             user_id: Uuid::new_v4(),
+            asset: Some("ETH".to_string()),
             amount: dec!(50.00),
             transaction_hash: None,
+            two_fa_code: None,
         };
         assert!(req.amount > Decimal::ZERO);
     }
@@ -733,6 +955,9 @@ mod tests {
             collateral_amount: dec!(1000.00),
             collateral_value_usd: dec!(1000.00),
             current_price: dec!(1.00),
+            positions: vec![holding("USDC", dec!(1000.00), dec!(1.00), 15000)],
+            total_value_usd: dec!(1000.00),
+            health_factor: dec!(2.0),
         };
 
         let json = serde_json::to_value(&info).unwrap();
@@ -758,4 +983,43 @@ mod tests {
         assert!(json.is_object());
         assert_eq!(json["borrowAsset"], "USDC");
     }
+
+    fn holding(asset: &str, amount: Decimal, price: Decimal, ratio_bps: i32) -> CollateralHolding {
+        CollateralHolding {
+            asset_code: asset.to_string(),
+            amount,
+            price,
+            collateral_ratio_bps: ratio_bps,
+            value_usd: amount * price,
+        }
+    }
+
+    #[test]
+    fn test_basket_counts_each_asset_at_its_ratio() {
+        let basket = vec![
+            holding("XLM", dec!(1500), dec!(1), 15000),
+            holding("ETH", dec!(1), dec!(1000), 20000),
+        ];
+        // 1500 / 1.5 + 1000 / 2.0
+        assert_eq!(borrowing_capacity(&basket), dec!(1500));
+        assert_eq!(aggregate_health_factor(&basket, dec!(1000)), dec!(2.5));
+        assert_eq!(
+            aggregate_health_factor(&basket, Decimal::ZERO),
+            Decimal::MAX
+        );
+    }
+
+    #[test]
+    fn test_max_withdrawable_counts_the_rest_of_the_basket() {
+        let basket = vec![
+            holding("XLM", dec!(1500), dec!(1), 15000),
+            holding("ETH", dec!(1), dec!(1000), 20000),
+        ];
+        // ETH alone backs 500, so 750 XLM must stay against 1000 of debt
+        assert_eq!(max_withdrawable(&basket, "XLM", dec!(1000)), dec!(750));
+        // XLM alone backs the whole debt
+        assert_eq!(max_withdrawable(&basket, "ETH", dec!(1000)), dec!(1));
+        assert_eq!(max_withdrawable(&basket, "ETH", dec!(1250)), dec!(0.5));
+        assert_eq!(max_withdrawable(&basket, "BTC", dec!(1000)), Decimal::ZERO);
+    }
 }
@@ -0,0 +1,306 @@
+//! # Post-Trigger Contest Windows
+//!
+//! Plans with a contest period are not released the moment they trigger:
+//! the contract opens a window (`CONTEST/START`) during which claims are
+//! blocked, the owner may cancel the trigger and emergency contacts may file
+//! a dispute. The window ends (`CONTEST/END`) either when the owner cancels
+//! or when it is closed after elapsing.
+//!
+//! The cross-chain relayer records both events here so that plan APIs can
+//! show a countdown without querying the chain.
+
+use crate::api_error::ApiError;
+use crate::stellar::{decode_scval, is_symbol, map_bool, map_u64, SorobanEvent};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// A contest window event emitted by the inheritance contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContestEvent {
+    Started {
+        contract_plan_id: u64,
+        started_at: u64,
+        ends_at: u64,
+    },
+    Ended {
+        contract_plan_id: u64,
+        ended_at: u64,
+        cancelled: bool,
+    },
+}
+
+/// Decode a `CONTEST/START` or `CONTEST/END` contract event. Returns `None`
+/// for any other event.
+pub fn parse_contest_event(event: &SorobanEvent) -> Option<ContestEvent> {
+    if event.topic.len() < 2 {
+        return None;
+    }
+    let topic0 = decode_scval(&event.topic[0])?;
+    if !is_symbol(&topic0, "CONTEST") {
+        return None;
+    }
+    let topic1 = decode_scval(&event.topic[1])?;
+    let value = decode_scval(&event.value)?;
+    let contract_plan_id = map_u64(&value, "plan_id")?;
+    if is_symbol(&topic1, "START") {
+        Some(ContestEvent::Started {
+            contract_plan_id,
+            started_at: map_u64(&value, "started_at")?,
+            ends_at: map_u64(&value, "ends_at")?,
+        })
+    } else if is_symbol(&topic1, "END") {
+        Some(ContestEvent::Ended {
+            contract_plan_id,
+            ended_at: map_u64(&value, "ended_at")?,
+            cancelled: map_bool(&value, "cancelled")?,
+        })
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContestState {
+    /// Claims are blocked; the owner can still cancel.
+    Open,
+    /// The window has elapsed but nobody has closed it on-chain yet.
+    Elapsed,
+    /// Closed after elapsing; the plan has been released.
+    Closed,
+    /// The owner cancelled the trigger.
+    Cancelled,
+}
+
+/// Countdown shown alongside a plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContestWindowStatus {
+    pub started_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub state: ContestState,
+    pub remaining_seconds: i64,
+}
+
+impl ContestWindowStatus {
+    pub fn at(
+        now: DateTime<Utc>,
+        started_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+        ended_at: Option<DateTime<Utc>>,
+        cancelled: bool,
+    ) -> Self {
+        let state = match (ended_at, cancelled) {
+            (Some(_), true) => ContestState::Cancelled,
+            (Some(_), false) => ContestState::Closed,
+            (None, _) if now < ends_at => ContestState::Open,
+            (None, _) => ContestState::Elapsed,
+        };
+        let remaining_seconds = if state == ContestState::Open {
+            (ends_at - now).num_seconds()
+        } else {
+            0
+        };
+        Self {
+            started_at,
+            ends_at,
+            ended_at,
+            state,
+            remaining_seconds,
+        }
+    }
+}
+
+fn timestamp(secs: u64) -> DateTime<Utc> {
+    Utc.timestamp_opt(secs as i64, 0)
+        .single()
+        .unwrap_or_else(Utc::now)
+}
+
+pub struct ContestWindowService;
+
+impl ContestWindowService {
+    /// Persist a contest window event observed on-chain.
+    pub async fn record_event(db: &PgPool, event: &ContestEvent) -> Result<(), ApiError> {
+        match *event {
+            ContestEvent::Started {
+                contract_plan_id,
+                started_at,
+                ends_at,
+            } => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO plan_contest_windows (contract_plan_id, started_at, ends_at)
+                    VALUES ($1, $2, $3)
+                    ON CONFLICT (contract_plan_id) DO UPDATE
+                    SET started_at = EXCLUDED.started_at,
+                        ends_at = EXCLUDED.ends_at,
+                        ended_at = NULL,
+                        cancelled = FALSE,
+                        updated_at = NOW()
+                    "#,
+                )
+                .bind(contract_plan_id as i64)
+                .bind(timestamp(started_at))
+                .bind(timestamp(ends_at))
+                .execute(db)
+                .await?;
+            }
+            ContestEvent::Ended {
+                contract_plan_id,
+                ended_at,
+                cancelled,
+            } => {
+                sqlx::query(
+                    r#"
+                    UPDATE plan_contest_windows
+                    SET ended_at = $2, cancelled = $3, updated_at = NOW()
+                    WHERE contract_plan_id = $1
+                    "#,
+                )
+                .bind(contract_plan_id as i64)
+                .bind(timestamp(ended_at))
+                .bind(cancelled)
+                .execute(db)
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The latest contest window of a plan, if it has ever had one.
+    pub async fn get_for_plan(
+        db: &PgPool,
+        plan_id: Uuid,
+    ) -> Result<Option<ContestWindowStatus>, ApiError> {
+        #[derive(sqlx::FromRow)]
+        struct Row {
+            started_at: DateTime<Utc>,
+            ends_at: DateTime<Utc>,
+            ended_at: Option<DateTime<Utc>>,
+            cancelled: bool,
+        }
+
+        let row = sqlx::query_as::<_, Row>(
+            r#"
+            SELECT w.started_at, w.ends_at, w.ended_at, w.cancelled
+            FROM plan_contest_windows w
+            JOIN plans p ON p.contract_plan_id = w.contract_plan_id
+            WHERE p.id = $1
+            "#,
+        )
+        .bind(plan_id)
+        .fetch_optional(db)
+        .await?;
+
+        Ok(row.map(|r| {
+            ContestWindowStatus::at(Utc::now(), r.started_at, r.ends_at, r.ended_at, r.cancelled)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+    use chrono::Duration;
+    use stellar_xdr::curr::{Limits, ScMap, ScMapEntry, ScSymbol, ScVal, WriteXdr};
+
+    fn symbol(s: &str) -> ScVal {
+        ScVal::Symbol(ScSymbol(s.try_into().unwrap()))
+    }
+
+    fn encode(val: &ScVal) -> String {
+        BASE64.encode(val.to_xdr(Limits::none()).unwrap())
+    }
+
+    fn event(topic1: &str, fields: Vec<(&str, ScVal)>) -> SorobanEvent {
+        let entries: Vec<ScMapEntry> = fields
+            .into_iter()
+            .map(|(k, v)| ScMapEntry {
+                key: symbol(k),
+                val: v,
+            })
+            .collect();
+        SorobanEvent {
+            event_type: "contract".to_string(),
+            ledger: 50,
+            ledger_closed_at: None,
+            contract_id: "CCONTRACT".to_string(),
+            id: "0000000050-0000000001".to_string(),
+            paging_token: None,
+            topic: vec![encode(&symbol("CONTEST")), encode(&symbol(topic1))],
+            value: encode(&ScVal::Map(Some(ScMap(entries.try_into().unwrap())))),
+            tx_hash: None,
+        }
+    }
+
+    #[test]
+    fn parses_start_and_end_events() {
+        let start = event(
+            "START",
+            vec![
+                ("ends_at", ScVal::U64(1_600)),
+                ("plan_id", ScVal::U64(3)),
+                ("started_at", ScVal::U64(1_000)),
+            ],
+        );
+        assert_eq!(
+            parse_contest_event(&start),
+            Some(ContestEvent::Started {
+                contract_plan_id: 3,
+                started_at: 1_000,
+                ends_at: 1_600,
+            })
+        );
+
+        let end = event(
+            "END",
+            vec![
+                ("cancelled", ScVal::Bool(true)),
+                ("ended_at", ScVal::U64(1_200)),
+                ("plan_id", ScVal::U64(3)),
+            ],
+        );
+        assert_eq!(
+            parse_contest_event(&end),
+            Some(ContestEvent::Ended {
+                contract_plan_id: 3,
+                ended_at: 1_200,
+                cancelled: true,
+            })
+        );
+
+        assert!(parse_contest_event(&event("OTHER", vec![("plan_id", ScVal::U64(3))])).is_none());
+    }
+
+    #[test]
+    fn countdown_reflects_state() {
+        let start = Utc::now();
+        let ends = start + Duration::seconds(600);
+
+        let open =
+            ContestWindowStatus::at(start + Duration::seconds(100), start, ends, None, false);
+        assert_eq!(open.state, ContestState::Open);
+        assert_eq!(open.remaining_seconds, 500);
+
+        let elapsed = ContestWindowStatus::at(ends, start, ends, None, false);
+        assert_eq!(elapsed.state, ContestState::Elapsed);
+        assert_eq!(elapsed.remaining_seconds, 0);
+
+        let cancelled = ContestWindowStatus::at(
+            start + Duration::seconds(100),
+            start,
+            ends,
+            Some(start + Duration::seconds(50)),
+            true,
+        );
+        assert_eq!(cancelled.state, ContestState::Cancelled);
+        assert_eq!(cancelled.remaining_seconds, 0);
+
+        let closed = ContestWindowStatus::at(ends, start, ends, Some(ends), false);
+        assert_eq!(closed.state, ContestState::Closed);
+    }
+}
@@ -0,0 +1,1041 @@
+//! # Contract Event Indexer
+//!
+//! Mirrors the events of the inheritance, lending, borrowing and governance
+//! contracts into Postgres, so the backend's own tables can be fed from, and
+//! checked against, what actually happened on-chain.
+//!
+//! For every configured contract the indexer pages Soroban `getEvents` from
+//! a cursor kept in `contract_indexer_cursors`, decodes each event's topics
+//! and body from XDR and stores it in `contract_events`. Rows are keyed by
+//! the RPC event id and a page's cursor advances in the same transaction as
+//! its events, so a restart, or a deliberate replay after
+//! [`ContractEventIndexer::rewind`], never records an event twice. Stellar
+//! ledgers are final, so there are no reorgs to unwind.
+//!
+//! An event whose XDR cannot be decoded is recorded raw in
+//! `undecodable_contract_events` and reported to error tracking. A cursor
+//! that has fallen out of the RPC's retention window resumes from the
+//! oldest retained ledger, with an alert naming the ledgers that were lost.
+//!
+//! Newly stored events are also fed to the services that keep their own
+//! history:
+//!
+//! | Event                          | Fed to                                  |
+//! |--------------------------------|-----------------------------------------|
+//! | lending `POOL/DEPOSIT`         | [`EventService::emit_deposit`]          |
+//! | lending `POOL/REPAY`           | [`EventService::emit_repay`]            |
+//! | lending `POOL/LIQUIDATE`       | [`EventService::emit_liquidation`]      |
+//! | inheritance `TRANCHE/CLAIMED`  | [`EventService::emit_tranche_claimed`]  |
+//! | inheritance `WILL/SIGNED`      | [`WillEventService::emit`]              |
+//! | inheritance `WILL/FINAL`       | [`WillEventService::emit`]              |
+//!
+//! Lending events are fed only when the account belongs to a known user,
+//! plan events only when a backend plan carries the contract plan id, and
+//! will events only when the plan has a will document. Everything else, and
+//! every borrowing and governance event, is kept in `contract_events` alone.
+
+use crate::api_error::ApiError;
+use crate::events::{
+    DepositMetadata, EventService, LiquidationMetadata, RepayMetadata, TrancheClaimedMetadata,
+};
+use crate::stellar::{
+    decode_scval, map_address, map_u32, map_u64, GetEventsResult, SorobanEvent, SorobanRpcClient,
+};
+use crate::will_events::{WillEvent, WillEventService};
+use chrono::{DateTime, TimeZone, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Duration;
+use stellar_xdr::curr::ScVal;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Events requested per `getEvents` call.
+const EVENTS_PAGE_LIMIT: u32 = 100;
+
+/// Pages drained per contract on each poll, so one busy contract cannot
+/// starve the others.
+const MAX_PAGES_PER_POLL: usize = 10;
+
+/// Decimal places of Stellar asset amounts; contracts report stroops.
+pub(crate) const STELLAR_ASSET_DECIMALS: u32 = 7;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Contracts
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The contracts whose events are indexed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractKind {
+    Inheritance,
+    Lending,
+    Borrowing,
+    Governance,
+}
+
+impl ContractKind {
+    pub const ALL: [ContractKind; 4] = [
+        ContractKind::Inheritance,
+        ContractKind::Lending,
+        ContractKind::Borrowing,
+        ContractKind::Governance,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContractKind::Inheritance => "inheritance",
+            ContractKind::Lending => "lending",
+            ContractKind::Borrowing => "borrowing",
+            ContractKind::Governance => "governance",
+        }
+    }
+
+    /// Environment variable holding the deployed contract's `C…` address.
+    pub fn env_var(&self) -> &'static str {
+        match self {
+            ContractKind::Inheritance => "INHERITANCE_CONTRACT_ID",
+            ContractKind::Lending => "LENDING_CONTRACT_ID",
+            ContractKind::Borrowing => "BORROWING_CONTRACT_ID",
+            ContractKind::Governance => "GOVERNANCE_CONTRACT_ID",
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Decoding
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A contract event as stored in `contract_events`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedEvent {
+    /// Soroban RPC event id; unique across the network.
+    pub event_id: String,
+    pub contract: ContractKind,
+    pub contract_id: String,
+    /// The event's leading symbol topics joined with `/`, e.g. `POOL/DEPOSIT`.
+    pub name: String,
+    pub ledger: i64,
+    pub ledger_closed_at: Option<DateTime<Utc>>,
+    pub tx_hash: Option<String>,
+    /// Every topic, decoded with [`scval_to_json`].
+    pub topics: Value,
+    /// The event body, decoded with [`scval_to_json`].
+    pub payload: Value,
+}
+
+/// Typed view of the events the backend feeds into its own records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainEvent {
+    /// Inheritance `INHERIT/TRIGGER` (`InheritanceTriggeredEvent`).
+    InheritanceTriggered {
+        plan_id: u64,
+        triggered_at: u64,
+        contest_ends_at: u64,
+    },
+    /// Inheritance `TRANCHE/CLAIMED` (`TrancheClaimedEvent`).
+    TrancheClaimed {
+        plan_id: u64,
+        beneficiary_index: u32,
+        amount: u64,
+        tranches_claimed: u32,
+        tranche_count: u32,
+        claimed_to_date: u64,
+    },
+    /// Inheritance `WILL/SIGNED` (`WillSignedEvent`).
+    WillSigned { vault_id: u64, signer: String },
+    /// Inheritance `WILL/FINAL` (`WillFinalizedEvent`).
+    WillFinalized {
+        vault_id: u64,
+        version: u32,
+        finalized_at: u64,
+    },
+    /// Lending `POOL/DEPOSIT` (`DepositEvent`).
+    Deposit {
+        depositor: String,
+        asset: String,
+        amount: u64,
+        shares_minted: u64,
+    },
+    /// Lending `POOL/REPAY` (`RepayEvent`).
+    Repay {
+        loan_id: u64,
+        borrower: String,
+        asset: String,
+        principal: u64,
+        interest: u64,
+        total_amount: u64,
+    },
+    /// Lending `POOL/LIQUIDATE` (`LiquidationEvent`).
+    Liquidation {
+        loan_id: u64,
+        borrower: String,
+        liquidator: String,
+        asset: String,
+        amount_repaid: u64,
+        collateral_seized: u64,
+        remaining_debt: u64,
+        bonus_bps: u32,
+    },
+}
+
+/// JSON rendering of a contract value.
+///
+/// 64- and 128-bit integers become strings so they survive JSON consumers
+/// that read numbers as doubles; addresses use their `G…`/`C…` form, bytes
+/// are hex. Maps keyed by symbols or strings become objects, which is how
+/// `#[contracttype]` structs are encoded.
+pub fn scval_to_json(val: &ScVal) -> Value {
+    match val {
+        ScVal::Void => Value::Null,
+        ScVal::Bool(b) => json!(b),
+        ScVal::U32(v) => json!(v),
+        ScVal::I32(v) => json!(v),
+        ScVal::U64(v) => json!(v.to_string()),
+        ScVal::I64(v) => json!(v.to_string()),
+        ScVal::Timepoint(t) => json!(t.0.to_string()),
+        ScVal::Duration(d) => json!(d.0.to_string()),
+        ScVal::U128(parts) => json!((((parts.hi as u128) << 64) | parts.lo as u128).to_string()),
+        ScVal::I128(parts) => {
+            json!((((parts.hi as i128) << 64) | parts.lo as i128).to_string())
+        }
+        ScVal::Bytes(bytes) => json!(hex::encode(bytes.as_slice())),
+        ScVal::String(s) => json!(s.0.to_utf8_string_lossy()),
+        ScVal::Symbol(s) => json!(s.0.to_utf8_string_lossy()),
+        ScVal::Address(address) => json!(address.to_string()),
+        ScVal::Vec(Some(items)) => Value::Array(items.iter().map(scval_to_json).collect()),
+        ScVal::Map(Some(entries)) => {
+            let keys: Option<Vec<String>> = entries
+                .iter()
+                .map(|entry| match &entry.key {
+                    ScVal::Symbol(s) => Some(s.0.to_utf8_string_lossy()),
+                    ScVal::String(s) => Some(s.0.to_utf8_string_lossy()),
+                    _ => None,
+                })
+                .collect();
+            match keys {
+                Some(keys) => Value::Object(
+                    keys.into_iter()
+                        .zip(entries.iter())
+                        .map(|(key, entry)| (key, scval_to_json(&entry.val)))
+                        .collect::<Map<_, _>>(),
+                ),
+                None => Value::Array(
+                    entries
+                        .iter()
+                        .map(|entry| json!([scval_to_json(&entry.key), scval_to_json(&entry.val)]))
+                        .collect(),
+                ),
+            }
+        }
+        ScVal::Vec(None) | ScVal::Map(None) => Value::Null,
+        other => json!(format!("{other:?}")),
+    }
+}
+
+/// Decode an RPC event into the row stored for it. Returns `None` when a
+/// topic or the body is not valid XDR.
+pub fn decode_event(contract: ContractKind, event: &SorobanEvent) -> Option<IndexedEvent> {
+    let topics = event
+        .topic
+        .iter()
+        .map(|t| decode_scval(t))
+        .collect::<Option<Vec<_>>>()?;
+    let payload = decode_scval(&event.value)?;
+
+    let name = topics
+        .iter()
+        .map_while(|t| match t {
+            ScVal::Symbol(s) => Some(s.0.to_utf8_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    Some(IndexedEvent {
+        event_id: event.id.clone(),
+        contract,
+        contract_id: event.contract_id.clone(),
+        name,
+        ledger: event.ledger as i64,
+        ledger_closed_at: event
+            .ledger_closed_at
+            .as_deref()
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc)),
+        tx_hash: event.tx_hash.clone(),
+        topics: Value::Array(topics.iter().map(scval_to_json).collect()),
+        payload: scval_to_json(&payload),
+    })
+}
+
+/// Decode the events listed in [`ChainEvent`]. Returns `None` for any other
+/// event, or one whose body does not have the expected fields.
+pub fn parse_chain_event(contract: ContractKind, event: &SorobanEvent) -> Option<ChainEvent> {
+    if event.topic.len() < 2 {
+        return None;
+    }
+    let topic0 = decode_scval(&event.topic[0])?;
+    let topic1 = decode_scval(&event.topic[1])?;
+    let ScVal::Symbol(first) = &topic0 else {
+        return None;
+    };
+    let ScVal::Symbol(second) = &topic1 else {
+        return None;
+    };
+    let value = decode_scval(&event.value)?;
+
+    let parsed = match (
+        contract,
+        first.0.to_utf8_string_lossy().as_str(),
+        second.0.to_utf8_string_lossy().as_str(),
+    ) {
+        (ContractKind::Inheritance, "INHERIT", "TRIGGER") => ChainEvent::InheritanceTriggered {
+            plan_id: map_u64(&value, "plan_id")?,
+            triggered_at: map_u64(&value, "triggered_at").unwrap_or(0),
+            contest_ends_at: map_u64(&value, "contest_ends_at").unwrap_or(0),
+        },
+        (ContractKind::Inheritance, "TRANCHE", "CLAIMED") => ChainEvent::TrancheClaimed {
+            plan_id: map_u64(&value, "plan_id")?,
+            beneficiary_index: map_u32(&value, "beneficiary_index")?,
+            amount: map_u64(&value, "amount")?,
+            tranches_claimed: map_u32(&value, "tranches_claimed")?,
+            tranche_count: map_u32(&value, "tranche_count")?,
+            claimed_to_date: map_u64(&value, "claimed_to_date")?,
+        },
+        (ContractKind::Inheritance, "WILL", "SIGNED") => ChainEvent::WillSigned {
+            vault_id: map_u64(&value, "vault_id")?,
+            signer: map_address(&value, "signer")?,
+        },
+        (ContractKind::Inheritance, "WILL", "FINAL") => ChainEvent::WillFinalized {
+            vault_id: map_u64(&value, "vault_id")?,
+            version: map_u32(&value, "version")?,
+            finalized_at: map_u64(&value, "finalized_at")?,
+        },
+        (ContractKind::Lending, "POOL", "DEPOSIT") => ChainEvent::Deposit {
+            depositor: map_address(&value, "depositor")?,
+            asset: map_address(&value, "asset")?,
+            amount: map_u64(&value, "amount")?,
+            shares_minted: map_u64(&value, "shares_minted")?,
+        },
+        (ContractKind::Lending, "POOL", "REPAY") => ChainEvent::Repay {
+            loan_id: map_u64(&value, "loan_id")?,
+            borrower: map_address(&value, "borrower")?,
+            asset: map_address(&value, "asset")?,
+            principal: map_u64(&value, "principal")?,
+            interest: map_u64(&value, "interest")?,
+            total_amount: map_u64(&value, "total_amount")?,
+        },
+        (ContractKind::Lending, "POOL", "LIQUIDATE") => ChainEvent::Liquidation {
+            loan_id: map_u64(&value, "loan_id")?,
+            borrower: map_address(&value, "borrower")?,
+            liquidator: map_address(&value, "liquidator")?,
+            asset: map_address(&value, "asset")?,
+            amount_repaid: map_u64(&value, "amount_repaid")?,
+            collateral_seized: map_u64(&value, "collateral_seized")?,
+            remaining_debt: map_u64(&value, "remaining_debt")?,
+            bonus_bps: map_u32(&value, "bonus_bps")?,
+        },
+        _ => return None,
+    };
+    Some(parsed)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Service
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub struct ContractEventIndexer {
+    db: PgPool,
+    rpc: SorobanRpcClient,
+    contracts: Vec<(ContractKind, String)>,
+}
+
+impl ContractEventIndexer {
+    pub fn new(db: PgPool, rpc: SorobanRpcClient) -> Self {
+        Self {
+            db,
+            rpc,
+            contracts: Vec::new(),
+        }
+    }
+
+    /// Index the events of `contract_id`, a deployment of `kind`.
+    pub fn with_contract(mut self, kind: ContractKind, contract_id: impl Into<String>) -> Self {
+        self.contracts.push((kind, contract_id.into()));
+        self
+    }
+
+    /// Index every contract whose address is set in the environment (see
+    /// [`ContractKind::env_var`]). Returns `None` when none is.
+    pub fn from_env(db: PgPool, rpc: SorobanRpcClient) -> Option<Self> {
+        let mut indexer = Self::new(db, rpc);
+        for kind in ContractKind::ALL {
+            if let Ok(contract_id) = std::env::var(kind.env_var()) {
+                if !contract_id.is_empty() {
+                    indexer = indexer.with_contract(kind, contract_id);
+                }
+            }
+        }
+        (!indexer.contracts.is_empty()).then_some(indexer)
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(15));
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.poll_once().await {
+                    error!("Contract event indexer error: {}", e);
+                    crate::error_tracking::capture_message(
+                        &format!("ContractEventIndexer::poll_once failed: {e}"),
+                        sentry::Level::Error,
+                    );
+                }
+            }
+        });
+    }
+
+    /// Catch every contract up with the chain, a bounded number of pages at
+    /// a time. Returns the number of newly stored events.
+    pub async fn poll_once(&self) -> Result<usize, ApiError> {
+        let mut stored = 0;
+        for (kind, contract_id) in &self.contracts {
+            for _ in 0..MAX_PAGES_PER_POLL {
+                let (new_events, page_len) = self.index_page(*kind, contract_id).await?;
+                stored += new_events;
+                if page_len < EVENTS_PAGE_LIMIT as usize {
+                    break;
+                }
+            }
+        }
+        if stored > 0 {
+            info!("Contract event indexer: stored {} new event(s)", stored);
+        }
+        Ok(stored)
+    }
+
+    /// Move `contract_id`'s cursor back to `ledger`. The next poll re-reads
+    /// events from there; those already stored are skipped.
+    pub async fn rewind(&self, contract_id: &str, ledger: i64) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            INSERT INTO contract_indexer_cursors (contract_id, last_ledger, paging_token)
+            VALUES ($1, $2, NULL)
+            ON CONFLICT (contract_id) DO UPDATE
+            SET last_ledger = EXCLUDED.last_ledger,
+                paging_token = NULL,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(contract_id)
+        .bind(ledger)
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+
+    /// One page of events after `paging_token`, or from `last_ledger` (the
+    /// oldest retained ledger when 0) when there is none.
+    async fn fetch_page(
+        &self,
+        contract_id: &str,
+        last_ledger: i64,
+        paging_token: Option<&str>,
+    ) -> Result<GetEventsResult, ApiError> {
+        match paging_token {
+            Some(cursor) => {
+                self.rpc
+                    .get_events(contract_id, None, Some(cursor), EVENTS_PAGE_LIMIT)
+                    .await
+            }
+            None => {
+                let start = if last_ledger > 0 {
+                    last_ledger as u64
+                } else {
+                    self.rpc.get_health().await?.oldest_ledger.unwrap_or(1)
+                };
+                self.rpc
+                    .get_events(contract_id, Some(start), None, EVENTS_PAGE_LIMIT)
+                    .await
+            }
+        }
+    }
+
+    /// Store one page of events after the cursor. Returns how many were new
+    /// and how many the page held. A cursor that fell out of the RPC's
+    /// retention is moved to the oldest retained ledger with an alert about
+    /// the skipped ledgers, and undecodable events are recorded in
+    /// `undecodable_contract_events`.
+    async fn index_page(
+        &self,
+        kind: ContractKind,
+        contract_id: &str,
+    ) -> Result<(usize, usize), ApiError> {
+        let (mut last_ledger, mut paging_token) = sqlx::query_as::<_, (i64, Option<String>)>(
+            "SELECT last_ledger, paging_token FROM contract_indexer_cursors WHERE contract_id = $1",
+        )
+        .bind(contract_id)
+        .fetch_optional(&self.db)
+        .await?
+        .unwrap_or((0, None));
+
+        let page = match self
+            .fetch_page(contract_id, last_ledger, paging_token.as_deref())
+            .await
+        {
+            Ok(page) => page,
+            Err(e) => {
+                // A cursor older than the RPC's retention window can never be
+                // read again; resume from the oldest retained ledger instead.
+                let oldest = self.rpc.get_health().await?.oldest_ledger.unwrap_or(1);
+                let Some(gap) = retention_gap(last_ledger, oldest) else {
+                    return Err(e);
+                };
+                let message = format!(
+                    "Contract event indexer: {} {contract_id} cursor at ledger {} is outside RPC \
+                     retention; events in ledgers {:?} were not indexed",
+                    kind.as_str(),
+                    gap.start,
+                    gap,
+                );
+                error!("{}", message);
+                crate::error_tracking::capture_message(&message, sentry::Level::Error);
+                last_ledger = oldest as i64;
+                paging_token = None;
+                self.fetch_page(contract_id, last_ledger, None).await?
+            }
+        };
+
+        let mut tx = self.db.begin().await?;
+        let mut stored = 0;
+        let mut will_events = Vec::new();
+        let mut undecodable = Vec::new();
+        for event in &page.events {
+            let Some(indexed) = decode_event(kind, event) else {
+                if insert_undecodable(&mut tx, kind, event).await? {
+                    undecodable.push(event.id.clone());
+                }
+                continue;
+            };
+            if !insert_event(&mut tx, &indexed).await? {
+                continue;
+            }
+            stored += 1;
+            if let Some(chain_event) = parse_chain_event(kind, event) {
+                if let Some(will_event) = feed(&mut tx, &indexed, chain_event).await? {
+                    will_events.push(will_event);
+                }
+            }
+        }
+
+        let next_token = page
+            .cursor
+            .clone()
+            .or_else(|| page.events.last().and_then(|e| e.paging_token.clone()))
+            .or(paging_token);
+        let next_ledger = page
+            .events
+            .last()
+            .map(|e| e.ledger as i64)
+            .unwrap_or(last_ledger);
+
+        sqlx::query(
+            r#"
+            INSERT INTO contract_indexer_cursors (contract_id, last_ledger, paging_token)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (contract_id) DO UPDATE
+            SET last_ledger = EXCLUDED.last_ledger,
+                paging_token = EXCLUDED.paging_token,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(contract_id)
+        .bind(next_ledger)
+        .bind(next_token)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        for event_id in undecodable {
+            let message = format!(
+                "Contract event indexer: recorded undecodable event {} from {} {}",
+                event_id,
+                kind.as_str(),
+                contract_id
+            );
+            warn!("{}", message);
+            crate::error_tracking::capture_message(&message, sentry::Level::Warning);
+        }
+        for event in will_events {
+            if let Err(e) = WillEventService::emit(&self.db, event).await {
+                warn!("Failed to emit on-chain will event: {}", e);
+            }
+        }
+        Ok((stored, page.events.len()))
+    }
+}
+
+/// Ledgers skipped when a cursor at `last_ledger` predates `oldest_ledger`,
+/// the oldest ledger the RPC still retains.
+fn retention_gap(last_ledger: i64, oldest_ledger: u64) -> Option<Range<u64>> {
+    let last_ledger = u64::try_from(last_ledger).ok().filter(|l| *l > 0)?;
+    (last_ledger < oldest_ledger).then_some(last_ledger..oldest_ledger)
+}
+
+/// Record an event whose topics or body could not be decoded, as raw XDR,
+/// unless it is already recorded. Returns whether it was new.
+async fn insert_undecodable(
+    tx: &mut Transaction<'_, Postgres>,
+    kind: ContractKind,
+    event: &SorobanEvent,
+) -> Result<bool, ApiError> {
+    let inserted = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO undecodable_contract_events (
+            event_id, contract_kind, contract_id, ledger, tx_hash, topics, value
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (event_id) DO NOTHING
+        RETURNING id
+        "#,
+    )
+    .bind(&event.id)
+    .bind(kind.as_str())
+    .bind(&event.contract_id)
+    .bind(event.ledger as i64)
+    .bind(&event.tx_hash)
+    .bind(json!(event.topic))
+    .bind(&event.value)
+    .fetch_optional(&mut **tx)
+    .await?;
+    Ok(inserted.is_some())
+}
+
+/// Insert `event` unless it is already stored. Returns whether it was new.
+async fn insert_event(
+    tx: &mut Transaction<'_, Postgres>,
+    event: &IndexedEvent,
+) -> Result<bool, ApiError> {
+    let inserted = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO contract_events (
+            event_id, contract_kind, contract_id, event_name, ledger,
+            ledger_closed_at, tx_hash, topics, payload
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        ON CONFLICT (event_id) DO NOTHING
+        RETURNING id
+        "#,
+    )
+    .bind(&event.event_id)
+    .bind(event.contract.as_str())
+    .bind(&event.contract_id)
+    .bind(&event.name)
+    .bind(event.ledger)
+    .bind(event.ledger_closed_at)
+    .bind(&event.tx_hash)
+    .bind(&event.topics)
+    .bind(&event.payload)
+    .fetch_optional(&mut **tx)
+    .await?;
+    Ok(inserted.is_some())
+}
+
+/// Record `chain_event` with the service that keeps its history. Lending
+/// events are written in `tx`; a will event is returned for the caller to
+/// emit once `tx` has committed.
+async fn feed(
+    tx: &mut Transaction<'_, Postgres>,
+    event: &IndexedEvent,
+    chain_event: ChainEvent,
+) -> Result<Option<WillEvent>, ApiError> {
+    let tx_hash = event.tx_hash.clone();
+    let block_number = Some(event.ledger);
+    let timestamp = event.ledger_closed_at.unwrap_or_else(Utc::now);
+
+    match chain_event {
+        ChainEvent::Deposit {
+            depositor,
+            asset,
+            amount,
+            ..
+        } => {
+            let Some(user_id) = user_for_wallet(tx, &depositor).await? else {
+                return Ok(None);
+            };
+            let amount = asset_amount(amount);
+            EventService::emit_deposit(
+                tx,
+                user_id,
+                None,
+                &asset,
+                amount,
+                DepositMetadata {
+                    collateral_ratio: None,
+                    total_deposited: amount,
+                },
+                tx_hash,
+                block_number,
+            )
+            .await?;
+        }
+        ChainEvent::Repay {
+            borrower,
+            asset,
+            principal,
+            interest,
+            total_amount,
+            ..
+        } => {
+            let Some(user_id) = user_for_wallet(tx, &borrower).await? else {
+                return Ok(None);
+            };
+            // Pool loans are repaid in full.
+            EventService::emit_repay(
+                tx,
+                user_id,
+                None,
+                &asset,
+                asset_amount(total_amount),
+                RepayMetadata {
+                    principal_amount: asset_amount(principal),
+                    interest_amount: asset_amount(interest),
+                    remaining_balance: Decimal::ZERO,
+                },
+                tx_hash,
+                block_number,
+            )
+            .await?;
+        }
+        ChainEvent::Liquidation {
+            borrower,
+            liquidator,
+            asset,
+            amount_repaid,
+            collateral_seized,
+            remaining_debt,
+            bonus_bps,
+            ..
+        } => {
+            let Some(user_id) = user_for_wallet(tx, &borrower).await? else {
+                return Ok(None);
+            };
+            // Keepers without an account are recorded as the system liquidator.
+            let liquidator_id = user_for_wallet(tx, &liquidator)
+                .await?
+                .unwrap_or(Uuid::nil());
+            let debt_covered = asset_amount(amount_repaid);
+            EventService::emit_liquidation(
+                tx,
+                user_id,
+                None,
+                &asset,
+                debt_covered,
+                LiquidationMetadata {
+                    liquidator_id,
+                    collateral_asset: asset.clone(),
+                    collateral_seized: asset_amount(collateral_seized),
+                    debt_covered,
+                    liquidation_penalty: debt_covered * Decimal::from(bonus_bps)
+                        / Decimal::from(10_000),
+                    remaining_debt: asset_amount(remaining_debt),
+                },
+                tx_hash,
+                block_number,
+            )
+            .await?;
+        }
+        ChainEvent::TrancheClaimed {
+            plan_id,
+            beneficiary_index,
+            amount,
+            tranches_claimed,
+            tranche_count,
+            claimed_to_date,
+        } => {
+            let Some((plan_id, user_id, asset_code)) = sqlx::query_as::<_, (Uuid, Uuid, String)>(
+                r#"
+                SELECT id, user_id, COALESCE(asset_code, 'USDC')
+                FROM plans
+                WHERE contract_plan_id = $1
+                "#,
+            )
+            .bind(plan_id as i64)
+            .fetch_optional(&mut **tx)
+            .await?
+            else {
+                return Ok(None);
+            };
+            EventService::emit_tranche_claimed(
+                tx,
+                user_id,
+                Some(plan_id),
+                &asset_code,
+                asset_amount(amount),
+                TrancheClaimedMetadata {
+                    beneficiary_index,
+                    tranches_claimed,
+                    tranche_count,
+                    claimed_to_date: asset_amount(claimed_to_date),
+                },
+                tx_hash,
+                block_number,
+            )
+            .await?;
+        }
+        ChainEvent::WillSigned { vault_id, signer } => {
+            let Some(document) = will_document(tx, vault_id, None).await? else {
+                return Ok(None);
+            };
+            return Ok(Some(WillEvent::WillSigned {
+                vault_id: vault_id.to_string(),
+                document_id: document.id,
+                plan_id: document.plan_id,
+                signer,
+                // The signature lives in the transaction; its hash identifies it.
+                signature_hash: tx_hash.unwrap_or_default(),
+                timestamp,
+            }));
+        }
+        ChainEvent::WillFinalized {
+            vault_id,
+            version,
+            finalized_at,
+        } => {
+            let Some(document) = will_document(tx, vault_id, Some(version)).await? else {
+                return Ok(None);
+            };
+            return Ok(Some(WillEvent::WillFinalized {
+                vault_id: vault_id.to_string(),
+                document_id: document.id,
+                plan_id: document.plan_id,
+                version,
+                will_hash: document.will_hash,
+                timestamp: Utc
+                    .timestamp_opt(finalized_at as i64, 0)
+                    .single()
+                    .unwrap_or(timestamp),
+            }));
+        }
+        // Plan triggers are acted on by the cross-chain relayer.
+        ChainEvent::InheritanceTriggered { .. } => {}
+    }
+    Ok(None)
+}
+
+pub(crate) fn asset_amount(stroops: u64) -> Decimal {
+    Decimal::from_i128_with_scale(stroops as i128, STELLAR_ASSET_DECIMALS)
+}
+
+async fn user_for_wallet(
+    tx: &mut Transaction<'_, Postgres>,
+    wallet_address: &str,
+) -> Result<Option<Uuid>, ApiError> {
+    Ok(
+        sqlx::query_scalar::<_, Uuid>("SELECT id FROM users WHERE wallet_address = $1 LIMIT 1")
+            .bind(wallet_address)
+            .fetch_optional(&mut **tx)
+            .await?,
+    )
+}
+
+#[derive(sqlx::FromRow)]
+struct WillDocumentRow {
+    id: Uuid,
+    plan_id: Uuid,
+    will_hash: String,
+}
+
+/// The will document of the plan with `contract_plan_id`: the given
+/// version, or the latest one.
+async fn will_document(
+    tx: &mut Transaction<'_, Postgres>,
+    contract_plan_id: u64,
+    version: Option<u32>,
+) -> Result<Option<WillDocumentRow>, ApiError> {
+    Ok(sqlx::query_as::<_, WillDocumentRow>(
+        r#"
+        SELECT d.id, d.plan_id, d.will_hash
+        FROM will_documents d
+        JOIN plans p ON p.id = d.plan_id
+        WHERE p.contract_plan_id = $1
+          AND ($2::INTEGER IS NULL OR d.version = $2)
+        ORDER BY d.version DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(contract_plan_id as i64)
+    .bind(version.map(|v| v as i32))
+    .fetch_optional(&mut **tx)
+    .await?)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Unit tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+    use stellar_xdr::curr::{
+        AccountId, Hash, Int128Parts, Limits, PublicKey, ScAddress, ScMap, ScMapEntry, ScSymbol,
+        ScVec, StringM, Uint256, WriteXdr,
+    };
+
+    fn symbol(s: &str) -> ScVal {
+        ScVal::Symbol(ScSymbol(StringM::try_from(s).unwrap()))
+    }
+
+    fn encode(val: &ScVal) -> String {
+        BASE64.encode(val.to_xdr(Limits::none()).unwrap())
+    }
+
+    fn account(byte: u8) -> ScVal {
+        ScVal::Address(ScAddress::Account(AccountId(
+            PublicKey::PublicKeyTypeEd25519(Uint256([byte; 32])),
+        )))
+    }
+
+    fn fields(entries: Vec<(&str, ScVal)>) -> ScVal {
+        let entries: Vec<ScMapEntry> = entries
+            .into_iter()
+            .map(|(key, val)| ScMapEntry {
+                key: symbol(key),
+                val,
+            })
+            .collect();
+        ScVal::Map(Some(ScMap(entries.try_into().unwrap())))
+    }
+
+    fn event(topics: &[ScVal], value: &ScVal) -> SorobanEvent {
+        SorobanEvent {
+            event_type: "contract".to_string(),
+            ledger: 1234,
+            ledger_closed_at: Some("2026-06-01T12:00:00Z".to_string()),
+            contract_id: "CCONTRACT".to_string(),
+            id: "0000005299989442560-0000000001".to_string(),
+            paging_token: None,
+            topic: topics.iter().map(encode).collect(),
+            value: encode(value),
+            tx_hash: Some("ab".repeat(32)),
+        }
+    }
+
+    #[test]
+    fn decodes_lending_deposit() {
+        let raw = event(
+            &[symbol("POOL"), symbol("DEPOSIT")],
+            &fields(vec![
+                ("amount", ScVal::U64(5_000_000)),
+                ("asset", ScVal::Address(ScAddress::Contract(Hash([9; 32])))),
+                ("depositor", account(1)),
+                ("shares_minted", ScVal::U64(4_900_000)),
+            ]),
+        );
+
+        let indexed = decode_event(ContractKind::Lending, &raw).unwrap();
+        assert_eq!(indexed.name, "POOL/DEPOSIT");
+        assert_eq!(indexed.ledger, 1234);
+        assert_eq!(indexed.payload["amount"], "5000000");
+        assert!(indexed.payload["depositor"]
+            .as_str()
+            .unwrap()
+            .starts_with('G'));
+        assert!(indexed.payload["asset"].as_str().unwrap().starts_with('C'));
+
+        let Some(ChainEvent::Deposit {
+            amount,
+            shares_minted,
+            ..
+        }) = parse_chain_event(ContractKind::Lending, &raw)
+        else {
+            panic!("expected a deposit");
+        };
+        assert_eq!((amount, shares_minted), (5_000_000, 4_900_000));
+        // The same topics from another contract are not a lending deposit.
+        assert_eq!(parse_chain_event(ContractKind::Borrowing, &raw), None);
+    }
+
+    #[test]
+    fn decodes_will_finalized_and_trigger() {
+        let finalized = event(
+            &[symbol("WILL"), symbol("FINAL")],
+            &fields(vec![
+                ("finalized_at", ScVal::U64(1_700_000_000)),
+                ("vault_id", ScVal::U64(7)),
+                ("version", ScVal::U32(2)),
+            ]),
+        );
+        assert_eq!(
+            parse_chain_event(ContractKind::Inheritance, &finalized),
+            Some(ChainEvent::WillFinalized {
+                vault_id: 7,
+                version: 2,
+                finalized_at: 1_700_000_000,
+            })
+        );
+
+        let trigger = event(
+            &[symbol("INHERIT"), symbol("TRIGGER")],
+            &fields(vec![
+                ("contest_ends_at", ScVal::U64(0)),
+                ("outstanding_loans", ScVal::U64(0)),
+                ("plan_id", ScVal::U64(7)),
+                ("triggered_at", ScVal::U64(1_700_000_000)),
+            ]),
+        );
+        assert_eq!(
+            parse_chain_event(ContractKind::Inheritance, &trigger),
+            Some(ChainEvent::InheritanceTriggered {
+                plan_id: 7,
+                triggered_at: 1_700_000_000,
+                contest_ends_at: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn amounts_are_scaled_from_stroops() {
+        assert_eq!(asset_amount(12_345_678).to_string(), "1.2345678");
+    }
+
+    #[test]
+    fn names_events_by_leading_symbol_topics() {
+        // Governance events carry a symbol followed by an address or id.
+        let raw = event(
+            &[symbol("PropQueue"), ScVal::U32(4)],
+            &ScVal::Vec(Some(ScVec(
+                vec![ScVal::I128(Int128Parts {
+                    hi: -1,
+                    lo: u64::MAX,
+                })]
+                .try_into()
+                .unwrap(),
+            ))),
+        );
+        let indexed = decode_event(ContractKind::Governance, &raw).unwrap();
+        assert_eq!(indexed.name, "PropQueue");
+        assert_eq!(indexed.topics, json!(["PropQueue", 4]));
+        assert_eq!(indexed.payload, json!(["-1"]));
+        assert_eq!(parse_chain_event(ContractKind::Governance, &raw), None);
+
+        let mut broken = raw.clone();
+        broken.value = "not xdr".to_string();
+        assert_eq!(decode_event(ContractKind::Governance, &broken), None);
+    }
+
+    #[test]
+    fn reports_ledgers_lost_to_rpc_retention() {
+        assert_eq!(retention_gap(100, 250), Some(100..250));
+        assert_eq!(retention_gap(250, 250), None);
+        assert_eq!(retention_gap(300, 250), None);
+        // A fresh cursor starts at the oldest retained ledger anyway
+        assert_eq!(retention_gap(0, 250), None);
+    }
+}
@@ -0,0 +1,1074 @@
+//! # Cross-Chain Release Relayer
+//!
+//! Off-chain counterpart of the inheritance contract's cross-chain manifest.
+//! The worker:
+//!
+//! 1. polls the inheritance contract for `INHERIT/TRIGGER` events through
+//!    [`SorobanRpcClient::get_events`], persisting its cursor in Postgres;
+//!    plans with a contest window are released on their `CONTEST/END` event
+//!    instead, unless the owner cancelled the trigger;
+//! 2. for every released plan, reads the bridge releases the contract
+//!    recorded on trigger, one per asset of the plan's cross-chain manifest,
+//!    and records one release job per release under its manifest index, from
+//!    the owner's linked wallet on the release's chain;
+//! 3. drives each job through its state machine using a chain-specific
+//!    [`ReleaseExecutor`]; and
+//! 4. reports confirmed releases back through a [`ConfirmationReporter`]
+//!    (on-chain this is `confirm_bridge_release`, sent by
+//!    [`SorobanConfirmationReporter`]).
+//!
+//! ```text
+//!  pending ──submit──▶ submitted ──confirmed──▶ confirmed
+//!     │                    │
+//!     └──── failed ◀───────┘
+//! ```
+//!
+//! Executors and the reporter are traits so the EVM/Bitcoin sides can be
+//! swapped for [`MockReleaseExecutor`] / [`MockConfirmationReporter`] in tests.
+
+use crate::api_error::ApiError;
+use crate::contest_window::{parse_contest_event, ContestEvent, ContestWindowService};
+use crate::contract_indexer::ContractKind;
+use crate::stellar::{
+    contract_enum_key, decode_scval, is_symbol, map_address, map_enum_variant, map_string,
+    map_u128, map_u32, map_u64, ContractCall, ContractInvoker, SorobanEvent, SorobanRpcClient,
+    StellarClient,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use stellar_xdr::curr::{ContractDataDurability, ScVal};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Name of the cursor row used by this worker in `cross_chain_relayer_cursors`.
+const CURSOR_NAME: &str = "inheritance_trigger_events";
+
+/// Events fetched per `getEvents` page.
+const EVENTS_PAGE_LIMIT: u32 = 100;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Job status
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Lifecycle state of a release job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseJobStatus {
+    Pending,
+    Submitted,
+    Confirmed,
+    Failed,
+}
+
+impl ReleaseJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReleaseJobStatus::Pending => "pending",
+            ReleaseJobStatus::Submitted => "submitted",
+            ReleaseJobStatus::Confirmed => "confirmed",
+            ReleaseJobStatus::Failed => "failed",
+        }
+    }
+
+    /// Check if a transition from this state to `next` is valid. A failed
+    /// submission may be retried, so `pending → pending` is allowed.
+    pub fn validate_transition(self, next: ReleaseJobStatus) -> Result<(), ApiError> {
+        let valid = matches!(
+            (self, next),
+            (ReleaseJobStatus::Pending, ReleaseJobStatus::Pending)
+                | (ReleaseJobStatus::Pending, ReleaseJobStatus::Submitted)
+                | (ReleaseJobStatus::Pending, ReleaseJobStatus::Failed)
+                | (ReleaseJobStatus::Submitted, ReleaseJobStatus::Confirmed)
+                | (ReleaseJobStatus::Submitted, ReleaseJobStatus::Failed)
+        );
+
+        if valid {
+            Ok(())
+        } else {
+            Err(ApiError::BadRequest(format!(
+                "invalid release job transition: {} → {}",
+                self, next
+            )))
+        }
+    }
+}
+
+impl fmt::Display for ReleaseJobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ReleaseJobStatus {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "pending" => Ok(ReleaseJobStatus::Pending),
+            "submitted" => Ok(ReleaseJobStatus::Submitted),
+            "confirmed" => Ok(ReleaseJobStatus::Confirmed),
+            "failed" => Ok(ReleaseJobStatus::Failed),
+            other => Err(ApiError::BadRequest(format!(
+                "unknown release job status: {other}"
+            ))),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Job record
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A single per-chain asset release for a triggered plan, mirroring one of
+/// the contract's bridge releases.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseJob {
+    pub id: Uuid,
+    pub plan_id: Option<Uuid>,
+    pub contract_plan_id: i64,
+    /// Index of the asset in the plan's cross-chain manifest.
+    pub asset_index: i32,
+    pub chain: String,
+    pub asset_symbol: String,
+    pub contract_address: Option<String>,
+    /// Owner wallet on `chain` the asset is released from.
+    pub source_address: String,
+    /// Amount in the asset's smallest unit, stored as a string for precision.
+    pub amount: String,
+    pub status: ReleaseJobStatus,
+    pub remote_tx_hash: Option<String>,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub reported_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct ReleaseJobRow {
+    id: Uuid,
+    plan_id: Option<Uuid>,
+    contract_plan_id: i64,
+    asset_index: i32,
+    chain: String,
+    asset_symbol: String,
+    contract_address: Option<String>,
+    source_address: String,
+    amount: String,
+    status: String,
+    remote_tx_hash: Option<String>,
+    attempts: i32,
+    last_error: Option<String>,
+    reported_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<ReleaseJobRow> for ReleaseJob {
+    type Error = ApiError;
+
+    fn try_from(row: ReleaseJobRow) -> Result<Self, Self::Error> {
+        Ok(ReleaseJob {
+            id: row.id,
+            plan_id: row.plan_id,
+            contract_plan_id: row.contract_plan_id,
+            asset_index: row.asset_index,
+            chain: row.chain,
+            asset_symbol: row.asset_symbol,
+            contract_address: row.contract_address,
+            source_address: row.source_address,
+            amount: row.amount,
+            status: row.status.parse()?,
+            remote_tx_hash: row.remote_tx_hash,
+            attempts: row.attempts,
+            last_error: row.last_error,
+            reported_at: row.reported_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+const JOB_COLUMNS: &str = "id, plan_id, contract_plan_id, asset_index, chain, asset_symbol, \
+     contract_address, source_address, amount, status, remote_tx_hash, attempts, last_error, \
+     reported_at, created_at, updated_at";
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Pluggable chain sides
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Outcome of a remote release as seen on the destination chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteReleaseStatus {
+    Pending,
+    Confirmed,
+    Failed(String),
+}
+
+/// Executes releases on one destination chain (an EVM bridge, a Bitcoin
+/// wallet, ...).
+#[async_trait]
+pub trait ReleaseExecutor: Send + Sync {
+    /// Broadcast the release and return the remote transaction hash.
+    async fn submit_release(&self, job: &ReleaseJob) -> anyhow::Result<String>;
+
+    /// Look up the state of a previously submitted release.
+    async fn release_status(
+        &self,
+        job: &ReleaseJob,
+        remote_tx_hash: &str,
+    ) -> anyhow::Result<RemoteReleaseStatus>;
+}
+
+/// Reports a confirmed release back to the inheritance contract.
+#[async_trait]
+pub trait ConfirmationReporter: Send + Sync {
+    async fn report_confirmation(&self, job: &ReleaseJob) -> anyhow::Result<()>;
+}
+
+/// In-process fake executor. Submissions succeed with a deterministic hash
+/// and stay pending until [`MockReleaseExecutor::settle`] is called.
+#[derive(Default)]
+pub struct MockReleaseExecutor {
+    fail_submissions: bool,
+    releases: Mutex<HashMap<String, RemoteReleaseStatus>>,
+}
+
+impl MockReleaseExecutor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An executor whose every submission errors.
+    pub fn failing() -> Self {
+        Self {
+            fail_submissions: true,
+            ..Self::default()
+        }
+    }
+
+    /// Set the remote outcome of a submitted release.
+    pub fn settle(&self, remote_tx_hash: &str, status: RemoteReleaseStatus) {
+        self.releases
+            .lock()
+            .unwrap()
+            .insert(remote_tx_hash.to_string(), status);
+    }
+
+    /// Hashes of every release submitted so far.
+    pub fn submitted(&self) -> Vec<String> {
+        self.releases.lock().unwrap().keys().cloned().collect()
+    }
+}
+
+#[async_trait]
+impl ReleaseExecutor for MockReleaseExecutor {
+    async fn submit_release(&self, job: &ReleaseJob) -> anyhow::Result<String> {
+        if self.fail_submissions {
+            anyhow::bail!("mock {} bridge rejected the release", job.chain);
+        }
+        let hash = format!(
+            "mock-{}-{}-{}",
+            job.chain, job.contract_plan_id, job.asset_index
+        );
+        info!(
+            "--- [MOCK BRIDGE RELEASE] {} {} -> {}",
+            job.amount, job.asset_symbol, hash
+        );
+        self.releases
+            .lock()
+            .unwrap()
+            .entry(hash.clone())
+            .or_insert(RemoteReleaseStatus::Pending);
+        Ok(hash)
+    }
+
+    async fn release_status(
+        &self,
+        _job: &ReleaseJob,
+        remote_tx_hash: &str,
+    ) -> anyhow::Result<RemoteReleaseStatus> {
+        Ok(self
+            .releases
+            .lock()
+            .unwrap()
+            .get(remote_tx_hash)
+            .cloned()
+            .unwrap_or(RemoteReleaseStatus::Pending))
+    }
+}
+
+/// In-process fake reporter that records every confirmation it is given.
+#[derive(Default)]
+pub struct MockConfirmationReporter {
+    reported: Mutex<Vec<(i64, i32, String)>>,
+}
+
+impl MockConfirmationReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `(contract_plan_id, asset_index, remote_tx_hash)` per reported release.
+    pub fn reported(&self) -> Vec<(i64, i32, String)> {
+        self.reported.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl ConfirmationReporter for MockConfirmationReporter {
+    async fn report_confirmation(&self, job: &ReleaseJob) -> anyhow::Result<()> {
+        info!(
+            "--- [MOCK CONFIRMATION] plan {} asset {}",
+            job.contract_plan_id, job.asset_index
+        );
+        self.reported.lock().unwrap().push((
+            job.contract_plan_id,
+            job.asset_index,
+            job.remote_tx_hash.clone().unwrap_or_default(),
+        ));
+        Ok(())
+    }
+}
+
+/// Reports confirmations on-chain by calling `confirm_bridge_release` from
+/// the invoker's account, which must hold the contract's relayer role.
+pub struct SorobanConfirmationReporter {
+    invoker: ContractInvoker,
+    contract_id: String,
+}
+
+impl SorobanConfirmationReporter {
+    pub fn new(invoker: ContractInvoker, contract_id: impl Into<String>) -> Self {
+        Self {
+            invoker,
+            contract_id: contract_id.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl ConfirmationReporter for SorobanConfirmationReporter {
+    async fn report_confirmation(&self, job: &ReleaseJob) -> anyhow::Result<()> {
+        let remote_tx = job
+            .remote_tx_hash
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("release {} has no remote transaction", job.id))?;
+        let call = ContractCall::confirm_bridge_release(
+            &self.contract_id,
+            &self.invoker.source_account(),
+            u64::try_from(job.contract_plan_id)?,
+            u32::try_from(job.asset_index)?,
+            remote_tx.as_bytes(),
+        )?;
+        let result = self.invoker.invoke_and_confirm(call).await?;
+        info!(
+            plan_id = job.contract_plan_id,
+            asset_index = job.asset_index,
+            ledger = ?result.ledger,
+            "Bridge release confirmed on-chain"
+        );
+        Ok(())
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Event parsing
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A plan trigger observed on-chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriggeredPlan {
+    pub contract_plan_id: u64,
+    pub triggered_at: u64,
+    /// End of the plan's contest window, or 0 if released immediately.
+    pub contest_ends_at: u64,
+    pub ledger: u64,
+}
+
+/// Decode an `INHERIT/TRIGGER` contract event. Returns `None` for any other
+/// event.
+pub fn parse_trigger_event(event: &SorobanEvent) -> Option<TriggeredPlan> {
+    if event.topic.len() < 2 {
+        return None;
+    }
+    let topic0 = decode_scval(&event.topic[0])?;
+    let topic1 = decode_scval(&event.topic[1])?;
+    if !is_symbol(&topic0, "INHERIT") || !is_symbol(&topic1, "TRIGGER") {
+        return None;
+    }
+    let value = decode_scval(&event.value)?;
+    Some(TriggeredPlan {
+        contract_plan_id: map_u64(&value, "plan_id")?,
+        triggered_at: map_u64(&value, "triggered_at").unwrap_or(0),
+        contest_ends_at: map_u64(&value, "contest_ends_at").unwrap_or(0),
+        ledger: event.ledger,
+    })
+}
+
+/// One of the bridge releases the contract records for a triggered plan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BridgeRelease {
+    /// Index of the asset in the plan's cross-chain manifest; the index
+    /// `confirm_bridge_release` expects.
+    pub asset_index: u32,
+    /// Lowercase chain name, e.g. `ethereum`, as executors are registered.
+    pub chain: String,
+    pub asset_symbol: String,
+    pub contract_address: Option<String>,
+    /// Amount in the asset's smallest unit.
+    pub amount: u128,
+    /// Already confirmed on-chain.
+    pub confirmed: bool,
+}
+
+/// Decode a `BridgeRelease` as the contract stores it.
+pub fn parse_bridge_release(val: &ScVal) -> Option<BridgeRelease> {
+    Some(BridgeRelease {
+        asset_index: map_u32(val, "asset_index")?,
+        chain: map_enum_variant(val, "chain")?.to_lowercase(),
+        asset_symbol: map_string(val, "asset_symbol")?,
+        contract_address: map_address(val, "contract_address"),
+        amount: map_u128(val, "amount")?,
+        confirmed: map_enum_variant(val, "status")? == "Confirmed",
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Service
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub struct CrossChainRelayerService {
+    db: PgPool,
+    rpc: SorobanRpcClient,
+    contract_id: String,
+    executors: HashMap<String, Arc<dyn ReleaseExecutor>>,
+    reporter: Arc<dyn ConfirmationReporter>,
+    max_attempts: i32,
+}
+
+impl CrossChainRelayerService {
+    pub fn new(
+        db: PgPool,
+        rpc: SorobanRpcClient,
+        contract_id: String,
+        reporter: Arc<dyn ConfirmationReporter>,
+    ) -> Self {
+        Self {
+            db,
+            rpc,
+            contract_id,
+            executors: HashMap::new(),
+            reporter,
+            max_attempts: 5,
+        }
+    }
+
+    /// Relay the inheritance contract set in `INHERITANCE_CONTRACT_ID`,
+    /// confirming releases from the [`ContractInvoker::from_env`] account,
+    /// which must hold the contract's relayer role. `None` unless both are
+    /// configured.
+    pub fn from_env(db: PgPool, rpc: SorobanRpcClient) -> Result<Option<Self>, ApiError> {
+        let Some(contract_id) = std::env::var(ContractKind::Inheritance.env_var())
+            .ok()
+            .filter(|id| !id.is_empty())
+        else {
+            return Ok(None);
+        };
+        let Some(invoker) = ContractInvoker::from_env(StellarClient::from_env())? else {
+            return Ok(None);
+        };
+        let reporter = Arc::new(SorobanConfirmationReporter::new(
+            invoker,
+            contract_id.clone(),
+        ));
+        Ok(Some(Self::new(db, rpc, contract_id, reporter)))
+    }
+
+    /// Register the executor used for releases on `chain`.
+    pub fn with_executor(mut self, chain: &str, executor: Arc<dyn ReleaseExecutor>) -> Self {
+        self.executors.insert(chain.to_lowercase(), executor);
+        self
+    }
+
+    /// Maximum submission attempts before a job is marked failed.
+    pub fn with_max_attempts(mut self, max_attempts: i32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(30));
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.poll_trigger_events().await {
+                    error!("Cross-chain relayer error (events): {}", e);
+                    crate::error_tracking::capture_message(
+                        &format!("CrossChainRelayerService::poll_trigger_events failed: {e}"),
+                        sentry::Level::Error,
+                    );
+                }
+                if let Err(e) = self.process_jobs().await {
+                    error!("Cross-chain relayer error (jobs): {}", e);
+                    crate::error_tracking::capture_message(
+                        &format!("CrossChainRelayerService::process_jobs failed: {e}"),
+                        sentry::Level::Error,
+                    );
+                }
+            }
+        });
+    }
+
+    /// Fetch new contract events since the stored cursor and enqueue release
+    /// jobs for every triggered plan. Returns the number of triggers seen.
+    pub async fn poll_trigger_events(&self) -> Result<usize, ApiError> {
+        let (last_ledger, paging_token) = sqlx::query_as::<_, (i64, Option<String>)>(
+            "SELECT last_ledger, paging_token FROM cross_chain_relayer_cursors WHERE name = $1",
+        )
+        .bind(CURSOR_NAME)
+        .fetch_optional(&self.db)
+        .await?
+        .unwrap_or((0, None));
+
+        let page = match paging_token.as_deref() {
+            Some(cursor) => {
+                self.rpc
+                    .get_events(&self.contract_id, None, Some(cursor), EVENTS_PAGE_LIMIT)
+                    .await?
+            }
+            None => {
+                let start = if last_ledger > 0 {
+                    last_ledger as u64
+                } else {
+                    self.rpc.get_health().await?.oldest_ledger.unwrap_or(1)
+                };
+                self.rpc
+                    .get_events(&self.contract_id, Some(start), None, EVENTS_PAGE_LIMIT)
+                    .await?
+            }
+        };
+
+        let mut triggers = 0;
+        for event in &page.events {
+            if let Some(triggered) = parse_trigger_event(event) {
+                triggers += 1;
+                // Contested plans are released when their window closes.
+                if triggered.contest_ends_at == 0 {
+                    self.enqueue_plan_releases(
+                        triggered.contract_plan_id as i64,
+                        triggered.ledger as i64,
+                    )
+                    .await?;
+                }
+            } else if let Some(contest) = parse_contest_event(event) {
+                ContestWindowService::record_event(&self.db, &contest).await?;
+                if let ContestEvent::Ended {
+                    contract_plan_id,
+                    cancelled: false,
+                    ..
+                } = contest
+                {
+                    self.enqueue_plan_releases(contract_plan_id as i64, event.ledger as i64)
+                        .await?;
+                }
+            }
+        }
+
+        let next_token = page
+            .cursor
+            .clone()
+            .or_else(|| page.events.last().and_then(|e| e.paging_token.clone()))
+            .or(paging_token);
+        let next_ledger = page
+            .events
+            .last()
+            .map(|e| e.ledger as i64)
+            .unwrap_or(last_ledger);
+
+        sqlx::query(
+            r#"
+            INSERT INTO cross_chain_relayer_cursors (name, last_ledger, paging_token)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (name) DO UPDATE
+            SET last_ledger = EXCLUDED.last_ledger,
+                paging_token = EXCLUDED.paging_token,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(CURSOR_NAME)
+        .bind(next_ledger)
+        .bind(next_token)
+        .execute(&self.db)
+        .await?;
+
+        Ok(triggers)
+    }
+
+    /// Bridge releases the contract recorded for a plan, in manifest order.
+    pub async fn bridge_releases(
+        &self,
+        contract_plan_id: u64,
+    ) -> Result<Vec<BridgeRelease>, ApiError> {
+        let key = contract_enum_key("BridgeReleases", vec![ScVal::U64(contract_plan_id)])?;
+        let stored = self
+            .rpc
+            .get_contract_data(&self.contract_id, &key, ContractDataDurability::Persistent)
+            .await?;
+        let Some(ScVal::Vec(Some(items))) = stored else {
+            return Ok(Vec::new());
+        };
+        items
+            .iter()
+            .map(|item| {
+                parse_bridge_release(item).ok_or_else(|| {
+                    ApiError::ExternalService(format!(
+                        "Undecodable bridge release for plan {contract_plan_id}"
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Create a release job for every bridge release the contract recorded
+    /// for the plan, keyed by its manifest index. Idempotent per
+    /// `(plan, asset_index)`. Releases on a chain the owner has no linked
+    /// wallet on are recorded as failed.
+    pub async fn enqueue_plan_releases(
+        &self,
+        contract_plan_id: i64,
+        trigger_ledger: i64,
+    ) -> Result<Vec<ReleaseJob>, ApiError> {
+        let releases = self.bridge_releases(contract_plan_id as u64).await?;
+        let plan_id =
+            sqlx::query_scalar::<_, Uuid>("SELECT id FROM plans WHERE contract_plan_id = $1")
+                .bind(contract_plan_id)
+                .fetch_optional(&self.db)
+                .await?;
+        let wallets = sqlx::query_as::<_, (String, String)>(
+            r#"
+            SELECT w.chain, w.address
+            FROM plans p
+            JOIN cross_chain_wallets w ON w.user_id = p.user_id
+            WHERE p.contract_plan_id = $1
+            "#,
+        )
+        .bind(contract_plan_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut jobs = Vec::new();
+        for release in releases.iter().filter(|r| !r.confirmed) {
+            let wallet = wallets
+                .iter()
+                .find(|(chain, _)| chain.eq_ignore_ascii_case(&release.chain))
+                .map(|(_, address)| address.as_str());
+            let (status, last_error) = match wallet {
+                Some(_) => (ReleaseJobStatus::Pending, None),
+                None => {
+                    warn!(
+                        "Cross-chain relayer: plan {} has no linked {} wallet for asset {}",
+                        contract_plan_id, release.chain, release.asset_index
+                    );
+                    (
+                        ReleaseJobStatus::Failed,
+                        Some(format!("Owner has no linked {} wallet", release.chain)),
+                    )
+                }
+            };
+            let job = sqlx::query_as::<_, ReleaseJobRow>(&format!(
+                r#"
+                INSERT INTO cross_chain_release_jobs (
+                    plan_id, contract_plan_id, asset_index, chain, asset_symbol,
+                    contract_address, source_address, amount, trigger_ledger, status, last_error
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                ON CONFLICT (contract_plan_id, asset_index) DO UPDATE
+                SET updated_at = cross_chain_release_jobs.updated_at
+                RETURNING {JOB_COLUMNS}
+                "#
+            ))
+            .bind(plan_id)
+            .bind(contract_plan_id)
+            .bind(release.asset_index as i32)
+            .bind(&release.chain)
+            .bind(&release.asset_symbol)
+            .bind(&release.contract_address)
+            .bind(wallet.unwrap_or_default())
+            .bind(release.amount.to_string())
+            .bind(trigger_ledger)
+            .bind(status.as_str())
+            .bind(last_error)
+            .fetch_one(&self.db)
+            .await?;
+            jobs.push(ReleaseJob::try_from(job)?);
+        }
+
+        info!(
+            "Cross-chain relayer: {} release job(s) for plan {}",
+            jobs.len(),
+            contract_plan_id
+        );
+        Ok(jobs)
+    }
+
+    /// Advance every non-terminal job one step and report confirmations that
+    /// have not been reported yet.
+    pub async fn process_jobs(&self) -> Result<(), ApiError> {
+        let jobs = sqlx::query_as::<_, ReleaseJobRow>(&format!(
+            r#"
+            SELECT {JOB_COLUMNS}
+            FROM cross_chain_release_jobs
+            WHERE status IN ('pending', 'submitted')
+               OR (status = 'confirmed' AND reported_at IS NULL)
+            ORDER BY created_at
+            LIMIT 100
+            "#
+        ))
+        .fetch_all(&self.db)
+        .await?;
+
+        for row in jobs {
+            let job = ReleaseJob::try_from(row)?;
+            if let Err(e) = self.advance_job(&job).await {
+                error!(
+                    "Cross-chain relayer: job {} failed to advance: {}",
+                    job.id, e
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn advance_job(&self, job: &ReleaseJob) -> Result<(), ApiError> {
+        if job.status == ReleaseJobStatus::Confirmed {
+            return self.report(job).await;
+        }
+
+        let Some(executor) = self.executors.get(&job.chain) else {
+            warn!(
+                "Cross-chain relayer: no executor registered for {}",
+                job.chain
+            );
+            return Ok(());
+        };
+
+        match job.status {
+            ReleaseJobStatus::Pending => match executor.submit_release(job).await {
+                Ok(hash) => {
+                    self.transition(job, ReleaseJobStatus::Submitted, Some(&hash), None)
+                        .await
+                }
+                Err(e) => {
+                    let next = if job.attempts + 1 >= self.max_attempts {
+                        ReleaseJobStatus::Failed
+                    } else {
+                        ReleaseJobStatus::Pending
+                    };
+                    self.transition(job, next, None, Some(&e.to_string())).await
+                }
+            },
+            ReleaseJobStatus::Submitted => {
+                let hash = job.remote_tx_hash.clone().unwrap_or_default();
+                let status = executor
+                    .release_status(job, &hash)
+                    .await
+                    .map_err(|e| ApiError::ExternalService(e.to_string()))?;
+                match status {
+                    RemoteReleaseStatus::Pending => Ok(()),
+                    RemoteReleaseStatus::Confirmed => {
+                        self.transition(job, ReleaseJobStatus::Confirmed, None, None)
+                            .await?;
+                        let mut confirmed = job.clone();
+                        confirmed.status = ReleaseJobStatus::Confirmed;
+                        self.report(&confirmed).await
+                    }
+                    RemoteReleaseStatus::Failed(reason) => {
+                        self.transition(job, ReleaseJobStatus::Failed, None, Some(&reason))
+                            .await
+                    }
+                }
+            }
+            ReleaseJobStatus::Confirmed | ReleaseJobStatus::Failed => Ok(()),
+        }
+    }
+
+    async fn transition(
+        &self,
+        job: &ReleaseJob,
+        next: ReleaseJobStatus,
+        remote_tx_hash: Option<&str>,
+        error: Option<&str>,
+    ) -> Result<(), ApiError> {
+        job.status.validate_transition(next)?;
+        let attempts = if job.status == ReleaseJobStatus::Pending {
+            job.attempts + 1
+        } else {
+            job.attempts
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE cross_chain_release_jobs
+            SET status = $2,
+                remote_tx_hash = COALESCE($3, remote_tx_hash),
+                last_error = $4,
+                attempts = $5,
+                submitted_at = CASE WHEN $2 = 'submitted' THEN NOW() ELSE submitted_at END,
+                confirmed_at = CASE WHEN $2 = 'confirmed' THEN NOW() ELSE confirmed_at END,
+                updated_at = NOW()
+            WHERE id = $1 AND status = $6
+            "#,
+        )
+        .bind(job.id)
+        .bind(next.as_str())
+        .bind(remote_tx_hash)
+        .bind(error)
+        .bind(attempts)
+        .bind(job.status.as_str())
+        .execute(&self.db)
+        .await?;
+
+        info!(
+            "Cross-chain relayer: job {} {} → {}",
+            job.id, job.status, next
+        );
+        Ok(())
+    }
+
+    async fn report(&self, job: &ReleaseJob) -> Result<(), ApiError> {
+        self.reporter
+            .report_confirmation(job)
+            .await
+            .map_err(|e| ApiError::ExternalService(e.to_string()))?;
+
+        sqlx::query(
+            "UPDATE cross_chain_release_jobs SET reported_at = NOW(), updated_at = NOW() WHERE id = $1",
+        )
+        .bind(job.id)
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+
+    /// All release jobs for a contract plan, in manifest order.
+    pub async fn get_plan_jobs(
+        db: &PgPool,
+        contract_plan_id: i64,
+    ) -> Result<Vec<ReleaseJob>, ApiError> {
+        let rows = sqlx::query_as::<_, ReleaseJobRow>(&format!(
+            r#"
+            SELECT {JOB_COLUMNS}
+            FROM cross_chain_release_jobs
+            WHERE contract_plan_id = $1
+            ORDER BY asset_index
+            "#
+        ))
+        .bind(contract_plan_id)
+        .fetch_all(db)
+        .await?;
+
+        rows.into_iter().map(ReleaseJob::try_from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+    use stellar_xdr::curr::{
+        Limits, ScMap, ScMapEntry, ScString, ScSymbol, UInt128Parts, WriteXdr,
+    };
+
+    fn symbol(s: &str) -> ScVal {
+        ScVal::Symbol(ScSymbol(s.try_into().unwrap()))
+    }
+
+    fn encode(val: &ScVal) -> String {
+        BASE64.encode(val.to_xdr(Limits::none()).unwrap())
+    }
+
+    fn trigger_event(topic0: &str, topic1: &str, plan_id: u64) -> SorobanEvent {
+        let value = ScVal::Map(Some(ScMap(
+            vec![
+                ScMapEntry {
+                    key: symbol("outstanding_loans"),
+                    val: ScVal::U64(0),
+                },
+                ScMapEntry {
+                    key: symbol("plan_id"),
+                    val: ScVal::U64(plan_id),
+                },
+                ScMapEntry {
+                    key: symbol("triggered_at"),
+                    val: ScVal::U64(1_700_000_000),
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        )));
+        SorobanEvent {
+            event_type: "contract".to_string(),
+            ledger: 42,
+            ledger_closed_at: None,
+            contract_id: "CCONTRACT".to_string(),
+            id: "0000000042-0000000001".to_string(),
+            paging_token: Some("0000000042-0000000001".to_string()),
+            topic: vec![encode(&symbol(topic0)), encode(&symbol(topic1))],
+            value: encode(&value),
+            tx_hash: None,
+        }
+    }
+
+    fn job(chain: &str) -> ReleaseJob {
+        ReleaseJob {
+            id: Uuid::new_v4(),
+            plan_id: None,
+            contract_plan_id: 7,
+            asset_index: 1,
+            chain: chain.to_string(),
+            asset_symbol: "ETH".to_string(),
+            contract_address: None,
+            source_address: "0x0000000000000000000000000000000000000001".to_string(),
+            amount: "1000".to_string(),
+            status: ReleaseJobStatus::Pending,
+            remote_tx_hash: None,
+            attempts: 0,
+            last_error: None,
+            reported_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn parses_inheritance_trigger_event() {
+        let parsed = parse_trigger_event(&trigger_event("INHERIT", "TRIGGER", 9)).unwrap();
+        assert_eq!(parsed.contract_plan_id, 9);
+        assert_eq!(parsed.triggered_at, 1_700_000_000);
+        assert_eq!(parsed.ledger, 42);
+    }
+
+    #[test]
+    fn reads_contest_deadline_from_trigger_event() {
+        let mut event = trigger_event("INHERIT", "TRIGGER", 9);
+        assert_eq!(parse_trigger_event(&event).unwrap().contest_ends_at, 0);
+
+        let ScVal::Map(Some(map)) = decode_scval(&event.value).unwrap() else {
+            unreachable!()
+        };
+        let mut entries: Vec<ScMapEntry> = map.0.to_vec();
+        entries.insert(
+            0,
+            ScMapEntry {
+                key: symbol("contest_ends_at"),
+                val: ScVal::U64(1_700_000_600),
+            },
+        );
+        event.value = encode(&ScVal::Map(Some(ScMap(entries.try_into().unwrap()))));
+        assert_eq!(
+            parse_trigger_event(&event).unwrap().contest_ends_at,
+            1_700_000_600
+        );
+    }
+
+    #[test]
+    fn ignores_other_events() {
+        assert!(parse_trigger_event(&trigger_event("LOAN", "FREEZE", 9)).is_none());
+        let mut garbled = trigger_event("INHERIT", "TRIGGER", 9);
+        garbled.value = "not-xdr".to_string();
+        assert!(parse_trigger_event(&garbled).is_none());
+    }
+
+    fn bridge_release(asset_index: u32, status: &str) -> ScVal {
+        let entry = |key: &str, val: ScVal| ScMapEntry {
+            key: symbol(key),
+            val,
+        };
+        let variant = |name: &str| ScVal::Vec(Some(vec![symbol(name)].try_into().unwrap()));
+        ScVal::Map(Some(ScMap(
+            vec![
+                entry("amount", ScVal::U128(UInt128Parts { hi: 1, lo: 5 })),
+                entry("asset_index", ScVal::U32(asset_index)),
+                entry(
+                    "asset_symbol",
+                    ScVal::String(ScString("USDC".try_into().unwrap())),
+                ),
+                entry("chain", variant("Ethereum")),
+                entry("status", variant(status)),
+            ]
+            .try_into()
+            .unwrap(),
+        )))
+    }
+
+    #[test]
+    fn parses_bridge_release_under_its_manifest_index() {
+        let release = parse_bridge_release(&bridge_release(3, "Pending")).unwrap();
+        assert_eq!(release.asset_index, 3);
+        assert_eq!(release.chain, "ethereum");
+        assert_eq!(release.asset_symbol, "USDC");
+        assert_eq!(release.contract_address, None);
+        assert_eq!(release.amount, (1u128 << 64) + 5);
+        assert!(!release.confirmed);
+
+        assert!(
+            parse_bridge_release(&bridge_release(3, "Confirmed"))
+                .unwrap()
+                .confirmed
+        );
+        assert!(parse_bridge_release(&ScVal::U32(3)).is_none());
+    }
+
+    #[test]
+    fn status_transitions() {
+        use ReleaseJobStatus::*;
+        assert!(Pending.validate_transition(Submitted).is_ok());
+        assert!(Pending.validate_transition(Pending).is_ok());
+        assert!(Submitted.validate_transition(Confirmed).is_ok());
+        assert!(Submitted.validate_transition(Failed).is_ok());
+        assert!(Confirmed.validate_transition(Pending).is_err());
+        assert!(Failed.validate_transition(Submitted).is_err());
+        assert_eq!("SUBMITTED".parse::<ReleaseJobStatus>().unwrap(), Submitted);
+        assert!("bogus".parse::<ReleaseJobStatus>().is_err());
+    }
+
+    #[tokio::test]
+    async fn mock_executor_tracks_submissions() {
+        let executor = MockReleaseExecutor::new();
+        let job = job("ethereum");
+        let hash = executor.submit_release(&job).await.unwrap();
+        assert_eq!(
+            executor.release_status(&job, &hash).await.unwrap(),
+            RemoteReleaseStatus::Pending
+        );
+
+        executor.settle(&hash, RemoteReleaseStatus::Confirmed);
+        assert_eq!(
+            executor.release_status(&job, &hash).await.unwrap(),
+            RemoteReleaseStatus::Confirmed
+        );
+        assert_eq!(executor.submitted(), vec![hash]);
+
+        assert!(MockReleaseExecutor::failing()
+            .submit_release(&job)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn mock_reporter_records_confirmations() {
+        let reporter = MockConfirmationReporter::new();
+        let mut job = job("bitcoin");
+        job.remote_tx_hash = Some("btc-tx".to_string());
+        reporter.report_confirmation(&job).await.unwrap();
+        assert_eq!(reporter.reported(), vec![(7, 1, "btc-tx".to_string())]);
+    }
+}
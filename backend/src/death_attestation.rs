@@ -0,0 +1,335 @@
+//! # Death-Certificate Attestation
+//!
+//! Registrars, notaries and hospitals registered by an admin act as death
+//! attestors. An attestor uploads the certificate for a plan, which is
+//! encrypted at rest by [`DocumentStorageService`]; the returned SHA-256 hash
+//! is what the attestor then submits to the contract's
+//! `submit_death_attestation`. Once enough attestors agree on the same hash
+//! and the challenge window passes without a veto, the plan's oracle trigger
+//! condition is met on-chain.
+
+use crate::api_error::ApiError;
+use crate::document_storage::{
+    hashes_match, sha256_hex, AttestationDocument, DocumentStorageService,
+};
+use crate::notifications::{audit_action, entity_type, AuditLogService};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Largest certificate accepted, in bytes (decoded).
+pub const MAX_CERTIFICATE_BYTES: usize = 10 * 1024 * 1024;
+
+/// Mirrors the contract's `AttestorKind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttestorKind {
+    Registrar,
+    Notary,
+    Hospital,
+}
+
+impl AttestorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttestorKind::Registrar => "registrar",
+            AttestorKind::Notary => "notary",
+            AttestorKind::Hospital => "hospital",
+        }
+    }
+}
+
+impl fmt::Display for AttestorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AttestorKind {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "registrar" => Ok(AttestorKind::Registrar),
+            "notary" => Ok(AttestorKind::Notary),
+            "hospital" => Ok(AttestorKind::Hospital),
+            other => Err(ApiError::Internal(anyhow::anyhow!(
+                "Unknown attestor kind: {other}"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeathAttestor {
+    pub user_id: Uuid,
+    pub kind: AttestorKind,
+    pub registered_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct DeathAttestorRow {
+    user_id: Uuid,
+    kind: String,
+    registered_by: Uuid,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<DeathAttestorRow> for DeathAttestor {
+    type Error = ApiError;
+
+    fn try_from(row: DeathAttestorRow) -> Result<Self, Self::Error> {
+        Ok(DeathAttestor {
+            user_id: row.user_id,
+            kind: row.kind.parse()?,
+            registered_by: row.registered_by,
+            created_at: row.created_at,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterAttestorRequest {
+    pub user_id: Uuid,
+    pub kind: AttestorKind,
+}
+
+/// Check an uploaded certificate against the attestor's claimed hash.
+/// Returns the certificate's hex SHA-256.
+pub fn check_certificate(bytes: &[u8], claimed_hash: Option<&str>) -> Result<String, ApiError> {
+    if bytes.is_empty() {
+        return Err(ApiError::BadRequest("Certificate is empty".to_string()));
+    }
+    if bytes.len() > MAX_CERTIFICATE_BYTES {
+        return Err(ApiError::PayloadTooLarge(format!(
+            "Certificate exceeds {MAX_CERTIFICATE_BYTES} bytes"
+        )));
+    }
+    let hash = sha256_hex(bytes);
+    if let Some(claimed) = claimed_hash {
+        if !hashes_match(claimed, &hash) {
+            return Err(ApiError::BadRequest(format!(
+                "Certificate hash {hash} does not match claimed hash {claimed}"
+            )));
+        }
+    }
+    Ok(hash)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyCertificateRequest {
+    pub document_hash: String,
+}
+
+pub struct DeathAttestationService;
+
+impl DeathAttestationService {
+    /// Register (or re-classify) a user as a death attestor.
+    pub async fn register_attestor(
+        db: &PgPool,
+        admin_id: Uuid,
+        req: &RegisterAttestorRequest,
+    ) -> Result<DeathAttestor, ApiError> {
+        let mut tx = db.begin().await?;
+        let row = sqlx::query_as::<_, DeathAttestorRow>(
+            r#"
+            INSERT INTO death_attestors (user_id, kind, registered_by)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id) DO UPDATE
+                SET kind = EXCLUDED.kind, registered_by = EXCLUDED.registered_by
+            RETURNING user_id, kind, registered_by, created_at
+            "#,
+        )
+        .bind(req.user_id)
+        .bind(req.kind.as_str())
+        .bind(admin_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        AuditLogService::log(
+            &mut *tx,
+            Some(req.user_id),
+            Some(admin_id),
+            audit_action::ATTESTOR_REGISTERED,
+            Some(req.user_id),
+            Some(entity_type::USER),
+            None,
+            Some(req.kind.as_str()),
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+
+        row.try_into()
+    }
+
+    pub async fn remove_attestor(
+        db: &PgPool,
+        admin_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), ApiError> {
+        let mut tx = db.begin().await?;
+        let removed = sqlx::query("DELETE FROM death_attestors WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        if removed == 0 {
+            return Err(ApiError::NotFound(format!("Attestor {user_id} not found")));
+        }
+
+        AuditLogService::log(
+            &mut *tx,
+            Some(user_id),
+            Some(admin_id),
+            audit_action::ATTESTOR_REMOVED,
+            Some(user_id),
+            Some(entity_type::USER),
+            None,
+            None,
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn list_attestors(db: &PgPool) -> Result<Vec<DeathAttestor>, ApiError> {
+        let rows = sqlx::query_as::<_, DeathAttestorRow>(
+            "SELECT user_id, kind, registered_by, created_at \
+             FROM death_attestors ORDER BY created_at",
+        )
+        .fetch_all(db)
+        .await?;
+        rows.into_iter().map(DeathAttestor::try_from).collect()
+    }
+
+    async fn require_attestor(db: &PgPool, user_id: Uuid) -> Result<DeathAttestor, ApiError> {
+        let row = sqlx::query_as::<_, DeathAttestorRow>(
+            "SELECT user_id, kind, registered_by, created_at \
+             FROM death_attestors WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("Not a registered death attestor".to_string()))?;
+        row.try_into()
+    }
+
+    /// Store an attestor's death certificate for a plan, rejecting it if it
+    /// does not hash to `claimed_hash`. The returned `document_hash` is the
+    /// value to submit on-chain.
+    pub async fn upload_certificate(
+        db: &PgPool,
+        attestor_user_id: Uuid,
+        plan_id: Uuid,
+        certificate: &[u8],
+        claimed_hash: Option<&str>,
+    ) -> Result<AttestationDocument, ApiError> {
+        let attestor = Self::require_attestor(db, attestor_user_id).await?;
+        let hash = check_certificate(certificate, claimed_hash)?;
+
+        let plan_exists: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM plans WHERE id = $1)")
+                .bind(plan_id)
+                .fetch_one(db)
+                .await?;
+        if !plan_exists {
+            return Err(ApiError::NotFound(format!("Plan {plan_id} not found")));
+        }
+
+        let doc = DocumentStorageService::store_attestation_certificate(
+            db,
+            attestor_user_id,
+            plan_id,
+            certificate,
+        )
+        .await?;
+
+        AuditLogService::log(
+            db,
+            Some(attestor_user_id),
+            None,
+            audit_action::DEATH_CERTIFICATE_UPLOADED,
+            Some(plan_id),
+            Some(entity_type::PLAN),
+            None,
+            Some(&hash),
+            Some(serde_json::json!({
+                "document_id": doc.id,
+                "attestor_kind": attestor.kind,
+            })),
+        )
+        .await?;
+
+        Ok(doc)
+    }
+
+    /// Whether a stored certificate's content hashes to `document_hash`.
+    pub async fn verify_certificate(
+        db: &PgPool,
+        document_id: Uuid,
+        document_hash: &str,
+    ) -> Result<bool, ApiError> {
+        DocumentStorageService::verify_attestation_certificate(db, document_id, document_hash).await
+    }
+
+    pub async fn list_plan_certificates(
+        db: &PgPool,
+        plan_id: Uuid,
+    ) -> Result<Vec<AttestationDocument>, ApiError> {
+        DocumentStorageService::list_attestation_certificates(db, plan_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attestor_kind_round_trips() {
+        for kind in [
+            AttestorKind::Registrar,
+            AttestorKind::Notary,
+            AttestorKind::Hospital,
+        ] {
+            assert_eq!(kind.as_str().parse::<AttestorKind>().unwrap(), kind);
+        }
+        assert!("coroner".parse::<AttestorKind>().is_err());
+    }
+
+    #[test]
+    fn certificate_with_matching_hash_is_accepted() {
+        let content = b"certificate of death";
+        let claimed = sha256_hex(content).to_uppercase();
+        assert_eq!(
+            check_certificate(content, Some(&claimed)).unwrap(),
+            sha256_hex(content)
+        );
+        assert_eq!(
+            check_certificate(content, None).unwrap(),
+            sha256_hex(content)
+        );
+    }
+
+    #[test]
+    fn certificate_with_mismatched_hash_is_rejected() {
+        let forged = sha256_hex(b"forged");
+        assert!(matches!(
+            check_certificate(b"certificate of death", Some(&forged)),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn empty_certificate_is_rejected() {
+        assert!(matches!(
+            check_certificate(b"", None),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+}
@@ -1,7 +1,9 @@
 //! Encrypted document storage with backup support.
 //!
 //! Provides AES-256-GCM encryption for will documents at rest,
-//! per-user access control, and a backup mechanism.
+//! per-user access control, and a backup mechanism. Death certificates
+//! uploaded by attestors are stored the same way, alongside the SHA-256 hash
+//! that attestors submit on-chain.
 
 use crate::api_error::ApiError;
 use chrono::{DateTime, Utc};
@@ -34,13 +36,16 @@ fn derive_key(secret: &[u8]) -> Result<LessSafeKey, ApiError> {
     Ok(LessSafeKey::new(unbound))
 }
 
-fn load_encryption_secret() -> Vec<u8> {
+pub(crate) fn load_encryption_secret() -> Vec<u8> {
     std::env::var("DOCUMENT_ENCRYPTION_KEY")
         .unwrap_or_default()
         .into_bytes()
 }
 
-fn encrypt_bytes(plaintext: &[u8], secret: &[u8]) -> Result<(Vec<u8>, Vec<u8>), ApiError> {
+pub(crate) fn encrypt_bytes(
+    plaintext: &[u8],
+    secret: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), ApiError> {
     let key = derive_key(secret)?;
     let rng = SystemRandom::new();
     let mut nonce_bytes = [0u8; NONCE_LEN];
@@ -55,7 +60,7 @@ fn encrypt_bytes(plaintext: &[u8], secret: &[u8]) -> Result<(Vec<u8>, Vec<u8>),
     Ok((in_out, nonce_bytes.to_vec()))
 }
 
-fn decrypt_bytes(
+pub(crate) fn decrypt_bytes(
     ciphertext: &[u8],
     nonce_bytes: &[u8],
     secret: &[u8],
@@ -75,6 +80,22 @@ fn decrypt_bytes(
     Ok(plaintext.to_vec())
 }
 
+/// Lowercase hex SHA-256 of `bytes`, as submitted on-chain by attestors.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(digest(&SHA256, bytes).as_ref())
+}
+
+/// Compare a claimed document hash against a computed one, ignoring case and
+/// an optional `0x` prefix.
+pub fn hashes_match(claimed: &str, actual: &str) -> bool {
+    let claimed = claimed.trim();
+    let claimed = claimed
+        .strip_prefix("0x")
+        .or_else(|| claimed.strip_prefix("0X"))
+        .unwrap_or(claimed);
+    claimed.eq_ignore_ascii_case(actual)
+}
+
 // ---------------------------------------------------------------------------
 // Data types
 // ---------------------------------------------------------------------------
@@ -88,6 +109,16 @@ pub struct BackupRecord {
     pub created_at: DateTime<Utc>,
 }
 
+/// An encrypted death certificate uploaded by an attestor.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AttestationDocument {
+    pub id: Uuid,
+    pub plan_id: Uuid,
+    pub attestor_user_id: Uuid,
+    pub document_hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
 // ---------------------------------------------------------------------------
 // Service
 // ---------------------------------------------------------------------------
@@ -305,6 +336,92 @@ impl DocumentStorageService {
             })
             .collect())
     }
+
+    /// Encrypt and store a death certificate for a plan, recording the
+    /// SHA-256 hash of the plaintext.
+    pub async fn store_attestation_certificate(
+        db: &PgPool,
+        attestor_user_id: Uuid,
+        plan_id: Uuid,
+        content_bytes: &[u8],
+    ) -> Result<AttestationDocument, ApiError> {
+        let secret = load_encryption_secret();
+        if secret.is_empty() {
+            return Err(ApiError::Internal(anyhow::anyhow!(
+                "DOCUMENT_ENCRYPTION_KEY is not configured"
+            )));
+        }
+
+        let document_hash = sha256_hex(content_bytes);
+        let (ciphertext, nonce) = encrypt_bytes(content_bytes, &secret)?;
+
+        let doc = sqlx::query_as::<_, AttestationDocument>(
+            "INSERT INTO attestation_documents \
+             (plan_id, attestor_user_id, document_hash, encrypted_content, encryption_nonce) \
+             VALUES ($1, $2, $3, $4, $5) \
+             RETURNING id, plan_id, attestor_user_id, document_hash, created_at",
+        )
+        .bind(plan_id)
+        .bind(attestor_user_id)
+        .bind(&document_hash)
+        .bind(&ciphertext)
+        .bind(&nonce)
+        .fetch_one(db)
+        .await?;
+
+        Ok(doc)
+    }
+
+    /// Decrypt a stored death certificate and check that both its recorded
+    /// hash and its actual content match `claimed_hash`.
+    pub async fn verify_attestation_certificate(
+        db: &PgPool,
+        document_id: Uuid,
+        claimed_hash: &str,
+    ) -> Result<bool, ApiError> {
+        #[derive(sqlx::FromRow)]
+        struct Row {
+            document_hash: String,
+            encrypted_content: Vec<u8>,
+            encryption_nonce: Vec<u8>,
+        }
+
+        let row = sqlx::query_as::<_, Row>(
+            "SELECT document_hash, encrypted_content, encryption_nonce \
+             FROM attestation_documents WHERE id = $1",
+        )
+        .bind(document_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Document {document_id} not found")))?;
+
+        let secret = load_encryption_secret();
+        if secret.is_empty() {
+            return Err(ApiError::Internal(anyhow::anyhow!(
+                "DOCUMENT_ENCRYPTION_KEY is not configured"
+            )));
+        }
+
+        let plaintext = decrypt_bytes(&row.encrypted_content, &row.encryption_nonce, &secret)?;
+        let actual = sha256_hex(&plaintext);
+        Ok(actual == row.document_hash && hashes_match(claimed_hash, &actual))
+    }
+
+    /// List the death certificates uploaded for a plan, newest first.
+    pub async fn list_attestation_certificates(
+        db: &PgPool,
+        plan_id: Uuid,
+    ) -> Result<Vec<AttestationDocument>, ApiError> {
+        let docs = sqlx::query_as::<_, AttestationDocument>(
+            "SELECT id, plan_id, attestor_user_id, document_hash, created_at \
+             FROM attestation_documents WHERE plan_id = $1 \
+             ORDER BY created_at DESC",
+        )
+        .bind(plan_id)
+        .fetch_all(db)
+        .await?;
+        Ok(docs)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -398,4 +515,21 @@ mod tests {
 
         assert_eq!(buf1, buf2);
     }
+
+    #[test]
+    fn test_sha256_hex_matches_known_digest() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_hashes_match_ignores_case_and_prefix() {
+        let actual = sha256_hex(b"certificate");
+        assert!(hashes_match(&actual.to_uppercase(), &actual));
+        assert!(hashes_match(&format!("0x{actual}"), &actual));
+        assert!(!hashes_match(&sha256_hex(b"forged"), &actual));
+        assert!(!hashes_match("", &actual));
+    }
 }
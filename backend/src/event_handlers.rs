@@ -131,8 +131,9 @@ fn parse_event_type(s: &str) -> Result<EventType, ApiError> {
         "repay" => Ok(EventType::Repay),
         "liquidation" => Ok(EventType::Liquidation),
         "interest_accrual" => Ok(EventType::InterestAccrual),
+        "tranche_claimed" => Ok(EventType::TrancheClaimed),
         _ => Err(ApiError::BadRequest(format!(
-            "Invalid event type: {s}. Valid types: deposit, borrow, repay, liquidation, interest_accrual"
+            "Invalid event type: {s}. Valid types: deposit, borrow, repay, liquidation, interest_accrual, tranche_claimed"
         ))),
     }
 }
@@ -163,6 +164,10 @@ mod tests {
             parse_event_type("interest_accrual").unwrap(),
             EventType::InterestAccrual
         ));
+        assert!(matches!(
+            parse_event_type("tranche_claimed").unwrap(),
+            EventType::TrancheClaimed
+        ));
         assert!(parse_event_type("invalid").is_err());
     }
 
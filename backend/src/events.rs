@@ -14,6 +14,8 @@ pub enum EventType {
     Liquidation,
     #[sqlx(rename = "interest_accrual")]
     InterestAccrual,
+    #[sqlx(rename = "tranche_claimed")]
+    TrancheClaimed,
 }
 
 /// Lending event record
@@ -105,6 +107,10 @@ pub struct LiquidationMetadata {
     pub collateral_seized: rust_decimal::Decimal,
     pub debt_covered: rust_decimal::Decimal,
     pub liquidation_penalty: rust_decimal::Decimal,
+    /// Debt still outstanding after this liquidation; zero once the loan is
+    /// fully liquidated.
+    #[serde(default)]
+    pub remaining_debt: rust_decimal::Decimal,
 }
 
 /// Metadata for interest accrual events
@@ -116,6 +122,16 @@ pub struct InterestAccrualMetadata {
     pub total_balance: rust_decimal::Decimal,
 }
 
+/// Metadata for periodic payout (tranche) claims, mirroring the contract's
+/// `TRANCHE/CLAIMED` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrancheClaimedMetadata {
+    pub beneficiary_index: u32,
+    pub tranches_claimed: u32,
+    pub tranche_count: u32,
+    pub claimed_to_date: rust_decimal::Decimal,
+}
+
 /// Parameters for emitting an event
 struct EmitEventParams<'a> {
     event_type: EventType,
@@ -292,6 +308,38 @@ impl EventService {
         .await
     }
 
+    /// Emit a tranche claimed event
+    #[allow(clippy::too_many_arguments)]
+    pub async fn emit_tranche_claimed(
+        tx: &mut Transaction<'_, Postgres>,
+        user_id: Uuid,
+        plan_id: Option<Uuid>,
+        asset_code: &str,
+        amount: rust_decimal::Decimal,
+        metadata: TrancheClaimedMetadata,
+        transaction_hash: Option<String>,
+        block_number: Option<i64>,
+    ) -> Result<LendingEvent, ApiError> {
+        let metadata_json = serde_json::to_value(metadata).map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Failed to serialize metadata: {e}"))
+        })?;
+
+        Self::emit_event(
+            tx,
+            EmitEventParams {
+                event_type: EventType::TrancheClaimed,
+                user_id,
+                plan_id,
+                asset_code,
+                amount,
+                metadata: metadata_json,
+                transaction_hash,
+                block_number,
+            },
+        )
+        .await
+    }
+
     /// Internal method to emit any event type
     async fn emit_event(
         tx: &mut Transaction<'_, Postgres>,
@@ -505,4 +553,18 @@ mod tests {
         assert!(json.is_object());
         assert_eq!(json["principal_amount"], "500.00");
     }
+
+    #[test]
+    fn test_tranche_claimed_metadata_serialization() {
+        let metadata = TrancheClaimedMetadata {
+            beneficiary_index: 0,
+            tranches_claimed: 3,
+            tranche_count: 12,
+            claimed_to_date: dec!(1470.00),
+        };
+
+        let json = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(json["tranches_claimed"], 3);
+        assert_eq!(json["claimed_to_date"], "1470.00");
+    }
 }
@@ -4,17 +4,24 @@ pub mod api_error;
 pub mod api_versioning;
 pub mod app;
 pub mod auth;
+pub mod bank_account_vault;
 pub mod beneficiary_sync;
 pub mod cache;
+pub mod chain_reconciliation;
+pub mod check_in;
 pub mod circuit_breaker;
 pub mod collateral_management;
 pub mod compliance;
 pub mod config;
+pub mod contest_window;
 pub mod contingent_beneficiary;
+pub mod contract_indexer;
 pub mod cross_chain_asset_discovery;
+pub mod cross_chain_relayer;
 pub mod csrf;
 pub mod data_retention;
 pub mod db;
+pub mod death_attestation;
 pub mod document_storage;
 pub mod document_verification;
 pub mod emergency_access;
@@ -48,11 +55,14 @@ pub mod risk_engine;
 pub mod safe_math;
 pub mod secrets;
 pub mod secure_messages;
+pub mod sep10;
 pub mod service;
 pub mod session;
 pub mod stellar;
 pub mod stress_testing;
 pub mod telemetry;
+pub mod trigger_expression;
+pub mod two_factor;
 pub mod validation;
 pub mod webhook;
 pub mod will_audit;
@@ -66,14 +76,25 @@ pub mod yield_service;
 
 pub use api_error::ApiError;
 pub use app::create_app;
+pub use chain_reconciliation::{ChainReconciler, Discrepancy, ReconciliationReport, Severity};
+pub use check_in::{
+    ActivityRecorder, CheckInService, MockActivityRecorder, SorobanActivityRecorder,
+};
 pub use circuit_breaker::CircuitBreaker;
 pub use compliance::ComplianceEngine;
 pub use config::Config;
+pub use contest_window::{ContestWindowService, ContestWindowStatus};
+pub use contract_indexer::{ChainEvent, ContractEventIndexer, ContractKind, IndexedEvent};
 pub use cross_chain_asset_discovery::{
     ArbitrumClient, Asset, BitcoinClient, CrossChainAsset, CrossChainAssetDiscoveryService,
     EthereumClient, PolygonClient, ServiceError,
 };
+pub use cross_chain_relayer::{
+    ConfirmationReporter, CrossChainRelayerService, MockConfirmationReporter, MockReleaseExecutor,
+    ReleaseExecutor, ReleaseJob, ReleaseJobStatus, SorobanConfirmationReporter,
+};
 pub use data_retention::DataRetentionService;
+pub use death_attestation::{AttestorKind, DeathAttestationService};
 pub use events::{EventService, EventType, LendingEvent};
 pub use fitbit_integration::{
     ActivityAnalyzer, FitbitIntegrationService, FitbitWebAPIClient, HeartRateAnalyzer,
@@ -95,9 +116,12 @@ pub use safe_math::SafeMath;
 pub use secure_messages::{
     LegacyMessageDeliveryService, MessageEncryptionService, MessageKeyService,
 };
+pub use sep10::{Sep10Claims, Sep10Config, Sep10Service};
 pub use stellar::{
-    HorizonClient, SorobanRpcClient, StellarClient, StellarConfig, StellarHealthStatus,
-    TransactionMonitor, TransactionStatus,
+    ContractCall, ContractInvoker, ExternalSigner, HorizonClient, InMemorySigner, SequenceManager,
+    SignedTransaction, SorobanRpcClient, SorobanTransaction, StellarClient, StellarConfig,
+    StellarHealthStatus, TransactionBuilder, TransactionMonitor, TransactionSigner,
+    TransactionStatus,
 };
 pub use stress_testing::StressTestingEngine;
 pub use webhook::{event_types, WebhookService};
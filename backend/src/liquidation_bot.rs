@@ -13,13 +13,47 @@ use uuid::Uuid;
 pub struct LiquidationBotService {
     db: PgPool,
     liquidation_penalty_rate: Decimal, // e.g., 0.05 for 5% penalty
+    close_factor: Decimal,             // e.g., 0.5 to cover at most half the debt per run
+}
+
+/// One incremental liquidation of a loan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiquidationStep {
+    pub debt_to_cover: Decimal,
+    pub penalty_amount: Decimal,
+    pub collateral_seized: Decimal,
+    pub remaining_debt: Decimal,
+}
+
+/// Cover at most `close_factor` of `total_debt`, seizing the covered debt
+/// plus penalty from what is left of the collateral (1:1 pricing, as in the
+/// rest of the bot).
+pub fn plan_liquidation_step(
+    total_debt: Decimal,
+    collateral_available: Decimal,
+    penalty_rate: Decimal,
+    close_factor: Decimal,
+) -> LiquidationStep {
+    let close_factor = close_factor.clamp(Decimal::ZERO, Decimal::ONE);
+    let debt_to_cover = (total_debt * close_factor).min(total_debt);
+    let penalty_amount = debt_to_cover * penalty_rate;
+    let collateral_seized = (debt_to_cover + penalty_amount)
+        .min(collateral_available)
+        .max(Decimal::ZERO);
+    LiquidationStep {
+        debt_to_cover,
+        penalty_amount,
+        collateral_seized,
+        remaining_debt: total_debt - debt_to_cover,
+    }
 }
 
 impl LiquidationBotService {
-    pub fn new(db: PgPool, liquidation_penalty_rate: Decimal) -> Self {
+    pub fn new(db: PgPool, liquidation_penalty_rate: Decimal, close_factor: Decimal) -> Self {
         Self {
             db,
             liquidation_penalty_rate,
+            close_factor,
         }
     }
 
@@ -50,6 +84,7 @@ impl LiquidationBotService {
             total_debt: Decimal,
             collateral_asset: Option<String>,
             collateral_amount: Option<Decimal>,
+            collateral_seized: Decimal,
         }
 
         // Find plans where is_risky = true and not yet liquidated
@@ -59,12 +94,14 @@ impl LiquidationBotService {
                 SELECT plan_id, user_id, asset_code AS borrow_asset,
                        SUM(CASE WHEN event_type = 'borrow' THEN CAST(amount AS numeric) ELSE 0 END) -
                        SUM(CASE WHEN event_type = 'repay' THEN CAST(amount AS numeric) ELSE 0 END) -
-                       SUM(CASE WHEN event_type = 'liquidation' THEN CAST(amount AS numeric) ELSE 0 END) AS total_debt
+                       SUM(CASE WHEN event_type = 'liquidation' THEN CAST(amount AS numeric) ELSE 0 END) AS total_debt,
+                       COALESCE(SUM(CASE WHEN event_type = 'liquidation'
+                                         THEN CAST(metadata->>'collateral_seized' AS numeric) ELSE 0 END), 0) AS collateral_seized
                 FROM lending_events
                 WHERE plan_id IS NOT NULL
                 GROUP BY plan_id, user_id, asset_code
             )
-            SELECT lb.plan_id, lb.user_id, lb.borrow_asset, lb.total_debt,
+            SELECT lb.plan_id, lb.user_id, lb.borrow_asset, lb.total_debt, lb.collateral_seized,
                    p.asset_code as collateral_asset, CAST(p.net_amount AS numeric) as collateral_amount
             FROM loan_balances lb
             JOIN plans p ON p.id = lb.plan_id
@@ -80,22 +117,32 @@ impl LiquidationBotService {
 
         for loan in risky_loans {
             let collat_asset = loan.collateral_asset.unwrap_or_else(|| "USDC".to_string());
-            let collat_amount = loan.collateral_amount.unwrap_or(Decimal::ZERO);
-
-            // Assume we liquidate the entire debt for simplicity.
-            let debt_to_cover = loan.total_debt;
-            // A realistic liquidation grabs debt + penalty from the collateral.
-            let penalty_amount = debt_to_cover * self.liquidation_penalty_rate;
-            // Since we assume simple 1:1 price for simplicity in this mock, collateral seized is debt_to_cover + penalty.
-            // In a real system, we'd use prices to convert debt_to_cover to collateral token amounts.
-            let mut collateral_seized = debt_to_cover + penalty_amount;
-            if collateral_seized > collat_amount {
-                collateral_seized = collat_amount; // Cap at max collateral available
-            }
+            // Collateral left after earlier partial liquidations of this loan.
+            let collat_amount =
+                loan.collateral_amount.unwrap_or(Decimal::ZERO) - loan.collateral_seized;
+
+            // Each run covers at most the close factor of the debt; the loan is
+            // picked up again on the next tick while debt remains.
+            let LiquidationStep {
+                debt_to_cover,
+                penalty_amount,
+                collateral_seized,
+                remaining_debt,
+            } = plan_liquidation_step(
+                loan.total_debt,
+                collat_amount,
+                self.liquidation_penalty_rate,
+                self.close_factor,
+            );
 
             info!(
-                "Triggering auto-liquidation for Plan {}. Seizing {} {} to cover {} {} debt.",
-                loan.plan_id, collateral_seized, collat_asset, debt_to_cover, loan.borrow_asset
+                "Triggering auto-liquidation for Plan {}. Seizing {} {} to cover {} {} debt ({} remaining).",
+                loan.plan_id,
+                collateral_seized,
+                collat_asset,
+                debt_to_cover,
+                loan.borrow_asset,
+                remaining_debt
             );
 
             let mut tx = self
@@ -111,6 +158,7 @@ impl LiquidationBotService {
                 collateral_seized,
                 debt_covered: debt_to_cover,
                 liquidation_penalty: penalty_amount,
+                remaining_debt,
             };
 
             EventService::emit_liquidation(
@@ -125,20 +173,22 @@ impl LiquidationBotService {
             )
             .await?;
 
-            // 2. Mark Plan as Liquidated
-            sqlx::query(
-                r#"
-                UPDATE plans
-                SET status = 'liquidated'
-                WHERE id = $1
-                "#,
-            )
-            .bind(loan.plan_id)
-            .execute(&mut *tx)
-            .await
-            .map_err(|e| {
-                ApiError::Internal(anyhow::anyhow!("DB error updating plan status: {}", e))
-            })?;
+            // 2. Mark Plan as Liquidated once no debt remains
+            if remaining_debt <= Decimal::ZERO {
+                sqlx::query(
+                    r#"
+                    UPDATE plans
+                    SET status = 'liquidated'
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(loan.plan_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| {
+                    ApiError::Internal(anyhow::anyhow!("DB error updating plan status: {}", e))
+                })?;
+            }
 
             // 3. Notify User
             #[allow(clippy::explicit_auto_deref)]
@@ -147,8 +197,9 @@ impl LiquidationBotService {
                 loan.user_id,
                 notif_type::LIQUIDATION_WARNING, // Using LIQUIDATION_WARNING as a fallback if notif_type::LIQUIDATED doesn't exist
                 format!(
-                    "Your loan against plan {} has been liquidated. {} {} debt was covered by seizing {} {}.",
-                    loan.plan_id, debt_to_cover, loan.borrow_asset, collateral_seized, collat_asset
+                    "Your loan against plan {} has been liquidated. {} {} debt was covered by seizing {} {}; {} {} remains outstanding.",
+                    loan.plan_id, debt_to_cover, loan.borrow_asset, collateral_seized, collat_asset,
+                    remaining_debt, loan.borrow_asset
                 ),
             ).await?;
 
@@ -180,3 +231,26 @@ impl LiquidationBotService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn step_is_capped_by_close_factor() {
+        let step = plan_liquidation_step(dec!(1000), dec!(5000), dec!(0.05), dec!(0.5));
+        assert_eq!(step.debt_to_cover, dec!(500));
+        assert_eq!(step.penalty_amount, dec!(25));
+        assert_eq!(step.collateral_seized, dec!(525));
+        assert_eq!(step.remaining_debt, dec!(500));
+    }
+
+    #[test]
+    fn step_seizes_no_more_than_remaining_collateral() {
+        let step = plan_liquidation_step(dec!(1000), dec!(300), dec!(0.05), dec!(1));
+        assert_eq!(step.debt_to_cover, dec!(1000));
+        assert_eq!(step.collateral_seized, dec!(300));
+        assert_eq!(step.remaining_debt, dec!(0));
+    }
+}
@@ -127,6 +127,50 @@ impl FromStr for LoanStatus {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Rate mode enum
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// How a loan's interest rate is set, mirroring the lending contract.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RateMode {
+    /// Follows the pool's borrow rate as utilization moves.
+    #[default]
+    Variable,
+    /// Locked when the loan is opened, at the pool's rate plus a premium.
+    Fixed,
+}
+
+impl RateMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RateMode::Variable => "variable",
+            RateMode::Fixed => "fixed",
+        }
+    }
+}
+
+impl fmt::Display for RateMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for RateMode {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "variable" => Ok(RateMode::Variable),
+            "fixed" => Ok(RateMode::Fixed),
+            other => Err(ApiError::BadRequest(format!(
+                "unknown rate mode '{other}'; valid values: variable, fixed"
+            ))),
+        }
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // DB row / public record types
 // ─────────────────────────────────────────────────────────────────────────────
@@ -142,6 +186,8 @@ pub struct LoanLifecycleRecord {
     pub collateral_asset: String,
     pub principal: Decimal,
     pub interest_rate_bps: i32,
+    /// `variable` or `fixed`; see [`RateMode`].
+    pub rate_mode: String,
     pub collateral_amount: Decimal,
     pub amount_repaid: Decimal,
     pub status: String,
@@ -176,6 +222,7 @@ pub(crate) struct LoanLifecycleRow {
     pub collateral_asset: String,
     pub principal: Decimal,
     pub interest_rate_bps: i32,
+    pub rate_mode: String,
     pub collateral_amount: Decimal,
     pub amount_repaid: Decimal,
     pub status: String,
@@ -196,6 +243,7 @@ pub(crate) struct LoanLifecycleRowWithPlan {
     pub collateral_asset: String,
     pub principal: Decimal,
     pub interest_rate_bps: i32,
+    pub rate_mode: String,
     pub collateral_amount: Decimal,
     pub amount_repaid: Decimal,
     pub status: String,
@@ -220,6 +268,7 @@ impl From<LoanLifecycleRow> for LoanLifecycleRecord {
             collateral_asset: r.collateral_asset,
             principal: r.principal,
             interest_rate_bps: r.interest_rate_bps,
+            rate_mode: r.rate_mode,
             collateral_amount: r.collateral_amount,
             amount_repaid: r.amount_repaid,
             status: r.status,
@@ -251,6 +300,7 @@ impl From<LoanLifecycleRowWithPlan> for LoanLifecycleRecord {
             collateral_asset: r.collateral_asset,
             principal: r.principal,
             interest_rate_bps: r.interest_rate_bps,
+            rate_mode: r.rate_mode,
             collateral_amount: r.collateral_amount,
             amount_repaid: r.amount_repaid,
             status: r.status,
@@ -281,6 +331,9 @@ pub struct CreateLoanRequest {
     pub principal: Decimal,
     /// Annual interest rate expressed in basis-points (e.g. 800 = 8 %).
     pub interest_rate_bps: i32,
+    /// Whether the rate follows the pool or is locked; defaults to variable.
+    #[serde(default)]
+    pub rate_mode: RateMode,
     pub collateral_amount: Decimal,
     /// ISO-8601 datetime when the loan is due.
     pub due_date: DateTime<Utc>,
@@ -333,7 +386,7 @@ impl LoanLifecycleService {
         let row = sqlx::query_as::<_, LoanLifecycleRowWithPlan>(
             r#"
             SELECT ll.id, ll.user_id, ll.plan_id, ll.borrow_asset, ll.collateral_asset,
-                   ll.principal, ll.interest_rate_bps, ll.collateral_amount, ll.amount_repaid,
+                   ll.principal, ll.interest_rate_bps, ll.rate_mode, ll.collateral_amount, ll.amount_repaid,
                    ll.status, ll.due_date, ll.transaction_hash,
                    ll.created_at, ll.updated_at, ll.repaid_at, ll.liquidated_at,
                    p.title AS plan_title, p.status AS plan_status, p.is_paused AS plan_is_paused
@@ -356,7 +409,7 @@ impl LoanLifecycleService {
         let row = sqlx::query_as::<_, LoanLifecycleRowWithPlan>(
             r#"
             SELECT ll.id, ll.user_id, ll.plan_id, ll.borrow_asset, ll.collateral_asset,
-                   ll.principal, ll.interest_rate_bps, ll.collateral_amount, ll.amount_repaid,
+                   ll.principal, ll.interest_rate_bps, ll.rate_mode, ll.collateral_amount, ll.amount_repaid,
                    ll.status, ll.due_date, ll.transaction_hash,
                    ll.created_at, ll.updated_at, ll.repaid_at, ll.liquidated_at,
                    p.title AS plan_title, p.status AS plan_status, p.is_paused AS plan_is_paused
@@ -381,7 +434,7 @@ impl LoanLifecycleService {
         let rows = sqlx::query_as::<_, LoanLifecycleRowWithPlan>(
             r#"
             SELECT ll.id, ll.user_id, ll.plan_id, ll.borrow_asset, ll.collateral_asset,
-                   ll.principal, ll.interest_rate_bps, ll.collateral_amount, ll.amount_repaid,
+                   ll.principal, ll.interest_rate_bps, ll.rate_mode, ll.collateral_amount, ll.amount_repaid,
                    ll.status, ll.due_date, ll.transaction_hash,
                    ll.created_at, ll.updated_at, ll.repaid_at, ll.liquidated_at,
                    p.title AS plan_title, p.status AS plan_status, p.is_paused AS plan_is_paused
@@ -418,7 +471,7 @@ impl LoanLifecycleService {
         let rows = sqlx::query_as::<_, LoanLifecycleRowWithPlan>(
             r#"
             SELECT ll.id, ll.user_id, ll.plan_id, ll.borrow_asset, ll.collateral_asset,
-                   ll.principal, ll.interest_rate_bps, ll.collateral_amount, ll.amount_repaid,
+                   ll.principal, ll.interest_rate_bps, ll.rate_mode, ll.collateral_amount, ll.amount_repaid,
                    ll.status, ll.due_date, ll.transaction_hash,
                    ll.created_at, ll.updated_at, ll.repaid_at, ll.liquidated_at,
                    p.title AS plan_title, p.status AS plan_status, p.is_paused AS plan_is_paused
@@ -575,12 +628,12 @@ impl LoanLifecycleService {
             r#"
             INSERT INTO loan_lifecycle (
                 user_id, plan_id, borrow_asset, collateral_asset,
-                principal, interest_rate_bps, collateral_amount,
+                principal, interest_rate_bps, rate_mode, collateral_amount,
                 due_date, transaction_hash, status
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, 'draft')
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, 'draft')
             RETURNING id, user_id, plan_id, borrow_asset, collateral_asset,
-                      principal, interest_rate_bps, collateral_amount, amount_repaid,
+                      principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
                       status, due_date, transaction_hash,
                       created_at, updated_at, repaid_at, liquidated_at
             "#,
@@ -591,6 +644,7 @@ impl LoanLifecycleService {
         .bind(&req.collateral_asset)
         .bind(req.principal)
         .bind(req.interest_rate_bps)
+        .bind(req.rate_mode.as_str())
         .bind(req.collateral_amount)
         .bind(req.due_date)
         .bind(&req.transaction_hash)
@@ -628,7 +682,7 @@ impl LoanLifecycleService {
         let row = sqlx::query_as::<_, LoanLifecycleRow>(
             r#"
             SELECT id, user_id, plan_id, borrow_asset, collateral_asset,
-                   principal, interest_rate_bps, collateral_amount, amount_repaid,
+                   principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
                    status, due_date, transaction_hash,
                    created_at, updated_at, repaid_at, liquidated_at
             FROM loan_lifecycle
@@ -652,7 +706,7 @@ impl LoanLifecycleService {
             SET status = 'applied'
             WHERE id = $1
             RETURNING id, user_id, plan_id, borrow_asset, collateral_asset,
-                      principal, interest_rate_bps, collateral_amount, amount_repaid,
+                      principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
                       status, due_date, transaction_hash,
                       created_at, updated_at, repaid_at, liquidated_at
             "#,
@@ -691,7 +745,7 @@ impl LoanLifecycleService {
         let row = sqlx::query_as::<_, LoanLifecycleRow>(
             r#"
             SELECT id, user_id, plan_id, borrow_asset, collateral_asset,
-                   principal, interest_rate_bps, collateral_amount, amount_repaid,
+                   principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
                    status, due_date, transaction_hash,
                    created_at, updated_at, repaid_at, liquidated_at
             FROM loan_lifecycle
@@ -714,7 +768,7 @@ impl LoanLifecycleService {
             SET status = 'under_review'
             WHERE id = $1
             RETURNING id, user_id, plan_id, borrow_asset, collateral_asset,
-                      principal, interest_rate_bps, collateral_amount, amount_repaid,
+                      principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
                       status, due_date, transaction_hash,
                       created_at, updated_at, repaid_at, liquidated_at
             "#,
@@ -753,7 +807,7 @@ impl LoanLifecycleService {
         let row = sqlx::query_as::<_, LoanLifecycleRow>(
             r#"
             SELECT id, user_id, plan_id, borrow_asset, collateral_asset,
-                   principal, interest_rate_bps, collateral_amount, amount_repaid,
+                   principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
                    status, due_date, transaction_hash,
                    created_at, updated_at, repaid_at, liquidated_at
             FROM loan_lifecycle
@@ -776,7 +830,7 @@ impl LoanLifecycleService {
             SET status = 'approved'
             WHERE id = $1
             RETURNING id, user_id, plan_id, borrow_asset, collateral_asset,
-                      principal, interest_rate_bps, collateral_amount, amount_repaid,
+                      principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
                       status, due_date, transaction_hash,
                       created_at, updated_at, repaid_at, liquidated_at
             "#,
@@ -815,7 +869,7 @@ impl LoanLifecycleService {
         let row = sqlx::query_as::<_, LoanLifecycleRow>(
             r#"
             SELECT id, user_id, plan_id, borrow_asset, collateral_asset,
-                   principal, interest_rate_bps, collateral_amount, amount_repaid,
+                   principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
                    status, due_date, transaction_hash,
                    created_at, updated_at, repaid_at, liquidated_at
             FROM loan_lifecycle
@@ -838,7 +892,7 @@ impl LoanLifecycleService {
             SET status = 'rejected'
             WHERE id = $1
             RETURNING id, user_id, plan_id, borrow_asset, collateral_asset,
-                      principal, interest_rate_bps, collateral_amount, amount_repaid,
+                      principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
                       status, due_date, transaction_hash,
                       created_at, updated_at, repaid_at, liquidated_at
             "#,
@@ -877,7 +931,7 @@ impl LoanLifecycleService {
         let row = sqlx::query_as::<_, LoanLifecycleRow>(
             r#"
             SELECT id, user_id, plan_id, borrow_asset, collateral_asset,
-                   principal, interest_rate_bps, collateral_amount, amount_repaid,
+                   principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
                    status, due_date, transaction_hash,
                    created_at, updated_at, repaid_at, liquidated_at
             FROM loan_lifecycle
@@ -901,7 +955,7 @@ impl LoanLifecycleService {
             SET status = 'active'
             WHERE id = $1
             RETURNING id, user_id, plan_id, borrow_asset, collateral_asset,
-                      principal, interest_rate_bps, collateral_amount, amount_repaid,
+                      principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
                       status, due_date, transaction_hash,
                       created_at, updated_at, repaid_at, liquidated_at
             "#,
@@ -952,7 +1006,7 @@ impl LoanLifecycleService {
         let row = sqlx::query_as::<_, LoanLifecycleRow>(
             r#"
             SELECT ll.id, ll.user_id, ll.plan_id, ll.borrow_asset, ll.collateral_asset,
-                   ll.principal, ll.interest_rate_bps, ll.collateral_amount, ll.amount_repaid,
+                   ll.principal, ll.interest_rate_bps, ll.rate_mode, ll.collateral_amount, ll.amount_repaid,
                    ll.status, ll.due_date, ll.transaction_hash,
                    ll.created_at, ll.updated_at, ll.repaid_at, ll.liquidated_at
             FROM loan_lifecycle ll
@@ -992,7 +1046,7 @@ impl LoanLifecycleService {
                 repaid_at      = CASE WHEN $2 THEN NOW() ELSE repaid_at END
             WHERE id = $3
             RETURNING id, user_id, plan_id, borrow_asset, collateral_asset,
-                      principal, interest_rate_bps, collateral_amount, amount_repaid,
+                      principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
                       status, due_date, transaction_hash,
                       created_at, updated_at, repaid_at, liquidated_at
             "#,
@@ -1040,7 +1094,7 @@ impl LoanLifecycleService {
         let row = sqlx::query_as::<_, LoanLifecycleRow>(
             r#"
             SELECT id, user_id, plan_id, borrow_asset, collateral_asset,
-                   principal, interest_rate_bps, collateral_amount, amount_repaid,
+                   principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
                    status, due_date, transaction_hash,
                    created_at, updated_at, repaid_at, liquidated_at
             FROM loan_lifecycle
@@ -1064,7 +1118,7 @@ impl LoanLifecycleService {
                 liquidated_at = NOW()
             WHERE id = $1
             RETURNING id, user_id, plan_id, borrow_asset, collateral_asset,
-                      principal, interest_rate_bps, collateral_amount, amount_repaid,
+                      principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
                       status, due_date, transaction_hash,
                       created_at, updated_at, repaid_at, liquidated_at
             "#,
@@ -1093,6 +1147,92 @@ impl LoanLifecycleService {
         Ok(record)
     }
 
+    /// Record the rate of an active loan after it switched to `rate_mode`
+    /// on-chain, at `interest_rate_bps` as reported by the lending contract.
+    /// A fixed loan may also be moved to `fixed` again to record a rebalance
+    /// to a new fixed rate.
+    pub async fn switch_rate_mode(
+        pool: &PgPool,
+        loan_id: Uuid,
+        user_id: Uuid,
+        rate_mode: RateMode,
+        interest_rate_bps: i32,
+    ) -> Result<LoanLifecycleRecord, ApiError> {
+        if interest_rate_bps < 0 {
+            return Err(ApiError::BadRequest(
+                "interest rate must not be negative".to_string(),
+            ));
+        }
+
+        let mut tx = pool.begin().await?;
+
+        let row = sqlx::query_as::<_, LoanLifecycleRow>(
+            r#"
+            SELECT id, user_id, plan_id, borrow_asset, collateral_asset,
+                   principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
+                   status, due_date, transaction_hash,
+                   created_at, updated_at, repaid_at, liquidated_at
+            FROM loan_lifecycle
+            WHERE id = $1 AND user_id = $2
+            FOR UPDATE
+            "#,
+        )
+        .bind(loan_id)
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("loan {loan_id} not found")))?;
+
+        if LoanStatus::from_str(&row.status)? != LoanStatus::Active {
+            return Err(ApiError::BadRequest(
+                "only active loans can switch rate mode".to_string(),
+            ));
+        }
+        if RateMode::from_str(&row.rate_mode)? == RateMode::Variable
+            && rate_mode == RateMode::Variable
+        {
+            return Err(ApiError::BadRequest(format!(
+                "loan {loan_id} already has a variable rate"
+            )));
+        }
+
+        let updated = sqlx::query_as::<_, LoanLifecycleRow>(
+            r#"
+            UPDATE loan_lifecycle
+            SET rate_mode         = $1,
+                interest_rate_bps = $2
+            WHERE id = $3
+            RETURNING id, user_id, plan_id, borrow_asset, collateral_asset,
+                      principal, interest_rate_bps, rate_mode, collateral_amount, amount_repaid,
+                      status, due_date, transaction_hash,
+                      created_at, updated_at, repaid_at, liquidated_at
+            "#,
+        )
+        .bind(rate_mode.as_str())
+        .bind(interest_rate_bps)
+        .bind(loan_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let record: LoanLifecycleRecord = updated.into();
+
+        AuditLogService::log(
+            &mut *tx,
+            Some(user_id),
+            None,
+            audit_action::LOAN_RATE_MODE_SWITCHED,
+            Some(loan_id),
+            Some(entity_type::LOAN),
+            None,
+            None,
+            None,
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(record)
+    }
+
     /// Convenience wrapper used by older call-sites to create and immediately
     /// activate a loan. This delegates to `create_draft_loan` and then
     /// transitions the loan to `active` so higher-level handlers can call a
@@ -1163,6 +1303,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rate_mode_round_trips_and_defaults_to_variable() {
+        for (s, expected) in [("variable", RateMode::Variable), ("fixed", RateMode::Fixed)] {
+            let parsed = RateMode::from_str(s).expect("should parse");
+            assert_eq!(parsed, expected);
+            assert_eq!(parsed.as_str(), s);
+        }
+        assert!(RateMode::from_str("stable").is_err());
+        assert_eq!(RateMode::default(), RateMode::Variable);
+        assert_eq!(
+            serde_json::to_value(RateMode::Fixed).unwrap(),
+            serde_json::json!("fixed")
+        );
+    }
+
     #[test]
     fn loan_status_from_str_rejects_unknown() {
         assert!(LoanStatus::from_str("pending").is_err());
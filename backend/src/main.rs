@@ -1,6 +1,7 @@
 use inheritx_backend::{
-    create_app, db, error_tracking, metrics, telemetry, Config, LegacyMessageDeliveryService,
-    LendingDataWarehouseService, MessageKeyService,
+    create_app, db, error_tracking, metrics, telemetry, ChainReconciler, Config,
+    ContractEventIndexer, CrossChainRelayerService, LegacyMessageDeliveryService,
+    LendingDataWarehouseService, MessageKeyService, SorobanRpcClient,
 };
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -103,6 +104,27 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
         Arc::new(LegacyMessageDeliveryService::new(db_pool.clone()));
     legacy_message_delivery_service.start();
 
+    // Mirror contract events into Postgres when contract addresses are configured.
+    if let Some(indexer) =
+        ContractEventIndexer::from_env(db_pool.clone(), SorobanRpcClient::from_env())
+    {
+        Arc::new(indexer).start();
+    }
+
+    // Diff plan, loan and pool state in contract storage against Postgres.
+    if let Some(reconciler) =
+        ChainReconciler::from_env(db_pool.clone(), SorobanRpcClient::from_env())
+    {
+        Arc::new(reconciler).start();
+    }
+
+    // Relay the cross-chain releases of triggered plans.
+    if let Some(relayer) =
+        CrossChainRelayerService::from_env(db_pool.clone(), SorobanRpcClient::from_env())?
+    {
+        Arc::new(relayer).start();
+    }
+
     // Start server
     let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
     info!("Starting INHERITX backend server on {}", addr);
@@ -32,11 +32,13 @@ pub struct RequestContext {
 /// Middleware that enforces a maximum request body size (bytes) and validates
 /// JSON string lengths using the validation helpers.
 pub async fn enforce_max_request_size(req: Request<Body>, next: Next) -> Response {
+    let max_body_bytes = crate::validation::max_body_bytes_for(req.uri().path());
+
     // Respect a client-provided Content-Length header when present.
     if let Some(clv) = req.headers().get(axum::http::header::CONTENT_LENGTH) {
         if let Ok(s) = clv.to_str() {
             if let Ok(n) = s.parse::<usize>() {
-                if n > crate::validation::DEFAULT_MAX_BODY_BYTES {
+                if n > max_body_bytes {
                     return (
                         StatusCode::PAYLOAD_TOO_LARGE,
                         axum::Json(serde_json::json!({
@@ -52,17 +54,16 @@ pub async fn enforce_max_request_size(req: Request<Body>, next: Next) -> Respons
 
     // Read the body up to the configured cap so we can inspect JSON payloads.
     let (parts, body) = req.into_parts();
-    let bytes: axum::body::Bytes =
-        match axum::body::to_bytes(body, crate::validation::DEFAULT_MAX_BODY_BYTES + 1).await {
-            Ok(b) => b,
-            Err(_) => {
-                // If body couldn't be read, let the inner handler observe the failure.
-                let req = Request::from_parts(parts, Body::empty());
-                return next.run(req).await;
-            }
-        };
+    let bytes: axum::body::Bytes = match axum::body::to_bytes(body, max_body_bytes + 1).await {
+        Ok(b) => b,
+        Err(_) => {
+            // If body couldn't be read, let the inner handler observe the failure.
+            let req = Request::from_parts(parts, Body::empty());
+            return next.run(req).await;
+        }
+    };
 
-    if bytes.len() > crate::validation::DEFAULT_MAX_BODY_BYTES {
+    if bytes.len() > max_body_bytes {
         return (
             StatusCode::PAYLOAD_TOO_LARGE,
             axum::Json(serde_json::json!({
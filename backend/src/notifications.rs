@@ -37,6 +37,8 @@ pub mod notif_type {
     pub const INSURANCE_CLAIM_APPROVED: &str = "insurance_claim_approved";
     pub const INSURANCE_CLAIM_REJECTED: &str = "insurance_claim_rejected";
     pub const INSURANCE_CLAIM_PAID: &str = "insurance_claim_paid";
+    // Dead-man's-switch check-ins
+    pub const CHECK_IN_REMINDER: &str = "check_in_reminder";
 }
 
 // ─── Notification ────────────────────────────────────────────────────────────
@@ -308,6 +310,7 @@ pub mod audit_action {
     pub const LOAN_PARTIAL_REPAYMENT: &str = "loan_partial_repayment";
     pub const LOAN_LIQUIDATED: &str = "loan_liquidated";
     pub const LOAN_MARKED_OVERDUE: &str = "loan_marked_overdue";
+    pub const LOAN_RATE_MODE_SWITCHED: &str = "loan_rate_mode_switched";
     // Collateral management
     pub const COLLATERAL_ADDED: &str = "collateral_added";
     pub const COLLATERAL_REMOVED: &str = "collateral_removed";
@@ -332,6 +335,23 @@ pub mod audit_action {
     pub const INSURANCE_CLAIM_CREATED: &str = "insurance_claim_created";
     pub const INSURANCE_CLAIM_PROCESSED: &str = "insurance_claim_processed";
     pub const INSURANCE_CLAIM_PAID: &str = "insurance_claim_paid";
+    // Dead-man's-switch check-ins
+    pub const OWNER_CHECKED_IN: &str = "owner_checked_in";
+    pub const CHECK_IN_REMINDER_SENT: &str = "check_in_reminder_sent";
+    // Death-certificate attestation
+    pub const ATTESTOR_REGISTERED: &str = "attestor_registered";
+    pub const ATTESTOR_REMOVED: &str = "attestor_removed";
+    pub const DEATH_CERTIFICATE_UPLOADED: &str = "death_certificate_uploaded";
+    // Bank account references
+    pub const BANK_ACCOUNT_STORED: &str = "bank_account_stored";
+    pub const BANK_ACCOUNT_IMPORTED: &str = "bank_account_imported";
+    pub const BANK_ACCOUNT_RESOLVED: &str = "bank_account_resolved";
+    // Authenticator-app second factor
+    pub const TOTP_ENABLED: &str = "totp_enabled";
+    pub const TOTP_DISABLED: &str = "totp_disabled";
+    pub const RECOVERY_CODE_USED: &str = "recovery_code_used";
+    pub const RECOVERY_CODES_REGENERATED: &str = "recovery_codes_regenerated";
+    pub const TWO_FA_POLICY_UPDATED: &str = "2fa_policy_updated";
 }
 
 /// Entity type constants — stored in `entity_type` column of `action_logs`.
@@ -342,6 +362,7 @@ pub mod entity_type {
     // Insurance fund monitoring (Issue #249)
     pub const INSURANCE_FUND: &str = "insurance_fund";
     pub const INSURANCE_CLAIM: &str = "insurance_claim";
+    pub const BANK_ACCOUNT: &str = "bank_account";
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -0,0 +1,780 @@
+//! # SEP-10 Stellar Web Authentication
+//!
+//! Lets any SEP-10 wallet log in by proving control of a Stellar account:
+//!
+//! 1. `GET /api/auth/sep10?account=G…` returns a challenge: a transaction
+//!    with sequence number 0, sourced and signed by the server's signing
+//!    key, whose first operation is a `<home domain> auth` manage-data
+//!    operation sourced by the client account and carrying a random nonce.
+//!    It can never be submitted, only signed.
+//! 2. The wallet adds its signatures and posts the envelope back to
+//!    `POST /api/auth/sep10`.
+//! 3. The server checks that the challenge is its own, unexpired and not
+//!    used before, and that the client signatures reach the account's
+//!    medium threshold with the signers Horizon lists for it. An account
+//!    that does not exist yet must be signed for by its master key. It then
+//!    issues a JWT carrying the SEP-10 claims.
+//!
+//! The token also carries the backend's `user_id` and `email`, so it is
+//! accepted wherever [`AuthenticatedUser`](crate::auth::AuthenticatedUser)
+//! is. The wallet's user is created on its first login, as with the other
+//! wallet logins.
+//!
+//! Wallets find the endpoint and the signing key through the home domain's
+//! `stellar.toml`: `WEB_AUTH_ENDPOINT` must point at `/api/auth/sep10` and
+//! `SIGNING_KEY` must be [`Sep10Service::signing_account`].
+
+use crate::api_error::ApiError;
+use crate::app::AppState;
+use crate::auth::find_or_create_wallet_user;
+use crate::stellar::{
+    account_strkey, decorated_signature, transaction_hash, HorizonClient, InMemorySigner,
+    StellarConfig, TransactionSigner,
+};
+use axum::extract::{Query, State};
+use axum::Json;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{Duration, TimeZone, Utc};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use stellar_strkey::Strkey;
+use stellar_xdr::curr::{
+    DataValue, DecoratedSignature, Limits, ManageDataOp, Memo, MuxedAccount, Operation,
+    OperationBody, Preconditions, ReadXdr, SequenceNumber, String64, StringM, TimeBounds,
+    TimePoint, Transaction, TransactionEnvelope, TransactionExt, TransactionV1Envelope, Uint256,
+    WriteXdr,
+};
+use uuid::Uuid;
+
+/// Path of the challenge and token endpoints.
+pub const SEP10_PATH: &str = "/api/auth/sep10";
+
+const DEFAULT_CHALLENGE_TTL_SECS: u64 = 900;
+
+/// Clock skew tolerated on a challenge's lower time bound.
+const MIN_TIME_GRACE_SECS: u64 = 300;
+
+/// Random bytes in a challenge nonce; 64 once base64-encoded, as SEP-10
+/// requires.
+const NONCE_BYTES: usize = 48;
+
+const WEB_AUTH_DOMAIN_KEY: &str = "web_auth_domain";
+
+/// Nominal fee per operation; challenges are never submitted.
+const CHALLENGE_BASE_FEE: u32 = 100;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Configuration
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct Sep10Config {
+    /// Domain whose `stellar.toml` advertises this server.
+    pub home_domain: String,
+    /// Domain serving the endpoints, bound into every challenge.
+    pub web_auth_domain: String,
+    pub network_passphrase: String,
+    pub challenge_ttl_secs: u64,
+    pub token_ttl_hours: i64,
+}
+
+impl Sep10Config {
+    /// Load from environment variables. `None` when no home domain is set.
+    ///
+    /// | Variable                    | Default                         |
+    /// |-----------------------------|---------------------------------|
+    /// | `SEP10_HOME_DOMAIN`         | — (SEP-10 disabled)             |
+    /// | `SEP10_WEB_AUTH_DOMAIN`     | `SEP10_HOME_DOMAIN`             |
+    /// | `SEP10_CHALLENGE_TTL_SECS`  | 900                             |
+    /// | `SEP10_TOKEN_TTL_HOURS`     | 24                              |
+    ///
+    /// The network passphrase is `STELLAR_NETWORK_PASSPHRASE`, see
+    /// [`StellarConfig::from_env`].
+    pub fn from_env() -> Option<Self> {
+        let home_domain = std::env::var("SEP10_HOME_DOMAIN")
+            .ok()
+            .filter(|d| !d.is_empty())?;
+        let web_auth_domain = std::env::var("SEP10_WEB_AUTH_DOMAIN")
+            .ok()
+            .filter(|d| !d.is_empty())
+            .unwrap_or_else(|| home_domain.clone());
+        Some(Self {
+            home_domain,
+            web_auth_domain,
+            network_passphrase: StellarConfig::from_env().network_passphrase,
+            challenge_ttl_secs: std::env::var("SEP10_CHALLENGE_TTL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_CHALLENGE_TTL_SECS),
+            token_ttl_hours: std::env::var("SEP10_TOKEN_TTL_HOURS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(24),
+        })
+    }
+
+    fn auth_data_name(&self) -> String {
+        format!("{} auth", self.home_domain)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Challenges
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A challenge the server issued, read back from a client-signed envelope.
+#[derive(Debug, Clone)]
+pub struct Challenge {
+    /// `G…` address of the client account.
+    pub account: String,
+    account_key: [u8; 32],
+    /// Hash the signatures are over; identifies the challenge.
+    pub hash: [u8; 32],
+    /// Upper time bound, in Unix seconds.
+    pub expires_at: u64,
+    /// Every signature on the envelope other than the server's.
+    client_signatures: Vec<DecoratedSignature>,
+}
+
+/// SEP-10 token claims, plus the backend identity [`crate::auth::UserClaims`]
+/// reads.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sep10Claims {
+    /// URI of this authentication endpoint.
+    pub iss: String,
+    /// The authenticated account.
+    pub sub: String,
+    pub iat: usize,
+    pub exp: usize,
+    /// Hex hash of the challenge the token was issued for.
+    pub jti: String,
+    pub home_domain: String,
+    pub user_id: Uuid,
+    pub email: String,
+}
+
+fn invalid(message: &str) -> ApiError {
+    ApiError::BadRequest(format!("Invalid SEP-10 challenge: {message}"))
+}
+
+fn ed25519_account(key: [u8; 32]) -> MuxedAccount {
+    MuxedAccount::Ed25519(Uint256(key))
+}
+
+fn manage_data(source: [u8; 32], name: &str, value: &[u8]) -> Result<Operation, ApiError> {
+    let data_name = StringM::<64>::try_from(name)
+        .map(String64)
+        .map_err(|_| ApiError::BadRequest(format!("Data entry name too long: {name}")))?;
+    let data_value = value
+        .to_vec()
+        .try_into()
+        .map(DataValue)
+        .map_err(|_| ApiError::BadRequest("Data entry value too long".to_string()))?;
+    Ok(Operation {
+        source_account: Some(ed25519_account(source)),
+        body: OperationBody::ManageData(ManageDataOp {
+            data_name,
+            data_value: Some(data_value),
+        }),
+    })
+}
+
+fn verifies(public_key: &[u8; 32], hash: &[u8; 32], signature: &DecoratedSignature) -> bool {
+    signature.hint.0 == public_key[28..]
+        && UnparsedPublicKey::new(&ED25519, public_key)
+            .verify(hash, signature.signature.0.as_slice())
+            .is_ok()
+}
+
+/// Combined weight of the distinct `signers` that signed `hash`.
+///
+/// Fails when any signature is not from one of `signers`, as SEP-10
+/// requires.
+pub fn signed_weight(
+    hash: &[u8; 32],
+    signatures: &[DecoratedSignature],
+    signers: &[([u8; 32], u32)],
+) -> Result<u32, ApiError> {
+    let mut counted: Vec<[u8; 32]> = Vec::new();
+    let mut weight = 0u32;
+    for signature in signatures {
+        let (key, key_weight) = signers
+            .iter()
+            .find(|(key, _)| verifies(key, hash, signature))
+            .ok_or(ApiError::Unauthorized)?;
+        if !counted.contains(key) {
+            counted.push(*key);
+            weight = weight.saturating_add(*key_weight);
+        }
+    }
+    Ok(weight)
+}
+
+fn now_secs() -> u64 {
+    Utc::now().timestamp().max(0) as u64
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Service
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub struct Sep10Service {
+    config: Sep10Config,
+    signer: Arc<dyn TransactionSigner>,
+    horizon: HorizonClient,
+}
+
+impl Sep10Service {
+    pub fn new(
+        config: Sep10Config,
+        signer: Arc<dyn TransactionSigner>,
+        horizon: HorizonClient,
+    ) -> Self {
+        Self {
+            config,
+            signer,
+            horizon,
+        }
+    }
+
+    /// Build from [`Sep10Config::from_env`] and the `S…` secret in
+    /// `SEP10_SIGNING_SECRET`. `None` when either is unset.
+    pub fn from_env() -> Result<Option<Self>, ApiError> {
+        let Some(config) = Sep10Config::from_env() else {
+            return Ok(None);
+        };
+        let Some(secret) = std::env::var("SEP10_SIGNING_SECRET")
+            .ok()
+            .filter(|s| !s.is_empty())
+        else {
+            return Ok(None);
+        };
+        let signer = Arc::new(InMemorySigner::from_secret(&secret)?);
+        Ok(Some(Self::new(config, signer, HorizonClient::from_env())))
+    }
+
+    pub fn config(&self) -> &Sep10Config {
+        &self.config
+    }
+
+    /// `G…` address challenges are signed with: the `SIGNING_KEY` of the
+    /// home domain's `stellar.toml`.
+    pub fn signing_account(&self) -> String {
+        self.signer.account_id()
+    }
+
+    /// A signed challenge for `account`, as base64 XDR.
+    pub async fn challenge(&self, account: &str) -> Result<String, ApiError> {
+        self.challenge_at(account, now_secs()).await
+    }
+
+    async fn challenge_at(&self, account: &str, now: u64) -> Result<String, ApiError> {
+        let account_key = match Strkey::from_string(account) {
+            Ok(Strkey::PublicKeyEd25519(pk)) => pk.0,
+            _ => {
+                return Err(ApiError::BadRequest(
+                    "account must be a G… Stellar address".to_string(),
+                ))
+            }
+        };
+        let server = self.signer.public_key();
+        if account_key == server {
+            return Err(ApiError::BadRequest(
+                "account must not be the server signing account".to_string(),
+            ));
+        }
+
+        let mut nonce = [0u8; NONCE_BYTES];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("Failed to generate nonce")))?;
+        let operations = vec![
+            manage_data(
+                account_key,
+                &self.config.auth_data_name(),
+                BASE64.encode(nonce).as_bytes(),
+            )?,
+            manage_data(
+                server,
+                WEB_AUTH_DOMAIN_KEY,
+                self.config.web_auth_domain.as_bytes(),
+            )?,
+        ];
+
+        let tx = Transaction {
+            source_account: ed25519_account(server),
+            fee: CHALLENGE_BASE_FEE * operations.len() as u32,
+            seq_num: SequenceNumber(0),
+            cond: Preconditions::Time(TimeBounds {
+                min_time: TimePoint(now),
+                max_time: TimePoint(now + self.config.challenge_ttl_secs),
+            }),
+            memo: Memo::None,
+            operations: operations
+                .try_into()
+                .expect("two operations fit in a transaction"),
+            ext: TransactionExt::V0,
+        };
+        let hash = transaction_hash(&self.config.network_passphrase, &tx)?;
+        let signature = self.signer.sign_hash(&hash).await?;
+        let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx,
+            signatures: vec![decorated_signature(&server, signature)]
+                .try_into()
+                .expect("one signature fits in an envelope"),
+        });
+        let bytes = envelope
+            .to_xdr(Limits::none())
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to encode challenge: {e}")))?;
+        Ok(BASE64.encode(bytes))
+    }
+
+    /// Read a client-signed challenge back and check that this server issued
+    /// it, for this home domain, and that it has not expired. Client
+    /// signatures are checked by [`verify`](Self::verify).
+    pub fn read_challenge(&self, envelope_xdr: &str) -> Result<Challenge, ApiError> {
+        self.read_challenge_at(envelope_xdr, now_secs())
+    }
+
+    fn read_challenge_at(&self, envelope_xdr: &str, now: u64) -> Result<Challenge, ApiError> {
+        let bytes = BASE64
+            .decode(envelope_xdr)
+            .map_err(|_| invalid("not base64"))?;
+        let envelope = TransactionEnvelope::from_xdr(bytes, Limits::none())
+            .map_err(|_| invalid("not a transaction envelope"))?;
+        let TransactionEnvelope::Tx(TransactionV1Envelope { tx, signatures }) = envelope else {
+            return Err(invalid("not a V1 transaction"));
+        };
+
+        let server = self.signer.public_key();
+        if tx.source_account != ed25519_account(server) {
+            return Err(invalid("not issued by this server"));
+        }
+        if tx.seq_num.0 != 0 {
+            return Err(invalid("sequence number must be 0"));
+        }
+        let Preconditions::Time(bounds) = &tx.cond else {
+            return Err(invalid("missing time bounds"));
+        };
+        if bounds.min_time.0 > now + MIN_TIME_GRACE_SECS || now > bounds.max_time.0 {
+            return Err(invalid("expired"));
+        }
+
+        let (first, rest) = tx
+            .operations
+            .split_first()
+            .ok_or_else(|| invalid("no operations"))?;
+        let auth_name = self.config.auth_data_name();
+        let account_key = match (&first.source_account, &first.body) {
+            (Some(MuxedAccount::Ed25519(Uint256(key))), OperationBody::ManageData(op))
+                if op.data_name.0.as_slice() == auth_name.as_bytes()
+                    && op.data_value.as_ref().is_some_and(|v| v.0.len() == 64) =>
+            {
+                *key
+            }
+            _ => {
+                return Err(invalid(&format!(
+                    "first operation must be the '{auth_name}' manage-data operation"
+                )))
+            }
+        };
+        if account_key == server {
+            return Err(invalid("client account is the server account"));
+        }
+        for op in rest {
+            let OperationBody::ManageData(data) = &op.body else {
+                return Err(invalid("only manage-data operations are allowed"));
+            };
+            if op.source_account != Some(ed25519_account(server)) {
+                return Err(invalid("further operations must be sourced by the server"));
+            }
+            if data.data_name.0.as_slice() == WEB_AUTH_DOMAIN_KEY.as_bytes()
+                && data.data_value.as_ref().map(|v| v.0.as_slice())
+                    != Some(self.config.web_auth_domain.as_bytes())
+            {
+                return Err(invalid("web_auth_domain does not match"));
+            }
+        }
+
+        let hash = transaction_hash(&self.config.network_passphrase, &tx)?;
+        let (server_signatures, client_signatures): (Vec<_>, Vec<_>) = signatures
+            .iter()
+            .cloned()
+            .partition(|signature| verifies(&server, &hash, signature));
+        if server_signatures.is_empty() {
+            return Err(invalid("not signed by this server"));
+        }
+
+        Ok(Challenge {
+            account: account_strkey(&account_key),
+            account_key,
+            hash,
+            expires_at: bounds.max_time.0,
+            client_signatures,
+        })
+    }
+
+    /// Read a client-signed challenge and check its client signatures
+    /// against the account's signers and medium threshold on Horizon.
+    pub async fn verify(&self, envelope_xdr: &str) -> Result<Challenge, ApiError> {
+        let challenge = self.read_challenge(envelope_xdr)?;
+        self.verify_signers(&challenge).await?;
+        Ok(challenge)
+    }
+
+    async fn verify_signers(&self, challenge: &Challenge) -> Result<(), ApiError> {
+        let (signers, threshold) = match self.horizon.get_account(&challenge.account).await {
+            Ok(account) => {
+                let signers: Vec<([u8; 32], u32)> = account
+                    .signers
+                    .iter()
+                    .filter(|s| s.signer_type == "ed25519_public_key" && s.weight > 0)
+                    .filter_map(|s| match Strkey::from_string(&s.key) {
+                        Ok(Strkey::PublicKeyEd25519(pk)) => Some((pk.0, s.weight)),
+                        _ => None,
+                    })
+                    .collect();
+                (signers, u32::from(account.thresholds.med_threshold))
+            }
+            // An account that does not exist yet has only its master key.
+            Err(ApiError::NotFound(_)) => (vec![(challenge.account_key, 1)], 1),
+            Err(e) => return Err(e),
+        };
+
+        let weight = signed_weight(&challenge.hash, &challenge.client_signatures, &signers)?;
+        if weight == 0 || weight < threshold {
+            return Err(ApiError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// JWT for a verified `challenge`, signed with `jwt_secret`.
+    pub fn issue_token(
+        &self,
+        challenge: &Challenge,
+        user_id: Uuid,
+        email: String,
+        jwt_secret: &str,
+    ) -> Result<String, ApiError> {
+        let now = Utc::now();
+        let claims = Sep10Claims {
+            iss: format!("https://{}{SEP10_PATH}", self.config.web_auth_domain),
+            sub: challenge.account.clone(),
+            iat: now.timestamp() as usize,
+            exp: (now + Duration::hours(self.config.token_ttl_hours)).timestamp() as usize,
+            jti: hex::encode(challenge.hash),
+            home_domain: self.config.home_domain.clone(),
+            user_id,
+            email,
+        };
+        encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(jwt_secret.as_bytes()),
+        )
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Handlers
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct ChallengeQuery {
+    pub account: String,
+    pub home_domain: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChallengeResponse {
+    pub transaction: String,
+    pub network_passphrase: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TokenRequest {
+    pub transaction: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TokenResponse {
+    pub token: String,
+}
+
+fn service(state: &AppState) -> Result<&Sep10Service, ApiError> {
+    state.sep10.as_deref().ok_or_else(|| {
+        ApiError::ServiceUnavailable("SEP-10 authentication is not configured".to_string())
+    })
+}
+
+pub async fn get_challenge(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ChallengeQuery>,
+) -> Result<Json<ChallengeResponse>, ApiError> {
+    let sep10 = service(&state)?;
+    if let Some(home_domain) = &query.home_domain {
+        if home_domain != &sep10.config().home_domain {
+            return Err(ApiError::BadRequest(format!(
+                "Unsupported home_domain: {home_domain}"
+            )));
+        }
+    }
+
+    let transaction = sep10.challenge(&query.account).await?;
+    Ok(Json(ChallengeResponse {
+        transaction,
+        network_passphrase: sep10.config().network_passphrase.clone(),
+    }))
+}
+
+pub async fn post_token(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<TokenRequest>,
+) -> Result<Json<TokenResponse>, ApiError> {
+    let sep10 = service(&state)?;
+    let challenge = sep10.verify(&payload.transaction).await?;
+    let expires_at = Utc
+        .timestamp_opt(challenge.expires_at as i64, 0)
+        .single()
+        .ok_or_else(|| invalid("time bounds out of range"))?;
+
+    let mut tx = state.db.begin().await?;
+    sqlx::query("DELETE FROM sep10_used_challenges WHERE expires_at < NOW()")
+        .execute(&mut *tx)
+        .await?;
+    let first_use = sqlx::query(
+        r#"
+        INSERT INTO sep10_used_challenges (tx_hash, account, expires_at)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (tx_hash) DO NOTHING
+        "#,
+    )
+    .bind(hex::encode(challenge.hash))
+    .bind(&challenge.account)
+    .bind(expires_at)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected()
+        == 1;
+    if !first_use {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let (user_id, email) = find_or_create_wallet_user(&state.db, &challenge.account).await?;
+    let token = sep10.issue_token(&challenge, user_id, email, &state.config.jwt_secret)?;
+    tx.commit().await?;
+
+    Ok(Json(TokenResponse { token }))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Unit tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::UserClaims;
+    use httpmock::prelude::*;
+    use jsonwebtoken::{decode, DecodingKey, Validation};
+    use serde_json::json;
+
+    const PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+    fn signer(seed: u8) -> InMemorySigner {
+        InMemorySigner::from_seed(&[seed; 32]).unwrap()
+    }
+
+    fn service(horizon: &MockServer) -> Sep10Service {
+        let config = Sep10Config {
+            home_domain: "inheritx.example".to_string(),
+            web_auth_domain: "api.inheritx.example".to_string(),
+            network_passphrase: PASSPHRASE.to_string(),
+            challenge_ttl_secs: DEFAULT_CHALLENGE_TTL_SECS,
+            token_ttl_hours: 24,
+        };
+        let horizon = HorizonClient::new(StellarConfig {
+            network_passphrase: PASSPHRASE.to_string(),
+            horizon_url: horizon.base_url(),
+            rpc_url: horizon.base_url(),
+            request_timeout_secs: 5,
+            max_retries: 1,
+        });
+        Sep10Service::new(config, Arc::new(signer(1)), horizon)
+    }
+
+    /// Serve `account` from the fake Horizon with `signers` and a medium
+    /// threshold of `med`.
+    async fn account(horizon: &MockServer, account: &str, signers: &[(&str, u32)], med: u8) {
+        let signers: Vec<_> = signers
+            .iter()
+            .map(|(key, weight)| json!({ "key": key, "weight": weight, "type": "ed25519_public_key" }))
+            .collect();
+        let body = json!({
+            "id": account,
+            "account_id": account,
+            "sequence": "1",
+            "balances": [],
+            "subentry_count": 0,
+            "last_modified_ledger": 1,
+            "last_modified_time": "2026-01-01T00:00:00Z",
+            "thresholds": { "low_threshold": 0, "med_threshold": med, "high_threshold": med },
+            "flags": {
+                "auth_required": false,
+                "auth_revocable": false,
+                "auth_immutable": false,
+                "auth_clawback_enabled": false
+            },
+            "signers": signers
+        });
+        horizon
+            .mock_async(|when, then| {
+                when.method(GET).path(format!("/accounts/{account}"));
+                then.status(200).json_body(body);
+            })
+            .await;
+    }
+
+    /// Add signatures by `signers` to a challenge envelope.
+    async fn cosign(envelope_xdr: &str, signers: &[&InMemorySigner]) -> String {
+        let bytes = BASE64.decode(envelope_xdr).unwrap();
+        let TransactionEnvelope::Tx(mut envelope) =
+            TransactionEnvelope::from_xdr(bytes, Limits::none()).unwrap()
+        else {
+            panic!("challenge is a V1 envelope");
+        };
+        let hash = transaction_hash(PASSPHRASE, &envelope.tx).unwrap();
+        let mut signatures = envelope.signatures.to_vec();
+        for signer in signers {
+            let signature = signer.sign_hash(&hash).await.unwrap();
+            signatures.push(decorated_signature(&signer.public_key(), signature));
+        }
+        envelope.signatures = signatures.try_into().unwrap();
+        let signed = TransactionEnvelope::Tx(envelope);
+        BASE64.encode(signed.to_xdr(Limits::none()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn signed_challenge_logs_in_account() {
+        let horizon = MockServer::start_async().await;
+        let sep10 = service(&horizon);
+        let client = signer(2);
+        account(
+            &horizon,
+            &client.account_id(),
+            &[(&client.account_id(), 1)],
+            0,
+        )
+        .await;
+
+        let challenge = sep10.challenge(&client.account_id()).await.unwrap();
+        let unsigned = sep10.verify(&challenge).await;
+        assert!(matches!(unsigned, Err(ApiError::Unauthorized)));
+
+        let signed = cosign(&challenge, &[&client]).await;
+        let verified = sep10.verify(&signed).await.unwrap();
+        assert_eq!(verified.account, client.account_id());
+
+        let user_id = Uuid::new_v4();
+        let token = sep10
+            .issue_token(&verified, user_id, "w@inheritx.auth".to_string(), "secret")
+            .unwrap();
+        let key = DecodingKey::from_secret(b"secret");
+        let claims = decode::<Sep10Claims>(&token, &key, &Validation::default())
+            .unwrap()
+            .claims;
+        assert_eq!(claims.sub, client.account_id());
+        assert_eq!(claims.iss, "https://api.inheritx.example/api/auth/sep10");
+        assert_eq!(claims.jti, hex::encode(verified.hash));
+        let user = decode::<UserClaims>(&token, &key, &Validation::default())
+            .unwrap()
+            .claims;
+        assert_eq!(user.user_id, user_id);
+    }
+
+    #[tokio::test]
+    async fn multisig_account_needs_medium_threshold() {
+        let horizon = MockServer::start_async().await;
+        let sep10 = service(&horizon);
+        let (master, cosigner, stranger) = (signer(2), signer(3), signer(4));
+        account(
+            &horizon,
+            &master.account_id(),
+            &[(&master.account_id(), 1), (&cosigner.account_id(), 1)],
+            2,
+        )
+        .await;
+
+        let challenge = sep10.challenge(&master.account_id()).await.unwrap();
+        let one = cosign(&challenge, &[&master]).await;
+        assert!(matches!(
+            sep10.verify(&one).await,
+            Err(ApiError::Unauthorized)
+        ));
+        // The same key twice still counts once.
+        let repeated = cosign(&challenge, &[&master, &master]).await;
+        assert!(sep10.verify(&repeated).await.is_err());
+        let extra = cosign(&challenge, &[&master, &cosigner, &stranger]).await;
+        assert!(sep10.verify(&extra).await.is_err());
+
+        let both = cosign(&challenge, &[&master, &cosigner]).await;
+        assert_eq!(
+            sep10.verify(&both).await.unwrap().account,
+            master.account_id()
+        );
+    }
+
+    #[tokio::test]
+    async fn unfunded_account_needs_master_key() {
+        let horizon = MockServer::start_async().await;
+        let sep10 = service(&horizon);
+        let (client, other) = (signer(2), signer(3));
+        horizon
+            .mock_async(|when, then| {
+                when.method(GET);
+                then.status(404).json_body(json!({ "status": 404 }));
+            })
+            .await;
+
+        let challenge = sep10.challenge(&client.account_id()).await.unwrap();
+        let wrong = cosign(&challenge, &[&other]).await;
+        assert!(matches!(
+            sep10.verify(&wrong).await,
+            Err(ApiError::Unauthorized)
+        ));
+        let signed = cosign(&challenge, &[&client]).await;
+        assert!(sep10.verify(&signed).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn foreign_or_expired_challenges_are_rejected() {
+        let horizon = MockServer::start_async().await;
+        let sep10 = service(&horizon);
+        let client = signer(2);
+
+        let now = now_secs();
+        let stale = sep10
+            .challenge_at(&client.account_id(), now - 2 * DEFAULT_CHALLENGE_TTL_SECS)
+            .await
+            .unwrap();
+        assert!(matches!(
+            sep10.read_challenge_at(&stale, now),
+            Err(ApiError::BadRequest(_))
+        ));
+
+        let mut other_server = service(&horizon);
+        other_server.signer = Arc::new(signer(9));
+        let foreign = other_server.challenge(&client.account_id()).await.unwrap();
+        assert!(sep10.read_challenge(&foreign).is_err());
+
+        let mut other_domain = service(&horizon);
+        other_domain.config.home_domain = "evil.example".to_string();
+        let challenge = sep10.challenge(&client.account_id()).await.unwrap();
+        assert!(other_domain.read_challenge(&challenge).is_err());
+        assert!(sep10.read_challenge(&challenge).is_ok());
+    }
+}
@@ -14,6 +14,7 @@ use crate::api_error::ApiError;
 use crate::notifications::{
     audit_action, entity_type, notif_type, AuditLogService, NotificationService,
 };
+use crate::two_factor::{SensitiveAction, TwoFactorService};
 use crate::yield_service::OnChainYieldService;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
@@ -536,6 +537,15 @@ impl PlanService {
             ));
         }
 
+        // 1b. Second factor, when the user's policy requires one for claims
+        TwoFactorService::enforce(
+            pool,
+            user_id,
+            SensitiveAction::Claim,
+            Some(&req.two_fa_code),
+        )
+        .await?;
+
         // 2. Start the transaction
         let mut tx = pool.begin().await?;
 
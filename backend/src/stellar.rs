@@ -3,8 +3,12 @@
 //! Provides:
 //! * [`HorizonClient`]     – REST wrapper around the Stellar Horizon API.
 //! * [`SorobanRpcClient`]  – JSON-RPC wrapper for the Soroban RPC endpoint,
-//!   enabling contract invocations from the backend.
+//!   enabling contract invocations and contract storage reads from the
+//!   backend.
 //! * [`TransactionMonitor`] – polling-based transaction-status monitor.
+//! * [`ContractInvoker`]   – builds `InvokeHostFunction` transactions from
+//!   a [`ContractCall`], assembles the simulated footprint and fees, signs
+//!   them through a [`TransactionSigner`] and submits them.
 //!
 //! All network I/O goes through `reqwest`, which is already part of the
 //! dependency graph.  XDR encoding relies on `stellar-xdr`.
@@ -12,13 +16,29 @@
 use crate::api_error::ApiError;
 use crate::circuit_breaker::CircuitBreaker;
 use crate::retry::{retry_async, RetryConfig};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use reqwest::Client;
+use ring::signature::{Ed25519KeyPair, KeyPair};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use stellar_strkey::Strkey;
+use stellar_xdr::curr::{
+    AccountId, ContractDataDurability, DecoratedSignature, FeeBumpTransaction,
+    FeeBumpTransactionEnvelope, FeeBumpTransactionExt, FeeBumpTransactionInnerTx, Hash,
+    HostFunction, Int128Parts, InvokeContractArgs, InvokeHostFunctionOp, LedgerEntryData,
+    LedgerKey, LedgerKeyContractData, Limits, Memo, MuxedAccount, Operation, OperationBody,
+    Preconditions, PublicKey, ReadXdr, ScAddress, ScBytes, ScSymbol, ScVal, ScVec, SequenceNumber,
+    Signature, SignatureHint, SorobanAuthorizationEntry, SorobanCredentials,
+    SorobanTransactionData, StringM, TimeBounds, TimePoint, Transaction, TransactionEnvelope,
+    TransactionExt, TransactionSignaturePayload, TransactionSignaturePayloadTaggedTransaction,
+    TransactionV1Envelope, Uint256, VecM, WriteXdr,
+};
+use tokio::sync::{Mutex, RwLock};
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
@@ -88,6 +108,18 @@ pub struct HorizonAccount {
     pub last_modified_time: String,
     pub thresholds: Thresholds,
     pub flags: Flags,
+    #[serde(default)]
+    pub signers: Vec<AccountSigner>,
+}
+
+/// A key allowed to sign for an account, with the weight it carries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountSigner {
+    /// `G…` address for `ed25519_public_key` signers.
+    pub key: String,
+    pub weight: u32,
+    #[serde(rename = "type")]
+    pub signer_type: String,
 }
 
 /// Asset balance for a Stellar account.
@@ -193,6 +225,7 @@ struct JsonRpcError {
 
 /// Soroban RPC `getHealth` result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SorobanHealth {
     pub status: String,
     pub latest_ledger: Option<u64>,
@@ -201,6 +234,7 @@ pub struct SorobanHealth {
 
 /// Soroban RPC `getLedgerEntries` result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct LedgerEntry {
     pub key: String,
     pub xdr: String,
@@ -208,6 +242,7 @@ pub struct LedgerEntry {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetLedgerEntriesResult {
     pub entries: Option<Vec<LedgerEntry>>,
     pub latest_ledger: u64,
@@ -215,11 +250,16 @@ pub struct GetLedgerEntriesResult {
 
 /// Soroban RPC `simulateTransaction` result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SimulateTransactionResult {
     pub error: Option<String>,
     pub results: Option<Vec<SimulateInvocationResult>>,
     pub cost: Option<SimulateCost>,
     pub latest_ledger: u64,
+    /// Base64 XDR `SorobanTransactionData`: footprint and resource limits.
+    pub transaction_data: Option<String>,
+    /// Resource fee, in stroops, to add on top of the inclusion fee.
+    pub min_resource_fee: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -238,6 +278,7 @@ pub struct SimulateCost {
 
 /// Soroban RPC `sendTransaction` result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SendTransactionResult {
     pub status: String,
     pub hash: String,
@@ -249,6 +290,7 @@ pub struct SendTransactionResult {
 
 /// Soroban RPC `getTransaction` result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetTransactionResult {
     pub status: String,
     pub latest_ledger: u64,
@@ -268,11 +310,40 @@ pub struct GetTransactionResult {
     pub result_meta_xdr: Option<String>,
 }
 
+/// A single contract event returned by Soroban RPC `getEvents`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SorobanEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub ledger: u64,
+    pub ledger_closed_at: Option<String>,
+    pub contract_id: String,
+    pub id: String,
+    pub paging_token: Option<String>,
+    /// Base64-encoded XDR `ScVal` topics.
+    pub topic: Vec<String>,
+    /// Base64-encoded XDR `ScVal` event payload.
+    pub value: String,
+    pub tx_hash: Option<String>,
+}
+
+/// Soroban RPC `getEvents` result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEventsResult {
+    pub events: Vec<SorobanEvent>,
+    pub latest_ledger: u64,
+    pub cursor: Option<String>,
+}
+
 /// Known statuses for a pending Soroban transaction.
 pub const SOROBAN_STATUS_PENDING: &str = "PENDING";
 pub const SOROBAN_STATUS_SUCCESS: &str = "SUCCESS";
 pub const SOROBAN_STATUS_ERROR: &str = "ERROR";
 pub const SOROBAN_STATUS_NOT_FOUND: &str = "NOT_FOUND";
+/// `sendTransaction` status when the server declined the transaction for now.
+pub const SOROBAN_STATUS_TRY_AGAIN_LATER: &str = "TRY_AGAIN_LATER";
 
 // ─────────────────────────────────────────────────────────────────────────────
 // HorizonClient
@@ -345,6 +416,12 @@ impl HorizonClient {
                                     }
                                 })?;
 
+                            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                                return Err(ApiError::NotFound(format!(
+                                    "Horizon resource not found: {path}"
+                                )));
+                            }
+
                             if !response.status().is_success() {
                                 let status = response.status();
                                 let body = response.text().await.unwrap_or_default();
@@ -651,26 +728,80 @@ impl SorobanRpcClient {
         self.call("getLatestLedger", json!({})).await
     }
 
-    /// Call `getContractData` to read a specific entry in a contract's storage.
+    /// Call `getEvents` for a single contract.
+    ///
+    /// Pass `start_ledger` on the first call and the returned `cursor` (or the
+    /// last event's paging token) afterwards; the RPC rejects requests that
+    /// carry both.
+    pub async fn get_events(
+        &self,
+        contract_id: &str,
+        start_ledger: Option<u64>,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<GetEventsResult, ApiError> {
+        debug!(
+            contract_id,
+            ?start_ledger,
+            ?cursor,
+            "Fetching contract events"
+        );
+        let mut params = json!({
+            "filters": [{ "type": "contract", "contractIds": [contract_id] }],
+            "pagination": { "limit": limit }
+        });
+        match cursor {
+            Some(c) => params["pagination"]["cursor"] = json!(c),
+            None => params["startLedger"] = json!(start_ledger.unwrap_or(0)),
+        }
+        self.call("getEvents", params).await
+    }
+
+    /// Read one entry of a contract's storage through `getLedgerEntries`.
     ///
-    /// `contract_id` is the contract's Stellar address (C… form).
-    /// `key_xdr`     is the base64-encoded XDR `ScVal` key.
+    /// `contract_id` is the contract's Stellar address (C… form) and `key`
+    /// the storage key as the contract encodes it, see [`contract_enum_key`].
+    /// Returns `None` when the entry does not exist or has been archived.
     pub async fn get_contract_data(
         &self,
         contract_id: &str,
-        key_xdr: &str,
-        durability: &str,
-    ) -> Result<Value, ApiError> {
+        key: &ScVal,
+        durability: ContractDataDurability,
+    ) -> Result<Option<ScVal>, ApiError> {
         debug!(contract_id, "Fetching contract data from Soroban RPC");
-        self.call(
-            "getContractData",
-            json!({
-                "contract": contract_id,
-                "key": key_xdr,
-                "durability": durability
-            }),
-        )
-        .await
+        let key_xdr = contract_data_key(contract_id, key, durability)?;
+        let result = self.get_ledger_entries(vec![key_xdr]).await?;
+        result
+            .entries
+            .unwrap_or_default()
+            .first()
+            .map(|entry| decode_contract_data(&entry.xdr))
+            .transpose()
+    }
+
+    /// Read a value from a contract's instance storage, which lives inside
+    /// the contract instance entry rather than under a key of its own.
+    pub async fn get_instance_data(
+        &self,
+        contract_id: &str,
+        key: &ScVal,
+    ) -> Result<Option<ScVal>, ApiError> {
+        let instance = self
+            .get_contract_data(
+                contract_id,
+                &ScVal::LedgerKeyContractInstance,
+                ContractDataDurability::Persistent,
+            )
+            .await?;
+        let Some(ScVal::ContractInstance(instance)) = instance else {
+            return Ok(None);
+        };
+        Ok(instance.storage.and_then(|storage| {
+            storage
+                .iter()
+                .find(|entry| &entry.key == key)
+                .map(|entry| entry.val.clone())
+        }))
     }
 }
 
@@ -940,6 +1071,1067 @@ pub struct StellarHealthStatus {
     pub network_passphrase: String,
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Contract calls
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Inclusion fee bid per operation, in stroops, when none is configured.
+pub const DEFAULT_BASE_FEE: u32 = 100;
+
+/// Transactions built by the backend expire this long after being built.
+pub const DEFAULT_TX_TIMEOUT_SECS: u64 = 300;
+
+fn xdr_error(context: &str, e: stellar_xdr::curr::Error) -> ApiError {
+    ApiError::Internal(anyhow::anyhow!("{context}: {e}"))
+}
+
+/// Parse a `G…` account or `C…` contract address into an [`ScAddress`].
+pub fn parse_sc_address(address: &str) -> Result<ScAddress, ApiError> {
+    match Strkey::from_string(address) {
+        Ok(Strkey::PublicKeyEd25519(pk)) => Ok(ScAddress::Account(AccountId(
+            PublicKey::PublicKeyTypeEd25519(Uint256(pk.0)),
+        ))),
+        Ok(Strkey::Contract(contract)) => Ok(ScAddress::Contract(Hash(contract.0))),
+        _ => Err(ApiError::BadRequest(format!(
+            "Invalid Stellar address: {address}"
+        ))),
+    }
+}
+
+/// `G…` form of an Ed25519 public key.
+pub fn account_strkey(public_key: &[u8; 32]) -> String {
+    format!(
+        "{}",
+        Strkey::PublicKeyEd25519(stellar_strkey::ed25519::PublicKey(*public_key))
+    )
+}
+
+/// Network ID transactions are signed for: the SHA-256 of the passphrase.
+pub fn network_id(network_passphrase: &str) -> Hash {
+    Hash(Sha256::digest(network_passphrase.as_bytes()).into())
+}
+
+/// One contract function call, the payload of an `InvokeHostFunction`
+/// operation.
+///
+/// Arguments are appended in the order the contract function declares them.
+/// The associated constructors build the calls backend jobs make against the
+/// inheritance, lending and governance contracts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractCall {
+    pub contract: ScAddress,
+    pub function: ScSymbol,
+    pub args: Vec<ScVal>,
+}
+
+impl ContractCall {
+    pub fn new(contract_id: &str, function: &str) -> Result<Self, ApiError> {
+        let contract = parse_sc_address(contract_id)?;
+        if !matches!(contract, ScAddress::Contract(_)) {
+            return Err(ApiError::BadRequest(format!(
+                "{contract_id} is not a contract address"
+            )));
+        }
+        let function = StringM::<32>::try_from(function)
+            .map(ScSymbol)
+            .map_err(|_| ApiError::BadRequest(format!("Invalid function name: {function}")))?;
+        Ok(Self {
+            contract,
+            function,
+            args: Vec::new(),
+        })
+    }
+
+    pub fn arg(mut self, value: ScVal) -> Self {
+        self.args.push(value);
+        self
+    }
+
+    pub fn address_arg(self, address: &str) -> Result<Self, ApiError> {
+        Ok(self.arg(ScVal::Address(parse_sc_address(address)?)))
+    }
+
+    pub fn u32_arg(self, value: u32) -> Self {
+        self.arg(ScVal::U32(value))
+    }
+
+    pub fn u64_arg(self, value: u64) -> Self {
+        self.arg(ScVal::U64(value))
+    }
+
+    pub fn i128_arg(self, value: i128) -> Self {
+        self.arg(ScVal::I128(Int128Parts {
+            hi: (value >> 64) as i64,
+            lo: value as u64,
+        }))
+    }
+
+    pub fn bytes_arg(self, value: &[u8]) -> Result<Self, ApiError> {
+        let bytes = ScBytes::try_from(value.to_vec())
+            .map_err(|e| xdr_error("Contract argument too large", e))?;
+        Ok(self.arg(ScVal::Bytes(bytes)))
+    }
+
+    // ── inheritance contract ──────────────────────────────────────────────────
+
+    /// `confirm_bridge_release(relayer, plan_id, asset_index, remote_tx)`.
+    pub fn confirm_bridge_release(
+        contract_id: &str,
+        relayer: &str,
+        plan_id: u64,
+        asset_index: u32,
+        remote_tx: &[u8],
+    ) -> Result<Self, ApiError> {
+        Self::new(contract_id, "confirm_bridge_release")?
+            .address_arg(relayer)?
+            .u64_arg(plan_id)
+            .u32_arg(asset_index)
+            .bytes_arg(remote_tx)
+    }
+
+    /// `record_activity_as_delegate(delegate, plan_id)`.
+    pub fn record_activity_as_delegate(
+        contract_id: &str,
+        delegate: &str,
+        plan_id: u64,
+    ) -> Result<Self, ApiError> {
+        Ok(Self::new(contract_id, "record_activity_as_delegate")?
+            .address_arg(delegate)?
+            .u64_arg(plan_id))
+    }
+
+    // ── lending contract ──────────────────────────────────────────────────────
+
+    /// `liquidate(liquidator, borrower, amount)`.
+    pub fn liquidate(
+        contract_id: &str,
+        liquidator: &str,
+        borrower: &str,
+        amount: u64,
+    ) -> Result<Self, ApiError> {
+        Ok(Self::new(contract_id, "liquidate")?
+            .address_arg(liquidator)?
+            .address_arg(borrower)?
+            .u64_arg(amount))
+    }
+
+    // ── governance contract ───────────────────────────────────────────────────
+
+    /// `queue_proposal(proposal_id)`.
+    pub fn queue_proposal(contract_id: &str, proposal_id: u32) -> Result<Self, ApiError> {
+        Ok(Self::new(contract_id, "queue_proposal")?.u32_arg(proposal_id))
+    }
+
+    /// `execute_proposal(executor, proposal_id)`.
+    pub fn execute_proposal(
+        contract_id: &str,
+        executor: &str,
+        proposal_id: u32,
+    ) -> Result<Self, ApiError> {
+        Ok(Self::new(contract_id, "execute_proposal")?
+            .address_arg(executor)?
+            .u32_arg(proposal_id))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Contract storage
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Storage key of a `#[contracttype]` enum variant, e.g. `DataKey::Plan(7)`:
+/// the variant name as a symbol followed by its fields.
+pub fn contract_enum_key(variant: &str, fields: Vec<ScVal>) -> Result<ScVal, ApiError> {
+    let name = StringM::<32>::try_from(variant)
+        .map_err(|_| ApiError::BadRequest(format!("Invalid key variant: {variant}")))?;
+    let mut items = vec![ScVal::Symbol(ScSymbol(name))];
+    items.extend(fields);
+    let items = VecM::try_from(items).map_err(|e| xdr_error("Storage key too long", e))?;
+    Ok(ScVal::Vec(Some(ScVec(items))))
+}
+
+/// Base64 XDR `LedgerKey` of a contract storage entry, as `getLedgerEntries`
+/// expects it.
+pub fn contract_data_key(
+    contract_id: &str,
+    key: &ScVal,
+    durability: ContractDataDurability,
+) -> Result<String, ApiError> {
+    let contract = parse_sc_address(contract_id)?;
+    if !matches!(contract, ScAddress::Contract(_)) {
+        return Err(ApiError::BadRequest(format!(
+            "{contract_id} is not a contract address"
+        )));
+    }
+    let ledger_key = LedgerKey::ContractData(LedgerKeyContractData {
+        contract,
+        key: key.clone(),
+        durability,
+    });
+    let bytes = ledger_key
+        .to_xdr(Limits::none())
+        .map_err(|e| xdr_error("Failed to encode ledger key", e))?;
+    Ok(BASE64.encode(bytes))
+}
+
+/// The value stored in a base64 XDR `LedgerEntryData` returned by
+/// `getLedgerEntries` for a contract storage key.
+pub fn decode_contract_data(entry_xdr: &str) -> Result<ScVal, ApiError> {
+    let bytes = BASE64
+        .decode(entry_xdr)
+        .map_err(|e| ApiError::ExternalService(format!("Invalid ledger entry encoding: {e}")))?;
+    match LedgerEntryData::from_xdr(bytes, Limits::none()) {
+        Ok(LedgerEntryData::ContractData(data)) => Ok(data.val),
+        Ok(_) => Err(ApiError::ExternalService(
+            "Ledger entry is not contract data".to_string(),
+        )),
+        Err(e) => Err(ApiError::ExternalService(format!(
+            "Failed to decode ledger entry: {e}"
+        ))),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// ScVal decoding
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Decode a base64 XDR `ScVal`, such as an event topic or body.
+pub(crate) fn decode_scval(xdr: &str) -> Option<ScVal> {
+    let bytes = BASE64.decode(xdr).ok()?;
+    ScVal::from_xdr(bytes, Limits::none()).ok()
+}
+
+pub(crate) fn is_symbol(val: &ScVal, expected: &str) -> bool {
+    matches!(val, ScVal::Symbol(sym) if sym.0.as_slice() == expected.as_bytes())
+}
+
+pub(crate) fn map_field<'a>(val: &'a ScVal, field: &str) -> Option<&'a ScVal> {
+    let ScVal::Map(Some(map)) = val else {
+        return None;
+    };
+    map.iter()
+        .find(|entry| is_symbol(&entry.key, field))
+        .map(|entry| &entry.val)
+}
+
+pub(crate) fn map_u64(val: &ScVal, field: &str) -> Option<u64> {
+    match map_field(val, field)? {
+        ScVal::U64(v) => Some(*v),
+        _ => None,
+    }
+}
+
+pub(crate) fn map_u32(val: &ScVal, field: &str) -> Option<u32> {
+    match map_field(val, field)? {
+        ScVal::U32(v) => Some(*v),
+        _ => None,
+    }
+}
+
+/// An address field in its `G…`/`C…` form.
+pub(crate) fn map_address(val: &ScVal, field: &str) -> Option<String> {
+    match map_field(val, field)? {
+        ScVal::Address(address) => Some(address.to_string()),
+        _ => None,
+    }
+}
+
+pub(crate) fn map_bool(val: &ScVal, field: &str) -> Option<bool> {
+    match map_field(val, field)? {
+        ScVal::Bool(v) => Some(*v),
+        _ => None,
+    }
+}
+
+pub(crate) fn map_u128(val: &ScVal, field: &str) -> Option<u128> {
+    match map_field(val, field)? {
+        ScVal::U128(parts) => Some((u128::from(parts.hi) << 64) | u128::from(parts.lo)),
+        _ => None,
+    }
+}
+
+pub(crate) fn map_string(val: &ScVal, field: &str) -> Option<String> {
+    match map_field(val, field)? {
+        ScVal::String(s) => String::from_utf8(s.0.to_vec()).ok(),
+        _ => None,
+    }
+}
+
+/// Name of a unit variant of a `#[contracttype]` enum field, e.g. `Ethereum`.
+pub(crate) fn map_enum_variant(val: &ScVal, field: &str) -> Option<String> {
+    let ScVal::Vec(Some(items)) = map_field(val, field)? else {
+        return None;
+    };
+    match items.first()? {
+        ScVal::Symbol(sym) => String::from_utf8(sym.0.to_vec()).ok(),
+        _ => None,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Transaction building
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Builds a single-operation `InvokeHostFunction` transaction.
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+    source: [u8; 32],
+    sequence: i64,
+    base_fee: u32,
+    timeout_secs: u64,
+    memo: Memo,
+}
+
+impl TransactionBuilder {
+    /// `sequence` is the sequence number the transaction will carry: one
+    /// more than the source account's current sequence.
+    pub fn new(source: [u8; 32], sequence: i64) -> Self {
+        Self {
+            source,
+            sequence,
+            base_fee: DEFAULT_BASE_FEE,
+            timeout_secs: DEFAULT_TX_TIMEOUT_SECS,
+            memo: Memo::None,
+        }
+    }
+
+    pub fn base_fee(mut self, base_fee: u32) -> Self {
+        self.base_fee = base_fee;
+        self
+    }
+
+    /// Seconds until the transaction expires; `0` means it never does.
+    pub fn timeout_secs(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = timeout_secs;
+        self
+    }
+
+    pub fn memo_text(mut self, text: &str) -> Result<Self, ApiError> {
+        let text = StringM::<28>::try_from(text)
+            .map_err(|_| ApiError::BadRequest("Memo text exceeds 28 bytes".to_string()))?;
+        self.memo = Memo::Text(text);
+        Ok(self)
+    }
+
+    pub fn invoke(self, call: ContractCall) -> Result<SorobanTransaction, ApiError> {
+        let args =
+            VecM::try_from(call.args).map_err(|e| xdr_error("Too many contract arguments", e))?;
+        let operation = Operation {
+            source_account: None,
+            body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                    contract_address: call.contract,
+                    function_name: call.function,
+                    args,
+                }),
+                auth: VecM::default(),
+            }),
+        };
+        let max_time = if self.timeout_secs == 0 {
+            0
+        } else {
+            (chrono::Utc::now().timestamp().max(0) as u64).saturating_add(self.timeout_secs)
+        };
+
+        Ok(SorobanTransaction {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256(self.source)),
+                fee: self.base_fee,
+                seq_num: SequenceNumber(self.sequence),
+                cond: Preconditions::Time(TimeBounds {
+                    min_time: TimePoint(0),
+                    max_time: TimePoint(max_time),
+                }),
+                memo: self.memo,
+                operations: vec![operation]
+                    .try_into()
+                    .map_err(|e| xdr_error("Failed to build operations", e))?,
+                ext: TransactionExt::V0,
+            },
+        })
+    }
+}
+
+/// An unsigned Soroban transaction.
+///
+/// Simulate the unsigned envelope, [`assemble`](Self::assemble) the
+/// simulation's footprint, resources and authorizations into it, then sign.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SorobanTransaction {
+    tx: Transaction,
+}
+
+impl SorobanTransaction {
+    pub fn transaction(&self) -> &Transaction {
+        &self.tx
+    }
+
+    /// Base64 XDR of the envelope without signatures, as `simulateTransaction`
+    /// expects it.
+    pub fn to_envelope_xdr(&self) -> Result<String, ApiError> {
+        envelope_to_base64(&TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: self.tx.clone(),
+            signatures: VecM::default(),
+        }))
+    }
+
+    /// Hash signers sign: SHA-256 of the transaction tagged with the network.
+    pub fn hash(&self, network_passphrase: &str) -> Result<[u8; 32], ApiError> {
+        transaction_hash(network_passphrase, &self.tx)
+    }
+
+    /// Fold a successful simulation into the transaction: its footprint and
+    /// resource limits, the resource fee on top of the inclusion fee, and the
+    /// authorizations the call needs.
+    ///
+    /// Authorizations are only accepted when they are covered by the source
+    /// account's own signature; a call that needs another address to sign
+    /// cannot be completed by the backend.
+    pub fn assemble(mut self, simulation: &SimulateTransactionResult) -> Result<Self, ApiError> {
+        if let Some(error) = &simulation.error {
+            return Err(ApiError::ExternalService(format!(
+                "Transaction simulation failed: {error}"
+            )));
+        }
+        let data_xdr = simulation.transaction_data.as_deref().ok_or_else(|| {
+            ApiError::ExternalService("Simulation returned no transaction data".to_string())
+        })?;
+        let data = SorobanTransactionData::from_xdr(decode_base64(data_xdr)?, Limits::none())
+            .map_err(|e| xdr_error("Invalid simulated transaction data", e))?;
+
+        let min_resource_fee = match simulation.min_resource_fee.as_deref() {
+            Some(fee) => fee.parse::<i64>().map_err(|_| {
+                ApiError::ExternalService(format!("Invalid simulated resource fee: {fee}"))
+            })?,
+            None => data.resource_fee,
+        };
+
+        let mut auth = Vec::new();
+        for result in simulation.results.iter().flatten() {
+            for entry_xdr in &result.auth {
+                let entry =
+                    SorobanAuthorizationEntry::from_xdr(decode_base64(entry_xdr)?, Limits::none())
+                        .map_err(|e| xdr_error("Invalid simulated authorization", e))?;
+                if let SorobanCredentials::Address(credentials) = &entry.credentials {
+                    return Err(ApiError::BadRequest(format!(
+                        "Call needs a signature from {}, which the backend cannot provide",
+                        credentials.address
+                    )));
+                }
+                auth.push(entry);
+            }
+        }
+
+        let mut operations = self.tx.operations.to_vec();
+        match operations.first_mut().map(|op| &mut op.body) {
+            Some(OperationBody::InvokeHostFunction(op)) => {
+                op.auth = auth
+                    .try_into()
+                    .map_err(|e| xdr_error("Too many authorizations", e))?;
+            }
+            _ => {
+                return Err(ApiError::BadRequest(
+                    "Only InvokeHostFunction transactions can be assembled".to_string(),
+                ))
+            }
+        }
+        self.tx.operations = operations
+            .try_into()
+            .map_err(|e| xdr_error("Failed to rebuild operations", e))?;
+
+        let inclusion_fee = match &self.tx.ext {
+            // Re-assembling replaces the resource fee added last time.
+            TransactionExt::V1(previous) => {
+                (self.tx.fee as i64).saturating_sub(previous.resource_fee)
+            }
+            TransactionExt::V0 => self.tx.fee as i64,
+        };
+        self.tx.fee =
+            u32::try_from(inclusion_fee.saturating_add(min_resource_fee)).map_err(|_| {
+                ApiError::ExternalService(format!("Resource fee {min_resource_fee} is too large"))
+            })?;
+        self.tx.ext = TransactionExt::V1(SorobanTransactionData {
+            resource_fee: min_resource_fee,
+            ..data
+        });
+        Ok(self)
+    }
+
+    /// Attach `signature`, made by `public_key` over [`hash`](Self::hash).
+    pub fn with_signature(self, public_key: &[u8; 32], signature: [u8; 64]) -> SignedTransaction {
+        SignedTransaction {
+            envelope: TransactionEnvelope::Tx(TransactionV1Envelope {
+                tx: self.tx,
+                signatures: vec![decorated_signature(public_key, signature)]
+                    .try_into()
+                    .expect("one signature fits in an envelope"),
+            }),
+        }
+    }
+}
+
+/// A signed envelope, ready for `sendTransaction`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedTransaction {
+    envelope: TransactionEnvelope,
+}
+
+impl SignedTransaction {
+    pub fn envelope(&self) -> &TransactionEnvelope {
+        &self.envelope
+    }
+
+    pub fn to_xdr(&self) -> Result<String, ApiError> {
+        envelope_to_base64(&self.envelope)
+    }
+
+    /// Hash the network identifies the transaction by; for a fee bump this
+    /// is the hash of the outer transaction.
+    pub fn hash(&self, network_passphrase: &str) -> Result<[u8; 32], ApiError> {
+        let tagged = match &self.envelope {
+            TransactionEnvelope::Tx(env) => {
+                TransactionSignaturePayloadTaggedTransaction::Tx(env.tx.clone())
+            }
+            TransactionEnvelope::TxFeeBump(env) => {
+                TransactionSignaturePayloadTaggedTransaction::TxFeeBump(env.tx.clone())
+            }
+            TransactionEnvelope::TxV0(_) => {
+                return Err(ApiError::BadRequest(
+                    "V0 envelopes are not supported".to_string(),
+                ))
+            }
+        };
+        payload_hash(network_passphrase, tagged)
+    }
+
+    /// Wrap the transaction in an unsigned fee bump paid by `fee_source`,
+    /// bidding `base_fee` per operation on top of the inner resource fee.
+    pub fn fee_bump_transaction(
+        &self,
+        fee_source: &[u8; 32],
+        base_fee: u32,
+    ) -> Result<FeeBumpTransaction, ApiError> {
+        let TransactionEnvelope::Tx(inner) = &self.envelope else {
+            return Err(ApiError::BadRequest(
+                "Only signed V1 transactions can be fee-bumped".to_string(),
+            ));
+        };
+        let resource_fee = match &inner.tx.ext {
+            TransactionExt::V1(data) => data.resource_fee,
+            TransactionExt::V0 => 0,
+        };
+        // A fee bump counts as one more operation than it wraps.
+        let operations = inner.tx.operations.len() as i64 + 1;
+        let fee = (base_fee as i64)
+            .saturating_mul(operations)
+            .saturating_add(resource_fee);
+        if fee <= inner.tx.fee as i64 {
+            return Err(ApiError::BadRequest(format!(
+                "Fee bump of {fee} does not exceed the inner fee of {}",
+                inner.tx.fee
+            )));
+        }
+        Ok(FeeBumpTransaction {
+            fee_source: MuxedAccount::Ed25519(Uint256(*fee_source)),
+            fee,
+            inner_tx: FeeBumpTransactionInnerTx::Tx(inner.clone()),
+            ext: FeeBumpTransactionExt::V0,
+        })
+    }
+}
+
+fn signed_fee_bump(
+    bump: FeeBumpTransaction,
+    public_key: &[u8; 32],
+    signature: [u8; 64],
+) -> SignedTransaction {
+    SignedTransaction {
+        envelope: TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope {
+            tx: bump,
+            signatures: vec![decorated_signature(public_key, signature)]
+                .try_into()
+                .expect("one signature fits in an envelope"),
+        }),
+    }
+}
+
+/// Hash signers sign for a V1 transaction: SHA-256 of the transaction tagged
+/// with the network.
+pub fn transaction_hash(network_passphrase: &str, tx: &Transaction) -> Result<[u8; 32], ApiError> {
+    payload_hash(
+        network_passphrase,
+        TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()),
+    )
+}
+
+/// `signature` by `public_key`, hinted with the key's last four bytes.
+pub fn decorated_signature(public_key: &[u8; 32], signature: [u8; 64]) -> DecoratedSignature {
+    let mut hint = [0u8; 4];
+    hint.copy_from_slice(&public_key[28..]);
+    DecoratedSignature {
+        hint: SignatureHint(hint),
+        signature: Signature(
+            signature
+                .to_vec()
+                .try_into()
+                .expect("ed25519 signatures are 64 bytes"),
+        ),
+    }
+}
+
+fn payload_hash(
+    network_passphrase: &str,
+    tagged_transaction: TransactionSignaturePayloadTaggedTransaction,
+) -> Result<[u8; 32], ApiError> {
+    let payload = TransactionSignaturePayload {
+        network_id: network_id(network_passphrase),
+        tagged_transaction,
+    };
+    let bytes = payload
+        .to_xdr(Limits::none())
+        .map_err(|e| xdr_error("Failed to encode signature payload", e))?;
+    Ok(Sha256::digest(bytes).into())
+}
+
+fn envelope_to_base64(envelope: &TransactionEnvelope) -> Result<String, ApiError> {
+    let bytes = envelope
+        .to_xdr(Limits::none())
+        .map_err(|e| xdr_error("Failed to encode transaction envelope", e))?;
+    Ok(BASE64.encode(bytes))
+}
+
+fn decode_base64(value: &str) -> Result<Vec<u8>, ApiError> {
+    BASE64
+        .decode(value)
+        .map_err(|e| ApiError::ExternalService(format!("Invalid base64 XDR from RPC: {e}")))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Signing
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Signs transaction hashes on behalf of one Stellar account.
+///
+/// [`InMemorySigner`] keeps the secret key in-process and is meant for tests
+/// and local networks; [`ExternalSigner`] delegates to a signing service so
+/// production keys never enter the backend.
+#[async_trait]
+pub trait TransactionSigner: Send + Sync {
+    /// Ed25519 public key of the signing account.
+    fn public_key(&self) -> [u8; 32];
+
+    /// Ed25519 signature over a 32-byte transaction hash.
+    async fn sign_hash(&self, hash: &[u8; 32]) -> Result<[u8; 64], ApiError>;
+
+    /// `G…` address of the signing account.
+    fn account_id(&self) -> String {
+        account_strkey(&self.public_key())
+    }
+}
+
+/// Signer holding an Ed25519 secret key in memory.
+pub struct InMemorySigner {
+    key_pair: Ed25519KeyPair,
+    public_key: [u8; 32],
+}
+
+impl InMemorySigner {
+    pub fn from_seed(seed: &[u8; 32]) -> Result<Self, ApiError> {
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(seed)
+            .map_err(|e| ApiError::BadRequest(format!("Invalid Ed25519 seed: {e}")))?;
+        let mut public_key = [0u8; 32];
+        public_key.copy_from_slice(key_pair.public_key().as_ref());
+        Ok(Self {
+            key_pair,
+            public_key,
+        })
+    }
+
+    /// Build from an `S…` secret seed.
+    pub fn from_secret(secret: &str) -> Result<Self, ApiError> {
+        match Strkey::from_string(secret) {
+            Ok(Strkey::PrivateKeyEd25519(seed)) => Self::from_seed(&seed.0),
+            _ => Err(ApiError::BadRequest(
+                "Invalid Stellar secret seed".to_string(),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for InMemorySigner {
+    fn public_key(&self) -> [u8; 32] {
+        self.public_key
+    }
+
+    async fn sign_hash(&self, hash: &[u8; 32]) -> Result<[u8; 64], ApiError> {
+        let mut signature = [0u8; 64];
+        signature.copy_from_slice(self.key_pair.sign(hash).as_ref());
+        Ok(signature)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SignRequest<'a> {
+    account_id: &'a str,
+    hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SignResponse {
+    signature: String,
+}
+
+/// Signer backed by a remote signing service (an HSM or KMS front end).
+///
+/// Each hash is sent as `POST {url}` with `{"account_id": "G…", "hash":
+/// "<hex>"}`; the service answers `{"signature": "<hex>"}`.
+#[derive(Clone)]
+pub struct ExternalSigner {
+    client: Client,
+    url: String,
+    auth_token: Option<String>,
+    public_key: [u8; 32],
+}
+
+impl ExternalSigner {
+    /// `account_id` is the `G…` account the service signs for.
+    pub fn new(
+        url: impl Into<String>,
+        account_id: &str,
+        auth_token: Option<String>,
+    ) -> Result<Self, ApiError> {
+        let public_key = match Strkey::from_string(account_id) {
+            Ok(Strkey::PublicKeyEd25519(pk)) => pk.0,
+            _ => {
+                return Err(ApiError::BadRequest(format!(
+                    "Invalid Stellar account: {account_id}"
+                )))
+            }
+        };
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("failed to build reqwest client");
+        Ok(Self {
+            client,
+            url: url.into(),
+            auth_token,
+            public_key,
+        })
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for ExternalSigner {
+    fn public_key(&self) -> [u8; 32] {
+        self.public_key
+    }
+
+    async fn sign_hash(&self, hash: &[u8; 32]) -> Result<[u8; 64], ApiError> {
+        let account_id = self.account_id();
+        let mut request = self.client.post(&self.url).json(&SignRequest {
+            account_id: &account_id,
+            hash: hex::encode(hash),
+        });
+        if let Some(token) = &self.auth_token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ApiError::ExternalService(format!("Signing service unreachable: {e}")))?;
+        if !response.status().is_success() {
+            return Err(ApiError::ExternalService(format!(
+                "Signing service returned {}",
+                response.status()
+            )));
+        }
+        let body: SignResponse = response.json().await.map_err(|e| {
+            ApiError::ExternalService(format!("Invalid signing service response: {e}"))
+        })?;
+
+        let bytes = hex::decode(&body.signature).map_err(|e| {
+            ApiError::ExternalService(format!("Invalid signature from signing service: {e}"))
+        })?;
+        <[u8; 64]>::try_from(bytes.as_slice()).map_err(|_| {
+            ApiError::ExternalService("Signing service returned a malformed signature".to_string())
+        })
+    }
+}
+
+/// Signer configured through the environment, if any.
+///
+/// | Variable                 | Meaning                                    |
+/// |--------------------------|--------------------------------------------|
+/// | `STELLAR_SIGNER_SECRET`  | `S…` seed for an [`InMemorySigner`]        |
+/// | `STELLAR_SIGNER_URL`     | endpoint of an [`ExternalSigner`] service  |
+/// | `STELLAR_SIGNER_ACCOUNT` | `G…` account the signing service signs for |
+/// | `STELLAR_SIGNER_TOKEN`   | optional bearer token for the service      |
+///
+/// A secret seed takes precedence over a signing service.
+pub fn signer_from_env() -> Result<Option<Arc<dyn TransactionSigner>>, ApiError> {
+    if let Ok(secret) = std::env::var("STELLAR_SIGNER_SECRET") {
+        return Ok(Some(Arc::new(InMemorySigner::from_secret(&secret)?)));
+    }
+    let Ok(url) = std::env::var("STELLAR_SIGNER_URL") else {
+        return Ok(None);
+    };
+    let account = std::env::var("STELLAR_SIGNER_ACCOUNT").map_err(|_| {
+        ApiError::BadRequest(
+            "STELLAR_SIGNER_ACCOUNT must be set with STELLAR_SIGNER_URL".to_string(),
+        )
+    })?;
+    let token = std::env::var("STELLAR_SIGNER_TOKEN").ok();
+    Ok(Some(Arc::new(ExternalSigner::new(url, &account, token)?)))
+}
+
+impl SorobanTransaction {
+    /// Sign with `signer`, which must be the transaction's source account.
+    pub async fn sign(
+        self,
+        signer: &dyn TransactionSigner,
+        network_passphrase: &str,
+    ) -> Result<SignedTransaction, ApiError> {
+        let hash = self.hash(network_passphrase)?;
+        let signature = signer.sign_hash(&hash).await?;
+        Ok(self.with_signature(&signer.public_key(), signature))
+    }
+}
+
+impl SignedTransaction {
+    /// Wrap the transaction in a fee bump paid and signed by `fee_source`.
+    pub async fn fee_bump(
+        self,
+        fee_source: &dyn TransactionSigner,
+        base_fee: u32,
+        network_passphrase: &str,
+    ) -> Result<SignedTransaction, ApiError> {
+        let public_key = fee_source.public_key();
+        let bump = self.fee_bump_transaction(&public_key, base_fee)?;
+        let hash = payload_hash(
+            network_passphrase,
+            TransactionSignaturePayloadTaggedTransaction::TxFeeBump(bump.clone()),
+        )?;
+        let signature = fee_source.sign_hash(&hash).await?;
+        Ok(signed_fee_bump(bump, &public_key, signature))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Sequence numbers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Hands out sequence numbers for the accounts the backend submits from.
+///
+/// The first number for an account is read from Horizon; later ones are
+/// issued locally so jobs can submit back-to-back without waiting for each
+/// transaction to close. Call [`SequenceManager::invalidate`] when a
+/// transaction built with an issued number is not accepted, so the next one
+/// starts again from the network's view.
+#[derive(Clone)]
+pub struct SequenceManager {
+    horizon: HorizonClient,
+    last_issued: Arc<Mutex<HashMap<String, i64>>>,
+}
+
+impl SequenceManager {
+    pub fn new(horizon: HorizonClient) -> Self {
+        Self {
+            horizon,
+            last_issued: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Sequence number the next transaction from `account_id` should carry.
+    pub async fn next(&self, account_id: &str) -> Result<i64, ApiError> {
+        let mut issued = self.last_issued.lock().await;
+        let next = match issued.get(account_id) {
+            Some(last) => last + 1,
+            None => {
+                let account = self.horizon.get_account(account_id).await?;
+                let current: i64 = account.sequence.parse().map_err(|_| {
+                    ApiError::ExternalService(format!(
+                        "Invalid sequence number for {account_id}: {}",
+                        account.sequence
+                    ))
+                })?;
+                current + 1
+            }
+        };
+        issued.insert(account_id.to_string(), next);
+        Ok(next)
+    }
+
+    /// Forget the locally issued sequence for `account_id`.
+    pub async fn invalidate(&self, account_id: &str) {
+        self.last_issued.lock().await.remove(account_id);
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// ContractInvoker
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Builds, simulates, signs and submits contract calls from one backend
+/// account, optionally with fees paid by a separate account through a fee
+/// bump.
+#[derive(Clone)]
+pub struct ContractInvoker {
+    stellar: StellarClient,
+    signer: Arc<dyn TransactionSigner>,
+    fee_payer: Option<Arc<dyn TransactionSigner>>,
+    sequences: SequenceManager,
+    base_fee: u32,
+    timeout_secs: u64,
+    confirm_poll_interval: Duration,
+    confirm_max_attempts: u32,
+}
+
+impl ContractInvoker {
+    pub fn new(stellar: StellarClient, signer: Arc<dyn TransactionSigner>) -> Self {
+        let sequences = SequenceManager::new(stellar.horizon.clone());
+        Self {
+            stellar,
+            signer,
+            fee_payer: None,
+            sequences,
+            base_fee: DEFAULT_BASE_FEE,
+            timeout_secs: DEFAULT_TX_TIMEOUT_SECS,
+            confirm_poll_interval: Duration::from_secs(2),
+            confirm_max_attempts: 30,
+        }
+    }
+
+    /// Build from the environment: the signer from [`signer_from_env`] and,
+    /// when `STELLAR_FEE_PAYER_SECRET` is set, a fee payer holding that seed.
+    /// Returns `None` when no signer is configured.
+    pub fn from_env(stellar: StellarClient) -> Result<Option<Self>, ApiError> {
+        let Some(signer) = signer_from_env()? else {
+            return Ok(None);
+        };
+        let mut invoker = Self::new(stellar, signer);
+        if let Ok(secret) = std::env::var("STELLAR_FEE_PAYER_SECRET") {
+            invoker = invoker.with_fee_payer(Arc::new(InMemorySigner::from_secret(&secret)?));
+        }
+        if let Some(base_fee) = std::env::var("STELLAR_BASE_FEE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            invoker = invoker.with_base_fee(base_fee);
+        }
+        Ok(Some(invoker))
+    }
+
+    /// Pay for every transaction with a fee bump signed by `fee_payer`.
+    pub fn with_fee_payer(mut self, fee_payer: Arc<dyn TransactionSigner>) -> Self {
+        self.fee_payer = Some(fee_payer);
+        self
+    }
+
+    pub fn with_base_fee(mut self, base_fee: u32) -> Self {
+        self.base_fee = base_fee;
+        self
+    }
+
+    /// How [`ContractInvoker::invoke_and_confirm`] polls for the outcome.
+    pub fn with_confirmation_polling(mut self, interval: Duration, max_attempts: u32) -> Self {
+        self.confirm_poll_interval = interval;
+        self.confirm_max_attempts = max_attempts;
+        self
+    }
+
+    /// `G…` account the invoker submits from.
+    pub fn source_account(&self) -> String {
+        self.signer.account_id()
+    }
+
+    /// Build, simulate, sign and send `call`, and register it with the
+    /// [`TransactionMonitor`]. Returns once the RPC has accepted it.
+    pub async fn invoke(&self, call: ContractCall) -> Result<SendTransactionResult, ApiError> {
+        let source = self.source_account();
+        let sequence = self.sequences.next(&source).await?;
+        let result = self.submit(call, sequence).await;
+        if result.is_err() {
+            // The sequence number was not consumed; resync before reusing it.
+            self.sequences.invalidate(&source).await;
+        }
+        result
+    }
+
+    /// Like [`ContractInvoker::invoke`], then wait for the transaction to be
+    /// applied. Fails if it fails on-chain or is not seen in time.
+    pub async fn invoke_and_confirm(
+        &self,
+        call: ContractCall,
+    ) -> Result<GetTransactionResult, ApiError> {
+        let sent = self.invoke(call).await?;
+        self.confirm(&sent.hash).await
+    }
+
+    /// Wait for a sent transaction to be applied. Fails if it fails on-chain
+    /// or is not seen in time.
+    pub async fn confirm(&self, hash: &str) -> Result<GetTransactionResult, ApiError> {
+        for _ in 0..self.confirm_max_attempts {
+            let result = self.stellar.soroban.get_transaction(hash).await?;
+            match result.status.as_str() {
+                SOROBAN_STATUS_SUCCESS => return Ok(result),
+                SOROBAN_STATUS_ERROR => {
+                    return Err(ApiError::ExternalService(format!(
+                        "Transaction {hash} failed on-chain"
+                    )))
+                }
+                _ => sleep(self.confirm_poll_interval).await,
+            }
+        }
+        warn!(hash = %hash, "Transaction not confirmed in time");
+        Err(ApiError::Timeout)
+    }
+
+    async fn submit(
+        &self,
+        call: ContractCall,
+        sequence: i64,
+    ) -> Result<SendTransactionResult, ApiError> {
+        let passphrase = &self.stellar.config.network_passphrase;
+        let tx = TransactionBuilder::new(self.signer.public_key(), sequence)
+            .base_fee(self.base_fee)
+            .timeout_secs(self.timeout_secs)
+            .invoke(call)?;
+
+        let simulation = self
+            .stellar
+            .soroban
+            .simulate_transaction(&tx.to_envelope_xdr()?)
+            .await?;
+        let mut signed = tx
+            .assemble(&simulation)?
+            .sign(self.signer.as_ref(), passphrase)
+            .await?;
+        if let Some(fee_payer) = &self.fee_payer {
+            signed = signed
+                .fee_bump(fee_payer.as_ref(), self.base_fee, passphrase)
+                .await?;
+        }
+
+        let sent = self
+            .stellar
+            .soroban
+            .send_transaction(&signed.to_xdr()?)
+            .await?;
+        if sent.status == SOROBAN_STATUS_ERROR || sent.status == SOROBAN_STATUS_TRY_AGAIN_LATER {
+            return Err(ApiError::ExternalService(format!(
+                "Transaction {} not accepted ({}): {}",
+                sent.hash,
+                sent.status,
+                sent.error_result_xdr.as_deref().unwrap_or("no result")
+            )));
+        }
+        self.stellar.monitor.track(sent.hash.clone()).await;
+        Ok(sent)
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Unit tests
 // ─────────────────────────────────────────────────────────────────────────────
@@ -1003,6 +2195,175 @@ mod tests {
         assert!(!client.rpc_url.ends_with('/'));
     }
 
+    fn contract_id() -> String {
+        format!("{}", Strkey::Contract(stellar_strkey::Contract([7; 32])))
+    }
+
+    fn simulation(resource_fee: i64) -> SimulateTransactionResult {
+        let data = SorobanTransactionData {
+            ext: stellar_xdr::curr::ExtensionPoint::V0,
+            resources: stellar_xdr::curr::SorobanResources {
+                footprint: stellar_xdr::curr::LedgerFootprint {
+                    read_only: VecM::default(),
+                    read_write: VecM::default(),
+                },
+                instructions: 1_000,
+                read_bytes: 200,
+                write_bytes: 100,
+            },
+            resource_fee: 0,
+        };
+        SimulateTransactionResult {
+            error: None,
+            results: Some(vec![SimulateInvocationResult {
+                auth: vec![],
+                xdr: String::new(),
+            }]),
+            cost: None,
+            latest_ledger: 1,
+            transaction_data: Some(BASE64.encode(data.to_xdr(Limits::none()).unwrap())),
+            min_resource_fee: Some(resource_fee.to_string()),
+        }
+    }
+
+    #[test]
+    fn rpc_results_read_camel_case_fields() {
+        let sim: SimulateTransactionResult = serde_json::from_value(json!({
+            "transactionData": "AAAA",
+            "minResourceFee": "58181",
+            "results": [{ "auth": [], "xdr": "AAAAAQ==" }],
+            "cost": { "cpuInsns": "1", "memBytes": "2" },
+            "latestLedger": 1_234
+        }))
+        .unwrap();
+        assert_eq!(sim.latest_ledger, 1_234);
+        assert_eq!(sim.min_resource_fee.as_deref(), Some("58181"));
+
+        let health: SorobanHealth = serde_json::from_value(json!({
+            "status": "healthy",
+            "latestLedger": 2_000,
+            "oldestLedger": 1_000
+        }))
+        .unwrap();
+        assert_eq!(health.oldest_ledger, Some(1_000));
+    }
+
+    #[test]
+    fn network_id_is_hash_of_passphrase() {
+        assert_eq!(
+            hex::encode(network_id(&testnet_config().network_passphrase).0),
+            "cee0302d59844d32bdca915c8203dd44b33fbb7edc19051ea37abedf28ecd472"
+        );
+    }
+
+    #[test]
+    fn contract_call_requires_contract_address() {
+        let account = account_strkey(&[1; 32]);
+        assert!(matches!(
+            ContractCall::new(&account, "liquidate"),
+            Err(ApiError::BadRequest(_))
+        ));
+
+        let call =
+            ContractCall::confirm_bridge_release(&contract_id(), &account, 9, 2, b"0xabc").unwrap();
+        assert_eq!(
+            call.function,
+            ScSymbol(StringM::try_from("confirm_bridge_release").unwrap())
+        );
+        assert_eq!(call.args.len(), 4);
+        assert_eq!(call.args[1], ScVal::U64(9));
+        assert_eq!(call.args[2], ScVal::U32(2));
+
+        let call = ContractCall::record_activity_as_delegate(&contract_id(), &account, 4).unwrap();
+        assert_eq!(
+            call.function,
+            ScSymbol(StringM::try_from("record_activity_as_delegate").unwrap())
+        );
+        assert_eq!(call.args[1], ScVal::U64(4));
+    }
+
+    #[test]
+    fn assemble_adds_resource_fee_once() {
+        let call = ContractCall::queue_proposal(&contract_id(), 3).unwrap();
+        let tx = TransactionBuilder::new([1; 32], 42)
+            .invoke(call)
+            .unwrap()
+            .assemble(&simulation(600))
+            .unwrap();
+        assert_eq!(tx.transaction().fee, DEFAULT_BASE_FEE + 600);
+        assert_eq!(tx.transaction().seq_num, SequenceNumber(42));
+        let TransactionExt::V1(data) = &tx.transaction().ext else {
+            panic!("assembled transaction carries no Soroban data");
+        };
+        assert_eq!(data.resource_fee, 600);
+        assert_eq!(data.resources.instructions, 1_000);
+
+        // Re-simulating replaces the earlier resource fee instead of adding to it.
+        let tx = tx.assemble(&simulation(800)).unwrap();
+        assert_eq!(tx.transaction().fee, DEFAULT_BASE_FEE + 800);
+    }
+
+    #[test]
+    fn assemble_rejects_failed_simulation() {
+        let call = ContractCall::queue_proposal(&contract_id(), 3).unwrap();
+        let tx = TransactionBuilder::new([1; 32], 42).invoke(call).unwrap();
+        let mut sim = simulation(600);
+        sim.error = Some("HostError: Error(Contract, #5)".to_string());
+        assert!(matches!(
+            tx.assemble(&sim),
+            Err(ApiError::ExternalService(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn signed_and_fee_bumped_transactions_verify() {
+        use ring::signature::{UnparsedPublicKey, ED25519};
+
+        let passphrase = testnet_config().network_passphrase;
+        let signer = InMemorySigner::from_seed(&[1; 32]).unwrap();
+        let fee_payer = InMemorySigner::from_seed(&[2; 32]).unwrap();
+        let call = ContractCall::execute_proposal(&contract_id(), &signer.account_id(), 3).unwrap();
+
+        let signed = TransactionBuilder::new(signer.public_key(), 7)
+            .invoke(call)
+            .unwrap()
+            .assemble(&simulation(600))
+            .unwrap()
+            .sign(&signer, &passphrase)
+            .await
+            .unwrap();
+        let TransactionEnvelope::Tx(inner) = signed.envelope() else {
+            panic!("expected a v1 envelope");
+        };
+        UnparsedPublicKey::new(&ED25519, signer.public_key())
+            .verify(
+                &signed.hash(&passphrase).unwrap(),
+                inner.signatures[0].signature.as_slice(),
+            )
+            .expect("source signature verifies");
+        // A fee bump has to bid more than the inner transaction already pays.
+        assert!(signed
+            .fee_bump_transaction(&fee_payer.public_key(), 50)
+            .is_err());
+
+        let bumped = signed.fee_bump(&fee_payer, 200, &passphrase).await.unwrap();
+        let TransactionEnvelope::TxFeeBump(outer) = bumped.envelope() else {
+            panic!("expected a fee bump envelope");
+        };
+        // Two operations' worth of inclusion fee plus the resource fee.
+        assert_eq!(outer.tx.fee, 2 * 200 + 600);
+        assert_eq!(
+            outer.tx.fee_source,
+            MuxedAccount::Ed25519(Uint256(fee_payer.public_key()))
+        );
+        UnparsedPublicKey::new(&ED25519, fee_payer.public_key())
+            .verify(
+                &bumped.hash(&passphrase).unwrap(),
+                outer.signatures[0].signature.as_slice(),
+            )
+            .expect("fee payer signature verifies");
+    }
+
     #[tokio::test]
     async fn transaction_monitor_track_and_get() {
         let soroban = SorobanRpcClient::new(testnet_config());
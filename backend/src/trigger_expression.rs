@@ -0,0 +1,477 @@
+//! # Trigger Expressions
+//!
+//! Backend mirror of the inheritance contract's composable trigger logic.
+//! Clients describe an expression as a nested JSON tree; before it is
+//! submitted on-chain we validate it against the contract's bounds, flatten
+//! it into the contract's node layout (node `0` is the root, children follow
+//! their parent), and preview how it behaves — including which single
+//! signals would be enough to fire it on their own.
+//!
+//! ```json
+//! { "type": "or", "children": [
+//!     { "type": "and", "children": [
+//!         { "type": "condition", "condition": "inactivity" },
+//!         { "type": "condition", "condition": "oracle" } ] },
+//!     { "type": "and", "children": [
+//!         { "type": "condition", "condition": "time" },
+//!         { "type": "guardian_approvals", "count": 2 } ] } ] }
+//! ```
+
+use crate::api_error::ApiError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Must match `MAX_TRIGGER_NODES` in the inheritance contract.
+pub const MAX_TRIGGER_NODES: usize = 16;
+
+/// Must match `MAX_TRIGGER_CHILDREN` in the inheritance contract.
+pub const MAX_TRIGGER_CHILDREN: usize = 8;
+
+/// Leaf conditions, matching the contract's `TriggerConditionType`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerCondition {
+    Manual,
+    Time,
+    Inactivity,
+    Oracle,
+    Health,
+}
+
+impl fmt::Display for TriggerCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TriggerCondition::Manual => "Manual",
+            TriggerCondition::Time => "Time",
+            TriggerCondition::Inactivity => "Inactivity",
+            TriggerCondition::Oracle => "Oracle",
+            TriggerCondition::Health => "Health",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Nested expression as submitted by clients.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TriggerExpr {
+    Condition { condition: TriggerCondition },
+    GuardianApprovals { count: u32 },
+    And { children: Vec<TriggerExpr> },
+    Or { children: Vec<TriggerExpr> },
+    Threshold { k: u32, children: Vec<TriggerExpr> },
+}
+
+/// One node in the contract's flat layout (`TriggerNode` on-chain).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FlatTriggerNode {
+    Condition { condition: TriggerCondition },
+    GuardianApprovals { count: u32 },
+    And { children: Vec<u32> },
+    Or { children: Vec<u32> },
+    Threshold { k: u32, children: Vec<u32> },
+}
+
+/// Hypothetical on-chain state to evaluate an expression against.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TriggerState {
+    #[serde(default)]
+    pub met_conditions: Vec<TriggerCondition>,
+    #[serde(default)]
+    pub guardian_approvals: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PreviewTriggerExpressionRequest {
+    pub expression: TriggerExpr,
+    pub state: Option<TriggerState>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TriggerExpressionPreview {
+    pub description: String,
+    pub node_count: usize,
+    /// Nodes in the order to submit to `set_trigger_expression`.
+    pub nodes: Vec<FlatTriggerNode>,
+    /// Outcome against the supplied state, if any.
+    pub fires: Option<bool>,
+    /// Signals that would fire the expression on their own.
+    pub single_points_of_failure: Vec<String>,
+}
+
+impl TriggerExpr {
+    fn children(&self) -> &[TriggerExpr] {
+        match self {
+            TriggerExpr::And { children }
+            | TriggerExpr::Or { children }
+            | TriggerExpr::Threshold { children, .. } => children,
+            TriggerExpr::Condition { .. } | TriggerExpr::GuardianApprovals { .. } => &[],
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        1 + self
+            .children()
+            .iter()
+            .map(|c| c.node_count())
+            .sum::<usize>()
+    }
+
+    /// Check the expression against the contract's bounds.
+    pub fn validate(&self) -> Result<(), ApiError> {
+        let count = self.node_count();
+        if count > MAX_TRIGGER_NODES {
+            return Err(ApiError::Validation(format!(
+                "trigger expression has {count} nodes; at most {MAX_TRIGGER_NODES} are allowed"
+            )));
+        }
+        self.validate_node()
+    }
+
+    fn validate_node(&self) -> Result<(), ApiError> {
+        match self {
+            TriggerExpr::Condition { .. } => {}
+            TriggerExpr::GuardianApprovals { count } => {
+                if *count == 0 {
+                    return Err(ApiError::Validation(
+                        "guardian_approvals count must be at least 1".to_string(),
+                    ));
+                }
+            }
+            TriggerExpr::And { children }
+            | TriggerExpr::Or { children }
+            | TriggerExpr::Threshold { children, .. } => {
+                if children.is_empty() || children.len() > MAX_TRIGGER_CHILDREN {
+                    return Err(ApiError::Validation(format!(
+                        "and/or/threshold nodes need between 1 and {MAX_TRIGGER_CHILDREN} children"
+                    )));
+                }
+                if let TriggerExpr::Threshold { k, .. } = self {
+                    if *k == 0 || *k as usize > children.len() {
+                        return Err(ApiError::Validation(format!(
+                            "threshold k must be between 1 and {}",
+                            children.len()
+                        )));
+                    }
+                }
+                for child in children {
+                    child.validate_node()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Flatten into the contract layout: pre-order, so every child index is
+    /// greater than its parent's.
+    pub fn flatten(&self) -> Vec<FlatTriggerNode> {
+        let mut nodes = Vec::with_capacity(self.node_count());
+        self.flatten_into(&mut nodes);
+        nodes
+    }
+
+    fn flatten_into(&self, nodes: &mut Vec<FlatTriggerNode>) -> u32 {
+        let index = nodes.len();
+        nodes.push(FlatTriggerNode::GuardianApprovals { count: 0 });
+        let child_indexes: Vec<u32> = self
+            .children()
+            .iter()
+            .map(|c| c.flatten_into(nodes))
+            .collect();
+        nodes[index] = match self {
+            TriggerExpr::Condition { condition } => FlatTriggerNode::Condition {
+                condition: *condition,
+            },
+            TriggerExpr::GuardianApprovals { count } => {
+                FlatTriggerNode::GuardianApprovals { count: *count }
+            }
+            TriggerExpr::And { .. } => FlatTriggerNode::And {
+                children: child_indexes,
+            },
+            TriggerExpr::Or { .. } => FlatTriggerNode::Or {
+                children: child_indexes,
+            },
+            TriggerExpr::Threshold { k, .. } => FlatTriggerNode::Threshold {
+                k: *k,
+                children: child_indexes,
+            },
+        };
+        index as u32
+    }
+
+    pub fn evaluate(&self, state: &TriggerState) -> bool {
+        let true_children = || self.children().iter().filter(|c| c.evaluate(state)).count();
+        match self {
+            TriggerExpr::Condition { condition } => state.met_conditions.contains(condition),
+            TriggerExpr::GuardianApprovals { count } => state.guardian_approvals >= *count,
+            TriggerExpr::And { children } => true_children() == children.len(),
+            TriggerExpr::Or { .. } => true_children() > 0,
+            TriggerExpr::Threshold { k, .. } => true_children() >= *k as usize,
+        }
+    }
+
+    fn collect_conditions(&self, out: &mut Vec<TriggerCondition>) {
+        if let TriggerExpr::Condition { condition } = self {
+            if !out.contains(condition) {
+                out.push(*condition);
+            }
+        }
+        for child in self.children() {
+            child.collect_conditions(out);
+        }
+    }
+
+    fn max_guardian_approvals(&self) -> u32 {
+        let own = match self {
+            TriggerExpr::GuardianApprovals { count } => *count,
+            _ => 0,
+        };
+        self.children()
+            .iter()
+            .map(|c| c.max_guardian_approvals())
+            .fold(own, u32::max)
+    }
+
+    /// Signals that satisfy the expression with nothing else met: each
+    /// condition on its own, and guardian approvals on their own.
+    pub fn single_points_of_failure(&self) -> Vec<String> {
+        let mut conditions = Vec::new();
+        self.collect_conditions(&mut conditions);
+
+        let mut found: Vec<String> = conditions
+            .into_iter()
+            .filter(|c| {
+                self.evaluate(&TriggerState {
+                    met_conditions: vec![*c],
+                    guardian_approvals: 0,
+                })
+            })
+            .map(|c| c.to_string())
+            .collect();
+
+        let approvals = self.max_guardian_approvals();
+        if approvals > 0
+            && self.evaluate(&TriggerState {
+                met_conditions: Vec::new(),
+                guardian_approvals: approvals,
+            })
+        {
+            found.push("guardian approvals".to_string());
+        }
+        found
+    }
+
+    pub fn preview(
+        &self,
+        state: Option<&TriggerState>,
+    ) -> Result<TriggerExpressionPreview, ApiError> {
+        self.validate()?;
+        Ok(TriggerExpressionPreview {
+            description: self.to_string(),
+            node_count: self.node_count(),
+            nodes: self.flatten(),
+            fires: state.map(|s| self.evaluate(s)),
+            single_points_of_failure: self.single_points_of_failure(),
+        })
+    }
+}
+
+fn write_joined(f: &mut fmt::Formatter<'_>, children: &[TriggerExpr], sep: &str) -> fmt::Result {
+    for (i, child) in children.iter().enumerate() {
+        if i > 0 {
+            write!(f, "{sep}")?;
+        }
+        if child.children().is_empty() {
+            write!(f, "{child}")?;
+        } else {
+            write!(f, "({child})")?;
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for TriggerExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TriggerExpr::Condition { condition } => write!(f, "{condition}"),
+            TriggerExpr::GuardianApprovals { count } => write!(f, "{count} guardian approvals"),
+            TriggerExpr::And { children } => write_joined(f, children, " AND "),
+            TriggerExpr::Or { children } => write_joined(f, children, " OR "),
+            TriggerExpr::Threshold { k, children } => {
+                write!(f, "{k}-of-{} [", children.len())?;
+                write_joined(f, children, ", ")?;
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn cond(condition: TriggerCondition) -> TriggerExpr {
+        TriggerExpr::Condition { condition }
+    }
+
+    /// `(Inactivity AND Oracle) OR (Time AND 2 guardian approvals)`
+    fn layered() -> TriggerExpr {
+        TriggerExpr::Or {
+            children: vec![
+                TriggerExpr::And {
+                    children: vec![
+                        cond(TriggerCondition::Inactivity),
+                        cond(TriggerCondition::Oracle),
+                    ],
+                },
+                TriggerExpr::And {
+                    children: vec![
+                        cond(TriggerCondition::Time),
+                        TriggerExpr::GuardianApprovals { count: 2 },
+                    ],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_nested_json() {
+        let expr: TriggerExpr = serde_json::from_value(json!({
+            "type": "or", "children": [
+                { "type": "and", "children": [
+                    { "type": "condition", "condition": "inactivity" },
+                    { "type": "condition", "condition": "oracle" } ] },
+                { "type": "and", "children": [
+                    { "type": "condition", "condition": "time" },
+                    { "type": "guardian_approvals", "count": 2 } ] } ]
+        }))
+        .unwrap();
+        assert_eq!(expr, layered());
+    }
+
+    #[test]
+    fn describes_expression() {
+        assert_eq!(
+            layered().to_string(),
+            "(Inactivity AND Oracle) OR (Time AND 2 guardian approvals)"
+        );
+        let threshold = TriggerExpr::Threshold {
+            k: 2,
+            children: vec![
+                cond(TriggerCondition::Time),
+                cond(TriggerCondition::Oracle),
+                cond(TriggerCondition::Health),
+            ],
+        };
+        assert_eq!(threshold.to_string(), "2-of-3 [Time, Oracle, Health]");
+    }
+
+    #[test]
+    fn flattens_in_contract_order() {
+        let nodes = layered().flatten();
+        assert_eq!(nodes.len(), 7);
+        assert_eq!(
+            nodes[0],
+            FlatTriggerNode::Or {
+                children: vec![1, 4]
+            }
+        );
+        assert_eq!(
+            nodes[1],
+            FlatTriggerNode::And {
+                children: vec![2, 3]
+            }
+        );
+        assert_eq!(
+            nodes[4],
+            FlatTriggerNode::And {
+                children: vec![5, 6]
+            }
+        );
+        assert_eq!(nodes[6], FlatTriggerNode::GuardianApprovals { count: 2 });
+    }
+
+    #[test]
+    fn evaluates_against_state() {
+        let expr = layered();
+        let state = |met: Vec<TriggerCondition>, approvals| TriggerState {
+            met_conditions: met,
+            guardian_approvals: approvals,
+        };
+        assert!(!expr.evaluate(&state(vec![TriggerCondition::Oracle], 0)));
+        assert!(expr.evaluate(&state(
+            vec![TriggerCondition::Oracle, TriggerCondition::Inactivity],
+            0
+        )));
+        assert!(!expr.evaluate(&state(vec![TriggerCondition::Time], 1)));
+        assert!(expr.evaluate(&state(vec![TriggerCondition::Time], 2)));
+    }
+
+    #[test]
+    fn reports_single_points_of_failure() {
+        assert!(layered().single_points_of_failure().is_empty());
+
+        let weak = TriggerExpr::Or {
+            children: vec![
+                cond(TriggerCondition::Oracle),
+                TriggerExpr::And {
+                    children: vec![
+                        cond(TriggerCondition::Time),
+                        cond(TriggerCondition::Inactivity),
+                    ],
+                },
+                TriggerExpr::GuardianApprovals { count: 3 },
+            ],
+        };
+        assert_eq!(
+            weak.single_points_of_failure(),
+            vec!["Oracle".to_string(), "guardian approvals".to_string()]
+        );
+    }
+
+    #[test]
+    fn rejects_out_of_bounds_expressions() {
+        let bad_k = TriggerExpr::Threshold {
+            k: 3,
+            children: vec![cond(TriggerCondition::Time), cond(TriggerCondition::Oracle)],
+        };
+        assert!(bad_k.validate().is_err());
+        assert!(TriggerExpr::And { children: vec![] }.validate().is_err());
+        assert!(TriggerExpr::GuardianApprovals { count: 0 }
+            .validate()
+            .is_err());
+
+        let wide = TriggerExpr::Or {
+            children: vec![cond(TriggerCondition::Time); MAX_TRIGGER_CHILDREN + 1],
+        };
+        assert!(wide.validate().is_err());
+
+        let big = TriggerExpr::Or {
+            children: vec![
+                TriggerExpr::And {
+                    children: vec![cond(TriggerCondition::Time); MAX_TRIGGER_CHILDREN],
+                },
+                TriggerExpr::And {
+                    children: vec![cond(TriggerCondition::Oracle); MAX_TRIGGER_CHILDREN],
+                },
+            ],
+        };
+        assert_eq!(big.node_count(), 19);
+        assert!(big.validate().is_err());
+    }
+
+    #[test]
+    fn preview_includes_outcome() {
+        let preview = layered()
+            .preview(Some(&TriggerState {
+                met_conditions: vec![TriggerCondition::Time],
+                guardian_approvals: 2,
+            }))
+            .unwrap();
+        assert_eq!(preview.node_count, 7);
+        assert_eq!(preview.fires, Some(true));
+        assert!(preview.single_points_of_failure.is_empty());
+    }
+}
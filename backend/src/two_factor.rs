@@ -0,0 +1,880 @@
+//! # Authenticator-App Second Factor
+//!
+//! Besides the one-time codes [`send_2fa`](crate::auth::send_2fa) delivers,
+//! a user can enroll an authenticator app (RFC 6238 TOTP: HMAC-SHA1, six
+//! digits, 30-second steps):
+//!
+//! 1. [`TwoFactorService::enroll_totp`] generates a secret and returns it with
+//!    an `otpauth://` URI for the app to scan. The secret is encrypted at rest
+//!    like bank account numbers.
+//! 2. [`TwoFactorService::confirm_totp`] takes the first code from the app,
+//!    turns the factor on and returns ten single-use recovery codes. Only
+//!    their SHA-256 hashes are kept.
+//!
+//! [`verify_2fa_internal`] accepts an authenticator code, a recovery code or
+//! a delivered OTP. A code from a time step already used is rejected, and
+//! repeated wrong authenticator or recovery codes lock both for a while.
+//!
+//! Each user's policy decides whether plan claims and collateral withdrawals
+//! need a code; [`TwoFactorService::enforce`] applies it. Confirming an
+//! authenticator app turns both on.
+
+use crate::api_error::ApiError;
+use crate::auth::verify_2fa_internal;
+use crate::document_storage::{decrypt_bytes, encrypt_bytes, load_encryption_secret, sha256_hex};
+use crate::notifications::{audit_action, entity_type, AuditLogService};
+use chrono::{DateTime, Duration, Utc};
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+pub const TOTP_DIGITS: u32 = 6;
+pub const TOTP_STEP_SECS: i64 = 30;
+
+/// Steps either side of the current one still accepted, for clock drift.
+const TOTP_SKEW_STEPS: i64 = 1;
+
+/// 160-bit secrets, as RFC 4226 recommends for HMAC-SHA1.
+const TOTP_SECRET_BYTES: usize = 20;
+
+const MAX_TOTP_FAILURES: i32 = 5;
+const TOTP_LOCKOUT_MINUTES: i64 = 15;
+
+pub const RECOVERY_CODE_COUNT: usize = 10;
+
+/// Characters in a recovery code, shown as two groups of five.
+const RECOVERY_CODE_LEN: usize = 10;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Types
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Actions a user's policy can put behind a second factor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensitiveAction {
+    Claim,
+    Withdrawal,
+}
+
+impl SensitiveAction {
+    fn describe(self) -> &'static str {
+        match self {
+            SensitiveAction::Claim => "claim a plan",
+            SensitiveAction::Withdrawal => "withdraw collateral",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TwoFactorPolicy {
+    pub require_for_claims: bool,
+    pub require_for_withdrawals: bool,
+}
+
+impl TwoFactorPolicy {
+    pub fn requires(&self, action: SensitiveAction) -> bool {
+        match action {
+            SensitiveAction::Claim => self.require_for_claims,
+            SensitiveAction::Withdrawal => self.require_for_withdrawals,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TwoFactorStatus {
+    pub totp_enabled: bool,
+    /// An enrollment is waiting for its first code.
+    pub totp_pending: bool,
+    pub recovery_codes_remaining: i64,
+    #[serde(flatten)]
+    pub policy: TwoFactorPolicy,
+}
+
+/// Shown once, when the user starts enrolling an authenticator app.
+#[derive(Debug, Clone, Serialize)]
+pub struct TotpEnrollment {
+    /// Base32 secret, for apps that cannot scan the URI.
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+/// Shown once; only hashes are stored.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecoveryCodes {
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TwoFactorCodeRequest {
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTwoFactorPolicyRequest {
+    pub require_for_claims: Option<bool>,
+    pub require_for_withdrawals: Option<bool>,
+    /// Current second factor, so a stolen session cannot relax the policy.
+    pub code: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct TotpRow {
+    encrypted_secret: Vec<u8>,
+    encryption_nonce: Vec<u8>,
+    last_used_step: Option<i64>,
+    locked_until: Option<DateTime<Utc>>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// TOTP and recovery codes
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// RFC 4648 base32 without padding, as authenticator apps expect secrets.
+pub fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+/// RFC 4226 HOTP value for `counter`, before reducing to digits.
+fn hotp(secret: &[u8], counter: u64) -> u32 {
+    let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, secret);
+    let tag = hmac::sign(&key, &counter.to_be_bytes());
+    let mac = tag.as_ref();
+    let offset = (mac[mac.len() - 1] & 0x0f) as usize;
+    u32::from_be_bytes([
+        mac[offset] & 0x7f,
+        mac[offset + 1],
+        mac[offset + 2],
+        mac[offset + 3],
+    ])
+}
+
+/// The six-digit code for time step `step`.
+pub fn totp_code(secret: &[u8], step: i64) -> String {
+    let code = hotp(secret, step.max(0) as u64) % 10u32.pow(TOTP_DIGITS);
+    format!("{code:0width$}", width = TOTP_DIGITS as usize)
+}
+
+pub fn totp_step(at: DateTime<Utc>) -> i64 {
+    at.timestamp().div_euclid(TOTP_STEP_SECS)
+}
+
+/// Step within the drift window whose code is `code`, skipping steps at or
+/// before `last_used_step`.
+fn matching_step(
+    secret: &[u8],
+    code: &str,
+    now: DateTime<Utc>,
+    last_used_step: Option<i64>,
+) -> Option<i64> {
+    let current = totp_step(now);
+    (current - TOTP_SKEW_STEPS..=current + TOTP_SKEW_STEPS)
+        .filter(|step| !matches!(last_used_step, Some(last) if *step <= last))
+        .find(|step| totp_code(secret, *step) == code)
+}
+
+fn is_totp_code(code: &str) -> bool {
+    code.len() == TOTP_DIGITS as usize && code.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// Key URI authenticator apps scan to add the account.
+pub fn otpauth_uri(issuer: &str, account: &str, secret_base32: &str) -> String {
+    let issuer = percent_encode(issuer);
+    format!(
+        "otpauth://totp/{issuer}:{account}?secret={secret_base32}&issuer={issuer}\
+         &algorithm=SHA1&digits={TOTP_DIGITS}&period={TOTP_STEP_SECS}",
+        account = percent_encode(account),
+    )
+}
+
+fn totp_issuer() -> String {
+    std::env::var("TOTP_ISSUER")
+        .ok()
+        .filter(|i| !i.is_empty())
+        .unwrap_or_else(|| "InheritX".to_string())
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N], ApiError> {
+    let mut bytes = [0u8; N];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Failed to generate random bytes")))?;
+    Ok(bytes)
+}
+
+/// A fresh recovery code, e.g. `K7QX2-MB4TZ`.
+fn new_recovery_code() -> Result<String, ApiError> {
+    let bytes = random_bytes::<RECOVERY_CODE_LEN>()?;
+    let chars: String = bytes
+        .iter()
+        .map(|b| BASE32_ALPHABET[(b & 31) as usize] as char)
+        .collect();
+    let (head, tail) = chars.split_at(RECOVERY_CODE_LEN / 2);
+    Ok(format!("{head}-{tail}"))
+}
+
+/// Canonical form of something that looks like a recovery code: separators
+/// dropped, upper case.
+fn normalize_recovery_code(code: &str) -> Option<String> {
+    let normalized: String = code
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    (normalized.len() == RECOVERY_CODE_LEN
+        && normalized.bytes().all(|b| BASE32_ALPHABET.contains(&b)))
+    .then_some(normalized)
+}
+
+fn encryption_secret() -> Result<Vec<u8>, ApiError> {
+    let secret = load_encryption_secret();
+    if secret.is_empty() {
+        return Err(ApiError::Internal(anyhow::anyhow!(
+            "DOCUMENT_ENCRYPTION_KEY is not configured"
+        )));
+    }
+    Ok(secret)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Service
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub struct TwoFactorService;
+
+impl TwoFactorService {
+    /// Start enrolling an authenticator app, replacing any enrollment that
+    /// was never confirmed.
+    pub async fn enroll_totp(
+        db: &PgPool,
+        user_id: Uuid,
+        email: &str,
+    ) -> Result<TotpEnrollment, ApiError> {
+        let secret = random_bytes::<TOTP_SECRET_BYTES>()?;
+        let (ciphertext, nonce) = encrypt_bytes(&secret, &encryption_secret()?)?;
+
+        let stored = sqlx::query(
+            r#"
+            INSERT INTO user_totp (user_id, encrypted_secret, encryption_nonce)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id) DO UPDATE
+            SET encrypted_secret = EXCLUDED.encrypted_secret,
+                encryption_nonce = EXCLUDED.encryption_nonce,
+                last_used_step = NULL,
+                failed_attempts = 0,
+                locked_until = NULL,
+                updated_at = NOW()
+            WHERE user_totp.confirmed_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(&ciphertext)
+        .bind(&nonce)
+        .execute(db)
+        .await?
+        .rows_affected();
+        if stored == 0 {
+            return Err(ApiError::Conflict(
+                "An authenticator app is already enrolled".to_string(),
+            ));
+        }
+
+        let secret = base32_encode(&secret);
+        Ok(TotpEnrollment {
+            otpauth_uri: otpauth_uri(&totp_issuer(), email, &secret),
+            secret,
+        })
+    }
+
+    /// Confirm a pending enrollment with a code from the app. Turns the
+    /// factor on, requires it for claims and withdrawals unless the user has
+    /// set a policy already, and returns fresh recovery codes.
+    pub async fn confirm_totp(
+        db: &PgPool,
+        user_id: Uuid,
+        code: &str,
+    ) -> Result<RecoveryCodes, ApiError> {
+        let mut tx = db.begin().await?;
+        let row = sqlx::query_as::<_, TotpRow>(
+            r#"
+            SELECT encrypted_secret, encryption_nonce, last_used_step, locked_until
+            FROM user_totp
+            WHERE user_id = $1 AND confirmed_at IS NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| {
+            ApiError::BadRequest("No authenticator app enrollment is pending".to_string())
+        })?;
+        Self::check_lock(row.locked_until)?;
+
+        let secret = decrypt_bytes(
+            &row.encrypted_secret,
+            &row.encryption_nonce,
+            &encryption_secret()?,
+        )?;
+        let Some(step) = matching_step(&secret, code.trim(), Utc::now(), None) else {
+            Self::record_failure(&mut tx, user_id).await?;
+            tx.commit().await?;
+            return Err(ApiError::Unauthorized);
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE user_totp
+            SET confirmed_at = NOW(), last_used_step = $2, failed_attempts = 0,
+                locked_until = NULL, updated_at = NOW()
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO user_2fa_policies (user_id, require_for_claims, require_for_withdrawals)
+            VALUES ($1, TRUE, TRUE)
+            ON CONFLICT (user_id) DO NOTHING
+            "#,
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+        let recovery_codes = Self::replace_recovery_codes(&mut tx, user_id).await?;
+
+        AuditLogService::log(
+            &mut *tx,
+            Some(user_id),
+            None,
+            audit_action::TOTP_ENABLED,
+            Some(user_id),
+            Some(entity_type::USER),
+            None,
+            None,
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(RecoveryCodes { recovery_codes })
+    }
+
+    /// Remove the authenticator app and its recovery codes. Needs a current
+    /// second factor.
+    pub async fn disable_totp(db: &PgPool, user_id: Uuid, code: &str) -> Result<(), ApiError> {
+        verify_2fa_internal(db, user_id, code).await?;
+
+        let mut tx = db.begin().await?;
+        let removed = sqlx::query("DELETE FROM user_totp WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        if removed == 0 {
+            return Err(ApiError::NotFound(
+                "No authenticator app is enrolled".to_string(),
+            ));
+        }
+        sqlx::query("DELETE FROM user_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        AuditLogService::log(
+            &mut *tx,
+            Some(user_id),
+            None,
+            audit_action::TOTP_DISABLED,
+            Some(user_id),
+            Some(entity_type::USER),
+            None,
+            None,
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Replace all recovery codes, used or not. Needs a current second
+    /// factor.
+    pub async fn regenerate_recovery_codes(
+        db: &PgPool,
+        user_id: Uuid,
+        code: &str,
+    ) -> Result<RecoveryCodes, ApiError> {
+        if !Self::status(db, user_id).await?.totp_enabled {
+            return Err(ApiError::BadRequest(
+                "Recovery codes need an enrolled authenticator app".to_string(),
+            ));
+        }
+        verify_2fa_internal(db, user_id, code).await?;
+
+        let mut tx = db.begin().await?;
+        let recovery_codes = Self::replace_recovery_codes(&mut tx, user_id).await?;
+        AuditLogService::log(
+            &mut *tx,
+            Some(user_id),
+            None,
+            audit_action::RECOVERY_CODES_REGENERATED,
+            Some(user_id),
+            Some(entity_type::USER),
+            None,
+            None,
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(RecoveryCodes { recovery_codes })
+    }
+
+    pub async fn status(db: &PgPool, user_id: Uuid) -> Result<TwoFactorStatus, ApiError> {
+        let (totp_enabled, totp_pending, recovery_codes_remaining): (bool, bool, i64) =
+            sqlx::query_as(
+                r#"
+                SELECT
+                    EXISTS(SELECT 1 FROM user_totp
+                           WHERE user_id = $1 AND confirmed_at IS NOT NULL),
+                    EXISTS(SELECT 1 FROM user_totp
+                           WHERE user_id = $1 AND confirmed_at IS NULL),
+                    (SELECT COUNT(*) FROM user_recovery_codes
+                     WHERE user_id = $1 AND used_at IS NULL)
+                "#,
+            )
+            .bind(user_id)
+            .fetch_one(db)
+            .await?;
+
+        Ok(TwoFactorStatus {
+            totp_enabled,
+            totp_pending,
+            recovery_codes_remaining,
+            policy: Self::policy(db, user_id).await?,
+        })
+    }
+
+    /// The user's policy; nothing is required until one is set.
+    pub async fn policy(db: &PgPool, user_id: Uuid) -> Result<TwoFactorPolicy, ApiError> {
+        let policy = sqlx::query_as::<_, TwoFactorPolicy>(
+            r#"
+            SELECT require_for_claims, require_for_withdrawals
+            FROM user_2fa_policies
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(db)
+        .await?;
+        Ok(policy.unwrap_or_default())
+    }
+
+    /// Change which actions need a second factor. Needs a current one.
+    pub async fn update_policy(
+        db: &PgPool,
+        user_id: Uuid,
+        req: &UpdateTwoFactorPolicyRequest,
+    ) -> Result<TwoFactorPolicy, ApiError> {
+        verify_2fa_internal(db, user_id, &req.code).await?;
+
+        let mut tx = db.begin().await?;
+        let policy = sqlx::query_as::<_, TwoFactorPolicy>(
+            r#"
+            INSERT INTO user_2fa_policies (user_id, require_for_claims, require_for_withdrawals)
+            VALUES ($1, COALESCE($2, FALSE), COALESCE($3, FALSE))
+            ON CONFLICT (user_id) DO UPDATE
+            SET require_for_claims = COALESCE($2, user_2fa_policies.require_for_claims),
+                require_for_withdrawals =
+                    COALESCE($3, user_2fa_policies.require_for_withdrawals),
+                updated_at = NOW()
+            RETURNING require_for_claims, require_for_withdrawals
+            "#,
+        )
+        .bind(user_id)
+        .bind(req.require_for_claims)
+        .bind(req.require_for_withdrawals)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        AuditLogService::log(
+            &mut *tx,
+            Some(user_id),
+            None,
+            audit_action::TWO_FA_POLICY_UPDATED,
+            Some(user_id),
+            Some(entity_type::USER),
+            None,
+            Some(&serde_json::to_string(&policy).unwrap_or_default()),
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(policy)
+    }
+
+    /// Require a valid second factor for `action` when the user's policy
+    /// asks for one.
+    pub async fn enforce(
+        db: &PgPool,
+        user_id: Uuid,
+        action: SensitiveAction,
+        code: Option<&str>,
+    ) -> Result<(), ApiError> {
+        if !Self::policy(db, user_id).await?.requires(action) {
+            return Ok(());
+        }
+        let code = code
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .ok_or_else(|| {
+                ApiError::Forbidden(format!(
+                    "A two-factor code is required to {}",
+                    action.describe()
+                ))
+            })?;
+        verify_2fa_internal(db, user_id, code).await
+    }
+
+    /// Check `code` as an authenticator code or recovery code.
+    ///
+    /// `Ok(false)` means it is neither and should be checked as a delivered
+    /// OTP. A six-digit code that does not match the authenticator is also
+    /// handed on while an OTP is pending, and the caller reports it with
+    /// [`Self::record_missed_code`] if the OTP does not match either;
+    /// otherwise it counts as a failure here.
+    pub(crate) async fn verify_second_factor(
+        db: &PgPool,
+        user_id: Uuid,
+        code: &str,
+    ) -> Result<bool, ApiError> {
+        let code = code.trim();
+        if let Some(recovery_code) = normalize_recovery_code(code) {
+            Self::use_recovery_code(db, user_id, &recovery_code).await?;
+            return Ok(true);
+        }
+        if !is_totp_code(code) {
+            return Ok(false);
+        }
+
+        let mut tx = db.begin().await?;
+        let row = sqlx::query_as::<_, TotpRow>(
+            r#"
+            SELECT encrypted_secret, encryption_nonce, last_used_step, locked_until
+            FROM user_totp
+            WHERE user_id = $1 AND confirmed_at IS NOT NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(row) = row else {
+            return Ok(false);
+        };
+        Self::check_lock(row.locked_until)?;
+
+        let secret = decrypt_bytes(
+            &row.encrypted_secret,
+            &row.encryption_nonce,
+            &encryption_secret()?,
+        )?;
+        if let Some(step) = matching_step(&secret, code, Utc::now(), row.last_used_step) {
+            sqlx::query(
+                r#"
+                UPDATE user_totp
+                SET last_used_step = $2, failed_attempts = 0, locked_until = NULL,
+                    updated_at = NOW()
+                WHERE user_id = $1
+                "#,
+            )
+            .bind(user_id)
+            .bind(step)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            return Ok(true);
+        }
+
+        let otp_pending = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM user_2fa WHERE user_id = $1 AND expires_at > NOW())",
+        )
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if otp_pending {
+            return Ok(false);
+        }
+        Self::record_failure(&mut tx, user_id).await?;
+        tx.commit().await?;
+        Err(ApiError::Unauthorized)
+    }
+
+    /// Spend a recovery code. Wrong codes count towards the same lockout as
+    /// wrong authenticator codes, and are refused while it lasts.
+    async fn use_recovery_code(db: &PgPool, user_id: Uuid, code: &str) -> Result<(), ApiError> {
+        let mut tx = db.begin().await?;
+        // Recovery codes only exist alongside a confirmed authenticator app,
+        // whose row carries the failure count.
+        let locked_until: Option<DateTime<Utc>> = sqlx::query_scalar(
+            r#"
+            SELECT locked_until
+            FROM user_totp
+            WHERE user_id = $1 AND confirmed_at IS NOT NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+        Self::check_lock(locked_until)?;
+
+        let used: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            UPDATE user_recovery_codes
+            SET used_at = NOW()
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            RETURNING id
+            "#,
+        )
+        .bind(user_id)
+        .bind(sha256_hex(code.as_bytes()))
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(id) = used else {
+            Self::record_failure(&mut tx, user_id).await?;
+            tx.commit().await?;
+            return Err(ApiError::Unauthorized);
+        };
+
+        sqlx::query(
+            "UPDATE user_totp SET failed_attempts = 0, updated_at = NOW() WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+        AuditLogService::log(
+            &mut *tx,
+            Some(user_id),
+            None,
+            audit_action::RECOVERY_CODE_USED,
+            Some(id),
+            Some(entity_type::USER),
+            None,
+            None,
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn replace_recovery_codes(
+        tx: &mut Transaction<'_, Postgres>,
+        user_id: Uuid,
+    ) -> Result<Vec<String>, ApiError> {
+        sqlx::query("DELETE FROM user_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut **tx)
+            .await?;
+
+        let mut codes = Vec::with_capacity(RECOVERY_CODE_COUNT);
+        while codes.len() < RECOVERY_CODE_COUNT {
+            let code = new_recovery_code()?;
+            let hash = normalize_recovery_code(&code)
+                .map(|normalized| sha256_hex(normalized.as_bytes()))
+                .expect("generated recovery codes are well-formed");
+            let inserted = sqlx::query(
+                r#"
+                INSERT INTO user_recovery_codes (user_id, code_hash)
+                VALUES ($1, $2)
+                ON CONFLICT (user_id, code_hash) DO NOTHING
+                "#,
+            )
+            .bind(user_id)
+            .bind(hash)
+            .execute(&mut **tx)
+            .await?
+            .rows_affected();
+            if inserted == 1 {
+                codes.push(code);
+            }
+        }
+        Ok(codes)
+    }
+
+    fn check_lock(locked_until: Option<DateTime<Utc>>) -> Result<(), ApiError> {
+        match locked_until {
+            Some(until) if until > Utc::now() => Err(ApiError::TooManyRequests(
+                "Too many wrong two-factor codes. Try again later.".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Count `code` as a failed authenticator attempt after it matched
+    /// neither the authenticator nor the pending OTP. Codes that could not be
+    /// authenticator codes, and users without an authenticator, are ignored.
+    pub(crate) async fn record_missed_code(
+        db: &PgPool,
+        user_id: Uuid,
+        code: &str,
+    ) -> Result<(), ApiError> {
+        if !is_totp_code(code.trim()) {
+            return Ok(());
+        }
+        let mut tx = db.begin().await?;
+        Self::record_failure(&mut tx, user_id).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn record_failure(
+        tx: &mut Transaction<'_, Postgres>,
+        user_id: Uuid,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            UPDATE user_totp
+            SET failed_attempts = CASE WHEN failed_attempts + 1 >= $2 THEN 0
+                                       ELSE failed_attempts + 1 END,
+                locked_until = CASE WHEN failed_attempts + 1 >= $2 THEN $3
+                                    ELSE NULL END,
+                updated_at = NOW()
+            WHERE user_id = $1 AND confirmed_at IS NOT NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(MAX_TOTP_FAILURES)
+        .bind(Utc::now() + Duration::minutes(TOTP_LOCKOUT_MINUTES))
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Unit tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// The SHA-1 secret of the RFC 6238 test vectors.
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn totp_matches_rfc_6238_vectors() {
+        // Appendix B, truncated from eight digits to six.
+        for (time, code) in [
+            (59, "287082"),
+            (1_111_111_109, "081804"),
+            (1_111_111_111, "050471"),
+            (1_234_567_890, "005924"),
+            (2_000_000_000, "279037"),
+        ] {
+            let at = Utc.timestamp_opt(time, 0).unwrap();
+            assert_eq!(totp_code(RFC_SECRET, totp_step(at)), code, "t = {time}");
+        }
+    }
+
+    #[test]
+    fn codes_match_within_one_step_and_never_twice() {
+        let now = Utc.timestamp_opt(1_234_567_890, 0).unwrap();
+        let step = totp_step(now);
+        let previous = totp_code(RFC_SECRET, step - 1);
+
+        assert_eq!(
+            matching_step(RFC_SECRET, &previous, now, None),
+            Some(step - 1)
+        );
+        assert_eq!(
+            matching_step(RFC_SECRET, &previous, now, Some(step - 1)),
+            None
+        );
+        assert_eq!(
+            matching_step(RFC_SECRET, &totp_code(RFC_SECRET, step - 2), now, None),
+            None
+        );
+        assert_eq!(matching_step(RFC_SECRET, "000000", now, None), None);
+    }
+
+    #[test]
+    fn secrets_are_base32_in_the_otpauth_uri() {
+        assert_eq!(
+            base32_encode(RFC_SECRET),
+            "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"
+        );
+        assert_eq!(base32_encode(b"f"), "MY");
+        assert_eq!(
+            otpauth_uri("InheritX", "a+b@example.com", "GEZDGNBV"),
+            "otpauth://totp/InheritX:a%2Bb%40example.com?secret=GEZDGNBV&issuer=InheritX\
+             &algorithm=SHA1&digits=6&period=30"
+        );
+    }
+
+    #[test]
+    fn recovery_codes_normalize_and_do_not_look_like_otps() {
+        let code = new_recovery_code().unwrap();
+        assert_eq!(code.len(), RECOVERY_CODE_LEN + 1);
+        assert_ne!(code, new_recovery_code().unwrap());
+
+        let normalized = normalize_recovery_code(&code).unwrap();
+        assert_eq!(
+            normalize_recovery_code(&code.to_lowercase().replace('-', " ")),
+            Some(normalized)
+        );
+        assert_eq!(normalize_recovery_code("123456"), None);
+        assert_eq!(normalize_recovery_code("ABCDE-FGHI1"), None);
+        assert!(is_totp_code("012345"));
+        assert!(!is_totp_code("01234a"));
+    }
+
+    #[test]
+    fn policy_only_requires_what_is_enabled() {
+        let policy = TwoFactorPolicy {
+            require_for_claims: true,
+            require_for_withdrawals: false,
+        };
+        assert!(policy.requires(SensitiveAction::Claim));
+        assert!(!policy.requires(SensitiveAction::Withdrawal));
+        assert!(!TwoFactorPolicy::default().requires(SensitiveAction::Claim));
+    }
+}
@@ -281,6 +281,19 @@ pub const DEFAULT_MAX_FIELD_LENGTH: usize = 1024;
 /// Maximum allowed request body size (bytes) — used by middleware checks.
 pub const DEFAULT_MAX_BODY_BYTES: usize = 16 * 1024; // 16 KiB
 
+/// Maximum body size for raw document uploads (death certificates).
+pub const MAX_DOCUMENT_UPLOAD_BYTES: usize = 10 * 1024 * 1024; // 10 MiB
+
+/// Body size cap for a request path: document upload endpoints accept raw
+/// files, everything else is held to [`DEFAULT_MAX_BODY_BYTES`].
+pub fn max_body_bytes_for(path: &str) -> usize {
+    if path.ends_with("/death-certificates") {
+        MAX_DOCUMENT_UPLOAD_BYTES
+    } else {
+        DEFAULT_MAX_BODY_BYTES
+    }
+}
+
 /// Recursively validate that no string in the provided JSON value exceeds `max`.
 ///
 /// `path` is the JSON path used for error messages (e.g. `$.user.name`).
@@ -2,7 +2,7 @@
 
 use soroban_sdk::{contracttype, Address, Env, Vec};
 
-/// The four roles recognised across all InheritX contracts.
+/// The roles recognised across all InheritX contracts.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Role {
@@ -10,6 +10,8 @@ pub enum Role {
     Guardian,
     Beneficiary,
     Owner,
+    /// Off-chain bridge relayer allowed to confirm cross-chain releases.
+    Relayer,
 }
 
 /// Per-address storage key for role lists.
@@ -274,11 +276,11 @@ pub fn get_contract_version(env: &Env) -> u32 {
 /// Verify that a cross-contract call target has a compatible version.
 /// Returns `error` if the target contract version is outside the acceptable range.
 pub fn check_contract_version<E: Into<soroban_sdk::Error> + Copy>(
-    env: &Env,
-    target_contract: &Address,
-    min_version: u32,
-    max_version: u32,
-    error: E,
+    _env: &Env,
+    _target_contract: &Address,
+    _min_version: u32,
+    _max_version: u32,
+    _error: E,
 ) -> Result<(), E> {
     // For now, skip version checking to avoid compilation issues
     // TODO: Implement proper cross-contract version checking
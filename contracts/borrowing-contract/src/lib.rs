@@ -1,5 +1,6 @@
 #![no_std]
 use access_control::{self, Role};
+use price_oracle::{Price, PriceError};
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Vec,
 };
@@ -20,6 +21,15 @@ pub struct Loan {
     pub extension_count: u32,
 }
 
+/// Collateral posted against a loan in addition to its primary
+/// `collateral_token`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralPosition {
+    pub token: Address,
+    pub amount: i128,
+}
+
 // ─────────────────────────────────────────────────
 // Events
 // ─────────────────────────────────────────────────
@@ -57,6 +67,9 @@ pub struct LiquidationEvent {
     pub liquidator: Address,
     pub amount_liquidated: i128,
     pub collateral_seized: i128,
+    /// Collateral seized from the loan's basket once its primary collateral
+    /// ran out.
+    pub basket_seized: Vec<CollateralPosition>,
     pub health_factor: u32,
     pub timestamp: u64,
 }
@@ -110,6 +123,8 @@ pub struct AuctionBidEvent {
     pub bidder: Address,
     pub bid_amount: i128,
     pub discount_bps: u32,
+    /// Outbid bidder whose escrowed bid was refunded.
+    pub refunded_bidder: Option<Address>,
 }
 
 #[contracttype]
@@ -119,6 +134,26 @@ pub struct AuctionExecutedEvent {
     pub bidder: Address,
     pub bid_amount: i128,
     pub collateral_seized: i128,
+    pub basket_seized: Vec<CollateralPosition>,
+    pub surplus_returned: i128,
+    pub bad_debt: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadDebtRecordedEvent {
+    pub loan_id: u64,
+    pub borrower: Address,
+    pub amount: i128,
+    pub total_bad_debt: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DebtSettlementSetEvent {
+    pub admin: Address,
+    pub debt_asset: Address,
+    pub lender: Address,
 }
 
 #[contracttype]
@@ -148,6 +183,26 @@ pub struct LoanIncreasedEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralAddedEvent {
+    pub loan_id: u64,
+    pub borrower: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralWithdrawnEvent {
+    pub loan_id: u64,
+    pub borrower: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct OracleUpdatedEvent {
@@ -155,6 +210,14 @@ pub struct OracleUpdatedEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceOracleSetEvent {
+    pub admin: Address,
+    pub oracle: Address,
+    pub debt_asset: Address,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct VolatilityBufferSetEvent {
@@ -226,6 +289,21 @@ pub enum DataKey {
     OraclePriceTimestamp(Address),
     MaxOracleAge,
     VolatilityBufferBps(Address),
+    /// SEP-40 price feed; when unset, amounts are compared 1:1.
+    PriceOracle,
+    /// Token loan principals are denominated in, for pricing debt.
+    DebtAsset,
+    AssetDecimals(Address),
+    /// Lender recorded on loans created from now on.
+    Lender,
+    /// Receives debt repaid through the loan's liquidation auction; fixed
+    /// when the loan is created.
+    LoanLender(u64),
+    /// Debt left uncovered after a loan's collateral was auctioned off.
+    BadDebt(u64),
+    TotalBadDebt,
+    /// Collateral other than the primary token posted against a loan.
+    CollateralBasket(u64),
 }
 
 #[contracterror]
@@ -248,6 +326,19 @@ pub enum BorrowingError {
     ReentrantCall = 15,
     ContractPaused = 16,
     OraclePriceStale = 17,
+    OraclePriceUnavailable = 18,
+    BidTooLow = 19,
+    AuctionNotEnded = 20,
+    DebtSettlementNotSet = 21,
+}
+
+impl From<PriceError> for BorrowingError {
+    fn from(err: PriceError) -> Self {
+        match err {
+            PriceError::Stale => BorrowingError::OraclePriceStale,
+            PriceError::Unavailable => BorrowingError::OraclePriceUnavailable,
+        }
+    }
 }
 
 #[contract]
@@ -419,13 +510,12 @@ impl BorrowingContract {
             return Err(BorrowingError::Paused);
         }
 
-        // Oracle staleness check – enforced only when max_oracle_age > 0
-        let max_oracle_age: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MaxOracleAge)
-            .unwrap_or(0);
-        if max_oracle_age > 0 {
+        // Without a price feed, fall back to the manually recorded update time,
+        // enforced once an admin has configured max_oracle_age. Feed prices are
+        // always checked against max_oracle_age (see `prices`).
+        let configured_max_age: Option<u64> = env.storage().instance().get(&DataKey::MaxOracleAge);
+        let has_price_feed = env.storage().instance().has(&DataKey::PriceOracle);
+        if let (Some(max_oracle_age), false) = (configured_max_age, has_price_feed) {
             let current_time = env.ledger().timestamp();
             match env
                 .storage()
@@ -441,23 +531,15 @@ impl BorrowingContract {
         }
 
         // Volatility-adjusted collateral ratio
-        let ratio: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::CollateralRatio)
-            .unwrap_or(15000);
-        let volatility_buffer: u32 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::VolatilityBufferBps(collateral_token.clone()))
-            .unwrap_or(0);
-        let effective_ratio = ratio.saturating_add(volatility_buffer);
+        let effective_ratio = Self::effective_ratio(&env, &collateral_token);
         let required_collateral = (principal as u128)
             .checked_mul(effective_ratio as u128)
             .and_then(|v| v.checked_div(10000))
             .unwrap_or(0) as i128;
 
-        if collateral_amount < required_collateral {
+        let collateral_value =
+            Self::collateral_in_debt_units(&env, &collateral_token, collateral_amount)?;
+        if collateral_value < required_collateral {
             return Err(BorrowingError::InsufficientCollateral);
         }
 
@@ -486,6 +568,11 @@ impl BorrowingContract {
         env.storage()
             .persistent()
             .set(&DataKey::Loan(loan_id), &loan);
+        if let Some(lender) = Self::get_lender(env.clone()) {
+            env.storage()
+                .persistent()
+                .set(&DataKey::LoanLender(loan_id), &lender);
+        }
 
         // Emit borrow event
         env.events().publish(
@@ -529,6 +616,11 @@ impl BorrowingContract {
                 &loan.borrower,
                 &loan.collateral_amount,
             );
+            Self::transfer_positions(&env, &Self::basket(&env, loan_id), &loan.borrower);
+            env.storage()
+                .persistent()
+                .remove(&DataKey::CollateralBasket(loan_id));
+            Self::close_auction(&env, loan_id)?;
         }
 
         // Invariant I-2: amount_repaid must not exceed principal (over-repayment guard)
@@ -682,15 +774,7 @@ impl BorrowingContract {
             return Err(BorrowingError::InvalidAmount);
         }
 
-        // Calculate health factor inline – avoids a redundant storage read from get_health_factor
-        let health_factor = if debt == 0 {
-            10000
-        } else {
-            (loan.collateral_amount as u128)
-                .checked_mul(10000)
-                .and_then(|v| v.checked_div(debt as u128))
-                .unwrap_or(0) as u32
-        };
+        let health_factor = Self::loan_health_factor(&env, loan_id, &loan)?;
 
         // Cache both threshold and bonus in a single pass over instance storage
         let liquidation_threshold: u32 = env
@@ -714,9 +798,15 @@ impl BorrowingContract {
             .checked_mul(liquidation_bonus as u128)
             .and_then(|v| v.checked_div(10000))
             .unwrap_or(0) as i128;
-        let liquidator_reward = liquidate_amount + bonus_amount;
-
-        if liquidator_reward > loan.collateral_amount {
+        // Seize the primary collateral first, then the basket
+        let mut basket = Self::basket(&env, loan_id);
+        let (liquidator_reward, basket_seized, unfilled) = Self::seize(
+            &env,
+            &mut loan,
+            &mut basket,
+            liquidate_amount + bonus_amount,
+        )?;
+        if unfilled > 0 {
             return Err(BorrowingError::InvalidAmount);
         }
 
@@ -727,13 +817,15 @@ impl BorrowingContract {
             &liquidator,
             &liquidator_reward,
         );
+        Self::transfer_positions(&env, &basket_seized, &liquidator);
+        Self::set_basket(&env, loan_id, &basket);
 
-        loan.collateral_amount -= liquidator_reward;
         loan.amount_repaid += liquidate_amount;
 
         // Mark loan as inactive if fully repaid
         if loan.amount_repaid >= loan.principal {
             loan.is_active = false;
+            Self::close_auction(&env, loan_id)?;
         }
 
         // Invariant I-3: liquidation only proceeds when health factor < threshold
@@ -760,6 +852,7 @@ impl BorrowingContract {
                 liquidator: liquidator.clone(),
                 amount_liquidated: liquidate_amount,
                 collateral_seized: liquidator_reward,
+                basket_seized,
                 health_factor,
                 timestamp: env.ledger().timestamp(),
             },
@@ -769,24 +862,195 @@ impl BorrowingContract {
         Ok(())
     }
 
-    pub fn get_health_factor(env: Env, loan_id: u64) -> Result<u32, BorrowingError> {
-        let loan: Loan = env
+    /// Post more collateral against an active loan. The loan's primary token
+    /// tops up its own collateral; any other whitelisted token joins the
+    /// loan's basket, where it counts at its own volatility-adjusted ratio.
+    pub fn add_collateral(
+        env: Env,
+        loan_id: u64,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), BorrowingError> {
+        access_control::reentrancy_enter(&env, BorrowingError::ReentrantCall)?;
+        Self::require_not_paused(&env)?;
+
+        let mut loan: Loan = env
             .storage()
             .persistent()
             .get(&DataKey::Loan(loan_id))
             .ok_or(BorrowingError::LoanNotFound)?;
+        loan.borrower.require_auth();
 
-        let debt = loan.principal - loan.amount_repaid;
-        let health_factor = if debt == 0 {
-            10000
+        if !loan.is_active {
+            return Err(BorrowingError::LoanNotActive);
+        }
+        if amount <= 0 {
+            return Err(BorrowingError::InvalidAmount);
+        }
+        if !Self::is_whitelisted(env.clone(), token.clone()) {
+            return Err(BorrowingError::CollateralNotWhitelisted);
+        }
+        if Self::is_vault_paused(env.clone(), token.clone()) {
+            return Err(BorrowingError::Paused);
+        }
+
+        token::Client::new(&env, &token).transfer(
+            &loan.borrower,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        if token == loan.collateral_token {
+            loan.collateral_amount += amount;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Loan(loan_id), &loan);
         } else {
-            (loan.collateral_amount as u128)
-                .checked_mul(10000)
-                .and_then(|v| v.checked_div(debt as u128))
-                .unwrap_or(0) as u32
-        };
+            let mut basket = Self::basket(&env, loan_id);
+            match basket.iter().position(|p| p.token == token) {
+                Some(i) => {
+                    let mut position = basket.get(i as u32).unwrap();
+                    position.amount += amount;
+                    basket.set(i as u32, position);
+                }
+                None => basket.push_back(CollateralPosition {
+                    token: token.clone(),
+                    amount,
+                }),
+            }
+            Self::set_basket(&env, loan_id, &basket);
+        }
+
+        env.events().publish(
+            (symbol_short!("LOAN"), symbol_short!("ADDCOLL")),
+            CollateralAddedEvent {
+                loan_id,
+                borrower: loan.borrower,
+                token,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
-        Ok(health_factor)
+        access_control::reentrancy_exit(&env);
+        Ok(())
+    }
+
+    /// Withdraw collateral from an active loan, provided what remains still
+    /// backs the outstanding debt. Blocked while a liquidation auction runs.
+    pub fn withdraw_collateral(
+        env: Env,
+        loan_id: u64,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), BorrowingError> {
+        access_control::reentrancy_enter(&env, BorrowingError::ReentrantCall)?;
+        Self::require_not_paused(&env)?;
+
+        let mut loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Loan(loan_id))
+            .ok_or(BorrowingError::LoanNotFound)?;
+        loan.borrower.require_auth();
+
+        if !loan.is_active {
+            return Err(BorrowingError::LoanNotActive);
+        }
+        if amount <= 0 {
+            return Err(BorrowingError::InvalidAmount);
+        }
+        if let Some(auction) = env
+            .storage()
+            .persistent()
+            .get::<_, LiquidationAuction>(&DataKey::Auction(loan_id))
+        {
+            if auction.status == AuctionStatus::Active {
+                return Err(BorrowingError::AuctionAlreadyActive);
+            }
+        }
+
+        let mut basket = Self::basket(&env, loan_id);
+        if token == loan.collateral_token {
+            if amount > loan.collateral_amount {
+                return Err(BorrowingError::InvalidAmount);
+            }
+            loan.collateral_amount -= amount;
+        } else {
+            let i = basket
+                .iter()
+                .position(|p| p.token == token)
+                .ok_or(BorrowingError::InvalidAmount)? as u32;
+            let mut position = basket.get(i).unwrap();
+            if amount > position.amount {
+                return Err(BorrowingError::InvalidAmount);
+            }
+            position.amount -= amount;
+            basket.set(i, position);
+        }
+
+        let mut positions = Vec::new(&env);
+        positions.push_back(CollateralPosition {
+            token: loan.collateral_token.clone(),
+            amount: loan.collateral_amount,
+        });
+        positions.append(&basket);
+        if Self::borrowing_capacity(&env, &positions)? < loan.principal - loan.amount_repaid {
+            return Err(BorrowingError::InsufficientCollateral);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Loan(loan_id), &loan);
+        Self::set_basket(&env, loan_id, &basket);
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &loan.borrower,
+            &amount,
+        );
+
+        env.events().publish(
+            (symbol_short!("LOAN"), symbol_short!("WDCOLL")),
+            CollateralWithdrawnEvent {
+                loan_id,
+                borrower: loan.borrower,
+                token,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        access_control::reentrancy_exit(&env);
+        Ok(())
+    }
+
+    /// Every collateral position backing a loan, primary collateral first.
+    pub fn get_collateral_basket(
+        env: Env,
+        loan_id: u64,
+    ) -> Result<Vec<CollateralPosition>, BorrowingError> {
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Loan(loan_id))
+            .ok_or(BorrowingError::LoanNotFound)?;
+        Ok(Self::loan_collateral(&env, loan_id, &loan))
+    }
+
+    /// Collateral ratio applied to `token`: the base ratio plus its
+    /// volatility buffer.
+    pub fn get_effective_collateral_ratio(env: Env, token: Address) -> u32 {
+        Self::effective_ratio(&env, &token)
+    }
+
+    pub fn get_health_factor(env: Env, loan_id: u64) -> Result<u32, BorrowingError> {
+        let loan: Loan = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Loan(loan_id))
+            .ok_or(BorrowingError::LoanNotFound)?;
+
+        Self::loan_health_factor(&env, loan_id, &loan)
     }
 
     pub fn start_liquidation_auction(
@@ -821,15 +1085,7 @@ impl BorrowingContract {
             return Err(BorrowingError::LoanNotActive);
         }
 
-        // Compute health factor inline (avoids second storage read via get_health_factor)
-        let health_factor = if debt == 0 {
-            10000u32
-        } else {
-            (loan.collateral_amount as u128)
-                .checked_mul(10000)
-                .and_then(|v| v.checked_div(debt as u128))
-                .unwrap_or(0) as u32
-        };
+        let health_factor = Self::loan_health_factor(&env, loan_id, &loan)?;
 
         let liquidation_threshold: u32 = env
             .storage()
@@ -845,7 +1101,12 @@ impl BorrowingContract {
             .persistent()
             .get::<_, LiquidationAuction>(&DataKey::Auction(loan_id))
         {
-            if existing_auction.status == AuctionStatus::Active {
+            // An auction that ended without bids can never be executed;
+            // replace it so the loan stays liquidatable.
+            if existing_auction.status == AuctionStatus::Active
+                && (existing_auction.winning_bidder.is_some()
+                    || !Self::auction_ended(&env, &existing_auction))
+            {
                 return Err(BorrowingError::AuctionAlreadyActive);
             }
         }
@@ -889,25 +1150,85 @@ impl BorrowingContract {
             return Ok(auction.locked_discount_bps);
         }
 
-        let current_time = env.ledger().timestamp();
-        let elapsed = current_time.saturating_sub(auction.start_time);
+        Ok(Self::auction_discount(&env, &auction))
+    }
+
+    fn auction_ended(env: &Env, auction: &LiquidationAuction) -> bool {
+        env.ledger().timestamp() >= auction.start_time.saturating_add(auction.duration)
+    }
+
+    /// Cancel the loan's active auction, if any, refunding the escrowed
+    /// winning bid. Called when the loan closes outside the auction.
+    fn close_auction(env: &Env, loan_id: u64) -> Result<(), BorrowingError> {
+        let Some(mut auction) = env
+            .storage()
+            .persistent()
+            .get::<_, LiquidationAuction>(&DataKey::Auction(loan_id))
+        else {
+            return Ok(());
+        };
+        if auction.status != AuctionStatus::Active {
+            return Ok(());
+        }
+
+        if let Some(bidder) = auction.winning_bidder.clone() {
+            let (debt_asset, _) = Self::debt_settlement(env)?;
+            token::Client::new(env, &debt_asset).transfer(
+                &env.current_contract_address(),
+                &bidder,
+                &auction.winning_bid_amount,
+            );
+        }
+
+        auction.status = AuctionStatus::Cancelled;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Auction(loan_id), &auction);
+
+        env.events().publish(
+            (symbol_short!("AUCTION"), symbol_short!("CANCEL")),
+            AuctionCancelledEvent { loan_id },
+        );
+        Ok(())
+    }
 
+    /// Discount of an active auction at the current time: rises linearly
+    /// from `initial_discount_bps` to `max_discount_bps` over `duration`.
+    fn auction_discount(env: &Env, auction: &LiquidationAuction) -> u32 {
+        let elapsed = env.ledger().timestamp().saturating_sub(auction.start_time);
         if elapsed >= auction.duration {
-            return Ok(auction.max_discount_bps);
+            return auction.max_discount_bps;
         }
 
         let discount_diff = auction
             .max_discount_bps
             .saturating_sub(auction.initial_discount_bps);
-
         let current_addition = (discount_diff as u64)
             .checked_mul(elapsed)
             .and_then(|v| v.checked_div(auction.duration))
             .unwrap_or(0) as u32;
 
-        Ok(auction.initial_discount_bps + current_addition)
+        auction.initial_discount_bps + current_addition
+    }
+
+    /// Debt token and current lender used to settle auctions.
+    fn debt_settlement(env: &Env) -> Result<(Address, Address), BorrowingError> {
+        let debt_asset = env
+            .storage()
+            .instance()
+            .get(&DataKey::DebtAsset)
+            .ok_or(BorrowingError::DebtSettlementNotSet)?;
+        let lender = env
+            .storage()
+            .instance()
+            .get(&DataKey::Lender)
+            .ok_or(BorrowingError::DebtSettlementNotSet)?;
+        Ok((debt_asset, lender))
     }
 
+    /// Bid `bid_amount` of the debt token, escrowed by the contract, at the
+    /// auction's current discount. A bid must exceed the current winning bid,
+    /// whose bidder is refunded. Bids close when the auction's duration ends.
     pub fn bid_on_liquidation(
         env: Env,
         bidder: Address,
@@ -927,6 +1248,9 @@ impl BorrowingContract {
         if auction.status != AuctionStatus::Active {
             return Err(BorrowingError::AuctionNotActive);
         }
+        if Self::auction_ended(&env, &auction) {
+            return Err(BorrowingError::AuctionNotActive);
+        }
 
         // Single storage read for the loan (avoids calling get_loan which re-reads)
         let loan: Loan = env
@@ -934,34 +1258,29 @@ impl BorrowingContract {
             .persistent()
             .get(&DataKey::Loan(loan_id))
             .ok_or(BorrowingError::LoanNotFound)?;
+        if !loan.is_active {
+            return Err(BorrowingError::LoanNotActive);
+        }
         let debt = loan.principal - loan.amount_repaid;
 
         if bid_amount <= 0 || bid_amount > debt {
             return Err(BorrowingError::InvalidAmount);
         }
-
-        if auction.winning_bidder.is_some() {
-            return Err(BorrowingError::AuctionAlreadyActive);
+        if auction.winning_bidder.is_some() && bid_amount <= auction.winning_bid_amount {
+            return Err(BorrowingError::BidTooLow);
         }
 
-        // Compute discount inline from already-loaded auction data (avoids re-reading auction)
-        let current_discount = {
-            let current_time = env.ledger().timestamp();
-            let elapsed = current_time.saturating_sub(auction.start_time);
-            if elapsed >= auction.duration {
-                auction.max_discount_bps
-            } else {
-                let discount_diff = auction
-                    .max_discount_bps
-                    .saturating_sub(auction.initial_discount_bps);
-                let current_addition = (discount_diff as u64)
-                    .checked_mul(elapsed)
-                    .and_then(|v| v.checked_div(auction.duration))
-                    .unwrap_or(0) as u32;
-                auction.initial_discount_bps + current_addition
-            }
-        };
+        let (debt_asset, _) = Self::debt_settlement(&env)?;
+        let debt_token = token::Client::new(&env, &debt_asset);
+        let contract_id = env.current_contract_address();
+        debt_token.transfer(&bidder, &contract_id, &bid_amount);
 
+        let refunded_bidder = auction.winning_bidder.take();
+        if let Some(previous) = &refunded_bidder {
+            debt_token.transfer(&contract_id, previous, &auction.winning_bid_amount);
+        }
+
+        let current_discount = Self::auction_discount(&env, &auction);
         auction.winning_bidder = Some(bidder.clone());
         auction.winning_bid_amount = bid_amount;
         auction.locked_discount_bps = current_discount;
@@ -977,6 +1296,7 @@ impl BorrowingContract {
                 bidder,
                 bid_amount,
                 discount_bps: current_discount,
+                refunded_bidder,
             },
         );
 
@@ -984,6 +1304,12 @@ impl BorrowingContract {
         Ok(())
     }
 
+    /// Settle an auction once it has ended, or earlier if the winning bid
+    /// covers the whole debt. The escrowed bid repays the lender and the
+    /// bidder receives collateral worth the bid plus its locked discount.
+    /// If the debt is cleared, leftover collateral returns to the borrower;
+    /// if the collateral runs out first, the unpaid debt is recorded as bad
+    /// debt. Otherwise the loan stays open with its remaining debt.
     pub fn execute_auction(env: Env, loan_id: u64) -> Result<(), BorrowingError> {
         access_control::reentrancy_enter(&env, BorrowingError::ReentrantCall)?;
         Self::require_not_paused(&env)?;
@@ -1010,30 +1336,60 @@ impl BorrowingContract {
             .persistent()
             .get(&DataKey::Loan(loan_id))
             .ok_or(BorrowingError::LoanNotFound)?;
+        if !loan.is_active {
+            return Err(BorrowingError::LoanNotActive);
+        }
+        let debt = loan.principal - loan.amount_repaid;
+
+        if !Self::auction_ended(&env, &auction) && bid_amount < debt {
+            return Err(BorrowingError::AuctionNotEnded);
+        }
 
         let bonus_amount = (bid_amount as u128)
             .checked_mul(discount as u128)
             .and_then(|v| v.checked_div(10000))
             .unwrap_or(0) as i128;
+        // The reward is capped at whatever collateral the loan still holds
+        let mut basket = Self::basket(&env, loan_id);
+        let (liquidator_reward, basket_seized, _) =
+            Self::seize(&env, &mut loan, &mut basket, bid_amount + bonus_amount)?;
 
-        let liquidator_reward = bid_amount + bonus_amount;
-
-        if liquidator_reward > loan.collateral_amount {
-            return Err(BorrowingError::InvalidAmount);
-        }
+        let (debt_asset, current_lender) = Self::debt_settlement(&env)?;
+        let lender = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LoanLender(loan_id))
+            .unwrap_or(current_lender);
+        let contract_id = env.current_contract_address();
+        token::Client::new(&env, &debt_asset).transfer(&contract_id, &lender, &bid_amount);
 
-        let token_client = token::Client::new(&env, &loan.collateral_token);
-        token_client.transfer(&env.current_contract_address(), &bidder, &liquidator_reward);
+        let collateral_token = token::Client::new(&env, &loan.collateral_token);
+        collateral_token.transfer(&contract_id, &bidder, &liquidator_reward);
+        Self::transfer_positions(&env, &basket_seized, &bidder);
 
-        loan.collateral_amount -= liquidator_reward;
         loan.amount_repaid += bid_amount;
-
-        if loan.amount_repaid >= loan.principal {
+        let remaining_debt = loan.principal - loan.amount_repaid;
+
+        let mut surplus_returned = 0;
+        let mut bad_debt = 0;
+        if remaining_debt <= 0 {
+            surplus_returned = loan.collateral_amount;
+            if surplus_returned > 0 {
+                collateral_token.transfer(&contract_id, &loan.borrower, &surplus_returned);
+            }
+            Self::transfer_positions(&env, &basket, &loan.borrower);
+            basket = Vec::new(&env);
+            loan.collateral_amount = 0;
+            loan.is_active = false;
+        } else if loan.collateral_amount == 0 && basket.is_empty() {
+            bad_debt = remaining_debt;
+            Self::record_bad_debt(&env, loan_id, &loan.borrower, bad_debt);
             loan.is_active = false;
         }
 
         auction.status = AuctionStatus::Executed;
 
+        Self::set_basket(&env, loan_id, &basket);
         env.storage()
             .persistent()
             .set(&DataKey::Loan(loan_id), &loan);
@@ -1048,6 +1404,9 @@ impl BorrowingContract {
                 bidder,
                 bid_amount,
                 collateral_seized: liquidator_reward,
+                basket_seized,
+                surplus_returned,
+                bad_debt,
             },
         );
 
@@ -1055,6 +1414,39 @@ impl BorrowingContract {
         Ok(())
     }
 
+    fn record_bad_debt(env: &Env, loan_id: u64, borrower: &Address, amount: i128) {
+        let total_bad_debt = Self::get_total_bad_debt(env.clone()) + amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::BadDebt(loan_id), &amount);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalBadDebt, &total_bad_debt);
+        env.events().publish(
+            (symbol_short!("LOAN"), symbol_short!("BADDEBT")),
+            BadDebtRecordedEvent {
+                loan_id,
+                borrower: borrower.clone(),
+                amount,
+                total_bad_debt,
+            },
+        );
+    }
+
+    pub fn get_bad_debt(env: Env, loan_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BadDebt(loan_id))
+            .unwrap_or(0)
+    }
+
+    pub fn get_total_bad_debt(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalBadDebt)
+            .unwrap_or(0)
+    }
+
     pub fn get_auction_status(
         env: Env,
         loan_id: u64,
@@ -1068,7 +1460,7 @@ impl BorrowingContract {
     pub fn cancel_auction(env: Env, loan_id: u64) -> Result<(), BorrowingError> {
         Self::require_not_paused(&env)?;
 
-        let mut auction: LiquidationAuction = env
+        let auction: LiquidationAuction = env
             .storage()
             .persistent()
             .get(&DataKey::Auction(loan_id))
@@ -1084,15 +1476,7 @@ impl BorrowingContract {
             .persistent()
             .get(&DataKey::Loan(loan_id))
             .ok_or(BorrowingError::LoanNotFound)?;
-        let debt = loan.principal - loan.amount_repaid;
-        let health_factor = if debt == 0 {
-            10000u32
-        } else {
-            (loan.collateral_amount as u128)
-                .checked_mul(10000)
-                .and_then(|v| v.checked_div(debt as u128))
-                .unwrap_or(0) as u32
-        };
+        let health_factor = Self::loan_health_factor(&env, loan_id, &loan)?;
         let liquidation_threshold: u32 = env
             .storage()
             .instance()
@@ -1103,17 +1487,8 @@ impl BorrowingContract {
             return Err(BorrowingError::StillUnhealthy);
         }
 
-        auction.status = AuctionStatus::Cancelled;
-        env.storage()
-            .persistent()
-            .set(&DataKey::Auction(loan_id), &auction);
-
-        env.events().publish(
-            (symbol_short!("AUCTION"), symbol_short!("CANCEL")),
-            AuctionCancelledEvent { loan_id },
-        );
-
-        Ok(())
+        // Refunds the escrowed winning bid
+        Self::close_auction(&env, loan_id)
     }
 
     /// Sets the maximum number of extensions allowed per loan. Admin-only.
@@ -1176,18 +1551,8 @@ impl BorrowingContract {
             return Err(BorrowingError::LoanNotActive);
         }
 
-        let ratio = Self::get_collateral_ratio(env.clone());
-        let volatility_buffer: u32 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::VolatilityBufferBps(loan.collateral_token.clone()))
-            .unwrap_or(0);
-        let effective_ratio = ratio.saturating_add(volatility_buffer);
-        // max_borrow = collateral * 10000 / effective_ratio
-        let max_borrow = (loan.collateral_amount as u128)
-            .checked_mul(10000)
-            .and_then(|v| v.checked_div(effective_ratio as u128))
-            .unwrap_or(0) as i128;
+        let max_borrow =
+            Self::borrowing_capacity(&env, &Self::loan_collateral(&env, loan_id, &loan))?;
 
         let current_debt = loan.principal - loan.amount_repaid;
         let additional = max_borrow.saturating_sub(current_debt);
@@ -1320,22 +1685,9 @@ impl BorrowingContract {
             return Err(BorrowingError::CollateralNotWhitelisted);
         }
 
-        // Compute max additional with volatility-adjusted ratio
-        let ratio: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::CollateralRatio)
-            .unwrap_or(15000);
-        let volatility_buffer: u32 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::VolatilityBufferBps(loan.collateral_token.clone()))
-            .unwrap_or(0);
-        let effective_ratio = ratio.saturating_add(volatility_buffer);
-        let max_borrow = (loan.collateral_amount as u128)
-            .checked_mul(10000)
-            .and_then(|v| v.checked_div(effective_ratio as u128))
-            .unwrap_or(0) as i128;
+        // Compute max additional with each asset's volatility-adjusted ratio
+        let max_borrow =
+            Self::borrowing_capacity(&env, &Self::loan_collateral(&env, loan_id, &loan))?;
         let current_debt = loan.principal - loan.amount_repaid;
         let max_additional = max_borrow.saturating_sub(current_debt).max(0);
 
@@ -1367,7 +1719,7 @@ impl BorrowingContract {
     }
 
     /// Records the current ledger timestamp as the latest oracle price update for `token`.
-    /// Admin-only. Call this whenever an authoritative price feed is confirmed fresh.
+    /// Admin-only. Only consulted while no price feed is configured; see `set_price_oracle`.
     pub fn update_oracle_timestamp(
         env: Env,
         admin: Address,
@@ -1386,13 +1738,16 @@ impl BorrowingContract {
     }
 
     /// Sets the maximum seconds an oracle price update may be aged before new loans are blocked.
-    /// Set to 0 to disable staleness enforcement (default).
+    /// Feed prices default to `price_oracle::DEFAULT_MAX_PRICE_AGE`; must be non-zero.
     pub fn set_max_oracle_age(
         env: Env,
         admin: Address,
         max_age_seconds: u64,
     ) -> Result<(), BorrowingError> {
         Self::require_admin(&env, &admin)?;
+        if max_age_seconds == 0 {
+            return Err(BorrowingError::InvalidAmount);
+        }
         env.storage()
             .instance()
             .set(&DataKey::MaxOracleAge, &max_age_seconds);
@@ -1422,6 +1777,99 @@ impl BorrowingContract {
         Ok(())
     }
 
+    /// Prices collateral and debt through a SEP-40 feed. Loan principals are
+    /// treated as amounts of `debt_asset`; health factors and collateral
+    /// requirements compare values in the feed's quote currency, and prices
+    /// older than `max_oracle_age` are rejected. Admin-only.
+    pub fn set_price_oracle(
+        env: Env,
+        admin: Address,
+        oracle: Address,
+        debt_asset: Address,
+    ) -> Result<(), BorrowingError> {
+        Self::require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::PriceOracle, &oracle);
+        env.storage()
+            .instance()
+            .set(&DataKey::DebtAsset, &debt_asset);
+        env.events().publish(
+            (symbol_short!("ORACLE"), symbol_short!("SET")),
+            PriceOracleSetEvent {
+                admin,
+                oracle,
+                debt_asset,
+            },
+        );
+        Ok(())
+    }
+
+    /// Sets the debt token auction bids are escrowed in and the lender
+    /// recorded on new loans. A winning bid repays the loan's own lender, so
+    /// changing the lender does not redirect proceeds of existing loans;
+    /// loans created before any lender was set repay the current one.
+    /// Admin-only.
+    pub fn set_debt_settlement(
+        env: Env,
+        admin: Address,
+        debt_asset: Address,
+        lender: Address,
+    ) -> Result<(), BorrowingError> {
+        Self::require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::DebtAsset, &debt_asset);
+        env.storage().instance().set(&DataKey::Lender, &lender);
+        env.events().publish(
+            (symbol_short!("ADMIN"), symbol_short!("SETTLE")),
+            DebtSettlementSetEvent {
+                admin,
+                debt_asset,
+                lender,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get_lender(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Lender)
+    }
+
+    /// Lender the winning bid on `loan_id`'s auction repays.
+    pub fn get_loan_lender(env: Env, loan_id: u64) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LoanLender(loan_id))
+            .or_else(|| Self::get_lender(env))
+    }
+
+    pub fn get_price_oracle(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PriceOracle)
+    }
+
+    /// Sets the decimals of `token` used to value its amounts. Admin-only.
+    pub fn set_asset_decimals(
+        env: Env,
+        admin: Address,
+        token: Address,
+        decimals: u32,
+    ) -> Result<(), BorrowingError> {
+        Self::require_admin(&env, &admin)?;
+        if decimals > price_oracle::MAX_ASSET_DECIMALS {
+            return Err(BorrowingError::InvalidAmount);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::AssetDecimals(token), &decimals);
+        Ok(())
+    }
+
+    pub fn get_asset_decimals(env: Env, token: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AssetDecimals(token))
+            .unwrap_or(price_oracle::DEFAULT_ASSET_DECIMALS)
+    }
+
     pub fn get_oracle_timestamp(env: Env, token: Address) -> u64 {
         env.storage()
             .persistent()
@@ -1433,7 +1881,7 @@ impl BorrowingContract {
         env.storage()
             .instance()
             .get(&DataKey::MaxOracleAge)
-            .unwrap_or(0)
+            .unwrap_or(price_oracle::DEFAULT_MAX_PRICE_AGE)
     }
 
     pub fn get_volatility_buffer(env: Env, token: Address) -> u32 {
@@ -1443,6 +1891,226 @@ impl BorrowingContract {
             .unwrap_or(0)
     }
 
+    /// Fresh prices of `collateral_token` and the debt asset, or `None` when
+    /// no price feed is configured.
+    fn prices(
+        env: &Env,
+        collateral_token: &Address,
+    ) -> Result<Option<(Price, Price)>, BorrowingError> {
+        let Some(oracle) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::PriceOracle)
+        else {
+            return Ok(None);
+        };
+        let debt_asset: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::DebtAsset)
+            .ok_or(BorrowingError::OraclePriceUnavailable)?;
+        let max_age = Self::get_max_oracle_age(env.clone());
+        let price = |token: &Address| -> Result<Price, BorrowingError> {
+            Ok(Price {
+                price: price_oracle::fresh_price(env, &oracle, token, max_age)?,
+                decimals: Self::get_asset_decimals(env.clone(), token.clone()),
+            })
+        };
+        Ok(Some((price(collateral_token)?, price(&debt_asset)?)))
+    }
+
+    /// Value of `amount` of `collateral_token` in debt units.
+    fn collateral_in_debt_units(
+        env: &Env,
+        collateral_token: &Address,
+        amount: i128,
+    ) -> Result<i128, BorrowingError> {
+        match Self::prices(env, collateral_token)? {
+            None => Ok(amount),
+            Some((collateral, debt)) => {
+                price_oracle::convert(amount, collateral, debt).ok_or(BorrowingError::InvalidAmount)
+            }
+        }
+    }
+
+    /// Amount of `collateral_token` worth `amount` debt units.
+    fn debt_in_collateral_units(
+        env: &Env,
+        collateral_token: &Address,
+        amount: i128,
+    ) -> Result<i128, BorrowingError> {
+        match Self::prices(env, collateral_token)? {
+            None => Ok(amount),
+            Some((collateral, debt)) => {
+                price_oracle::convert(amount, debt, collateral).ok_or(BorrowingError::InvalidAmount)
+            }
+        }
+    }
+
+    /// Value of every collateral position over outstanding debt value, in
+    /// basis points.
+    fn loan_health_factor(env: &Env, loan_id: u64, loan: &Loan) -> Result<u32, BorrowingError> {
+        let debt = loan.principal - loan.amount_repaid;
+        let mut collateral_value: i128 = 0;
+        let mut debt_value = debt;
+        for position in Self::loan_collateral(env, loan_id, loan).iter() {
+            let value = match Self::prices(env, &position.token)? {
+                None => position.amount,
+                Some((collateral, debt_price)) => {
+                    debt_value = price_oracle::to_quote(debt, debt_price)
+                        .ok_or(BorrowingError::InvalidAmount)?;
+                    price_oracle::to_quote(position.amount, collateral)
+                        .ok_or(BorrowingError::InvalidAmount)?
+                }
+            };
+            collateral_value = collateral_value
+                .checked_add(value)
+                .ok_or(BorrowingError::InvalidAmount)?;
+        }
+        Ok(price_oracle::health_factor_bps(
+            collateral_value,
+            debt_value,
+        ))
+    }
+
+    /// Base collateral ratio plus the token's volatility buffer.
+    fn effective_ratio(env: &Env, token: &Address) -> u32 {
+        let ratio: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::CollateralRatio)
+            .unwrap_or(15000);
+        let volatility_buffer: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VolatilityBufferBps(token.clone()))
+            .unwrap_or(0);
+        ratio.saturating_add(volatility_buffer)
+    }
+
+    /// Debt the positions can back: each position's value divided by its
+    /// token's effective collateral ratio.
+    fn borrowing_capacity(
+        env: &Env,
+        positions: &Vec<CollateralPosition>,
+    ) -> Result<i128, BorrowingError> {
+        let mut capacity: i128 = 0;
+        for position in positions.iter() {
+            let value = Self::collateral_in_debt_units(env, &position.token, position.amount)?;
+            let backed = (value.max(0) as u128)
+                .checked_mul(10000)
+                .and_then(|v| v.checked_div(Self::effective_ratio(env, &position.token) as u128))
+                .unwrap_or(0) as i128;
+            capacity = capacity.saturating_add(backed);
+        }
+        Ok(capacity)
+    }
+
+    fn basket(env: &Env, loan_id: u64) -> Vec<CollateralPosition> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CollateralBasket(loan_id))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Store a loan's basket, dropping emptied positions.
+    fn set_basket(env: &Env, loan_id: u64, basket: &Vec<CollateralPosition>) {
+        let mut kept = Vec::new(env);
+        for position in basket.iter() {
+            if position.amount > 0 {
+                kept.push_back(position);
+            }
+        }
+        let key = DataKey::CollateralBasket(loan_id);
+        if kept.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &kept);
+        }
+    }
+
+    /// Primary collateral followed by the loan's basket.
+    fn loan_collateral(env: &Env, loan_id: u64, loan: &Loan) -> Vec<CollateralPosition> {
+        let mut positions = Vec::new(env);
+        positions.push_back(CollateralPosition {
+            token: loan.collateral_token.clone(),
+            amount: loan.collateral_amount,
+        });
+        positions.append(&Self::basket(env, loan_id));
+        positions
+    }
+
+    /// Take collateral worth `value` debt units, from the primary collateral
+    /// first and then the basket in order. Returns the primary amount taken,
+    /// the basket positions taken and the value left unfilled once all
+    /// collateral is exhausted.
+    fn seize(
+        env: &Env,
+        loan: &mut Loan,
+        basket: &mut Vec<CollateralPosition>,
+        value: i128,
+    ) -> Result<(i128, Vec<CollateralPosition>, i128), BorrowingError> {
+        let mut remaining = value;
+        let primary = Self::take(
+            env,
+            &loan.collateral_token,
+            &mut loan.collateral_amount,
+            &mut remaining,
+        )?;
+        let mut seized = Vec::new(env);
+        for i in 0..basket.len() {
+            if remaining <= 0 {
+                break;
+            }
+            let mut position = basket.get(i).unwrap();
+            let taken = Self::take(env, &position.token, &mut position.amount, &mut remaining)?;
+            if taken > 0 {
+                seized.push_back(CollateralPosition {
+                    token: position.token.clone(),
+                    amount: taken,
+                });
+            }
+            basket.set(i, position);
+        }
+        Ok((primary, seized, remaining.max(0)))
+    }
+
+    /// Take up to `remaining` debt units' worth of `token` out of `held`.
+    fn take(
+        env: &Env,
+        token: &Address,
+        held: &mut i128,
+        remaining: &mut i128,
+    ) -> Result<i128, BorrowingError> {
+        if *remaining <= 0 || *held <= 0 {
+            return Ok(0);
+        }
+        let held_value = Self::collateral_in_debt_units(env, token, *held)?;
+        let taken = if held_value <= *remaining {
+            *remaining -= held_value;
+            *held
+        } else {
+            let units = Self::debt_in_collateral_units(env, token, *remaining)?.min(*held);
+            *remaining = 0;
+            units
+        };
+        *held -= taken;
+        Ok(taken)
+    }
+
+    fn transfer_positions(env: &Env, positions: &Vec<CollateralPosition>, to: &Address) {
+        let contract_id = env.current_contract_address();
+        for position in positions.iter() {
+            if position.amount > 0 {
+                token::Client::new(env, &position.token).transfer(
+                    &contract_id,
+                    to,
+                    &position.amount,
+                );
+            }
+        }
+    }
+
     fn get_next_loan_id(env: &Env) -> u64 {
         // Use instance storage for the counter – cheaper than persistent for a single
         // frequently-updated scalar value that doesn't need long-term archival.
@@ -342,18 +342,30 @@ fn test_liquidation_auction() {
     let hf = client.get_health_factor(&loan_id);
     assert_eq!(hf, 12000);
 
+    let debt_addr = create_token_addr(&env);
+    let lender = Address::generate(&env);
+    client.set_debt_settlement(&admin, &debt_addr, &lender);
+
     // Start auction
     client.start_liquidation_auction(&loan_id, &1000, &100, &2000);
 
-    // Place bid
-    sac_client(&env, &collateral_addr).mint(&liquidator, &1000);
+    // Place bid, escrowed in the debt token
+    sac_client(&env, &debt_addr).mint(&liquidator, &1000);
     client.bid_on_liquidation(&liquidator, &loan_id, &1000);
+    let debt_token = token::Client::new(&env, &debt_addr);
+    assert_eq!(debt_token.balance(&liquidator), 0);
+    assert_eq!(debt_token.balance(&contract_id), 1000);
 
-    // Execute auction
+    // A bid covering the whole debt settles without waiting for the end
     client.execute_auction(&loan_id);
 
     let loan = client.get_loan(&loan_id);
     assert!(!loan.is_active);
+    assert_eq!(debt_token.balance(&lender), 1000);
+    // 1000 plus the 1% discount locked at bid time; the rest goes back
+    let collateral = token::Client::new(&env, &collateral_addr);
+    assert_eq!(collateral.balance(&liquidator), 1010);
+    assert_eq!(collateral.balance(&borrower), 190);
 }
 
 #[test]
@@ -563,8 +575,11 @@ fn test_oracle_disabled_by_default() {
     env.mock_all_auths();
     let (client, collateral_addr, _admin) = setup(&env);
 
-    // Default max_oracle_age = 0 → staleness check skipped
-    assert_eq!(client.get_max_oracle_age(), 0);
+    // Manual timestamps are only checked once max_oracle_age is configured
+    assert_eq!(
+        client.get_max_oracle_age(),
+        price_oracle::DEFAULT_MAX_PRICE_AGE
+    );
 
     let borrower = Address::generate(&env);
     sac_client(&env, &collateral_addr).mint(&borrower, &1500);
@@ -649,3 +664,494 @@ fn test_oracle_and_volatility_combined() {
     let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1700);
     assert_eq!(loan_id, 1);
 }
+
+// Prices at the mock feed's 14 decimals.
+const ONE: i128 = 100_000_000_000_000;
+
+fn setup_price_feed(
+    env: &Env,
+    client: &BorrowingContractClient,
+    admin: &Address,
+) -> (mock_oracle::MockOracleClient<'static>, Address) {
+    let oracle_id = env.register_contract(None, mock_oracle::MockOracle);
+    let oracle = mock_oracle::MockOracleClient::new(env, &oracle_id);
+    let debt_asset = create_token_addr(env);
+    oracle.set_price(&debt_asset, &ONE, &env.ledger().timestamp());
+    client.set_price_oracle(admin, &oracle_id, &debt_asset);
+    (oracle, debt_asset)
+}
+
+#[test]
+fn test_price_feed_values_collateral() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, admin) = setup(&env);
+    let (oracle, _) = setup_price_feed(&env, &client, &admin);
+    oracle.set_price(&collateral_addr, &(ONE / 2), &0);
+
+    let borrower = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &3000);
+
+    // 1500 collateral at 0.50 is worth 750, short of 150% of 1000.
+    let result = client.try_create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1500);
+    assert_eq!(result, Err(Ok(BorrowingError::InsufficientCollateral)));
+
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &3000);
+    assert_eq!(client.get_health_factor(&loan_id), 15000);
+    assert_eq!(client.get_max_additional_borrow(&loan_id), 0);
+
+    // The collateral doubles in value.
+    oracle.set_price(&collateral_addr, &ONE, &0);
+    assert_eq!(client.get_health_factor(&loan_id), 30000);
+    assert_eq!(client.get_max_additional_borrow(&loan_id), 1000);
+}
+
+#[test]
+fn test_price_feed_respects_asset_decimals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, admin) = setup(&env);
+    let (oracle, debt_asset) = setup_price_feed(&env, &client, &admin);
+    oracle.set_price(&collateral_addr, &ONE, &0);
+    assert_eq!(client.get_asset_decimals(&debt_asset), 7);
+
+    // Principal in a 6-decimal token: 1000 units are worth ten times 1000
+    // units of the 7-decimal collateral.
+    client.set_asset_decimals(&admin, &debt_asset, &6);
+    let borrower = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &15000);
+
+    let result = client.try_create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1500);
+    assert_eq!(result, Err(Ok(BorrowingError::InsufficientCollateral)));
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &15000);
+    assert_eq!(client.get_health_factor(&loan_id), 15000);
+
+    let result = client.try_set_asset_decimals(&admin, &debt_asset, &19);
+    assert_eq!(result, Err(Ok(BorrowingError::InvalidAmount)));
+}
+
+#[test]
+fn test_price_drop_allows_liquidation_at_market_value() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, admin) = setup(&env);
+    let (oracle, _) = setup_price_feed(&env, &client, &admin);
+    oracle.set_price(&collateral_addr, &(ONE / 2), &0);
+
+    let borrower = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &3000);
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &3000);
+
+    let liquidator = Address::generate(&env);
+    let result = client.try_liquidate(&liquidator, &loan_id, &100);
+    assert_eq!(result, Err(Ok(BorrowingError::LoanHealthy)));
+
+    // 3000 collateral at 0.30 is worth 900 against 1000 of debt.
+    oracle.set_price(&collateral_addr, &(ONE * 3 / 10), &0);
+    assert_eq!(client.get_health_factor(&loan_id), 9000);
+
+    // Repaying 100 earns 105 of value: 350 collateral tokens.
+    client.liquidate(&liquidator, &loan_id, &100);
+    let token = token::Client::new(&env, &collateral_addr);
+    assert_eq!(token.balance(&liquidator), 350);
+    let loan = client.get_loan(&loan_id);
+    assert_eq!(loan.collateral_amount, 2650);
+    assert_eq!(loan.amount_repaid, 100);
+}
+
+#[test]
+fn test_price_feed_staleness_uses_max_oracle_age() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, admin) = setup(&env);
+    let (oracle, debt_asset) = setup_price_feed(&env, &client, &admin);
+    client.set_max_oracle_age(&admin, &3600);
+
+    let borrower = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &3000);
+
+    // No price published for the collateral.
+    let result = client.try_create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1500);
+    assert_eq!(result, Err(Ok(BorrowingError::OraclePriceUnavailable)));
+
+    // The feed's own timestamps are checked, not update_oracle_timestamp.
+    oracle.set_price(&collateral_addr, &ONE, &0);
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1500);
+
+    env.ledger().with_mut(|l| l.timestamp = 7201);
+    client.update_oracle_timestamp(&admin, &collateral_addr);
+    let result = client.try_get_health_factor(&loan_id);
+    assert_eq!(result, Err(Ok(BorrowingError::OraclePriceStale)));
+    let result = client.try_create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1500);
+    assert_eq!(result, Err(Ok(BorrowingError::OraclePriceStale)));
+
+    oracle.set_price(&collateral_addr, &ONE, &7200);
+    oracle.set_price(&debt_asset, &ONE, &7200);
+    assert_eq!(client.get_health_factor(&loan_id), 15000);
+}
+
+// ─────────────────────────────────────────────────
+// Auction settlement
+// ─────────────────────────────────────────────────
+
+/// A loan of 1000 against 1200 collateral (health 12000, below the 13000
+/// threshold) with settlement configured. Returns
+/// (client, collateral, debt token, lender, borrower, loan id).
+fn setup_auction(
+    env: &Env,
+) -> (
+    BorrowingContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    Address,
+    u64,
+) {
+    let admin = Address::generate(env);
+    let collateral_addr = create_token_addr(env);
+    let debt_addr = create_token_addr(env);
+    let lender = Address::generate(env);
+    let contract_id = env.register_contract(None, BorrowingContract);
+    let client = BorrowingContractClient::new(env, &contract_id);
+    client.initialize(&admin, &12000, &13000, &500);
+    client.whitelist_collateral(&admin, &collateral_addr);
+    client.set_debt_settlement(&admin, &debt_addr, &lender);
+
+    let borrower = Address::generate(env);
+    sac_client(env, &collateral_addr).mint(&borrower, &1200);
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1200);
+    (
+        client,
+        collateral_addr,
+        debt_addr,
+        lender,
+        borrower,
+        loan_id,
+    )
+}
+
+#[test]
+fn test_auction_outbid_refunds_previous_bidder() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, debt_addr, lender, _, loan_id) = setup_auction(&env);
+    let debt_token = token::Client::new(&env, &debt_addr);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    sac_client(&env, &debt_addr).mint(&first, &1000);
+    sac_client(&env, &debt_addr).mint(&second, &1000);
+
+    client.start_liquidation_auction(&loan_id, &1000, &100, &2000);
+    client.bid_on_liquidation(&first, &loan_id, &500);
+    assert_eq!(debt_token.balance(&first), 500);
+
+    assert_eq!(
+        client.try_bid_on_liquidation(&second, &loan_id, &500),
+        Err(Ok(BorrowingError::BidTooLow))
+    );
+
+    env.ledger().set_timestamp(500);
+    client.bid_on_liquidation(&second, &loan_id, &800);
+    assert_eq!(debt_token.balance(&first), 1000);
+    assert_eq!(debt_token.balance(&second), 200);
+
+    let auction = client.get_auction_status(&loan_id);
+    assert_eq!(auction.winning_bidder, Some(second.clone()));
+    assert_eq!(auction.locked_discount_bps, 1050);
+
+    // A partial bid waits for the auction to end; bidding is then closed
+    assert_eq!(
+        client.try_execute_auction(&loan_id),
+        Err(Ok(BorrowingError::AuctionNotEnded))
+    );
+    env.ledger().set_timestamp(1000);
+    assert_eq!(
+        client.try_bid_on_liquidation(&first, &loan_id, &900),
+        Err(Ok(BorrowingError::AuctionNotActive))
+    );
+    client.execute_auction(&loan_id);
+
+    // 800 plus 10.5% is 884 collateral; the loan stays open for the rest
+    assert_eq!(debt_token.balance(&lender), 800);
+    let collateral = token::Client::new(&env, &collateral_addr);
+    assert_eq!(collateral.balance(&second), 884);
+    let loan = client.get_loan(&loan_id);
+    assert!(loan.is_active);
+    assert_eq!(loan.amount_repaid, 800);
+    assert_eq!(loan.collateral_amount, 316);
+    assert_eq!(client.get_bad_debt(&loan_id), 0);
+}
+
+#[test]
+fn test_auction_shortfall_recorded_as_bad_debt() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, debt_addr, lender, borrower, loan_id) = setup_auction(&env);
+    let bidder = Address::generate(&env);
+    sac_client(&env, &debt_addr).mint(&bidder, &900);
+
+    client.start_liquidation_auction(&loan_id, &1000, &100, &5000);
+    env.ledger().set_timestamp(999);
+    client.bid_on_liquidation(&bidder, &loan_id, &900);
+    env.ledger().set_timestamp(1000);
+    client.execute_auction(&loan_id);
+
+    // 900 at a ~50% discount claims all 1200 collateral; 100 is unpaid
+    let collateral = token::Client::new(&env, &collateral_addr);
+    assert_eq!(collateral.balance(&bidder), 1200);
+    assert_eq!(collateral.balance(&borrower), 0);
+    assert_eq!(token::Client::new(&env, &debt_addr).balance(&lender), 900);
+    assert!(!client.get_loan(&loan_id).is_active);
+    assert_eq!(client.get_bad_debt(&loan_id), 100);
+    assert_eq!(client.get_total_bad_debt(), 100);
+}
+
+#[test]
+fn test_cancel_auction_refunds_escrowed_bid() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, debt_addr, _, _, loan_id) = setup_auction(&env);
+    let bidder = Address::generate(&env);
+    sac_client(&env, &debt_addr).mint(&bidder, &500);
+
+    client.start_liquidation_auction(&loan_id, &1000, &100, &2000);
+    client.bid_on_liquidation(&bidder, &loan_id, &500);
+
+    // Repaying 200 brings the health factor back to 15000
+    client.repay_loan(&loan_id, &200);
+    client.cancel_auction(&loan_id);
+    assert_eq!(token::Client::new(&env, &debt_addr).balance(&bidder), 500);
+    assert_eq!(
+        client.get_auction_status(&loan_id).status,
+        AuctionStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_expired_auction_without_bids_can_be_restarted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, debt_addr, _, _, loan_id) = setup_auction(&env);
+    let bidder = Address::generate(&env);
+    sac_client(&env, &debt_addr).mint(&bidder, &500);
+
+    // A one-second auction nobody bid on
+    client.start_liquidation_auction(&loan_id, &1, &100, &2000);
+    env.ledger().set_timestamp(1);
+    assert_eq!(
+        client.try_bid_on_liquidation(&bidder, &loan_id, &500),
+        Err(Ok(BorrowingError::AuctionNotActive))
+    );
+    assert_eq!(
+        client.try_execute_auction(&loan_id),
+        Err(Ok(BorrowingError::InvalidAmount))
+    );
+
+    client.start_liquidation_auction(&loan_id, &1000, &100, &2000);
+    let auction = client.get_auction_status(&loan_id);
+    assert_eq!(auction.start_time, 1);
+    assert_eq!(auction.status, AuctionStatus::Active);
+    client.bid_on_liquidation(&bidder, &loan_id, &500);
+
+    // An auction with a bid is not replaced, even once it has ended
+    env.ledger().set_timestamp(1001);
+    assert_eq!(
+        client.try_start_liquidation_auction(&loan_id, &1000, &100, &2000),
+        Err(Ok(BorrowingError::AuctionAlreadyActive))
+    );
+}
+
+#[test]
+fn test_repaying_loan_closes_auction_and_refunds_bid() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, debt_addr, lender, borrower, loan_id) = setup_auction(&env);
+    let debt_token = token::Client::new(&env, &debt_addr);
+    let bidder = Address::generate(&env);
+    sac_client(&env, &debt_addr).mint(&bidder, &500);
+
+    client.start_liquidation_auction(&loan_id, &1000, &100, &2000);
+    client.bid_on_liquidation(&bidder, &loan_id, &500);
+    client.repay_loan(&loan_id, &1000);
+
+    assert_eq!(debt_token.balance(&bidder), 500);
+    assert_eq!(
+        client.get_auction_status(&loan_id).status,
+        AuctionStatus::Cancelled
+    );
+    env.ledger().set_timestamp(1000);
+    assert_eq!(
+        client.try_execute_auction(&loan_id),
+        Err(Ok(BorrowingError::AuctionNotActive))
+    );
+    assert_eq!(debt_token.balance(&lender), 0);
+    let collateral = token::Client::new(&env, &collateral_addr);
+    assert_eq!(collateral.balance(&borrower), 1200);
+    assert_eq!(collateral.balance(&bidder), 0);
+
+    // The closed loan cannot be auctioned again
+    assert_eq!(
+        client.try_start_liquidation_auction(&loan_id, &1000, &100, &2000),
+        Err(Ok(BorrowingError::LoanNotActive))
+    );
+}
+
+#[test]
+fn test_bid_requires_debt_settlement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let collateral_addr = create_token_addr(&env);
+    let contract_id = env.register_contract(None, BorrowingContract);
+    let client = BorrowingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &12000, &13000, &500);
+    client.whitelist_collateral(&admin, &collateral_addr);
+    let borrower = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &1200);
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1200);
+
+    client.start_liquidation_auction(&loan_id, &1000, &100, &2000);
+    assert_eq!(
+        client.try_bid_on_liquidation(&Address::generate(&env), &loan_id, &500),
+        Err(Ok(BorrowingError::DebtSettlementNotSet))
+    );
+}
+
+#[test]
+fn test_auction_repays_the_loans_own_lender() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let collateral_addr = create_token_addr(&env);
+    let debt_addr = create_token_addr(&env);
+    let lender = Address::generate(&env);
+    let contract_id = env.register_contract(None, BorrowingContract);
+    let client = BorrowingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &12000, &13000, &500);
+    client.whitelist_collateral(&admin, &collateral_addr);
+    client.set_debt_settlement(&admin, &debt_addr, &lender);
+    let borrower = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &1200);
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1200);
+
+    // Proceeds of the existing loan stay with the lender it was created under
+    let new_lender = Address::generate(&env);
+    client.set_debt_settlement(&admin, &debt_addr, &new_lender);
+    let debt_token = token::Client::new(&env, &debt_addr);
+    assert_eq!(client.get_loan_lender(&loan_id), Some(lender.clone()));
+
+    let bidder = Address::generate(&env);
+    sac_client(&env, &debt_addr).mint(&bidder, &1000);
+    client.start_liquidation_auction(&loan_id, &1000, &100, &2000);
+    client.bid_on_liquidation(&bidder, &loan_id, &1000);
+    client.execute_auction(&loan_id);
+
+    assert_eq!(debt_token.balance(&lender), 1000);
+    assert_eq!(debt_token.balance(&new_lender), 0);
+}
+
+// ─────────────────────────────────────────────────
+// Collateral baskets
+// ─────────────────────────────────────────────────
+
+#[test]
+fn test_collateral_basket_counts_each_asset_at_its_ratio() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, admin) = setup(&env);
+    let second = create_token_addr(&env);
+    client.whitelist_collateral(&admin, &second);
+    client.set_volatility_buffer(&admin, &second, &5000);
+    assert_eq!(client.get_effective_collateral_ratio(&second), 20000);
+
+    let borrower = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &1500);
+    sac_client(&env, &second).mint(&borrower, &1000);
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1500);
+    assert_eq!(client.get_max_additional_borrow(&loan_id), 0);
+
+    // 1000 of the second token backs 500 more at its 200% ratio
+    client.add_collateral(&loan_id, &second, &1000);
+    let basket = client.get_collateral_basket(&loan_id);
+    assert_eq!(basket.len(), 2);
+    assert_eq!(
+        basket.get(1).unwrap(),
+        CollateralPosition {
+            token: second.clone(),
+            amount: 1000
+        }
+    );
+    assert_eq!(client.get_health_factor(&loan_id), 25000);
+    assert_eq!(client.get_max_additional_borrow(&loan_id), 500);
+
+    // The primary collateral cannot leave while the basket alone is short
+    let result = client.try_withdraw_collateral(&loan_id, &collateral_addr, &1500);
+    assert_eq!(result, Err(Ok(BorrowingError::InsufficientCollateral)));
+    client.withdraw_collateral(&loan_id, &second, &1000);
+    assert_eq!(client.get_collateral_basket(&loan_id).len(), 1);
+    assert_eq!(token::Client::new(&env, &second).balance(&borrower), 1000);
+}
+
+#[test]
+fn test_add_collateral_rejects_unlisted_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, _) = setup(&env);
+    let borrower = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &2000);
+    let loan_id = client.create_loan(&borrower, &1000, &5, &1000000, &collateral_addr, &1500);
+
+    let unlisted = create_token_addr(&env);
+    let result = client.try_add_collateral(&loan_id, &unlisted, &100);
+    assert_eq!(result, Err(Ok(BorrowingError::CollateralNotWhitelisted)));
+
+    // The primary token tops up the loan itself
+    client.add_collateral(&loan_id, &collateral_addr, &500);
+    assert_eq!(client.get_loan(&loan_id).collateral_amount, 2000);
+    assert_eq!(client.get_collateral_basket(&loan_id).len(), 1);
+
+    // Repaying returns every position
+    client.repay_loan(&loan_id, &1000);
+    assert_eq!(
+        token::Client::new(&env, &collateral_addr).balance(&borrower),
+        2000
+    );
+}
+
+#[test]
+fn test_liquidation_seizes_basket_after_primary_collateral() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, admin) = setup(&env);
+    let (oracle, _) = setup_price_feed(&env, &client, &admin);
+    let second = create_token_addr(&env);
+    client.whitelist_collateral(&admin, &second);
+    oracle.set_price(&collateral_addr, &ONE, &0);
+    oracle.set_price(&second, &ONE, &0);
+
+    let borrower = Address::generate(&env);
+    sac_client(&env, &collateral_addr).mint(&borrower, &1200);
+    sac_client(&env, &second).mint(&borrower, &400);
+    let loan_id = client.create_loan(&borrower, &800, &5, &1000000, &collateral_addr, &1200);
+    client.add_collateral(&loan_id, &second, &400);
+
+    // 1200 primary at 0.30 plus 400 of the second token: 760 against 800
+    oracle.set_price(&collateral_addr, &(ONE * 3 / 10), &0);
+    assert_eq!(client.get_health_factor(&loan_id), 9500);
+
+    // Repaying 500 earns 525 of value: all 360 of the primary collateral,
+    // then 165 of the second token
+    let liquidator = Address::generate(&env);
+    client.liquidate(&liquidator, &loan_id, &500);
+    assert_eq!(
+        token::Client::new(&env, &collateral_addr).balance(&liquidator),
+        1200
+    );
+    assert_eq!(token::Client::new(&env, &second).balance(&liquidator), 165);
+
+    let loan = client.get_loan(&loan_id);
+    assert_eq!(loan.collateral_amount, 0);
+    assert_eq!(loan.amount_repaid, 500);
+    let basket = client.get_collateral_basket(&loan_id);
+    assert_eq!(basket.get(1).unwrap().amount, 235);
+}
@@ -0,0 +1,37 @@
+#![no_std]
+
+//! Flash-loan receiver interface of the InheritX lending contract.
+//!
+//! A flash loan hands a receiver contract funds that must be back, together
+//! with a fee, by the time its callback returns. The lending contract
+//! transfers the funds, invokes the callback and then checks its own
+//! balance of every lent asset. Receivers repay by transferring
+//! `amount + fee` of each asset back to the lending contract (the callback's
+//! invoker) before returning; repaying in any other way fails the loan.
+//!
+//! While a callback runs the lending contract holds its reentrancy guard, so
+//! calls back into it that move funds are rejected.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, Vec};
+
+/// One asset lent in a flash loan.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FlashLoan {
+    pub asset: Address,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+/// Callbacks a flash-loan receiver implements.
+#[contractclient(name = "FlashLoanReceiverClient")]
+pub trait FlashLoanReceiver {
+    /// Callback of a single-asset `flash_loan`: `amount` of the requested
+    /// asset has been transferred to the receiver and `amount + fee` is due.
+    fn execute_operation(env: Env, amount: u64, fee: u64, initiator: Address);
+
+    /// Callback of a multi-asset `flash_loan_batch`: each loan's `amount`
+    /// has been transferred to the receiver and `amount + fee` of each asset
+    /// is due.
+    fn execute_batch(env: Env, loans: Vec<FlashLoan>, initiator: Address);
+}
@@ -1,4 +1,7 @@
 #![no_std]
+// `GeneticMarker` variants intentionally share the `Marker` suffix; the lint fires
+// on code generated by `#[contracttype]`, so it cannot be allowed on the enum.
+#![allow(clippy::enum_variant_names)]
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, Address, Bytes, BytesN, Env, String, Vec,
 };
@@ -1,11 +1,20 @@
 #![no_std]
 use access_control::{self, Role};
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, Env, IntoVal, String, Symbol, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, Address, Env, IntoVal,
+    String, Symbol, TryFromVal, Vec,
 };
 
 mod test;
 
+/// The parts of the governance token that voting power is read from.
+#[contractclient(name = "VotesTokenClient")]
+pub trait VotesToken {
+    fn balance(env: Env, id: Address) -> i128;
+    /// Balance `id` held at the end of `ledger`.
+    fn balance_at(env: Env, id: Address, ledger: u32) -> i128;
+}
+
 // ─────────────────────────────────────────────────
 // Constants
 // ─────────────────────────────────────────────────
@@ -13,6 +22,12 @@ mod test;
 const PROPOSAL_DURATION: u64 = 604_800; // 7 days in seconds
 const DEFAULT_QUORUM_THRESHOLD: i128 = 1; // Default minimum total votes required to consider a proposal valid
 const PENDING_TX_TIMEOUT_SECONDS: u64 = 604_800; // 7 days — pending multi-sig tx expiry
+const DEFAULT_CLOSE_FACTOR_BPS: u32 = 5000; // Max share of a loan repaid per liquidation
+const MAX_BPS: u32 = 10000;
+const DEFAULT_TIMELOCK_DELAY: u64 = 172_800; // 2 days between queueing and execution
+const MIN_TIMELOCK_DELAY: u64 = 3_600; // 1 hour
+const TIMELOCK_GRACE_PERIOD: u64 = 1_209_600; // 14 days — queued proposals go stale after this
+const MAX_DELEGATORS: u32 = 50; // Distinct accounts that may ever delegate to one delegate
 
 // ─────────────────────────────────────────────────
 // Storage Keys
@@ -28,6 +43,7 @@ pub enum DataKey {
     Delegation(Address),
     Delegators(Address),
     DelegationHistory,
+    // Legacy admin-written balances (kept for enum stability)
     TokenBalance(Address),
     // Legacy vote storage (kept for enum stability)
     Vote(Address, u32),
@@ -40,6 +56,16 @@ pub enum DataKey {
     NextProposalId,
     Proposal(u32),
     UserVoteChoice(Address, u32),
+    AssetLiquidationBonus(Address),
+    CloseFactor,
+    // Timelock
+    TimelockDelay,
+    GuardianConfig,
+    GuardianVetoes(u32),
+    // Token-weighted voting
+    GovernanceToken,
+    DelegateCheckpoints(Address),
+    PastDelegators(Address),
 }
 
 // ────────────────────────────────────���────────────
@@ -54,6 +80,7 @@ pub enum ProposalStatus {
     Rejected,
     Executed,
     Cancelled,
+    Queued,
 }
 
 #[contracttype]
@@ -80,6 +107,8 @@ pub struct Proposal {
     pub target: Address,
     pub function: Symbol,
     pub args: Vec<soroban_sdk::Val>,
+    pub eta: u64,             // earliest execution time once queued, 0 before
+    pub snapshot_ledger: u32, // ledger whose balances and delegations count for votes
 }
 
 #[contracttype]
@@ -137,6 +166,15 @@ pub struct DelegationRecord {
     pub action: DelegationAction,
 }
 
+/// Who a delegator's votes went to from `ledger` on; `None` once
+/// undelegated.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegateCheckpoint {
+    pub ledger: u32,
+    pub delegate: Option<Address>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DelegationAction {
@@ -180,6 +218,20 @@ pub struct ProposalCancelledEvent {
     pub proposer: Address,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalQueuedEvent {
+    pub proposal_id: u32,
+    pub eta: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalVetoedEvent {
+    pub proposal_id: u32,
+    pub guardians: Vec<Address>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct VotesDelegatedEvent {
@@ -234,6 +286,12 @@ pub enum GovernanceError {
     ContractPaused = 19,
     TransactionExpired = 20,
     TransactionNotExpired = 21,
+    InvalidParameter = 22,
+    ProposalNotQueued = 23,
+    TimelockNotReady = 24,
+    TimelockExpired = 25,
+    NoVotingPower = 26,
+    TooManyDelegators = 27,
 }
 
 // ─────────────────────────────────────────────────
@@ -252,9 +310,11 @@ pub struct GovernanceContract;
 impl GovernanceContract {
     // ─── Admin / Init ───────────────────────────────
 
+    /// `token` is the governance token voting power is read from.
     pub fn initialize(
         env: Env,
         admin: Address,
+        token: Address,
         interest_rate: u32,
         collateral_ratio: u32,
         liquidation_bonus: u32,
@@ -263,6 +323,9 @@ impl GovernanceContract {
             return Err(GovernanceError::AlreadyInitialized);
         }
         env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::GovernanceToken, &token);
         env.storage()
             .instance()
             .set(&DataKey::InterestRate, &interest_rate);
@@ -360,28 +423,77 @@ impl GovernanceContract {
         access_control::require_not_paused(env, GovernanceError::ContractPaused)
     }
 
-    pub fn update_interest_rate(env: Env, new_rate: u32) -> Result<(), GovernanceError> {
-        Self::check_admin(&env)?;
-        env.storage()
-            .instance()
-            .set(&DataKey::InterestRate, &new_rate);
-        Ok(())
+    // Admin parameter updates are queued behind the timelock like a passed
+    // proposal, so guardians can veto them; each returns the proposal ID.
+
+    pub fn update_interest_rate(env: Env, new_rate: u32) -> Result<u32, GovernanceError> {
+        let args = soroban_sdk::vec![&env, new_rate.into_val(&env)];
+        Self::queue_admin_update(&env, "Update Interest Rate", "update_interest_rate", args)
     }
 
-    pub fn update_collateral_ratio(env: Env, new_ratio: u32) -> Result<(), GovernanceError> {
-        Self::check_admin(&env)?;
-        env.storage()
-            .instance()
-            .set(&DataKey::CollateralRatio, &new_ratio);
-        Ok(())
+    pub fn update_collateral_ratio(env: Env, new_ratio: u32) -> Result<u32, GovernanceError> {
+        let args = soroban_sdk::vec![&env, new_ratio.into_val(&env)];
+        Self::queue_admin_update(
+            &env,
+            "Update Collateral Ratio",
+            "update_collateral_ratio",
+            args,
+        )
     }
 
-    pub fn update_liquidation_bonus(env: Env, new_bonus: u32) -> Result<(), GovernanceError> {
-        Self::check_admin(&env)?;
-        env.storage()
-            .instance()
-            .set(&DataKey::LiquidationBonus, &new_bonus);
-        Ok(())
+    pub fn update_liquidation_bonus(env: Env, new_bonus: u32) -> Result<u32, GovernanceError> {
+        let args = soroban_sdk::vec![&env, new_bonus.into_val(&env)];
+        Self::queue_admin_update(
+            &env,
+            "Update Liquidation Bonus",
+            "update_liquidation_bonus",
+            args,
+        )
+    }
+
+    /// Override the liquidation bonus for one asset, in basis points of the
+    /// repaid amount.
+    pub fn update_asset_liquidation_bonus(
+        env: Env,
+        asset: Address,
+        new_bonus: u32,
+    ) -> Result<u32, GovernanceError> {
+        Self::check_bps(new_bonus)?;
+        let args = soroban_sdk::vec![&env, asset.into_val(&env), new_bonus.into_val(&env)];
+        Self::queue_admin_update(
+            &env,
+            "Update Asset Liquidation Bonus",
+            "update_asset_liquidation_bonus",
+            args,
+        )
+    }
+
+    /// Set the largest share of a loan's principal, in basis points, that a
+    /// single liquidation may repay.
+    pub fn update_close_factor(env: Env, new_close_factor: u32) -> Result<u32, GovernanceError> {
+        Self::check_close_factor(new_close_factor)?;
+        let args = soroban_sdk::vec![&env, new_close_factor.into_val(&env)];
+        Self::queue_admin_update(&env, "Update Close Factor", "update_close_factor", args)
+    }
+
+    /// Queue a call to one of this contract's own parameter updates as the
+    /// admin; `invoke_target` applies it once the timelock delay has passed.
+    fn queue_admin_update(
+        env: &Env,
+        title: &str,
+        function: &str,
+        args: Vec<soroban_sdk::Val>,
+    ) -> Result<u32, GovernanceError> {
+        Self::check_admin(env)?;
+        Self::require_not_paused(env)?;
+        Ok(Self::queue_call(
+            env,
+            Self::get_admin(env.clone()),
+            title,
+            env.current_contract_address(),
+            Symbol::new(env, function),
+            args,
+        ))
     }
 
     // Multi-sig versions of critical functions
@@ -395,8 +507,8 @@ impl GovernanceContract {
         Self::create_proposal(
             env.clone(),
             proposer,
-            String::from_slice(&env, "Update Interest Rate"),
-            String::from_slice(&env, "Proposal to update the interest rate"),
+            String::from_str(&env, "Update Interest Rate"),
+            String::from_str(&env, "Proposal to update the interest rate"),
             env.current_contract_address(),
             Symbol::new(&env, "update_interest_rate"),
             args,
@@ -413,8 +525,8 @@ impl GovernanceContract {
         Self::create_proposal(
             env.clone(),
             proposer,
-            String::from_slice(&env, "Update Collateral Ratio"),
-            String::from_slice(&env, "Proposal to update the collateral ratio"),
+            String::from_str(&env, "Update Collateral Ratio"),
+            String::from_str(&env, "Proposal to update the collateral ratio"),
             env.current_contract_address(),
             Symbol::new(&env, "update_collateral_ratio"),
             args,
@@ -431,14 +543,72 @@ impl GovernanceContract {
         Self::create_proposal(
             env.clone(),
             proposer,
-            String::from_slice(&env, "Update Liquidation Bonus"),
-            String::from_slice(&env, "Proposal to update the liquidation bonus"),
+            String::from_str(&env, "Update Liquidation Bonus"),
+            String::from_str(&env, "Proposal to update the liquidation bonus"),
             env.current_contract_address(),
             Symbol::new(&env, "update_liquidation_bonus"),
             args,
         )
     }
 
+    pub fn propose_asset_liquidation_bonus(
+        env: Env,
+        proposer: Address,
+        asset: Address,
+        new_bonus: u32,
+    ) -> Result<u32, GovernanceError> {
+        let mut args = Vec::new(&env);
+        args.push_back(asset.into_val(&env));
+        args.push_back(new_bonus.into_val(&env));
+        Self::create_proposal(
+            env.clone(),
+            proposer,
+            String::from_str(&env, "Update Asset Liquidation Bonus"),
+            String::from_str(&env, "Proposal to update the liquidation bonus of an asset"),
+            env.current_contract_address(),
+            Symbol::new(&env, "update_asset_liquidation_bonus"),
+            args,
+        )
+    }
+
+    pub fn propose_update_close_factor(
+        env: Env,
+        proposer: Address,
+        new_close_factor: u32,
+    ) -> Result<u32, GovernanceError> {
+        let mut args = Vec::new(&env);
+        args.push_back(new_close_factor.into_val(&env));
+        Self::create_proposal(
+            env.clone(),
+            proposer,
+            String::from_str(&env, "Update Close Factor"),
+            String::from_str(&env, "Proposal to update the liquidation close factor"),
+            env.current_contract_address(),
+            Symbol::new(&env, "update_close_factor"),
+            args,
+        )
+    }
+
+    /// Propose a new timelock delay. The delay can only change through the
+    /// timelock itself.
+    pub fn propose_update_timelock_delay(
+        env: Env,
+        proposer: Address,
+        new_delay: u64,
+    ) -> Result<u32, GovernanceError> {
+        let mut args = Vec::new(&env);
+        args.push_back(new_delay.into_val(&env));
+        Self::create_proposal(
+            env.clone(),
+            proposer,
+            String::from_str(&env, "Update Timelock Delay"),
+            String::from_str(&env, "Proposal to update the timelock delay"),
+            env.current_contract_address(),
+            Symbol::new(&env, "update_timelock_delay"),
+            args,
+        )
+    }
+
     pub fn get_interest_rate(env: Env) -> u32 {
         env.storage()
             .instance()
@@ -453,13 +623,23 @@ impl GovernanceContract {
             .unwrap_or(0)
     }
 
-    pub fn get_liquidation_bonus(env: Env) -> u32 {
+    /// Liquidation bonus for `asset` in basis points: its override if one is
+    /// set, otherwise the protocol-wide bonus.
+    pub fn get_liquidation_bonus(env: Env, asset: Address) -> u32 {
         env.storage()
             .instance()
-            .get(&DataKey::LiquidationBonus)
+            .get(&DataKey::AssetLiquidationBonus(asset))
+            .or_else(|| env.storage().instance().get(&DataKey::LiquidationBonus))
             .unwrap_or(0)
     }
 
+    pub fn get_close_factor(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CloseFactor)
+            .unwrap_or(DEFAULT_CLOSE_FACTOR_BPS)
+    }
+
     pub fn get_quorum_threshold(env: Env) -> i128 {
         env.storage()
             .instance()
@@ -467,15 +647,19 @@ impl GovernanceContract {
             .unwrap_or(DEFAULT_QUORUM_THRESHOLD)
     }
 
-    pub fn set_quorum_threshold(env: Env, new_threshold: i128) -> Result<(), GovernanceError> {
-        Self::check_admin(&env)?;
+    /// Queue a new quorum threshold through the timelock. Returns the ID of
+    /// the queued proposal.
+    pub fn set_quorum_threshold(env: Env, new_threshold: i128) -> Result<u32, GovernanceError> {
         if new_threshold < 0 {
             return Err(GovernanceError::ZeroAmount);
         }
-        env.storage()
-            .instance()
-            .set(&DataKey::QuorumThreshold, &new_threshold);
-        Ok(())
+        let args = soroban_sdk::vec![&env, new_threshold.into_val(&env)];
+        Self::queue_admin_update(
+            &env,
+            "Update Quorum Threshold",
+            "set_quorum_threshold",
+            args,
+        )
     }
 
     pub fn get_multi_sig_config(env: Env) -> MultiSig {
@@ -587,7 +771,7 @@ impl GovernanceContract {
         multi_sig: &MultiSig,
     ) -> Result<(), GovernanceError> {
         // Check threshold is met
-        if (signatures.len() as u32) < multi_sig.threshold {
+        if signatures.len() < multi_sig.threshold {
             return Err(GovernanceError::QuorumNotMet);
         }
 
@@ -648,10 +832,15 @@ impl GovernanceContract {
             .instance()
             .set(&DataKey::PendingTransaction(tx_id), &pending_tx);
 
-        let result = env.invoke_contract(&pending_tx.target, &pending_tx.function, pending_tx.args);
+        let result = Self::invoke_target(
+            &env,
+            &pending_tx.target,
+            &pending_tx.function,
+            pending_tx.args,
+        );
 
         access_control::reentrancy_exit(&env);
-        Ok(result)
+        result
     }
 
     pub fn get_pending_transaction(env: Env, tx_id: u32) -> Option<PendingTransaction> {
@@ -684,6 +873,90 @@ impl GovernanceContract {
         Ok(())
     }
 
+    /// Invoke `function` on `target`. Calls targeting this contract are
+    /// applied directly, since Soroban rejects a contract re-entering itself.
+    fn invoke_target(
+        env: &Env,
+        target: &Address,
+        function: &Symbol,
+        args: Vec<soroban_sdk::Val>,
+    ) -> Result<soroban_sdk::Val, GovernanceError> {
+        if *target != env.current_contract_address() {
+            return Ok(env.invoke_contract(target, function, args));
+        }
+        let arg = |i: u32| args.get(i).ok_or(GovernanceError::InvalidParameter);
+        let u32_arg = |i: u32| {
+            u32::try_from_val(env, &arg(i)?).map_err(|_| GovernanceError::InvalidParameter)
+        };
+        let storage = env.storage().instance();
+        if *function == Symbol::new(env, "update_interest_rate") {
+            storage.set(&DataKey::InterestRate, &u32_arg(0)?);
+        } else if *function == Symbol::new(env, "update_collateral_ratio") {
+            storage.set(&DataKey::CollateralRatio, &u32_arg(0)?);
+        } else if *function == Symbol::new(env, "update_liquidation_bonus") {
+            storage.set(&DataKey::LiquidationBonus, &u32_arg(0)?);
+        } else if *function == Symbol::new(env, "update_asset_liquidation_bonus") {
+            let asset = Address::try_from_val(env, &arg(0)?)
+                .map_err(|_| GovernanceError::InvalidParameter)?;
+            let bonus = u32_arg(1)?;
+            Self::check_bps(bonus)?;
+            storage.set(&DataKey::AssetLiquidationBonus(asset), &bonus);
+        } else if *function == Symbol::new(env, "update_close_factor") {
+            let close_factor = u32_arg(0)?;
+            Self::check_close_factor(close_factor)?;
+            storage.set(&DataKey::CloseFactor, &close_factor);
+        } else if *function == Symbol::new(env, "set_quorum_threshold") {
+            let threshold =
+                i128::try_from_val(env, &arg(0)?).map_err(|_| GovernanceError::InvalidParameter)?;
+            if threshold < 0 {
+                return Err(GovernanceError::ZeroAmount);
+            }
+            storage.set(&DataKey::QuorumThreshold, &threshold);
+        } else if *function == Symbol::new(env, "set_guardians") {
+            let guardians = MultiSig {
+                signers: Vec::try_from_val(env, &arg(0)?)
+                    .map_err(|_| GovernanceError::InvalidParameter)?,
+                threshold: u32_arg(1)?,
+            };
+            Self::check_multi_sig(&guardians)?;
+            storage.set(&DataKey::GuardianConfig, &guardians);
+        } else if *function == Symbol::new(env, "update_timelock_delay") {
+            let delay =
+                u64::try_from_val(env, &arg(0)?).map_err(|_| GovernanceError::InvalidParameter)?;
+            if delay < MIN_TIMELOCK_DELAY {
+                return Err(GovernanceError::InvalidParameter);
+            }
+            storage.set(&DataKey::TimelockDelay, &delay);
+        } else {
+            return Err(GovernanceError::InvalidParameter);
+        }
+        Ok(soroban_sdk::Val::VOID.into())
+    }
+
+    fn check_bps(bps: u32) -> Result<(), GovernanceError> {
+        if bps > MAX_BPS {
+            return Err(GovernanceError::InvalidParameter);
+        }
+        Ok(())
+    }
+
+    fn check_close_factor(close_factor: u32) -> Result<(), GovernanceError> {
+        if close_factor == 0 || close_factor > MAX_BPS {
+            return Err(GovernanceError::InvalidParameter);
+        }
+        Ok(())
+    }
+
+    fn check_multi_sig(config: &MultiSig) -> Result<(), GovernanceError> {
+        if config.signers.is_empty()
+            || config.threshold == 0
+            || config.threshold > config.signers.len()
+        {
+            return Err(GovernanceError::InvalidParameter);
+        }
+        Ok(())
+    }
+
     fn check_admin(env: &Env) -> Result<(), GovernanceError> {
         let admin: Address = env
             .storage()
@@ -696,17 +969,20 @@ impl GovernanceContract {
 
     // ─── Token Balance ───────────────────────────────
 
-    pub fn set_token_balance(env: Env, address: Address, balance: i128) {
+    pub fn get_governance_token(env: Env) -> Address {
         env.storage()
             .instance()
-            .set(&DataKey::TokenBalance(address), &balance);
+            .get(&DataKey::GovernanceToken)
+            .expect("Not initialized")
     }
 
+    /// Current governance token balance of `address`.
     pub fn get_token_balance(env: Env, address: Address) -> i128 {
-        env.storage()
-            .instance()
-            .get(&DataKey::TokenBalance(address))
-            .unwrap_or(0)
+        Self::votes_token(&env).balance(&address)
+    }
+
+    fn votes_token(env: &Env) -> VotesTokenClient<'_> {
+        VotesTokenClient::new(env, &Self::get_governance_token(env.clone()))
     }
 
     // ─── Delegation ──────────────────────────────────
@@ -728,8 +1004,27 @@ impl GovernanceContract {
         }
 
         let delegator_balance = Self::get_token_balance(env.clone(), delegator.clone());
+        if delegator_balance <= 0 {
+            return Err(GovernanceError::NoVotingPower);
+        }
         let existing_delegate = Self::get_delegate(env.clone(), delegator.clone());
 
+        // Voting power walks every account that ever delegated to `delegate`,
+        // so that list is capped.
+        let past_key = DataKey::PastDelegators(delegate.clone());
+        let mut past: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&past_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        if !past.contains(&delegator) {
+            if past.len() >= MAX_DELEGATORS {
+                return Err(GovernanceError::TooManyDelegators);
+            }
+            past.push_back(delegator.clone());
+            env.storage().persistent().set(&past_key, &past);
+        }
+
         let history_key = DataKey::DelegationHistory;
         let mut history: Vec<DelegationRecord> = env
             .storage()
@@ -768,7 +1063,8 @@ impl GovernanceContract {
 
             // Emit delegation event
             let new_delegate_power =
-                Self::get_voting_power(env.clone(), delegate.clone()) + delegator_balance;
+                Self::get_voting_power(env.clone(), delegate.clone(), env.ledger().sequence())
+                    + delegator_balance;
             env.events().publish(
                 (Symbol::new(&env, "VotesDelegated"), delegator.clone()),
                 VotesDelegatedEvent {
@@ -787,6 +1083,7 @@ impl GovernanceContract {
             .set(&DataKey::Delegation(delegator.clone()), &delegate);
 
         Self::add_to_delegators(&env, &delegate, &delegator);
+        Self::checkpoint_delegate(&env, &delegator, Some(delegate));
 
         Ok(())
     }
@@ -809,6 +1106,7 @@ impl GovernanceContract {
         env.storage()
             .instance()
             .remove(&DataKey::Delegation(delegator.clone()));
+        Self::checkpoint_delegate(&env, &delegator, None);
 
         let history_key = DataKey::DelegationHistory;
         let mut history: Vec<DelegationRecord> = env
@@ -853,39 +1151,30 @@ impl GovernanceContract {
             .unwrap_or_else(|| Vec::new(&env))
     }
 
-    pub fn get_voting_power(env: Env, address: Address) -> i128 {
-        // Delegated accounts have zero direct voting power
-        if env
-            .storage()
-            .instance()
-            .has(&DataKey::Delegation(address.clone()))
-        {
-            return 0;
-        }
+    /// Voting power of `address` at the end of `ledger`: its own token
+    /// balance unless it had delegated, plus the balances of everyone who had
+    /// delegated to it, all as they stood at that ledger. At most
+    /// `MAX_DELEGATORS` past delegators are read.
+    pub fn get_voting_power(env: Env, address: Address, ledger: u32) -> i128 {
+        let token = Self::votes_token(&env);
 
-        let own_balance: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TokenBalance(address.clone()))
-            .unwrap_or(0);
+        let mut power: i128 = 0;
+        if Self::delegate_at(&env, &address, ledger).is_none() {
+            power += token.balance_at(&address, &ledger);
+        }
 
-        let delegators: Vec<Address> = env
+        let past_delegators: Vec<Address> = env
             .storage()
-            .instance()
-            .get(&DataKey::Delegators(address.clone()))
+            .persistent()
+            .get(&DataKey::PastDelegators(address.clone()))
             .unwrap_or_else(|| Vec::new(&env));
-
-        let mut total_delegated: i128 = 0;
-        for delegator_addr in delegators.iter() {
-            let bal: i128 = env
-                .storage()
-                .instance()
-                .get(&DataKey::TokenBalance(delegator_addr))
-                .unwrap_or(0);
-            total_delegated += bal;
+        for delegator in past_delegators.iter() {
+            if Self::delegate_at(&env, &delegator, ledger).as_ref() == Some(&address) {
+                power += token.balance_at(&delegator, &ledger);
+            }
         }
 
-        own_balance + total_delegated
+        power
     }
 
     pub fn get_delegation_history(env: Env) -> Vec<DelegationRecord> {
@@ -912,7 +1201,7 @@ impl GovernanceContract {
     /// Get all delegators who have delegated to a specific delegate
     pub fn get_delegate_info(env: Env, delegate: Address) -> (i128, Vec<Address>) {
         let delegators = Self::get_delegators(env.clone(), delegate.clone());
-        let voting_power = Self::get_voting_power(env, delegate);
+        let voting_power = Self::get_voting_power(env.clone(), delegate, env.ledger().sequence());
         (voting_power, delegators)
     }
 
@@ -927,12 +1216,11 @@ impl GovernanceContract {
         let mut unique_delegators = Vec::new(&env);
 
         for record in history.iter() {
-            if record.action == DelegationAction::Delegated
-                || record.action == DelegationAction::Redelegated
+            if (record.action == DelegationAction::Delegated
+                || record.action == DelegationAction::Redelegated)
+                && !unique_delegators.contains(&record.delegator)
             {
-                if !unique_delegators.contains(&record.delegator) {
-                    unique_delegators.push_back(record.delegator);
-                }
+                unique_delegators.push_back(record.delegator);
             }
         }
 
@@ -947,7 +1235,7 @@ impl GovernanceContract {
         }
 
         // Otherwise return their full voting power (own + delegated)
-        Self::get_voting_power(env, address)
+        Self::get_voting_power(env.clone(), address, env.ledger().sequence())
     }
 
     // ─── Proposal Governance ─────────────────────────
@@ -988,6 +1276,8 @@ impl GovernanceContract {
             target,
             function,
             args,
+            eta: 0,
+            snapshot_ledger: Self::snapshot_ledger(&env),
         };
 
         env.storage()
@@ -1010,7 +1300,8 @@ impl GovernanceContract {
     }
 
     /// Vote on a proposal with yes, no, or abstain.
-    /// Voting power is automatically derived from the voter's token balance plus any delegated balances.
+    /// Voting power is the voter's governance token balance plus any delegated balances, as of the
+    /// proposal's snapshot ledger.
     pub fn vote(
         env: Env,
         voter: Address,
@@ -1020,40 +1311,6 @@ impl GovernanceContract {
         voter.require_auth();
         Self::require_not_paused(&env)?;
 
-        // Delegated voters cannot vote directly
-        if env
-            .storage()
-            .instance()
-            .has(&DataKey::Delegation(voter.clone()))
-        {
-            return Err(GovernanceError::Unauthorized);
-        }
-
-        // Compute voting power inline
-        let own_balance: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TokenBalance(voter.clone()))
-            .unwrap_or(0);
-        let delegators: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Delegators(voter.clone()))
-            .unwrap_or_else(|| Vec::new(&env));
-        let mut voting_power = own_balance;
-        for delegator_addr in delegators.iter() {
-            let bal: i128 = env
-                .storage()
-                .instance()
-                .get(&DataKey::TokenBalance(delegator_addr))
-                .unwrap_or(0);
-            voting_power += bal;
-        }
-
-        if voting_power == 0 {
-            return Err(GovernanceError::ZeroAmount);
-        }
-
         // Validate proposal state
         let mut proposal: Proposal = env
             .storage()
@@ -1070,6 +1327,19 @@ impl GovernanceContract {
             return Err(GovernanceError::ProposalNotActive);
         }
 
+        // Voters who had delegated at the snapshot cannot vote directly
+        if Self::delegate_at(&env, &voter, proposal.snapshot_ledger).is_some() {
+            return Err(GovernanceError::Unauthorized);
+        }
+
+        // Voting power as of the proposal's snapshot, so tokens moved or
+        // delegated afterwards cannot be counted twice
+        let voting_power =
+            Self::get_voting_power(env.clone(), voter.clone(), proposal.snapshot_ledger);
+        if voting_power == 0 {
+            return Err(GovernanceError::ZeroAmount);
+        }
+
         // Prevent double voting
         let vote_choice_key = DataKey::UserVoteChoice(voter.clone(), proposal_id);
         if env.storage().instance().has(&vote_choice_key) {
@@ -1104,10 +1374,47 @@ impl GovernanceContract {
         Ok(())
     }
 
-    /// Execute a passed proposal. Anyone can call this after the voting period ends.
+    /// Move a passed proposal into the timelock queue. Anyone can call this
+    /// once voting has ended. Returns the earliest time the proposal can be
+    /// executed.
+    pub fn queue_proposal(env: Env, proposal_id: u32) -> Result<u64, GovernanceError> {
+        Self::require_not_paused(&env)?;
+
+        let status = Self::evaluate_proposal_status(&env, proposal_id)?;
+        if status == ProposalStatus::Executed {
+            return Err(GovernanceError::ProposalAlreadyExecuted);
+        }
+        if status != ProposalStatus::Passed {
+            return Err(GovernanceError::ProposalNotPassed);
+        }
+
+        let mut proposal: Proposal = env
+            .storage()
+            .instance()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(GovernanceError::ProposalNotFound)?;
+        proposal.status = ProposalStatus::Queued;
+        proposal.eta = env.ledger().timestamp() + Self::get_timelock_delay(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+
+        env.events().publish(
+            (Symbol::new(&env, "PropQueue"), proposal_id),
+            ProposalQueuedEvent {
+                proposal_id,
+                eta: proposal.eta,
+            },
+        );
+
+        Ok(proposal.eta)
+    }
+
+    /// Execute a queued proposal. Anyone can call this once the timelock
+    /// delay has passed and before the grace period runs out.
     ///
     /// This function:
-    /// 1. Validates the proposal has passed voting
+    /// 1. Validates the proposal is queued and its timelock has elapsed
     /// 2. Invokes the target contract with the stored function and arguments
     /// 3. Updates the proposal status to Executed
     /// 4. Emits a ProposalExecutedEvent
@@ -1124,6 +1431,9 @@ impl GovernanceContract {
     /// # Errors
     /// * `ProposalNotFound` - If the proposal does not exist
     /// * `ProposalNotPassed` - If the proposal has not passed voting
+    /// * `ProposalNotQueued` - If the proposal passed but was not queued
+    /// * `TimelockNotReady` - If the timelock delay has not yet elapsed
+    /// * `TimelockExpired` - If the grace period after the delay has passed
     /// * `ProposalAlreadyExecuted` - If the proposal has already been executed
     /// * `ContractPaused` - If the governance contract is paused
     /// * `ReentrantCall` - If a reentrancy attempt is detected
@@ -1136,30 +1446,27 @@ impl GovernanceContract {
         access_control::reentrancy_enter(&env, GovernanceError::ReentrantCall)?;
         Self::require_not_paused(&env)?;
 
-        let status = Self::evaluate_proposal_status(&env, proposal_id)?;
-        if status != ProposalStatus::Passed {
-            access_control::reentrancy_exit(&env);
-            return Err(GovernanceError::ProposalNotPassed);
-        }
-
         let mut proposal: Proposal = env
             .storage()
             .instance()
             .get(&DataKey::Proposal(proposal_id))
             .ok_or(GovernanceError::ProposalNotFound)?;
 
-        // Prevent double execution
-        if proposal.status == ProposalStatus::Executed {
+        if let Err(err) = Self::check_executable(&env, &proposal) {
             access_control::reentrancy_exit(&env);
-            return Err(GovernanceError::ProposalAlreadyExecuted);
+            return Err(err);
         }
 
         // Execute the proposal's intended action on the target contract
-        env.invoke_contract::<soroban_sdk::Val>(
+        if let Err(err) = Self::invoke_target(
+            &env,
             &proposal.target,
             &proposal.function,
             proposal.args.clone(),
-        );
+        ) {
+            access_control::reentrancy_exit(&env);
+            return Err(err);
+        }
 
         // Update proposal status to Executed
         proposal.status = ProposalStatus::Executed;
@@ -1167,6 +1474,16 @@ impl GovernanceContract {
             .instance()
             .set(&DataKey::Proposal(proposal_id), &proposal);
 
+        if proposal.target != env.current_contract_address() {
+            env.events().publish(
+                (Symbol::new(&env, "EXECUTE"), proposal.target.clone()),
+                ContractExecutedEvent {
+                    contract: proposal.target,
+                    func: proposal.function,
+                },
+            );
+        }
+
         // Emit execution event
         env.events().publish(
             (Symbol::new(&env, "PropExec"), executor.clone()),
@@ -1180,6 +1497,110 @@ impl GovernanceContract {
         Ok(())
     }
 
+    /// Seconds a queued proposal waits before it can be executed.
+    pub fn get_timelock_delay(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TimelockDelay)
+            .unwrap_or(DEFAULT_TIMELOCK_DELAY)
+    }
+
+    /// Seconds after its ETA during which a queued proposal stays executable.
+    pub fn get_timelock_grace_period() -> u64 {
+        TIMELOCK_GRACE_PERIOD
+    }
+
+    /// Queue a new guardian multisig, which can veto queued proposals,
+    /// through the timelock; the current guardians can veto the change.
+    /// Admin-only. Returns the ID of the queued proposal.
+    pub fn set_guardians(
+        env: Env,
+        admin: Address,
+        signers: Vec<Address>,
+        threshold: u32,
+    ) -> Result<u32, GovernanceError> {
+        admin.require_auth();
+        Self::check_multi_sig(&MultiSig {
+            signers: signers.clone(),
+            threshold,
+        })?;
+        let args = soroban_sdk::vec![&env, signers.into_val(&env), threshold.into_val(&env)];
+        Self::queue_admin_update(&env, "Update Guardians", "set_guardians", args)
+    }
+
+    pub fn get_guardians(env: Env) -> Option<MultiSig> {
+        env.storage().instance().get(&DataKey::GuardianConfig)
+    }
+
+    /// Approve vetoing a queued proposal as one of the guardians. Once the
+    /// guardian threshold is reached the proposal is cancelled. Returns
+    /// whether it was.
+    ///
+    /// Guardians can veto while the contract is paused.
+    pub fn veto_proposal(
+        env: Env,
+        guardian: Address,
+        proposal_id: u32,
+    ) -> Result<bool, GovernanceError> {
+        guardian.require_auth();
+
+        let guardians: MultiSig = env
+            .storage()
+            .instance()
+            .get(&DataKey::GuardianConfig)
+            .ok_or(GovernanceError::Unauthorized)?;
+        if !guardians.signers.contains(&guardian) {
+            return Err(GovernanceError::Unauthorized);
+        }
+
+        let mut proposal: Proposal = env
+            .storage()
+            .instance()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(GovernanceError::ProposalNotFound)?;
+        if proposal.status != ProposalStatus::Queued {
+            return Err(GovernanceError::ProposalNotQueued);
+        }
+
+        let key = DataKey::GuardianVetoes(proposal_id);
+        let mut vetoes: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env));
+        if !vetoes.contains(&guardian) {
+            vetoes.push_back(guardian);
+        }
+
+        // Only guardians still in the set count toward the threshold.
+        let mut approvals: Vec<Address> = Vec::new(&env);
+        for vetoer in vetoes.iter() {
+            if guardians.signers.contains(&vetoer) {
+                approvals.push_back(vetoer);
+            }
+        }
+        if approvals.len() < guardians.threshold {
+            env.storage().instance().set(&key, &vetoes);
+            return Ok(false);
+        }
+
+        proposal.status = ProposalStatus::Cancelled;
+        env.storage()
+            .instance()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+        env.storage().instance().remove(&key);
+
+        env.events().publish(
+            (Symbol::new(&env, "PropVeto"), proposal_id),
+            ProposalVetoedEvent {
+                proposal_id,
+                guardians: approvals,
+            },
+        );
+
+        Ok(true)
+    }
+
     /// Get full proposal details.
     pub fn get_proposal(env: Env, proposal_id: u32) -> Option<Proposal> {
         env.storage()
@@ -1308,6 +1729,79 @@ impl GovernanceContract {
         }
     }
 
+    fn check_executable(env: &Env, proposal: &Proposal) -> Result<(), GovernanceError> {
+        match proposal.status {
+            ProposalStatus::Queued => {}
+            ProposalStatus::Executed => return Err(GovernanceError::ProposalAlreadyExecuted),
+            _ => {
+                return match Self::evaluate_proposal_status(env, proposal.id)? {
+                    ProposalStatus::Passed => Err(GovernanceError::ProposalNotQueued),
+                    _ => Err(GovernanceError::ProposalNotPassed),
+                }
+            }
+        }
+
+        let now = env.ledger().timestamp();
+        if now < proposal.eta {
+            return Err(GovernanceError::TimelockNotReady);
+        }
+        if now > proposal.eta + TIMELOCK_GRACE_PERIOD {
+            return Err(GovernanceError::TimelockExpired);
+        }
+        Ok(())
+    }
+
+    /// Store a call that skips voting and goes straight into the timelock
+    /// queue, as a proposal by `proposer`. Returns the proposal ID.
+    fn queue_call(
+        env: &Env,
+        proposer: Address,
+        title: &str,
+        target: Address,
+        function: Symbol,
+        args: Vec<soroban_sdk::Val>,
+    ) -> u32 {
+        let proposal_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProposalId)
+            .unwrap_or(1u32);
+
+        let now = env.ledger().timestamp();
+        let eta = now + Self::get_timelock_delay(env.clone());
+        let proposal = Proposal {
+            id: proposal_id,
+            title: String::from_str(env, title),
+            description: String::from_str(env, "Queued by the admin"),
+            proposer,
+            yes_votes: 0,
+            no_votes: 0,
+            abstain_votes: 0,
+            status: ProposalStatus::Queued,
+            created_at: now,
+            expires_at: now,
+            target,
+            function,
+            args,
+            eta,
+            snapshot_ledger: Self::snapshot_ledger(env),
+        };
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposalId, &(proposal_id + 1));
+
+        env.events().publish(
+            (Symbol::new(env, "PropQueue"), proposal_id),
+            ProposalQueuedEvent { proposal_id, eta },
+        );
+
+        proposal_id
+    }
+
     fn check_circular_delegation(
         env: &Env,
         delegator: &Address,
@@ -1376,6 +1870,49 @@ impl GovernanceContract {
         }
     }
 
+    /// Snapshot for a proposal created now: the last closed ledger, whose
+    /// balances can no longer change.
+    fn snapshot_ledger(env: &Env) -> u32 {
+        env.ledger().sequence().saturating_sub(1)
+    }
+
+    fn checkpoint_delegate(env: &Env, delegator: &Address, delegate: Option<Address>) {
+        let key = DataKey::DelegateCheckpoints(delegator.clone());
+        let mut history: Vec<DelegateCheckpoint> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(env));
+        let ledger = env.ledger().sequence();
+        let entry = DelegateCheckpoint { ledger, delegate };
+        match history.last() {
+            Some(last) if last.ledger == ledger => history.set(history.len() - 1, entry),
+            _ => history.push_back(entry),
+        }
+        env.storage().persistent().set(&key, &history);
+    }
+
+    /// Who `delegator` had delegated to at the end of `ledger`.
+    fn delegate_at(env: &Env, delegator: &Address, ledger: u32) -> Option<Address> {
+        let history: Vec<DelegateCheckpoint> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DelegateCheckpoints(delegator.clone()))?;
+        let (mut low, mut high) = (0, history.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            if history.get_unchecked(mid).ledger <= ledger {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        if low == 0 {
+            return None;
+        }
+        history.get_unchecked(low - 1).delegate
+    }
+
     // ─── Cross-Contract Integration ──────────────────
 
     pub fn add_controlled_contract(
@@ -1413,15 +1950,17 @@ impl GovernanceContract {
             .unwrap_or_else(|| Vec::new(&env))
     }
 
+    /// Queue a call to a controlled contract. It goes through the timelock
+    /// like a passed proposal and can be vetoed by the guardians. Returns the
+    /// ID of the queued proposal.
     pub fn execute_on_contract(
         env: Env,
         _admin: Address,
         contract: Address,
         func: Symbol,
         args: Vec<soroban_sdk::Val>,
-    ) -> Result<soroban_sdk::Val, GovernanceError> {
+    ) -> Result<u32, GovernanceError> {
         Self::check_admin(&env)?;
-        access_control::reentrancy_enter(&env, GovernanceError::ReentrantCall)?;
         Self::require_not_paused(&env)?;
 
         let contracts = Self::get_controlled_contracts(env.clone());
@@ -1429,25 +1968,25 @@ impl GovernanceContract {
             return Err(GovernanceError::Unauthorized);
         }
 
-        let result = env.invoke_contract(&contract, &func, args);
-
-        env.events().publish(
-            (Symbol::new(&env, "EXECUTE"), contract.clone()),
-            ContractExecutedEvent { contract, func },
-        );
-
-        access_control::reentrancy_exit(&env);
-        Ok(result)
+        Ok(Self::queue_call(
+            &env,
+            Self::get_admin(env.clone()),
+            "Admin Call",
+            contract,
+            func,
+            args,
+        ))
     }
 
+    /// Queue an upgrade of a controlled contract to `new_wasm_hash` through
+    /// the timelock. Returns the ID of the queued proposal.
     pub fn upgrade_controlled_contract(
         env: Env,
         _admin: Address,
         contract: Address,
         new_wasm_hash: soroban_sdk::BytesN<32>,
-    ) -> Result<(), GovernanceError> {
+    ) -> Result<u32, GovernanceError> {
         Self::check_admin(&env)?;
-        access_control::reentrancy_enter(&env, GovernanceError::ReentrantCall)?;
         Self::require_not_paused(&env)?;
 
         let contracts = Self::get_controlled_contracts(env.clone());
@@ -1459,13 +1998,13 @@ impl GovernanceContract {
         args.push_back(env.current_contract_address().into_val(&env));
         args.push_back(new_wasm_hash.into_val(&env));
 
-        env.invoke_contract::<soroban_sdk::Val>(
-            &contract,
-            &Symbol::new(&env, "upgrade_contract"),
+        Ok(Self::queue_call(
+            &env,
+            Self::get_admin(env.clone()),
+            "Contract Upgrade",
+            contract,
+            Symbol::new(&env, "upgrade_contract"),
             args,
-        );
-
-        access_control::reentrancy_exit(&env);
-        Ok(())
+        ))
     }
 }
@@ -1,6 +1,7 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::testutils::{Address as _, Ledger};
+use governance_token::{GovernanceToken, GovernanceTokenClient};
+use soroban_sdk::testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke};
 use soroban_sdk::{Env, String, Symbol};
 
 // ─────────────────────────────────────────────────
@@ -11,10 +12,29 @@ fn setup_contract(env: &Env) -> (GovernanceContractClient<'_>, Address) {
     let contract_id = env.register_contract(None, GovernanceContract);
     let client = GovernanceContractClient::new(env, &contract_id);
     let admin = Address::generate(env);
-    client.initialize(&admin, &500, &15000, &500);
+    let token = env.register_contract(None, GovernanceToken);
+    GovernanceTokenClient::new(env, &token).initialize(&admin);
+    client.initialize(&admin, &token, &500, &15000, &500);
     (client, admin)
 }
 
+/// Mint governance tokens to `to`, authorised as the token admin only.
+fn mint(env: &Env, client: &GovernanceContractClient, to: &Address, amount: i128) {
+    let token = client.get_governance_token();
+    let admin = client.get_admin();
+    GovernanceTokenClient::new(env, &token)
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &token,
+                fn_name: "mint",
+                args: (to.clone(), amount).into_val(env),
+                sub_invokes: &[],
+            },
+        }])
+        .mint(to, &amount);
+}
+
 fn make_proposal(env: &Env, client: &GovernanceContractClient, proposer: &Address) -> u32 {
     let contract_id = client.address.clone();
     client.create_proposal(
@@ -23,7 +43,7 @@ fn make_proposal(env: &Env, client: &GovernanceContractClient, proposer: &Addres
         &String::from_str(env, "A test governance proposal"),
         &contract_id,
         &Symbol::new(env, "update_interest_rate"),
-        &Vec::new(env),
+        &soroban_sdk::vec![env, 800u32.into_val(env)],
     )
 }
 
@@ -39,8 +59,8 @@ fn test_delegation_flow() {
     let delegator = Address::generate(&env);
     let delegate = Address::generate(&env);
 
-    client.set_token_balance(&delegator, &1000);
-    client.set_token_balance(&delegate, &500);
+    mint(&env, &client, &delegator, 1000);
+    mint(&env, &client, &delegate, 500);
 
     assert_eq!(client.get_delegate(&delegator), None);
     assert_eq!(client.get_delegators(&delegate).len(), 0);
@@ -52,8 +72,14 @@ fn test_delegation_flow() {
     assert_eq!(client.get_delegators(&delegate).len(), 1);
     assert_eq!(client.get_delegators(&delegate).get(0).unwrap(), delegator);
 
-    assert_eq!(client.get_voting_power(&delegate), 1500);
-    assert_eq!(client.get_voting_power(&delegator), 0);
+    assert_eq!(
+        client.get_voting_power(&delegate, &env.ledger().sequence()),
+        1500
+    );
+    assert_eq!(
+        client.get_voting_power(&delegator, &env.ledger().sequence()),
+        0
+    );
 }
 
 #[test]
@@ -64,22 +90,31 @@ fn test_undelegation_flow() {
     let delegator = Address::generate(&env);
     let delegate = Address::generate(&env);
 
-    client.set_token_balance(&delegator, &1000);
-    client.set_token_balance(&delegate, &500);
+    mint(&env, &client, &delegator, 1000);
+    mint(&env, &client, &delegate, 500);
 
     env.mock_all_auths();
     client.delegate_votes(&delegator, &delegate);
 
     assert_eq!(client.get_delegate(&delegator), Some(delegate.clone()));
-    assert_eq!(client.get_voting_power(&delegate), 1500);
+    assert_eq!(
+        client.get_voting_power(&delegate, &env.ledger().sequence()),
+        1500
+    );
 
     client.undelegate_votes(&delegator);
 
     assert_eq!(client.get_delegate(&delegator), None);
     assert_eq!(client.get_delegators(&delegate).len(), 0);
 
-    assert_eq!(client.get_voting_power(&delegate), 500);
-    assert_eq!(client.get_voting_power(&delegator), 1000);
+    assert_eq!(
+        client.get_voting_power(&delegate, &env.ledger().sequence()),
+        500
+    );
+    assert_eq!(
+        client.get_voting_power(&delegator, &env.ledger().sequence()),
+        1000
+    );
 }
 
 #[test]
@@ -91,16 +126,19 @@ fn test_delegate_votes_with_aggregated_power() {
     let delegator2 = Address::generate(&env);
     let delegate = Address::generate(&env);
 
-    client.set_token_balance(&delegator1, &1000);
-    client.set_token_balance(&delegator2, &2000);
-    client.set_token_balance(&delegate, &500);
+    mint(&env, &client, &delegator1, 1000);
+    mint(&env, &client, &delegator2, 2000);
+    mint(&env, &client, &delegate, 500);
 
     env.mock_all_auths();
     client.delegate_votes(&delegator1, &delegate);
     client.delegate_votes(&delegator2, &delegate);
 
     assert_eq!(client.get_delegators(&delegate).len(), 2);
-    assert_eq!(client.get_voting_power(&delegate), 3500);
+    assert_eq!(
+        client.get_voting_power(&delegate, &env.ledger().sequence()),
+        3500
+    );
 
     let proposal_id = make_proposal(&env, &client, &delegate);
     client.vote(&delegate, &proposal_id, &VoteChoice::Yes);
@@ -114,7 +152,7 @@ fn test_self_delegation_fails() {
     let (client, _admin) = setup_contract(&env);
 
     let user = Address::generate(&env);
-    client.set_token_balance(&user, &1000);
+    mint(&env, &client, &user, 1000);
 
     env.mock_all_auths();
     let result = client.try_delegate_votes(&user, &user);
@@ -130,9 +168,9 @@ fn test_circular_delegation_prevention() {
     let user_b = Address::generate(&env);
     let user_c = Address::generate(&env);
 
-    client.set_token_balance(&user_a, &1000);
-    client.set_token_balance(&user_b, &1000);
-    client.set_token_balance(&user_c, &1000);
+    mint(&env, &client, &user_a, 1000);
+    mint(&env, &client, &user_b, 1000);
+    mint(&env, &client, &user_c, 1000);
 
     env.mock_all_auths();
 
@@ -151,8 +189,8 @@ fn test_circular_delegation_direct() {
     let user_a = Address::generate(&env);
     let user_b = Address::generate(&env);
 
-    client.set_token_balance(&user_a, &1000);
-    client.set_token_balance(&user_b, &1000);
+    mint(&env, &client, &user_a, 1000);
+    mint(&env, &client, &user_b, 1000);
 
     env.mock_all_auths();
 
@@ -172,10 +210,10 @@ fn test_multiple_delegators_to_one_delegate() {
     let delegator3 = Address::generate(&env);
     let delegate = Address::generate(&env);
 
-    client.set_token_balance(&delegator1, &1000);
-    client.set_token_balance(&delegator2, &2000);
-    client.set_token_balance(&delegator3, &3000);
-    client.set_token_balance(&delegate, &500);
+    mint(&env, &client, &delegator1, 1000);
+    mint(&env, &client, &delegator2, 2000);
+    mint(&env, &client, &delegator3, 3000);
+    mint(&env, &client, &delegate, 500);
 
     env.mock_all_auths();
     client.delegate_votes(&delegator1, &delegate);
@@ -185,11 +223,23 @@ fn test_multiple_delegators_to_one_delegate() {
     let delegators = client.get_delegators(&delegate);
     assert_eq!(delegators.len(), 3);
 
-    assert_eq!(client.get_voting_power(&delegate), 6500);
+    assert_eq!(
+        client.get_voting_power(&delegate, &env.ledger().sequence()),
+        6500
+    );
 
-    assert_eq!(client.get_voting_power(&delegator1), 0);
-    assert_eq!(client.get_voting_power(&delegator2), 0);
-    assert_eq!(client.get_voting_power(&delegator3), 0);
+    assert_eq!(
+        client.get_voting_power(&delegator1, &env.ledger().sequence()),
+        0
+    );
+    assert_eq!(
+        client.get_voting_power(&delegator2, &env.ledger().sequence()),
+        0
+    );
+    assert_eq!(
+        client.get_voting_power(&delegator3, &env.ledger().sequence()),
+        0
+    );
 }
 
 #[test]
@@ -201,8 +251,8 @@ fn test_delegation_overwrite() {
     let delegate1 = Address::generate(&env);
     let delegate2 = Address::generate(&env);
 
-    client.set_token_balance(&delegator, &1000);
-    client.set_token_balance(&delegate1, &500);
+    mint(&env, &client, &delegator, 1000);
+    mint(&env, &client, &delegate1, 500);
 
     env.mock_all_auths();
     client.delegate_votes(&delegator, &delegate1);
@@ -221,8 +271,8 @@ fn test_delegator_cannot_vote_when_delegated() {
     let delegator = Address::generate(&env);
     let delegate = Address::generate(&env);
 
-    client.set_token_balance(&delegator, &1000);
-    client.set_token_balance(&delegate, &500);
+    mint(&env, &client, &delegator, 1000);
+    mint(&env, &client, &delegate, 500);
 
     env.mock_all_auths();
     let proposal_id = make_proposal(&env, &client, &admin);
@@ -242,7 +292,7 @@ fn test_delegation_history_tracking() {
     let delegate1 = Address::generate(&env);
     let delegate2 = Address::generate(&env);
 
-    client.set_token_balance(&delegator, &1000);
+    mint(&env, &client, &delegator, 1000);
 
     env.mock_all_auths();
     client.delegate_votes(&delegator, &delegate1);
@@ -270,20 +320,20 @@ fn test_voting_integrity_no_double_counting() {
     let delegator2 = Address::generate(&env);
     let delegate = Address::generate(&env);
 
-    client.set_token_balance(&delegator1, &1000);
-    client.set_token_balance(&delegator2, &2000);
-    client.set_token_balance(&delegate, &500);
+    mint(&env, &client, &delegator1, 1000);
+    mint(&env, &client, &delegator2, 2000);
+    mint(&env, &client, &delegate, 500);
 
     env.mock_all_auths();
     client.delegate_votes(&delegator1, &delegate);
     client.delegate_votes(&delegator2, &delegate);
 
-    let total_voting_power = client.get_voting_power(&delegate);
+    let total_voting_power = client.get_voting_power(&delegate, &env.ledger().sequence());
     assert_eq!(total_voting_power, 3500);
 
-    let delegator1_power = client.get_voting_power(&delegator1);
-    let delegator2_power = client.get_voting_power(&delegator2);
-    let delegate_power = client.get_voting_power(&delegate);
+    let delegator1_power = client.get_voting_power(&delegator1, &env.ledger().sequence());
+    let delegator2_power = client.get_voting_power(&delegator2, &env.ledger().sequence());
+    let delegate_power = client.get_voting_power(&delegate, &env.ledger().sequence());
 
     // Total power is consolidated in delegate; delegators show 0
     let sum_of_all_powers = delegator1_power + delegator2_power + delegate_power;
@@ -304,13 +354,16 @@ fn test_undelegate_then_vote() {
     let delegator = Address::generate(&env);
     let delegate = Address::generate(&env);
 
-    client.set_token_balance(&delegator, &1000);
+    mint(&env, &client, &delegator, 1000);
 
     env.mock_all_auths();
     let proposal_id = make_proposal(&env, &client, &delegator);
     client.delegate_votes(&delegator, &delegate);
 
-    assert_eq!(client.get_voting_power(&delegator), 0);
+    assert_eq!(
+        client.get_voting_power(&delegator, &env.ledger().sequence()),
+        0
+    );
 
     client.undelegate_votes(&delegator);
 
@@ -327,7 +380,7 @@ fn test_no_double_voting() {
 
     let voter = Address::generate(&env);
 
-    client.set_token_balance(&voter, &1000);
+    mint(&env, &client, &voter, 1000);
 
     env.mock_all_auths();
     let proposal_id = make_proposal(&env, &client, &voter);
@@ -348,8 +401,8 @@ fn test_vote_with_exact_voting_power() {
     let delegator = Address::generate(&env);
     let delegate = Address::generate(&env);
 
-    client.set_token_balance(&delegator, &1000);
-    client.set_token_balance(&delegate, &500);
+    mint(&env, &client, &delegator, 1000);
+    mint(&env, &client, &delegate, 500);
 
     env.mock_all_auths();
     client.delegate_votes(&delegator, &delegate);
@@ -395,13 +448,51 @@ fn test_delegate_without_balance() {
     let delegator = Address::generate(&env);
     let delegate = Address::generate(&env);
 
-    client.set_token_balance(&delegate, &500);
+    mint(&env, &client, &delegate, 500);
 
     env.mock_all_auths();
-    client.delegate_votes(&delegator, &delegate);
+    assert_eq!(
+        client.try_delegate_votes(&delegator, &delegate),
+        Err(Ok(GovernanceError::NoVotingPower))
+    );
+    assert_eq!(client.get_delegate(&delegator), None);
+    assert_eq!(
+        client.get_voting_power(&delegate, &env.ledger().sequence()),
+        500
+    );
+}
+
+#[test]
+fn test_delegators_per_delegate_are_capped() {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    let (client, _admin) = setup_contract(&env);
+    let delegate = Address::generate(&env);
+    env.mock_all_auths();
+
+    let mut first = None;
+    for _ in 0..MAX_DELEGATORS {
+        let delegator = Address::generate(&env);
+        mint(&env, &client, &delegator, 1);
+        client.delegate_votes(&delegator, &delegate);
+        first.get_or_insert(delegator);
+    }
+    assert_eq!(
+        client.get_voting_power(&delegate, &env.ledger().sequence()),
+        MAX_DELEGATORS as i128
+    );
+
+    let extra = Address::generate(&env);
+    mint(&env, &client, &extra, 1);
+    assert_eq!(
+        client.try_delegate_votes(&extra, &delegate),
+        Err(Ok(GovernanceError::TooManyDelegators))
+    );
 
-    assert_eq!(client.get_voting_power(&delegate), 500);
-    assert_eq!(client.get_voting_power(&delegator), 0);
+    // A past delegator may delegate again
+    let first = first.unwrap();
+    client.undelegate_votes(&first);
+    client.delegate_votes(&first, &delegate);
 }
 
 #[test]
@@ -414,10 +505,10 @@ fn test_chain_delegation_depth_prevention() {
     let user_c = Address::generate(&env);
     let user_d = Address::generate(&env);
 
-    client.set_token_balance(&user_a, &100);
-    client.set_token_balance(&user_b, &100);
-    client.set_token_balance(&user_c, &100);
-    client.set_token_balance(&user_d, &100);
+    mint(&env, &client, &user_a, 100);
+    mint(&env, &client, &user_b, 100);
+    mint(&env, &client, &user_c, 100);
+    mint(&env, &client, &user_d, 100);
 
     env.mock_all_auths();
 
@@ -436,19 +527,99 @@ fn test_governance_flow() {
 
     assert_eq!(client.get_interest_rate(), 500);
     assert_eq!(client.get_collateral_ratio(), 15000);
-    assert_eq!(client.get_liquidation_bonus(), 500);
+    let asset = Address::generate(&env);
+    assert_eq!(client.get_liquidation_bonus(&asset), 500);
     assert_eq!(client.get_admin(), admin);
 
     env.mock_all_auths();
 
-    client.update_interest_rate(&600);
-    assert_eq!(client.get_interest_rate(), 600);
+    let rate_id = client.update_interest_rate(&600);
+    let ratio_id = client.update_collateral_ratio(&16000);
+    let bonus_id = client.update_liquidation_bonus(&700);
+    let quorum_id = client.set_quorum_threshold(&50);
+    // Admin updates wait in the timelock like passed proposals
+    assert_eq!(client.get_proposal_status(&rate_id), ProposalStatus::Queued);
+    assert_eq!(client.get_interest_rate(), 500);
+    assert_eq!(
+        client.try_execute_proposal(&admin, &rate_id),
+        Err(Ok(GovernanceError::TimelockNotReady))
+    );
 
-    client.update_collateral_ratio(&16000);
+    execute_queued(&env, &client, &admin, rate_id);
+    assert_eq!(client.get_interest_rate(), 600);
+    execute_queued(&env, &client, &admin, ratio_id);
     assert_eq!(client.get_collateral_ratio(), 16000);
+    execute_queued(&env, &client, &admin, bonus_id);
+    assert_eq!(client.get_liquidation_bonus(&asset), 700);
+    execute_queued(&env, &client, &admin, quorum_id);
+    assert_eq!(client.get_quorum_threshold(), 50);
+}
 
-    client.update_liquidation_bonus(&700);
-    assert_eq!(client.get_liquidation_bonus(), 700);
+/// Execute a queued proposal once its timelock delay has passed.
+fn execute_queued(env: &Env, client: &GovernanceContractClient, admin: &Address, id: u32) {
+    let eta = client.get_proposal(&id).unwrap().eta;
+    if env.ledger().timestamp() < eta {
+        env.ledger().with_mut(|li| li.timestamp = eta);
+    }
+    client.execute_proposal(admin, &id);
+}
+
+#[test]
+fn test_liquidation_parameters_per_asset() {
+    let env = Env::default();
+    let (client, admin) = setup_contract(&env);
+    env.mock_all_auths();
+
+    let volatile = Address::generate(&env);
+    let stable = Address::generate(&env);
+    let bonus_id = client.update_asset_liquidation_bonus(&volatile, &1000);
+    execute_queued(&env, &client, &admin, bonus_id);
+    assert_eq!(client.get_liquidation_bonus(&volatile), 1000);
+    assert_eq!(client.get_liquidation_bonus(&stable), 500);
+
+    assert_eq!(client.get_close_factor(), 5000);
+    let close_factor_id = client.update_close_factor(&2500);
+    execute_queued(&env, &client, &admin, close_factor_id);
+    assert_eq!(client.get_close_factor(), 2500);
+
+    assert_eq!(
+        client.try_update_close_factor(&0),
+        Err(Ok(GovernanceError::InvalidParameter))
+    );
+    assert_eq!(
+        client.try_update_close_factor(&10001),
+        Err(Ok(GovernanceError::InvalidParameter))
+    );
+    assert_eq!(
+        client.try_update_asset_liquidation_bonus(&volatile, &10001),
+        Err(Ok(GovernanceError::InvalidParameter))
+    );
+}
+
+#[test]
+fn test_close_factor_set_by_proposal() {
+    let env = Env::default();
+    let (client, admin) = setup_contract(&env);
+    let voter = Address::generate(&env);
+    let asset = Address::generate(&env);
+    mint(&env, &client, &voter, 1000);
+    env.mock_all_auths();
+
+    let close_factor_id = client.propose_update_close_factor(&admin, &4000);
+    let bonus_id = client.propose_asset_liquidation_bonus(&admin, &asset, &800);
+    client.vote(&voter, &close_factor_id, &VoteChoice::Yes);
+    client.vote(&voter, &bonus_id, &VoteChoice::Yes);
+    env.ledger().with_mut(|li| li.timestamp = 604_801);
+
+    client.queue_proposal(&close_factor_id);
+    client.queue_proposal(&bonus_id);
+    env.ledger()
+        .with_mut(|li| li.timestamp = 604_801 + client.get_timelock_delay());
+
+    client.execute_proposal(&admin, &close_factor_id);
+    client.execute_proposal(&admin, &bonus_id);
+    assert_eq!(client.get_close_factor(), 4000);
+    assert_eq!(client.get_liquidation_bonus(&asset), 800);
 }
 
 #[test]
@@ -473,6 +644,9 @@ fn test_create_proposal() {
         &admin,
         &String::from_str(&env, "Adjust Interest Rate"),
         &String::from_str(&env, "Proposal to change the base interest rate to 800 bps"),
+        &client.address,
+        &Symbol::new(&env, "update_interest_rate"),
+        &Vec::new(&env),
     );
 
     assert_eq!(proposal_id, 1u32);
@@ -512,9 +686,9 @@ fn test_vote_yes_no_abstain() {
     let voter_no = Address::generate(&env);
     let voter_abstain = Address::generate(&env);
 
-    client.set_token_balance(&voter_yes, &1000);
-    client.set_token_balance(&voter_no, &500);
-    client.set_token_balance(&voter_abstain, &250);
+    mint(&env, &client, &voter_yes, 1000);
+    mint(&env, &client, &voter_no, 500);
+    mint(&env, &client, &voter_abstain, 250);
 
     env.mock_all_auths();
     let proposal_id = make_proposal(&env, &client, &admin);
@@ -535,7 +709,7 @@ fn test_get_user_vote() {
     let (client, admin) = setup_contract(&env);
 
     let voter = Address::generate(&env);
-    client.set_token_balance(&voter, &1000);
+    mint(&env, &client, &voter, 1000);
 
     env.mock_all_auths();
     let proposal_id = make_proposal(&env, &client, &admin);
@@ -570,7 +744,7 @@ fn test_execute_proposal_after_voting_period() {
     let (client, admin) = setup_contract(&env);
 
     let voter = Address::generate(&env);
-    client.set_token_balance(&voter, &1000);
+    mint(&env, &client, &voter, 1000);
 
     env.mock_all_auths();
     let proposal_id = make_proposal(&env, &client, &admin);
@@ -584,10 +758,200 @@ fn test_execute_proposal_after_voting_period() {
     let status = client.get_proposal_status(&proposal_id);
     assert_eq!(status, ProposalStatus::Passed);
 
+    let eta = client.queue_proposal(&proposal_id);
+    assert_eq!(
+        client.get_proposal_status(&proposal_id),
+        ProposalStatus::Queued
+    );
+    env.ledger().with_mut(|li| {
+        li.timestamp = eta;
+    });
+
     client.execute_proposal(&admin, &proposal_id);
 
     let proposal = client.get_proposal(&proposal_id).unwrap();
     assert_eq!(proposal.status, ProposalStatus::Executed);
+    assert_eq!(client.get_interest_rate(), 800);
+}
+
+/// Vote a proposal through and advance past the voting period.
+fn pass_proposal(env: &Env, client: &GovernanceContractClient, admin: &Address) -> u32 {
+    let voter = Address::generate(env);
+    mint(env, client, &voter, 1000);
+    let proposal_id = make_proposal(env, client, admin);
+    client.vote(&voter, &proposal_id, &VoteChoice::Yes);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 604_801;
+    });
+    proposal_id
+}
+
+#[test]
+fn test_execute_proposal_requires_queue_and_delay() {
+    let env = Env::default();
+    let (client, admin) = setup_contract(&env);
+    env.mock_all_auths();
+    let proposal_id = pass_proposal(&env, &client, &admin);
+
+    assert_eq!(
+        client.try_execute_proposal(&admin, &proposal_id),
+        Err(Ok(GovernanceError::ProposalNotQueued))
+    );
+
+    let eta = client.queue_proposal(&proposal_id);
+    assert_eq!(eta, 604_801 + client.get_timelock_delay());
+    assert_eq!(
+        client.try_execute_proposal(&admin, &proposal_id),
+        Err(Ok(GovernanceError::TimelockNotReady))
+    );
+    assert_eq!(client.get_interest_rate(), 500);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = eta;
+    });
+    client.execute_proposal(&admin, &proposal_id);
+    assert_eq!(client.get_interest_rate(), 800);
+    assert_eq!(
+        client.try_execute_proposal(&admin, &proposal_id),
+        Err(Ok(GovernanceError::ProposalAlreadyExecuted))
+    );
+}
+
+#[test]
+fn test_queued_proposal_goes_stale_after_grace_period() {
+    let env = Env::default();
+    let (client, admin) = setup_contract(&env);
+    env.mock_all_auths();
+    let proposal_id = pass_proposal(&env, &client, &admin);
+
+    let eta = client.queue_proposal(&proposal_id);
+    env.ledger().with_mut(|li| {
+        li.timestamp = eta + client.get_timelock_grace_period() + 1;
+    });
+    assert_eq!(
+        client.try_execute_proposal(&admin, &proposal_id),
+        Err(Ok(GovernanceError::TimelockExpired))
+    );
+}
+
+#[test]
+fn test_guardians_veto_queued_proposal() {
+    let env = Env::default();
+    let (client, admin) = setup_contract(&env);
+    env.mock_all_auths();
+    let proposal_id = pass_proposal(&env, &client, &admin);
+
+    let guardian1 = Address::generate(&env);
+    let guardian2 = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let guardians_id = client.set_guardians(
+        &admin,
+        &soroban_sdk::vec![&env, guardian1.clone(), guardian2.clone()],
+        &2,
+    );
+    assert_eq!(client.get_guardians(), None);
+    execute_queued(&env, &client, &admin, guardians_id);
+    assert_eq!(client.get_guardians().unwrap().threshold, 2);
+    assert_eq!(
+        client.try_set_guardians(&admin, &soroban_sdk::vec![&env, guardian1.clone()], &2),
+        Err(Ok(GovernanceError::InvalidParameter))
+    );
+
+    // Only queued proposals can be vetoed.
+    assert_eq!(
+        client.try_veto_proposal(&guardian1, &proposal_id),
+        Err(Ok(GovernanceError::ProposalNotQueued))
+    );
+    let eta = client.queue_proposal(&proposal_id);
+
+    assert_eq!(
+        client.try_veto_proposal(&outsider, &proposal_id),
+        Err(Ok(GovernanceError::Unauthorized))
+    );
+    assert!(!client.veto_proposal(&guardian1, &proposal_id));
+    // A guardian approving twice does not reach the threshold alone.
+    assert!(!client.veto_proposal(&guardian1, &proposal_id));
+    assert!(client.veto_proposal(&guardian2, &proposal_id));
+    assert_eq!(
+        client.get_proposal_status(&proposal_id),
+        ProposalStatus::Cancelled
+    );
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = eta;
+    });
+    assert_eq!(
+        client.try_execute_proposal(&admin, &proposal_id),
+        Err(Ok(GovernanceError::ProposalNotPassed))
+    );
+    assert_eq!(client.get_interest_rate(), 500);
+}
+
+#[test]
+fn test_admin_calls_go_through_timelock() {
+    let env = Env::default();
+    let (client, admin) = setup_contract(&env);
+    env.mock_all_auths();
+
+    let target = env.register_contract(None, GovernanceContract);
+    let target_client = GovernanceContractClient::new(&env, &target);
+    target_client.initialize(
+        &client.address,
+        &client.get_governance_token(),
+        &100,
+        &15000,
+        &500,
+    );
+
+    let args = soroban_sdk::vec![&env, client.address.into_val(&env)];
+    let func = Symbol::new(&env, "pause");
+    assert_eq!(
+        client.try_execute_on_contract(&admin, &target, &func, &args),
+        Err(Ok(GovernanceError::Unauthorized))
+    );
+
+    client.add_controlled_contract(&admin, &target);
+    let proposal_id = client.execute_on_contract(&admin, &target, &func, &args);
+    assert_eq!(
+        client.get_proposal_status(&proposal_id),
+        ProposalStatus::Queued
+    );
+    assert!(!target_client.is_paused());
+    assert_eq!(
+        client.try_execute_proposal(&admin, &proposal_id),
+        Err(Ok(GovernanceError::TimelockNotReady))
+    );
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = client.get_timelock_delay());
+    client.execute_proposal(&admin, &proposal_id);
+    assert!(target_client.is_paused());
+}
+
+#[test]
+fn test_timelock_delay_changes_only_through_proposal() {
+    let env = Env::default();
+    let (client, admin) = setup_contract(&env);
+    let voter = Address::generate(&env);
+    mint(&env, &client, &voter, 1000);
+    env.mock_all_auths();
+
+    let too_short = client.propose_update_timelock_delay(&admin, &60);
+    let proposal_id = client.propose_update_timelock_delay(&admin, &86_400);
+    client.vote(&voter, &too_short, &VoteChoice::Yes);
+    client.vote(&voter, &proposal_id, &VoteChoice::Yes);
+    env.ledger().with_mut(|li| li.timestamp = 604_801);
+
+    client.queue_proposal(&too_short);
+    let eta = client.queue_proposal(&proposal_id);
+    env.ledger().with_mut(|li| li.timestamp = eta);
+
+    assert_eq!(
+        client.try_execute_proposal(&admin, &too_short),
+        Err(Ok(GovernanceError::InvalidParameter))
+    );
+    client.execute_proposal(&admin, &proposal_id);
+    assert_eq!(client.get_timelock_delay(), 86_400);
 }
 
 #[test]
@@ -596,7 +960,7 @@ fn test_execute_rejected_proposal_fails() {
     let (client, admin) = setup_contract(&env);
 
     let voter = Address::generate(&env);
-    client.set_token_balance(&voter, &1000);
+    mint(&env, &client, &voter, 1000);
 
     env.mock_all_auths();
     let proposal_id = make_proposal(&env, &client, &admin);
@@ -634,7 +998,7 @@ fn test_cancel_proposal_by_admin() {
     let (client, admin) = setup_contract(&env);
 
     let proposer = Address::generate(&env);
-    client.set_token_balance(&proposer, &1000);
+    mint(&env, &client, &proposer, 1000);
 
     env.mock_all_auths();
     let proposal_id = make_proposal(&env, &client, &proposer);
@@ -666,7 +1030,7 @@ fn test_vote_on_cancelled_proposal_fails() {
     let (client, admin) = setup_contract(&env);
 
     let voter = Address::generate(&env);
-    client.set_token_balance(&voter, &1000);
+    mint(&env, &client, &voter, 1000);
 
     env.mock_all_auths();
     let proposal_id = make_proposal(&env, &client, &admin);
@@ -699,7 +1063,7 @@ fn test_vote_on_nonexistent_proposal_fails() {
     let (client, _admin) = setup_contract(&env);
 
     let voter = Address::generate(&env);
-    client.set_token_balance(&voter, &1000);
+    mint(&env, &client, &voter, 1000);
 
     env.mock_all_auths();
     let result = client.try_vote(&voter, &99u32, &VoteChoice::Yes);
@@ -712,7 +1076,7 @@ fn test_has_voted_reflects_state() {
     let (client, admin) = setup_contract(&env);
 
     let voter = Address::generate(&env);
-    client.set_token_balance(&voter, &500);
+    mint(&env, &client, &voter, 500);
 
     env.mock_all_auths();
     let proposal_id = make_proposal(&env, &client, &admin);
@@ -801,6 +1165,9 @@ fn test_pause_blocks_create_proposal() {
         &proposer,
         &soroban_sdk::String::from_str(&env, "Test"),
         &soroban_sdk::String::from_str(&env, "Desc"),
+        &client.address,
+        &Symbol::new(&env, "update_interest_rate"),
+        &Vec::new(&env),
     );
     assert!(result.is_err());
 }
@@ -813,11 +1180,14 @@ fn test_unpause_restores_proposal_creation() {
     client.pause(&admin);
     client.unpause(&admin);
     let proposer = Address::generate(&env);
-    client.set_token_balance(&proposer, &1000);
+    mint(&env, &client, &proposer, 1000);
     let result = client.try_create_proposal(
         &proposer,
         &soroban_sdk::String::from_str(&env, "Test"),
         &soroban_sdk::String::from_str(&env, "Desc"),
+        &client.address,
+        &Symbol::new(&env, "update_interest_rate"),
+        &Vec::new(&env),
     );
     assert!(result.is_ok());
 }
@@ -947,7 +1317,7 @@ fn test_get_delegator_history() {
     let delegate1 = Address::generate(&env);
     let delegate2 = Address::generate(&env);
 
-    client.set_token_balance(&delegator, &1000);
+    mint(&env, &client, &delegator, 1000);
 
     env.mock_all_auths();
 
@@ -987,9 +1357,9 @@ fn test_get_delegate_info() {
     let delegator2 = Address::generate(&env);
     let delegate = Address::generate(&env);
 
-    client.set_token_balance(&delegator1, &1000);
-    client.set_token_balance(&delegator2, &2000);
-    client.set_token_balance(&delegate, &500);
+    mint(&env, &client, &delegator1, 1000);
+    mint(&env, &client, &delegator2, 2000);
+    mint(&env, &client, &delegate, 500);
 
     env.mock_all_auths();
     client.delegate_votes(&delegator1, &delegate);
@@ -1011,7 +1381,7 @@ fn test_has_delegated() {
     let delegator = Address::generate(&env);
     let delegate = Address::generate(&env);
 
-    client.set_token_balance(&delegator, &1000);
+    mint(&env, &client, &delegator, 1000);
 
     // Initially not delegated
     assert!(!client.has_delegated(&delegator));
@@ -1038,9 +1408,9 @@ fn test_get_total_delegators_count() {
     let delegator3 = Address::generate(&env);
     let delegate = Address::generate(&env);
 
-    client.set_token_balance(&delegator1, &1000);
-    client.set_token_balance(&delegator2, &2000);
-    client.set_token_balance(&delegator3, &3000);
+    mint(&env, &client, &delegator1, 1000);
+    mint(&env, &client, &delegator2, 2000);
+    mint(&env, &client, &delegator3, 3000);
 
     env.mock_all_auths();
 
@@ -1069,8 +1439,8 @@ fn test_get_effective_voting_power() {
     let delegator = Address::generate(&env);
     let delegate = Address::generate(&env);
 
-    client.set_token_balance(&delegator, &1000);
-    client.set_token_balance(&delegate, &500);
+    mint(&env, &client, &delegator, 1000);
+    mint(&env, &client, &delegate, 500);
 
     // Before delegation, effective power equals balance
     assert_eq!(client.get_effective_voting_power(&delegator), 1000);
@@ -1101,29 +1471,44 @@ fn test_delegation_with_multiple_redelegations() {
     let delegate2 = Address::generate(&env);
     let delegate3 = Address::generate(&env);
 
-    client.set_token_balance(&delegator, &1000);
-    client.set_token_balance(&delegate1, &100);
-    client.set_token_balance(&delegate2, &200);
-    client.set_token_balance(&delegate3, &300);
+    mint(&env, &client, &delegator, 1000);
+    mint(&env, &client, &delegate1, 100);
+    mint(&env, &client, &delegate2, 200);
+    mint(&env, &client, &delegate3, 300);
 
     env.mock_all_auths();
 
     // First delegation
     client.delegate_votes(&delegator, &delegate1);
-    assert_eq!(client.get_voting_power(&delegate1), 1100);
+    assert_eq!(
+        client.get_voting_power(&delegate1, &env.ledger().sequence()),
+        1100
+    );
     assert_eq!(client.get_delegators(&delegate1).len(), 1);
 
     // Redelegate to delegate2
     client.delegate_votes(&delegator, &delegate2);
-    assert_eq!(client.get_voting_power(&delegate1), 100); // Lost delegated power
-    assert_eq!(client.get_voting_power(&delegate2), 1200); // Gained delegated power
+    assert_eq!(
+        client.get_voting_power(&delegate1, &env.ledger().sequence()),
+        100
+    ); // Lost delegated power
+    assert_eq!(
+        client.get_voting_power(&delegate2, &env.ledger().sequence()),
+        1200
+    ); // Gained delegated power
     assert_eq!(client.get_delegators(&delegate1).len(), 0);
     assert_eq!(client.get_delegators(&delegate2).len(), 1);
 
     // Redelegate to delegate3
     client.delegate_votes(&delegator, &delegate3);
-    assert_eq!(client.get_voting_power(&delegate2), 200); // Lost delegated power
-    assert_eq!(client.get_voting_power(&delegate3), 1300); // Gained delegated power
+    assert_eq!(
+        client.get_voting_power(&delegate2, &env.ledger().sequence()),
+        200
+    ); // Lost delegated power
+    assert_eq!(
+        client.get_voting_power(&delegate3, &env.ledger().sequence()),
+        1300
+    ); // Gained delegated power
     assert_eq!(client.get_delegators(&delegate2).len(), 0);
     assert_eq!(client.get_delegators(&delegate3).len(), 1);
 
@@ -1150,9 +1535,9 @@ fn test_delegation_voting_power_consistency() {
     let delegator2 = Address::generate(&env);
     let delegate = Address::generate(&env);
 
-    client.set_token_balance(&delegator1, &1000);
-    client.set_token_balance(&delegator2, &2000);
-    client.set_token_balance(&delegate, &500);
+    mint(&env, &client, &delegator1, 1000);
+    mint(&env, &client, &delegator2, 2000);
+    mint(&env, &client, &delegate, 500);
 
     env.mock_all_auths();
 
@@ -1160,26 +1545,35 @@ fn test_delegation_voting_power_consistency() {
     let total_tokens = 1000 + 2000 + 500;
 
     // Before delegation
-    let total_power_before = client.get_voting_power(&delegator1)
-        + client.get_voting_power(&delegator2)
-        + client.get_voting_power(&delegate);
+    let total_power_before = client.get_voting_power(&delegator1, &env.ledger().sequence())
+        + client.get_voting_power(&delegator2, &env.ledger().sequence())
+        + client.get_voting_power(&delegate, &env.ledger().sequence());
     assert_eq!(total_power_before, total_tokens);
 
     // After delegation
     client.delegate_votes(&delegator1, &delegate);
     client.delegate_votes(&delegator2, &delegate);
 
-    let total_power_after = client.get_voting_power(&delegator1)
-        + client.get_voting_power(&delegator2)
-        + client.get_voting_power(&delegate);
+    let total_power_after = client.get_voting_power(&delegator1, &env.ledger().sequence())
+        + client.get_voting_power(&delegator2, &env.ledger().sequence())
+        + client.get_voting_power(&delegate, &env.ledger().sequence());
 
     // Total voting power should remain the same
     assert_eq!(total_power_after, total_tokens);
 
     // All power should be with the delegate
-    assert_eq!(client.get_voting_power(&delegate), total_tokens);
-    assert_eq!(client.get_voting_power(&delegator1), 0);
-    assert_eq!(client.get_voting_power(&delegator2), 0);
+    assert_eq!(
+        client.get_voting_power(&delegate, &env.ledger().sequence()),
+        total_tokens
+    );
+    assert_eq!(
+        client.get_voting_power(&delegator1, &env.ledger().sequence()),
+        0
+    );
+    assert_eq!(
+        client.get_voting_power(&delegator2, &env.ledger().sequence()),
+        0
+    );
 }
 
 #[test]
@@ -1192,10 +1586,10 @@ fn test_complex_delegation_chain_prevention() {
     let user_c = Address::generate(&env);
     let user_d = Address::generate(&env);
 
-    client.set_token_balance(&user_a, &1000);
-    client.set_token_balance(&user_b, &1000);
-    client.set_token_balance(&user_c, &1000);
-    client.set_token_balance(&user_d, &1000);
+    mint(&env, &client, &user_a, 1000);
+    mint(&env, &client, &user_b, 1000);
+    mint(&env, &client, &user_c, 1000);
+    mint(&env, &client, &user_d, 1000);
 
     env.mock_all_auths();
 
@@ -1216,3 +1610,97 @@ fn test_complex_delegation_chain_prevention() {
     let result = client.try_delegate_votes(&user_d, &user_c);
     assert!(result.is_err());
 }
+
+// ─────────────────────────────────────────────────
+// Checkpointed Voting Power Tests
+// ─────────────────────────────────────────────────
+
+fn token_client<'a>(env: &'a Env, client: &GovernanceContractClient) -> GovernanceTokenClient<'a> {
+    GovernanceTokenClient::new(env, &client.get_governance_token())
+}
+
+#[test]
+fn test_voting_power_at_past_ledgers() {
+    let env = Env::default();
+    let (client, _admin) = setup_contract(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+    mint(&env, &client, &alice, 1000);
+    env.mock_all_auths();
+
+    env.ledger().with_mut(|li| li.sequence_number = 20);
+    client.delegate_votes(&alice, &bob);
+    env.ledger().with_mut(|li| li.sequence_number = 30);
+    token_client(&env, &client).transfer(&alice, &bob, &400);
+    env.ledger().with_mut(|li| li.sequence_number = 40);
+    client.undelegate_votes(&alice);
+
+    assert_eq!(client.get_voting_power(&alice, &9), 0);
+    assert_eq!(client.get_voting_power(&alice, &15), 1000);
+    assert_eq!(client.get_voting_power(&bob, &15), 0);
+    // Delegation moves alice's checkpointed balance to bob...
+    assert_eq!(client.get_voting_power(&alice, &25), 0);
+    assert_eq!(client.get_voting_power(&bob, &25), 1000);
+    // ...transfers keep counting once, whichever side they land on...
+    assert_eq!(client.get_voting_power(&bob, &35), 1000);
+    // ...and undelegating moves it back.
+    assert_eq!(client.get_voting_power(&alice, &45), 600);
+    assert_eq!(client.get_voting_power(&bob, &45), 400);
+}
+
+#[test]
+fn test_tokens_moved_after_snapshot_cannot_vote_twice() {
+    let env = Env::default();
+    let (client, admin) = setup_contract(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+    mint(&env, &client, &alice, 1000);
+    env.mock_all_auths();
+
+    env.ledger().with_mut(|li| li.sequence_number = 11);
+    let proposal_id = make_proposal(&env, &client, &admin);
+    client.vote(&alice, &proposal_id, &VoteChoice::Yes);
+
+    // Vote, then pass the tokens on to vote again.
+    token_client(&env, &client).transfer(&alice, &bob, &1000);
+    assert_eq!(
+        client.try_vote(&bob, &proposal_id, &VoteChoice::Yes),
+        Err(Ok(GovernanceError::ZeroAmount))
+    );
+
+    // Delegating after the snapshot does not carry power either.
+    env.ledger().with_mut(|li| li.sequence_number = 12);
+    client.delegate_votes(&bob, &carol);
+    assert_eq!(
+        client.try_vote(&carol, &proposal_id, &VoteChoice::Yes),
+        Err(Ok(GovernanceError::ZeroAmount))
+    );
+
+    assert_eq!(client.get_vote_count(&proposal_id).yes_votes, 1000);
+}
+
+#[test]
+fn test_balance_minted_in_creation_ledger_is_not_counted() {
+    let env = Env::default();
+    let (client, admin) = setup_contract(&env);
+    let voter = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+    env.mock_all_auths();
+    let proposal_id = make_proposal(&env, &client, &admin);
+    mint(&env, &client, &voter, 1000);
+
+    assert_eq!(
+        client.get_proposal(&proposal_id).unwrap().snapshot_ledger,
+        9
+    );
+    assert_eq!(
+        client.try_vote(&voter, &proposal_id, &VoteChoice::Yes),
+        Err(Ok(GovernanceError::ZeroAmount))
+    );
+}
@@ -0,0 +1,249 @@
+#![no_std]
+
+//! Governance token with balance checkpoints.
+//!
+//! Every change to an account's balance, and to the total supply, is
+//! recorded against the ledger sequence it happened in, so what an account
+//! held at any past ledger can be read back with `balance_at`. The
+//! governance contract takes voting power from these checkpoints at each
+//! proposal's snapshot ledger, so tokens moved after a proposal was created
+//! cannot be voted a second time.
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Vec,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TokenError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NegativeAmount = 3,
+    InsufficientBalance = 4,
+    Overflow = 5,
+}
+
+/// A value as it stood at the end of `ledger`, until the next checkpoint.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub value: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GovernanceTokenDataKey {
+    Admin,
+    Balance(Address),
+    TotalSupply,
+}
+
+#[contract]
+pub struct GovernanceToken;
+
+#[contractimpl]
+impl GovernanceToken {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), TokenError> {
+        if env.storage().instance().has(&GovernanceTokenDataKey::Admin) {
+            return Err(TokenError::AlreadyInitialized);
+        }
+        env.storage()
+            .instance()
+            .set(&GovernanceTokenDataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn admin(env: Env) -> Result<Address, TokenError> {
+        env.storage()
+            .instance()
+            .get(&GovernanceTokenDataKey::Admin)
+            .ok_or(TokenError::NotInitialized)
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        latest(&env, &GovernanceTokenDataKey::Balance(id))
+    }
+
+    pub fn total_supply(env: Env) -> i128 {
+        latest(&env, &GovernanceTokenDataKey::TotalSupply)
+    }
+
+    /// Balance `id` held at the end of `ledger`. For the current ledger this
+    /// is the balance so far.
+    pub fn balance_at(env: Env, id: Address, ledger: u32) -> i128 {
+        value_at(&env, &GovernanceTokenDataKey::Balance(id), ledger)
+    }
+
+    pub fn total_supply_at(env: Env, ledger: u32) -> i128 {
+        value_at(&env, &GovernanceTokenDataKey::TotalSupply, ledger)
+    }
+
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), TokenError> {
+        let admin = Self::admin(env.clone())?;
+        admin.require_auth();
+        if amount < 0 {
+            return Err(TokenError::NegativeAmount);
+        }
+
+        let supply = latest(&env, &GovernanceTokenDataKey::TotalSupply)
+            .checked_add(amount)
+            .ok_or(TokenError::Overflow)?;
+        let balance_key = GovernanceTokenDataKey::Balance(to.clone());
+        let balance = latest(&env, &balance_key)
+            .checked_add(amount)
+            .ok_or(TokenError::Overflow)?;
+        checkpoint(&env, &GovernanceTokenDataKey::TotalSupply, supply);
+        checkpoint(&env, &balance_key, balance);
+
+        env.events()
+            .publish((symbol_short!("mint"), admin, to), amount);
+        Ok(())
+    }
+
+    pub fn burn(env: Env, from: Address, amount: i128) -> Result<(), TokenError> {
+        from.require_auth();
+        if amount < 0 {
+            return Err(TokenError::NegativeAmount);
+        }
+
+        let balance_key = GovernanceTokenDataKey::Balance(from.clone());
+        let balance = latest(&env, &balance_key);
+        if balance < amount {
+            return Err(TokenError::InsufficientBalance);
+        }
+        let supply = latest(&env, &GovernanceTokenDataKey::TotalSupply);
+        checkpoint(&env, &balance_key, balance - amount);
+        checkpoint(&env, &GovernanceTokenDataKey::TotalSupply, supply - amount);
+
+        env.events().publish((symbol_short!("burn"), from), amount);
+        Ok(())
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), TokenError> {
+        from.require_auth();
+        if amount < 0 {
+            return Err(TokenError::NegativeAmount);
+        }
+
+        let from_key = GovernanceTokenDataKey::Balance(from.clone());
+        let from_balance = latest(&env, &from_key);
+        if from_balance < amount {
+            return Err(TokenError::InsufficientBalance);
+        }
+        checkpoint(&env, &from_key, from_balance - amount);
+
+        let to_key = GovernanceTokenDataKey::Balance(to.clone());
+        let to_balance = latest(&env, &to_key)
+            .checked_add(amount)
+            .ok_or(TokenError::Overflow)?;
+        checkpoint(&env, &to_key, to_balance);
+
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), amount);
+        Ok(())
+    }
+}
+
+fn checkpoints(env: &Env, key: &GovernanceTokenDataKey) -> Vec<Checkpoint> {
+    env.storage()
+        .persistent()
+        .get(key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn latest(env: &Env, key: &GovernanceTokenDataKey) -> i128 {
+    checkpoints(env, key).last().map(|c| c.value).unwrap_or(0)
+}
+
+/// Value of the last checkpoint at or before `ledger`.
+fn value_at(env: &Env, key: &GovernanceTokenDataKey, ledger: u32) -> i128 {
+    let history = checkpoints(env, key);
+    let (mut low, mut high) = (0, history.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        if history.get_unchecked(mid).ledger <= ledger {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    if low == 0 {
+        0
+    } else {
+        history.get_unchecked(low - 1).value
+    }
+}
+
+/// Record `value` for the current ledger, replacing an earlier checkpoint
+/// from the same ledger.
+fn checkpoint(env: &Env, key: &GovernanceTokenDataKey, value: i128) {
+    let mut history = checkpoints(env, key);
+    let ledger = env.ledger().sequence();
+    let entry = Checkpoint { ledger, value };
+    match history.last() {
+        Some(last) if last.ledger == ledger => history.set(history.len() - 1, entry),
+        _ => history.push_back(entry),
+    }
+    env.storage().persistent().set(key, &history);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+
+    fn setup(env: &Env) -> GovernanceTokenClient<'_> {
+        let contract_id = env.register_contract(None, GovernanceToken);
+        let client = GovernanceTokenClient::new(env, &contract_id);
+        client.initialize(&Address::generate(env));
+        client
+    }
+
+    #[test]
+    fn balances_are_readable_at_past_ledgers() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = setup(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        env.ledger().with_mut(|li| li.sequence_number = 10);
+        client.mint(&alice, &1000);
+        env.ledger().with_mut(|li| li.sequence_number = 20);
+        client.transfer(&alice, &bob, &400);
+        // A second change in the same ledger replaces its checkpoint.
+        client.transfer(&alice, &bob, &100);
+        env.ledger().with_mut(|li| li.sequence_number = 30);
+        client.burn(&bob, &200);
+
+        assert_eq!(client.balance_at(&alice, &9), 0);
+        assert_eq!(client.balance_at(&alice, &10), 1000);
+        assert_eq!(client.balance_at(&alice, &19), 1000);
+        assert_eq!(client.balance_at(&alice, &20), 500);
+        assert_eq!(client.balance_at(&bob, &25), 500);
+        assert_eq!(client.balance_at(&bob, &30), 300);
+        assert_eq!(client.balance(&bob), 300);
+        assert_eq!(client.total_supply_at(&29), 1000);
+        assert_eq!(client.total_supply(), 800);
+    }
+
+    #[test]
+    fn transfer_beyond_balance_fails() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = setup(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        client.mint(&alice, &100);
+        assert_eq!(
+            client.try_transfer(&alice, &bob, &101),
+            Err(Ok(TokenError::InsufficientBalance))
+        );
+        assert_eq!(
+            client.try_mint(&alice, &-1),
+            Err(Ok(TokenError::NegativeAmount))
+        );
+    }
+}
@@ -0,0 +1,137 @@
+//! Multi-asset plan baskets.
+//!
+//! A plan's primary asset (the token it was created with) is still tracked on
+//! `InheritancePlan::total_amount`. Any other admin-whitelisted Stellar Asset
+//! Contract token deposited into the plan is held in a per-plan basket of
+//! [`PlanAsset`] entries, each carrying its own balance and, optionally, its
+//! own per-beneficiary split.
+//!
+//! On claim, basket assets are transferred to the claimer's wallet, while the
+//! primary payout is still settled in fiat off-chain from the claim event.
+
+use soroban_sdk::{contracttype, Address, Symbol, Vec};
+
+/// Maximum number of secondary assets a single plan can hold. Bounds the
+/// per-claim transfer loop.
+pub const MAX_PLAN_ASSETS: u32 = 5;
+
+/// Maximum number of tokens on the admin whitelist. Bounds the symbol lookup
+/// performed when validating a plan's primary asset.
+pub const MAX_WHITELISTED_ASSETS: u32 = 20;
+
+/// A secondary asset held by a plan.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanAsset {
+    /// Token contract address (must be whitelisted when first deposited).
+    pub token: Address,
+    /// Ticker registered for the token at whitelisting time, e.g. `XLM`.
+    pub symbol: Symbol,
+    /// Amount currently escrowed by the contract for this plan.
+    pub balance: u64,
+    /// Per-beneficiary allocation in basis points, indexed like
+    /// `InheritancePlan::beneficiaries`. Empty means "use each beneficiary's
+    /// default `allocation_bp`".
+    pub allocations: Vec<u32>,
+}
+
+impl PlanAsset {
+    /// Allocation (in basis points) of this asset for the beneficiary at
+    /// `index`, falling back to the beneficiary's default allocation.
+    pub fn allocation_for(&self, index: u32, default_bp: u32) -> u32 {
+        if self.allocations.is_empty() {
+            return default_bp;
+        }
+        self.allocations.get(index).unwrap_or(0)
+    }
+}
+
+/// Share of `balance` owed to a beneficiary holding `allocation_bp`, given
+/// that beneficiaries holding `claimed_bp` have already been paid out of the
+/// original balance. Dividing by the still-unclaimed basis points keeps every
+/// beneficiary's payout proportional to the balance at the time of the first
+/// claim, regardless of claim order.
+pub fn share_of_remaining(balance: u64, allocation_bp: u32, claimed_bp: u32) -> u64 {
+    let outstanding_bp = 10000u32.saturating_sub(claimed_bp);
+    if outstanding_bp == 0 || allocation_bp == 0 {
+        return 0;
+    }
+    let share = (balance as u128)
+        .checked_mul(allocation_bp as u128)
+        .and_then(|v| v.checked_div(outstanding_bp as u128))
+        .unwrap_or(0) as u64;
+    share.min(balance)
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetWhitelistedEvent {
+    pub token: Address,
+    pub symbol: Symbol,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetDelistedEvent {
+    pub token: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetDepositEvent {
+    pub plan_id: u64,
+    pub token: Address,
+    pub amount: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetWithdrawEvent {
+    pub plan_id: u64,
+    pub token: Address,
+    pub amount: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetClaimedEvent {
+    pub plan_id: u64,
+    pub beneficiary_index: u32,
+    pub token: Address,
+    pub amount: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetAllocationsSetEvent {
+    pub plan_id: u64,
+    pub token: Address,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn share_is_proportional_to_original_balance_regardless_of_order() {
+        // 1000 split 60/40: first claimant takes 600 of 1000, second 400 of
+        // the remaining 400.
+        assert_eq!(share_of_remaining(1000, 6000, 0), 600);
+        assert_eq!(share_of_remaining(400, 4000, 6000), 400);
+        // Reverse order yields the same amounts.
+        assert_eq!(share_of_remaining(1000, 4000, 0), 400);
+        assert_eq!(share_of_remaining(600, 6000, 4000), 600);
+    }
+
+    #[test]
+    fn unallocated_basis_points_stay_in_the_basket() {
+        // Only 50% allocated: the sole beneficiary receives half.
+        assert_eq!(share_of_remaining(1000, 5000, 0), 500);
+    }
+
+    #[test]
+    fn fully_claimed_or_zero_allocation_yields_nothing() {
+        assert_eq!(share_of_remaining(1000, 5000, 10000), 0);
+        assert_eq!(share_of_remaining(1000, 0, 0), 0);
+    }
+}
@@ -0,0 +1,229 @@
+//! Death-certificate attestation.
+//!
+//! Admin-registered attestors (registrars, notaries, hospitals) attest to an
+//! owner's death by submitting the SHA-256 hash of the certificate.
+//! Attestations are counted per certificate hash, so an early attestation of
+//! a wrong document cannot block the others. Once `threshold` distinct
+//! attestors have attested to the same document, a challenge window of at
+//! least [`MIN_CHALLENGE_WINDOW`] opens during which the owner or one of the plan's
+//! emergency contacts can veto the round. When the window closes unvetoed,
+//! the plan's `Oracle` trigger condition is met.
+
+use soroban_sdk::{contracttype, Address, BytesN, Vec};
+
+/// Maximum number of registered attestors.
+pub const MAX_ATTESTORS: u32 = 20;
+
+/// Shortest challenge window an owner may configure: one day.
+pub const MIN_CHALLENGE_WINDOW: u64 = 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AttestorKind {
+    Registrar,
+    Notary,
+    Hospital,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttestorRecord {
+    pub attestor: Address,
+    pub kind: AttestorKind,
+    pub registered_at: u64,
+}
+
+/// Per-plan M-of-N attestation settings chosen by the owner.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeathAttestationConfig {
+    pub threshold: u32,
+    /// Seconds between reaching the threshold and finalization.
+    pub challenge_window: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    pub attestor: Address,
+    pub kind: AttestorKind,
+    pub document_hash: BytesN<32>,
+    pub attested_at: u64,
+}
+
+/// An in-progress attestation round for a plan.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeathAttestationRound {
+    /// The certificate hash that reached the threshold. While collecting it
+    /// is the most recently attested hash; each attestation carries its own.
+    pub document_hash: BytesN<32>,
+    pub attestations: Vec<Attestation>,
+    /// When the threshold was reached, or 0 while still collecting.
+    pub threshold_reached_at: u64,
+    /// End of the challenge window, or 0 while still collecting.
+    pub challenge_ends_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeathAttestationStatus {
+    Collecting,
+    Challenge,
+    Finalized,
+}
+
+/// Read-only view of a plan's attestation round.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeathAttestationView {
+    pub plan_id: u64,
+    pub threshold: u32,
+    pub round: DeathAttestationRound,
+    pub status: DeathAttestationStatus,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeathAttestedEvent {
+    pub plan_id: u64,
+    pub attestor: Address,
+    pub document_hash: BytesN<32>,
+    pub attestations: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChallengeWindowOpenedEvent {
+    pub plan_id: u64,
+    pub document_hash: BytesN<32>,
+    pub challenge_ends_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeathAttestationVetoedEvent {
+    pub plan_id: u64,
+    pub vetoed_by: Address,
+    pub document_hash: BytesN<32>,
+}
+
+impl DeathAttestationRound {
+    pub fn status(&self, now: u64) -> DeathAttestationStatus {
+        if self.threshold_reached_at == 0 {
+            DeathAttestationStatus::Collecting
+        } else if now < self.challenge_ends_at {
+            DeathAttestationStatus::Challenge
+        } else {
+            DeathAttestationStatus::Finalized
+        }
+    }
+
+    pub fn has_attested(&self, attestor: &Address) -> bool {
+        self.attestations.iter().any(|a| a.attestor == *attestor)
+    }
+
+    /// Number of attestations of `document_hash`.
+    pub fn attestations_for(&self, document_hash: &BytesN<32>) -> u32 {
+        self.attestations
+            .iter()
+            .filter(|a| a.document_hash == *document_hash)
+            .count() as u32
+    }
+
+    /// Record an attestation; opens the challenge window when its document
+    /// reaches `threshold`. Returns `true` if this attestation reached it.
+    pub fn record(
+        &mut self,
+        attestation: Attestation,
+        threshold: u32,
+        challenge_window: u64,
+    ) -> bool {
+        let now = attestation.attested_at;
+        let document_hash = attestation.document_hash.clone();
+        self.attestations.push_back(attestation);
+        if self.threshold_reached_at == 0 {
+            self.document_hash = document_hash.clone();
+        }
+        if self.threshold_reached_at == 0 && self.attestations_for(&document_hash) >= threshold {
+            self.threshold_reached_at = now;
+            self.challenge_ends_at = now.saturating_add(challenge_window);
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{testutils::Address as _, Env};
+
+    fn round(env: &Env) -> DeathAttestationRound {
+        DeathAttestationRound {
+            document_hash: BytesN::from_array(env, &[0; 32]),
+            attestations: Vec::new(env),
+            threshold_reached_at: 0,
+            challenge_ends_at: 0,
+        }
+    }
+
+    fn attestation(env: &Env, at: u64) -> Attestation {
+        attestation_of(env, 7, at)
+    }
+
+    fn attestation_of(env: &Env, document: u8, at: u64) -> Attestation {
+        Attestation {
+            attestor: Address::generate(env),
+            kind: AttestorKind::Registrar,
+            document_hash: BytesN::from_array(env, &[document; 32]),
+            attested_at: at,
+        }
+    }
+
+    #[test]
+    fn threshold_opens_challenge_window() {
+        let env = Env::default();
+        let mut r = round(&env);
+        assert!(!r.record(attestation(&env, 100), 2, 50));
+        assert_eq!(r.status(120), DeathAttestationStatus::Collecting);
+
+        assert!(r.record(attestation(&env, 130), 2, 50));
+        assert_eq!(r.document_hash, BytesN::from_array(&env, &[7; 32]));
+        assert_eq!(r.challenge_ends_at, 180);
+        assert_eq!(r.status(179), DeathAttestationStatus::Challenge);
+        assert_eq!(r.status(180), DeathAttestationStatus::Finalized);
+
+        // Further attestations do not move the window.
+        assert!(!r.record(attestation(&env, 150), 2, 50));
+        assert_eq!(r.challenge_ends_at, 180);
+    }
+
+    #[test]
+    fn counts_attestations_per_document() {
+        let env = Env::default();
+        let mut r = round(&env);
+        assert!(!r.record(attestation_of(&env, 1, 100), 2, 50));
+        assert!(!r.record(attestation_of(&env, 2, 110), 2, 50));
+        assert_eq!(r.status(120), DeathAttestationStatus::Collecting);
+
+        assert!(r.record(attestation_of(&env, 2, 120), 2, 50));
+        assert_eq!(r.document_hash, BytesN::from_array(&env, &[2; 32]));
+        assert_eq!(r.attestations_for(&BytesN::from_array(&env, &[1; 32])), 1);
+
+        // The reached document stays fixed.
+        assert!(!r.record(attestation_of(&env, 1, 130), 2, 50));
+        assert_eq!(r.document_hash, BytesN::from_array(&env, &[2; 32]));
+    }
+
+    #[test]
+    fn tracks_who_attested() {
+        let env = Env::default();
+        let mut r = round(&env);
+        let a = attestation(&env, 1);
+        let who = a.attestor.clone();
+        r.record(a, 3, 10);
+        assert!(r.has_attested(&who));
+        assert!(!r.has_attested(&Address::generate(&env)));
+    }
+}
@@ -0,0 +1,82 @@
+//! Post-trigger contest window.
+//!
+//! When an owner configures a contest period, triggering a plan opens a
+//! window before anything is released: claims are blocked and cross-chain
+//! releases are held back, the owner can prove they are alive by cancelling
+//! the trigger with a signed call, and emergency contacts can file a dispute
+//! that freezes the plan. Once the window has elapsed anyone may close it,
+//! which releases the plan.
+
+use soroban_sdk::{contracttype, Address};
+
+/// Longest contest period an owner may configure (90 days).
+pub const MAX_CONTEST_PERIOD: u64 = 90 * 24 * 60 * 60;
+
+/// An open contest window, stored from trigger until it is closed or the
+/// trigger is cancelled.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContestWindow {
+    pub started_at: u64,
+    pub ends_at: u64,
+    /// `is_lendable` before the trigger froze lending, restored on cancel.
+    pub was_lendable: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContestWindowStartedEvent {
+    pub plan_id: u64,
+    pub started_at: u64,
+    pub ends_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContestWindowEndedEvent {
+    pub plan_id: u64,
+    pub ended_at: u64,
+    /// `true` if the owner cancelled the trigger, `false` if the window
+    /// elapsed and the plan was released.
+    pub cancelled: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TriggerCancelledEvent {
+    pub plan_id: u64,
+    pub owner: Address,
+    pub cancelled_at: u64,
+}
+
+impl ContestWindow {
+    pub fn is_open(&self, now: u64) -> bool {
+        now < self.ends_at
+    }
+
+    /// Seconds left in the window, or 0 once it has elapsed.
+    pub fn remaining(&self, now: u64) -> u64 {
+        self.ends_at.saturating_sub(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_counts_down_and_closes() {
+        let window = ContestWindow {
+            started_at: 1_000,
+            ends_at: 1_600,
+            was_lendable: true,
+        };
+        assert!(window.is_open(1_000));
+        assert_eq!(window.remaining(1_000), 600);
+        assert!(window.is_open(1_599));
+        assert_eq!(window.remaining(1_599), 1);
+        assert!(!window.is_open(1_600));
+        assert_eq!(window.remaining(1_600), 0);
+        assert_eq!(window.remaining(5_000), 0);
+    }
+}
@@ -5,8 +5,14 @@
 //! self-contained validation — they intentionally do not touch contract storage
 //! so they can be reused by higher-level cross-chain inheritance logic and unit
 //! tested in isolation.
+//!
+//! When a plan with a cross-chain manifest is triggered, the contract records
+//! one [`BridgeRelease`] per manifest asset. Each release is an instruction for
+//! an off-chain relayer: which chain and bridge to use and how the asset is
+//! split across beneficiaries. The relayer confirms each remote release back
+//! on-chain once it has executed it.
 
-use soroban_sdk::{contracterror, contracttype, Address, String, Vec};
+use soroban_sdk::{contracterror, contracttype, Address, Bytes, Env, String, Vec};
 
 /// Minimum length (in bytes) of an asset symbol such as `"USDC"`.
 pub const MIN_ASSET_SYMBOL_LEN: u32 = 1;
@@ -139,6 +145,107 @@ impl CrossChainInheritancePlan {
     }
 }
 
+/// One beneficiary's portion of a bridged asset.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeShare {
+    /// Index into the plan's beneficiary list.
+    pub beneficiary_index: u32,
+    /// Allocation in basis points at trigger time.
+    pub allocation_bp: u32,
+    /// Amount, in the asset's smallest unit, to release to this beneficiary.
+    pub amount: u128,
+}
+
+/// Lifecycle of a bridge release instruction.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BridgeReleaseStatus {
+    /// Emitted on trigger, waiting for a relayer to execute it.
+    Pending,
+    /// A relayer has confirmed the remote release.
+    Confirmed,
+}
+
+/// Release instruction for a single manifest asset, recorded on trigger.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeRelease {
+    pub plan_id: u64,
+    /// Index of the asset in the plan's manifest.
+    pub asset_index: u32,
+    pub chain: SupportedChain,
+    /// EVM chain ID of `chain`, or `0` for non-EVM chains.
+    pub chain_id: u32,
+    pub bridge_protocol: BridgeProtocol,
+    pub contract_address: Address,
+    pub asset_symbol: String,
+    pub amount: u128,
+    pub shares: Vec<BridgeShare>,
+    pub status: BridgeReleaseStatus,
+    pub created_at: u64,
+    /// Relayer that confirmed the release, once confirmed.
+    pub confirmed_by: Option<Address>,
+    /// Remote transaction reference supplied by the relayer.
+    pub remote_tx: Bytes,
+    pub confirmed_at: u64,
+}
+
+impl BridgeRelease {
+    /// Build a pending release for `asset`, splitting its amount by the
+    /// supplied per-beneficiary allocations (in basis points). Any rounding
+    /// remainder is left unallocated rather than over-released.
+    pub fn pending(
+        env: &Env,
+        plan_id: u64,
+        asset_index: u32,
+        asset: &CrossChainAsset,
+        allocations: &Vec<u32>,
+        now: u64,
+    ) -> Self {
+        let mut shares = Vec::new(env);
+        for (i, bp) in allocations.iter().enumerate() {
+            shares.push_back(BridgeShare {
+                beneficiary_index: i as u32,
+                allocation_bp: bp,
+                amount: asset.amount.saturating_mul(bp as u128) / 10000,
+            });
+        }
+        BridgeRelease {
+            plan_id,
+            asset_index,
+            chain: asset.chain.clone(),
+            chain_id: asset.chain.evm_chain_id(),
+            bridge_protocol: asset.bridge_protocol.clone(),
+            contract_address: asset.contract_address.clone(),
+            asset_symbol: asset.asset_symbol.clone(),
+            amount: asset.amount,
+            shares,
+            status: BridgeReleaseStatus::Pending,
+            created_at: now,
+            confirmed_by: None,
+            remote_tx: Bytes::new(env),
+            confirmed_at: 0,
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CrossChainManifestEvent {
+    pub plan_id: u64,
+    pub asset_count: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeReleaseConfirmedEvent {
+    pub plan_id: u64,
+    pub asset_index: u32,
+    pub relayer: Address,
+    pub remote_tx: Bytes,
+}
+
 /// Errors raised when validating cross-chain data structures.
 #[contracterror]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -302,6 +409,27 @@ mod tests {
         );
     }
 
+    // --- BridgeRelease construction ---
+
+    #[test]
+    fn pending_release_splits_amount_by_allocation() {
+        let env = Env::default();
+        let a = asset(&env, SupportedChain::Polygon, 1_001, "USDC");
+        let mut allocations = Vec::new(&env);
+        allocations.push_back(6000u32);
+        allocations.push_back(4000u32);
+
+        let release = BridgeRelease::pending(&env, 7, 2, &a, &allocations, 100);
+        assert_eq!(release.plan_id, 7);
+        assert_eq!(release.asset_index, 2);
+        assert_eq!(release.chain_id, 137);
+        assert_eq!(release.status, BridgeReleaseStatus::Pending);
+        assert_eq!(release.shares.len(), 2);
+        assert_eq!(release.shares.get(0).unwrap().amount, 600);
+        assert_eq!(release.shares.get(1).unwrap().amount, 400);
+        assert_eq!(release.confirmed_by, None);
+    }
+
     // --- clone / equality (serialization round-trip surface) ---
 
     #[test]
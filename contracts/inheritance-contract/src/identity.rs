@@ -0,0 +1,166 @@
+//! Beneficiary identity.
+//!
+//! A beneficiary is identified by email and a six-digit claim code, which is
+//! small enough to brute-force given enough attempts. Beneficiaries can
+//! instead be bound to a Stellar wallet, either by the owner or by the
+//! beneficiary proving their claim code once; a bound beneficiary can only
+//! claim from that wallet, authorized with `require_auth`. Binding attempts
+//! are rate-limited per plan and beneficiary email, so rotating the calling
+//! wallet does not buy more guesses.
+//!
+//! Bank details are no longer stored on-chain. `bank_account_ref` is an
+//! opaque reference issued by the backend, which keeps the encrypted account
+//! number and resolves it when settling a fiat payout.
+//!
+//! Plans written before v2 stored the plaintext `bank_account`; `migrate`
+//! rewrites them with an empty `bank_account_ref`. An unsalted digest of the
+//! account would be as easy to reverse as the account number is to guess, so
+//! the backend issues a random reference when importing the legacy account
+//! and the admin records it with `assign_legacy_bank_account_ref`.
+
+use crate::{Beneficiary, DistributionMethod, InheritancePlan};
+use soroban_sdk::{
+    contracttype, Address, Bytes, BytesN, Env, FromVal, Map, String, Symbol, Val, Vec,
+};
+
+/// Longest bank account reference accepted.
+pub const MAX_BANK_ACCOUNT_REF_LEN: u32 = 64;
+
+/// How a claimer proves they are a beneficiary.
+pub(crate) enum ClaimProof {
+    /// Email and claim code; only valid for beneficiaries without a wallet.
+    ClaimCode { email: String, claim_code: u32 },
+    /// The claimer's authorized address is the beneficiary's bound wallet.
+    Wallet,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BeneficiaryWalletBoundEvent {
+    pub plan_id: u64,
+    pub beneficiary_index: u32,
+    pub wallet: Address,
+    pub bound_by: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BeneficiaryWalletUnboundEvent {
+    pub plan_id: u64,
+    pub beneficiary_index: u32,
+    pub wallet: Address,
+}
+
+/// Beneficiary layout before v2.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyBeneficiary {
+    pub hashed_full_name: BytesN<32>,
+    pub hashed_email: BytesN<32>,
+    pub hashed_claim_code: BytesN<32>,
+    pub bank_account: Bytes,
+    pub allocation_bp: u32,
+    pub priority: u32,
+    pub is_claimed: bool,
+}
+
+/// Plan layout before v2.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyInheritancePlan {
+    pub plan_name: String,
+    pub description: String,
+    pub asset_type: Symbol,
+    pub total_amount: u64,
+    pub distribution_method: DistributionMethod,
+    pub beneficiaries: Vec<LegacyBeneficiary>,
+    pub total_allocation_bp: u32,
+    pub owner: Address,
+    pub created_at: u64,
+    pub is_active: bool,
+    pub is_lendable: bool,
+    pub total_loaned: u64,
+    pub waterfall_enabled: bool,
+}
+
+/// Whether a stored plan uses the pre-v2 beneficiary layout. Inspects the
+/// raw map rather than attempting a decode, which would trap on mismatch.
+/// Plans without beneficiaries encode identically in both layouts.
+pub fn is_legacy_plan(env: &Env, raw: &Val) -> bool {
+    let plan: Map<Symbol, Val> = Map::from_val(env, raw);
+    let Some(beneficiaries) = plan.get(Symbol::new(env, "beneficiaries")) else {
+        return false;
+    };
+    let beneficiaries: Vec<Map<Symbol, Val>> = Vec::from_val(env, &beneficiaries);
+    let legacy_field = Symbol::new(env, "bank_account");
+    beneficiaries
+        .iter()
+        .any(|b| b.contains_key(legacy_field.clone()))
+}
+
+impl LegacyBeneficiary {
+    pub fn upgrade(self, env: &Env) -> Beneficiary {
+        Beneficiary {
+            hashed_full_name: self.hashed_full_name,
+            hashed_email: self.hashed_email,
+            hashed_claim_code: self.hashed_claim_code,
+            bank_account_ref: Bytes::new(env),
+            allocation_bp: self.allocation_bp,
+            priority: self.priority,
+            is_claimed: self.is_claimed,
+            wallet: None,
+        }
+    }
+}
+
+impl LegacyInheritancePlan {
+    pub fn upgrade(self, env: &Env) -> InheritancePlan {
+        let mut beneficiaries = Vec::new(env);
+        for b in self.beneficiaries.iter() {
+            beneficiaries.push_back(b.upgrade(env));
+        }
+        InheritancePlan {
+            plan_name: self.plan_name,
+            description: self.description,
+            asset_type: self.asset_type,
+            total_amount: self.total_amount,
+            distribution_method: self.distribution_method,
+            beneficiaries,
+            total_allocation_bp: self.total_allocation_bp,
+            owner: self.owner,
+            created_at: self.created_at,
+            is_active: self.is_active,
+            is_lendable: self.is_lendable,
+            total_loaned: self.total_loaned,
+            waterfall_enabled: self.waterfall_enabled,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_beneficiary_keeps_claim_data_and_drops_plaintext() {
+        let env = Env::default();
+        let account = Bytes::from_slice(&env, b"1234567890123456");
+        let legacy = LegacyBeneficiary {
+            hashed_full_name: BytesN::from_array(&env, &[1; 32]),
+            hashed_email: BytesN::from_array(&env, &[2; 32]),
+            hashed_claim_code: BytesN::from_array(&env, &[3; 32]),
+            bank_account: account.clone(),
+            allocation_bp: 5000,
+            priority: 1,
+            is_claimed: true,
+        };
+
+        let b = legacy.clone().upgrade(&env);
+        assert_eq!(b.hashed_email, legacy.hashed_email);
+        assert_eq!(b.hashed_claim_code, legacy.hashed_claim_code);
+        assert_eq!(b.allocation_bp, 5000);
+        assert!(b.is_claimed);
+        assert_eq!(b.wallet, None);
+        assert!(b.bank_account_ref.is_empty());
+    }
+}
@@ -1,8 +1,7 @@
 #![no_std]
 use access_control::{self, Role};
 use genetic_verification::{
-    is_valid_risk_score, DNAVerificationStatus, GeneticCondition, GeneticInheritance,
-    GeneticTriggerConfig, GeneticTriggerType,
+    is_valid_risk_score, GeneticInheritance, GeneticTriggerConfig, GeneticTriggerType,
 };
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, log, symbol_short, token, vec, Address,
@@ -12,13 +11,54 @@ use soroban_sdk::{
 mod disputes;
 use disputes::{DisputeRecord, DisputeStatus};
 
+mod assets;
+pub use assets::{
+    AssetAllocationsSetEvent, AssetClaimedEvent, AssetDelistedEvent, AssetDepositEvent,
+    AssetWhitelistedEvent, AssetWithdrawEvent, PlanAsset, MAX_PLAN_ASSETS, MAX_WHITELISTED_ASSETS,
+};
+
+mod vesting;
+pub use vesting::{TrancheClaimedEvent, TrancheProgress, VestingSchedule, MAX_TRANCHES};
+
+mod trigger_expr;
+pub use trigger_expr::{
+    TriggerApprovedEvent, TriggerExprError, TriggerExpression, TriggerExpressionSetEvent,
+    TriggerNode, MAX_TRIGGER_CHILDREN, MAX_TRIGGER_NODES,
+};
+
+mod attestation;
+pub use attestation::{
+    Attestation, AttestorKind, AttestorRecord, ChallengeWindowOpenedEvent, DeathAttestationConfig,
+    DeathAttestationRound, DeathAttestationStatus, DeathAttestationVetoedEvent,
+    DeathAttestationView, DeathAttestedEvent, MAX_ATTESTORS, MIN_CHALLENGE_WINDOW,
+};
+
+mod contest;
+pub use contest::{
+    ContestWindow, ContestWindowEndedEvent, ContestWindowStartedEvent, TriggerCancelledEvent,
+    MAX_CONTEST_PERIOD,
+};
+
+mod identity;
+use identity::ClaimProof;
+pub use identity::{
+    BeneficiaryWalletBoundEvent, BeneficiaryWalletUnboundEvent, LegacyBeneficiary,
+    LegacyInheritancePlan, MAX_BANK_ACCOUNT_REF_LEN,
+};
+
+mod plan_index;
+use plan_index::{PlanIdPage, MIGRATION_BATCH};
+pub use plan_index::{PlanIndex, PlanPage, PlanStatus, MAX_PAGE_SIZE};
+
 mod cross_chain;
 pub use cross_chain::{
-    BridgeProtocol, CrossChainAsset, CrossChainError, CrossChainInheritancePlan, SupportedChain,
+    BridgeProtocol, BridgeRelease, BridgeReleaseConfirmedEvent, BridgeReleaseStatus, BridgeShare,
+    CrossChainAsset, CrossChainError, CrossChainInheritancePlan, CrossChainManifestEvent,
+    SupportedChain,
 };
 
 /// Current contract version - bump this on each upgrade
-const CONTRACT_VERSION: u32 = 1;
+const CONTRACT_VERSION: u32 = 3;
 
 /// Hard cap on beneficiaries per plan — bounds all O(n) loops.
 const MAX_BENEFICIARIES: u32 = 10;
@@ -44,10 +84,11 @@ pub struct Beneficiary {
     pub hashed_full_name: BytesN<32>,
     pub hashed_email: BytesN<32>,
     pub hashed_claim_code: BytesN<32>,
-    pub bank_account: Bytes, // Plain text for fiat settlement (MVP trade-off)
-    pub allocation_bp: u32,  // Allocation in basis points (0-10000, where 10000 = 100%)
-    pub priority: u32,       // Priority level (1=highest)
-    pub is_claimed: bool,    // Whether the beneficiary has already claimed their portion
+    pub bank_account_ref: Bytes, // Backend reference to the encrypted account number
+    pub allocation_bp: u32,      // Allocation in basis points (0-10000, where 10000 = 100%)
+    pub priority: u32,           // Priority level (1=highest)
+    pub is_claimed: bool,        // Whether the beneficiary has already claimed their portion
+    pub wallet: Option<Address>, // Bound wallet; when set, claims require its signature
 }
 
 #[contracttype]
@@ -56,9 +97,10 @@ pub struct BeneficiaryInput {
     pub name: String,
     pub email: String,
     pub claim_code: u32,
-    pub bank_account: Bytes,
+    pub bank_account_ref: Bytes,
     pub allocation_bp: u32,
     pub priority: u32,
+    pub wallet: Option<Address>,
 }
 
 #[contracttype]
@@ -66,7 +108,7 @@ pub struct BeneficiaryInput {
 pub struct InheritancePlan {
     pub plan_name: String,
     pub description: String,
-    pub asset_type: Symbol, // Only USDC allowed
+    pub asset_type: Symbol, // Primary asset ticker (USDC or a whitelisted asset)
     pub total_amount: u64,
     pub distribution_method: DistributionMethod,
     pub beneficiaries: Vec<Beneficiary>,
@@ -114,6 +156,22 @@ pub enum InheritanceError {
     NothingToClaim = 30,
     EmergencyAccessAlreadyActive = 31,
     InvalidGuardianThreshold = 32,
+    TooManyAssets = 33,
+    InvalidTrancheCount = 34,
+    BridgeReleaseNotFound = 35,
+    InvalidTriggerExpression = 36,
+    DocumentHashMismatch = 37,
+    ChallengeWindowClosed = 38,
+    AttestationNotFound = 39,
+    ContestWindowActive = 40,
+    InvalidContestPeriod = 41,
+    WalletAlreadyBound = 42,
+    InvalidAttestationThreshold = 43,
+    ChallengeWindowTooShort = 44,
+    AttestationRoundActive = 45,
+    AlreadyAttested = 46,
+    AttestationNotConfigured = 47,
+    BankAccountRefAlreadySet = 48,
     // Consolidated errors to stay under Soroban limits
     // Additional specific errors can be handled with these generic ones:
     // - Use InvalidAllocation for DuplicatePriority, PriorityOutOfRange
@@ -123,18 +181,38 @@ pub enum InheritanceError {
     // - Use ClaimNotAllowedYet for SignatureExpired, RateLimitExceeded
 }
 
+impl From<TriggerExprError> for InheritanceError {
+    fn from(_: TriggerExprError) -> Self {
+        InheritanceError::InvalidTriggerExpression
+    }
+}
+
+impl From<CrossChainError> for InheritanceError {
+    fn from(err: CrossChainError) -> Self {
+        match err {
+            CrossChainError::ZeroAmount => InheritanceError::InvalidTotalAmount,
+            CrossChainError::EmptyAssetSymbol | CrossChainError::AssetSymbolTooLong => {
+                InheritanceError::InvalidAssetType
+            }
+            CrossChainError::NoAssets => InheritanceError::MissingRequiredField,
+            CrossChainError::TooManyAssets => InheritanceError::TooManyAssets,
+        }
+    }
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     NextPlanId,
     Plan(u64),
-    Claim(BytesN<32>),           // keyed by hashed_email
-    ClaimSalt(u64, u32),         // (plan_id, beneficiary_index) -> BytesN<32>
-    ClaimAttempts(u64, Address), // (plan_id, claimer) -> ClaimAttemptWindow
-    UserPlans(Address),          // keyed by owner Address, value is Vec<u64>
-    UserClaimedPlans(Address),   // keyed by owner Address, value is Vec<u64>
-    DeactivatedPlans,            // value is Vec<u64> of all deactivated plan IDs
-    AllClaimedPlans,             // value is Vec<u64> of all claimed plan IDs
+    Claim(BytesN<32>),             // keyed by hashed_email
+    ClaimSalt(u64, u32),           // (plan_id, beneficiary_index) -> BytesN<32>
+    ClaimAttempts(u64, Address),   // (plan_id, claimer) -> ClaimAttemptWindow
+    BindAttempts(u64, BytesN<32>), // (plan_id, hashed_email) -> ClaimAttemptWindow
+    UserPlans(Address),            // pre-v3 Vec<u64> of owned plans; removed by migrate
+    UserClaimedPlans(Address),     // pre-v3 Vec<u64> of claimed plans; removed by migrate
+    DeactivatedPlans,              // pre-v3 Vec<u64>; removed by migrate
+    AllClaimedPlans,               // pre-v3 Vec<u64>; removed by migrate
     Admin,
     Kyc(Address),
     Version,
@@ -162,6 +240,19 @@ pub enum DataKey {
     // Various notification/acknowledgment keys consolidated into single pattern
     PlanMetadata(u64, u32), // Generic key for plan-specific metadata (plan_id, metadata_type)
     UserMetadata(Address, u32), // Generic key for user-specific metadata (user, metadata_type)
+    WhitelistedAsset(Address), // token -> Symbol (admin-approved plan asset)
+    WhitelistedAssets,      // Vec<Address> of all whitelisted plan assets
+    PlanToken(u64),         // plan_id -> Address (primary token the plan was created with)
+    LegacyPlanToken,        // Address (primary token of plans created before PlanToken)
+    PlanAssets(u64),        // plan_id -> Vec<PlanAsset> (secondary asset basket)
+    TrancheCount(u64),      // plan_id -> u32 (owner-configured periodic payout tranches)
+    TrancheProgress(u64, u32), // (plan_id, beneficiary_index) -> TrancheProgress
+    CrossChainPlan(u64),    // plan_id -> CrossChainInheritancePlan (cross-chain asset manifest)
+    BridgeReleases(u64),    // plan_id -> Vec<BridgeRelease> (recorded on trigger)
+    IndexLen(PlanIndex),    // number of plans in a plan index
+    IndexEntry(PlanIndex, u32), // (index, position) -> plan_id
+    IndexPos(PlanIndex, u64), // (index, plan_id) -> position
+    MigrationCursor,        // next plan_id for an in-progress `migrate`
 }
 
 #[contracttype]
@@ -301,6 +392,8 @@ pub struct InheritanceTriggeredEvent {
     pub plan_id: u64,
     pub triggered_at: u64,
     pub outstanding_loans: u64,
+    /// End of the contest window, or 0 if the plan is released immediately.
+    pub contest_ends_at: u64,
 }
 
 #[contracttype]
@@ -806,16 +899,16 @@ impl InheritanceContract {
         plan_id: u64,
         claimer: &Address,
     ) -> Result<(), InheritanceError> {
+        Self::check_and_record_attempt(env, DataKey::ClaimAttempts(plan_id, claimer.clone()))
+    }
+
+    fn check_and_record_attempt(env: &Env, key: DataKey) -> Result<(), InheritanceError> {
         let now = env.ledger().timestamp();
-        let key = DataKey::ClaimAttempts(plan_id, claimer.clone());
         let mut w: ClaimAttemptWindow =
-            env.storage()
-                .persistent()
-                .get(&key)
-                .unwrap_or(ClaimAttemptWindow {
-                    window_start: now,
-                    attempts: 0,
-                });
+            plan_index::get_persistent(env, &key).unwrap_or(ClaimAttemptWindow {
+                window_start: now,
+                attempts: 0,
+            });
 
         if now.saturating_sub(w.window_start) >= Self::CLAIM_ATTEMPT_WINDOW_SECONDS {
             w.window_start = now;
@@ -826,7 +919,7 @@ impl InheritanceContract {
         }
 
         w.attempts = w.attempts.saturating_add(1);
-        env.storage().persistent().set(&key, &w);
+        plan_index::set_persistent(env, &key, &w);
         Ok(())
     }
 
@@ -927,10 +1020,7 @@ impl InheritanceContract {
     }
 
     fn is_arbitrator(env: &Env, who: &Address) -> bool {
-        let list: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(0, 6000))
+        let list: Vec<Address> = plan_index::get_persistent(env, &DataKey::PlanMetadata(0, 6000))
             .unwrap_or(Vec::new(env));
         for a in list.iter() {
             if a == *who {
@@ -946,20 +1036,16 @@ impl InheritanceContract {
         arbitrator: Address,
     ) -> Result<(), InheritanceError> {
         Self::require_admin(&env, &admin)?;
-        let mut list: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(0, 6000))
-            .unwrap_or(Vec::new(&env));
+        let mut list: Vec<Address> =
+            plan_index::get_persistent(&env, &DataKey::PlanMetadata(0, 6000))
+                .unwrap_or(Vec::new(&env));
         for a in list.iter() {
             if a == arbitrator {
                 return Ok(());
             }
         }
         list.push_back(arbitrator);
-        env.storage()
-            .persistent()
-            .set(&DataKey::PlanMetadata(0, 6000), &list);
+        plan_index::set_persistent(&env, &DataKey::PlanMetadata(0, 6000), &list);
         Ok(())
     }
 
@@ -969,10 +1055,7 @@ impl InheritanceContract {
         arbitrator: Address,
     ) -> Result<(), InheritanceError> {
         Self::require_admin(&env, &admin)?;
-        let list: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(0, 6000))
+        let list: Vec<Address> = plan_index::get_persistent(&env, &DataKey::PlanMetadata(0, 6000))
             .unwrap_or(Vec::new(&env));
         let mut updated: Vec<Address> = Vec::new(&env);
         for a in list.iter() {
@@ -980,17 +1063,12 @@ impl InheritanceContract {
                 updated.push_back(a);
             }
         }
-        env.storage()
-            .persistent()
-            .set(&DataKey::PlanMetadata(0, 6000), &updated);
+        plan_index::set_persistent(&env, &DataKey::PlanMetadata(0, 6000), &updated);
         Ok(())
     }
 
     pub fn get_arbitrators(env: Env) -> Vec<Address> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(0, 6000))
-            .unwrap_or(Vec::new(&env))
+        plan_index::get_persistent(&env, &DataKey::PlanMetadata(0, 6000)).unwrap_or(Vec::new(&env))
     }
 
     pub fn file_dispute(
@@ -1005,19 +1083,13 @@ impl InheritanceContract {
         // Ensure plan exists.
         let _ = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
 
-        let dispute_id = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(0, 5002))
-            .unwrap_or(0u64);
+        let dispute_id =
+            plan_index::get_persistent(&env, &DataKey::PlanMetadata(0, 5002)).unwrap_or(0u64);
 
         let mut arbitrator = Self::get_admin(&env).ok_or(InheritanceError::AdminNotSet)?;
-        let list: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(0, 6000))
+        let list: Vec<Address> = plan_index::get_persistent(&env, &DataKey::PlanMetadata(0, 6000))
             .unwrap_or(Vec::new(&env));
-        if list.len() > 0 {
+        if !list.is_empty() {
             arbitrator = list.get(0).unwrap();
         }
 
@@ -1033,23 +1105,38 @@ impl InheritanceContract {
             arbitrator,
         };
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::PlanMetadata(dispute_id, 5000), &record);
+        plan_index::set_persistent(&env, &DataKey::PlanMetadata(dispute_id, 5000), &record);
 
-        let mut plan_disputes: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(plan_id, 5001))
-            .unwrap_or(Vec::new(&env));
+        let mut plan_disputes: Vec<u64> =
+            plan_index::get_persistent(&env, &DataKey::PlanMetadata(plan_id, 5001))
+                .unwrap_or(Vec::new(&env));
         plan_disputes.push_back(dispute_id);
-        env.storage()
-            .persistent()
-            .set(&DataKey::PlanMetadata(plan_id, 5001), &plan_disputes);
+        plan_index::set_persistent(&env, &DataKey::PlanMetadata(plan_id, 5001), &plan_disputes);
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::PlanMetadata(0, 5002), &(dispute_id + 1));
+        plan_index::set_persistent(&env, &DataKey::PlanMetadata(0, 5002), &(dispute_id + 1));
+
+        // An emergency contact contesting a trigger freezes the plan until
+        // the dispute is reviewed.
+        let contested = Self::get_contest_window(env.clone(), plan_id)
+            .is_some_and(|w| w.is_open(env.ledger().timestamp()));
+        if contested && Self::get_emergency_contacts(env.clone(), plan_id).contains(&disputer) {
+            let fr = FreezeRecord {
+                plan_id,
+                frozen_at: env.ledger().timestamp(),
+                reason: String::from_str(&env, "contest"),
+                frozen_by: disputer.clone(),
+            };
+            plan_index::set_persistent(&env, &DataKey::PlanMetadata(plan_id, 200), &fr);
+            env.events().publish(
+                (symbol_short!("PLAN"), symbol_short!("FROZE")),
+                PlanFrozenEvent {
+                    plan_id,
+                    frozen_by: disputer.clone(),
+                    frozen_at: fr.frozen_at,
+                    reason: fr.reason.clone(),
+                },
+            );
+        }
 
         env.events().publish(
             (symbol_short!("DSPT"), symbol_short!("FILED")),
@@ -1066,15 +1153,11 @@ impl InheritanceContract {
     }
 
     pub fn get_dispute(env: Env, dispute_id: u64) -> Option<DisputeRecord> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(dispute_id, 5000))
+        plan_index::get_persistent(&env, &DataKey::PlanMetadata(dispute_id, 5000))
     }
 
     pub fn get_plan_disputes(env: Env, plan_id: u64) -> Vec<u64> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(plan_id, 5001))
+        plan_index::get_persistent(&env, &DataKey::PlanMetadata(plan_id, 5001))
             .unwrap_or(Vec::new(&env))
     }
 
@@ -1089,11 +1172,9 @@ impl InheritanceContract {
         arbitrator.require_auth();
         Self::check_not_paused(&env);
 
-        let mut record: DisputeRecord = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(dispute_id, 5000))
-            .ok_or(InheritanceError::PlanNotFound)?;
+        let mut record: DisputeRecord =
+            plan_index::get_persistent(&env, &DataKey::PlanMetadata(dispute_id, 5000))
+                .ok_or(InheritanceError::PlanNotFound)?;
 
         let is_admin = Self::get_admin(&env)
             .map(|a| a == arbitrator)
@@ -1106,15 +1187,13 @@ impl InheritanceContract {
             return Err(InheritanceError::AlreadyClaimed);
         }
 
-        record.status = new_status.clone();
+        record.status = new_status;
         record.resolution_notes = resolution_notes;
         if new_status == DisputeStatus::Resolved || new_status == DisputeStatus::Rejected {
             record.resolved_at = env.ledger().timestamp();
         }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::PlanMetadata(dispute_id, 5000), &record);
+        plan_index::set_persistent(&env, &DataKey::PlanMetadata(dispute_id, 5000), &record);
 
         if freeze_plan {
             let fr = FreezeRecord {
@@ -1123,9 +1202,7 @@ impl InheritanceContract {
                 reason: String::from_str(&env, "dispute"),
                 frozen_by: arbitrator.clone(),
             };
-            env.storage()
-                .persistent()
-                .set(&DataKey::PlanMetadata(record.plan_id, 200), &fr);
+            plan_index::set_persistent(&env, &DataKey::PlanMetadata(record.plan_id, 200), &fr);
             env.events().publish(
                 (symbol_short!("PLAN"), symbol_short!("FROZE")),
                 PlanFrozenEvent {
@@ -1143,7 +1220,7 @@ impl InheritanceContract {
                 disputes::DisputeResolvedEvent {
                     dispute_id,
                     plan_id: record.plan_id,
-                    status: record.status.clone(),
+                    status: record.status,
                     arbitrator: arbitrator.clone(),
                     resolved_at: record.resolved_at,
                 },
@@ -1169,6 +1246,7 @@ impl InheritanceContract {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn create_beneficiary(
         env: &Env,
         plan_id: u64,
@@ -1176,12 +1254,15 @@ impl InheritanceContract {
         full_name: String,
         email: String,
         claim_code: u32,
-        bank_account: Bytes,
+        bank_account_ref: Bytes,
         allocation_bp: u32,
         priority: u32,
     ) -> Result<Beneficiary, InheritanceError> {
         // Validate inputs
-        if full_name.is_empty() || email.is_empty() || bank_account.is_empty() {
+        if full_name.is_empty() || email.is_empty() || bank_account_ref.is_empty() {
+            return Err(InheritanceError::InvalidBeneficiaryData);
+        }
+        if bank_account_ref.len() > MAX_BANK_ACCOUNT_REF_LEN {
             return Err(InheritanceError::InvalidBeneficiaryData);
         }
 
@@ -1203,10 +1284,11 @@ impl InheritanceContract {
             hashed_full_name: Self::hash_string(env, full_name),
             hashed_email: Self::hash_string(env, email),
             hashed_claim_code,
-            bank_account,
+            bank_account_ref,
             allocation_bp,
             priority,
             is_claimed: false,
+            wallet: None,
         })
     }
 
@@ -1228,8 +1310,9 @@ impl InheritanceContract {
             return Err(InheritanceError::DescriptionTooLong);
         }
 
-        // Validate asset type (only USDC allowed)
-        if asset_type != Symbol::new(env, "USDC") {
+        // Validate asset type (USDC or any admin-whitelisted asset)
+        if asset_type != Symbol::new(env, "USDC") && !Self::is_whitelisted_symbol(env, &asset_type)
+        {
             return Err(InheritanceError::InvalidAssetType);
         }
 
@@ -1293,11 +1376,8 @@ impl InheritanceContract {
     /// - KycNotSubmitted: If user has not submitted KYC
     fn check_kyc_approved(env: &Env, user: &Address) -> Result<(), InheritanceError> {
         let key = DataKey::Kyc(user.clone());
-        let status: KycStatus = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .ok_or(InheritanceError::KycNotSubmitted)?;
+        let status: KycStatus =
+            plan_index::get_persistent(env, &key).ok_or(InheritanceError::KycNotSubmitted)?;
 
         if !status.approved {
             return Err(InheritanceError::KycNotSubmitted);
@@ -1322,84 +1402,119 @@ impl InheritanceContract {
 
     fn store_plan(env: &Env, plan_id: u64, plan: &InheritancePlan) {
         let key = DataKey::Plan(plan_id);
-        env.storage().persistent().set(&key, plan);
+        plan_index::set_persistent(env, &key, plan);
+        plan_index::extend_persistent(env, &key);
+        plan_index::extend_instance(env);
     }
 
     fn get_plan(env: &Env, plan_id: u64) -> Option<InheritancePlan> {
         let key = DataKey::Plan(plan_id);
-        env.storage().persistent().get(&key)
+        let plan = plan_index::get_persistent(env, &key);
+        if plan.is_some() {
+            plan_index::extend_persistent(env, &key);
+        }
+        plan
     }
 
     fn add_plan_to_user(env: &Env, owner: Address, plan_id: u64) {
-        let key = DataKey::UserPlans(owner.clone());
-        let mut plans: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(env));
-
-        plans.push_back(plan_id);
-        env.storage().persistent().set(&key, &plans);
+        plan_index::insert(env, &PlanIndex::Owner(owner), plan_id);
+        plan_index::insert(env, &PlanIndex::Active, plan_id);
     }
 
     fn remove_plan_from_user(env: &Env, owner: Address, plan_id: u64) {
-        let key = DataKey::UserPlans(owner);
-        let mut plans: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(env));
-
-        for i in 0..plans.len() {
-            if plans.get(i).unwrap() == plan_id {
-                plans.remove(i);
-                break;
-            }
-        }
-        env.storage().persistent().set(&key, &plans);
+        plan_index::remove(env, &PlanIndex::Owner(owner), plan_id);
     }
 
     fn add_plan_to_deactivated(env: &Env, plan_id: u64) {
-        let key = DataKey::DeactivatedPlans;
-        let mut plans: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(env));
-
-        // Avoid duplicates if called multiple times (though logic should prevent this)
-        if !plans.contains(plan_id) {
-            plans.push_back(plan_id);
-            env.storage().persistent().set(&key, &plans);
-        }
+        plan_index::remove(env, &PlanIndex::Active, plan_id);
+        plan_index::insert(env, &PlanIndex::Deactivated, plan_id);
     }
 
     fn add_plan_to_claimed(env: &Env, owner: Address, plan_id: u64) {
-        let key_user = DataKey::UserClaimedPlans(owner);
-        let mut user_plans: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key_user)
-            .unwrap_or(Vec::new(env));
+        plan_index::insert(env, &PlanIndex::OwnerClaimed(owner), plan_id);
+        plan_index::insert(env, &PlanIndex::Claimed, plan_id);
+    }
 
-        if !user_plans.contains(plan_id) {
-            user_plans.push_back(plan_id);
-            env.storage().persistent().set(&key_user, &user_plans);
+    /// Ids of all plans in creation order; plan ids are dense from 1.
+    fn all_plans_page(env: &Env, cursor: u32, limit: u32) -> PlanIdPage {
+        let count = Self::get_next_plan_id(env).saturating_sub(1) as u32;
+        let (start, end, next_cursor) = plan_index::page_bounds(count, cursor, limit);
+        let mut plan_ids = Vec::new(env);
+        for position in start..end {
+            plan_ids.push_back(position as u64 + 1);
+        }
+        PlanIdPage {
+            plan_ids,
+            next_cursor,
         }
+    }
 
-        let key_all = DataKey::AllClaimedPlans;
-        let mut all_plans: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key_all)
-            .unwrap_or(Vec::new(env));
+    /// Every plan `next_page` yields, following cursors from 0. Large sets
+    /// may exceed the per-transaction read limits; the paginated getters
+    /// do not.
+    fn load_all_plans(env: &Env, next_page: impl Fn(u32) -> PlanIdPage) -> Vec<InheritancePlan> {
+        let mut plans = Vec::new(env);
+        let mut cursor = Some(0);
+        while let Some(position) = cursor {
+            let page = Self::load_plan_page(env, next_page(position));
+            plans.append(&page.plans);
+            cursor = page.next_cursor;
+        }
+        plans
+    }
 
-        if !all_plans.contains(plan_id) {
-            all_plans.push_back(plan_id);
-            env.storage().persistent().set(&key_all, &all_plans);
+    fn load_plan_page(env: &Env, page: PlanIdPage) -> PlanPage {
+        let mut plan_ids = Vec::new(env);
+        let mut plans = Vec::new(env);
+        for plan_id in page.plan_ids.iter() {
+            if let Some(plan) = Self::get_plan(env, plan_id) {
+                plan_ids.push_back(plan_id);
+                plans.push_back(plan);
+            }
+        }
+        PlanPage {
+            plan_ids,
+            plans,
+            next_cursor: page.next_cursor,
         }
     }
 
+    /// Plans owned by `user`, `limit` (at most `MAX_PAGE_SIZE`) at a time
+    /// starting at `cursor`.
+    pub fn get_user_plans_page(env: Env, user: Address, cursor: u32, limit: u32) -> PlanPage {
+        user.require_auth();
+        let page = plan_index::page(&env, &PlanIndex::Owner(user), cursor, limit);
+        Self::load_plan_page(&env, page)
+    }
+
+    /// Plans owned by `user` with at least one completed claim, paginated.
+    pub fn get_user_claimed_plans_page(
+        env: Env,
+        user: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> PlanPage {
+        user.require_auth();
+        let page = plan_index::page(&env, &PlanIndex::OwnerClaimed(user), cursor, limit);
+        Self::load_plan_page(&env, page)
+    }
+
+    /// All plans, or only those with `status`, paginated (Admin only).
+    pub fn get_plans_page(
+        env: Env,
+        admin: Address,
+        status: Option<PlanStatus>,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<PlanPage, InheritanceError> {
+        Self::require_admin(&env, &admin)?;
+        let page = match status {
+            Some(status) => plan_index::page(&env, &status.index(), cursor, limit),
+            None => Self::all_plans_page(&env, cursor, limit),
+        };
+        Ok(Self::load_plan_page(&env, page))
+    }
+
     /// Get plan details
     ///
     /// # Arguments
@@ -1439,11 +1554,7 @@ impl InheritanceContract {
     /// Internal helper to check and potentially expire emergency access based on the 7-day period.
     fn check_and_expire_emergency_access(env: &Env, plan_id: u64) -> bool {
         let key = DataKey::EmergencyAccess(plan_id);
-        if let Some(record) = env
-            .storage()
-            .persistent()
-            .get::<_, EmergencyAccessRecord>(&key)
-        {
+        if let Some(record) = plan_index::get_persistent::<EmergencyAccessRecord>(env, &key) {
             if env.ledger().timestamp() > record.activated_at + Self::EMERGENCY_EXPIRATION_PERIOD {
                 // Expired
                 env.storage().persistent().remove(&key);
@@ -1462,38 +1573,36 @@ impl InheritanceContract {
         false
     }
 
+    /// All plans owned by `user`; `get_user_plans_page` pages through them.
     pub fn get_user_plans(env: Env, user: Address) -> Vec<InheritancePlan> {
         user.require_auth();
-        let key = DataKey::UserPlans(user);
-        let plan_ids: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
-
-        let mut plans = Vec::new(&env);
-        for plan_id in plan_ids.iter() {
-            if let Some(plan) = Self::get_plan(&env, plan_id) {
-                plans.push_back(plan);
-            }
-        }
-        plans
+        let index = PlanIndex::Owner(user);
+        Self::load_all_plans(&env, |cursor| {
+            plan_index::page(&env, &index, cursor, MAX_PAGE_SIZE)
+        })
     }
 
+    /// All plans (Admin only); `get_plans_page` pages through them.
     pub fn get_all_plans(
         env: Env,
         admin: Address,
     ) -> Result<Vec<InheritancePlan>, InheritanceError> {
         Self::require_admin(&env, &admin)?;
+        Ok(Self::load_all_plans(&env, |cursor| {
+            Self::all_plans_page(&env, cursor, MAX_PAGE_SIZE)
+        }))
+    }
 
-        let mut plans = Vec::new(&env);
-        let next_plan_id = Self::get_next_plan_id(&env);
-        for plan_id in 1..next_plan_id {
-            if let Some(plan) = Self::get_plan(&env, plan_id) {
-                plans.push_back(plan);
-            }
-        }
-        Ok(plans)
+    /// All plans in `index` (Admin only).
+    fn get_all_indexed_plans(
+        env: &Env,
+        admin: &Address,
+        index: PlanIndex,
+    ) -> Result<Vec<InheritancePlan>, InheritanceError> {
+        Self::require_admin(env, admin)?;
+        Ok(Self::load_all_plans(env, |cursor| {
+            plan_index::page(env, &index, cursor, MAX_PAGE_SIZE)
+        }))
     }
 
     pub fn get_user_pending_plans(env: Env, user: Address) -> Vec<InheritancePlan> {
@@ -1507,18 +1616,13 @@ impl InheritanceContract {
         pending
     }
 
+    /// All active plans (Admin only); `get_plans_page` with
+    /// `PlanStatus::Active` pages through them.
     pub fn get_all_pending_plans(
         env: Env,
         admin: Address,
     ) -> Result<Vec<InheritancePlan>, InheritanceError> {
-        let all_plans = Self::get_all_plans(env.clone(), admin)?;
-        let mut pending = Vec::new(&env);
-        for plan in all_plans.iter() {
-            if plan.is_active {
-                pending.push_back(plan);
-            }
-        }
-        Ok(pending)
+        Self::get_all_indexed_plans(&env, &admin, PlanIndex::Active)
     }
 
     /// Add a beneficiary to an existing inheritance plan
@@ -1527,7 +1631,7 @@ impl InheritanceContract {
     /// * `env` - The environment
     /// * `owner` - The plan owner (must authorize this call)
     /// * `plan_id` - The ID of the plan to add beneficiary to
-    /// * `beneficiary_input` - Beneficiary data (name, email, claim_code, bank_account, allocation_bp)
+    /// * `beneficiary_input` - Beneficiary data (name, email, claim_code, bank_account_ref, allocation_bp, optional wallet)
     ///
     /// # Returns
     /// Ok(()) on success
@@ -1540,6 +1644,7 @@ impl InheritanceContract {
     /// - InvalidBeneficiaryData: If any required field is empty
     /// - InvalidAllocation: If allocation_bp is 0
     /// - InvalidClaimCodeRange: If claim_code > 999999
+    /// - WalletAlreadyBound: If the wallet is already bound to another beneficiary
     pub fn add_beneficiary(
         env: Env,
         owner: Address,
@@ -1576,17 +1681,21 @@ impl InheritanceContract {
         }
 
         // Create the beneficiary (validates inputs and hashes sensitive data)
-        let beneficiary = Self::create_beneficiary(
+        let mut beneficiary = Self::create_beneficiary(
             &env,
             plan_id,
             plan.beneficiaries.len(),
             beneficiary_input.name,
             beneficiary_input.email.clone(),
             beneficiary_input.claim_code,
-            beneficiary_input.bank_account,
+            beneficiary_input.bank_account_ref,
             beneficiary_input.allocation_bp,
             beneficiary_input.priority,
         )?;
+        if let Some(wallet) = beneficiary_input.wallet {
+            Self::ensure_wallet_unbound(&plan, &wallet)?;
+            beneficiary.wallet = Some(wallet);
+        }
 
         // Add beneficiary to plan
         plan.beneficiaries.push_back(beneficiary.clone());
@@ -1594,6 +1703,7 @@ impl InheritanceContract {
 
         // Store updated plan
         Self::store_plan(&env, plan_id, &plan);
+        Self::clear_asset_allocations(&env, plan_id);
 
         // Emit event
         env.events().publish(
@@ -1668,6 +1778,7 @@ impl InheritanceContract {
 
         // Store updated plan
         Self::store_plan(&env, plan_id, &plan);
+        Self::clear_asset_allocations(&env, plan_id);
 
         // Emit event
         env.events().publish(
@@ -1695,12 +1806,13 @@ impl InheritanceContract {
     /// # Arguments
     /// * `env` - The environment
     /// * `owner` - The plan owner (must authorize and have sufficient token balance)
-    /// * `token` - The token contract address (e.g. USDC)
+    /// * `token` - The primary token contract address (USDC, or a whitelisted asset once
+    ///   an asset whitelist has been configured)
     /// * `plan_name` - Name of the inheritance plan (required)
     /// * `description` - Description of the plan (max 500 characters)
     /// * `total_amount` - User-input amount (must be > 0); fee is 2% of this, plan stores net
     /// * `distribution_method` - How to distribute the inheritance
-    /// * `beneficiaries_data` - Vector of beneficiary data tuples: (full_name, email, claim_code, bank_account, allocation_bp)
+    /// * `beneficiaries_data` - Vector of beneficiary data tuples: (full_name, email, claim_code, bank_account_ref, allocation_bp)
     ///
     /// # Returns
     /// The plan ID of the created inheritance plan
@@ -1710,6 +1822,7 @@ impl InheritanceContract {
     /// - InsufficientBalance: Owner balance less than total_amount
     /// - FeeTransferFailed: Fee transfer to admin failed
     /// - InvalidTotalAmount: Net amount would be zero after fee
+    /// - InvalidAssetType: An asset whitelist is configured and `token` is not on it
     /// - Other validation errors from validate_plan_inputs / validate_beneficiaries
     pub fn create_inheritance_plan(
         env: Env,
@@ -1751,12 +1864,12 @@ impl InheritanceContract {
         }
 
         // Validate plan inputs using user input for "full amount" validation
-        let usdc_symbol = Symbol::new(&env, "USDC");
+        let asset_type = Self::resolve_primary_asset(&env, &token)?;
         Self::validate_plan_inputs(
             &env,
             plan_name.clone(),
             description.clone(),
-            usdc_symbol.clone(),
+            asset_type.clone(),
             total_amount,
         )?;
 
@@ -1838,7 +1951,7 @@ impl InheritanceContract {
         let plan = InheritancePlan {
             plan_name,
             description,
-            asset_type: Symbol::new(&env, "USDC"),
+            asset_type,
             total_amount: net_amount,
             distribution_method,
             beneficiaries,
@@ -1853,6 +1966,7 @@ impl InheritanceContract {
 
         // Store the plan
         Self::store_plan(&env, plan_id, &plan);
+        plan_index::set_persistent(&env, &DataKey::PlanToken(plan_id), &token);
 
         // Add to user's plan list
         Self::add_plan_to_user(&env, owner.clone(), plan_id);
@@ -1933,6 +2047,15 @@ impl InheritanceContract {
             return Err(InheritanceError::PlanNotActive);
         }
 
+        // The plan's primary token tops up `total_amount`; any other token
+        // must be whitelisted and is credited to the plan's asset basket.
+        let mut basket = Self::get_basket(&env, plan_id);
+        let basket_index = if Self::is_primary_token(&env, plan_id, &token) {
+            None
+        } else {
+            Some(Self::basket_slot_for_deposit(&env, &mut basket, &token)?)
+        };
+
         let token_client = token::Client::new(&env, &token);
         let balance = token_client.balance(&caller);
         let required = amount as i128;
@@ -1953,13 +2076,35 @@ impl InheritanceContract {
             return Err(InheritanceError::FeeTransferFailed);
         }
 
-        plan.total_amount += amount;
-        Self::store_plan(&env, plan_id, &plan);
+        match basket_index {
+            None => {
+                plan.total_amount += amount;
+                Self::store_plan(&env, plan_id, &plan);
 
-        env.events().publish(
-            (symbol_short!("VAULT"), symbol_short!("DEPOSIT")),
-            VaultDepositEvent { plan_id, amount },
-        );
+                env.events().publish(
+                    (symbol_short!("VAULT"), symbol_short!("DEPOSIT")),
+                    VaultDepositEvent { plan_id, amount },
+                );
+            }
+            Some(i) => {
+                let mut asset = basket.get(i).unwrap();
+                asset.balance = asset
+                    .balance
+                    .checked_add(amount)
+                    .ok_or(InheritanceError::InvalidTotalAmount)?;
+                basket.set(i, asset);
+                Self::store_basket(&env, plan_id, &basket);
+
+                env.events().publish(
+                    (symbol_short!("ASSET"), symbol_short!("DEPOSIT")),
+                    AssetDepositEvent {
+                        plan_id,
+                        token: token.clone(),
+                        amount,
+                    },
+                );
+            }
+        }
         log!(&env, "Deposited {} into plan {}", amount, plan_id);
         Self::exit_guard(&env);
         Ok(())
@@ -2001,6 +2146,53 @@ impl InheritanceContract {
             return Err(InheritanceError::PlanNotActive);
         }
 
+        // Secondary assets are withdrawn from the plan's basket.
+        if !Self::is_primary_token(&env, plan_id, &token) {
+            let mut basket = Self::get_basket(&env, plan_id);
+            let i = Self::find_basket_asset(&basket, &token)
+                .ok_or(InheritanceError::InvalidAssetType)?;
+            let mut asset = basket.get(i).unwrap();
+
+            if Self::is_emergency_active(&env, plan_id) {
+                let limit = (asset.balance as u128)
+                    .checked_mul(EMERGENCY_TRANSFER_LIMIT_BP as u128)
+                    .and_then(|v| v.checked_div(10000))
+                    .unwrap_or(0) as u64;
+
+                if amount > limit {
+                    return Err(InheritanceError::EmergencyCooldownActive);
+                }
+            }
+
+            if amount > asset.balance {
+                return Err(InheritanceError::InsufficientBalance);
+            }
+
+            Self::transfer_from_vault(&env, &token, &caller, amount)?;
+
+            asset.balance -= amount;
+            basket.set(i, asset);
+            Self::store_basket(&env, plan_id, &basket);
+
+            env.events().publish(
+                (symbol_short!("ASSET"), symbol_short!("WITHDRAW")),
+                AssetWithdrawEvent {
+                    plan_id,
+                    token: token.clone(),
+                    amount,
+                },
+            );
+            log!(
+                &env,
+                "Withdrew {} of {} from plan {}",
+                amount,
+                token,
+                plan_id
+            );
+            Self::exit_guard(&env);
+            return Ok(());
+        }
+
         // Emergency Guard: Limit withdrawal if emergency access was recently activated
         if Self::is_emergency_active(&env, plan_id) {
             let limit = (plan.total_amount as u128)
@@ -2056,61 +2248,405 @@ impl InheritanceContract {
         Ok(())
     }
 
-    pub fn set_beneficiary_priority(
+    // ─── Multi-Asset Plans ──────────────────────────────
+
+    /// Whitelist a token contract as an accepted plan asset (admin only).
+    /// `symbol` is the ticker recorded on plans that use the token, e.g. `XLM`.
+    pub fn whitelist_asset(
         env: Env,
-        owner: Address,
-        plan_id: u64,
-        beneficiary_index: u32,
-        priority: u32,
+        admin: Address,
+        token: Address,
+        symbol: Symbol,
     ) -> Result<(), InheritanceError> {
-        owner.require_auth();
-        let mut plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
-        if plan.owner != owner {
-            return Err(InheritanceError::Unauthorized);
-        }
+        Self::require_admin(&env, &admin)?;
 
-        if beneficiary_index >= plan.beneficiaries.len() {
-            return Err(InheritanceError::InvalidBeneficiaryIndex);
+        let mut list = Self::get_whitelisted_assets(env.clone());
+        if !list.contains(token.clone()) {
+            if list.len() >= MAX_WHITELISTED_ASSETS {
+                return Err(InheritanceError::TooManyAssets);
+            }
+            list.push_back(token.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::WhitelistedAssets, &list);
         }
+        env.storage()
+            .persistent()
+            .set(&DataKey::WhitelistedAsset(token.clone()), &symbol);
 
-        if priority == 0 {
-            return Err(InheritanceError::InvalidAllocation);
-        }
+        env.events().publish(
+            (symbol_short!("ASSET"), symbol_short!("WLIST")),
+            AssetWhitelistedEvent { token, symbol },
+        );
+        Ok(())
+    }
 
-        // Check for duplicate priorities
-        for i in 0..plan.beneficiaries.len() {
-            if i != beneficiary_index {
-                let b = plan.beneficiaries.get(i).unwrap();
-                if b.priority == priority {
-                    return Err(InheritanceError::InvalidAllocation);
-                }
+    /// Remove a token from the plan-asset whitelist (admin only).
+    /// Plans already holding the token can still withdraw and pay it out;
+    /// only new deposits and new plans are rejected.
+    pub fn remove_asset(env: Env, admin: Address, token: Address) -> Result<(), InheritanceError> {
+        Self::require_admin(&env, &admin)?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::WhitelistedAsset(token.clone()));
+        let list = Self::get_whitelisted_assets(env.clone());
+        let mut updated: Vec<Address> = Vec::new(&env);
+        for a in list.iter() {
+            if a != token {
+                updated.push_back(a);
             }
         }
-
-        let mut beneficiary = plan.beneficiaries.get(beneficiary_index).unwrap();
-        beneficiary.priority = priority;
-        plan.beneficiaries.set(beneficiary_index, beneficiary);
-        Self::store_plan(&env, plan_id, &plan);
+        env.storage()
+            .persistent()
+            .set(&DataKey::WhitelistedAssets, &updated);
 
         env.events().publish(
-            (symbol_short!("BENEFIC"), symbol_short!("PRIO")),
-            PrioritySetEvent {
-                plan_id,
-                beneficiary_index,
-                priority,
-            },
+            (symbol_short!("ASSET"), symbol_short!("DELIST")),
+            AssetDelistedEvent { token },
         );
-
         Ok(())
     }
 
-    pub fn get_beneficiary_priority(
-        env: Env,
-        plan_id: u64,
-        beneficiary_index: u32,
-    ) -> Result<u32, InheritanceError> {
-        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
-        if beneficiary_index >= plan.beneficiaries.len() {
+    /// Check if a token is whitelisted as a plan asset
+    pub fn is_asset_whitelisted(env: Env, token: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::WhitelistedAsset(token))
+    }
+
+    pub fn get_whitelisted_assets(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::WhitelistedAssets)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Record the token plans created before multi-asset support were
+    /// funded with (admin only). Those plans were USDC-only, so they all
+    /// share this one primary token.
+    pub fn set_legacy_plan_token(
+        env: Env,
+        admin: Address,
+        token: Address,
+    ) -> Result<(), InheritanceError> {
+        Self::require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::LegacyPlanToken, &token);
+        Ok(())
+    }
+
+    /// Primary token the plan was created with. Plans created before
+    /// multi-asset support fall back to the token set with
+    /// [`set_legacy_plan_token`](Self::set_legacy_plan_token), and have none
+    /// until it is set.
+    pub fn get_plan_token(env: Env, plan_id: u64) -> Option<Address> {
+        plan_index::get_persistent(&env, &DataKey::PlanToken(plan_id))
+            .or_else(|| env.storage().persistent().get(&DataKey::LegacyPlanToken))
+    }
+
+    /// Secondary assets held by the plan, with their balances and splits.
+    pub fn get_plan_assets(env: Env, plan_id: u64) -> Vec<PlanAsset> {
+        Self::get_basket(&env, plan_id)
+    }
+
+    /// Set a per-beneficiary split for one basket asset, overriding the
+    /// beneficiaries' default `allocation_bp` for that asset only.
+    ///
+    /// `allocations` must have one entry per beneficiary and total 10000 basis
+    /// points; pass an empty vector to fall back to the default allocations.
+    /// Custom splits are cleared whenever the beneficiary list changes.
+    ///
+    /// # Errors
+    /// - Unauthorized: If caller is not the plan owner
+    /// - InvalidAssetType: If the plan does not hold `token`
+    /// - AllocationPercentageMismatch: If the split is malformed
+    /// - AlreadyClaimed: If any beneficiary has already claimed
+    /// - InheritanceAlreadyTriggered: If inheritance has been triggered
+    pub fn set_asset_allocations(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        token: Address,
+        allocations: Vec<u32>,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+        if Self::get_trigger_info(&env, plan_id).is_some() {
+            return Err(InheritanceError::InheritanceAlreadyTriggered);
+        }
+        for b in plan.beneficiaries.iter() {
+            if b.is_claimed {
+                return Err(InheritanceError::AlreadyClaimed);
+            }
+        }
+
+        if !allocations.is_empty() {
+            if allocations.len() != plan.beneficiaries.len() {
+                return Err(InheritanceError::AllocationPercentageMismatch);
+            }
+            let mut total: u32 = 0;
+            for bp in allocations.iter() {
+                total = total
+                    .checked_add(bp)
+                    .ok_or(InheritanceError::AllocationPercentageMismatch)?;
+            }
+            if total != 10000 {
+                return Err(InheritanceError::AllocationPercentageMismatch);
+            }
+        }
+
+        let mut basket = Self::get_basket(&env, plan_id);
+        let i =
+            Self::find_basket_asset(&basket, &token).ok_or(InheritanceError::InvalidAssetType)?;
+        let mut asset = basket.get(i).unwrap();
+        asset.allocations = allocations;
+        basket.set(i, asset);
+        Self::store_basket(&env, plan_id, &basket);
+
+        env.events().publish(
+            (symbol_short!("ASSET"), symbol_short!("ALLOC")),
+            AssetAllocationsSetEvent { plan_id, token },
+        );
+        Ok(())
+    }
+
+    fn get_basket(env: &Env, plan_id: u64) -> Vec<PlanAsset> {
+        plan_index::get_persistent(env, &DataKey::PlanAssets(plan_id)).unwrap_or(Vec::new(env))
+    }
+
+    fn store_basket(env: &Env, plan_id: u64, basket: &Vec<PlanAsset>) {
+        plan_index::set_persistent(env, &DataKey::PlanAssets(plan_id), basket);
+    }
+
+    fn find_basket_asset(basket: &Vec<PlanAsset>, token: &Address) -> Option<u32> {
+        basket
+            .iter()
+            .position(|a| a.token == *token)
+            .map(|i| i as u32)
+    }
+
+    /// Index of `token` in the basket, appending a new entry if needed.
+    /// The token must currently be whitelisted.
+    fn basket_slot_for_deposit(
+        env: &Env,
+        basket: &mut Vec<PlanAsset>,
+        token: &Address,
+    ) -> Result<u32, InheritanceError> {
+        let symbol: Symbol = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WhitelistedAsset(token.clone()))
+            .ok_or(InheritanceError::InvalidAssetType)?;
+        if let Some(i) = Self::find_basket_asset(basket, token) {
+            return Ok(i);
+        }
+        if basket.len() >= MAX_PLAN_ASSETS {
+            return Err(InheritanceError::TooManyAssets);
+        }
+        basket.push_back(PlanAsset {
+            token: token.clone(),
+            symbol,
+            balance: 0,
+            allocations: Vec::new(env),
+        });
+        Ok(basket.len() - 1)
+    }
+
+    /// Whether `token` is the plan's primary asset. Never true for a plan
+    /// whose primary token is unknown.
+    fn is_primary_token(env: &Env, plan_id: u64, token: &Address) -> bool {
+        Self::get_plan_token(env.clone(), plan_id).as_ref() == Some(token)
+    }
+
+    fn is_whitelisted_symbol(env: &Env, symbol: &Symbol) -> bool {
+        for token in Self::get_whitelisted_assets(env.clone()).iter() {
+            let registered: Option<Symbol> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::WhitelistedAsset(token));
+            if registered.as_ref() == Some(symbol) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Ticker for a new plan's primary token. Until an asset whitelist is
+    /// configured every plan is treated as USDC, as before; afterwards the
+    /// token must be whitelisted.
+    fn resolve_primary_asset(env: &Env, token: &Address) -> Result<Symbol, InheritanceError> {
+        if let Some(symbol) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WhitelistedAsset(token.clone()))
+        {
+            return Ok(symbol);
+        }
+        if Self::get_whitelisted_assets(env.clone()).is_empty() {
+            return Ok(Symbol::new(env, "USDC"));
+        }
+        Err(InheritanceError::InvalidAssetType)
+    }
+
+    /// Drop custom per-asset splits; called whenever beneficiary indices change.
+    fn clear_asset_allocations(env: &Env, plan_id: u64) {
+        let mut basket = Self::get_basket(env, plan_id);
+        let mut changed = false;
+        for i in 0..basket.len() {
+            let mut asset = basket.get(i).unwrap();
+            if !asset.allocations.is_empty() {
+                asset.allocations = Vec::new(env);
+                basket.set(i, asset);
+                changed = true;
+            }
+        }
+        if changed {
+            Self::store_basket(env, plan_id, &basket);
+        }
+    }
+
+    /// Non-zero basket payouts owed to the beneficiary at `index`, computed
+    /// from the plan state before that beneficiary is marked as claimed.
+    fn basket_shares(
+        env: &Env,
+        plan_id: u64,
+        plan: &InheritancePlan,
+        index: u32,
+    ) -> Vec<(Address, u64)> {
+        let mut shares = Vec::new(env);
+        let beneficiary = plan.beneficiaries.get(index).unwrap();
+        for asset in Self::get_basket(env, plan_id).iter() {
+            let mut claimed_bp: u32 = 0;
+            for j in 0..plan.beneficiaries.len() {
+                let b = plan.beneficiaries.get(j).unwrap();
+                if j != index && b.is_claimed {
+                    claimed_bp =
+                        claimed_bp.saturating_add(asset.allocation_for(j, b.allocation_bp));
+                }
+            }
+            let bp = asset.allocation_for(index, beneficiary.allocation_bp);
+            let share = assets::share_of_remaining(asset.balance, bp, claimed_bp);
+            if share > 0 {
+                shares.push_back((asset.token.clone(), share));
+            }
+        }
+        shares
+    }
+
+    /// Transfer basket payouts to `recipient` and debit the basket.
+    fn pay_basket_shares(
+        env: &Env,
+        plan_id: u64,
+        beneficiary_index: u32,
+        recipient: &Address,
+        shares: &Vec<(Address, u64)>,
+    ) -> Result<(), InheritanceError> {
+        if shares.is_empty() {
+            return Ok(());
+        }
+        let mut basket = Self::get_basket(env, plan_id);
+        for (token, amount) in shares.iter() {
+            let i = Self::find_basket_asset(&basket, &token)
+                .ok_or(InheritanceError::InvalidAssetType)?;
+            let mut asset = basket.get(i).unwrap();
+            Self::transfer_from_vault(env, &token, recipient, amount)?;
+            asset.balance = asset.balance.saturating_sub(amount);
+            basket.set(i, asset);
+
+            env.events().publish(
+                (symbol_short!("ASSET"), symbol_short!("CLAIM")),
+                AssetClaimedEvent {
+                    plan_id,
+                    beneficiary_index,
+                    token,
+                    amount,
+                },
+            );
+        }
+        Self::store_basket(env, plan_id, &basket);
+        Ok(())
+    }
+
+    fn transfer_from_vault(
+        env: &Env,
+        token: &Address,
+        to: &Address,
+        amount: u64,
+    ) -> Result<(), InheritanceError> {
+        let args: Vec<Val> = vec![
+            env,
+            env.current_contract_address().into_val(env),
+            to.clone().into_val(env),
+            (amount as i128).into_val(env),
+        ];
+        let res =
+            env.try_invoke_contract::<(), InvokeError>(token, &symbol_short!("transfer"), args);
+        if res.is_err() {
+            return Err(InheritanceError::FeeTransferFailed);
+        }
+        Ok(())
+    }
+
+    pub fn set_beneficiary_priority(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        beneficiary_index: u32,
+        priority: u32,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        let mut plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+
+        if beneficiary_index >= plan.beneficiaries.len() {
+            return Err(InheritanceError::InvalidBeneficiaryIndex);
+        }
+
+        if priority == 0 {
+            return Err(InheritanceError::InvalidAllocation);
+        }
+
+        // Check for duplicate priorities
+        for i in 0..plan.beneficiaries.len() {
+            if i != beneficiary_index {
+                let b = plan.beneficiaries.get(i).unwrap();
+                if b.priority == priority {
+                    return Err(InheritanceError::InvalidAllocation);
+                }
+            }
+        }
+
+        let mut beneficiary = plan.beneficiaries.get(beneficiary_index).unwrap();
+        beneficiary.priority = priority;
+        plan.beneficiaries.set(beneficiary_index, beneficiary);
+        Self::store_plan(&env, plan_id, &plan);
+
+        env.events().publish(
+            (symbol_short!("BENEFIC"), symbol_short!("PRIO")),
+            PrioritySetEvent {
+                plan_id,
+                beneficiary_index,
+                priority,
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_beneficiary_priority(
+        env: Env,
+        plan_id: u64,
+        beneficiary_index: u32,
+    ) -> Result<u32, InheritanceError> {
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if beneficiary_index >= plan.beneficiaries.len() {
             return Err(InheritanceError::InvalidBeneficiaryIndex);
         }
         Ok(plan.beneficiaries.get(beneficiary_index).unwrap().priority)
@@ -2169,17 +2705,10 @@ impl InheritanceContract {
         entitlement.min(plan.total_amount)
     }
 
-    /// Check if a beneficiary has an active vesting schedule
-    fn has_active_vesting_schedule(_env: &Env, _plan_id: u64, _beneficiary_index: u32) -> bool {
-        // For MVP, we don't implement vesting schedules yet
-        // This is a placeholder that always returns false
-        false
-    }
-
     /// Get vesting exit settlement amount for a beneficiary
     fn get_vesting_exit_settlement(env: &Env, plan_id: u64, beneficiary_index: u32) -> u64 {
         let settle_key = DataKey::PlanMetadata(plan_id, 3000 + beneficiary_index);
-        env.storage().persistent().get(&settle_key).unwrap_or(0u64)
+        plan_index::get_persistent(env, &settle_key).unwrap_or(0u64)
     }
 
     pub fn get_claimable_by_priority(
@@ -2191,13 +2720,236 @@ impl InheritanceContract {
         if beneficiary_index >= plan.beneficiaries.len() {
             return Err(InheritanceError::InvalidBeneficiaryIndex);
         }
-        Ok(Self::calculate_waterfall_payout(
+        let waterfall = Self::calculate_waterfall_payout(&env, &plan, beneficiary_index);
+        if plan.distribution_method == DistributionMethod::LumpSum || waterfall == 0 {
+            return Ok(waterfall);
+        }
+        let schedule = Self::build_vesting_schedule(&env, plan_id, &plan, beneficiary_index);
+        Ok(schedule.claimable_amount)
+    }
+
+    // ─── Periodic Payouts ────────────────────────────────────────
+
+    /// Set how many tranches a Monthly/Quarterly/Yearly plan pays out over.
+    /// Only allowed before inheritance is triggered and before any tranche
+    /// has been claimed.
+    pub fn set_payout_tranches(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        tranche_count: u32,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        Self::check_not_paused(&env);
+
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+        if plan.distribution_method == DistributionMethod::LumpSum
+            || tranche_count == 0
+            || tranche_count > MAX_TRANCHES
+        {
+            return Err(InheritanceError::InvalidTrancheCount);
+        }
+        if Self::get_trigger_info(&env, plan_id).is_some() {
+            return Err(InheritanceError::InheritanceAlreadyTriggered);
+        }
+        for i in 0..plan.beneficiaries.len() {
+            if Self::get_tranche_progress(&env, plan_id, i).tranches_claimed > 0 {
+                return Err(InheritanceError::AlreadyClaimed);
+            }
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::TrancheCount(plan_id), &tranche_count);
+        Ok(())
+    }
+
+    /// Payout schedule for a beneficiary. Lump-sum plans report a single
+    /// tranche that is always unlocked.
+    pub fn get_vesting_schedule(
+        env: Env,
+        plan_id: u64,
+        beneficiary_index: u32,
+    ) -> Result<VestingSchedule, InheritanceError> {
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if beneficiary_index >= plan.beneficiaries.len() {
+            return Err(InheritanceError::InvalidBeneficiaryIndex);
+        }
+        Ok(Self::build_vesting_schedule(
             &env,
+            plan_id,
             &plan,
             beneficiary_index,
         ))
     }
 
+    fn get_tranche_count(env: &Env, plan_id: u64, method: &DistributionMethod) -> u32 {
+        if *method == DistributionMethod::LumpSum {
+            return 1;
+        }
+        env.storage()
+            .persistent()
+            .get(&DataKey::TrancheCount(plan_id))
+            .unwrap_or(vesting::default_tranche_count(method))
+    }
+
+    fn get_tranche_progress(env: &Env, plan_id: u64, beneficiary_index: u32) -> TrancheProgress {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TrancheProgress(plan_id, beneficiary_index))
+            .unwrap_or_default()
+    }
+
+    fn build_vesting_schedule(
+        env: &Env,
+        plan_id: u64,
+        plan: &InheritancePlan,
+        beneficiary_index: u32,
+    ) -> VestingSchedule {
+        let beneficiary = plan.beneficiaries.get(beneficiary_index).unwrap();
+        let period = vesting::period_seconds(&plan.distribution_method);
+        let tranche_count = Self::get_tranche_count(env, plan_id, &plan.distribution_method);
+        let progress = Self::get_tranche_progress(env, plan_id, beneficiary_index);
+        let triggered_at = Self::get_trigger_info(env, plan_id).map(|t| t.triggered_at);
+        let now = env.ledger().timestamp();
+
+        // Entitlements are computed against the plan balance before any
+        // tranche was paid out so they stay fixed as beneficiaries claim.
+        let mut paid_out = 0u64;
+        for i in 0..plan.beneficiaries.len() {
+            paid_out =
+                paid_out.saturating_add(Self::get_tranche_progress(env, plan_id, i).claimed_amount);
+        }
+        let total_entitlement = (plan.total_amount.saturating_add(paid_out) as u128)
+            .checked_mul(beneficiary.allocation_bp as u128)
+            .and_then(|v| v.checked_div(10000))
+            .unwrap_or(0) as u64;
+
+        let unlocked = if beneficiary.is_claimed {
+            tranche_count
+        } else {
+            vesting::tranches_unlocked(period, tranche_count, plan.created_at, triggered_at, now)
+        };
+        let claimable_amount = if beneficiary.is_claimed {
+            0
+        } else {
+            vesting::vested_amount(total_entitlement, tranche_count, unlocked)
+                .saturating_sub(progress.claimed_amount)
+        };
+
+        VestingSchedule {
+            plan_id,
+            beneficiary_index,
+            distribution_method: plan.distribution_method.clone(),
+            period_seconds: period,
+            tranche_count,
+            total_entitlement,
+            tranches_unlocked: unlocked,
+            tranches_claimed: progress.tranches_claimed,
+            claimed_amount: progress.claimed_amount,
+            claimable_amount,
+            next_unlock_at: vesting::next_unlock_at(
+                period,
+                tranche_count,
+                plan.created_at,
+                triggered_at,
+                now,
+            ),
+        }
+    }
+
+    /// Record a periodic payout and emit `TRANCHE/CLAIMED`. Returns true once
+    /// the final tranche has been paid.
+    fn record_tranche_claim(
+        env: &Env,
+        plan_id: u64,
+        schedule: &VestingSchedule,
+        amount: u64,
+    ) -> bool {
+        let progress = TrancheProgress {
+            claimed_amount: schedule.claimed_amount.saturating_add(amount),
+            tranches_claimed: schedule.tranches_unlocked,
+        };
+        env.storage().persistent().set(
+            &DataKey::TrancheProgress(plan_id, schedule.beneficiary_index),
+            &progress,
+        );
+
+        env.events().publish(
+            (symbol_short!("TRANCHE"), symbol_short!("CLAIMED")),
+            TrancheClaimedEvent {
+                plan_id,
+                beneficiary_index: schedule.beneficiary_index,
+                amount,
+                tranches_claimed: progress.tranches_claimed,
+                tranche_count: schedule.tranche_count,
+                claimed_to_date: progress.claimed_amount,
+            },
+        );
+
+        progress.tranches_claimed >= schedule.tranche_count
+    }
+
+    /// Index of the beneficiary `claimer` is claiming as. A beneficiary bound
+    /// to a wallet can only be claimed by that wallet, even with a valid
+    /// claim code.
+    fn find_claiming_beneficiary(
+        env: &Env,
+        plan_id: u64,
+        plan: &InheritancePlan,
+        claimer: &Address,
+        proof: &ClaimProof,
+    ) -> Result<u32, InheritanceError> {
+        let count = plan.beneficiaries.len().min(MAX_BENEFICIARIES);
+        match proof {
+            ClaimProof::Wallet => (0..count)
+                .find(|&i| plan.beneficiaries.get(i).unwrap().wallet.as_ref() == Some(claimer))
+                .ok_or(InheritanceError::BeneficiaryNotFound),
+            ClaimProof::ClaimCode { email, claim_code } => {
+                // Find beneficiary by email, then validate claim_code against salted hash.
+                let hashed_email = Self::hash_string(env, email.clone());
+                for i in 0..count {
+                    let b = plan.beneficiaries.get(i).unwrap();
+                    if b.hashed_email != hashed_email {
+                        continue;
+                    }
+
+                    let salt: BytesN<32> = env
+                        .storage()
+                        .persistent()
+                        .get(&DataKey::ClaimSalt(plan_id, i))
+                        .unwrap_or(BytesN::<32>::from_array(env, &[0u8; 32]));
+                    let hashed_claim_code =
+                        Self::hash_claim_code_with_salt(env, *claim_code, &salt)?;
+                    if b.hashed_claim_code == hashed_claim_code {
+                        if b.wallet.as_ref().is_some_and(|w| w != claimer) {
+                            return Err(InheritanceError::Unauthorized);
+                        }
+                        return Ok(i);
+                    }
+                }
+                Err(InheritanceError::BeneficiaryNotFound)
+            }
+        }
+    }
+
+    fn ensure_wallet_unbound(
+        plan: &InheritancePlan,
+        wallet: &Address,
+    ) -> Result<(), InheritanceError> {
+        if plan
+            .beneficiaries
+            .iter()
+            .any(|b| b.wallet.as_ref() == Some(wallet))
+        {
+            return Err(InheritanceError::WalletAlreadyBound);
+        }
+        Ok(())
+    }
+
     fn is_claim_time_valid(env: &Env, plan: &InheritancePlan) -> bool {
         let now = env.ledger().timestamp();
         let elapsed = now - plan.created_at;
@@ -2216,6 +2968,34 @@ impl InheritanceContract {
         claimer: Address,
         email: String,
         claim_code: u32,
+    ) -> Result<(), InheritanceError> {
+        Self::claim_as(
+            env,
+            plan_id,
+            claimer,
+            ClaimProof::ClaimCode { email, claim_code },
+        )
+    }
+
+    /// Claim as a wallet-bound beneficiary. The claimer's signature proves
+    /// their identity, so no email or claim code is needed.
+    ///
+    /// # Errors
+    /// - BeneficiaryNotFound: If no beneficiary of the plan is bound to `claimer`
+    /// - Otherwise the same as `claim_inheritance_plan`
+    pub fn claim_with_wallet(
+        env: Env,
+        plan_id: u64,
+        claimer: Address,
+    ) -> Result<(), InheritanceError> {
+        Self::claim_as(env, plan_id, claimer, ClaimProof::Wallet)
+    }
+
+    fn claim_as(
+        env: Env,
+        plan_id: u64,
+        claimer: Address,
+        proof: ClaimProof,
     ) -> Result<(), InheritanceError> {
         // Require claimer authorization
         claimer.require_auth();
@@ -2250,10 +3030,13 @@ impl InheritanceContract {
         }
 
         // Track claim attempts to reduce brute-force claim-code guessing.
-        Self::check_and_record_claim_attempt(&env, plan_id, &claimer)?;
+        if let ClaimProof::ClaimCode { .. } = proof {
+            Self::check_and_record_claim_attempt(&env, plan_id, &claimer)?;
+        }
 
         // Bring trigger state up to date before checking claimability.
         let _ = Self::auto_trigger_check(env.clone(), plan_id);
+        Self::settle_contest_window(&env, plan_id)?;
 
         // When inheritance is triggered, bypass the time-based check so
         // that inheritance execution cannot be blocked.
@@ -2262,8 +3045,9 @@ impl InheritanceContract {
             return Err(InheritanceError::ClaimNotAllowedYet);
         }
 
-        // Hash email
-        let hashed_email = Self::hash_string(&env, email.clone());
+        let index = Self::find_claiming_beneficiary(&env, plan_id, &plan, &claimer, &proof)?;
+        let hashed_email = plan.beneficiaries.get(index).unwrap().hashed_email;
+        let count = plan.beneficiaries.len().min(MAX_BENEFICIARIES);
 
         // Build claim key including plan ID
         let claim_key = {
@@ -2278,43 +3062,13 @@ impl InheritanceContract {
             return Err(InheritanceError::AlreadyClaimed);
         }
 
-        // Find beneficiary by email, then validate claim_code against salted hash.
-        let mut beneficiary_index: Option<u32> = None;
-        let count = plan.beneficiaries.len().min(MAX_BENEFICIARIES);
-        for i in 0..count {
-            let b = plan.beneficiaries.get(i).unwrap();
-            if b.hashed_email != hashed_email {
-                continue;
-            }
-
-            let salt: BytesN<32> = env
-                .storage()
-                .persistent()
-                .get(&DataKey::ClaimSalt(plan_id, i))
-                .unwrap_or(BytesN::<32>::from_array(&env, &[0u8; 32]));
-            let hashed_claim_code = Self::hash_claim_code_with_salt(&env, claim_code, &salt)?;
-            if b.hashed_claim_code == hashed_claim_code {
-                beneficiary_index = Some(i);
-                break;
-            }
-        }
-
-        let index = beneficiary_index.ok_or(InheritanceError::BeneficiaryNotFound)?;
-
         // Reject claim if the beneficiary is frozen
-        if env
-            .storage()
-            .persistent()
-            .get::<DataKey, bool>(&DataKey::PlanMetadata(plan_id, index))
+        if plan_index::get_persistent::<bool>(&env, &DataKey::PlanMetadata(plan_id, index))
             .unwrap_or(false)
         {
             return Err(InheritanceError::PlanNotActive);
         }
 
-        if Self::has_active_vesting_schedule(&env, plan_id, index) {
-            return Err(InheritanceError::PlanNotActive);
-        }
-
         // Waterfall ordering: if enabled, every strictly higher-priority
         // beneficiary (non-zero priority) must have claimed first.
         if plan.waterfall_enabled {
@@ -2328,7 +3082,16 @@ impl InheritanceContract {
         }
 
         // --- Payout Logic ---
-        let mut payout = Self::calculate_waterfall_payout(&env, &plan, index);
+        // Periodic plans pay out whatever tranches have unlocked so far.
+        let schedule = if plan.distribution_method == DistributionMethod::LumpSum {
+            None
+        } else {
+            Some(Self::build_vesting_schedule(&env, plan_id, &plan, index))
+        };
+        let mut payout = match &schedule {
+            Some(s) => s.claimable_amount,
+            None => Self::calculate_waterfall_payout(&env, &plan, index),
+        };
 
         let exit_settlement = Self::get_vesting_exit_settlement(&env, plan_id, index);
         if exit_settlement > 0 {
@@ -2364,69 +3127,254 @@ impl InheritanceContract {
             return Err(InheritanceError::InsufficientLiquidity);
         }
 
-        if payout == 0 {
+        // Secondary basket assets are paid out on-chain to the claimer once
+        // their primary entitlement is fully claimed.
+        let basket_shares = Self::basket_shares(&env, plan_id, &plan, index);
+
+        if payout == 0 && (schedule.is_some() || basket_shares.is_empty()) {
             return Err(InheritanceError::NothingToClaim);
         }
 
-        // Transfer funds to beneficiary
-        // Note: For fiat (bank_account), this would typically emit an event for off-chain processing.
-        // Here, we'll try to transfer USDC if an address can be derived, or just emit an event.
-        // As a simplification, we'll emit the event first.
+        // The primary payout is not transferred here: it is settled in fiat to
+        // the beneficiary's `bank_account_ref`, off-chain, from the CLAIM
+        // SUCCESS event below. Only basket assets, which have no fiat rail,
+        // are transferred to the claimer.
 
         // Update plan balances and mark beneficiary as claimed when fully finalized
         let mut updated_plan = plan.clone();
 
         let exit_remaining_after = exit_settlement.saturating_sub(payout);
-        let exit_finalized = exit_settlement == 0 || exit_remaining_after == 0;
+        let tranches_finalized = match &schedule {
+            Some(s) => Self::record_tranche_claim(&env, plan_id, s, payout),
+            None => true,
+        };
+        let exit_finalized =
+            tranches_finalized && (exit_settlement == 0 || exit_remaining_after == 0);
+
+        // Update the specific beneficiary in the vector
+        let mut b = updated_plan.beneficiaries.get(index).unwrap();
+        if exit_finalized {
+            b.is_claimed = true;
+        }
+        updated_plan.beneficiaries.set(index, b);
+
+        updated_plan.total_amount = updated_plan.total_amount.saturating_sub(payout);
+        Self::store_plan(&env, plan_id, &updated_plan);
+
+        if exit_settlement > 0 {
+            let settle_key = DataKey::PlanMetadata(plan_id, 3000 + index);
+            if exit_remaining_after == 0 {
+                env.storage().persistent().remove(&settle_key);
+            } else {
+                plan_index::set_persistent(&env, &settle_key, &exit_remaining_after);
+            }
+        }
+
+        if exit_finalized {
+            let claim = ClaimRecord {
+                plan_id,
+                beneficiary_index: index,
+                claimed_at: env.ledger().timestamp(),
+            };
+            plan_index::set_persistent(&env, &claim_key, &claim);
+            Self::add_plan_to_claimed(&env, plan.owner.clone(), plan_id);
+            Self::pay_basket_shares(&env, plan_id, index, &claimer, &basket_shares)?;
+        }
+
+        // Grant Beneficiary role to the claimer as an on-chain record of a successful claim
+        access_control::assign_role(&env, &claimer, Role::Beneficiary);
+
+        // Emit claim event
+        env.events().publish(
+            (symbol_short!("CLAIM"), symbol_short!("SUCCESS")),
+            (plan_id, hashed_email, payout),
+        );
+
+        log!(
+            &env,
+            "Inheritance claimed for plan {} by beneficiary {}",
+            plan_id,
+            index
+        );
+
+        Self::exit_guard(&env);
+        Ok(())
+    }
+
+    /// Bind a beneficiary to a wallet. From then on the beneficiary can only
+    /// claim from that wallet, either with `claim_with_wallet` or with their
+    /// claim code signed by the wallet.
+    ///
+    /// # Errors
+    /// - Unauthorized: If caller is not the plan owner
+    /// - InvalidBeneficiaryIndex: If `beneficiary_index` is out of range
+    /// - InheritanceAlreadyTriggered: If the plan has been triggered
+    /// - AlreadyClaimed: If the beneficiary has already claimed
+    /// - WalletAlreadyBound: If the wallet is bound to a beneficiary of this plan
+    pub fn bind_beneficiary_wallet(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        beneficiary_index: u32,
+        wallet: Address,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        Self::check_not_paused(&env);
+
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+        if Self::get_trigger_info(&env, plan_id).is_some() {
+            return Err(InheritanceError::InheritanceAlreadyTriggered);
+        }
+        Self::set_beneficiary_wallet(&env, plan_id, plan, beneficiary_index, wallet, owner)
+    }
+
+    /// Bind the caller's wallet to the beneficiary identified by `email` and
+    /// `claim_code`. This is how beneficiaries of existing plans move off
+    /// claim codes. Attempts are limited per plan and beneficiary email,
+    /// whichever wallet makes them.
+    ///
+    /// Returns the beneficiary index, or `None` if the email and claim code
+    /// do not match. A mismatch is not an error so that the attempt it used
+    /// is kept; a failed call would roll the count back.
+    ///
+    /// # Errors
+    /// - ClaimNotAllowedYet: If the beneficiary's attempts are exhausted
+    /// - AlreadyClaimed: If the beneficiary has already claimed
+    /// - WalletAlreadyBound: If the beneficiary already has a wallet, or the
+    ///   wallet is bound to another beneficiary of this plan
+    pub fn bind_wallet_with_claim_code(
+        env: Env,
+        wallet: Address,
+        plan_id: u64,
+        email: String,
+        claim_code: u32,
+    ) -> Result<Option<u32>, InheritanceError> {
+        wallet.require_auth();
+        Self::check_not_paused(&env);
+
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        let hashed_email = Self::hash_string(&env, email.clone());
+        Self::check_and_record_attempt(&env, DataKey::BindAttempts(plan_id, hashed_email))?;
+        let index = match Self::find_claiming_beneficiary(
+            &env,
+            plan_id,
+            &plan,
+            &wallet,
+            &ClaimProof::ClaimCode { email, claim_code },
+        ) {
+            Ok(index) => index,
+            Err(InheritanceError::BeneficiaryNotFound) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        if plan.beneficiaries.get(index).unwrap().wallet.is_some() {
+            return Err(InheritanceError::WalletAlreadyBound);
+        }
+        Self::set_beneficiary_wallet(&env, plan_id, plan, index, wallet.clone(), wallet)?;
+        Ok(Some(index))
+    }
 
-        // Update the specific beneficiary in the vector
-        let mut b = updated_plan.beneficiaries.get(index).unwrap();
-        if exit_finalized {
-            b.is_claimed = true;
+    /// Record the backend-issued `bank_account_ref` of a beneficiary migrated
+    /// from the pre-v2 layout, whose reference `migrate` left empty.
+    pub fn assign_legacy_bank_account_ref(
+        env: Env,
+        admin: Address,
+        plan_id: u64,
+        beneficiary_index: u32,
+        bank_account_ref: Bytes,
+    ) -> Result<(), InheritanceError> {
+        Self::require_admin(&env, &admin)?;
+        if bank_account_ref.is_empty() || bank_account_ref.len() > MAX_BANK_ACCOUNT_REF_LEN {
+            return Err(InheritanceError::InvalidBeneficiaryData);
         }
-        updated_plan.beneficiaries.set(index, b);
+        let mut plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        let mut b = plan
+            .beneficiaries
+            .get(beneficiary_index)
+            .ok_or(InheritanceError::InvalidBeneficiaryIndex)?;
+        if !b.bank_account_ref.is_empty() {
+            return Err(InheritanceError::BankAccountRefAlreadySet);
+        }
+        b.bank_account_ref = bank_account_ref;
+        plan.beneficiaries.set(beneficiary_index, b);
+        Self::store_plan(&env, plan_id, &plan);
+        Ok(())
+    }
 
-        updated_plan.total_amount = updated_plan.total_amount.saturating_sub(payout);
-        Self::store_plan(&env, plan_id, &updated_plan);
+    /// Remove a beneficiary's wallet binding, returning them to claim-code
+    /// claims.
+    pub fn unbind_beneficiary_wallet(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        beneficiary_index: u32,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        Self::check_not_paused(&env);
 
-        if exit_settlement > 0 {
-            let settle_key = DataKey::PlanMetadata(plan_id, 3000 + index);
-            if exit_remaining_after == 0 {
-                env.storage().persistent().remove(&settle_key);
-            } else {
-                env.storage()
-                    .persistent()
-                    .set(&settle_key, &exit_remaining_after);
-            }
+        let mut plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
         }
-
-        if exit_finalized {
-            let claim = ClaimRecord {
-                plan_id,
-                beneficiary_index: index,
-                claimed_at: env.ledger().timestamp(),
-            };
-            env.storage().persistent().set(&claim_key, &claim);
-            Self::add_plan_to_claimed(&env, plan.owner.clone(), plan_id);
+        if Self::get_trigger_info(&env, plan_id).is_some() {
+            return Err(InheritanceError::InheritanceAlreadyTriggered);
         }
+        let mut b = plan
+            .beneficiaries
+            .get(beneficiary_index)
+            .ok_or(InheritanceError::InvalidBeneficiaryIndex)?;
+        let wallet = b
+            .wallet
+            .take()
+            .ok_or(InheritanceError::BeneficiaryNotFound)?;
+        plan.beneficiaries.set(beneficiary_index, b);
+        Self::store_plan(&env, plan_id, &plan);
 
-        // Grant Beneficiary role to the claimer as an on-chain record of a successful claim
-        access_control::assign_role(&env, &claimer, Role::Beneficiary);
-
-        // Emit claim event
         env.events().publish(
-            (symbol_short!("CLAIM"), symbol_short!("SUCCESS")),
-            (plan_id, hashed_email, payout),
+            (symbol_short!("BENEFIC"), symbol_short!("UNBIND")),
+            BeneficiaryWalletUnboundEvent {
+                plan_id,
+                beneficiary_index,
+                wallet,
+            },
         );
+        Ok(())
+    }
 
-        log!(
-            &env,
-            "Inheritance claimed for plan {} by {}",
-            plan_id,
-            email
-        );
+    fn set_beneficiary_wallet(
+        env: &Env,
+        plan_id: u64,
+        mut plan: InheritancePlan,
+        beneficiary_index: u32,
+        wallet: Address,
+        bound_by: Address,
+    ) -> Result<(), InheritanceError> {
+        let mut b = plan
+            .beneficiaries
+            .get(beneficiary_index)
+            .ok_or(InheritanceError::InvalidBeneficiaryIndex)?;
+        if b.is_claimed {
+            return Err(InheritanceError::AlreadyClaimed);
+        }
+        if b.wallet.as_ref() == Some(&wallet) {
+            return Ok(());
+        }
+        Self::ensure_wallet_unbound(&plan, &wallet)?;
+        b.wallet = Some(wallet.clone());
+        plan.beneficiaries.set(beneficiary_index, b);
+        Self::store_plan(env, plan_id, &plan);
 
-        Self::exit_guard(&env);
+        env.events().publish(
+            (symbol_short!("BENEFIC"), symbol_short!("BIND")),
+            BeneficiaryWalletBoundEvent {
+                plan_id,
+                beneficiary_index,
+                wallet,
+                bound_by,
+            },
+        );
         Ok(())
     }
 
@@ -2435,7 +3383,7 @@ impl InheritanceContract {
         user.require_auth();
 
         let key = DataKey::Kyc(user.clone());
-        let mut status = env.storage().persistent().get(&key).unwrap_or(KycStatus {
+        let mut status = plan_index::get_persistent(&env, &key).unwrap_or(KycStatus {
             submitted: false,
             approved: false,
             rejected: false,
@@ -2450,7 +3398,7 @@ impl InheritanceContract {
 
         status.submitted = true;
         status.submitted_at = env.ledger().timestamp();
-        env.storage().persistent().set(&key, &status);
+        plan_index::set_persistent(&env, &key, &status);
 
         Ok(())
     }
@@ -2460,11 +3408,8 @@ impl InheritanceContract {
         Self::require_admin(&env, &admin)?;
 
         let key = DataKey::Kyc(user.clone());
-        let mut status: KycStatus = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .ok_or(InheritanceError::KycNotSubmitted)?;
+        let mut status: KycStatus =
+            plan_index::get_persistent(&env, &key).ok_or(InheritanceError::KycNotSubmitted)?;
 
         if !status.submitted {
             return Err(InheritanceError::KycNotSubmitted);
@@ -2476,7 +3421,7 @@ impl InheritanceContract {
 
         status.approved = true;
         status.approved_at = env.ledger().timestamp();
-        env.storage().persistent().set(&key, &status);
+        plan_index::set_persistent(&env, &key, &status);
 
         env.events().publish(
             (symbol_short!("KYC"), symbol_short!("APPROV")),
@@ -2504,11 +3449,8 @@ impl InheritanceContract {
         Self::require_admin(&env, &admin)?;
 
         let key = DataKey::Kyc(user.clone());
-        let mut status: KycStatus = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .ok_or(InheritanceError::KycNotSubmitted)?;
+        let mut status: KycStatus =
+            plan_index::get_persistent(&env, &key).ok_or(InheritanceError::KycNotSubmitted)?;
 
         if !status.submitted {
             return Err(InheritanceError::KycNotSubmitted);
@@ -2520,7 +3462,7 @@ impl InheritanceContract {
 
         status.rejected = true;
         status.rejected_at = env.ledger().timestamp();
-        env.storage().persistent().set(&key, &status);
+        plan_index::set_persistent(&env, &key, &status);
 
         env.events().publish(
             (symbol_short!("KYC"), symbol_short!("REJECT")),
@@ -2639,7 +3581,7 @@ impl InheritanceContract {
         };
 
         // Store the emergency access record
-        env.storage().persistent().set(&key, &emergency_access);
+        plan_index::set_persistent(&env, &key, &emergency_access);
 
         // Emit event
         env.events().publish(
@@ -2706,11 +3648,8 @@ impl InheritanceContract {
         }
 
         let key = DataKey::EmergencyContacts(plan_id);
-        let mut contacts: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
+        let mut contacts: Vec<Address> =
+            plan_index::get_persistent(&env, &key).unwrap_or(Vec::new(&env));
 
         // Check for duplicates
         for c in contacts.iter() {
@@ -2725,7 +3664,7 @@ impl InheritanceContract {
         }
 
         contacts.push_back(contact.clone());
-        env.storage().persistent().set(&key, &contacts);
+        plan_index::set_persistent(&env, &key, &contacts);
 
         env.events().publish(
             (symbol_short!("EMERG"), symbol_short!("CON_ADD")),
@@ -2755,11 +3694,8 @@ impl InheritanceContract {
         }
 
         let key = DataKey::EmergencyContacts(plan_id);
-        let mut contacts: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
+        let mut contacts: Vec<Address> =
+            plan_index::get_persistent(&env, &key).unwrap_or(Vec::new(&env));
 
         // Find and remove the contact
         let mut found_index: Option<u32> = None;
@@ -2780,7 +3716,7 @@ impl InheritanceContract {
         }
         contacts.pop_back();
 
-        env.storage().persistent().set(&key, &contacts);
+        plan_index::set_persistent(&env, &key, &contacts);
 
         env.events().publish(
             (symbol_short!("EMERG"), symbol_short!("CON_REM")),
@@ -2798,10 +3734,7 @@ impl InheritanceContract {
     /// Get all emergency contacts for a vault/plan.
     pub fn get_emergency_contacts(env: Env, plan_id: u64) -> Vec<Address> {
         let key = DataKey::EmergencyContacts(plan_id);
-        env.storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env))
+        plan_index::get_persistent(&env, &key).unwrap_or(Vec::new(&env))
     }
 
     pub fn approve_emergency_access(
@@ -2846,11 +3779,8 @@ impl InheritanceContract {
         }
 
         let key_approvals = DataKey::EmergencyApprovals(plan_id, trusted_contact.clone());
-        let mut approvals: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&key_approvals)
-            .unwrap_or(Vec::new(&env));
+        let mut approvals: Vec<Address> =
+            plan_index::get_persistent(&env, &key_approvals).unwrap_or(Vec::new(&env));
 
         let mut already_approved = false;
         for a in approvals.iter() {
@@ -2864,7 +3794,7 @@ impl InheritanceContract {
         }
 
         approvals.push_back(guardian.clone());
-        env.storage().persistent().set(&key_approvals, &approvals);
+        plan_index::set_persistent(&env, &key_approvals, &approvals);
 
         env.events().publish(
             (symbol_short!("EMERG"), symbol_short!("APPROVE")),
@@ -2883,9 +3813,7 @@ impl InheritanceContract {
                 trusted_contact: trusted_contact.clone(),
                 activated_at: now,
             };
-            env.storage()
-                .persistent()
-                .set(&key_access, &emergency_access);
+            plan_index::set_persistent(&env, &key_access, &emergency_access);
 
             env.events().publish(
                 (symbol_short!("EMERG"), symbol_short!("ACTIV")),
@@ -2916,7 +3844,7 @@ impl InheritanceContract {
     pub fn get_emergency_access(env: Env, plan_id: u64) -> Option<EmergencyAccessRecord> {
         if Self::check_and_expire_emergency_access(&env, plan_id) {
             let key = DataKey::EmergencyAccess(plan_id);
-            env.storage().persistent().get(&key)
+            plan_index::get_persistent(&env, &key)
         } else {
             None
         }
@@ -3021,202 +3949,514 @@ impl InheritanceContract {
             return Err(InheritanceError::PlanNotActive);
         }
 
-        Ok(plan)
+        Ok(plan)
+    }
+
+    /// Retrieve the user's deactivated plans
+    pub fn get_user_deactivated_plans(env: Env, user: Address) -> Vec<InheritancePlan> {
+        let user_plans = Self::get_user_plans(env.clone(), user);
+
+        let mut deactivated_plans = Vec::new(&env);
+        for plan in user_plans.iter() {
+            if !plan.is_active {
+                deactivated_plans.push_back(plan);
+            }
+        }
+
+        deactivated_plans
+    }
+
+    /// Retrieve all deactivated plans (Admin only)
+    pub fn get_all_deactivated_plans(
+        env: Env,
+        admin: Address,
+    ) -> Result<Vec<InheritancePlan>, InheritanceError> {
+        admin.require_auth();
+
+        // Verify admin
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(InheritanceError::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(InheritanceError::Unauthorized);
+        }
+
+        Ok(Self::load_all_plans(&env, |cursor| {
+            plan_index::page(&env, &PlanIndex::Deactivated, cursor, MAX_PAGE_SIZE)
+        }))
+    }
+
+    /// Retrieve a specific claimed plan belonging to the authenticated user
+    pub fn get_claimed_plan(
+        env: Env,
+        user: Address,
+        plan_id: u64,
+    ) -> Result<InheritancePlan, InheritanceError> {
+        user.require_auth();
+
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+
+        if plan.owner != user {
+            return Err(InheritanceError::Unauthorized);
+        }
+
+        if !plan_index::contains(&env, &PlanIndex::OwnerClaimed(user), plan_id) {
+            return Err(InheritanceError::PlanNotFound);
+        }
+
+        Ok(plan)
+    }
+
+    /// Retrieve all claimed plans of the authenticated user;
+    /// `get_user_claimed_plans_page` pages through them
+    pub fn get_user_claimed_plans(env: Env, user: Address) -> Vec<InheritancePlan> {
+        user.require_auth();
+        let index = PlanIndex::OwnerClaimed(user);
+        Self::load_all_plans(&env, |cursor| {
+            plan_index::page(&env, &index, cursor, MAX_PAGE_SIZE)
+        })
+    }
+
+    /// Retrieve all claimed plans across all users; accessible only by
+    /// administrators
+    pub fn get_all_claimed_plans(
+        env: Env,
+        admin: Address,
+    ) -> Result<Vec<InheritancePlan>, InheritanceError> {
+        Self::get_all_indexed_plans(&env, &admin, PlanIndex::Claimed)
+    }
+
+    // ───────────────────────────────────────────
+    // Loan Recall on Inheritance Trigger
+    // ───────────────────────────────────────────
+
+    fn get_trigger_info(env: &Env, plan_id: u64) -> Option<InheritanceTriggerInfo> {
+        let key = DataKey::InheritanceTrigger(plan_id);
+        plan_index::get_persistent(env, &key)
+    }
+
+    fn set_trigger_info(env: &Env, plan_id: u64, info: &InheritanceTriggerInfo) {
+        let key = DataKey::InheritanceTrigger(plan_id);
+        plan_index::set_persistent(env, &key, info);
+    }
+
+    fn get_trigger_config(env: &Env, plan_id: u64) -> Option<TriggerConfig> {
+        plan_index::get_persistent(env, &DataKey::PlanMetadata(plan_id, 4000))
+    }
+
+    fn save_trigger_config(env: &Env, plan_id: u64, config: &TriggerConfig) {
+        plan_index::set_persistent(env, &DataKey::PlanMetadata(plan_id, 4000), config);
+    }
+
+    fn get_grace_period(env: &Env, plan_id: u64) -> u64 {
+        plan_index::get_persistent(env, &DataKey::PlanMetadata(plan_id, 4001)).unwrap_or(0)
+    }
+
+    fn get_trigger_expr(env: &Env, plan_id: u64) -> Option<TriggerExpression> {
+        plan_index::get_persistent(env, &DataKey::PlanMetadata(plan_id, 4002))
+    }
+
+    fn condition_met(
+        env: &Env,
+        plan_id: u64,
+        config: &TriggerConfig,
+        condition: &TriggerConditionType,
+        now: u64,
+    ) -> bool {
+        match condition {
+            TriggerConditionType::Time => config.trigger_date > 0 && now >= config.trigger_date,
+            TriggerConditionType::Inactivity => {
+                config.inactivity_period > 0
+                    && config.last_activity > 0
+                    && now
+                        >= config
+                            .last_activity
+                            .saturating_add(config.inactivity_period)
+                            .saturating_add(Self::get_grace_period(env, plan_id))
+            }
+            TriggerConditionType::Oracle => {
+                config.oracle_triggered || Self::death_attested(env, plan_id, now)
+            }
+            TriggerConditionType::Health => config.health_triggered,
+            TriggerConditionType::Manual => false,
+        }
+    }
+
+    /// Whether the plan's trigger conditions are met. If the owner has set a
+    /// trigger expression it decides; otherwise any single configured
+    /// condition suffices.
+    pub fn check_trigger_conditions(env: Env, plan_id: u64) -> bool {
+        let config = Self::get_trigger_config(&env, plan_id);
+        let now = env.ledger().timestamp();
+
+        if let Some(expr) = Self::get_trigger_expr(&env, plan_id) {
+            let approvals = Self::get_trigger_approvals(env.clone(), plan_id).len();
+            return expr.evaluate(
+                |condition| {
+                    config
+                        .as_ref()
+                        .is_some_and(|c| Self::condition_met(&env, plan_id, c, condition, now))
+                },
+                approvals,
+            );
+        }
+
+        match config {
+            Some(config) => config
+                .conditions
+                .iter()
+                .any(|c| Self::condition_met(&env, plan_id, &config, &c, now)),
+            None => false,
+        }
+    }
+
+    /// Replace the flat any-condition semantics with a boolean expression,
+    /// e.g. `(Inactivity AND Oracle) OR (Time AND 2 guardian approvals)`.
+    pub fn set_trigger_expression(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        expression: TriggerExpression,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+        if Self::get_trigger_info(&env, plan_id).is_some() {
+            return Err(InheritanceError::InheritanceAlreadyTriggered);
+        }
+        expression.validate()?;
+
+        plan_index::set_persistent(&env, &DataKey::PlanMetadata(plan_id, 4002), &expression);
+        env.events().publish(
+            (symbol_short!("TRIG"), symbol_short!("EXPRSET")),
+            TriggerExpressionSetEvent {
+                plan_id,
+                node_count: expression.nodes.len(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Remove the plan's trigger expression, restoring any-condition
+    /// semantics.
+    pub fn clear_trigger_expression(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+        if Self::get_trigger_info(&env, plan_id).is_some() {
+            return Err(InheritanceError::InheritanceAlreadyTriggered);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PlanMetadata(plan_id, 4002));
+        Ok(())
+    }
+
+    pub fn get_trigger_expression(env: Env, plan_id: u64) -> Option<TriggerExpression> {
+        Self::get_trigger_expr(&env, plan_id)
+    }
+
+    /// Record a guardian's approval to trigger the plan, counted by
+    /// `GuardianApprovals` nodes of the trigger expression.
+    pub fn approve_trigger(
+        env: Env,
+        guardian: Address,
+        plan_id: u64,
+    ) -> Result<u32, InheritanceError> {
+        guardian.require_auth();
+        Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if Self::get_trigger_info(&env, plan_id).is_some() {
+            return Err(InheritanceError::InheritanceAlreadyTriggered);
+        }
+        let config: GuardianConfig = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Guardians(plan_id))
+            .ok_or(InheritanceError::BeneficiaryNotFound)?;
+        if !config.guardians.contains(&guardian) {
+            return Err(InheritanceError::Unauthorized);
+        }
+
+        let mut approvals = Self::get_trigger_approvals(env.clone(), plan_id);
+        if approvals.contains(&guardian) {
+            return Err(InheritanceError::AlreadyClaimed);
+        }
+        approvals.push_back(guardian.clone());
+        plan_index::set_persistent(&env, &DataKey::PlanMetadata(plan_id, 4003), &approvals);
+
+        env.events().publish(
+            (symbol_short!("TRIG"), symbol_short!("APPROVE")),
+            TriggerApprovedEvent {
+                plan_id,
+                guardian,
+                approvals: approvals.len(),
+            },
+        );
+        Ok(approvals.len())
     }
 
-    /// Retrieve all deactivated plans for a user
-    pub fn get_user_deactivated_plans(env: Env, user: Address) -> Vec<InheritancePlan> {
-        user.require_auth();
+    pub fn get_trigger_approvals(env: Env, plan_id: u64) -> Vec<Address> {
+        plan_index::get_persistent(&env, &DataKey::PlanMetadata(plan_id, 4003))
+            .unwrap_or(Vec::new(&env))
+    }
 
-        let key = DataKey::UserPlans(user.clone());
-        let user_plan_ids: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
+    // ───────────────────────────────────────────
+    // Death-Certificate Attestation
+    // ───────────────────────────────────────────
 
-        let mut deactivated_plans = Vec::new(&env);
+    fn get_death_attestation_config(env: &Env, plan_id: u64) -> Option<DeathAttestationConfig> {
+        plan_index::get_persistent(env, &DataKey::PlanMetadata(plan_id, 8001))
+    }
 
-        for plan_id in user_plan_ids.iter() {
-            if let Some(plan) = Self::get_plan(&env, plan_id) {
-                if !plan.is_active {
-                    deactivated_plans.push_back(plan);
-                }
-            }
-        }
+    fn get_death_attestation_round(env: &Env, plan_id: u64) -> Option<DeathAttestationRound> {
+        plan_index::get_persistent(env, &DataKey::PlanMetadata(plan_id, 8002))
+    }
 
-        deactivated_plans
+    /// Whether an attestation round reached its threshold and survived the
+    /// challenge window.
+    fn death_attested(env: &Env, plan_id: u64, now: u64) -> bool {
+        Self::get_death_attestation_round(env, plan_id)
+            .is_some_and(|r| r.status(now) == DeathAttestationStatus::Finalized)
     }
 
-    /// Retrieve all deactivated plans (Admin only)
-    pub fn get_all_deactivated_plans(
+    /// Register (or re-classify) an attestor allowed to attest to deaths.
+    pub fn register_attestor(
         env: Env,
         admin: Address,
-    ) -> Result<Vec<InheritancePlan>, InheritanceError> {
-        admin.require_auth();
-
-        // Verify admin
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(InheritanceError::Unauthorized)?;
-        if admin != stored_admin {
-            return Err(InheritanceError::Unauthorized);
+        attestor: Address,
+        kind: AttestorKind,
+    ) -> Result<(), InheritanceError> {
+        Self::require_admin(&env, &admin)?;
+        let mut list = Self::get_attestors(env.clone());
+        let record = AttestorRecord {
+            attestor: attestor.clone(),
+            kind,
+            registered_at: env.ledger().timestamp(),
+        };
+        match list.iter().position(|r| r.attestor == attestor) {
+            Some(index) => list.set(index as u32, record),
+            None => {
+                if list.len() >= MAX_ATTESTORS {
+                    return Err(InheritanceError::TooManyBeneficiaries);
+                }
+                list.push_back(record);
+            }
         }
+        plan_index::set_persistent(&env, &DataKey::PlanMetadata(0, 8000), &list);
+        Ok(())
+    }
 
-        let key = DataKey::DeactivatedPlans;
-        let deactivated_ids: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
-
-        let mut plans = Vec::new(&env);
-        for plan_id in deactivated_ids.iter() {
-            if let Some(plan) = Self::get_plan(&env, plan_id) {
-                // Double check it's inactive just in case
-                if !plan.is_active {
-                    plans.push_back(plan);
-                }
+    pub fn remove_attestor(
+        env: Env,
+        admin: Address,
+        attestor: Address,
+    ) -> Result<(), InheritanceError> {
+        Self::require_admin(&env, &admin)?;
+        let list = Self::get_attestors(env.clone());
+        let mut updated: Vec<AttestorRecord> = Vec::new(&env);
+        for r in list.iter() {
+            if r.attestor != attestor {
+                updated.push_back(r);
             }
         }
+        plan_index::set_persistent(&env, &DataKey::PlanMetadata(0, 8000), &updated);
+        Ok(())
+    }
 
-        Ok(plans)
+    pub fn get_attestors(env: Env) -> Vec<AttestorRecord> {
+        plan_index::get_persistent(&env, &DataKey::PlanMetadata(0, 8000)).unwrap_or(Vec::new(&env))
     }
 
-    /// Retrieve a specific claimed plan belonging to the authenticated user
-    pub fn get_claimed_plan(
+    /// Require `threshold` registered attestors to attest to the owner's
+    /// death before the plan's `Oracle` condition is met, with a
+    /// `challenge_window` of at least [`MIN_CHALLENGE_WINDOW`] for the owner
+    /// or emergency contacts to veto.
+    pub fn set_death_attestation_config(
         env: Env,
-        user: Address,
+        owner: Address,
         plan_id: u64,
-    ) -> Result<InheritancePlan, InheritanceError> {
-        user.require_auth();
-
+        threshold: u32,
+        challenge_window: u64,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
-
-        if plan.owner != user {
+        if plan.owner != owner {
             return Err(InheritanceError::Unauthorized);
         }
-
-        let key = DataKey::UserClaimedPlans(user);
-        let user_plans: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
-
-        if !user_plans.contains(plan_id) {
-            return Err(InheritanceError::PlanNotFound);
+        if Self::get_trigger_info(&env, plan_id).is_some() {
+            return Err(InheritanceError::InheritanceAlreadyTriggered);
+        }
+        if threshold == 0 || threshold > MAX_ATTESTORS {
+            return Err(InheritanceError::InvalidAttestationThreshold);
+        }
+        if challenge_window < MIN_CHALLENGE_WINDOW {
+            return Err(InheritanceError::ChallengeWindowTooShort);
+        }
+        if Self::get_death_attestation_round(&env, plan_id).is_some() {
+            return Err(InheritanceError::AttestationRoundActive);
         }
 
-        Ok(plan)
-    }
-
-    /// Retrieve all claimed plans for the authenticated user
-    pub fn get_user_claimed_plans(env: Env, user: Address) -> Vec<InheritancePlan> {
-        user.require_auth();
-
-        let key = DataKey::UserClaimedPlans(user);
-        let user_plan_ids: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
+        env.storage().persistent().set(
+            &DataKey::PlanMetadata(plan_id, 8001),
+            &DeathAttestationConfig {
+                threshold,
+                challenge_window,
+            },
+        );
 
-        let mut plans = Vec::new(&env);
-        for id in user_plan_ids.iter() {
-            if let Some(plan) = Self::get_plan(&env, id) {
-                plans.push_back(plan);
-            }
+        let mut config = Self::get_trigger_config(&env, plan_id).unwrap_or(TriggerConfig {
+            conditions: Vec::new(&env),
+            trigger_date: 0,
+            inactivity_period: 0,
+            last_activity: env.ledger().timestamp(),
+            oracle_address: None,
+            oracle_triggered: false,
+            health_triggered: false,
+        });
+        if !config.conditions.contains(TriggerConditionType::Oracle) {
+            config.conditions.push_back(TriggerConditionType::Oracle);
+            Self::save_trigger_config(&env, plan_id, &config);
         }
-        plans
+        Ok(())
     }
 
-    /// Retrieve all claimed plans across all users; accessible only by administrators
-    pub fn get_all_claimed_plans(
+    /// Attest to the plan owner's death. Attestations are counted per
+    /// certificate hash until one reaches the threshold; after that only
+    /// that hash is accepted. Returns the number of attestations of
+    /// `document_hash`.
+    pub fn submit_death_attestation(
         env: Env,
-        admin: Address,
-    ) -> Result<Vec<InheritancePlan>, InheritanceError> {
-        Self::require_admin(&env, &admin)?;
-
-        let key = DataKey::AllClaimedPlans;
-        let all_plan_ids: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env));
-
-        let mut plans = Vec::new(&env);
-        for id in all_plan_ids.iter() {
-            if let Some(plan) = Self::get_plan(&env, id) {
-                plans.push_back(plan);
-            }
+        attestor: Address,
+        plan_id: u64,
+        document_hash: BytesN<32>,
+    ) -> Result<u32, InheritanceError> {
+        attestor.require_auth();
+        Self::check_not_paused(&env);
+        Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if Self::get_trigger_info(&env, plan_id).is_some() {
+            return Err(InheritanceError::InheritanceAlreadyTriggered);
         }
-        Ok(plans)
-    }
+        let config = Self::get_death_attestation_config(&env, plan_id)
+            .ok_or(InheritanceError::AttestationNotConfigured)?;
+        let record = Self::get_attestors(env.clone())
+            .iter()
+            .find(|r| r.attestor == attestor)
+            .ok_or(InheritanceError::Unauthorized)?;
 
-    // ───────────────────────────────────────────
-    // Loan Recall on Inheritance Trigger
-    // ───────────────────────────────────────────
+        let now = env.ledger().timestamp();
+        let mut round =
+            Self::get_death_attestation_round(&env, plan_id).unwrap_or(DeathAttestationRound {
+                document_hash: document_hash.clone(),
+                attestations: Vec::new(&env),
+                threshold_reached_at: 0,
+                challenge_ends_at: 0,
+            });
+        if round.threshold_reached_at != 0 && round.document_hash != document_hash {
+            return Err(InheritanceError::DocumentHashMismatch);
+        }
+        if round.has_attested(&attestor) {
+            return Err(InheritanceError::AlreadyAttested);
+        }
+
+        let reached = round.record(
+            Attestation {
+                attestor: attestor.clone(),
+                kind: record.kind,
+                document_hash: document_hash.clone(),
+                attested_at: now,
+            },
+            config.threshold,
+            config.challenge_window,
+        );
+        plan_index::set_persistent(&env, &DataKey::PlanMetadata(plan_id, 8002), &round);
 
-    fn get_trigger_info(env: &Env, plan_id: u64) -> Option<InheritanceTriggerInfo> {
-        let key = DataKey::InheritanceTrigger(plan_id);
-        env.storage().persistent().get(&key)
+        env.events().publish(
+            (symbol_short!("DEATH"), symbol_short!("ATTEST")),
+            DeathAttestedEvent {
+                plan_id,
+                attestor,
+                document_hash: document_hash.clone(),
+                attestations: round.attestations_for(&document_hash),
+            },
+        );
+        let attestations = round.attestations_for(&document_hash);
+        if reached {
+            env.events().publish(
+                (symbol_short!("DEATH"), symbol_short!("CHALLENGE")),
+                ChallengeWindowOpenedEvent {
+                    plan_id,
+                    document_hash,
+                    challenge_ends_at: round.challenge_ends_at,
+                },
+            );
+        }
+        Ok(attestations)
     }
 
-    fn set_trigger_info(env: &Env, plan_id: u64, info: &InheritanceTriggerInfo) {
-        let key = DataKey::InheritanceTrigger(plan_id);
-        env.storage().persistent().set(&key, info);
-    }
+    /// Veto the plan's attestation round before its challenge window closes.
+    /// Callable by the owner or one of the plan's emergency contacts; an
+    /// owner veto also counts as activity.
+    pub fn veto_death_attestation(
+        env: Env,
+        caller: Address,
+        plan_id: u64,
+    ) -> Result<(), InheritanceError> {
+        caller.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        let is_owner = plan.owner == caller;
+        if !is_owner && !Self::get_emergency_contacts(env.clone(), plan_id).contains(&caller) {
+            return Err(InheritanceError::Unauthorized);
+        }
+        let round = Self::get_death_attestation_round(&env, plan_id)
+            .ok_or(InheritanceError::AttestationNotFound)?;
+        let now = env.ledger().timestamp();
+        if round.status(now) == DeathAttestationStatus::Finalized {
+            return Err(InheritanceError::ChallengeWindowClosed);
+        }
 
-    fn get_trigger_config(env: &Env, plan_id: u64) -> Option<TriggerConfig> {
         env.storage()
             .persistent()
-            .get(&DataKey::PlanMetadata(plan_id, 4000))
-    }
+            .remove(&DataKey::PlanMetadata(plan_id, 8002));
+        if is_owner {
+            if let Some(mut config) = Self::get_trigger_config(&env, plan_id) {
+                config.last_activity = now;
+                Self::save_trigger_config(&env, plan_id, &config);
+            }
+        }
 
-    fn save_trigger_config(env: &Env, plan_id: u64, config: &TriggerConfig) {
-        env.storage()
-            .persistent()
-            .set(&DataKey::PlanMetadata(plan_id, 4000), config);
+        env.events().publish(
+            (symbol_short!("DEATH"), symbol_short!("VETO")),
+            DeathAttestationVetoedEvent {
+                plan_id,
+                vetoed_by: caller,
+                document_hash: round.document_hash,
+            },
+        );
+        Ok(())
     }
 
-    pub fn check_trigger_conditions(env: Env, plan_id: u64) -> bool {
-        let config = match Self::get_trigger_config(&env, plan_id) {
-            Some(c) => c,
-            None => return false,
-        };
-        let now = env.ledger().timestamp();
-        for condition in config.conditions.iter() {
-            match condition {
-                TriggerConditionType::Time => {
-                    if config.trigger_date > 0 && now >= config.trigger_date {
-                        return true;
-                    }
-                }
-                TriggerConditionType::Inactivity => {
-                    if config.inactivity_period > 0
-                        && config.last_activity > 0
-                        && now >= config.last_activity + config.inactivity_period
-                    {
-                        return true;
-                    }
-                }
-                TriggerConditionType::Oracle => {
-                    if config.oracle_triggered {
-                        return true;
-                    }
-                }
-                TriggerConditionType::Health => {
-                    if config.health_triggered {
-                        return true;
-                    }
-                }
-                TriggerConditionType::Manual => {}
-            }
-        }
-        false
+    pub fn get_death_attestation(env: Env, plan_id: u64) -> Option<DeathAttestationView> {
+        let round = Self::get_death_attestation_round(&env, plan_id)?;
+        let threshold = Self::get_death_attestation_config(&env, plan_id)
+            .map(|c| c.threshold)
+            .unwrap_or(0);
+        let status = round.status(env.ledger().timestamp());
+        Some(DeathAttestationView {
+            plan_id,
+            threshold,
+            round,
+            status,
+        })
     }
 
     pub fn get_trigger_conditions(env: Env, plan_id: u64) -> Option<TriggerConfig> {
@@ -3385,52 +4625,141 @@ impl InheritanceContract {
         Ok(())
     }
 
-    pub fn add_health_trigger(
+    pub fn add_health_trigger(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        oracle_address: Address,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+        let mut config = Self::get_trigger_config(&env, plan_id).unwrap_or(TriggerConfig {
+            conditions: Vec::new(&env),
+            trigger_date: 0,
+            inactivity_period: 0,
+            last_activity: env.ledger().timestamp(),
+            oracle_address: None,
+            oracle_triggered: false,
+            health_triggered: false,
+        });
+        let mut already = false;
+        for c in config.conditions.iter() {
+            if c == TriggerConditionType::Health {
+                already = true;
+                break;
+            }
+        }
+        if !already {
+            config.conditions.push_back(TriggerConditionType::Health);
+        }
+        config.oracle_address = Some(oracle_address);
+        Self::save_trigger_config(&env, plan_id, &config);
+        Ok(())
+    }
+
+    pub fn record_activity(env: Env, owner: Address, plan_id: u64) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+        let mut config =
+            Self::get_trigger_config(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        config.last_activity = env.ledger().timestamp();
+        Self::save_trigger_config(&env, plan_id, &config);
+        Ok(())
+    }
+
+    /// Let `delegate` record activity for the plan on the owner's behalf, so
+    /// a check-in service holding that account can keep the inactivity timer
+    /// reset without the owner's key. `None` revokes the delegation.
+    pub fn set_activity_delegate(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        delegate: Option<Address>,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+        let key = DataKey::PlanMetadata(plan_id, 4004);
+        match delegate {
+            Some(delegate) => plan_index::set_persistent(&env, &key, &delegate),
+            None => env.storage().persistent().remove(&key),
+        }
+        Ok(())
+    }
+
+    pub fn get_activity_delegate(env: Env, plan_id: u64) -> Option<Address> {
+        plan_index::get_persistent(&env, &DataKey::PlanMetadata(plan_id, 4004))
+    }
+
+    /// `record_activity` by the plan's activity delegate.
+    pub fn record_activity_as_delegate(
+        env: Env,
+        delegate: Address,
+        plan_id: u64,
+    ) -> Result<(), InheritanceError> {
+        delegate.require_auth();
+        if Self::get_activity_delegate(env.clone(), plan_id) != Some(delegate) {
+            return Err(InheritanceError::Unauthorized);
+        }
+        let mut config =
+            Self::get_trigger_config(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        config.last_activity = env.ledger().timestamp();
+        Self::save_trigger_config(&env, plan_id, &config);
+        Ok(())
+    }
+
+    /// Set the grace window that follows the inactivity period. An
+    /// inactivity trigger only fires once `last_activity + inactivity_period
+    /// + grace_seconds` has passed, giving the owner time to answer check-in
+    /// reminders before `auto_trigger_check` can act.
+    pub fn set_inactivity_grace_period(
         env: Env,
         owner: Address,
         plan_id: u64,
-        oracle_address: Address,
+        grace_seconds: u64,
     ) -> Result<(), InheritanceError> {
         owner.require_auth();
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         if plan.owner != owner {
             return Err(InheritanceError::Unauthorized);
         }
-        let mut config = Self::get_trigger_config(&env, plan_id).unwrap_or(TriggerConfig {
-            conditions: Vec::new(&env),
-            trigger_date: 0,
-            inactivity_period: 0,
-            last_activity: env.ledger().timestamp(),
-            oracle_address: None,
-            oracle_triggered: false,
-            health_triggered: false,
-        });
-        let mut already = false;
-        for c in config.conditions.iter() {
-            if c == TriggerConditionType::Health {
-                already = true;
-                break;
-            }
-        }
-        if !already {
-            config.conditions.push_back(TriggerConditionType::Health);
+        if Self::get_trigger_info(&env, plan_id).is_some() {
+            return Err(InheritanceError::InheritanceAlreadyTriggered);
         }
-        config.oracle_address = Some(oracle_address);
-        Self::save_trigger_config(&env, plan_id, &config);
+        plan_index::set_persistent(&env, &DataKey::PlanMetadata(plan_id, 4001), &grace_seconds);
         Ok(())
     }
 
-    pub fn record_activity(env: Env, owner: Address, plan_id: u64) -> Result<(), InheritanceError> {
-        owner.require_auth();
-        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
-        if plan.owner != owner {
-            return Err(InheritanceError::Unauthorized);
+    pub fn get_inactivity_grace_period(env: Env, plan_id: u64) -> u64 {
+        Self::get_grace_period(&env, plan_id)
+    }
+
+    /// Timestamp at which the inactivity trigger becomes eligible, including
+    /// the grace window, or `None` if the plan has no inactivity condition.
+    pub fn get_inactivity_deadline(env: Env, plan_id: u64) -> Option<u64> {
+        let config = Self::get_trigger_config(&env, plan_id)?;
+        if config.inactivity_period == 0
+            || !config
+                .conditions
+                .iter()
+                .any(|c| c == TriggerConditionType::Inactivity)
+        {
+            return None;
         }
-        let mut config =
-            Self::get_trigger_config(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
-        config.last_activity = env.ledger().timestamp();
-        Self::save_trigger_config(&env, plan_id, &config);
-        Ok(())
+        Some(
+            config
+                .last_activity
+                .saturating_add(config.inactivity_period)
+                .saturating_add(Self::get_grace_period(&env, plan_id)),
+        )
     }
 
     pub fn submit_oracle_trigger(
@@ -3479,6 +4808,7 @@ impl InheritanceContract {
         if !plan.is_active {
             return Err(InheritanceError::PlanNotActive);
         }
+        let was_lendable = plan.is_lendable;
         plan.is_lendable = false;
         Self::store_plan(&env, plan_id, &plan);
         let trigger_info = InheritanceTriggerInfo {
@@ -3498,14 +4828,19 @@ impl InheritanceContract {
                 triggered_at: now,
             },
         );
+        let contest_ends_at = Self::open_contest_window(&env, plan_id, was_lendable, now);
         env.events().publish(
             (symbol_short!("INHERIT"), symbol_short!("TRIGGER")),
             InheritanceTriggeredEvent {
                 plan_id,
                 triggered_at: now,
                 outstanding_loans: plan.total_loaned,
+                contest_ends_at,
             },
         );
+        if contest_ends_at == 0 {
+            Self::issue_bridge_releases(&env, plan_id, &plan, now);
+        }
         Ok(())
     }
 
@@ -3560,88 +4895,494 @@ impl InheritanceContract {
         if !is_authorized {
             return Err(InheritanceError::Unauthorized);
         }
-
-        Self::execute_inheritance_trigger(&env, plan_id)
+
+        Self::execute_inheritance_trigger(&env, plan_id)
+    }
+
+    fn execute_inheritance_trigger(env: &Env, plan_id: u64) -> Result<(), InheritanceError> {
+        Self::enter_guard(env);
+        let mut plan = Self::get_plan(env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+
+        if !plan.is_active {
+            Self::exit_guard(env);
+            return Err(InheritanceError::PlanNotActive);
+        }
+
+        // Freeze/legal hold check
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::PlanMetadata(plan_id, 200))
+        {
+            Self::exit_guard(env);
+            return Err(InheritanceError::PlanNotActive);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::PlanMetadata(plan_id, 201))
+        {
+            Self::exit_guard(env);
+            return Err(InheritanceError::PlanNotActive);
+        }
+
+        // Check if already triggered
+        if Self::get_trigger_info(env, plan_id).is_some() {
+            Self::exit_guard(env);
+            return Err(InheritanceError::InheritanceAlreadyTriggered);
+        }
+
+        let now = env.ledger().timestamp();
+
+        // Freeze new loans by setting is_lendable to false
+        let was_lendable = plan.is_lendable;
+        plan.is_lendable = false;
+        Self::store_plan(env, plan_id, &plan);
+
+        // Create trigger info
+        let trigger_info = InheritanceTriggerInfo {
+            triggered_at: now,
+            loan_freeze_active: true,
+            recall_attempted: false,
+            liquidation_triggered: false,
+            original_loaned: plan.total_loaned,
+            recalled_amount: 0,
+            settled_amount: 0,
+        };
+        Self::set_trigger_info(env, plan_id, &trigger_info);
+
+        let contest_ends_at = Self::open_contest_window(env, plan_id, was_lendable, now);
+
+        // Emit events
+        env.events().publish(
+            (symbol_short!("INHERIT"), symbol_short!("TRIGGER")),
+            InheritanceTriggeredEvent {
+                plan_id,
+                triggered_at: now,
+                outstanding_loans: plan.total_loaned,
+                contest_ends_at,
+            },
+        );
+
+        env.events().publish(
+            (symbol_short!("LOAN"), symbol_short!("FREEZE")),
+            LoanFreezeEvent {
+                plan_id,
+                frozen_at: now,
+            },
+        );
+
+        // Cross-chain releases wait for the contest window to close.
+        if contest_ends_at == 0 {
+            Self::issue_bridge_releases(env, plan_id, &plan, now);
+        }
+
+        log!(
+            env,
+            "Inheritance triggered for plan {} — loans frozen, outstanding: {}",
+            plan_id,
+            plan.total_loaned
+        );
+
+        Self::exit_guard(env);
+        Ok(())
+    }
+
+    // ─── Contest Window ──────────────────────────────────────────
+
+    /// Delay releases by `seconds` after the plan is triggered, giving the
+    /// owner time to cancel and emergency contacts time to dispute. Zero
+    /// disables the window.
+    pub fn set_contest_period(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        seconds: u64,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+        if Self::get_trigger_info(&env, plan_id).is_some() {
+            return Err(InheritanceError::InheritanceAlreadyTriggered);
+        }
+        if seconds > MAX_CONTEST_PERIOD {
+            return Err(InheritanceError::InvalidContestPeriod);
+        }
+        plan_index::set_persistent(&env, &DataKey::PlanMetadata(plan_id, 4005), &seconds);
+        Ok(())
+    }
+
+    pub fn get_contest_period(env: Env, plan_id: u64) -> u64 {
+        plan_index::get_persistent(&env, &DataKey::PlanMetadata(plan_id, 4005)).unwrap_or(0)
+    }
+
+    /// The plan's contest window, present from trigger until it is closed
+    /// or the trigger is cancelled.
+    pub fn get_contest_window(env: Env, plan_id: u64) -> Option<ContestWindow> {
+        plan_index::get_persistent(&env, &DataKey::PlanMetadata(plan_id, 4006))
+    }
+
+    /// Seconds until the plan's contest window closes; 0 if there is none
+    /// or it has elapsed.
+    pub fn get_contest_remaining(env: Env, plan_id: u64) -> u64 {
+        Self::get_contest_window(env.clone(), plan_id)
+            .map(|w| w.remaining(env.ledger().timestamp()))
+            .unwrap_or(0)
+    }
+
+    /// Open the contest window for a freshly triggered plan. Returns when it
+    /// ends, or 0 if the plan has no contest period.
+    fn open_contest_window(env: &Env, plan_id: u64, was_lendable: bool, now: u64) -> u64 {
+        let period = Self::get_contest_period(env.clone(), plan_id);
+        if period == 0 {
+            return 0;
+        }
+        let window = ContestWindow {
+            started_at: now,
+            ends_at: now.saturating_add(period),
+            was_lendable,
+        };
+        plan_index::set_persistent(env, &DataKey::PlanMetadata(plan_id, 4006), &window);
+        env.events().publish(
+            (symbol_short!("CONTEST"), symbol_short!("START")),
+            ContestWindowStartedEvent {
+                plan_id,
+                started_at: now,
+                ends_at: window.ends_at,
+            },
+        );
+        window.ends_at
+    }
+
+    /// Close an elapsed contest window and issue the deferred cross-chain
+    /// releases. Fails with `ContestWindowActive` while it is still open;
+    /// a no-op if there is no window.
+    fn settle_contest_window(env: &Env, plan_id: u64) -> Result<(), InheritanceError> {
+        let window = match Self::get_contest_window(env.clone(), plan_id) {
+            Some(w) => w,
+            None => return Ok(()),
+        };
+        let now = env.ledger().timestamp();
+        if window.is_open(now) {
+            return Err(InheritanceError::ContestWindowActive);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PlanMetadata(plan_id, 4006));
+        env.events().publish(
+            (symbol_short!("CONTEST"), symbol_short!("END")),
+            ContestWindowEndedEvent {
+                plan_id,
+                ended_at: now,
+                cancelled: false,
+            },
+        );
+        if let Some(plan) = Self::get_plan(env, plan_id) {
+            Self::issue_bridge_releases(env, plan_id, &plan, now);
+        }
+        Ok(())
+    }
+
+    /// Close the plan's contest window once it has elapsed. Callable by
+    /// anyone; claims also close it implicitly.
+    pub fn close_contest_window(env: Env, plan_id: u64) -> Result<(), InheritanceError> {
+        Self::check_not_paused(&env);
+        if Self::get_contest_window(env.clone(), plan_id).is_none() {
+            return Err(InheritanceError::ClaimNotAllowedYet);
+        }
+        Self::settle_contest_window(&env, plan_id)
+    }
+
+    /// Cancel a trigger during its contest window. The owner's signature is
+    /// proof of life: lending is restored and the trigger signals that fired
+    /// (activity, oracle/health flags, guardian approvals, death attestations,
+    /// an elapsed trigger date) are reset so the plan does not re-trigger.
+    pub fn cancel_trigger(env: Env, owner: Address, plan_id: u64) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        Self::check_not_paused(&env);
+        let mut plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+        if Self::get_trigger_info(&env, plan_id).is_none() {
+            return Err(InheritanceError::ClaimNotAllowedYet);
+        }
+        let now = env.ledger().timestamp();
+        let window = Self::get_contest_window(env.clone(), plan_id)
+            .filter(|w| w.is_open(now))
+            .ok_or(InheritanceError::ChallengeWindowClosed)?;
+
+        let storage = env.storage().persistent();
+        storage.remove(&DataKey::InheritanceTrigger(plan_id));
+        storage.remove(&DataKey::PlanMetadata(plan_id, 4006));
+        storage.remove(&DataKey::PlanMetadata(plan_id, 4003));
+        storage.remove(&DataKey::PlanMetadata(plan_id, 8002));
+
+        plan.is_lendable = window.was_lendable;
+        Self::store_plan(&env, plan_id, &plan);
+
+        if let Some(mut config) = Self::get_trigger_config(&env, plan_id) {
+            config.last_activity = now;
+            config.oracle_triggered = false;
+            config.health_triggered = false;
+            if config.trigger_date > 0 && config.trigger_date <= now {
+                config.trigger_date = 0;
+            }
+            Self::save_trigger_config(&env, plan_id, &config);
+        }
+
+        env.events().publish(
+            (symbol_short!("TRIG"), symbol_short!("CANCEL")),
+            TriggerCancelledEvent {
+                plan_id,
+                owner,
+                cancelled_at: now,
+            },
+        );
+        env.events().publish(
+            (symbol_short!("CONTEST"), symbol_short!("END")),
+            ContestWindowEndedEvent {
+                plan_id,
+                ended_at: now,
+                cancelled: true,
+            },
+        );
+        Ok(())
+    }
+
+    // ─── Cross-Chain Manifest ────────────────────────────────────
+
+    /// Attach (or replace) the cross-chain asset manifest of a plan. Assets
+    /// listed here are not held by this contract; on trigger the contract
+    /// emits a release instruction per asset for the bridge relayer.
+    pub fn set_cross_chain_manifest(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        primary_chain: SupportedChain,
+        assets: Vec<CrossChainAsset>,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        Self::check_not_paused(&env);
+        let plan = Self::require_manifest_editable(&env, &owner, plan_id)?;
+
+        let created_at = Self::get_cross_chain_plan(&env, plan_id)
+            .map(|m| m.created_at)
+            .unwrap_or(env.ledger().timestamp());
+        let manifest = CrossChainInheritancePlan {
+            plan_id,
+            owner,
+            primary_chain,
+            assets,
+            created_at,
+            is_active: plan.is_active,
+        };
+        Self::store_cross_chain_plan(&env, &manifest)
+    }
+
+    /// Append an asset to a plan's existing manifest.
+    pub fn add_cross_chain_asset(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        asset: CrossChainAsset,
+    ) -> Result<u32, InheritanceError> {
+        owner.require_auth();
+        Self::check_not_paused(&env);
+        Self::require_manifest_editable(&env, &owner, plan_id)?;
+
+        let mut manifest =
+            Self::get_cross_chain_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        manifest.assets.push_back(asset);
+        Self::store_cross_chain_plan(&env, &manifest)?;
+        Ok(manifest.assets.len() - 1)
+    }
+
+    /// Replace the asset at `asset_index` in a plan's manifest.
+    pub fn update_cross_chain_asset(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        asset_index: u32,
+        asset: CrossChainAsset,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        Self::check_not_paused(&env);
+        Self::require_manifest_editable(&env, &owner, plan_id)?;
+
+        let mut manifest =
+            Self::get_cross_chain_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if asset_index >= manifest.assets.len() {
+            return Err(InheritanceError::InvalidAssetType);
+        }
+        manifest.assets.set(asset_index, asset);
+        Self::store_cross_chain_plan(&env, &manifest)
+    }
+
+    /// Remove the asset at `asset_index`. Removing the last asset removes the
+    /// manifest entirely.
+    pub fn remove_cross_chain_asset(
+        env: Env,
+        owner: Address,
+        plan_id: u64,
+        asset_index: u32,
+    ) -> Result<(), InheritanceError> {
+        owner.require_auth();
+        Self::check_not_paused(&env);
+        Self::require_manifest_editable(&env, &owner, plan_id)?;
+
+        let mut manifest =
+            Self::get_cross_chain_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if asset_index >= manifest.assets.len() {
+            return Err(InheritanceError::InvalidAssetType);
+        }
+        manifest.assets.remove(asset_index);
+        if manifest.assets.is_empty() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::CrossChainPlan(plan_id));
+            env.events().publish(
+                (symbol_short!("XCHAIN"), symbol_short!("MANIFEST")),
+                CrossChainManifestEvent {
+                    plan_id,
+                    asset_count: 0,
+                },
+            );
+            return Ok(());
+        }
+        Self::store_cross_chain_plan(&env, &manifest)
     }
 
-    fn execute_inheritance_trigger(env: &Env, plan_id: u64) -> Result<(), InheritanceError> {
-        Self::enter_guard(env);
-        let mut plan = Self::get_plan(env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
-
-        if !plan.is_active {
-            Self::exit_guard(env);
-            return Err(InheritanceError::PlanNotActive);
-        }
+    pub fn get_cross_chain_manifest(env: Env, plan_id: u64) -> Option<CrossChainInheritancePlan> {
+        Self::get_cross_chain_plan(&env, plan_id)
+    }
 
-        // Freeze/legal hold check
-        if env
-            .storage()
+    /// Release instructions recorded when the plan was triggered.
+    pub fn get_bridge_releases(env: Env, plan_id: u64) -> Vec<BridgeRelease> {
+        env.storage()
             .persistent()
-            .has(&DataKey::PlanMetadata(plan_id, 200))
-        {
-            Self::exit_guard(env);
-            return Err(InheritanceError::PlanNotActive);
+            .get(&DataKey::BridgeReleases(plan_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Confirm that the remote release for `asset_index` has been executed.
+    /// Only addresses holding `Role::Relayer` may confirm.
+    pub fn confirm_bridge_release(
+        env: Env,
+        relayer: Address,
+        plan_id: u64,
+        asset_index: u32,
+        remote_tx: Bytes,
+    ) -> Result<(), InheritanceError> {
+        relayer.require_auth();
+        Self::check_not_paused(&env);
+        if !access_control::has_role(&env, &relayer, Role::Relayer) {
+            return Err(InheritanceError::Unauthorized);
         }
-        if env
-            .storage()
-            .persistent()
-            .has(&DataKey::PlanMetadata(plan_id, 201))
-        {
-            Self::exit_guard(env);
-            return Err(InheritanceError::PlanNotActive);
+        if remote_tx.is_empty() {
+            return Err(InheritanceError::MissingRequiredField);
         }
 
-        // Check if already triggered
-        if Self::get_trigger_info(env, plan_id).is_some() {
-            Self::exit_guard(env);
-            return Err(InheritanceError::InheritanceAlreadyTriggered);
+        let key = DataKey::BridgeReleases(plan_id);
+        let mut releases: Vec<BridgeRelease> = plan_index::get_persistent(&env, &key)
+            .ok_or(InheritanceError::BridgeReleaseNotFound)?;
+        let mut release = releases
+            .get(asset_index)
+            .ok_or(InheritanceError::BridgeReleaseNotFound)?;
+        if release.status == BridgeReleaseStatus::Confirmed {
+            return Err(InheritanceError::AlreadyClaimed);
         }
 
-        let now = env.ledger().timestamp();
-
-        // Freeze new loans by setting is_lendable to false
-        plan.is_lendable = false;
-        Self::store_plan(env, plan_id, &plan);
-
-        // Create trigger info
-        let trigger_info = InheritanceTriggerInfo {
-            triggered_at: now,
-            loan_freeze_active: true,
-            recall_attempted: false,
-            liquidation_triggered: false,
-            original_loaned: plan.total_loaned,
-            recalled_amount: 0,
-            settled_amount: 0,
-        };
-        Self::set_trigger_info(env, plan_id, &trigger_info);
+        release.status = BridgeReleaseStatus::Confirmed;
+        release.confirmed_by = Some(relayer.clone());
+        release.remote_tx = remote_tx.clone();
+        release.confirmed_at = env.ledger().timestamp();
+        releases.set(asset_index, release);
+        plan_index::set_persistent(&env, &key, &releases);
 
-        // Emit events
         env.events().publish(
-            (symbol_short!("INHERIT"), symbol_short!("TRIGGER")),
-            InheritanceTriggeredEvent {
+            (symbol_short!("XCHAIN"), symbol_short!("CONFIRM")),
+            BridgeReleaseConfirmedEvent {
                 plan_id,
-                triggered_at: now,
-                outstanding_loans: plan.total_loaned,
+                asset_index,
+                relayer,
+                remote_tx,
             },
         );
+        Ok(())
+    }
 
+    fn get_cross_chain_plan(env: &Env, plan_id: u64) -> Option<CrossChainInheritancePlan> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CrossChainPlan(plan_id))
+    }
+
+    fn store_cross_chain_plan(
+        env: &Env,
+        manifest: &CrossChainInheritancePlan,
+    ) -> Result<(), InheritanceError> {
+        manifest.validate()?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::CrossChainPlan(manifest.plan_id), manifest);
         env.events().publish(
-            (symbol_short!("LOAN"), symbol_short!("FREEZE")),
-            LoanFreezeEvent {
-                plan_id,
-                frozen_at: now,
+            (symbol_short!("XCHAIN"), symbol_short!("MANIFEST")),
+            CrossChainManifestEvent {
+                plan_id: manifest.plan_id,
+                asset_count: manifest.assets.len(),
             },
         );
+        Ok(())
+    }
 
-        log!(
-            env,
-            "Inheritance triggered for plan {} — loans frozen, outstanding: {}",
-            plan_id,
-            plan.total_loaned
-        );
+    /// The manifest can only be edited by the plan owner before trigger.
+    fn require_manifest_editable(
+        env: &Env,
+        owner: &Address,
+        plan_id: u64,
+    ) -> Result<InheritancePlan, InheritanceError> {
+        let plan = Self::get_plan(env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
+        if plan.owner != *owner {
+            return Err(InheritanceError::Unauthorized);
+        }
+        if !plan.is_active {
+            return Err(InheritanceError::PlanNotActive);
+        }
+        if Self::get_trigger_info(env, plan_id).is_some() {
+            return Err(InheritanceError::InheritanceAlreadyTriggered);
+        }
+        Ok(plan)
+    }
 
-        Self::exit_guard(env);
-        Ok(())
+    /// Record and emit one `XCHAIN/RELEASE` instruction per manifest asset.
+    /// Called once, when the plan is triggered.
+    fn issue_bridge_releases(env: &Env, plan_id: u64, plan: &InheritancePlan, now: u64) {
+        let manifest = match Self::get_cross_chain_plan(env, plan_id) {
+            Some(m) => m,
+            None => return,
+        };
+
+        let mut allocations = Vec::new(env);
+        for b in plan.beneficiaries.iter() {
+            allocations.push_back(b.allocation_bp);
+        }
+
+        let mut releases = Vec::new(env);
+        for (i, asset) in manifest.assets.iter().enumerate() {
+            let release = BridgeRelease::pending(env, plan_id, i as u32, &asset, &allocations, now);
+            env.events().publish(
+                (symbol_short!("XCHAIN"), symbol_short!("RELEASE")),
+                release.clone(),
+            );
+            releases.push_back(release);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::BridgeReleases(plan_id), &releases);
     }
 
     /// Attempt to recall loaned funds back to the plan.
@@ -3861,6 +5602,10 @@ impl InheritanceContract {
     /// storage migrations. If no migration is needed the function is a no-op
     /// so it is always safe to call.
     ///
+    /// Plans are migrated `MIGRATION_BATCH` at a time. While plans remain the
+    /// stored version is left unchanged; call again until `version` reports
+    /// the current version.
+    ///
     /// # Arguments
     /// * `env` - The environment
     /// * `admin` - The admin address (must be the initialized admin)
@@ -3875,10 +5620,21 @@ impl InheritanceContract {
         }
 
         // ── Version-specific migrations go here ──
-        // Example for a future migration:
-        // if stored_version < 2 {
-        //     // migrate from v1 → v2 schema changes
-        // }
+        // v2: beneficiary layout; v3: plan indexes. Both are per-plan.
+        if !Self::migrate_plans(&env, stored_version) {
+            log!(
+                &env,
+                "Migration from v{} in progress, call migrate again",
+                stored_version
+            );
+            return Ok(());
+        }
+        if stored_version < 3 {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::DeactivatedPlans);
+            env.storage().persistent().remove(&DataKey::AllClaimedPlans);
+        }
 
         // Update stored version to current
         env.storage()
@@ -3895,6 +5651,70 @@ impl InheritanceContract {
         Ok(())
     }
 
+    /// Migrate the next batch of plans from `stored_version`. Returns `true`
+    /// once every plan has been migrated.
+    ///
+    /// - v1 → v2: beneficiaries lose the plaintext `bank_account` in favour
+    ///   of an empty `bank_account_ref`, assigned later with
+    ///   `assign_legacy_bank_account_ref`, and gain an optional wallet
+    ///   binding. Plans
+    ///   already in the v2 layout are left untouched.
+    /// - v2 → v3: the per-owner and global `Vec<u64>` plan lists are replaced
+    ///   by the indexes in `plan_index`, and every plan's TTL is extended.
+    fn migrate_plans(env: &Env, stored_version: u32) -> bool {
+        let next_plan_id = Self::get_next_plan_id(env);
+        let start: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MigrationCursor)
+            .unwrap_or(1);
+        let end = start
+            .saturating_add(MIGRATION_BATCH as u64)
+            .min(next_plan_id);
+
+        for plan_id in start..end {
+            let key = DataKey::Plan(plan_id);
+            let Some(raw) = plan_index::get_persistent::<Val>(env, &key) else {
+                continue;
+            };
+            let plan = if stored_version < 2 && identity::is_legacy_plan(env, &raw) {
+                LegacyInheritancePlan::from_val(env, &raw).upgrade(env)
+            } else {
+                InheritancePlan::from_val(env, &raw)
+            };
+            Self::store_plan(env, plan_id, &plan);
+
+            if stored_version < 3 {
+                let owner = plan.owner.clone();
+                plan_index::insert(env, &PlanIndex::Owner(owner.clone()), plan_id);
+                if plan.is_active {
+                    plan_index::insert(env, &PlanIndex::Active, plan_id);
+                } else {
+                    plan_index::insert(env, &PlanIndex::Deactivated, plan_id);
+                }
+                if plan.beneficiaries.iter().any(|b| b.is_claimed) {
+                    Self::add_plan_to_claimed(env, owner.clone(), plan_id);
+                }
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::UserPlans(owner.clone()));
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::UserClaimedPlans(owner));
+            }
+        }
+
+        if end < next_plan_id {
+            env.storage()
+                .instance()
+                .set(&DataKey::MigrationCursor, &end);
+            false
+        } else {
+            env.storage().instance().remove(&DataKey::MigrationCursor);
+            true
+        }
+    }
+
     // ── Will Management System (Issues #314–#317) ──
 
     /// Store a SHA-256 hash of a will document on-chain, mapped to a plan_id.
@@ -3921,7 +5741,7 @@ impl InheritanceContract {
             return Err(InheritanceError::AlreadyClaimed);
         }
 
-        env.storage().persistent().set(&key, &will_hash);
+        plan_index::set_persistent(&env, &key, &will_hash);
 
         env.events().publish(
             (symbol_short!("WILL"), symbol_short!("STORED")),
@@ -3934,7 +5754,7 @@ impl InheritanceContract {
     /// Retrieve the stored will hash for a plan.
     pub fn get_will_hash(env: Env, plan_id: u64) -> Option<BytesN<32>> {
         let key = DataKey::WillHash(plan_id);
-        env.storage().persistent().get(&key)
+        plan_index::get_persistent(&env, &key)
     }
 
     /// Link a will document hash to a vault (plan). Prevents re-linking unless
@@ -3962,7 +5782,7 @@ impl InheritanceContract {
             return Err(InheritanceError::AlreadyClaimed);
         }
 
-        env.storage().persistent().set(&key, &will_hash);
+        plan_index::set_persistent(&env, &key, &will_hash);
 
         env.events().publish(
             (symbol_short!("WILL"), symbol_short!("LINKED")),
@@ -3975,7 +5795,7 @@ impl InheritanceContract {
     /// Retrieve the will hash linked to a vault.
     pub fn get_vault_will(env: Env, plan_id: u64) -> Option<BytesN<32>> {
         let key = DataKey::VaultWill(plan_id);
-        env.storage().persistent().get(&key)
+        plan_index::get_persistent(&env, &key)
     }
 
     /// Verify that the beneficiaries in a will document match those stored in the plan.
@@ -4014,7 +5834,7 @@ impl InheritanceContract {
 
         // Store verification result
         let ver_key = DataKey::BeneficiaryVerification(plan_id);
-        env.storage().persistent().set(&ver_key, &status);
+        plan_index::set_persistent(&env, &ver_key, &status);
 
         env.events().publish(
             (symbol_short!("WILL"), symbol_short!("VERIFY")),
@@ -4027,7 +5847,7 @@ impl InheritanceContract {
     /// Get the last beneficiary verification status for a plan.
     pub fn get_verification_status(env: Env, plan_id: u64) -> Option<bool> {
         let key = DataKey::BeneficiaryVerification(plan_id);
-        env.storage().persistent().get(&key)
+        plan_index::get_persistent(&env, &key)
     }
 
     /// Create a new will version for a plan. Auto-increments version number and
@@ -4047,35 +5867,28 @@ impl InheritanceContract {
 
         // Block creating a new version if the currently active version is finalized
         let active_key = DataKey::ActiveWillVersion(plan_id);
-        if let Some(active_ver_num) = env.storage().persistent().get::<_, u32>(&active_key) {
+        if let Some(active_ver_num) = plan_index::get_persistent::<u32>(&env, &active_key) {
             let fin_key = DataKey::PlanMetadata(plan_id, active_ver_num);
-            if env
-                .storage()
-                .persistent()
-                .get::<_, bool>(&fin_key)
-                .unwrap_or(false)
-            {
+            if plan_index::get_persistent::<bool>(&env, &fin_key).unwrap_or(false) {
                 return Err(InheritanceError::AlreadyClaimed);
             }
         }
 
         // Get and increment version count
         let count_key = DataKey::WillVersionCount(plan_id);
-        let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let current_count: u32 = plan_index::get_persistent(&env, &count_key).unwrap_or(0);
         let new_version = current_count + 1;
-        env.storage().persistent().set(&count_key, &new_version);
+        plan_index::set_persistent(&env, &count_key, &new_version);
 
         // Deactivate previously active version if any
         let active_key = DataKey::ActiveWillVersion(plan_id);
-        if let Some(prev_ver_num) = env.storage().persistent().get::<_, u32>(&active_key) {
+        if let Some(prev_ver_num) = plan_index::get_persistent::<u32>(&env, &active_key) {
             let prev_key = DataKey::WillVersion(plan_id, prev_ver_num);
-            if let Some(mut prev_ver) = env
-                .storage()
-                .persistent()
-                .get::<_, WillVersionInfo>(&prev_key)
+            if let Some(mut prev_ver) =
+                plan_index::get_persistent::<WillVersionInfo>(&env, &prev_key)
             {
                 prev_ver.is_active = false;
-                env.storage().persistent().set(&prev_key, &prev_ver);
+                plan_index::set_persistent(&env, &prev_key, &prev_ver);
             }
         }
 
@@ -4087,14 +5900,14 @@ impl InheritanceContract {
             is_active: true,
         };
         let ver_key = DataKey::WillVersion(plan_id, new_version);
-        env.storage().persistent().set(&ver_key, &version_info);
+        plan_index::set_persistent(&env, &ver_key, &version_info);
 
         // Set as active
-        env.storage().persistent().set(&active_key, &new_version);
+        plan_index::set_persistent(&env, &active_key, &new_version);
 
         // Update VaultWill link to point to latest will hash
         let vault_will_key = DataKey::VaultWill(plan_id);
-        env.storage().persistent().set(&vault_will_key, &will_hash);
+        plan_index::set_persistent(&env, &vault_will_key, &will_hash);
 
         env.events().publish(
             (symbol_short!("WILL"), symbol_short!("VERSION")),
@@ -4118,15 +5931,15 @@ impl InheritanceContract {
     /// Get a specific will version for a plan.
     pub fn get_will_version(env: Env, plan_id: u64, version: u32) -> Option<WillVersionInfo> {
         let key = DataKey::WillVersion(plan_id, version);
-        env.storage().persistent().get(&key)
+        plan_index::get_persistent(&env, &key)
     }
 
     /// Get the currently active will version for a plan.
     pub fn get_active_will_version(env: Env, plan_id: u64) -> Option<WillVersionInfo> {
         let active_key = DataKey::ActiveWillVersion(plan_id);
-        if let Some(active_ver) = env.storage().persistent().get::<_, u32>(&active_key) {
+        if let Some(active_ver) = plan_index::get_persistent::<u32>(&env, &active_key) {
             let key = DataKey::WillVersion(plan_id, active_ver);
-            env.storage().persistent().get(&key)
+            plan_index::get_persistent(&env, &key)
         } else {
             None
         }
@@ -4135,7 +5948,7 @@ impl InheritanceContract {
     /// Get the total number of will versions for a plan.
     pub fn get_will_version_count(env: Env, plan_id: u64) -> u32 {
         let key = DataKey::WillVersionCount(plan_id);
-        env.storage().persistent().get(&key).unwrap_or(0)
+        plan_index::get_persistent(&env, &key).unwrap_or(0)
     }
 
     // ── Will Signature Verification (Issue #318) ──
@@ -4169,17 +5982,12 @@ impl InheritanceContract {
         // Replay protection: check signature hash in SignatureUsed map
         let sig_hash = env.crypto().sha256(&signature.clone().into());
         let used_key = DataKey::SignatureUsed(sig_hash.clone().into());
-        if env
-            .storage()
-            .persistent()
-            .get::<_, bool>(&used_key)
-            .unwrap_or(false)
-        {
+        if plan_index::get_persistent::<bool>(&env, &used_key).unwrap_or(false) {
             return Err(InheritanceError::AlreadyClaimed);
         }
 
         // Mark signature as used
-        env.storage().persistent().set(&used_key, &true);
+        plan_index::set_persistent(&env, &used_key, &true);
 
         // Store the signature proof
         let proof = WillSignatureProof {
@@ -4231,10 +6039,7 @@ impl InheritanceContract {
             return Err(InheritanceError::MissingRequiredField);
         }
 
-        let current: u32 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(vault_id, 2000))
+        let current: u32 = plan_index::get_persistent(&env, &DataKey::PlanMetadata(vault_id, 2000))
             .unwrap_or(0u32);
         let next = current.saturating_add(1);
 
@@ -4242,12 +6047,8 @@ impl InheritanceContract {
             &DataKey::PlanMetadata(vault_id, 10000 + next),
             &new_key_reference,
         );
-        env.storage()
-            .persistent()
-            .set(&DataKey::PlanMetadata(vault_id, 2000), &next);
-        env.storage()
-            .persistent()
-            .set(&DataKey::PlanMetadata(vault_id, 2001), &next);
+        plan_index::set_persistent(&env, &DataKey::PlanMetadata(vault_id, 2000), &next);
+        plan_index::set_persistent(&env, &DataKey::PlanMetadata(vault_id, 2001), &next);
 
         env.events().publish(
             (symbol_short!("KEY"), symbol_short!("ROTATE")),
@@ -4258,21 +6059,13 @@ impl InheritanceContract {
     }
 
     pub fn get_vault_message_key(env: Env, vault_id: u64) -> Option<String> {
-        let ver: u32 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(vault_id, 2001))
+        let ver: u32 = plan_index::get_persistent(&env, &DataKey::PlanMetadata(vault_id, 2001))
             .unwrap_or(0u32);
-        env.storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(vault_id, 10000 + ver))
+        plan_index::get_persistent(&env, &DataKey::PlanMetadata(vault_id, 10000 + ver))
     }
 
     pub fn get_vault_message_key_version(env: Env, vault_id: u64) -> u32 {
-        env.storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(vault_id, 2001))
-            .unwrap_or(0u32)
+        plan_index::get_persistent(&env, &DataKey::PlanMetadata(vault_id, 2001)).unwrap_or(0u32)
     }
 
     /// Create a new legacy message with metadata stored on-chain
@@ -4314,16 +6107,14 @@ impl InheritanceContract {
         // use the current vault key (supports on-chain rotation).
         let mut key_ref = params.key_reference;
         if key_ref.is_empty() {
-            let ver: u32 = env
-                .storage()
-                .persistent()
-                .get(&DataKey::PlanMetadata(params.vault_id, 2001))
-                .unwrap_or(0u32);
-            key_ref = env
-                .storage()
-                .persistent()
-                .get(&DataKey::PlanMetadata(params.vault_id, 10000 + ver))
-                .unwrap_or(String::from_str(&env, ""));
+            let ver: u32 =
+                plan_index::get_persistent(&env, &DataKey::PlanMetadata(params.vault_id, 2001))
+                    .unwrap_or(0u32);
+            key_ref = plan_index::get_persistent(
+                &env,
+                &DataKey::PlanMetadata(params.vault_id, 10000 + ver),
+            )
+            .unwrap_or(String::from_str(&env, ""));
         }
 
         // Create message metadata
@@ -4735,23 +6526,16 @@ impl InheritanceContract {
 
         // Version must exist
         let ver_key = DataKey::WillVersion(vault_id, version);
-        env.storage()
-            .persistent()
-            .get::<_, WillVersionInfo>(&ver_key)
+        plan_index::get_persistent::<WillVersionInfo>(&env, &ver_key)
             .ok_or(InheritanceError::PlanNotFound)?;
 
         // Atomic finalization guard: set the flag first to prevent concurrent finalization.
         let fin_key = DataKey::PlanMetadata(vault_id, version);
-        if env
-            .storage()
-            .persistent()
-            .get::<_, bool>(&fin_key)
-            .unwrap_or(false)
-        {
+        if plan_index::get_persistent::<bool>(&env, &fin_key).unwrap_or(false) {
             return Err(InheritanceError::AlreadyClaimed);
         }
         // Mark finalized immediately so any concurrent call sees it and returns early.
-        env.storage().persistent().set(&fin_key, &true);
+        plan_index::set_persistent(&env, &fin_key, &true);
 
         // Owner must have signed the will
         if env
@@ -4766,21 +6550,13 @@ impl InheritanceContract {
 
         // All assigned witnesses must have signed
         let witnesses_key = DataKey::PlanMetadata(vault_id, 100); // Use 100 as witnesses marker
-        let witnesses: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&witnesses_key)
-            .unwrap_or_else(|| Vec::new(&env));
+        let witnesses: Vec<Address> =
+            plan_index::get_persistent(&env, &witnesses_key).unwrap_or_else(|| Vec::new(&env));
 
         for i in 0..witnesses.len() {
             let w = witnesses.get(i).unwrap();
             let wsig_key = DataKey::UserMetadata(w, vault_id as u32); // Use vault_id as metadata_type
-            if env
-                .storage()
-                .persistent()
-                .get::<_, u64>(&wsig_key)
-                .is_none()
-            {
+            if plan_index::get_persistent::<u64>(&env, &wsig_key).is_none() {
                 env.storage().persistent().remove(&fin_key);
                 return Err(InheritanceError::MissingRequiredField);
             }
@@ -4806,17 +6582,13 @@ impl InheritanceContract {
 
     /// Check whether a specific will version is finalized.
     pub fn is_will_finalized(env: Env, vault_id: u64, version: u32) -> bool {
-        env.storage()
-            .persistent()
-            .get::<_, bool>(&DataKey::PlanMetadata(vault_id, version))
+        plan_index::get_persistent::<bool>(&env, &DataKey::PlanMetadata(vault_id, version))
             .unwrap_or(false)
     }
 
     /// Get the finalization timestamp for a will version (None if not finalized).
     pub fn get_will_finalized_at(env: Env, vault_id: u64, version: u32) -> Option<u64> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(vault_id, version + 1000))
+        plan_index::get_persistent(&env, &DataKey::PlanMetadata(vault_id, version + 1000))
     }
 
     // ── Legal Witness Verification (Issue #320) ──
@@ -4836,11 +6608,8 @@ impl InheritanceContract {
         }
 
         let key = DataKey::PlanMetadata(vault_id, 100);
-        let mut witnesses: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or_else(|| Vec::new(&env));
+        let mut witnesses: Vec<Address> =
+            plan_index::get_persistent(&env, &key).unwrap_or_else(|| Vec::new(&env));
 
         // Prevent duplicates
         for i in 0..witnesses.len() {
@@ -4850,7 +6619,7 @@ impl InheritanceContract {
         }
 
         witnesses.push_back(witness.clone());
-        env.storage().persistent().set(&key, &witnesses);
+        plan_index::set_persistent(&env, &key, &witnesses);
 
         env.events().publish(
             (symbol_short!("WILL"), symbol_short!("WITNESS")),
@@ -4877,11 +6646,8 @@ impl InheritanceContract {
 
         // Witness must be in the registered list
         let key = DataKey::PlanMetadata(vault_id, 100);
-        let witnesses: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or_else(|| Vec::new(&env));
+        let witnesses: Vec<Address> =
+            plan_index::get_persistent(&env, &key).unwrap_or_else(|| Vec::new(&env));
 
         let mut found = false;
         for i in 0..witnesses.len() {
@@ -4902,31 +6668,21 @@ impl InheritanceContract {
         // Replay protection: check signature hash in SignatureUsed map
         let sig_hash = env.crypto().sha256(&signature.clone().into());
         let used_key = DataKey::SignatureUsed(sig_hash.clone().into());
-        if env
-            .storage()
-            .persistent()
-            .get::<_, bool>(&used_key)
-            .unwrap_or(false)
-        {
+        if plan_index::get_persistent::<bool>(&env, &used_key).unwrap_or(false) {
             return Err(InheritanceError::AlreadyClaimed);
         }
 
         // Prevent double-signing
         let wsig_key = DataKey::UserMetadata(witness.clone(), vault_id as u32);
-        if env
-            .storage()
-            .persistent()
-            .get::<_, u64>(&wsig_key)
-            .is_some()
-        {
+        if plan_index::get_persistent::<u64>(&env, &wsig_key).is_some() {
             return Err(InheritanceError::AlreadyClaimed);
         }
 
         // Mark signature as used
-        env.storage().persistent().set(&used_key, &true);
+        plan_index::set_persistent(&env, &used_key, &true);
 
         let signed_at = env.ledger().timestamp();
-        env.storage().persistent().set(&wsig_key, &signed_at);
+        plan_index::set_persistent(&env, &wsig_key, &signed_at);
 
         env.events().publish(
             (symbol_short!("WILL"), symbol_short!("WSIGN")),
@@ -4938,9 +6694,7 @@ impl InheritanceContract {
 
     /// Get all registered witnesses for a vault.
     pub fn get_witnesses(env: Env, vault_id: u64) -> Vec<Address> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::PlanMetadata(vault_id, 100))
+        plan_index::get_persistent(&env, &DataKey::PlanMetadata(vault_id, 100))
             .unwrap_or_else(|| Vec::new(&env))
     }
 
@@ -4987,6 +6741,12 @@ impl InheritanceContract {
                 fail += 1;
                 continue;
             }
+            if let Some(wallet) = &input.wallet {
+                if Self::ensure_wallet_unbound(&plan, wallet).is_err() {
+                    fail += 1;
+                    continue;
+                }
+            }
             match Self::create_beneficiary(
                 &env,
                 plan_id,
@@ -4994,11 +6754,12 @@ impl InheritanceContract {
                 input.name.clone(),
                 input.email.clone(),
                 input.claim_code,
-                input.bank_account.clone(),
+                input.bank_account_ref.clone(),
                 input.allocation_bp,
                 input.priority,
             ) {
-                Ok(beneficiary) => {
+                Ok(mut beneficiary) => {
+                    beneficiary.wallet = input.wallet.clone();
                     plan.total_allocation_bp = new_total;
                     plan.beneficiaries.push_back(beneficiary);
                     success += 1;
@@ -5009,6 +6770,7 @@ impl InheritanceContract {
             }
         }
         Self::store_plan(&env, plan_id, &plan);
+        Self::clear_asset_allocations(&env, plan_id);
         env.events().publish(
             (symbol_short!("BATCH"), symbol_short!("BEN_ADD")),
             BatchBeneficiariesAddedEvent {
@@ -5093,6 +6855,7 @@ impl InheritanceContract {
             success += 1;
         }
         Self::store_plan(&env, plan_id, &plan);
+        Self::clear_asset_allocations(&env, plan_id);
         env.events().publish(
             (symbol_short!("BATCH"), symbol_short!("BEN_REM")),
             BatchBeneficiariesRemovedEvent {
@@ -5169,7 +6932,7 @@ impl InheritanceContract {
         let now = env.ledger().timestamp();
         for user in users.iter() {
             let key = DataKey::Kyc(user.clone());
-            let maybe_status: Option<KycStatus> = env.storage().persistent().get(&key);
+            let maybe_status: Option<KycStatus> = plan_index::get_persistent(&env, &key);
             match maybe_status {
                 None => {
                     fail += 1;
@@ -5181,7 +6944,7 @@ impl InheritanceContract {
                     }
                     status.approved = true;
                     status.approved_at = now;
-                    env.storage().persistent().set(&key, &status);
+                    plan_index::set_persistent(&env, &key, &status);
                     env.events().publish(
                         (symbol_short!("KYC"), symbol_short!("APPROV")),
                         KycApprovedEvent {
@@ -5312,6 +7075,7 @@ impl InheritanceContract {
         }
         let plan = Self::get_plan(&env, plan_id).ok_or(InheritanceError::PlanNotFound)?;
         let _ = Self::auto_trigger_check(env.clone(), plan_id);
+        Self::settle_contest_window(&env, plan_id)?;
         let triggered = Self::get_trigger_info(&env, plan_id).is_some();
         if !plan.is_active {
             return Err(InheritanceError::PlanNotActive);
@@ -5367,43 +7131,41 @@ impl InheritanceContract {
                     continue;
                 }
             };
-            let mut beneficiary_index: Option<u32> = None;
-            for i in 0..current_plan.beneficiaries.len() {
-                let b = current_plan.beneficiaries.get(i).unwrap();
-                if b.hashed_email != hashed_email {
-                    continue;
-                }
-                let salt: BytesN<32> = env
-                    .storage()
-                    .persistent()
-                    .get(&DataKey::ClaimSalt(plan_id, i))
-                    .unwrap_or(BytesN::<32>::from_array(&env, &[0u8; 32]));
-                let hashed_claim_code =
-                    match Self::hash_claim_code_with_salt(&env, claim_code, &salt) {
-                        Ok(h) => h,
-                        Err(_) => {
-                            fail += 1;
-                            beneficiary_index = None;
-                            break;
-                        }
-                    };
-                if b.hashed_claim_code == hashed_claim_code {
-                    beneficiary_index = Some(i);
-                    break;
-                }
-            }
-            let index = match beneficiary_index {
-                Some(i) => i,
-                None => {
+            let index = match Self::find_claiming_beneficiary(
+                &env,
+                plan_id,
+                &current_plan,
+                &claimer,
+                &ClaimProof::ClaimCode { email, claim_code },
+            ) {
+                Ok(i) => i,
+                Err(_) => {
                     fail += 1;
                     continue;
                 }
             };
             let beneficiary = current_plan.beneficiaries.get(index).unwrap();
-            let base_payout = (current_plan.total_amount as u128)
-                .checked_mul(beneficiary.allocation_bp as u128)
-                .and_then(|v| v.checked_div(10000))
-                .unwrap_or(0) as u64;
+            let schedule = if current_plan.distribution_method == DistributionMethod::LumpSum {
+                None
+            } else {
+                Some(Self::build_vesting_schedule(
+                    &env,
+                    plan_id,
+                    &current_plan,
+                    index,
+                ))
+            };
+            let base_payout = match &schedule {
+                Some(s) => s.claimable_amount,
+                None => (current_plan.total_amount as u128)
+                    .checked_mul(beneficiary.allocation_bp as u128)
+                    .and_then(|v| v.checked_div(10000))
+                    .unwrap_or(0) as u64,
+            };
+            if schedule.is_some() && base_payout == 0 {
+                fail += 1;
+                continue;
+            }
             if Self::is_emergency_active(&env, plan_id) {
                 let limit = (current_plan.total_amount as u128)
                     .checked_mul(EMERGENCY_TRANSFER_LIMIT_BP as u128)
@@ -5417,16 +7179,29 @@ impl InheritanceContract {
             let _available = current_plan
                 .total_amount
                 .saturating_sub(current_plan.total_loaned);
-            let claim = ClaimRecord {
-                plan_id,
-                beneficiary_index: index,
-                claimed_at: env.ledger().timestamp(),
+            let shares = Self::basket_shares(&env, plan_id, &current_plan, index);
+            let finalized = match &schedule {
+                Some(s) => Self::record_tranche_claim(&env, plan_id, s, base_payout),
+                None => true,
             };
-            env.storage().persistent().set(&claim_key, &claim);
             let mut updated = current_plan.clone();
             updated.total_amount = updated.total_amount.saturating_sub(base_payout);
+            if finalized {
+                let mut claimed_beneficiary = beneficiary.clone();
+                claimed_beneficiary.is_claimed = true;
+                updated.beneficiaries.set(index, claimed_beneficiary);
+            }
             Self::store_plan(&env, plan_id, &updated);
-            Self::add_plan_to_claimed(&env, current_plan.owner.clone(), plan_id);
+            if finalized {
+                let claim = ClaimRecord {
+                    plan_id,
+                    beneficiary_index: index,
+                    claimed_at: env.ledger().timestamp(),
+                };
+                plan_index::set_persistent(&env, &claim_key, &claim);
+                Self::add_plan_to_claimed(&env, current_plan.owner.clone(), plan_id);
+                Self::pay_basket_shares(&env, plan_id, index, &claimer, &shares)?;
+            }
             env.events().publish(
                 (symbol_short!("CLAIM"), symbol_short!("SUCCESS")),
                 (plan_id, hashed_email, base_payout),
@@ -5500,44 +7275,6 @@ impl InheritanceContract {
         env.storage().instance().get(&DataKey::PlanMetadata(0, 2))
     }
 
-    fn require_lending_contract(env: &Env) -> Result<Address, InheritanceError> {
-        Self::get_lending_contract(env.clone()).ok_or(InheritanceError::AdminNotSet)
-    }
-
-    fn require_governance_contract(env: &Env) -> Result<Address, InheritanceError> {
-        Self::get_governance_contract(env.clone()).ok_or(InheritanceError::AdminNotSet)
-    }
-
-    fn invoke_lending_contract<R: soroban_sdk::TryFromVal<Env, Val>>(
-        env: &Env,
-        method: Symbol,
-        args: Vec<Val>,
-    ) -> Result<R, InheritanceError> {
-        let contract = Self::require_lending_contract(env)?;
-        match env.try_invoke_contract::<R, InvokeError>(&contract, &method, args) {
-            Ok(Ok(val)) => Ok(val),
-            _ => {
-                log!(&env, "Lending contract call failed");
-                Err(InheritanceError::PlanNotActive)
-            }
-        }
-    }
-
-    fn invoke_governance_contract<R: soroban_sdk::TryFromVal<Env, Val>>(
-        env: &Env,
-        method: Symbol,
-        args: Vec<Val>,
-    ) -> Result<R, InheritanceError> {
-        let contract = Self::require_governance_contract(env)?;
-        match env.try_invoke_contract::<R, InvokeError>(&contract, &method, args) {
-            Ok(Ok(val)) => Ok(val),
-            _ => {
-                log!(&env, "Governance contract call failed");
-                Err(InheritanceError::PlanNotActive)
-            }
-        }
-    }
-
     pub fn verify_plan_ownership(env: Env, plan_id: u64, user: Address) -> bool {
         if let Some(plan) = Self::get_plan(&env, plan_id) {
             return plan.owner == user;
@@ -5815,21 +7552,18 @@ impl InheritanceContract {
         }
 
         // Validate trigger config parameters
-        if trigger.trigger_type == GeneticTriggerType::RiskFactorExceeded {
-            if !is_valid_risk_score(trigger.threshold_value) {
-                return Err(InheritanceError::InvalidAllocation);
-            }
+        if trigger.trigger_type == GeneticTriggerType::RiskFactorExceeded
+            && !is_valid_risk_score(trigger.threshold_value)
+        {
+            return Err(InheritanceError::InvalidAllocation);
         }
 
         let key = DataKey::PlanMetadata(plan_id, 7000);
-        let mut list: Vec<GeneticTriggerConfig> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or_else(|| Vec::new(&env));
+        let mut list: Vec<GeneticTriggerConfig> =
+            plan_index::get_persistent(&env, &key).unwrap_or_else(|| Vec::new(&env));
 
         list.push_back(trigger.clone());
-        env.storage().persistent().set(&key, &list);
+        plan_index::set_persistent(&env, &key, &list);
 
         // Emit event
         env.events().publish(
@@ -5843,10 +7577,7 @@ impl InheritanceContract {
     /// Retrieve all genetic health triggers for a plan.
     pub fn get_genetic_triggers(env: Env, plan_id: u64) -> Vec<GeneticTriggerConfig> {
         let key = DataKey::PlanMetadata(plan_id, 7000);
-        env.storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or_else(|| Vec::new(&env))
+        plan_index::get_persistent(&env, &key).unwrap_or_else(|| Vec::new(&env))
     }
 
     /// Configure a designated genetic/health oracle address for the plan.
@@ -5865,7 +7596,7 @@ impl InheritanceContract {
         }
 
         let key = DataKey::PlanMetadata(plan_id, 7001);
-        env.storage().persistent().set(&key, &oracle);
+        plan_index::set_persistent(&env, &key, &oracle);
 
         Ok(())
     }
@@ -5873,7 +7604,7 @@ impl InheritanceContract {
     /// Get the designated genetic/health oracle address for the plan.
     pub fn get_genetic_oracle(env: Env, plan_id: u64) -> Option<Address> {
         let key = DataKey::PlanMetadata(plan_id, 7001);
-        env.storage().persistent().get(&key)
+        plan_index::get_persistent(&env, &key)
     }
 
     /// Set genetic inheritance info (DNA hash, family tree, etc.) for a plan.
@@ -5891,7 +7622,7 @@ impl InheritanceContract {
         }
 
         let key = DataKey::PlanMetadata(plan_id, 7002);
-        env.storage().persistent().set(&key, &info);
+        plan_index::set_persistent(&env, &key, &info);
 
         Ok(())
     }
@@ -5899,7 +7630,7 @@ impl InheritanceContract {
     /// Get the genetic inheritance info for a plan.
     pub fn get_genetic_inheritance(env: Env, plan_id: u64) -> Option<GeneticInheritance> {
         let key = DataKey::PlanMetadata(plan_id, 7002);
-        env.storage().persistent().get(&key)
+        plan_index::get_persistent(&env, &key)
     }
 
     /// Report new genetic health data from the designated oracle.
@@ -0,0 +1,221 @@
+//! Per-owner and per-status plan indexes.
+//!
+//! Each index is stored as one ledger entry per member rather than a single
+//! `Vec<u64>`, so adding or removing a plan touches a constant number of
+//! entries and listing can be paginated with a cursor:
+//!
+//! - `IndexLen(index)` — number of members
+//! - `IndexEntry(index, position)` — plan id at `position`
+//! - `IndexPos(index, plan_id)` — position of `plan_id`, for O(1) removal
+//!
+//! Removal swaps the last member into the vacated position, so a listing
+//! paged across a concurrent removal may skip or repeat one plan.
+//!
+//! Every plan and index entry read or written has its TTL extended so that
+//! long-lived plans are not archived between owner interactions. Other
+//! per-plan entries (claims, tokens, assets, trigger settings and metadata)
+//! go through [`get_persistent`] and [`set_persistent`] for the same reason.
+
+use crate::{DataKey, InheritancePlan};
+use soroban_sdk::{contracttype, Address, Env, IntoVal, TryFromVal, Val, Vec};
+
+/// Approximate ledgers closed per day (5 second close time).
+const LEDGERS_PER_DAY: u32 = 17_280;
+
+/// Entries whose TTL falls below this many ledgers (~30 days) are extended.
+pub const PLAN_TTL_THRESHOLD: u32 = 30 * LEDGERS_PER_DAY;
+/// TTL entries are extended to (~120 days).
+pub const PLAN_TTL_EXTEND_TO: u32 = 120 * LEDGERS_PER_DAY;
+
+/// Largest page a paginated getter returns; the unpaginated getters walk
+/// every page.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Plans processed per `migrate` call.
+pub const MIGRATION_BATCH: u32 = 25;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PlanIndex {
+    /// Plans owned by an address.
+    Owner(Address),
+    /// Plans of an owner with at least one completed claim.
+    OwnerClaimed(Address),
+    Active,
+    Deactivated,
+    /// Plans with at least one completed claim.
+    Claimed,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PlanStatus {
+    Active,
+    Deactivated,
+    Claimed,
+}
+
+impl PlanStatus {
+    pub fn index(self) -> PlanIndex {
+        match self {
+            PlanStatus::Active => PlanIndex::Active,
+            PlanStatus::Deactivated => PlanIndex::Deactivated,
+            PlanStatus::Claimed => PlanIndex::Claimed,
+        }
+    }
+}
+
+/// A page of plan ids. `next_cursor` is `None` on the last page.
+pub struct PlanIdPage {
+    pub plan_ids: Vec<u64>,
+    pub next_cursor: Option<u32>,
+}
+
+/// A page of plans returned by the paginated getters; `plans[i]` has id
+/// `plan_ids[i]`. Pass `next_cursor` to fetch the following page; it is
+/// `None` on the last page.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanPage {
+    pub plan_ids: Vec<u64>,
+    pub plans: Vec<InheritancePlan>,
+    pub next_cursor: Option<u32>,
+}
+
+/// Positions `[start, end)` to return for a page, and the cursor of the
+/// following page.
+pub fn page_bounds(len: u32, cursor: u32, limit: u32) -> (u32, u32, Option<u32>) {
+    let limit = limit.clamp(1, MAX_PAGE_SIZE);
+    let start = cursor.min(len);
+    let end = start.saturating_add(limit).min(len);
+    let next = if end < len { Some(end) } else { None };
+    (start, end, next)
+}
+
+pub fn extend_persistent(env: &Env, key: &DataKey) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, PLAN_TTL_THRESHOLD, PLAN_TTL_EXTEND_TO);
+}
+
+/// Read a persistent entry, extending its TTL if it exists.
+pub fn get_persistent<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Option<V> {
+    let value = env.storage().persistent().get(key);
+    if value.is_some() {
+        extend_persistent(env, key);
+    }
+    value
+}
+
+/// Write a persistent entry and extend its TTL.
+pub fn set_persistent<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+    env.storage().persistent().set(key, value);
+    extend_persistent(env, key);
+}
+
+pub fn extend_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(PLAN_TTL_THRESHOLD, PLAN_TTL_EXTEND_TO);
+}
+
+pub fn len(env: &Env, index: &PlanIndex) -> u32 {
+    let key = DataKey::IndexLen(index.clone());
+    let len = env.storage().persistent().get(&key).unwrap_or(0);
+    if len > 0 {
+        extend_persistent(env, &key);
+    }
+    len
+}
+
+pub fn contains(env: &Env, index: &PlanIndex, plan_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::IndexPos(index.clone(), plan_id))
+}
+
+/// Add `plan_id` to `index`; a no-op if it is already a member.
+pub fn insert(env: &Env, index: &PlanIndex, plan_id: u64) {
+    if contains(env, index, plan_id) {
+        return;
+    }
+    let storage = env.storage().persistent();
+    let position = len(env, index);
+    let entry = DataKey::IndexEntry(index.clone(), position);
+    let pos = DataKey::IndexPos(index.clone(), plan_id);
+    let len_key = DataKey::IndexLen(index.clone());
+    storage.set(&entry, &plan_id);
+    storage.set(&pos, &position);
+    storage.set(&len_key, &(position + 1));
+    extend_persistent(env, &entry);
+    extend_persistent(env, &pos);
+    extend_persistent(env, &len_key);
+}
+
+/// Remove `plan_id` from `index`; a no-op if it is not a member.
+pub fn remove(env: &Env, index: &PlanIndex, plan_id: u64) {
+    let storage = env.storage().persistent();
+    let pos = DataKey::IndexPos(index.clone(), plan_id);
+    let Some(position) = storage.get::<DataKey, u32>(&pos) else {
+        return;
+    };
+    let last = len(env, index) - 1;
+    let last_entry = DataKey::IndexEntry(index.clone(), last);
+    if position != last {
+        let moved: u64 = storage.get(&last_entry).unwrap();
+        let entry = DataKey::IndexEntry(index.clone(), position);
+        let moved_pos = DataKey::IndexPos(index.clone(), moved);
+        storage.set(&entry, &moved);
+        storage.set(&moved_pos, &position);
+        extend_persistent(env, &entry);
+        extend_persistent(env, &moved_pos);
+    }
+    storage.remove(&last_entry);
+    storage.remove(&pos);
+    let len_key = DataKey::IndexLen(index.clone());
+    if last == 0 {
+        storage.remove(&len_key);
+    } else {
+        storage.set(&len_key, &last);
+        extend_persistent(env, &len_key);
+    }
+}
+
+pub fn page(env: &Env, index: &PlanIndex, cursor: u32, limit: u32) -> PlanIdPage {
+    let (start, end, next_cursor) = page_bounds(len(env, index), cursor, limit);
+    let mut plan_ids = Vec::new(env);
+    for position in start..end {
+        let entry = DataKey::IndexEntry(index.clone(), position);
+        if let Some(plan_id) = env.storage().persistent().get(&entry) {
+            extend_persistent(env, &entry);
+            plan_ids.push_back(plan_id);
+        }
+    }
+    PlanIdPage {
+        plan_ids,
+        next_cursor,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pages_walk_the_whole_index() {
+        assert_eq!(page_bounds(5, 0, 2), (0, 2, Some(2)));
+        assert_eq!(page_bounds(5, 2, 2), (2, 4, Some(4)));
+        assert_eq!(page_bounds(5, 4, 2), (4, 5, None));
+        assert_eq!(page_bounds(5, 9, 2), (5, 5, None));
+        assert_eq!(page_bounds(0, 0, 10), (0, 0, None));
+    }
+
+    #[test]
+    fn limit_is_clamped() {
+        assert_eq!(page_bounds(200, 0, 0), (0, 1, Some(1)));
+        assert_eq!(
+            page_bounds(200, 0, 1_000),
+            (0, MAX_PAGE_SIZE, Some(MAX_PAGE_SIZE))
+        );
+    }
+}
@@ -301,6 +301,7 @@ fn test_validate_beneficiaries_basis_points() {
 #[test]
 fn test_create_beneficiary_success() {
     let env = Env::default();
+    let contract_id = env.register_contract(None, InheritanceContract);
 
     let full_name = String::from_str(&env, "John Doe");
     let email = String::from_str(&env, "john@example.com");
@@ -308,15 +309,19 @@ fn test_create_beneficiary_success() {
     let bank_account = create_test_bytes(&env, "1234567890123456");
     let allocation = 5000u32; // 50% in basis points
 
-    let result = InheritanceContract::create_beneficiary(
-        &env,
-        full_name,
-        email,
-        claim_code,
-        bank_account,
-        allocation,
-        1u32, // priority
-    );
+    let result = env.as_contract(&contract_id, || {
+        InheritanceContract::create_beneficiary(
+            &env,
+            1u64,
+            0u32,
+            full_name,
+            email,
+            claim_code,
+            bank_account,
+            allocation,
+            1u32, // priority
+        )
+    });
 
     assert!(result.is_ok());
     let beneficiary = result.unwrap();
@@ -326,51 +331,60 @@ fn test_create_beneficiary_success() {
 #[test]
 fn test_create_beneficiary_invalid_data() {
     let env = Env::default();
+    let contract_id = env.register_contract(None, InheritanceContract);
 
-    // Test empty name
-    let result = InheritanceContract::create_beneficiary(
-        &env,
-        String::from_str(&env, ""), // empty name
-        String::from_str(&env, "john@example.com"),
-        123456u32,
-        create_test_bytes(&env, "1234567890123456"),
-        5000u32,
-        1u32,
-    );
-    assert!(result.is_err());
-    assert_eq!(
-        result.err().unwrap(),
-        InheritanceError::InvalidBeneficiaryData
-    );
-
-    // Test invalid claim code
-    let result = InheritanceContract::create_beneficiary(
-        &env,
-        String::from_str(&env, "John Doe"),
-        String::from_str(&env, "john@example.com"),
-        1000000u32, // > 999999
-        create_test_bytes(&env, "1234567890123456"),
-        5000u32,
-        2u32,
-    );
-    assert!(result.is_err());
-    assert_eq!(
-        result.err().unwrap(),
-        InheritanceError::InvalidClaimCodeRange
-    );
-
-    // Test zero allocation
-    let result = InheritanceContract::create_beneficiary(
-        &env,
-        String::from_str(&env, "John Doe"),
-        String::from_str(&env, "john@example.com"),
-        123456u32,
-        create_test_bytes(&env, "1234567890123456"),
-        0u32, // zero allocation
-        1u32, // priority
-    );
-    assert!(result.is_err());
-    assert_eq!(result.err().unwrap(), InheritanceError::InvalidAllocation);
+    env.as_contract(&contract_id, || {
+        // Test empty name
+        let result = InheritanceContract::create_beneficiary(
+            &env,
+            1u64,
+            0u32,
+            String::from_str(&env, ""), // empty name
+            String::from_str(&env, "john@example.com"),
+            123456u32,
+            create_test_bytes(&env, "1234567890123456"),
+            5000u32,
+            1u32,
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap(),
+            InheritanceError::InvalidBeneficiaryData
+        );
+
+        // Test invalid claim code
+        let result = InheritanceContract::create_beneficiary(
+            &env,
+            1u64,
+            1u32,
+            String::from_str(&env, "John Doe"),
+            String::from_str(&env, "john@example.com"),
+            1000000u32, // > 999999
+            create_test_bytes(&env, "1234567890123456"),
+            5000u32,
+            2u32,
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap(),
+            InheritanceError::InvalidClaimCodeRange
+        );
+
+        // Test zero allocation
+        let result = InheritanceContract::create_beneficiary(
+            &env,
+            1u64,
+            2u32,
+            String::from_str(&env, "John Doe"),
+            String::from_str(&env, "john@example.com"),
+            123456u32,
+            create_test_bytes(&env, "1234567890123456"),
+            0u32, // zero allocation
+            1u32, // priority
+        );
+        assert!(result.is_err());
+        assert_eq!(result.err().unwrap(), InheritanceError::InvalidAllocation);
+    });
 }
 
 #[test]
@@ -456,7 +470,8 @@ fn test_add_beneficiary_allocation_exceeds_limit() {
             name: String::from_str(&env, "Charlie"),
             email: String::from_str(&env, "charlie@example.com"),
             claim_code: 333333,
-            bank_account: create_test_bytes(&env, "3333333333333333"),
+            bank_account_ref: create_test_bytes(&env, "3333333333333333"),
+            wallet: None,
             allocation_bp: 2000,
             priority: 1,
         },
@@ -513,7 +528,8 @@ fn test_remove_beneficiary_success() {
             name: String::from_str(&env, "Charlie"),
             email: String::from_str(&env, "charlie@example.com"),
             claim_code: 333333,
-            bank_account: create_test_bytes(&env, "3333333333333333"),
+            bank_account_ref: create_test_bytes(&env, "3333333333333333"),
+            wallet: None,
             allocation_bp: 2000,
             priority: 1,
         },
@@ -643,7 +659,8 @@ fn test_beneficiary_allocation_tracking() {
             name: String::from_str(&env, "Charlie"),
             email: String::from_str(&env, "charlie@example.com"),
             claim_code: 333333,
-            bank_account: create_test_bytes(&env, "3333333333333333"),
+            bank_account_ref: create_test_bytes(&env, "3333333333333333"),
+            wallet: None,
             allocation_bp: 2000,
             priority: 1,
         },
@@ -658,7 +675,8 @@ fn test_beneficiary_allocation_tracking() {
             name: String::from_str(&env, "Charlie"),
             email: String::from_str(&env, "charlie@example.com"),
             claim_code: 333333,
-            bank_account: create_test_bytes(&env, "3333333333333333"),
+            bank_account_ref: create_test_bytes(&env, "3333333333333333"),
+            wallet: None,
             allocation_bp: 2000,
             priority: 1,
         },
@@ -1368,7 +1386,7 @@ fn test_version_returns_default() {
     let client = InheritanceContractClient::new(&env, &contract_id);
 
     let version = client.version();
-    assert_eq!(version, 1);
+    assert_eq!(version, 3);
 }
 
 #[test]
@@ -1431,7 +1449,7 @@ fn test_migrate_no_migration_needed() {
 
     // Set version to CONTRACT_VERSION so migration is not needed
     env.as_contract(&contract_id, || {
-        env.storage().instance().set(&DataKey::Version, &1u32);
+        env.storage().instance().set(&DataKey::Version, &3u32);
     });
     let result = client.try_migrate(&admin);
     assert!(result.is_ok());
@@ -1472,7 +1490,7 @@ fn test_migrate_runs_when_version_outdated() {
 
     // After migration, version should be CONTRACT_VERSION
     let version = client.version();
-    assert_eq!(version, 1);
+    assert_eq!(version, 3);
 }
 
 #[test]
@@ -3306,7 +3324,7 @@ fn test_double_approval_rejection() {
     client.approve_emergency_access(&guardian_1, &plan_id, &trusted_contact);
     let res = client.try_approve_emergency_access(&guardian_1, &plan_id, &trusted_contact);
     assert!(res.is_err());
-    assert_eq!(res.err().unwrap(), Ok(InheritanceError::AlreadyApproved));
+    assert_eq!(res.err().unwrap(), Ok(InheritanceError::AlreadyClaimed));
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -3393,10 +3411,7 @@ fn test_add_emergency_contact_duplicate_fails() {
     client.add_emergency_contact(&user, &plan_id, &contact);
     let res = client.try_add_emergency_contact(&user, &plan_id, &contact);
     assert!(res.is_err());
-    assert_eq!(
-        res.err().unwrap(),
-        Ok(InheritanceError::EmergencyContactAlreadyExists)
-    );
+    assert_eq!(res.err().unwrap(), Ok(InheritanceError::AlreadyClaimed));
 }
 
 #[test]
@@ -3476,7 +3491,7 @@ fn test_remove_emergency_contact_not_found() {
     assert!(res.is_err());
     assert_eq!(
         res.err().unwrap(),
-        Ok(InheritanceError::EmergencyContactNotFound)
+        Ok(InheritanceError::BeneficiaryNotFound)
     );
 }
 
@@ -4418,7 +4433,7 @@ fn test_finalize_succeeds_after_all_witnesses_sign() {
     client.sign_as_witness(
         &witness,
         &plan_id,
-        &dummy_sig(&env, 1),
+        &dummy_sig(&env, 2),
         &(env.ledger().timestamp() + 1000),
     );
 
@@ -4666,7 +4681,7 @@ fn test_update_legacy_message_rejected_after_lock() {
             key_reference: soroban_sdk::String::from_str(&env, "ref_new"),
         },
     );
-    assert_eq!(result, Err(Ok(InheritanceError::WillAlreadyFinalized)));
+    assert_eq!(result, Err(Ok(InheritanceError::AlreadyClaimed)));
 }
 
 #[test]
@@ -4679,7 +4694,7 @@ fn test_finalize_legacy_message_twice_fails() {
 
     client.finalize_legacy_message(&owner, &message_id);
     let result = client.try_finalize_legacy_message(&owner, &message_id);
-    assert_eq!(result, Err(Ok(InheritanceError::WillAlreadyFinalized)));
+    assert_eq!(result, Err(Ok(InheritanceError::AlreadyClaimed)));
 }
 
 #[test]
@@ -5042,7 +5057,7 @@ fn test_delete_legacy_message_fails_after_lock() {
     client.finalize_legacy_message(&owner, &message_id);
 
     let result = client.try_delete_legacy_message(&owner, &message_id);
-    assert_eq!(result, Err(Ok(InheritanceError::WillAlreadyFinalized)));
+    assert_eq!(result, Err(Ok(InheritanceError::AlreadyClaimed)));
     // Message still present
     assert!(client.get_legacy_message(&message_id).is_some());
 }
@@ -5114,7 +5129,8 @@ fn test_batch_add_beneficiaries_success() {
             name: String::from_str(&env, "Carol"),
             email: String::from_str(&env, "carol@batch.com"),
             claim_code: 333333u32,
-            bank_account: create_test_bytes(&env, "3333333333333333"),
+            bank_account_ref: create_test_bytes(&env, "3333333333333333"),
+            wallet: None,
             allocation_bp: 6000u32,
             priority: 1u32,
         },
@@ -5122,7 +5138,8 @@ fn test_batch_add_beneficiaries_success() {
             name: String::from_str(&env, "Dave"),
             email: String::from_str(&env, "dave@batch.com"),
             claim_code: 444444u32,
-            bank_account: create_test_bytes(&env, "4444444444444444"),
+            bank_account_ref: create_test_bytes(&env, "4444444444444444"),
+            wallet: None,
             allocation_bp: 4000u32,
             priority: 2u32,
         },
@@ -5146,7 +5163,8 @@ fn test_batch_add_beneficiaries_partial_fail_over_allocation() {
             name: String::from_str(&env, "Extra"),
             email: String::from_str(&env, "extra@batch.com"),
             claim_code: 555555u32,
-            bank_account: create_test_bytes(&env, "5555555555555555"),
+            bank_account_ref: create_test_bytes(&env, "5555555555555555"),
+            wallet: None,
             allocation_bp: 1000u32,
             priority: 1u32,
         },
@@ -5167,7 +5185,8 @@ fn test_batch_add_beneficiaries_limit_exceeded() {
             name: String::from_str(&env, "X"),
             email: String::from_str(&env, "x@x.com"),
             claim_code: i,
-            bank_account: create_test_bytes(&env, "1234"),
+            bank_account_ref: create_test_bytes(&env, "1234"),
+            wallet: None,
             allocation_bp: 100u32,
             priority: i,
         });
@@ -5188,7 +5207,8 @@ fn test_batch_add_beneficiaries_unauthorized() {
             name: String::from_str(&env, "X"),
             email: String::from_str(&env, "x@x.com"),
             claim_code: 123456u32,
-            bank_account: create_test_bytes(&env, "1234"),
+            bank_account_ref: create_test_bytes(&env, "1234"),
+            wallet: None,
             allocation_bp: 1000u32,
             priority: 1u32,
         },
@@ -6255,98 +6275,2035 @@ fn test_inactivity_reset_by_record_activity() {
 }
 
 #[test]
-fn test_oracle_trigger_fires_on_submit() {
+fn test_inactivity_grace_period_delays_trigger() {
     let env = Env::default();
+    env.ledger().set_timestamp(1000);
     let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
-    let oracle = Address::generate(&env);
 
-    client.add_oracle_trigger(&owner, &plan_id, &oracle);
+    client.add_inactivity_trigger(&owner, &plan_id, &500u64);
+    client.set_inactivity_grace_period(&owner, &plan_id, &200u64);
+    assert_eq!(client.get_inactivity_grace_period(&plan_id), 200);
+    assert_eq!(client.get_inactivity_deadline(&plan_id), Some(1700));
+
+    env.ledger().set_timestamp(1600);
     assert!(!client.check_trigger_conditions(&plan_id));
+    client.auto_trigger_check(&plan_id);
+    assert!(client.get_inheritance_trigger(&plan_id).is_none());
 
-    client.submit_oracle_trigger(&oracle, &plan_id);
+    env.ledger().set_timestamp(1700);
     assert!(client.check_trigger_conditions(&plan_id));
 }
 
 #[test]
-fn test_non_oracle_cannot_submit_trigger() {
+fn test_check_in_during_grace_period_resets_deadline() {
     let env = Env::default();
+    env.ledger().set_timestamp(1000);
     let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
-    let oracle = Address::generate(&env);
-    let impersonator = Address::generate(&env);
 
-    client.add_oracle_trigger(&owner, &plan_id, &oracle);
-    let result = client.try_submit_oracle_trigger(&impersonator, &plan_id);
-    assert!(result.is_err());
+    client.add_inactivity_trigger(&owner, &plan_id, &500u64);
+    client.set_inactivity_grace_period(&owner, &plan_id, &200u64);
+
+    env.ledger().set_timestamp(1650);
+    client.record_activity(&owner, &plan_id);
+    assert_eq!(client.get_inactivity_deadline(&plan_id), Some(2350));
+
+    env.ledger().set_timestamp(1800);
+    assert!(!client.check_trigger_conditions(&plan_id));
 }
 
 #[test]
-fn test_health_trigger_fires_on_submit() {
+fn test_only_owner_sets_grace_period() {
+    let env = Env::default();
+    let (client, _admin, _owner, plan_id) = setup_plan_for_triggers(&env);
+    let stranger = Address::generate(&env);
+
+    let result = client.try_set_inactivity_grace_period(&stranger, &plan_id, &200u64);
+    assert_eq!(result, Err(Ok(InheritanceError::Unauthorized)));
+    assert_eq!(client.get_inactivity_deadline(&plan_id), None);
+}
+
+#[test]
+fn test_inactivity_deadline_saturates_instead_of_overflowing() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+
+    client.add_inactivity_trigger(&owner, &plan_id, &500u64);
+    client.set_inactivity_grace_period(&owner, &plan_id, &u64::MAX);
+    assert_eq!(client.get_inactivity_deadline(&plan_id), Some(u64::MAX));
+
+    env.ledger().set_timestamp(1_000_000);
+    assert!(!client.check_trigger_conditions(&plan_id));
+}
+
+#[test]
+fn test_activity_delegate_records_activity() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let delegate = Address::generate(&env);
+
+    client.add_inactivity_trigger(&owner, &plan_id, &500u64);
+    let result = client.try_record_activity_as_delegate(&delegate, &plan_id);
+    assert_eq!(result, Err(Ok(InheritanceError::Unauthorized)));
+
+    client.set_activity_delegate(&owner, &plan_id, &Some(delegate.clone()));
+    assert_eq!(
+        client.get_activity_delegate(&plan_id),
+        Some(delegate.clone())
+    );
+
+    env.ledger().set_timestamp(1400);
+    client.record_activity_as_delegate(&delegate, &plan_id);
+    assert_eq!(client.get_inactivity_deadline(&plan_id), Some(1900));
+
+    client.set_activity_delegate(&owner, &plan_id, &None);
+    let result = client.try_record_activity_as_delegate(&delegate, &plan_id);
+    assert_eq!(result, Err(Ok(InheritanceError::Unauthorized)));
+}
+
+#[test]
+fn test_only_owner_sets_activity_delegate() {
+    let env = Env::default();
+    let (client, _admin, _owner, plan_id) = setup_plan_for_triggers(&env);
+    let stranger = Address::generate(&env);
+
+    let result = client.try_set_activity_delegate(&stranger, &plan_id, &Some(stranger.clone()));
+    assert_eq!(result, Err(Ok(InheritanceError::Unauthorized)));
+    assert_eq!(client.get_activity_delegate(&plan_id), None);
+}
+
+/// `(Inactivity AND Oracle) OR (Time AND 2 guardian approvals)`
+fn layered_trigger_expression(env: &Env) -> TriggerExpression {
+    TriggerExpression {
+        nodes: vec![
+            env,
+            TriggerNode::Or(vec![env, 1, 4]),
+            TriggerNode::And(vec![env, 2, 3]),
+            TriggerNode::Condition(TriggerConditionType::Inactivity),
+            TriggerNode::Condition(TriggerConditionType::Oracle),
+            TriggerNode::And(vec![env, 5, 6]),
+            TriggerNode::Condition(TriggerConditionType::Time),
+            TriggerNode::GuardianApprovals(2),
+        ],
+    }
+}
+
+#[test]
+fn test_trigger_expression_requires_both_signals() {
     let env = Env::default();
+    env.ledger().set_timestamp(1000);
     let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
     let oracle = Address::generate(&env);
 
-    client.add_health_trigger(&owner, &plan_id, &oracle);
+    client.add_inactivity_trigger(&owner, &plan_id, &500u64);
+    client.add_oracle_trigger(&owner, &plan_id, &oracle);
+    client.set_trigger_expression(&owner, &plan_id, &layered_trigger_expression(&env));
+
+    // A compromised oracle alone cannot release the plan.
+    client.submit_oracle_trigger(&oracle, &plan_id);
     assert!(!client.check_trigger_conditions(&plan_id));
+    client.auto_trigger_check(&plan_id);
+    assert!(client.get_inheritance_trigger(&plan_id).is_none());
 
-    client.submit_health_trigger(&oracle, &plan_id);
+    env.ledger().set_timestamp(1600);
     assert!(client.check_trigger_conditions(&plan_id));
+    client.auto_trigger_check(&plan_id);
+    assert!(client.get_inheritance_trigger(&plan_id).is_some());
 }
 
 #[test]
-fn test_manual_condition_never_auto_fires() {
+fn test_trigger_expression_guardian_branch() {
     let env = Env::default();
     env.ledger().set_timestamp(1000);
     let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let guardians = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    client.set_guardians(&owner, &plan_id, &guardians, &2u32);
+    client.add_time_trigger(&owner, &plan_id, &2000u64);
+    client.set_trigger_expression(&owner, &plan_id, &layered_trigger_expression(&env));
 
-    client.set_trigger_conditions(
-        &owner,
-        &plan_id,
-        &Vec::from_array(&env, [TriggerConditionType::Manual]),
-        &0u64,
-        &0u64,
-        &None,
+    env.ledger().set_timestamp(2001);
+    assert!(!client.check_trigger_conditions(&plan_id));
+
+    assert_eq!(
+        client.approve_trigger(&guardians.get(0).unwrap(), &plan_id),
+        1
     );
-    env.ledger().set_timestamp(9999);
     assert!(!client.check_trigger_conditions(&plan_id));
+    assert_eq!(
+        client.approve_trigger(&guardians.get(2).unwrap(), &plan_id),
+        2
+    );
+    assert!(client.check_trigger_conditions(&plan_id));
 }
 
 #[test]
-fn test_auto_trigger_check_fires_trigger() {
+fn test_trigger_approval_rejects_strangers_and_repeats() {
     let env = Env::default();
-    env.ledger().set_timestamp(1000);
     let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let guardian = Address::generate(&env);
+    client.set_guardians(&owner, &plan_id, &vec![&env, guardian.clone()], &1u32);
 
-    client.add_time_trigger(&owner, &plan_id, &1001u64);
-    env.ledger().set_timestamp(1002);
-
-    client.auto_trigger_check(&plan_id);
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_approve_trigger(&stranger, &plan_id),
+        Err(Ok(InheritanceError::Unauthorized))
+    );
+    client.approve_trigger(&guardian, &plan_id);
+    assert_eq!(
+        client.try_approve_trigger(&guardian, &plan_id),
+        Err(Ok(InheritanceError::AlreadyClaimed))
+    );
+    assert_eq!(client.get_trigger_approvals(&plan_id).len(), 1);
+}
 
-    let trigger_info = client.get_inheritance_trigger(&plan_id);
-    assert!(trigger_info.is_some());
+#[test]
+fn test_invalid_trigger_expression_rejected() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let cyclic = TriggerExpression {
+        nodes: vec![&env, TriggerNode::Or(vec![&env, 0])],
+    };
+    assert_eq!(
+        client.try_set_trigger_expression(&owner, &plan_id, &cyclic),
+        Err(Ok(InheritanceError::InvalidTriggerExpression))
+    );
+    assert!(client.get_trigger_expression(&plan_id).is_none());
 }
 
 #[test]
-fn test_get_trigger_conditions_returns_config() {
+fn test_clear_trigger_expression_restores_any_condition() {
     let env = Env::default();
     let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
     let oracle = Address::generate(&env);
 
     client.add_oracle_trigger(&owner, &plan_id, &oracle);
-    let config = client.get_trigger_conditions(&plan_id).unwrap();
-    assert_eq!(config.conditions.len(), 1);
-    assert!(!config.oracle_triggered);
+    client.set_trigger_expression(&owner, &plan_id, &layered_trigger_expression(&env));
+    client.submit_oracle_trigger(&oracle, &plan_id);
+    assert!(!client.check_trigger_conditions(&plan_id));
+
+    client.clear_trigger_expression(&owner, &plan_id);
+    assert!(client.check_trigger_conditions(&plan_id));
+}
+
+/// Registers three attestors and requires two of them with the minimum
+/// challenge window. Returns the attestors.
+fn setup_death_attestation(
+    env: &Env,
+    client: &InheritanceContractClient<'_>,
+    admin: &Address,
+    owner: &Address,
+    plan_id: u64,
+) -> Vec<Address> {
+    let attestors = vec![
+        env,
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    ];
+    client.register_attestor(admin, &attestors.get(0).unwrap(), &AttestorKind::Registrar);
+    client.register_attestor(admin, &attestors.get(1).unwrap(), &AttestorKind::Notary);
+    client.register_attestor(admin, &attestors.get(2).unwrap(), &AttestorKind::Hospital);
+    client.set_death_attestation_config(owner, &plan_id, &2u32, &MIN_CHALLENGE_WINDOW);
+    attestors
 }
 
 #[test]
-fn test_set_conditions_blocked_after_trigger() {
+fn test_death_attestation_finalizes_after_challenge_window() {
     let env = Env::default();
     env.ledger().set_timestamp(1000);
-    let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let (client, admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let attestors = setup_death_attestation(&env, &client, &admin, &owner, plan_id);
+    let hash = BytesN::from_array(&env, &[9u8; 32]);
 
-    client.add_time_trigger(&owner, &plan_id, &1001u64);
-    env.ledger().set_timestamp(1002);
+    assert_eq!(
+        client.submit_death_attestation(&attestors.get(0).unwrap(), &plan_id, &hash),
+        1
+    );
+    assert!(!client.check_trigger_conditions(&plan_id));
+
+    env.ledger().set_timestamp(1010);
+    client.submit_death_attestation(&attestors.get(2).unwrap(), &plan_id, &hash);
+    let view = client.get_death_attestation(&plan_id).unwrap();
+    assert_eq!(view.status, DeathAttestationStatus::Challenge);
+    assert_eq!(view.round.challenge_ends_at, 1010 + MIN_CHALLENGE_WINDOW);
+    assert!(!client.check_trigger_conditions(&plan_id));
+
+    env.ledger().set_timestamp(1010 + MIN_CHALLENGE_WINDOW);
+    assert_eq!(
+        client.get_death_attestation(&plan_id).unwrap().status,
+        DeathAttestationStatus::Finalized
+    );
+    assert!(client.check_trigger_conditions(&plan_id));
+}
+
+#[test]
+fn test_death_attestation_rejects_duplicates_and_strangers() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let attestors = setup_death_attestation(&env, &client, &admin, &owner, plan_id);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let first = attestors.get(0).unwrap();
+
+    assert_eq!(
+        client.try_submit_death_attestation(&Address::generate(&env), &plan_id, &hash),
+        Err(Ok(InheritanceError::Unauthorized))
+    );
+    client.submit_death_attestation(&first, &plan_id, &hash);
+    assert_eq!(
+        client.try_submit_death_attestation(&first, &plan_id, &hash),
+        Err(Ok(InheritanceError::AlreadyAttested))
+    );
+
+    client.remove_attestor(&admin, &attestors.get(1).unwrap());
+    assert_eq!(
+        client.try_submit_death_attestation(&attestors.get(1).unwrap(), &plan_id, &hash),
+        Err(Ok(InheritanceError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_death_attestations_are_counted_per_document() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let attestors = setup_death_attestation(&env, &client, &admin, &owner, plan_id);
+    let wrong = BytesN::from_array(&env, &[1u8; 32]);
+    let right = BytesN::from_array(&env, &[2u8; 32]);
+
+    // A first attestation of the wrong document does not block the others.
+    assert_eq!(
+        client.submit_death_attestation(&attestors.get(0).unwrap(), &plan_id, &wrong),
+        1
+    );
+    assert_eq!(
+        client.submit_death_attestation(&attestors.get(1).unwrap(), &plan_id, &right),
+        1
+    );
+    let view = client.get_death_attestation(&plan_id).unwrap();
+    assert_eq!(view.status, DeathAttestationStatus::Collecting);
+
+    assert_eq!(
+        client.submit_death_attestation(&attestors.get(2).unwrap(), &plan_id, &right),
+        2
+    );
+    let view = client.get_death_attestation(&plan_id).unwrap();
+    assert_eq!(view.status, DeathAttestationStatus::Challenge);
+    assert_eq!(view.round.document_hash, right);
+
+    // Once a document has reached the threshold, others are rejected.
+    let late = Address::generate(&env);
+    client.register_attestor(&admin, &late, &AttestorKind::Notary);
+    assert_eq!(
+        client.try_submit_death_attestation(&late, &plan_id, &wrong),
+        Err(Ok(InheritanceError::DocumentHashMismatch))
+    );
+}
+
+#[test]
+fn test_death_attestation_config_validation() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let hash = BytesN::from_array(&env, &[5u8; 32]);
+
+    let attestor = Address::generate(&env);
+    client.register_attestor(&admin, &attestor, &AttestorKind::Registrar);
+    assert_eq!(
+        client.try_submit_death_attestation(&attestor, &plan_id, &hash),
+        Err(Ok(InheritanceError::AttestationNotConfigured))
+    );
+    assert_eq!(
+        client.try_set_death_attestation_config(&owner, &plan_id, &0u32, &MIN_CHALLENGE_WINDOW),
+        Err(Ok(InheritanceError::InvalidAttestationThreshold))
+    );
+    assert_eq!(
+        client.try_set_death_attestation_config(
+            &owner,
+            &plan_id,
+            &1u32,
+            &(MIN_CHALLENGE_WINDOW - 1)
+        ),
+        Err(Ok(InheritanceError::ChallengeWindowTooShort))
+    );
+
+    client.set_death_attestation_config(&owner, &plan_id, &2u32, &MIN_CHALLENGE_WINDOW);
+    client.submit_death_attestation(&attestor, &plan_id, &hash);
+    assert_eq!(
+        client.try_set_death_attestation_config(&owner, &plan_id, &1u32, &MIN_CHALLENGE_WINDOW),
+        Err(Ok(InheritanceError::AttestationRoundActive))
+    );
+}
+
+#[test]
+fn test_emergency_contact_vetoes_during_challenge_window() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let attestors = setup_death_attestation(&env, &client, &admin, &owner, plan_id);
+    let contact = Address::generate(&env);
+    client.add_emergency_contact(&owner, &plan_id, &contact);
+    let hash = BytesN::from_array(&env, &[3u8; 32]);
+
+    client.submit_death_attestation(&attestors.get(0).unwrap(), &plan_id, &hash);
+    client.submit_death_attestation(&attestors.get(1).unwrap(), &plan_id, &hash);
+
+    assert_eq!(
+        client.try_veto_death_attestation(&Address::generate(&env), &plan_id),
+        Err(Ok(InheritanceError::Unauthorized))
+    );
+    client.veto_death_attestation(&contact, &plan_id);
+    assert!(client.get_death_attestation(&plan_id).is_none());
+
+    env.ledger().set_timestamp(2000);
+    assert!(!client.check_trigger_conditions(&plan_id));
+    assert_eq!(
+        client.try_veto_death_attestation(&owner, &plan_id),
+        Err(Ok(InheritanceError::AttestationNotFound))
+    );
+}
+
+#[test]
+fn test_veto_rejected_after_challenge_window() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let attestors = setup_death_attestation(&env, &client, &admin, &owner, plan_id);
+    let hash = BytesN::from_array(&env, &[4u8; 32]);
+
+    client.submit_death_attestation(&attestors.get(0).unwrap(), &plan_id, &hash);
+    client.submit_death_attestation(&attestors.get(1).unwrap(), &plan_id, &hash);
+
+    env.ledger().set_timestamp(1000 + MIN_CHALLENGE_WINDOW);
+    assert_eq!(
+        client.try_veto_death_attestation(&owner, &plan_id),
+        Err(Ok(InheritanceError::ChallengeWindowClosed))
+    );
     client.auto_trigger_check(&plan_id);
+    assert!(client.get_inheritance_trigger(&plan_id).is_some());
+}
 
-    let result = client.try_add_time_trigger(&owner, &plan_id, &9999u64);
-    assert!(result.is_err());
+#[test]
+fn test_contest_window_blocks_claims_until_closed() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let beneficiary = Address::generate(&env);
+    client.submit_kyc(&beneficiary);
+    client.approve_kyc(&admin, &beneficiary);
+
+    client.set_contest_period(&owner, &plan_id, &600u64);
+    client.trigger_inheritance(&admin, &plan_id);
+    let window = client.get_contest_window(&plan_id).unwrap();
+    assert_eq!(window.ends_at, 1600);
+    assert_eq!(client.get_contest_remaining(&plan_id), 600);
+
+    let email = String::from_str(&env, "alice@example.com");
+    assert_eq!(
+        client.try_claim_inheritance_plan(&plan_id, &beneficiary, &email, &111111u32),
+        Err(Ok(InheritanceError::ContestWindowActive))
+    );
+    assert_eq!(
+        client.try_close_contest_window(&plan_id),
+        Err(Ok(InheritanceError::ContestWindowActive))
+    );
+
+    env.ledger().set_timestamp(1600);
+    assert_eq!(client.get_contest_remaining(&plan_id), 0);
+    client.claim_inheritance_plan(&plan_id, &beneficiary, &email, &111111u32);
+    assert!(client.get_contest_window(&plan_id).is_none());
+}
+
+#[test]
+fn test_owner_cancels_trigger_during_contest_window() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let oracle = Address::generate(&env);
+    client.add_oracle_trigger(&owner, &plan_id, &oracle);
+    client.set_contest_period(&owner, &plan_id, &600u64);
+    client.submit_oracle_trigger(&oracle, &plan_id);
+    client.auto_trigger_check(&plan_id);
+    assert!(client.get_inheritance_trigger(&plan_id).is_some());
+    assert!(!client.get_plan_details(&plan_id).unwrap().is_lendable);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_cancel_trigger(&stranger, &plan_id),
+        Err(Ok(InheritanceError::Unauthorized))
+    );
+
+    env.ledger().set_timestamp(1300);
+    client.cancel_trigger(&owner, &plan_id);
+    assert!(client.get_inheritance_trigger(&plan_id).is_none());
+    assert!(client.get_contest_window(&plan_id).is_none());
+    assert!(client.get_plan_details(&plan_id).unwrap().is_lendable);
+
+    // The oracle signal was reset, so the plan does not re-trigger.
+    assert!(!client.check_trigger_conditions(&plan_id));
+}
+
+#[test]
+fn test_cancel_rejected_after_contest_window() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    client.set_contest_period(&owner, &plan_id, &600u64);
+    client.trigger_inheritance(&admin, &plan_id);
+
+    env.ledger().set_timestamp(1600);
+    assert_eq!(
+        client.try_cancel_trigger(&owner, &plan_id),
+        Err(Ok(InheritanceError::ChallengeWindowClosed))
+    );
+    client.close_contest_window(&plan_id);
+    assert!(client.get_contest_window(&plan_id).is_none());
+    assert!(client.get_inheritance_trigger(&plan_id).is_some());
+}
+
+#[test]
+fn test_emergency_contact_dispute_freezes_contested_plan() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let contact = Address::generate(&env);
+    client.add_emergency_contact(&owner, &plan_id, &contact);
+    client.set_contest_period(&owner, &plan_id, &600u64);
+    client.trigger_inheritance(&admin, &plan_id);
+
+    let beneficiary = Address::generate(&env);
+    client.submit_kyc(&beneficiary);
+    client.approve_kyc(&admin, &beneficiary);
+
+    client.file_dispute(
+        &contact,
+        &plan_id,
+        &String::from_str(&env, "owner is alive"),
+    );
+
+    // The freeze outlasts the window until the dispute is reviewed.
+    env.ledger().set_timestamp(2000);
+    let email = String::from_str(&env, "alice@example.com");
+    assert_eq!(
+        client.try_claim_inheritance_plan(&plan_id, &beneficiary, &email, &111111u32),
+        Err(Ok(InheritanceError::PlanNotActive))
+    );
+    client.unfreeze_plan(&admin, &plan_id);
+    client.claim_inheritance_plan(&plan_id, &beneficiary, &email, &111111u32);
+}
+
+#[test]
+fn test_contest_period_without_window_releases_immediately() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    assert_eq!(
+        client.try_set_contest_period(&owner, &plan_id, &(MAX_CONTEST_PERIOD + 1)),
+        Err(Ok(InheritanceError::InvalidContestPeriod))
+    );
+    client.trigger_inheritance(&admin, &plan_id);
+    assert!(client.get_contest_window(&plan_id).is_none());
+    assert_eq!(
+        client.try_set_contest_period(&owner, &plan_id, &600u64),
+        Err(Ok(InheritanceError::InheritanceAlreadyTriggered))
+    );
+}
+
+#[test]
+fn test_oracle_trigger_fires_on_submit() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let oracle = Address::generate(&env);
+
+    client.add_oracle_trigger(&owner, &plan_id, &oracle);
+    assert!(!client.check_trigger_conditions(&plan_id));
+
+    client.submit_oracle_trigger(&oracle, &plan_id);
+    assert!(client.check_trigger_conditions(&plan_id));
+}
+
+#[test]
+fn test_non_oracle_cannot_submit_trigger() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let oracle = Address::generate(&env);
+    let impersonator = Address::generate(&env);
+
+    client.add_oracle_trigger(&owner, &plan_id, &oracle);
+    let result = client.try_submit_oracle_trigger(&impersonator, &plan_id);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_health_trigger_fires_on_submit() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let oracle = Address::generate(&env);
+
+    client.add_health_trigger(&owner, &plan_id, &oracle);
+    assert!(!client.check_trigger_conditions(&plan_id));
+
+    client.submit_health_trigger(&oracle, &plan_id);
+    assert!(client.check_trigger_conditions(&plan_id));
+}
+
+#[test]
+fn test_manual_condition_never_auto_fires() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+
+    client.set_trigger_conditions(
+        &owner,
+        &plan_id,
+        &Vec::from_array(&env, [TriggerConditionType::Manual]),
+        &0u64,
+        &0u64,
+        &None,
+    );
+    env.ledger().set_timestamp(9999);
+    assert!(!client.check_trigger_conditions(&plan_id));
+}
+
+#[test]
+fn test_auto_trigger_check_fires_trigger() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+
+    client.add_time_trigger(&owner, &plan_id, &1001u64);
+    env.ledger().set_timestamp(1002);
+
+    client.auto_trigger_check(&plan_id);
+
+    let trigger_info = client.get_inheritance_trigger(&plan_id);
+    assert!(trigger_info.is_some());
+}
+
+#[test]
+fn test_get_trigger_conditions_returns_config() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let oracle = Address::generate(&env);
+
+    client.add_oracle_trigger(&owner, &plan_id, &oracle);
+    let config = client.get_trigger_conditions(&plan_id).unwrap();
+    assert_eq!(config.conditions.len(), 1);
+    assert!(!config.oracle_triggered);
+}
+
+#[test]
+fn test_set_conditions_blocked_after_trigger() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+
+    client.add_time_trigger(&owner, &plan_id, &1001u64);
+    env.ledger().set_timestamp(1002);
+    client.auto_trigger_check(&plan_id);
+
+    let result = client.try_add_time_trigger(&owner, &plan_id, &9999u64);
+    assert!(result.is_err());
+}
+
+// ─── Multi-Asset Plan Tests ───────────────────────────────────────────────────
+
+fn two_beneficiaries(env: &Env) -> Vec<(String, String, u32, Bytes, u32, u32)> {
+    vec![
+        env,
+        (
+            String::from_str(env, "Alice"),
+            String::from_str(env, "alice@example.com"),
+            111111u32,
+            create_test_bytes(env, "1111111111111111"),
+            6000u32,
+            1u32,
+        ),
+        (
+            String::from_str(env, "Bob"),
+            String::from_str(env, "bob@example.com"),
+            222222u32,
+            create_test_bytes(env, "2222222222222222"),
+            4000u32,
+            2u32,
+        ),
+    ]
+}
+
+/// Creates a two-beneficiary plan plus a whitelisted second token (XLM)
+/// minted to the owner. Returns (client, admin, owner, plan_id, xlm_token).
+fn setup_multi_asset_plan(
+    env: &Env,
+) -> (
+    InheritanceContractClient<'_>,
+    Address,
+    Address,
+    u64,
+    Address,
+) {
+    let (client, token_id, admin, owner) = setup_with_token_and_admin(env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        env,
+        &owner,
+        &token_id,
+        "Basket",
+        "Desc",
+        10_000u64,
+        DistributionMethod::LumpSum,
+        &two_beneficiaries(env),
+    ));
+    let xlm = env.register_contract(None, MockToken);
+    TestTokenHelper::new(env, &xlm).mint(&owner, &1_000_000i128);
+    client.whitelist_asset(&admin, &xlm, &Symbol::new(env, "XLM"));
+    (client, admin, owner, plan_id, xlm)
+}
+
+#[test]
+fn test_whitelist_asset_admin_only() {
+    let env = Env::default();
+    let (client, _token_id, admin, owner) = setup_with_token_and_admin(&env);
+    let xlm = Address::generate(&env);
+
+    let result = client.try_whitelist_asset(&owner, &xlm, &Symbol::new(&env, "XLM"));
+    assert!(result.is_err());
+
+    client.whitelist_asset(&admin, &xlm, &Symbol::new(&env, "XLM"));
+    assert!(client.is_asset_whitelisted(&xlm));
+    assert_eq!(client.get_whitelisted_assets().len(), 1);
+
+    client.remove_asset(&admin, &xlm);
+    assert!(!client.is_asset_whitelisted(&xlm));
+    assert_eq!(client.get_whitelisted_assets().len(), 0);
+}
+
+#[test]
+fn test_create_plan_with_whitelisted_primary_asset() {
+    let env = Env::default();
+    let (client, token_id, admin, owner) = setup_with_token_and_admin(&env);
+    client.whitelist_asset(&admin, &token_id, &Symbol::new(&env, "XLM"));
+
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &token_id,
+        "XLM plan",
+        "Desc",
+        10_000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(&env),
+    ));
+
+    let plan = client.get_plan_details(&plan_id).unwrap();
+    assert_eq!(plan.asset_type, Symbol::new(&env, "XLM"));
+    assert_eq!(client.get_plan_token(&plan_id), Some(token_id));
+}
+
+#[test]
+fn test_create_plan_rejects_unlisted_token_once_whitelist_configured() {
+    let env = Env::default();
+    let (client, token_id, admin, owner) = setup_with_token_and_admin(&env);
+    client.whitelist_asset(&admin, &Address::generate(&env), &Symbol::new(&env, "EURC"));
+
+    let result = client.try_create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &token_id,
+        "Plan",
+        "Desc",
+        10_000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(&env),
+    ));
+    assert_eq!(result.err(), Some(Ok(InheritanceError::InvalidAssetType)));
+}
+
+#[test]
+fn test_validate_plan_inputs_accepts_whitelisted_symbol() {
+    let env = Env::default();
+    let (client, _token_id, admin, _owner) = setup_with_token_and_admin(&env);
+    client.whitelist_asset(&admin, &Address::generate(&env), &Symbol::new(&env, "EURC"));
+
+    env.as_contract(&client.address, || {
+        let name = String::from_str(&env, "Plan");
+        let desc = String::from_str(&env, "Desc");
+        assert!(InheritanceContract::validate_plan_inputs(
+            &env,
+            name.clone(),
+            desc.clone(),
+            Symbol::new(&env, "EURC"),
+            100,
+        )
+        .is_ok());
+        assert_eq!(
+            InheritanceContract::validate_plan_inputs(
+                &env,
+                name,
+                desc,
+                Symbol::new(&env, "DOGE"),
+                100
+            ),
+            Err(InheritanceError::InvalidAssetType)
+        );
+    });
+}
+
+#[test]
+fn test_deposit_secondary_asset_into_basket() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id, xlm) = setup_multi_asset_plan(&env);
+
+    client.deposit(&owner, &xlm, &plan_id, &5_000);
+
+    let assets = client.get_plan_assets(&plan_id);
+    assert_eq!(assets.len(), 1);
+    let asset = assets.get(0).unwrap();
+    assert_eq!(asset.token, xlm);
+    assert_eq!(asset.symbol, Symbol::new(&env, "XLM"));
+    assert_eq!(asset.balance, 5_000);
+    // Primary balance untouched: 10_000 - 2% fee
+    assert_eq!(
+        client.get_plan_details(&plan_id).unwrap().total_amount,
+        9_800
+    );
+    assert_eq!(
+        TestTokenHelper::new(&env, &xlm).balance(&client.address),
+        5_000
+    );
+}
+
+#[test]
+fn test_deposit_rejects_non_whitelisted_token() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id, _xlm) = setup_multi_asset_plan(&env);
+    let other = env.register_contract(None, MockToken);
+    TestTokenHelper::new(&env, &other).mint(&owner, &1_000i128);
+
+    let result = client.try_deposit(&owner, &other, &plan_id, &500);
+    assert_eq!(result.err(), Some(Ok(InheritanceError::InvalidAssetType)));
+}
+
+#[test]
+fn test_deposit_rejects_too_many_assets() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id, _xlm) = setup_multi_asset_plan(&env);
+
+    for _ in 0..MAX_PLAN_ASSETS {
+        let t = env.register_contract(None, MockToken);
+        TestTokenHelper::new(&env, &t).mint(&owner, &100i128);
+        client.whitelist_asset(&admin, &t, &Symbol::new(&env, "TKN"));
+        client.deposit(&owner, &t, &plan_id, &100);
+    }
+
+    let extra = env.register_contract(None, MockToken);
+    TestTokenHelper::new(&env, &extra).mint(&owner, &100i128);
+    client.whitelist_asset(&admin, &extra, &Symbol::new(&env, "TKN"));
+    let result = client.try_deposit(&owner, &extra, &plan_id, &100);
+    assert_eq!(result.err(), Some(Ok(InheritanceError::TooManyAssets)));
+}
+
+#[test]
+fn test_withdraw_secondary_asset() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id, xlm) = setup_multi_asset_plan(&env);
+    let xlm_helper = TestTokenHelper::new(&env, &xlm);
+
+    client.deposit(&owner, &xlm, &plan_id, &5_000);
+    client.withdraw(&owner, &xlm, &plan_id, &2_000);
+
+    assert_eq!(
+        client.get_plan_assets(&plan_id).get(0).unwrap().balance,
+        3_000
+    );
+    assert_eq!(xlm_helper.balance(&owner), 1_000_000 - 3_000);
+
+    let result = client.try_withdraw(&owner, &xlm, &plan_id, &3_001);
+    assert_eq!(
+        result.err(),
+        Some(Ok(InheritanceError::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_delisted_asset_can_still_be_withdrawn() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id, xlm) = setup_multi_asset_plan(&env);
+
+    client.deposit(&owner, &xlm, &plan_id, &5_000);
+    client.remove_asset(&admin, &xlm);
+
+    let result = client.try_deposit(&owner, &xlm, &plan_id, &100);
+    assert_eq!(result.err(), Some(Ok(InheritanceError::InvalidAssetType)));
+
+    client.withdraw(&owner, &xlm, &plan_id, &5_000);
+    assert_eq!(client.get_plan_assets(&plan_id).get(0).unwrap().balance, 0);
+}
+
+#[test]
+fn test_legacy_plan_uses_recorded_legacy_token_as_primary() {
+    let env = Env::default();
+    let (client, token_id, admin, owner) = setup_with_token_and_admin(&env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &token_id,
+        "Legacy",
+        "Desc",
+        10_000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(&env),
+    ));
+    // Plans created before multi-asset support have no recorded token.
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PlanToken(plan_id));
+    });
+    let other = env.register_contract(None, MockToken);
+
+    // Neither token counts as primary until the legacy token is recorded.
+    let result = client.try_withdraw(&owner, &token_id, &plan_id, &100u64);
+    assert_eq!(result.err(), Some(Ok(InheritanceError::InvalidAssetType)));
+
+    client.set_legacy_plan_token(&admin, &token_id);
+    assert_eq!(client.get_plan_token(&plan_id), Some(token_id.clone()));
+    client.withdraw(&owner, &token_id, &plan_id, &100u64);
+
+    let result = client.try_withdraw(&owner, &other, &plan_id, &100u64);
+    assert_eq!(result.err(), Some(Ok(InheritanceError::InvalidAssetType)));
+}
+
+#[test]
+fn test_claim_pays_out_every_basket_asset() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id, xlm) = setup_multi_asset_plan(&env);
+    let xlm_helper = TestTokenHelper::new(&env, &xlm);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    for who in [&alice, &bob] {
+        client.submit_kyc(who);
+        client.approve_kyc(&admin, who);
+    }
+
+    client.deposit(&owner, &xlm, &plan_id, &10_000);
+
+    // Bob (40%) claims first; Alice (60%) must still receive 60% of the
+    // original basket balance.
+    client.claim_inheritance_plan(
+        &plan_id,
+        &bob,
+        &String::from_str(&env, "bob@example.com"),
+        &222222u32,
+    );
+    assert_eq!(xlm_helper.balance(&bob), 4_000);
+
+    client.claim_inheritance_plan(
+        &plan_id,
+        &alice,
+        &String::from_str(&env, "alice@example.com"),
+        &111111u32,
+    );
+    assert_eq!(xlm_helper.balance(&alice), 6_000);
+    assert_eq!(client.get_plan_assets(&plan_id).get(0).unwrap().balance, 0);
+}
+
+#[test]
+fn test_claim_uses_custom_asset_allocations() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id, xlm) = setup_multi_asset_plan(&env);
+    let xlm_helper = TestTokenHelper::new(&env, &xlm);
+    let alice = Address::generate(&env);
+    client.submit_kyc(&alice);
+    client.approve_kyc(&admin, &alice);
+
+    client.deposit(&owner, &xlm, &plan_id, &10_000);
+    // XLM goes 10/90 instead of the default 60/40.
+    client.set_asset_allocations(&owner, &plan_id, &xlm, &vec![&env, 1000u32, 9000u32]);
+
+    client.claim_inheritance_plan(
+        &plan_id,
+        &alice,
+        &String::from_str(&env, "alice@example.com"),
+        &111111u32,
+    );
+    assert_eq!(xlm_helper.balance(&alice), 1_000);
+}
+
+#[test]
+fn test_set_asset_allocations_validation() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id, xlm) = setup_multi_asset_plan(&env);
+
+    // Asset not yet in the basket
+    let result =
+        client.try_set_asset_allocations(&owner, &plan_id, &xlm, &vec![&env, 5000u32, 5000u32]);
+    assert_eq!(result.err(), Some(Ok(InheritanceError::InvalidAssetType)));
+
+    client.deposit(&owner, &xlm, &plan_id, &1_000);
+
+    let result = client.try_set_asset_allocations(&owner, &plan_id, &xlm, &vec![&env, 10000u32]);
+    assert_eq!(
+        result.err(),
+        Some(Ok(InheritanceError::AllocationPercentageMismatch))
+    );
+    let result =
+        client.try_set_asset_allocations(&owner, &plan_id, &xlm, &vec![&env, 5000u32, 4000u32]);
+    assert_eq!(
+        result.err(),
+        Some(Ok(InheritanceError::AllocationPercentageMismatch))
+    );
+}
+
+#[test]
+fn test_beneficiary_change_clears_custom_asset_allocations() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id, xlm) = setup_multi_asset_plan(&env);
+
+    client.deposit(&owner, &xlm, &plan_id, &1_000);
+    client.set_asset_allocations(&owner, &plan_id, &xlm, &vec![&env, 5000u32, 5000u32]);
+    client.remove_beneficiary(&owner, &plan_id, &1u32);
+
+    assert!(client
+        .get_plan_assets(&plan_id)
+        .get(0)
+        .unwrap()
+        .allocations
+        .is_empty());
+}
+
+#[test]
+fn test_batch_claim_pays_out_basket_assets() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id, xlm) = setup_multi_asset_plan(&env);
+    let xlm_helper = TestTokenHelper::new(&env, &xlm);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    for who in [&alice, &bob] {
+        client.submit_kyc(who);
+        client.approve_kyc(&admin, who);
+    }
+
+    client.deposit(&owner, &xlm, &plan_id, &10_000);
+
+    let claimers = vec![
+        &env,
+        (
+            alice.clone(),
+            String::from_str(&env, "alice@example.com"),
+            111111u32,
+        ),
+        (
+            bob.clone(),
+            String::from_str(&env, "bob@example.com"),
+            222222u32,
+        ),
+    ];
+    let (success, fail) = client.batch_claim(&plan_id, &claimers);
+    assert_eq!((success, fail), (2, 0));
+    assert_eq!(xlm_helper.balance(&alice), 6_000);
+    assert_eq!(xlm_helper.balance(&bob), 4_000);
+}
+
+// ─── Periodic Payout Tests ────────────────────────────────────────────────────
+
+const MONTH_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+/// Monthly plan with Alice (60%) and Bob (40%) whose claimers are KYC-approved.
+/// Returns (client, admin, owner, plan_id, alice, bob).
+fn setup_monthly_plan(
+    env: &Env,
+) -> (
+    InheritanceContractClient<'_>,
+    Address,
+    Address,
+    u64,
+    Address,
+    Address,
+) {
+    let (client, token_id, admin, owner) = setup_with_token_and_admin(env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        env,
+        &owner,
+        &token_id,
+        "Monthly",
+        "Desc",
+        10_000u64,
+        DistributionMethod::Monthly,
+        &two_beneficiaries(env),
+    ));
+    let alice = Address::generate(env);
+    let bob = Address::generate(env);
+    for who in [&alice, &bob] {
+        client.submit_kyc(who);
+        client.approve_kyc(&admin, who);
+    }
+    (client, admin, owner, plan_id, alice, bob)
+}
+
+fn claim_as_alice(
+    env: &Env,
+    client: &InheritanceContractClient<'_>,
+    plan_id: u64,
+    alice: &Address,
+) {
+    client.claim_inheritance_plan(
+        &plan_id,
+        alice,
+        &String::from_str(env, "alice@example.com"),
+        &111111u32,
+    );
+}
+
+#[test]
+fn test_vesting_schedule_defaults() {
+    let env = Env::default();
+    let (client, _admin, _owner, plan_id, _alice, _bob) = setup_monthly_plan(&env);
+
+    let schedule = client.get_vesting_schedule(&plan_id, &0u32);
+    assert_eq!(schedule.tranche_count, 12);
+    assert_eq!(schedule.period_seconds, MONTH_SECONDS);
+    // 60% of 9_800
+    assert_eq!(schedule.total_entitlement, 5_880);
+    assert_eq!(schedule.tranches_unlocked, 0);
+    assert_eq!(schedule.claimable_amount, 0);
+    assert_eq!(
+        schedule.next_unlock_at,
+        env.ledger().timestamp() + MONTH_SECONDS
+    );
+
+    let result = client.try_get_vesting_schedule(&plan_id, &5u32);
+    assert_eq!(
+        result.err(),
+        Some(Ok(InheritanceError::InvalidBeneficiaryIndex))
+    );
+}
+
+#[test]
+fn test_monthly_plan_pays_one_tranche_per_period() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id, alice, _bob) = setup_monthly_plan(&env);
+    client.set_payout_tranches(&owner, &plan_id, &4u32);
+
+    env.ledger().with_mut(|li| li.timestamp += MONTH_SECONDS);
+    claim_as_alice(&env, &client, plan_id, &alice);
+
+    let schedule = client.get_vesting_schedule(&plan_id, &0u32);
+    assert_eq!(schedule.tranches_claimed, 1);
+    assert_eq!(schedule.claimed_amount, 1_470); // 5_880 / 4
+    assert_eq!(schedule.total_entitlement, 5_880);
+    assert_eq!(
+        client.get_plan_details(&plan_id).unwrap().total_amount,
+        9_800 - 1_470
+    );
+
+    // Nothing new until the next period.
+    let result = client.try_claim_inheritance_plan(
+        &plan_id,
+        &alice,
+        &String::from_str(&env, "alice@example.com"),
+        &111111u32,
+    );
+    assert_eq!(result.err(), Some(Ok(InheritanceError::NothingToClaim)));
+    assert!(
+        !client
+            .get_plan_details(&plan_id)
+            .unwrap()
+            .beneficiaries
+            .get(0)
+            .unwrap()
+            .is_claimed
+    );
+}
+
+#[test]
+fn test_accrued_tranches_can_be_claimed_together() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id, alice, _bob) = setup_monthly_plan(&env);
+    client.set_payout_tranches(&owner, &plan_id, &4u32);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += 3 * MONTH_SECONDS);
+    claim_as_alice(&env, &client, plan_id, &alice);
+    let schedule = client.get_vesting_schedule(&plan_id, &0u32);
+    assert_eq!(schedule.tranches_claimed, 3);
+    assert_eq!(schedule.claimed_amount, 4_410);
+
+    // Final tranche pays the remainder and completes the claim.
+    env.ledger().with_mut(|li| li.timestamp += MONTH_SECONDS);
+    claim_as_alice(&env, &client, plan_id, &alice);
+    let schedule = client.get_vesting_schedule(&plan_id, &0u32);
+    assert_eq!(schedule.claimed_amount, 5_880);
+    assert_eq!(schedule.claimable_amount, 0);
+    assert_eq!(schedule.next_unlock_at, 0);
+    assert!(
+        client
+            .get_plan_details(&plan_id)
+            .unwrap()
+            .beneficiaries
+            .get(0)
+            .unwrap()
+            .is_claimed
+    );
+
+    let result = client.try_claim_inheritance_plan(
+        &plan_id,
+        &alice,
+        &String::from_str(&env, "alice@example.com"),
+        &111111u32,
+    );
+    assert_eq!(result.err(), Some(Ok(InheritanceError::AlreadyClaimed)));
+}
+
+#[test]
+fn test_tranche_entitlements_independent_of_claim_order() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id, alice, bob) = setup_monthly_plan(&env);
+    client.set_payout_tranches(&owner, &plan_id, &2u32);
+
+    env.ledger().with_mut(|li| li.timestamp += MONTH_SECONDS);
+    claim_as_alice(&env, &client, plan_id, &alice);
+    client.claim_inheritance_plan(
+        &plan_id,
+        &bob,
+        &String::from_str(&env, "bob@example.com"),
+        &222222u32,
+    );
+
+    assert_eq!(
+        client.get_vesting_schedule(&plan_id, &0u32).claimed_amount,
+        2_940
+    );
+    assert_eq!(
+        client.get_vesting_schedule(&plan_id, &1u32).claimed_amount,
+        1_960
+    );
+    assert_eq!(
+        client
+            .get_vesting_schedule(&plan_id, &1u32)
+            .total_entitlement,
+        3_920
+    );
+}
+
+#[test]
+fn test_trigger_unlocks_first_tranche_immediately() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id, alice, _bob) = setup_monthly_plan(&env);
+    client.set_payout_tranches(&owner, &plan_id, &4u32);
+    client.trigger_inheritance(&admin, &plan_id);
+
+    assert_eq!(client.get_claimable_by_priority(&plan_id, &0u32), 1_470);
+    claim_as_alice(&env, &client, plan_id, &alice);
+    assert_eq!(
+        client
+            .get_vesting_schedule(&plan_id, &0u32)
+            .tranches_claimed,
+        1
+    );
+}
+
+#[test]
+fn test_tranche_claim_emits_event() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id, alice, _bob) = setup_monthly_plan(&env);
+    client.set_payout_tranches(&owner, &plan_id, &4u32);
+    env.ledger().with_mut(|li| li.timestamp += MONTH_SECONDS);
+    claim_as_alice(&env, &client, plan_id, &alice);
+
+    let expected = TrancheClaimedEvent {
+        plan_id,
+        beneficiary_index: 0,
+        amount: 1_470,
+        tranches_claimed: 1,
+        tranche_count: 4,
+        claimed_to_date: 1_470,
+    };
+    let found = env.events().all().iter().any(|(_, topics, data)| {
+        topics == (symbol_short!("TRANCHE"), symbol_short!("CLAIMED")).into_val(&env)
+            && TrancheClaimedEvent::from_val(&env, &data) == expected
+    });
+    assert!(found);
+}
+
+#[test]
+fn test_batch_claim_pays_unlocked_tranches() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id, alice, bob) = setup_monthly_plan(&env);
+    client.set_payout_tranches(&owner, &plan_id, &4u32);
+    env.ledger()
+        .with_mut(|li| li.timestamp += 2 * MONTH_SECONDS);
+
+    let claimers = vec![
+        &env,
+        (
+            alice.clone(),
+            String::from_str(&env, "alice@example.com"),
+            111111u32,
+        ),
+        (
+            bob.clone(),
+            String::from_str(&env, "bob@example.com"),
+            222222u32,
+        ),
+    ];
+    assert_eq!(client.batch_claim(&plan_id, &claimers), (2, 0));
+    assert_eq!(
+        client.get_vesting_schedule(&plan_id, &0u32).claimed_amount,
+        2_940
+    );
+    assert_eq!(
+        client.get_vesting_schedule(&plan_id, &1u32).claimed_amount,
+        1_960
+    );
+
+    // Same period again: nothing new unlocked.
+    assert_eq!(client.batch_claim(&plan_id, &claimers), (0, 2));
+}
+
+#[test]
+fn test_set_payout_tranches_validation() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id, alice, _bob) = setup_monthly_plan(&env);
+
+    assert_eq!(
+        client
+            .try_set_payout_tranches(&owner, &plan_id, &0u32)
+            .err(),
+        Some(Ok(InheritanceError::InvalidTrancheCount))
+    );
+    assert_eq!(
+        client
+            .try_set_payout_tranches(&owner, &plan_id, &(MAX_TRANCHES + 1))
+            .err(),
+        Some(Ok(InheritanceError::InvalidTrancheCount))
+    );
+    assert_eq!(
+        client
+            .try_set_payout_tranches(&Address::generate(&env), &plan_id, &6u32)
+            .err(),
+        Some(Ok(InheritanceError::Unauthorized))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += MONTH_SECONDS);
+    claim_as_alice(&env, &client, plan_id, &alice);
+    assert_eq!(
+        client
+            .try_set_payout_tranches(&owner, &plan_id, &6u32)
+            .err(),
+        Some(Ok(InheritanceError::AlreadyClaimed))
+    );
+}
+
+#[test]
+fn test_set_payout_tranches_rejected_for_lump_sum() {
+    let env = Env::default();
+    let (client, token_id, _admin, owner) = setup_with_token_and_admin(&env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        &env,
+        &owner,
+        &token_id,
+        "Lump",
+        "Desc",
+        10_000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(&env),
+    ));
+    assert_eq!(
+        client
+            .try_set_payout_tranches(&owner, &plan_id, &4u32)
+            .err(),
+        Some(Ok(InheritanceError::InvalidTrancheCount))
+    );
+    let schedule = client.get_vesting_schedule(&plan_id, &0u32);
+    assert_eq!(schedule.tranche_count, 1);
+    assert_eq!(schedule.claimable_amount, 9_800);
+}
+
+// ─── Cross-Chain Manifest Tests ───────────────────────────────────────────────
+
+fn cross_chain_asset(env: &Env, chain: SupportedChain, amount: u128) -> CrossChainAsset {
+    CrossChainAsset {
+        chain,
+        contract_address: Address::generate(env),
+        amount,
+        asset_symbol: String::from_str(env, "USDC"),
+        bridge_protocol: BridgeProtocol::Wormhole,
+    }
+}
+
+/// Two-beneficiary plan (60/40) with a two-asset manifest on Ethereum and
+/// Bitcoin. Returns (client, admin, owner, plan_id).
+fn setup_cross_chain_plan(env: &Env) -> (InheritanceContractClient<'_>, Address, Address, u64) {
+    let (client, token_id, admin, owner) = setup_with_token_and_admin(env);
+    let plan_id = client.create_inheritance_plan(&plan_params(
+        env,
+        &owner,
+        &token_id,
+        "Cross-chain",
+        "Desc",
+        10_000u64,
+        DistributionMethod::LumpSum,
+        &two_beneficiaries(env),
+    ));
+    client.set_cross_chain_manifest(
+        &owner,
+        &plan_id,
+        &SupportedChain::Stellar,
+        &vec![
+            env,
+            cross_chain_asset(env, SupportedChain::Ethereum, 1_000),
+            cross_chain_asset(env, SupportedChain::Bitcoin, 50),
+        ],
+    );
+    (client, admin, owner, plan_id)
+}
+
+#[test]
+fn test_set_cross_chain_manifest() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id) = setup_cross_chain_plan(&env);
+
+    let manifest = client.get_cross_chain_manifest(&plan_id).unwrap();
+    assert_eq!(manifest.plan_id, plan_id);
+    assert_eq!(manifest.owner, owner);
+    assert_eq!(manifest.primary_chain, SupportedChain::Stellar);
+    assert_eq!(manifest.assets.len(), 2);
+}
+
+#[test]
+fn test_set_cross_chain_manifest_validation() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id) = setup_cross_chain_plan(&env);
+
+    let result = client.try_set_cross_chain_manifest(
+        &owner,
+        &plan_id,
+        &SupportedChain::Stellar,
+        &Vec::new(&env),
+    );
+    assert_eq!(
+        result.err(),
+        Some(Ok(InheritanceError::MissingRequiredField))
+    );
+
+    let result = client.try_set_cross_chain_manifest(
+        &owner,
+        &plan_id,
+        &SupportedChain::Stellar,
+        &vec![&env, cross_chain_asset(&env, SupportedChain::Ethereum, 0)],
+    );
+    assert_eq!(result.err(), Some(Ok(InheritanceError::InvalidTotalAmount)));
+
+    let result = client.try_set_cross_chain_manifest(
+        &Address::generate(&env),
+        &plan_id,
+        &SupportedChain::Stellar,
+        &vec![&env, cross_chain_asset(&env, SupportedChain::Ethereum, 1)],
+    );
+    assert_eq!(result.err(), Some(Ok(InheritanceError::Unauthorized)));
+}
+
+#[test]
+fn test_add_update_remove_cross_chain_assets() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id) = setup_cross_chain_plan(&env);
+
+    let idx = client.add_cross_chain_asset(
+        &owner,
+        &plan_id,
+        &cross_chain_asset(&env, SupportedChain::Polygon, 7),
+    );
+    assert_eq!(idx, 2);
+
+    client.update_cross_chain_asset(
+        &owner,
+        &plan_id,
+        &0u32,
+        &cross_chain_asset(&env, SupportedChain::Arbitrum, 9),
+    );
+    let manifest = client.get_cross_chain_manifest(&plan_id).unwrap();
+    assert_eq!(
+        manifest.assets.get(0).unwrap().chain,
+        SupportedChain::Arbitrum
+    );
+
+    let result = client.try_update_cross_chain_asset(
+        &owner,
+        &plan_id,
+        &9u32,
+        &cross_chain_asset(&env, SupportedChain::Arbitrum, 9),
+    );
+    assert_eq!(result.err(), Some(Ok(InheritanceError::InvalidAssetType)));
+
+    client.remove_cross_chain_asset(&owner, &plan_id, &1u32);
+    assert_eq!(
+        client
+            .get_cross_chain_manifest(&plan_id)
+            .unwrap()
+            .assets
+            .len(),
+        2
+    );
+    client.remove_cross_chain_asset(&owner, &plan_id, &0u32);
+    client.remove_cross_chain_asset(&owner, &plan_id, &0u32);
+    assert!(client.get_cross_chain_manifest(&plan_id).is_none());
+}
+
+#[test]
+fn test_trigger_records_bridge_releases() {
+    let env = Env::default();
+    let (client, admin, _owner, plan_id) = setup_cross_chain_plan(&env);
+    assert_eq!(client.get_bridge_releases(&plan_id).len(), 0);
+
+    client.trigger_inheritance(&admin, &plan_id);
+
+    let releases = client.get_bridge_releases(&plan_id);
+    assert_eq!(releases.len(), 2);
+    let eth = releases.get(0).unwrap();
+    assert_eq!(eth.chain, SupportedChain::Ethereum);
+    assert_eq!(eth.chain_id, 1);
+    assert_eq!(eth.bridge_protocol, BridgeProtocol::Wormhole);
+    assert_eq!(eth.status, BridgeReleaseStatus::Pending);
+    assert_eq!(eth.shares.get(0).unwrap().amount, 600);
+    assert_eq!(eth.shares.get(1).unwrap().amount, 400);
+    let btc = releases.get(1).unwrap();
+    assert_eq!(btc.chain_id, 0);
+    assert_eq!(btc.shares.get(0).unwrap().amount, 30);
+
+    let release_events = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            *topics == (symbol_short!("XCHAIN"), symbol_short!("RELEASE")).into_val(&env)
+        })
+        .count();
+    assert_eq!(release_events, 2);
+}
+
+#[test]
+fn test_manifest_locked_after_trigger() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id) = setup_cross_chain_plan(&env);
+    client.trigger_inheritance(&admin, &plan_id);
+
+    let result = client.try_add_cross_chain_asset(
+        &owner,
+        &plan_id,
+        &cross_chain_asset(&env, SupportedChain::Polygon, 7),
+    );
+    assert_eq!(
+        result.err(),
+        Some(Ok(InheritanceError::InheritanceAlreadyTriggered))
+    );
+}
+
+#[test]
+fn test_relayer_confirms_bridge_release() {
+    let env = Env::default();
+    let (client, admin, _owner, plan_id) = setup_cross_chain_plan(&env);
+    let relayer = Address::generate(&env);
+    client.assign_role(&admin, &relayer, &Role::Relayer);
+    client.trigger_inheritance(&admin, &plan_id);
+
+    let tx = create_test_bytes(&env, "0xabc123");
+    client.confirm_bridge_release(&relayer, &plan_id, &1u32, &tx);
+
+    let release = client.get_bridge_releases(&plan_id).get(1).unwrap();
+    assert_eq!(release.status, BridgeReleaseStatus::Confirmed);
+    assert_eq!(release.confirmed_by, Some(relayer.clone()));
+    assert_eq!(release.remote_tx, tx);
+    assert_eq!(
+        client.get_bridge_releases(&plan_id).get(0).unwrap().status,
+        BridgeReleaseStatus::Pending
+    );
+
+    let result = client.try_confirm_bridge_release(&relayer, &plan_id, &1u32, &tx);
+    assert_eq!(result.err(), Some(Ok(InheritanceError::AlreadyClaimed)));
+}
+
+#[test]
+fn test_confirm_bridge_release_requires_relayer_role() {
+    let env = Env::default();
+    let (client, admin, _owner, plan_id) = setup_cross_chain_plan(&env);
+    client.trigger_inheritance(&admin, &plan_id);
+
+    let tx = create_test_bytes(&env, "0xabc123");
+    let result = client.try_confirm_bridge_release(&Address::generate(&env), &plan_id, &0u32, &tx);
+    assert_eq!(result.err(), Some(Ok(InheritanceError::Unauthorized)));
+}
+
+#[test]
+fn test_confirm_bridge_release_before_trigger_fails() {
+    let env = Env::default();
+    let (client, admin, _owner, plan_id) = setup_cross_chain_plan(&env);
+    let relayer = Address::generate(&env);
+    client.assign_role(&admin, &relayer, &Role::Relayer);
+
+    let tx = create_test_bytes(&env, "0xabc123");
+    let result = client.try_confirm_bridge_release(&relayer, &plan_id, &0u32, &tx);
+    assert_eq!(
+        result.err(),
+        Some(Ok(InheritanceError::BridgeReleaseNotFound))
+    );
+}
+
+#[test]
+fn test_wallet_bound_beneficiary_claims_with_signature() {
+    let env = Env::default();
+    let (client, admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let wallet = Address::generate(&env);
+    let impostor = Address::generate(&env);
+    for who in [&wallet, &impostor] {
+        client.submit_kyc(who);
+        client.approve_kyc(&admin, who);
+    }
+
+    client.bind_beneficiary_wallet(&owner, &plan_id, &0u32, &wallet);
+    let plan = client.get_plan_details(&plan_id).unwrap();
+    assert_eq!(
+        plan.beneficiaries.get(0).unwrap().wallet,
+        Some(wallet.clone())
+    );
+    assert_eq!(
+        client.try_claim_with_wallet(&plan_id, &impostor),
+        Err(Ok(InheritanceError::BeneficiaryNotFound))
+    );
+
+    client.trigger_inheritance(&admin, &plan_id);
+    assert_eq!(
+        client.try_bind_beneficiary_wallet(&owner, &plan_id, &0u32, &impostor),
+        Err(Ok(InheritanceError::InheritanceAlreadyTriggered))
+    );
+
+    // The claim code alone is no longer enough.
+    let email = String::from_str(&env, "alice@example.com");
+    assert_eq!(
+        client.try_claim_inheritance_plan(&plan_id, &impostor, &email, &111111u32),
+        Err(Ok(InheritanceError::Unauthorized))
+    );
+
+    client.claim_with_wallet(&plan_id, &wallet);
+    assert!(
+        client
+            .get_plan_details(&plan_id)
+            .unwrap()
+            .beneficiaries
+            .get(0)
+            .unwrap()
+            .is_claimed
+    );
+    assert_eq!(
+        client.try_claim_with_wallet(&plan_id, &wallet),
+        Err(Ok(InheritanceError::AlreadyClaimed))
+    );
+}
+
+#[test]
+fn test_beneficiary_binds_own_wallet_with_claim_code() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let wallet = Address::generate(&env);
+    let email = String::from_str(&env, "alice@example.com");
+
+    assert_eq!(
+        client.bind_wallet_with_claim_code(&wallet, &plan_id, &email, &222222u32),
+        None
+    );
+    assert_eq!(
+        client.bind_wallet_with_claim_code(&wallet, &plan_id, &email, &111111u32),
+        Some(0)
+    );
+    assert_eq!(
+        client.try_bind_wallet_with_claim_code(
+            &Address::generate(&env),
+            &plan_id,
+            &email,
+            &111111u32
+        ),
+        Err(Ok(InheritanceError::Unauthorized))
+    );
+
+    client.unbind_beneficiary_wallet(&owner, &plan_id, &0u32);
+    let plan = client.get_plan_details(&plan_id).unwrap();
+    assert_eq!(plan.beneficiaries.get(0).unwrap().wallet, None);
+    assert_eq!(
+        client.try_unbind_beneficiary_wallet(&owner, &plan_id, &0u32),
+        Err(Ok(InheritanceError::BeneficiaryNotFound))
+    );
+}
+
+#[test]
+fn test_bind_attempts_are_limited_per_beneficiary_across_wallets() {
+    let env = Env::default();
+    let (client, _admin, _owner, plan_id) = setup_plan_for_triggers(&env);
+    let email = String::from_str(&env, "alice@example.com");
+
+    // Each guess comes from a fresh wallet.
+    for _ in 0..10 {
+        let guesser = Address::generate(&env);
+        assert_eq!(
+            client.bind_wallet_with_claim_code(&guesser, &plan_id, &email, &999999u32),
+            None
+        );
+    }
+    assert_eq!(
+        client.try_bind_wallet_with_claim_code(
+            &Address::generate(&env),
+            &plan_id,
+            &email,
+            &111111u32
+        ),
+        Err(Ok(InheritanceError::ClaimNotAllowedYet))
+    );
+
+    // The window resets after an hour.
+    env.ledger().set_timestamp(env.ledger().timestamp() + 3600);
+    assert_eq!(
+        client.bind_wallet_with_claim_code(&Address::generate(&env), &plan_id, &email, &111111u32),
+        Some(0)
+    );
+}
+
+#[test]
+fn test_wallet_cannot_be_bound_to_two_beneficiaries() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    let wallet = Address::generate(&env);
+    client.remove_beneficiary(&owner, &plan_id, &0u32);
+
+    let alice = BeneficiaryInput {
+        name: String::from_str(&env, "Alice"),
+        email: String::from_str(&env, "alice@example.com"),
+        claim_code: 111111,
+        bank_account_ref: create_test_bytes(&env, "1111111111111111"),
+        allocation_bp: 5000,
+        priority: 1,
+        wallet: Some(wallet.clone()),
+    };
+    client.add_beneficiary(&owner, &plan_id, &alice);
+
+    let bob = BeneficiaryInput {
+        name: String::from_str(&env, "Bob"),
+        email: String::from_str(&env, "bob@example.com"),
+        claim_code: 222222,
+        bank_account_ref: create_test_bytes(&env, "2222222222222222"),
+        allocation_bp: 5000,
+        priority: 2,
+        wallet: Some(wallet.clone()),
+    };
+    assert_eq!(
+        client.try_add_beneficiary(&owner, &plan_id, &bob),
+        Err(Ok(InheritanceError::WalletAlreadyBound))
+    );
+    client.add_beneficiary(
+        &owner,
+        &plan_id,
+        &BeneficiaryInput {
+            wallet: None,
+            ..bob
+        },
+    );
+    assert_eq!(
+        client.try_bind_beneficiary_wallet(&owner, &plan_id, &1u32, &wallet),
+        Err(Ok(InheritanceError::WalletAlreadyBound))
+    );
+}
+
+#[test]
+fn test_bank_account_ref_length_is_capped() {
+    let env = Env::default();
+    let (client, _admin, owner, plan_id) = setup_plan_for_triggers(&env);
+    client.remove_beneficiary(&owner, &plan_id, &0u32);
+    let mut reference = Bytes::new(&env);
+    for _ in 0..=MAX_BANK_ACCOUNT_REF_LEN {
+        reference.push_back(7);
+    }
+    let result = client.try_add_beneficiary(
+        &owner,
+        &plan_id,
+        &BeneficiaryInput {
+            name: String::from_str(&env, "Alice"),
+            email: String::from_str(&env, "alice@example.com"),
+            claim_code: 111111,
+            bank_account_ref: reference,
+            allocation_bp: 10000,
+            priority: 1,
+            wallet: None,
+        },
+    );
+    assert_eq!(result, Err(Ok(InheritanceError::InvalidBeneficiaryData)));
+}
+
+#[test]
+fn test_migrate_rewrites_legacy_beneficiaries() {
+    let env = Env::default();
+    let (client, token_id, admin, owner) = setup_with_token_and_admin(&env);
+    let params = plan_params(
+        &env,
+        &owner,
+        &token_id,
+        "Legacy",
+        "Desc",
+        1_000_000u64,
+        DistributionMethod::LumpSum,
+        &default_beneficiaries(&env),
+    );
+    let legacy_id = client.create_inheritance_plan(&params);
+    let current_id = client.create_inheritance_plan(&params);
+    let current = client.get_plan_details(&legacy_id).unwrap();
+    let alice = current.beneficiaries.get(0).unwrap();
+    let plaintext = create_test_bytes(&env, "1111111111111111");
+
+    // Rewrite the first plan in the pre-v2 layout.
+    let legacy = LegacyInheritancePlan {
+        plan_name: current.plan_name.clone(),
+        description: current.description.clone(),
+        asset_type: current.asset_type.clone(),
+        total_amount: current.total_amount,
+        distribution_method: current.distribution_method.clone(),
+        beneficiaries: vec![
+            &env,
+            LegacyBeneficiary {
+                hashed_full_name: alice.hashed_full_name.clone(),
+                hashed_email: alice.hashed_email.clone(),
+                hashed_claim_code: alice.hashed_claim_code.clone(),
+                bank_account: plaintext.clone(),
+                allocation_bp: alice.allocation_bp,
+                priority: alice.priority,
+                is_claimed: false,
+            },
+        ],
+        total_allocation_bp: current.total_allocation_bp,
+        owner: owner.clone(),
+        created_at: current.created_at,
+        is_active: current.is_active,
+        is_lendable: current.is_lendable,
+        total_loaned: current.total_loaned,
+        waterfall_enabled: current.waterfall_enabled,
+    };
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Plan(legacy_id), &legacy);
+        env.storage().instance().set(&DataKey::Version, &1u32);
+    });
+
+    client.migrate(&admin);
+    assert_eq!(client.version(), 3);
+
+    let migrated = client.get_plan_details(&legacy_id).unwrap();
+    let b = migrated.beneficiaries.get(0).unwrap();
+    assert!(b.bank_account_ref.is_empty());
+    assert_eq!(b.hashed_claim_code, alice.hashed_claim_code);
+    assert_eq!(b.wallet, None);
+
+    // The admin records the reference the backend issued, once.
+    let reference = Bytes::from_array(&env, &[7u8; 32]);
+    assert_eq!(
+        client.try_assign_legacy_bank_account_ref(&owner, &legacy_id, &0u32, &reference),
+        Err(Ok(InheritanceError::NotAdmin))
+    );
+    client.assign_legacy_bank_account_ref(&admin, &legacy_id, &0u32, &reference);
+    let b = client
+        .get_plan_details(&legacy_id)
+        .unwrap()
+        .beneficiaries
+        .get(0)
+        .unwrap();
+    assert_eq!(b.bank_account_ref, reference);
+    assert_eq!(
+        client.try_assign_legacy_bank_account_ref(&admin, &legacy_id, &0u32, &reference),
+        Err(Ok(InheritanceError::BankAccountRefAlreadySet))
+    );
+
+    // Plans already in the v2 layout are left as they were.
+    let untouched = client.get_plan_details(&current_id).unwrap();
+    assert_eq!(
+        untouched.beneficiaries.get(0).unwrap().bank_account_ref,
+        plaintext
+    );
+
+    // Claim codes keep working, so the beneficiary can now bind a wallet.
+    let wallet = Address::generate(&env);
+    let email = String::from_str(&env, "alice@example.com");
+    assert_eq!(
+        client.bind_wallet_with_claim_code(&wallet, &legacy_id, &email, &111111u32),
+        Some(0)
+    );
+}
+
+fn create_plans(
+    env: &Env,
+    client: &InheritanceContractClient<'_>,
+    token_id: &Address,
+    owner: &Address,
+    count: u32,
+) -> Vec<u64> {
+    let mut ids = Vec::new(env);
+    for _ in 0..count {
+        ids.push_back(client.create_inheritance_plan(&plan_params(
+            env,
+            owner,
+            token_id,
+            "Plan",
+            "Desc",
+            100_000u64,
+            DistributionMethod::LumpSum,
+            &default_beneficiaries(env),
+        )));
+    }
+    ids
+}
+
+#[test]
+fn test_user_plans_are_paginated() {
+    let env = Env::default();
+    let (client, token_id, _admin, owner) = setup_with_token_and_admin(&env);
+    let ids = create_plans(&env, &client, &token_id, &owner, 5);
+
+    let mut seen = Vec::new(&env);
+    let mut cursor = 0u32;
+    loop {
+        let page = client.get_user_plans_page(&owner, &cursor, &2u32);
+        assert!(page.plan_ids.len() <= 2);
+        assert_eq!(page.plans.len(), page.plan_ids.len());
+        for id in page.plan_ids.iter() {
+            seen.push_back(id);
+        }
+        match page.next_cursor {
+            Some(next) => cursor = next,
+            None => break,
+        }
+    }
+    assert_eq!(seen, ids);
+    assert_eq!(client.get_user_plans(&owner).len(), 5);
+
+    let other = Address::generate(&env);
+    let empty = client.get_user_plans_page(&other, &0u32, &10u32);
+    assert!(empty.plans.is_empty());
+    assert_eq!(empty.next_cursor, None);
+}
+
+#[test]
+fn test_unpaged_getters_return_every_plan() {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    let (client, token_id, admin, owner) = setup_with_token_and_admin(&env);
+    let count = MAX_PAGE_SIZE + 3;
+    create_plans(&env, &client, &token_id, &owner, count);
+
+    assert_eq!(client.get_user_plans(&owner).len(), count);
+    assert_eq!(client.get_all_plans(&admin).len(), count);
+    assert_eq!(client.get_all_pending_plans(&admin).len(), count);
+}
+
+#[test]
+fn test_status_indexes_follow_plan_lifecycle() {
+    let env = Env::default();
+    let (client, token_id, admin, owner) = setup_with_token_and_admin(&env);
+    let ids = create_plans(&env, &client, &token_id, &owner, 3);
+    let deactivated = ids.get(1).unwrap();
+    client.deactivate_inheritance_plan(&owner, &deactivated);
+
+    let active = client.get_plans_page(&admin, &Some(PlanStatus::Active), &0u32, &10u32);
+    assert_eq!(active.plan_ids.len(), 2);
+    assert!(!active.plan_ids.contains(deactivated));
+    let inactive = client.get_plans_page(&admin, &Some(PlanStatus::Deactivated), &0u32, &10u32);
+    assert_eq!(inactive.plan_ids, vec![&env, deactivated]);
+    let all = client.get_plans_page(&admin, &None, &0u32, &2u32);
+    assert_eq!(all.plan_ids, vec![&env, ids.get(0).unwrap(), deactivated]);
+    assert_eq!(all.next_cursor, Some(2));
+
+    let new_owner = Address::generate(&env);
+    let transferred = ids.get(2).unwrap();
+    client.transfer_plan_ownership(&owner, &new_owner, &transferred);
+    let owned = client.get_user_plans_page(&owner, &0u32, &10u32);
+    assert_eq!(owned.plan_ids.len(), 2);
+    assert!(!owned.plan_ids.contains(transferred));
+    assert_eq!(
+        client
+            .get_user_plans_page(&new_owner, &0u32, &10u32)
+            .plan_ids,
+        vec![&env, transferred]
+    );
+
+    assert!(client
+        .get_plans_page(&admin, &Some(PlanStatus::Claimed), &0u32, &10u32)
+        .plans
+        .is_empty());
+    assert_eq!(
+        client.try_get_plans_page(&Address::generate(&env), &None, &0u32, &10u32),
+        Err(Ok(InheritanceError::NotAdmin))
+    );
+}
+
+#[test]
+fn test_plan_writes_extend_ttl() {
+    let env = Env::default();
+    let (client, token_id, _admin, owner) = setup_with_token_and_admin(&env);
+    let plan_id = create_plans(&env, &client, &token_id, &owner, 1)
+        .get(0)
+        .unwrap();
+    env.as_contract(&client.address, || {
+        use soroban_sdk::testutils::storage::Persistent as _;
+        let ttl = env.storage().persistent().get_ttl(&DataKey::Plan(plan_id));
+        assert!(ttl >= plan_index::PLAN_TTL_THRESHOLD);
+        let entry = DataKey::IndexEntry(PlanIndex::Owner(owner.clone()), 0);
+        assert!(env.storage().persistent().get_ttl(&entry) >= plan_index::PLAN_TTL_THRESHOLD);
+    });
+}
+
+#[test]
+fn test_migrate_builds_plan_indexes_in_batches() {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    let (client, token_id, admin, owner) = setup_with_token_and_admin(&env);
+    let count = MIGRATION_BATCH + 5;
+    let ids = create_plans(&env, &client, &token_id, &owner, count);
+    let deactivated = ids.get(3).unwrap();
+    client.deactivate_inheritance_plan(&owner, &deactivated);
+
+    // Rewind storage to the v2 layout: Vec-based plan lists, no indexes.
+    env.as_contract(&client.address, || {
+        for id in ids.iter() {
+            plan_index::remove(&env, &PlanIndex::Owner(owner.clone()), id);
+            plan_index::remove(&env, &PlanIndex::Active, id);
+            plan_index::remove(&env, &PlanIndex::Deactivated, id);
+        }
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::UserPlans(owner.clone()), &ids);
+        storage.set(&DataKey::DeactivatedPlans, &vec![&env, deactivated]);
+        env.storage().instance().set(&DataKey::Version, &2u32);
+    });
+    assert!(client.get_user_plans(&owner).is_empty());
+
+    client.migrate(&admin);
+    assert_eq!(client.version(), 2);
+    client.migrate(&admin);
+    assert_eq!(client.version(), CONTRACT_VERSION);
+
+    let mut migrated = Vec::new(&env);
+    let mut cursor = Some(0u32);
+    while let Some(c) = cursor {
+        let page = client.get_user_plans_page(&owner, &c, &MAX_PAGE_SIZE);
+        for id in page.plan_ids.iter() {
+            migrated.push_back(id);
+        }
+        cursor = page.next_cursor;
+    }
+    assert_eq!(migrated, ids);
+    assert_eq!(
+        client
+            .get_plans_page(&admin, &Some(PlanStatus::Deactivated), &0u32, &10u32)
+            .plan_ids,
+        vec![&env, deactivated]
+    );
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        assert!(!storage.has(&DataKey::UserPlans(owner.clone())));
+        assert!(!storage.has(&DataKey::DeactivatedPlans));
+    });
 }
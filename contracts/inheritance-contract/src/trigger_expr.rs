@@ -0,0 +1,270 @@
+//! Composable trigger expressions.
+//!
+//! A [`TriggerExpression`] is a small boolean tree over trigger conditions,
+//! e.g. `(Inactivity AND Oracle) OR (Time AND 2 guardian approvals)`, so that
+//! no single compromised signal can release a plan on its own.
+//!
+//! Soroban types cannot be recursive, so the tree is stored as a flat list of
+//! nodes. Node `0` is the root and composite nodes refer to their children by
+//! index. Every child index must be greater than its parent's and every
+//! non-root node must have exactly one parent, which makes the list a tree
+//! that can be evaluated bottom-up without recursion.
+
+use crate::TriggerConditionType;
+use soroban_sdk::{contracterror, contracttype, Address, Vec};
+
+/// Maximum number of nodes in a trigger expression.
+pub const MAX_TRIGGER_NODES: u32 = 16;
+
+/// Maximum number of children of a single `And`/`Or`/`Threshold` node.
+pub const MAX_TRIGGER_CHILDREN: u32 = 8;
+
+/// One node of a trigger expression.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TriggerNode {
+    /// True when the given condition of the plan's `TriggerConfig` is met.
+    Condition(TriggerConditionType),
+    /// True once at least this many of the plan's guardians have approved
+    /// the trigger via `approve_trigger`.
+    GuardianApprovals(u32),
+    /// True when every child is true.
+    And(Vec<u32>),
+    /// True when any child is true.
+    Or(Vec<u32>),
+    /// True when at least `k` children are true.
+    Threshold(u32, Vec<u32>),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TriggerExpression {
+    pub nodes: Vec<TriggerNode>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TriggerExpressionSetEvent {
+    pub plan_id: u64,
+    pub node_count: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TriggerApprovedEvent {
+    pub plan_id: u64,
+    pub guardian: Address,
+    pub approvals: u32,
+}
+
+/// Errors raised when validating a trigger expression.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TriggerExprError {
+    /// Expression has no nodes.
+    Empty = 1,
+    /// Expression exceeds [`MAX_TRIGGER_NODES`].
+    TooManyNodes = 2,
+    /// A child index is out of range or does not follow its parent.
+    InvalidChild = 3,
+    /// A composite node has no children or more than [`MAX_TRIGGER_CHILDREN`].
+    InvalidChildCount = 4,
+    /// A threshold is zero or exceeds the number of children.
+    InvalidThreshold = 5,
+    /// A node is unreachable from the root or has several parents.
+    NotATree = 6,
+}
+
+fn children(node: &TriggerNode) -> Option<Vec<u32>> {
+    match node {
+        TriggerNode::And(c) | TriggerNode::Or(c) | TriggerNode::Threshold(_, c) => Some(c.clone()),
+        TriggerNode::Condition(_) | TriggerNode::GuardianApprovals(_) => None,
+    }
+}
+
+impl TriggerExpression {
+    pub fn validate(&self) -> Result<(), TriggerExprError> {
+        let len = self.nodes.len();
+        if len == 0 {
+            return Err(TriggerExprError::Empty);
+        }
+        if len > MAX_TRIGGER_NODES {
+            return Err(TriggerExprError::TooManyNodes);
+        }
+
+        let mut parents = [0u32; MAX_TRIGGER_NODES as usize];
+        for (index, node) in self.nodes.iter().enumerate() {
+            let index = index as u32;
+            match &node {
+                TriggerNode::GuardianApprovals(k) if *k == 0 => {
+                    return Err(TriggerExprError::InvalidThreshold);
+                }
+                TriggerNode::Threshold(k, c) if *k == 0 || *k > c.len() => {
+                    return Err(TriggerExprError::InvalidThreshold);
+                }
+                _ => {}
+            }
+            if let Some(c) = children(&node) {
+                if c.is_empty() || c.len() > MAX_TRIGGER_CHILDREN {
+                    return Err(TriggerExprError::InvalidChildCount);
+                }
+                for child in c.iter() {
+                    if child <= index || child >= len {
+                        return Err(TriggerExprError::InvalidChild);
+                    }
+                    parents[child as usize] += 1;
+                }
+            }
+        }
+
+        if (1..len as usize).any(|i| parents[i] != 1) {
+            return Err(TriggerExprError::NotATree);
+        }
+        Ok(())
+    }
+
+    /// Evaluate the expression. `condition_met` answers condition leaves and
+    /// `guardian_approvals` is the number of guardians who approved.
+    ///
+    /// Assumes the expression has been validated.
+    pub fn evaluate<F>(&self, condition_met: F, guardian_approvals: u32) -> bool
+    where
+        F: Fn(&TriggerConditionType) -> bool,
+    {
+        let len = self.nodes.len().min(MAX_TRIGGER_NODES) as usize;
+        let mut values = [false; MAX_TRIGGER_NODES as usize];
+        for index in (0..len).rev() {
+            let node = self.nodes.get_unchecked(index as u32);
+            let count_true = |c: &Vec<u32>| c.iter().filter(|&i| values[i as usize]).count() as u32;
+            values[index] = match &node {
+                TriggerNode::Condition(condition) => condition_met(condition),
+                TriggerNode::GuardianApprovals(k) => guardian_approvals >= *k,
+                TriggerNode::And(c) => count_true(c) == c.len(),
+                TriggerNode::Or(c) => count_true(c) > 0,
+                TriggerNode::Threshold(k, c) => count_true(c) >= *k,
+            };
+        }
+        values[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{vec, Env};
+
+    /// `(Inactivity AND Oracle) OR (Time AND 2 guardian approvals)`
+    fn example(env: &Env) -> TriggerExpression {
+        TriggerExpression {
+            nodes: vec![
+                env,
+                TriggerNode::Or(vec![env, 1, 4]),
+                TriggerNode::And(vec![env, 2, 3]),
+                TriggerNode::Condition(TriggerConditionType::Inactivity),
+                TriggerNode::Condition(TriggerConditionType::Oracle),
+                TriggerNode::And(vec![env, 5, 6]),
+                TriggerNode::Condition(TriggerConditionType::Time),
+                TriggerNode::GuardianApprovals(2),
+            ],
+        }
+    }
+
+    #[test]
+    fn example_expression_is_valid() {
+        let env = Env::default();
+        assert_eq!(example(&env).validate(), Ok(()));
+    }
+
+    #[test]
+    fn single_signal_does_not_fire() {
+        let env = Env::default();
+        let expr = example(&env);
+        let only = |wanted: TriggerConditionType| move |c: &TriggerConditionType| *c == wanted;
+        assert!(!expr.evaluate(only(TriggerConditionType::Inactivity), 0));
+        assert!(!expr.evaluate(only(TriggerConditionType::Oracle), 0));
+        assert!(!expr.evaluate(only(TriggerConditionType::Time), 1));
+        assert!(!expr.evaluate(|_| false, 3));
+    }
+
+    #[test]
+    fn either_branch_fires() {
+        let env = Env::default();
+        let expr = example(&env);
+        assert!(expr.evaluate(
+            |c| matches!(
+                c,
+                TriggerConditionType::Inactivity | TriggerConditionType::Oracle
+            ),
+            0
+        ));
+        assert!(expr.evaluate(|c| *c == TriggerConditionType::Time, 2));
+    }
+
+    #[test]
+    fn threshold_counts_children() {
+        let env = Env::default();
+        let expr = TriggerExpression {
+            nodes: vec![
+                &env,
+                TriggerNode::Threshold(2, vec![&env, 1, 2, 3]),
+                TriggerNode::Condition(TriggerConditionType::Time),
+                TriggerNode::Condition(TriggerConditionType::Oracle),
+                TriggerNode::Condition(TriggerConditionType::Health),
+            ],
+        };
+        assert_eq!(expr.validate(), Ok(()));
+        assert!(!expr.evaluate(|c| *c == TriggerConditionType::Time, 0));
+        assert!(expr.evaluate(|c| *c != TriggerConditionType::Oracle, 0));
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        let env = Env::default();
+        let cond = || TriggerNode::Condition(TriggerConditionType::Time);
+
+        let empty = TriggerExpression {
+            nodes: Vec::new(&env),
+        };
+        assert_eq!(empty.validate(), Err(TriggerExprError::Empty));
+
+        let orphan = TriggerExpression {
+            nodes: vec![&env, cond(), cond()],
+        };
+        assert_eq!(orphan.validate(), Err(TriggerExprError::NotATree));
+
+        let self_ref = TriggerExpression {
+            nodes: vec![&env, TriggerNode::Or(vec![&env, 0])],
+        };
+        assert_eq!(self_ref.validate(), Err(TriggerExprError::InvalidChild));
+
+        let shared = TriggerExpression {
+            nodes: vec![&env, TriggerNode::And(vec![&env, 1, 1]), cond()],
+        };
+        assert_eq!(shared.validate(), Err(TriggerExprError::NotATree));
+
+        let bad_k = TriggerExpression {
+            nodes: vec![
+                &env,
+                TriggerNode::Threshold(3, vec![&env, 1, 2]),
+                cond(),
+                cond(),
+            ],
+        };
+        assert_eq!(bad_k.validate(), Err(TriggerExprError::InvalidThreshold));
+
+        let no_children = TriggerExpression {
+            nodes: vec![&env, TriggerNode::And(Vec::new(&env))],
+        };
+        assert_eq!(
+            no_children.validate(),
+            Err(TriggerExprError::InvalidChildCount)
+        );
+
+        let mut too_many = Vec::new(&env);
+        for _ in 0..=MAX_TRIGGER_NODES {
+            too_many.push_back(cond());
+        }
+        let too_many = TriggerExpression { nodes: too_many };
+        assert_eq!(too_many.validate(), Err(TriggerExprError::TooManyNodes));
+    }
+}
@@ -0,0 +1,164 @@
+//! Periodic (tranche) payouts for `Monthly`, `Quarterly` and `Yearly` plans.
+//!
+//! Each beneficiary's entitlement is split into `tranche_count` equal
+//! tranches. One tranche unlocks per period, counted from plan creation; once
+//! inheritance is triggered the first tranche is available immediately and
+//! the rest follow one period apart, whichever schedule is further ahead.
+//! Unlocked tranches accrue, so a beneficiary can claim several at once.
+
+use crate::DistributionMethod;
+use soroban_sdk::contracttype;
+
+const DAY_SECONDS: u64 = 24 * 60 * 60;
+
+/// Upper bound on tranches per plan (ten years of monthly payouts).
+pub const MAX_TRANCHES: u32 = 120;
+
+/// Per-beneficiary progress through a periodic payout schedule.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TrancheProgress {
+    pub claimed_amount: u64,
+    pub tranches_claimed: u32,
+}
+
+/// Read-only view of a beneficiary's payout schedule.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    pub plan_id: u64,
+    pub beneficiary_index: u32,
+    pub distribution_method: DistributionMethod,
+    pub period_seconds: u64,
+    pub tranche_count: u32,
+    /// Total amount owed to the beneficiary across all tranches.
+    pub total_entitlement: u64,
+    pub tranches_unlocked: u32,
+    pub tranches_claimed: u32,
+    pub claimed_amount: u64,
+    /// Unlocked but not yet claimed.
+    pub claimable_amount: u64,
+    /// Timestamp of the next unlock, or 0 once every tranche is unlocked.
+    pub next_unlock_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrancheClaimedEvent {
+    pub plan_id: u64,
+    pub beneficiary_index: u32,
+    pub amount: u64,
+    pub tranches_claimed: u32,
+    pub tranche_count: u32,
+    pub claimed_to_date: u64,
+}
+
+/// Length of one payout period, or 0 for lump-sum plans.
+pub fn period_seconds(method: &DistributionMethod) -> u64 {
+    match method {
+        DistributionMethod::LumpSum => 0,
+        DistributionMethod::Monthly => 30 * DAY_SECONDS,
+        DistributionMethod::Quarterly => 90 * DAY_SECONDS,
+        DistributionMethod::Yearly => 365 * DAY_SECONDS,
+    }
+}
+
+/// Tranche count used when the owner has not configured one: a year of
+/// monthly or quarterly payouts, or five yearly payouts.
+pub fn default_tranche_count(method: &DistributionMethod) -> u32 {
+    match method {
+        DistributionMethod::LumpSum => 1,
+        DistributionMethod::Monthly => 12,
+        DistributionMethod::Quarterly => 4,
+        DistributionMethod::Yearly => 5,
+    }
+}
+
+/// Number of tranches unlocked at `now`, capped at `tranche_count`.
+pub fn tranches_unlocked(
+    period: u64,
+    tranche_count: u32,
+    created_at: u64,
+    triggered_at: Option<u64>,
+    now: u64,
+) -> u32 {
+    if period == 0 {
+        return tranche_count;
+    }
+    let by_creation = now.saturating_sub(created_at) / period;
+    let by_trigger = match triggered_at {
+        Some(t) if now >= t => 1 + (now - t) / period,
+        _ => 0,
+    };
+    by_creation.max(by_trigger).min(tranche_count as u64) as u32
+}
+
+/// Timestamp at which the next tranche unlocks, or 0 if all are unlocked.
+pub fn next_unlock_at(
+    period: u64,
+    tranche_count: u32,
+    created_at: u64,
+    triggered_at: Option<u64>,
+    now: u64,
+) -> u64 {
+    let unlocked = tranches_unlocked(period, tranche_count, created_at, triggered_at, now);
+    if period == 0 || unlocked >= tranche_count {
+        return 0;
+    }
+    let by_creation = created_at + (now.saturating_sub(created_at) / period + 1) * period;
+    match triggered_at {
+        Some(t) if now >= t => by_creation.min(t + ((now - t) / period + 1) * period),
+        _ => by_creation,
+    }
+}
+
+/// Portion of `entitlement` vested after `unlocked` of `tranche_count`
+/// tranches. The final tranche absorbs any rounding remainder.
+pub fn vested_amount(entitlement: u64, tranche_count: u32, unlocked: u32) -> u64 {
+    if tranche_count == 0 || unlocked >= tranche_count {
+        return entitlement;
+    }
+    ((entitlement as u128) * (unlocked as u128) / (tranche_count as u128)) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONTH: u64 = 30 * DAY_SECONDS;
+
+    #[test]
+    fn tranches_unlock_one_per_period_from_creation() {
+        assert_eq!(tranches_unlocked(MONTH, 12, 0, None, MONTH - 1), 0);
+        assert_eq!(tranches_unlocked(MONTH, 12, 0, None, MONTH), 1);
+        assert_eq!(tranches_unlocked(MONTH, 12, 0, None, 5 * MONTH + 10), 5);
+        assert_eq!(tranches_unlocked(MONTH, 12, 0, None, 100 * MONTH), 12);
+    }
+
+    #[test]
+    fn trigger_unlocks_first_tranche_immediately() {
+        assert_eq!(tranches_unlocked(MONTH, 12, 0, Some(10), 10), 1);
+        assert_eq!(tranches_unlocked(MONTH, 12, 0, Some(10), 10 + MONTH), 2);
+        // Trigger never delays a schedule that is already further ahead.
+        assert_eq!(
+            tranches_unlocked(MONTH, 12, 0, Some(6 * MONTH), 6 * MONTH),
+            6
+        );
+    }
+
+    #[test]
+    fn next_unlock_tracks_the_earlier_schedule() {
+        assert_eq!(next_unlock_at(MONTH, 12, 0, None, 0), MONTH);
+        assert_eq!(next_unlock_at(MONTH, 12, 0, Some(10), 10), MONTH);
+        assert_eq!(next_unlock_at(MONTH, 12, 0, Some(10), MONTH), 10 + MONTH);
+        assert_eq!(next_unlock_at(MONTH, 2, 0, None, 2 * MONTH), 0);
+    }
+
+    #[test]
+    fn final_tranche_absorbs_rounding() {
+        assert_eq!(vested_amount(1000, 3, 1), 333);
+        assert_eq!(vested_amount(1000, 3, 2), 666);
+        assert_eq!(vested_amount(1000, 3, 3), 1000);
+        assert_eq!(vested_amount(1000, 3, 0), 0);
+    }
+}
@@ -0,0 +1,102 @@
+//! Multi-asset collateral baskets.
+//!
+//! A loan's `collateral_token`/`collateral_amount` remain its primary
+//! collateral. Further assets a borrower posts against the same position are
+//! kept in a basket keyed by borrower, so it follows the position through
+//! refinancing. Each asset counts toward the borrowing limit at its own
+//! collateral ratio.
+
+use crate::DataKey;
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralPosition {
+    pub token: Address,
+    pub amount: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralAddedEvent {
+    pub loan_id: u64,
+    pub borrower: Address,
+    pub token: Address,
+    pub amount: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralWithdrawnEvent {
+    pub loan_id: u64,
+    pub borrower: Address,
+    pub token: Address,
+    pub amount: u64,
+}
+
+/// Non-primary collateral posted by `borrower`.
+pub fn basket(env: &Env, borrower: &Address) -> Vec<CollateralPosition> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CollateralBasket(borrower.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Store a basket, dropping emptied positions.
+pub fn set_basket(env: &Env, borrower: &Address, basket: &Vec<CollateralPosition>) {
+    let mut kept = Vec::new(env);
+    for position in basket.iter() {
+        if position.amount > 0 {
+            kept.push_back(position);
+        }
+    }
+    let key = DataKey::CollateralBasket(borrower.clone());
+    if kept.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &kept);
+    }
+}
+
+pub fn clear_basket(env: &Env, borrower: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::CollateralBasket(borrower.clone()));
+}
+
+/// Add `amount` of `token` to `basket`. Returns false on overflow.
+pub fn deposit(basket: &mut Vec<CollateralPosition>, token: &Address, amount: u64) -> bool {
+    for i in 0..basket.len() {
+        let mut position = basket.get(i).unwrap();
+        if position.token == *token {
+            let Some(total) = position.amount.checked_add(amount) else {
+                return false;
+            };
+            position.amount = total;
+            basket.set(i, position);
+            return true;
+        }
+    }
+    basket.push_back(CollateralPosition {
+        token: token.clone(),
+        amount,
+    });
+    true
+}
+
+/// Remove `amount` of `token` from `basket`. Returns false if the basket
+/// holds less.
+pub fn withdraw(basket: &mut Vec<CollateralPosition>, token: &Address, amount: u64) -> bool {
+    for i in 0..basket.len() {
+        let mut position = basket.get(i).unwrap();
+        if position.token == *token {
+            if position.amount < amount {
+                return false;
+            }
+            position.amount -= amount;
+            basket.set(i, position);
+            return true;
+        }
+    }
+    amount == 0
+}
@@ -0,0 +1,225 @@
+#![cfg(test)]
+
+use crate::{LendingContract, LendingContractClient, LendingError};
+use mock_flash_receiver::{MockFlashReceiver, MockFlashReceiverClient, ReceiverMode};
+use soroban_sdk::{testutils::Address as _, token, vec, Address, Env, InvokeError};
+
+struct Fixture<'a> {
+    client: LendingContractClient<'a>,
+    asset: Address,
+    other: Address,
+    receiver: Address,
+    depositor: Address,
+}
+
+fn create_token_addr(env: &Env) -> Address {
+    env.register_stellar_asset_contract_v2(Address::generate(env))
+        .address()
+}
+
+fn mint_to(env: &Env, token: &Address, to: &Address, amount: i128) {
+    token::StellarAssetClient::new(env, token).mint(to, &amount);
+}
+
+/// Two funded pools and a whitelisted reference receiver holding enough of
+/// both assets to cover fees. With `receiver_is_admin` the receiver is also
+/// the lending contract's admin.
+fn setup(env: &Env, receiver_is_admin: bool) -> Fixture<'_> {
+    let asset = create_token_addr(env);
+    let other = create_token_addr(env);
+
+    let contract_id = env.register_contract(None, LendingContract);
+    let client = LendingContractClient::new(env, &contract_id);
+    let receiver = env.register_contract(None, MockFlashReceiver);
+    MockFlashReceiverClient::new(env, &receiver).initialize(&contract_id, &asset);
+
+    let admin = if receiver_is_admin {
+        receiver.clone()
+    } else {
+        Address::generate(env)
+    };
+    client.initialize(&admin, &asset, &500u32, &2000u32, &15000u32, &10000u32);
+    client.add_asset_pool(&admin, &other, &500u32, &2000u32, &10000u32);
+    client.whitelist_flash_loan_receiver(&admin, &receiver);
+
+    let depositor = Address::generate(env);
+    for token in [&asset, &other] {
+        mint_to(env, token, &depositor, 1_000_000);
+        client.deposit(&depositor, token, &100_000u64);
+        mint_to(env, token, &receiver, 10_000);
+    }
+
+    Fixture {
+        client,
+        asset,
+        other,
+        receiver,
+        depositor,
+    }
+}
+
+fn set_mode(env: &Env, f: &Fixture, mode: ReceiverMode) {
+    MockFlashReceiverClient::new(env, &f.receiver).set_mode(&mode);
+}
+
+#[test]
+fn test_flash_loan_through_reference_receiver() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let f = setup(&env, false);
+
+    let pool_before = f.client.get_pool_state(&f.asset);
+    f.client
+        .flash_loan(&f.depositor, &f.receiver, &f.asset, &50_000u64);
+
+    let pool_after = f.client.get_pool_state(&f.asset);
+    assert_eq!(pool_after.total_deposits, pool_before.total_deposits + 45);
+    assert_eq!(pool_after.total_borrowed, pool_before.total_borrowed);
+}
+
+#[test]
+fn test_flash_loan_batch_credits_fee_to_each_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let f = setup(&env, false);
+
+    let before = f.client.get_pool_state(&f.asset).total_deposits;
+    let other_before = f.client.get_pool_state(&f.other).total_deposits;
+
+    f.client.flash_loan_batch(
+        &f.depositor,
+        &f.receiver,
+        &vec![&env, f.asset.clone(), f.other.clone()],
+        &vec![&env, 50_000u64, 20_000u64],
+    );
+
+    assert_eq!(
+        f.client.get_pool_state(&f.asset).total_deposits,
+        before + 45
+    );
+    assert_eq!(
+        f.client.get_pool_state(&f.other).total_deposits,
+        other_before + 18
+    );
+}
+
+#[test]
+fn test_flash_loan_batch_rejects_malformed_requests() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let f = setup(&env, false);
+
+    let mismatched = f.client.try_flash_loan_batch(
+        &f.depositor,
+        &f.receiver,
+        &vec![&env, f.asset.clone(), f.other.clone()],
+        &vec![&env, 1_000u64],
+    );
+    assert_eq!(mismatched, Err(Ok(LendingError::InvalidAmount)));
+
+    let duplicate = f.client.try_flash_loan_batch(
+        &f.depositor,
+        &f.receiver,
+        &vec![&env, f.asset.clone(), f.asset.clone()],
+        &vec![&env, 1_000u64, 1_000u64],
+    );
+    assert_eq!(duplicate, Err(Ok(LendingError::InvalidAmount)));
+
+    let empty = f
+        .client
+        .try_flash_loan_batch(&f.depositor, &f.receiver, &vec![&env], &vec![&env]);
+    assert_eq!(empty, Err(Ok(LendingError::InvalidAmount)));
+}
+
+#[test]
+fn test_flash_loan_rejects_receiver_off_whitelist() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let f = setup(&env, false);
+
+    let stranger = env.register_contract(None, MockFlashReceiver);
+    let result = f
+        .client
+        .try_flash_loan(&f.depositor, &stranger, &f.asset, &1_000u64);
+    assert_eq!(result, Err(Ok(LendingError::Unauthorized)));
+}
+
+#[test]
+fn test_flash_loan_partial_repay_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let f = setup(&env, false);
+    set_mode(&env, &f, ReceiverMode::RepayPrincipalOnly);
+
+    let result = f
+        .client
+        .try_flash_loan(&f.depositor, &f.receiver, &f.asset, &50_000u64);
+    assert_eq!(result, Err(Ok(LendingError::FlashLoanNotRepaid)));
+
+    // One short leg fails the whole batch.
+    let result = f.client.try_flash_loan_batch(
+        &f.depositor,
+        &f.receiver,
+        &vec![&env, f.asset.clone(), f.other.clone()],
+        &vec![&env, 50_000u64, 20_000u64],
+    );
+    assert_eq!(result, Err(Ok(LendingError::FlashLoanNotRepaid)));
+}
+
+#[test]
+fn test_flash_loan_repaid_in_another_token_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let f = setup(&env, false);
+    set_mode(&env, &f, ReceiverMode::RepayWith(f.other.clone()));
+
+    let other_before = token::Client::new(&env, &f.other).balance(&f.client.address);
+    let result = f
+        .client
+        .try_flash_loan(&f.depositor, &f.receiver, &f.asset, &5_000u64);
+    assert_eq!(result, Err(Ok(LendingError::FlashLoanNotRepaid)));
+    // The swapped-in tokens went back with the rolled-back call.
+    assert_eq!(
+        token::Client::new(&env, &f.other).balance(&f.client.address),
+        other_before
+    );
+}
+
+#[test]
+fn test_flash_loan_reentrant_deposit_fails() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+    let f = setup(&env, false);
+    set_mode(&env, &f, ReceiverMode::Redeposit);
+
+    // The host refuses to re-enter a contract already on the call stack, so
+    // the deposit never reaches the pool.
+    let pool_before = f.client.get_pool_state(&f.asset);
+    let result = f
+        .client
+        .try_flash_loan(&f.depositor, &f.receiver, &f.asset, &50_000u64);
+    assert_eq!(result, Err(Err(InvokeError::Abort)));
+    assert_eq!(f.client.get_pool_state(&f.asset), pool_before);
+    assert_eq!(
+        f.client.get_shares_of(&f.asset, &f.receiver),
+        0,
+        "the receiver must not come away with pool shares"
+    );
+}
+
+#[test]
+fn test_flash_loan_booked_into_reserve_fails() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+    let f = setup(&env, true);
+    set_mode(&env, &f, ReceiverMode::ReplenishReserve);
+
+    // Returning the funds through `replenish_bad_debt_reserve` would satisfy
+    // the balance check; the re-entry itself is refused.
+    let pool_before = f.client.get_pool_state(&f.asset);
+    let result = f
+        .client
+        .try_flash_loan(&f.depositor, &f.receiver, &f.asset, &50_000u64);
+    assert_eq!(result, Err(Err(InvokeError::Abort)));
+    assert_eq!(f.client.get_pool_state(&f.asset), pool_before);
+}
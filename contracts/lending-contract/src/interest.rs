@@ -0,0 +1,277 @@
+//! Per-second compounding interest indexes.
+//!
+//! Each asset pool keeps a borrow index and a supply index, both starting at
+//! `INDEX_ONE`. Whenever a pool is touched it is first brought up to date:
+//! the borrow index compounds every elapsed second at the pool's current
+//! borrow rate, the pool's outstanding debt grows with it, and the interest
+//! that accrued is booked — the reserve factor's share to the protocol
+//! buckets, the rest to depositors, raising the value of a pool share. The
+//! supply index follows the value of a share.
+//!
+//! A loan remembers the borrow index its principal was measured at; its debt
+//! is the principal scaled by how far the index has grown since.
+//!
+//! Fixed-rate loans sit outside the index. Each compounds at the rate it was
+//! opened at from the time it was last measured, and the pool keeps their
+//! debt in a separate bucket that compounds at the debt-weighted average of
+//! those rates.
+//!
+//! Indexes and the pool's debt are kept at `INDEX_ONE` precision so that
+//! frequent accruals do not lose interest to rounding; the pool's
+//! `total_borrowed` is the whole-unit part of the debt.
+
+use crate::{DataKey, SECONDS_IN_YEAR};
+use soroban_sdk::{contracttype, Address, Env};
+
+/// Fixed-point one for indexes (18 decimals).
+pub const INDEX_ONE: u128 = 1_000_000_000_000_000_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InterestIndex {
+    pub borrow_index: u128,
+    pub supply_index: u128,
+    /// Outstanding variable-rate debt of the pool, scaled by `INDEX_ONE`.
+    pub debt: u128,
+    /// Outstanding fixed-rate debt of the pool, scaled by `INDEX_ONE`.
+    pub fixed_debt: u128,
+    /// Debt-weighted average rate of the fixed-rate debt.
+    pub fixed_rate_bps: u32,
+    pub last_update: u64,
+}
+
+/// How a loan's interest rate is set.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RateMode {
+    /// Follows the pool's borrow rate as utilization moves.
+    Variable,
+    /// Locked when the loan is opened, at the pool's rate plus a premium.
+    Fixed,
+}
+
+/// Terms of a fixed-rate loan: its rate and when its principal was last
+/// measured.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FixedRate {
+    pub rate_bps: u32,
+    pub since: u64,
+}
+
+impl InterestIndex {
+    /// Whole units of debt, as reported in the pool's `total_borrowed`.
+    pub fn debt_units(&self) -> u64 {
+        (self.debt.saturating_add(self.fixed_debt) / INDEX_ONE).min(u64::MAX as u128) as u64
+    }
+
+    pub fn add_debt(&mut self, amount: u64) {
+        self.debt = self
+            .debt
+            .saturating_add((amount as u128).saturating_mul(INDEX_ONE));
+    }
+
+    pub fn remove_debt(&mut self, amount: u64) {
+        self.debt = self
+            .debt
+            .saturating_sub((amount as u128).saturating_mul(INDEX_ONE));
+    }
+
+    /// Add `amount` of fixed-rate debt at `rate_bps`, folding it into the
+    /// bucket's average rate.
+    pub fn add_fixed_debt(&mut self, amount: u64, rate_bps: u32) {
+        let before = self.fixed_debt / INDEX_ONE;
+        let total = before.saturating_add(amount as u128);
+        let weighted = (self.fixed_rate_bps as u128)
+            .saturating_mul(before)
+            .saturating_add((rate_bps as u128).saturating_mul(amount as u128));
+        if let Some(average) = weighted.checked_div(total) {
+            self.fixed_rate_bps = average.min(u32::MAX as u128) as u32;
+        }
+        self.fixed_debt = self
+            .fixed_debt
+            .saturating_add((amount as u128).saturating_mul(INDEX_ONE));
+    }
+
+    /// Remove `amount` of fixed-rate debt that was carried at `rate_bps`,
+    /// taking it back out of the bucket's average rate.
+    pub fn remove_fixed_debt(&mut self, amount: u64, rate_bps: u32) {
+        let before = self.fixed_debt / INDEX_ONE;
+        let remaining = before.saturating_sub(amount as u128);
+        let weighted = (self.fixed_rate_bps as u128)
+            .saturating_mul(before)
+            .saturating_sub((rate_bps as u128).saturating_mul(amount as u128));
+        match weighted.checked_div(remaining) {
+            Some(average) => {
+                self.fixed_rate_bps = average.min(u32::MAX as u128) as u32;
+                self.fixed_debt = self
+                    .fixed_debt
+                    .saturating_sub((amount as u128).saturating_mul(INDEX_ONE));
+            }
+            // The last of the fixed-rate debt, along with any rounding dust
+            None => {
+                self.fixed_rate_bps = 0;
+                self.fixed_debt = 0;
+            }
+        }
+    }
+}
+
+/// Indexes of `asset`'s pool as last stored. A pool without any (one
+/// created before indexes existed) starts at `INDEX_ONE` now, carrying the
+/// pool's current borrows.
+pub fn load(env: &Env, asset: &Address, total_borrowed: u64) -> InterestIndex {
+    env.storage()
+        .instance()
+        .get(&DataKey::InterestIndex(asset.clone()))
+        .unwrap_or_else(|| InterestIndex {
+            borrow_index: INDEX_ONE,
+            supply_index: INDEX_ONE,
+            debt: (total_borrowed as u128) * INDEX_ONE,
+            fixed_debt: 0,
+            fixed_rate_bps: 0,
+            last_update: env.ledger().timestamp(),
+        })
+}
+
+pub fn save(env: &Env, asset: &Address, index: &InterestIndex) {
+    env.storage()
+        .instance()
+        .set(&DataKey::InterestIndex(asset.clone()), index);
+}
+
+/// Borrow index a loan's principal was last measured at. Loans opened
+/// before indexes existed count from `INDEX_ONE`.
+pub fn loan_index(env: &Env, loan_id: u64) -> u128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LoanBorrowIndex(loan_id))
+        .unwrap_or(INDEX_ONE)
+}
+
+pub fn set_loan_index(env: &Env, loan_id: u64, borrow_index: u128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::LoanBorrowIndex(loan_id), &borrow_index);
+}
+
+/// Terms of a fixed-rate loan; `None` for a variable-rate one.
+pub fn fixed_rate(env: &Env, loan_id: u64) -> Option<FixedRate> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FixedRateLoan(loan_id))
+}
+
+pub fn set_fixed_rate(env: &Env, loan_id: u64, terms: &FixedRate) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::FixedRateLoan(loan_id), terms);
+}
+
+/// Forget how a closed loan's interest was measured.
+pub fn clear_loan(env: &Env, loan_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::LoanBorrowIndex(loan_id));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::FixedRateLoan(loan_id));
+}
+
+/// `a * b / INDEX_ONE`, split so that `a` may itself carry `INDEX_ONE`
+/// precision without overflowing.
+pub fn mul_index(a: u128, b: u128) -> Option<u128> {
+    (a / INDEX_ONE)
+        .checked_mul(b)?
+        .checked_add((a % INDEX_ONE).checked_mul(b)? / INDEX_ONE)
+}
+
+/// Growth of a balance compounding every second for `elapsed` seconds at
+/// `rate_bps` a year, scaled by `INDEX_ONE`.
+pub fn compound_factor(rate_bps: u32, elapsed: u64) -> Option<u128> {
+    let per_second = (rate_bps as u128) * INDEX_ONE / (10000 * SECONDS_IN_YEAR as u128);
+    let mut base = INDEX_ONE + per_second;
+    let mut exponent = elapsed;
+    let mut factor = INDEX_ONE;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            factor = mul_index(factor, base)?;
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = mul_index(base, base)?;
+        }
+    }
+    Some(factor)
+}
+
+/// `principal` measured at `from_index`, carried to `to_index`, rounded to
+/// the nearest unit.
+pub fn scale(principal: u64, from_index: u128, to_index: u128) -> Option<u64> {
+    if from_index == 0 {
+        return None;
+    }
+    let scaled = (principal as u128)
+        .checked_mul(to_index)?
+        .checked_add(from_index / 2)?
+        / from_index;
+    u64::try_from(scaled).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compounding_matches_closed_form() {
+        assert_eq!(compound_factor(1500, 0), Some(INDEX_ONE));
+        assert_eq!(compound_factor(0, SECONDS_IN_YEAR), Some(INDEX_ONE));
+        // A year at 15% compounded per second is e^0.15 = 1.161834...
+        let year = compound_factor(1500, SECONDS_IN_YEAR).unwrap();
+        assert_eq!(year / 1_000_000_000_000, 1_161_834);
+        // Compounding is path independent.
+        let halves = mul_index(
+            compound_factor(1500, SECONDS_IN_YEAR / 2).unwrap(),
+            compound_factor(1500, SECONDS_IN_YEAR / 2).unwrap(),
+        )
+        .unwrap();
+        assert!(year.abs_diff(halves) < 1_000_000);
+    }
+
+    #[test]
+    fn debt_units_drop_the_fraction() {
+        let mut index = InterestIndex {
+            borrow_index: INDEX_ONE,
+            supply_index: INDEX_ONE,
+            debt: 5 * INDEX_ONE + INDEX_ONE / 2,
+            fixed_debt: 0,
+            fixed_rate_bps: 0,
+            last_update: 0,
+        };
+        assert_eq!(index.debt_units(), 5);
+        index.remove_debt(6);
+        assert_eq!(index.debt, 0);
+        index.add_debt(3);
+        assert_eq!(index.debt_units(), 3);
+    }
+
+    #[test]
+    fn fixed_debt_tracks_average_rate() {
+        let mut index = InterestIndex {
+            borrow_index: INDEX_ONE,
+            supply_index: INDEX_ONE,
+            debt: 0,
+            fixed_debt: 0,
+            fixed_rate_bps: 0,
+            last_update: 0,
+        };
+        index.add_fixed_debt(1000, 600);
+        index.add_fixed_debt(3000, 1000);
+        assert_eq!(index.fixed_rate_bps, 900);
+        assert_eq!(index.debt_units(), 4000);
+        index.remove_fixed_debt(3000, 1000);
+        assert_eq!(index.fixed_rate_bps, 600);
+        index.remove_fixed_debt(1001, 600);
+        assert_eq!((index.fixed_debt, index.fixed_rate_bps), (0, 0));
+    }
+}
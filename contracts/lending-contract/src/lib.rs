@@ -1,12 +1,20 @@
 #![no_std]
 use access_control::{self, Role};
+use flash_loan::{FlashLoan, FlashLoanReceiverClient};
+use price_oracle::{Price, PriceError};
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, log, symbol_short, token, vec, Address,
     Env, IntoVal, InvokeError, Val, Vec,
 };
 
+mod collateral;
+mod interest;
 mod reserves;
 
+pub use collateral::CollateralPosition;
+use collateral::{CollateralAddedEvent, CollateralWithdrawnEvent};
+pub use interest::{FixedRate, InterestIndex, RateMode};
+
 // ─────────────────────────────────────────────────
 // Constants
 // ─────────────────────────────────────────────────
@@ -15,7 +23,6 @@ mod reserves;
 const CONTRACT_VERSION: u32 = 1;
 
 const MINIMUM_LIQUIDITY: u64 = 1000;
-const PROTOCOL_INTEREST_BPS: u32 = 1000; // 10% of interest retained by protocol
 const BAD_DEBT_RESERVE_BPS: u32 = 5000; // 50% of protocol share routed to reserve
 const DEFAULT_GRACE_PERIOD_SECONDS: u64 = 259_200; // 3 days
 const DEFAULT_LATE_FEE_RATE_BPS: u32 = 500; // 5% per day = 0.058% per second (approx)
@@ -24,8 +31,13 @@ const DEFAULT_REWARD_RATE: u64 = 1_000_000_000; // Default reward rate per secon
 const REWARD_PRECISION: u64 = 1_000_000_000; // 9 decimals for reward calculations
 
 const LIQUIDATION_THRESHOLD_BPS: u32 = 15000; // 150% liquidation threshold in basis points
-                                              // Insurance constants
+const DEFAULT_LIQUIDATION_BONUS_BPS: u32 = 500; // 5% seize bonus without governance
+const DEFAULT_CLOSE_FACTOR_BPS: u32 = 10000; // Full repayment allowed without governance
+                                             // Insurance constants
 const DEFAULT_INSURANCE_PREMIUM_RATE_BPS: u32 = 200; // 2% premium of loan principal
+const DEFAULT_FIXED_RATE_PREMIUM_BPS: u32 = 200; // Fixed loans pay 2% over the variable rate
+const MAX_FLASH_LOAN_ASSETS: u32 = 10; // Assets lent by a single flash_loan_batch
+const DEFAULT_REBALANCE_UTILIZATION_BPS: u32 = 9500; // Fixed loans may be repriced above 95% utilization
 
 // ─────────────────────────────────────────────────
 // Data Types
@@ -36,7 +48,7 @@ const DEFAULT_INSURANCE_PREMIUM_RATE_BPS: u32 = 200; // 2% premium of loan princ
 pub struct PoolState {
     pub total_deposits: u64, // Total underlying tokens deposited (net, tracks repayments too)
     pub total_shares: u64,   // Total pool shares outstanding
-    pub total_borrowed: u64, // Total principal currently on loan
+    pub total_borrowed: u64, // Total owed on loans, including accrued interest
     pub base_rate_bps: u32,  // Base interest rate in basis points (1/10000)
     pub multiplier_bps: u32, // Multiplier applied to utilization to get variable rate
     pub utilization_cap_bps: u32, // Maximum utilization allowed in basis points (e.g., 8000 = 80%)
@@ -110,18 +122,32 @@ pub struct InsuranceFund {
     pub available_balance: u64,        // Current available balance for claims
 }
 
+/// Live state of a loan's lender-side claim, read by the loan NFT to build
+/// its metadata.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanClaim {
+    pub loan_id: u64,
+    pub asset: Address,
+    pub outstanding: u64, // Debt plus late fees due now
+    pub due_date: u64,
+    pub holder: Address, // Receives repayments and liquidation proceeds
+}
+
 #[soroban_sdk::contractclient(name = "LoanNFTClient")]
 pub trait LoanNFTInterface {
     fn initialize(env: Env, admin: Address);
     fn mint(env: Env, to: Address, metadata: LoanMetadata);
     fn burn(env: Env, loan_id: u64);
+    fn transfer(env: Env, from: Address, to: Address, loan_id: u64);
     fn get_metadata(env: Env, loan_id: u64) -> Option<LoanMetadata>;
     fn owner_of(env: Env, loan_id: u64) -> Option<Address>;
 }
 
-#[soroban_sdk::contractclient(name = "FlashLoanReceiverClient")]
-pub trait FlashLoanReceiverInterface {
-    fn execute_operation(env: Env, amount: u64, fee: u64, initiator: Address);
+#[soroban_sdk::contractclient(name = "GovernanceClient")]
+pub trait GovernanceInterface {
+    fn get_liquidation_bonus(env: Env, asset: Address) -> u32;
+    fn get_close_factor(env: Env) -> u32;
 }
 
 #[soroban_sdk::contractclient(name = "InheritanceContractClient")]
@@ -208,6 +234,19 @@ pub struct LiquidationEvent {
     pub amount_repaid: u64,
     pub collateral_seized: u64,
     pub health_factor: u32,
+    pub health_factor_after: u32,
+    pub remaining_debt: u64,
+    pub remaining_collateral: u64,
+    pub bonus_bps: u32,
+    /// Non-primary collateral seized once the primary collateral ran out.
+    pub basket_seized: Vec<CollateralPosition>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceOracleSetEvent {
+    pub admin: Address,
+    pub oracle: Address,
 }
 
 #[contracttype]
@@ -307,6 +346,24 @@ pub struct LoanSplitEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanClaimSoldEvent {
+    pub loan_id: u64,
+    pub buyer: Address,
+    pub asset: Address,
+    pub price: u64,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanClaimTransferredEvent {
+    pub loan_id: u64,
+    pub holder: Address,
+    pub timestamp: u64,
+}
+
 // ─────────────────────────────────────────────────
 // Yield Farming Data Types
 // ─────────────────────────────────────────────────
@@ -409,6 +466,38 @@ pub struct RateModel {
     pub reserve_factor_bps: u32,
 }
 
+/// Per-asset terms of fixed-rate borrowing.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FixedRateConfig {
+    pub premium_bps: u32, // Added to the variable borrow rate when a fixed rate is locked
+    pub rebalance_utilization_bps: u32, // Utilization from which fixed loans below the current fixed rate may be repriced
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateModeSwitchedEvent {
+    pub loan_id: u64,
+    pub borrower: Address,
+    pub asset: Address,
+    pub old_mode: RateMode,
+    pub new_mode: RateMode,
+    pub interest_rate_bps: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FixedRateRebalancedEvent {
+    pub loan_id: u64,
+    pub borrower: Address,
+    pub asset: Address,
+    pub old_rate_bps: u32,
+    pub new_rate_bps: u32,
+    pub utilization_bps: u32,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InsuranceCancelledEvent {
@@ -470,6 +559,23 @@ pub enum LendingError {
     InvalidInsuranceAmount = 31,
     InvalidRateModel = 32,
     ContractPaused = 33,
+    OraclePriceStale = 34,
+    OraclePriceUnavailable = 35,
+    LiquidationExceedsCloseFactor = 36,
+    LiquidationWorsensHealth = 37,
+    RateModeUnchanged = 38,
+    RebalanceNotAllowed = 39,
+    FlashLoanInvariantViolated = 40,
+    LoanClaimSold = 41,
+}
+
+impl From<PriceError> for LendingError {
+    fn from(err: PriceError) -> Self {
+        match err {
+            PriceError::Stale => LendingError::OraclePriceStale,
+            PriceError::Unavailable => LendingError::OraclePriceUnavailable,
+        }
+    }
 }
 
 // ─────────────────────────────────────────────────
@@ -503,6 +609,16 @@ pub enum DataKey {
     RateModel,
     Token,                             // Underlying token address for insurance operations
     WhitelistedFlashReceiver(Address), // Approved flash loan receiver contracts
+    PriceOracle,                       // SEP-40 price feed; unset = amounts compared 1:1
+    MaxOracleAge,                      // Max age of a feed price in seconds
+    AssetDecimals(Address),            // Decimals used to value a token's amounts
+    CollateralBasket(Address),         // Borrower -> non-primary collateral of their position
+    AssetCollateralRatio(Address),     // Per-token collateral ratio override (bps)
+    InterestIndex(Address),            // Per-asset borrow/supply indexes and pool debt
+    LoanBorrowIndex(u64),              // Borrow index a loan's principal was measured at
+    FixedRateLoan(u64),                // Rate and start of a fixed-rate loan; absent = variable
+    FixedRateConfig(Address),          // Per-asset fixed-rate premium and rebalancing threshold
+    LoanPayee(u64),                    // Holder of a sold loan's NFT; absent = the pool
 }
 
 // ─────────────────────────────────────────────────
@@ -577,6 +693,25 @@ impl LendingContract {
             },
         );
 
+        // Initialize insurance fund
+        env.storage().instance().set(
+            &DataKey::InsuranceFund,
+            &InsuranceFund {
+                total_premiums_collected: 0,
+                total_claims_paid: 0,
+                available_balance: 0,
+            },
+        );
+
+        // Initialize insurance premium rate
+        env.storage().instance().set(
+            &DataKey::InsurancePremiumRate,
+            &DEFAULT_INSURANCE_PREMIUM_RATE_BPS,
+        );
+
+        access_control::assign_role(&env, &admin, Role::Admin);
+        access_control::set_contract_version(&env, CONTRACT_VERSION);
+
         Ok(())
     }
 
@@ -641,23 +776,6 @@ impl LendingContract {
             },
         );
 
-        // Initialize insurance fund
-        env.storage().instance().set(
-            &DataKey::InsuranceFund,
-            &InsuranceFund {
-                total_premiums_collected: 0,
-                total_claims_paid: 0,
-                available_balance: 0,
-            },
-        );
-
-        // Initialize insurance premium rate
-        env.storage().instance().set(
-            &DataKey::InsurancePremiumRate,
-            &DEFAULT_INSURANCE_PREMIUM_RATE_BPS,
-        );
-
-        access_control::assign_role(&env, &admin, Role::Admin);
         Ok(())
     }
 
@@ -823,6 +941,160 @@ impl LendingContract {
         current
     }
 
+    /// Fresh price of `token`, or `None` when no price feed is configured.
+    fn price_of(env: &Env, token: &Address) -> Result<Option<Price>, LendingError> {
+        let Some(oracle) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::PriceOracle)
+        else {
+            return Ok(None);
+        };
+        let max_age = Self::get_max_oracle_age(env.clone());
+        Ok(Some(Price {
+            price: price_oracle::fresh_price(env, &oracle, token, max_age)?,
+            decimals: Self::get_asset_decimals(env.clone(), token.clone()),
+        }))
+    }
+
+    /// Fresh prices of `asset` and `collateral_token`, or `None` when no
+    /// price feed is configured.
+    fn prices(
+        env: &Env,
+        asset: &Address,
+        collateral_token: &Address,
+    ) -> Result<Option<(Price, Price)>, LendingError> {
+        match Self::price_of(env, asset)? {
+            None => Ok(None),
+            Some(asset_price) => Ok(Some((
+                asset_price,
+                Self::price_of(env, collateral_token)?
+                    .ok_or(LendingError::OraclePriceUnavailable)?,
+            ))),
+        }
+    }
+
+    /// All collateral backing `loan`: the primary asset, then the borrower's
+    /// basket.
+    fn loan_collateral(env: &Env, loan: &LoanRecord) -> Vec<CollateralPosition> {
+        let mut positions = vec![
+            env,
+            CollateralPosition {
+                token: loan.collateral_token.clone(),
+                amount: loan.collateral_amount,
+            },
+        ];
+        positions.append(&collateral::basket(env, &loan.borrower));
+        positions
+    }
+
+    /// Value of the positions in units of `asset`, each weighted by the
+    /// global collateral ratio over its token's own ratio so that riskier
+    /// collateral counts for less. Borrowing capacity and health factors are
+    /// both derived from it.
+    fn weighted_collateral_value(
+        env: &Env,
+        asset: &Address,
+        positions: &Vec<CollateralPosition>,
+    ) -> Result<u64, LendingError> {
+        let global_ratio = Self::get_collateral_ratio(env) as u128;
+        let mut weighted = 0u64;
+        for position in positions.iter() {
+            let value =
+                Self::collateral_in_asset_units(env, asset, &position.token, position.amount)?;
+            let position_weighted = (value as u128)
+                .checked_mul(global_ratio)
+                .and_then(|v| {
+                    v.checked_div(Self::asset_collateral_ratio(env, &position.token) as u128)
+                })
+                .unwrap_or(0);
+            weighted = weighted.saturating_add(position_weighted.min(u64::MAX as u128) as u64);
+        }
+        Ok(weighted)
+    }
+
+    /// How much of `asset` the positions can back: their weighted value
+    /// divided by the global collateral ratio.
+    fn borrowing_capacity(
+        env: &Env,
+        asset: &Address,
+        positions: &Vec<CollateralPosition>,
+    ) -> Result<u64, LendingError> {
+        let weighted = Self::weighted_collateral_value(env, asset, positions)?;
+        Ok((weighted as u128)
+            .checked_mul(10000)
+            .and_then(|v| v.checked_div(Self::get_collateral_ratio(env) as u128))
+            .unwrap_or(0)
+            .min(u64::MAX as u128) as u64)
+    }
+
+    /// Value of `amount` of `collateral_token` in units of `asset`.
+    fn collateral_in_asset_units(
+        env: &Env,
+        asset: &Address,
+        collateral_token: &Address,
+        amount: u64,
+    ) -> Result<u64, LendingError> {
+        match Self::prices(env, asset, collateral_token)? {
+            None => Ok(amount),
+            Some((asset_price, collateral_price)) => {
+                price_oracle::convert(amount as i128, collateral_price, asset_price)
+                    .and_then(|v| u64::try_from(v).ok())
+                    .ok_or(LendingError::InvalidAmount)
+            }
+        }
+    }
+
+    /// Amount of `collateral_token` worth `amount` of `asset`.
+    fn asset_in_collateral_units(
+        env: &Env,
+        asset: &Address,
+        collateral_token: &Address,
+        amount: u64,
+    ) -> Result<u64, LendingError> {
+        match Self::prices(env, asset, collateral_token)? {
+            None => Ok(amount),
+            Some((asset_price, collateral_price)) => {
+                price_oracle::convert(amount as i128, asset_price, collateral_price)
+                    .and_then(|v| u64::try_from(v).ok())
+                    .ok_or(LendingError::InvalidAmount)
+            }
+        }
+    }
+
+    /// Weighted collateral value over debt, in basis points.
+    fn loan_health_factor(env: &Env, loan: &LoanRecord) -> Result<u32, LendingError> {
+        Self::health_factor_of(
+            env,
+            &loan.asset,
+            Self::loan_debt(env, loan)?,
+            &Self::loan_collateral(env, loan),
+        )
+    }
+
+    /// Weighted value of `positions` over `principal` of `asset`, in basis
+    /// points; see `weighted_collateral_value`.
+    fn health_factor_of(
+        env: &Env,
+        asset: &Address,
+        principal: u64,
+        positions: &Vec<CollateralPosition>,
+    ) -> Result<u32, LendingError> {
+        let weighted = Self::weighted_collateral_value(env, asset, positions)?;
+        Ok(price_oracle::health_factor_bps(
+            weighted as i128,
+            principal as i128,
+        ))
+    }
+
+    /// Collateral ratio for `token`: its override, else the global ratio.
+    fn asset_collateral_ratio(env: &Env, token: &Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AssetCollateralRatio(token.clone()))
+            .unwrap_or_else(|| Self::get_collateral_ratio(env))
+    }
+
     fn get_collateral_ratio(env: &Env) -> u32 {
         env.storage()
             .instance()
@@ -1055,27 +1327,6 @@ impl LendingContract {
         }
     }
 
-    /// Calculate simple interest for a given principal, rate, and time elapsed.
-    fn calculate_interest(principal: u64, rate_bps: u32, elapsed_seconds: u64) -> u64 {
-        if elapsed_seconds == 0 || rate_bps == 0 {
-            return 0;
-        }
-        // Interest = (Principal * Rate * Time) / (10000 * SecondsPerYear)
-        // Use u128 for intermediate calculation to avoid overflow.
-        // Round to the nearest token unit to reduce precision loss for small loans.
-        let numerator = (principal as u128)
-            .checked_mul(rate_bps as u128)
-            .and_then(|v| v.checked_mul(elapsed_seconds as u128))
-            .unwrap_or(0);
-
-        let denominator = (10000u128).checked_mul(SECONDS_IN_YEAR as u128).unwrap();
-
-        numerator
-            .checked_add(denominator / 2)
-            .and_then(|v| v.checked_div(denominator))
-            .unwrap_or(0) as u64
-    }
-
     /// Calculate the pool utilization ratio in basis points (0 to 10000)
     fn get_utilization_bps(total_borrowed: u64, total_deposits: u64) -> u32 {
         if total_deposits == 0 {
@@ -1101,6 +1352,271 @@ impl LendingContract {
         base_rate_bps.saturating_add(variable_rate as u32)
     }
 
+    fn rate_model(env: &Env) -> Option<RateModel> {
+        env.storage().instance().get(&DataKey::RateModel)
+    }
+
+    /// Borrow rate of `pool` at its current utilization: the two-slope model
+    /// when one is set, the pool's linear model otherwise.
+    fn pool_borrow_rate(env: &Env, pool: &PoolState) -> u32 {
+        let utilization_bps = Self::get_utilization_bps(pool.total_borrowed, pool.total_deposits);
+        match Self::rate_model(env) {
+            Some(model) => Self::two_slope_rate(&model, utilization_bps),
+            None => Self::calculate_dynamic_rate(
+                pool.base_rate_bps,
+                pool.multiplier_bps,
+                utilization_bps,
+            ),
+        }
+    }
+
+    /// Share of interest kept by the protocol: the rate model's reserve
+    /// factor when one is set, the pool's otherwise.
+    fn pool_reserve_factor(env: &Env, pool: &PoolState) -> u32 {
+        match Self::rate_model(env) {
+            Some(model) => model.reserve_factor_bps,
+            None => pool.reserve_factor_bps,
+        }
+    }
+
+    /// Rate depositors earn: the borrow rate on the borrowed part of the
+    /// pool, less the reserve factor.
+    fn pool_supply_rate(env: &Env, pool: &PoolState) -> u32 {
+        let utilization_bps = Self::get_utilization_bps(pool.total_borrowed, pool.total_deposits);
+        let reserve_factor = Self::pool_reserve_factor(env, pool);
+        // supply_rate = borrow_rate * utilization * (10000 - reserve_factor) / 10000^2
+        ((Self::pool_borrow_rate(env, pool) as u128)
+            .checked_mul(utilization_bps as u128)
+            .unwrap_or(0)
+            .checked_mul((10000u32.saturating_sub(reserve_factor)) as u128)
+            .unwrap_or(0)
+            / (10000u128 * 10000u128)) as u32
+    }
+
+    /// `asset`'s indexes compounded to now, with the whole units of interest
+    /// the pool's debt accrued since they were stored.
+    fn advance_index(
+        env: &Env,
+        asset: &Address,
+        pool: &PoolState,
+    ) -> Result<(InterestIndex, u64), LendingError> {
+        let mut index = interest::load(env, asset, pool.total_borrowed);
+        let now = env.ledger().timestamp();
+        let elapsed = now.saturating_sub(index.last_update);
+        if elapsed == 0 {
+            return Ok((index, 0));
+        }
+        let factor = interest::compound_factor(Self::pool_borrow_rate(env, pool), elapsed)
+            .ok_or(LendingError::InvalidAmount)?;
+        let debt_before = index.debt_units();
+        index.borrow_index =
+            interest::mul_index(index.borrow_index, factor).ok_or(LendingError::InvalidAmount)?;
+        index.debt = interest::mul_index(index.debt, factor).ok_or(LendingError::InvalidAmount)?;
+        if index.fixed_debt > 0 {
+            let fixed_factor = interest::compound_factor(index.fixed_rate_bps, elapsed)
+                .ok_or(LendingError::InvalidAmount)?;
+            index.fixed_debt = interest::mul_index(index.fixed_debt, fixed_factor)
+                .ok_or(LendingError::InvalidAmount)?;
+        }
+        index.last_update = now;
+        let accrued = index.debt_units().saturating_sub(debt_before);
+        Ok((index, accrued))
+    }
+
+    /// Bring `asset`'s pool up to date before it changes: compound its
+    /// borrow index and debt to now and book the interest, the reserve
+    /// factor's share to the protocol buckets and the rest to depositors.
+    /// The caller stores `pool`.
+    fn accrue(
+        env: &Env,
+        asset: &Address,
+        pool: &mut PoolState,
+    ) -> Result<InterestIndex, LendingError> {
+        let index = Self::accrued_index(env, asset, pool)?;
+        interest::save(env, asset, &index);
+        Ok(index)
+    }
+
+    /// `asset`'s indexes as of now, booking the interest accrued since they
+    /// were stored into `pool` without storing either.
+    fn accrued_index(
+        env: &Env,
+        asset: &Address,
+        pool: &mut PoolState,
+    ) -> Result<InterestIndex, LendingError> {
+        let (mut index, accrued) = Self::advance_index(env, asset, pool)?;
+        if accrued > 0 {
+            let (depositor_interest, protocol_interest) =
+                Self::calculate_interest_split(accrued, Self::pool_reserve_factor(env, pool));
+            let reserve_share = ((protocol_interest as u128)
+                .checked_mul(BAD_DEBT_RESERVE_BPS as u128)
+                .and_then(|v| v.checked_div(10000u128))
+                .unwrap_or(0)) as u64;
+            let retained_share = protocol_interest.saturating_sub(reserve_share);
+
+            if pool.total_deposits > 0 {
+                let growth = (depositor_interest as u128) * interest::INDEX_ONE
+                    / pool.total_deposits as u128;
+                let gained = interest::mul_index(index.supply_index, growth).unwrap_or(0);
+                index.supply_index = index.supply_index.saturating_add(gained);
+            }
+            pool.total_deposits = pool.total_deposits.saturating_add(depositor_interest);
+            pool.retained_yield = pool.retained_yield.saturating_add(retained_share);
+            pool.bad_debt_reserve = pool.bad_debt_reserve.saturating_add(reserve_share);
+            pool.total_protocol_revenue = pool
+                .total_protocol_revenue
+                .saturating_add(protocol_interest);
+        }
+        pool.total_borrowed = index.debt_units();
+        Ok(index)
+    }
+
+    /// Accrue `asset`'s pool and store it.
+    fn accrue_pool(env: &Env, asset: &Address) -> Result<PoolState, LendingError> {
+        let mut pool = Self::get_pool(env, asset)?;
+        Self::accrue(env, asset, &mut pool)?;
+        Self::set_pool(env, asset, &pool);
+        Ok(pool)
+    }
+
+    /// Borrow index of `asset`'s pool as of now, including interest not yet
+    /// booked.
+    fn current_borrow_index(env: &Env, asset: &Address) -> Result<u128, LendingError> {
+        let pool = Self::get_pool(env, asset)?;
+        Ok(Self::advance_index(env, asset, &pool)?.0.borrow_index)
+    }
+
+    /// What `loan` owes now, before late fees: its principal grown with the
+    /// pool's borrow index, or at its own rate for a fixed-rate loan.
+    fn loan_debt(env: &Env, loan: &LoanRecord) -> Result<u64, LendingError> {
+        let grown = match interest::fixed_rate(env, loan.loan_id) {
+            Some(fixed) => interest::compound_factor(
+                fixed.rate_bps,
+                env.ledger().timestamp().saturating_sub(fixed.since),
+            )
+            .and_then(|factor| interest::scale(loan.principal, interest::INDEX_ONE, factor)),
+            None => interest::scale(
+                loan.principal,
+                interest::loan_index(env, loan.loan_id),
+                Self::current_borrow_index(env, &loan.asset)?,
+            ),
+        };
+        grown.ok_or(LendingError::InvalidAmount)
+    }
+
+    /// Fold the interest `loan` has accrued into its principal, measuring it
+    /// from now on. The pool must have been accrued.
+    fn capitalize_interest(env: &Env, loan: &mut LoanRecord) -> Result<(), LendingError> {
+        loan.principal = Self::loan_debt(env, loan)?;
+        match interest::fixed_rate(env, loan.loan_id) {
+            Some(mut fixed) => {
+                fixed.since = env.ledger().timestamp();
+                interest::set_fixed_rate(env, loan.loan_id, &fixed);
+            }
+            None => interest::set_loan_index(
+                env,
+                loan.loan_id,
+                Self::current_borrow_index(env, &loan.asset)?,
+            ),
+        }
+        Ok(())
+    }
+
+    /// Add a new loan of `amount` to the pool's debt: at `fixed_rate_bps`
+    /// when given, otherwise measured from the current index. The pool must
+    /// have been accrued.
+    fn open_debt(
+        env: &Env,
+        asset: &Address,
+        pool: &mut PoolState,
+        loan_id: u64,
+        amount: u64,
+        fixed_rate_bps: Option<u32>,
+    ) {
+        let mut index = interest::load(env, asset, pool.total_borrowed);
+        match fixed_rate_bps {
+            Some(rate_bps) => {
+                index.add_fixed_debt(amount, rate_bps);
+                interest::set_fixed_rate(
+                    env,
+                    loan_id,
+                    &FixedRate {
+                        rate_bps,
+                        since: env.ledger().timestamp(),
+                    },
+                );
+            }
+            None => {
+                index.add_debt(amount);
+                interest::set_loan_index(env, loan_id, index.borrow_index);
+            }
+        }
+        pool.total_borrowed = index.debt_units();
+        interest::save(env, asset, &index);
+    }
+
+    /// Remove `amount` of `loan_id`'s debt, repaid or written off, from the
+    /// pool's debt. The pool must have been accrued.
+    fn close_debt(env: &Env, asset: &Address, pool: &mut PoolState, loan_id: u64, amount: u64) {
+        let mut index = interest::load(env, asset, pool.total_borrowed);
+        match interest::fixed_rate(env, loan_id) {
+            Some(fixed) => index.remove_fixed_debt(amount, fixed.rate_bps),
+            None => index.remove_debt(amount),
+        }
+        pool.total_borrowed = index.debt_units();
+        interest::save(env, asset, &index);
+    }
+
+    fn rate_mode_of(env: &Env, loan_id: u64) -> RateMode {
+        match interest::fixed_rate(env, loan_id) {
+            Some(_) => RateMode::Fixed,
+            None => RateMode::Variable,
+        }
+    }
+
+    fn fixed_rate_config(env: &Env, asset: &Address) -> FixedRateConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::FixedRateConfig(asset.clone()))
+            .unwrap_or(FixedRateConfig {
+                premium_bps: DEFAULT_FIXED_RATE_PREMIUM_BPS,
+                rebalance_utilization_bps: DEFAULT_REBALANCE_UTILIZATION_BPS,
+            })
+    }
+
+    /// Rate a fixed-rate loan is locked at once `additional` more is
+    /// borrowed from `pool`: the borrow rate at that utilization plus the
+    /// asset's premium.
+    fn fixed_borrow_rate(env: &Env, asset: &Address, pool: &PoolState, additional: u64) -> u32 {
+        let mut after = pool.clone();
+        after.total_borrowed = after.total_borrowed.saturating_add(additional);
+        Self::pool_borrow_rate(env, &after)
+            .saturating_add(Self::fixed_rate_config(env, asset).premium_bps)
+    }
+
+    /// Fixed rate for a loan of `amount` opened in `mode`; `None` for a
+    /// variable-rate loan.
+    fn locked_rate(
+        env: &Env,
+        asset: &Address,
+        pool: &PoolState,
+        mode: RateMode,
+        amount: u64,
+    ) -> Option<u32> {
+        match mode {
+            RateMode::Fixed => Some(Self::fixed_borrow_rate(env, asset, pool, amount)),
+            RateMode::Variable => None,
+        }
+    }
+
+    /// Rate `loan` is paying now.
+    fn loan_rate(env: &Env, loan: &LoanRecord, pool: &PoolState) -> u32 {
+        match interest::fixed_rate(env, loan.loan_id) {
+            Some(fixed) => fixed.rate_bps,
+            None => Self::pool_borrow_rate(env, pool),
+        }
+    }
+
     // ─── Public Functions ────────────────────────────
 
     /// Deposit `amount` of the specific asset into its pool.
@@ -1124,6 +1640,7 @@ impl LendingContract {
         if pool.is_paused {
             return Err(LendingError::PoolPaused);
         }
+        Self::accrue(&env, &asset, &mut pool)?;
 
         let contract_id = env.current_contract_address();
         Self::transfer(&env, &asset, &depositor, &contract_id, amount)?;
@@ -1195,6 +1712,7 @@ impl LendingContract {
         if pool.is_paused {
             return Err(LendingError::PoolPaused);
         }
+        Self::accrue(&env, &asset, &mut pool)?;
 
         let amount = Self::assets_for_shares(&pool, shares);
 
@@ -1238,6 +1756,7 @@ impl LendingContract {
 
     /// Borrow `amount` of the specific asset from the pool with collateral.
     /// Requires overcollateralized borrowing based on collateral ratio.
+    /// The loan pays the pool's variable rate.
     /// Returns the unique loan ID.
     pub fn borrow(
         env: Env,
@@ -1247,6 +1766,32 @@ impl LendingContract {
         collateral_token: Address,
         collateral_amount: u64,
         duration_seconds: u64,
+    ) -> Result<u64, LendingError> {
+        Self::borrow_with_rate_mode(
+            env,
+            borrower,
+            asset,
+            amount,
+            collateral_token,
+            collateral_amount,
+            duration_seconds,
+            RateMode::Variable,
+        )
+    }
+
+    /// Borrow like `borrow`, choosing how the loan's rate is set: variable,
+    /// following the pool's utilization curve, or fixed at the pool's rate
+    /// plus the asset's fixed-rate premium.
+    #[allow(clippy::too_many_arguments)]
+    pub fn borrow_with_rate_mode(
+        env: Env,
+        borrower: Address,
+        asset: Address,
+        amount: u64,
+        collateral_token: Address,
+        collateral_amount: u64,
+        duration_seconds: u64,
+        rate_mode: RateMode,
     ) -> Result<u64, LendingError> {
         Self::require_not_paused(&env)?;
         Self::require_initialized(&env)?;
@@ -1261,6 +1806,7 @@ impl LendingContract {
         if pool.is_paused {
             return Err(LendingError::PoolPaused);
         }
+        Self::accrue(&env, &asset, &mut pool)?;
 
         // Check collateral token is whitelisted
         if !Self::is_collateral_whitelisted(&env, &collateral_token) {
@@ -1273,13 +1819,15 @@ impl LendingContract {
             return Err(LendingError::LoanAlreadyExists);
         }
 
-        // Check collateral ratio (collateral_amount must be >= amount * ratio / 10000)
-        let required_collateral = (amount as u128)
-            .checked_mul(Self::get_collateral_ratio(&env) as u128)
-            .and_then(|v| v.checked_div(10000))
-            .unwrap_or(0) as u64;
-
-        if collateral_amount < required_collateral {
+        // Check the collateral's ratio-weighted value covers the loan
+        let positions = vec![
+            &env,
+            CollateralPosition {
+                token: collateral_token.clone(),
+                amount: collateral_amount,
+            },
+        ];
+        if Self::borrowing_capacity(&env, &asset, &positions)? < amount {
             return Err(LendingError::InsufficientCollateral);
         }
 
@@ -1305,15 +1853,13 @@ impl LendingContract {
             collateral_amount,
         )?;
 
-        pool.total_borrowed += amount;
-
-        let utilization_bps = Self::get_utilization_bps(pool.total_borrowed, pool.total_deposits);
-        let dynamic_rate_bps =
-            Self::calculate_dynamic_rate(pool.base_rate_bps, pool.multiplier_bps, utilization_bps);
-
+        let loan_id = Self::increment_loan_id(&env);
+        let fixed_rate_bps = Self::locked_rate(&env, &asset, &pool, rate_mode, amount);
+        Self::open_debt(&env, &asset, &mut pool, loan_id, amount, fixed_rate_bps);
+        let interest_rate_bps =
+            fixed_rate_bps.unwrap_or_else(|| Self::pool_borrow_rate(&env, &pool));
         Self::set_pool(&env, &asset, &pool);
 
-        let loan_id = Self::increment_loan_id(&env);
         let borrow_time = env.ledger().timestamp();
         let due_date = borrow_time + duration_seconds;
 
@@ -1326,7 +1872,7 @@ impl LendingContract {
             collateral_token: collateral_token.clone(),
             borrow_time,
             due_date,
-            interest_rate_bps: dynamic_rate_bps,
+            interest_rate_bps,
         };
 
         env.storage()
@@ -1337,11 +1883,11 @@ impl LendingContract {
             .set(&DataKey::LoanById(loan_id), &loan);
         Self::add_user_loan(&env, &borrower, loan_id);
 
-        // Mint NFT if token is set
+        // Mint NFT if token is set; the pool holds the lender's claim until it sells it
         if let Some(nft_token) = Self::get_nft_token(&env) {
             let nft_client = LoanNFTClient::new(&env, &nft_token);
             nft_client.mint(
-                &borrower,
+                &contract_id,
                 &LoanMetadata {
                     borrower: borrower.clone(),
                     asset: asset.clone(),
@@ -1404,14 +1950,23 @@ impl LendingContract {
             .get(&DataKey::Loan(borrower.clone()))
             .ok_or(LendingError::NoOpenLoan)?;
 
-        let elapsed = env.ledger().timestamp().saturating_sub(loan.borrow_time);
-        let interest = Self::calculate_interest(loan.principal, loan.interest_rate_bps, elapsed);
+        let mut pool = Self::get_pool(&env, &loan.asset)?;
+        Self::accrue(&env, &loan.asset, &mut pool)?;
+        let debt = Self::loan_debt(&env, &loan)?;
+        let interest = debt.saturating_sub(loan.principal);
         let late_fee = Self::calculate_late_fee(env.clone(), borrower.clone())?;
-        let total_repayment = loan.principal + interest + late_fee;
+        let total_repayment = debt + late_fee;
         let grace_period_end = Self::grace_period_end(&env, &loan)?;
+        let payee = Self::loan_payee(&env, loan.loan_id);
 
         let contract_id = env.current_contract_address();
-        Self::transfer(&env, &loan.asset, &borrower, &contract_id, total_repayment)?;
+        Self::transfer(
+            &env,
+            &loan.asset,
+            &borrower,
+            payee.as_ref().unwrap_or(&contract_id),
+            total_repayment,
+        )?;
 
         // Return collateral to borrower
         Self::transfer(
@@ -1421,27 +1976,29 @@ impl LendingContract {
             &borrower,
             loan.collateral_amount,
         )?;
-
-        let mut pool = Self::get_pool(&env, &loan.asset)?;
-        pool.total_borrowed -= loan.principal;
-
-        // Retain 10% of interest for protocol buckets, with part routed to bad-debt reserve.
-        let protocol_share = ((interest as u128)
-            .checked_mul(PROTOCOL_INTEREST_BPS as u128)
-            .and_then(|v| v.checked_div(10000))
-            .unwrap_or(0)) as u64;
-        let reserve_share = ((protocol_share as u128)
-            .checked_mul(BAD_DEBT_RESERVE_BPS as u128)
-            .and_then(|v| v.checked_div(10000))
-            .unwrap_or(0)) as u64;
-        let retained_share = protocol_share.saturating_sub(reserve_share);
-        let pool_share = interest - protocol_share;
-
-        // Late fees go entirely to retained_yield (protocol reserve)
-        pool.total_deposits += pool_share; // Interest increases pool value for share holders
-        pool.retained_yield += retained_share + late_fee;
-        pool.bad_debt_reserve += reserve_share;
+        for position in collateral::basket(&env, &borrower).iter() {
+            Self::transfer(
+                &env,
+                &position.token,
+                &contract_id,
+                &borrower,
+                position.amount,
+            )?;
+        }
+        collateral::clear_basket(&env, &borrower);
+
+        // Interest was booked to depositors and reserves as it accrued;
+        // late fees go entirely to retained_yield (protocol reserve).
+        // A sold loan left the pool's books when it was sold.
+        if payee.is_none() {
+            Self::close_debt(&env, &loan.asset, &mut pool, loan.loan_id, debt);
+            pool.retained_yield += late_fee;
+        }
         Self::set_pool(&env, &loan.asset, &pool);
+        interest::clear_loan(&env, loan.loan_id);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::LoanPayee(loan.loan_id));
 
         env.storage()
             .persistent()
@@ -1515,11 +2072,9 @@ impl LendingContract {
 
         match loan_opt {
             Some(loan) => {
-                let elapsed = env.ledger().timestamp().saturating_sub(loan.borrow_time);
-                let interest =
-                    Self::calculate_interest(loan.principal, loan.interest_rate_bps, elapsed);
+                let debt = Self::loan_debt(&env, &loan)?;
                 let late_fee = Self::calculate_late_fee(env, borrower)?;
-                Ok(loan.principal + interest + late_fee)
+                Ok(debt + late_fee)
             }
             None => Err(LendingError::NoOpenLoan),
         }
@@ -1536,9 +2091,9 @@ impl LendingContract {
 
         match loan_opt {
             Some(loan) => {
+                let pool = Self::accrue_pool(&env, &loan.asset)?;
                 let elapsed = env.ledger().timestamp().saturating_sub(loan.borrow_time);
-                let interest =
-                    Self::calculate_interest(loan.principal, loan.interest_rate_bps, elapsed);
+                let interest = Self::loan_debt(&env, &loan)?.saturating_sub(loan.principal);
 
                 env.events().publish(
                     (symbol_short!("POOL"), symbol_short!("INTEREST")),
@@ -1548,7 +2103,7 @@ impl LendingContract {
                         asset: loan.asset.clone(),
                         principal: loan.principal,
                         interest_accrued: interest,
-                        interest_rate_bps: loan.interest_rate_bps,
+                        interest_rate_bps: Self::loan_rate(&env, &loan, &pool),
                         elapsed_seconds: elapsed,
                         timestamp: env.ledger().timestamp(),
                     },
@@ -1587,6 +2142,7 @@ impl LendingContract {
         }
 
         let mut pool = Self::get_pool(&env, &asset)?;
+        Self::accrue(&env, &asset, &mut pool)?;
 
         if amount > pool.retained_yield {
             return Err(LendingError::InsufficientLiquidity);
@@ -1652,16 +2208,26 @@ impl LendingContract {
         Ok(pool.total_deposits.saturating_sub(pool.total_borrowed))
     }
 
-    /// Returns the current dynamic interest rate that would be given to a new loan for a specific asset
+    /// Returns the current borrow rate for a specific asset: the rate its borrow index
+    /// compounds at, recorded on a new loan
     pub fn get_current_interest_rate(env: Env, asset: Address) -> Result<u32, LendingError> {
         Self::require_initialized(&env)?;
         let pool = Self::get_pool(&env, &asset)?;
-        let utilization_bps = Self::get_utilization_bps(pool.total_borrowed, pool.total_deposits);
-        Ok(Self::calculate_dynamic_rate(
-            pool.base_rate_bps,
-            pool.multiplier_bps,
-            utilization_bps,
-        ))
+        Ok(Self::pool_borrow_rate(&env, &pool))
+    }
+
+    /// Returns the current supply rate depositors earn on a specific asset
+    pub fn get_asset_supply_rate(env: Env, asset: Address) -> Result<u32, LendingError> {
+        Self::require_initialized(&env)?;
+        let pool = Self::get_pool(&env, &asset)?;
+        Ok(Self::pool_supply_rate(&env, &pool))
+    }
+
+    /// Returns a specific asset's borrow and supply indexes as of now
+    pub fn get_interest_index(env: Env, asset: Address) -> Result<InterestIndex, LendingError> {
+        Self::require_initialized(&env)?;
+        let mut pool = Self::get_pool(&env, &asset)?;
+        Self::accrued_index(&env, &asset, &mut pool)
     }
 
     // ─── Grace Period & Late Fee Functions ────────────
@@ -1740,11 +2306,10 @@ impl LendingContract {
             .get(&DataKey::Loan(borrower.clone()))
             .ok_or(LendingError::NoOpenLoan)?;
 
-        let elapsed = env.ledger().timestamp().saturating_sub(loan.borrow_time);
-        let interest = Self::calculate_interest(loan.principal, loan.interest_rate_bps, elapsed);
+        let debt = Self::loan_debt(&env, &loan)?;
         let late_fee = Self::calculate_late_fee(env, borrower)?;
 
-        Ok(loan.principal + interest + late_fee)
+        Ok(debt + late_fee)
     }
 
     // ─── Admin Functions ─────────────────────────────
@@ -1781,6 +2346,231 @@ impl LendingContract {
         Self::get_collateral_ratio(&env)
     }
 
+    /// Set the collateral ratio of one collateral token, overriding the
+    /// global ratio (admin only)
+    pub fn set_asset_collateral_ratio(
+        env: Env,
+        admin: Address,
+        token: Address,
+        ratio_bps: u32,
+    ) -> Result<(), LendingError> {
+        Self::require_admin(&env, &admin)?;
+        if ratio_bps < 10000 {
+            return Err(LendingError::InvalidAmount);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::AssetCollateralRatio(token), &ratio_bps);
+        Ok(())
+    }
+
+    /// Collateral ratio applied to `token` in basis points
+    pub fn get_asset_collateral_ratio_bps(env: Env, token: Address) -> u32 {
+        Self::asset_collateral_ratio(&env, &token)
+    }
+
+    /// Post more collateral against the borrower's open loan. The primary
+    /// collateral token tops up the loan itself; any other whitelisted
+    /// token joins the position's basket.
+    pub fn add_collateral(
+        env: Env,
+        borrower: Address,
+        token: Address,
+        amount: u64,
+    ) -> Result<(), LendingError> {
+        Self::require_not_paused(&env)?;
+        Self::require_initialized(&env)?;
+        Self::enter_reentrancy_guard(&env)?;
+        borrower.require_auth();
+
+        if amount == 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+        if !Self::is_collateral_whitelisted(&env, &token) {
+            return Err(LendingError::CollateralNotWhitelisted);
+        }
+        let mut loan: LoanRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Loan(borrower.clone()))
+            .ok_or(LendingError::NoOpenLoan)?;
+
+        Self::transfer(
+            &env,
+            &token,
+            &borrower,
+            &env.current_contract_address(),
+            amount,
+        )?;
+
+        if token == loan.collateral_token {
+            loan.collateral_amount = loan
+                .collateral_amount
+                .checked_add(amount)
+                .ok_or(LendingError::InvalidAmount)?;
+            Self::save_loan(&env, &loan);
+        } else {
+            let mut basket = collateral::basket(&env, &borrower);
+            if !collateral::deposit(&mut basket, &token, amount) {
+                return Err(LendingError::InvalidAmount);
+            }
+            collateral::set_basket(&env, &borrower, &basket);
+        }
+
+        env.events().publish(
+            (symbol_short!("COLL"), symbol_short!("ADD")),
+            CollateralAddedEvent {
+                loan_id: loan.loan_id,
+                borrower,
+                token,
+                amount,
+            },
+        );
+        Self::exit_reentrancy_guard(&env);
+        Ok(())
+    }
+
+    /// Withdraw collateral from the borrower's open loan, provided the
+    /// remaining collateral still covers the principal at each token's
+    /// collateral ratio.
+    pub fn withdraw_collateral(
+        env: Env,
+        borrower: Address,
+        token: Address,
+        amount: u64,
+    ) -> Result<(), LendingError> {
+        Self::require_not_paused(&env)?;
+        Self::require_initialized(&env)?;
+        Self::enter_reentrancy_guard(&env)?;
+        borrower.require_auth();
+
+        if amount == 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+        let mut loan: LoanRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Loan(borrower.clone()))
+            .ok_or(LendingError::NoOpenLoan)?;
+
+        let mut basket = collateral::basket(&env, &borrower);
+        if token == loan.collateral_token {
+            if loan.collateral_amount < amount {
+                return Err(LendingError::InvalidAmount);
+            }
+            loan.collateral_amount -= amount;
+        } else if !collateral::withdraw(&mut basket, &token, amount) {
+            return Err(LendingError::InvalidAmount);
+        }
+
+        let mut positions = vec![
+            &env,
+            CollateralPosition {
+                token: loan.collateral_token.clone(),
+                amount: loan.collateral_amount,
+            },
+        ];
+        positions.append(&basket);
+        if Self::borrowing_capacity(&env, &loan.asset, &positions)? < loan.principal {
+            return Err(LendingError::InsufficientCollateral);
+        }
+
+        Self::save_loan(&env, &loan);
+        collateral::set_basket(&env, &borrower, &basket);
+        Self::transfer(
+            &env,
+            &token,
+            &env.current_contract_address(),
+            &borrower,
+            amount,
+        )?;
+
+        env.events().publish(
+            (symbol_short!("COLL"), symbol_short!("WITHDRAW")),
+            CollateralWithdrawnEvent {
+                loan_id: loan.loan_id,
+                borrower,
+                token,
+                amount,
+            },
+        );
+        Self::exit_reentrancy_guard(&env);
+        Ok(())
+    }
+
+    /// Every collateral position backing the borrower's open loan, primary
+    /// collateral first
+    pub fn get_collateral_basket(
+        env: Env,
+        borrower: Address,
+    ) -> Result<Vec<CollateralPosition>, LendingError> {
+        let loan: LoanRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Loan(borrower))
+            .ok_or(LendingError::NoOpenLoan)?;
+        Ok(Self::loan_collateral(&env, &loan))
+    }
+
+    /// Largest amount of `token` the borrower can withdraw without falling
+    /// below the collateral requirement
+    pub fn get_max_withdrawable_collateral(
+        env: Env,
+        borrower: Address,
+        token: Address,
+    ) -> Result<u64, LendingError> {
+        let loan: LoanRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Loan(borrower.clone()))
+            .ok_or(LendingError::NoOpenLoan)?;
+        // Capacity of every other position, and what `token` must still cover
+        let mut held = 0u64;
+        let mut others = Vec::new(&env);
+        for position in Self::loan_collateral(&env, &loan).iter() {
+            if position.token == token {
+                held = position.amount;
+            } else {
+                others.push_back(position);
+            }
+        }
+        let shortfall =
+            loan.principal
+                .saturating_sub(Self::borrowing_capacity(&env, &loan.asset, &others)?);
+        if shortfall == 0 {
+            return Ok(held);
+        }
+        let required_value = (shortfall as u128)
+            .checked_mul(Self::asset_collateral_ratio(&env, &token) as u128)
+            .map(|v| v.div_ceil(10000))
+            .filter(|v| *v <= u64::MAX as u128)
+            .ok_or(LendingError::InvalidAmount)? as u64;
+        let mut required =
+            Self::asset_in_collateral_units(&env, &loan.asset, &token, required_value)?;
+        // Round the requirement up so the withdrawal passes the capacity check
+        while required < held {
+            let mut remaining = others.clone();
+            remaining.push_back(CollateralPosition {
+                token: token.clone(),
+                amount: required,
+            });
+            if Self::borrowing_capacity(&env, &loan.asset, &remaining)? >= loan.principal {
+                break;
+            }
+            required += 1;
+        }
+        Ok(held.saturating_sub(required))
+    }
+
+    fn save_loan(env: &Env, loan: &LoanRecord) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Loan(loan.borrower.clone()), loan);
+        env.storage()
+            .persistent()
+            .set(&DataKey::LoanById(loan.loan_id), loan);
+    }
+
     /// Set the grace period for loans (admin only)
     /// Grace period is the time after due date during which no late fees accrue
     pub fn set_grace_period(
@@ -1839,6 +2629,82 @@ impl LendingContract {
         Ok(pool.late_fee_rate_bps)
     }
 
+    /// Price collateral and debt through a SEP-40 feed (admin only).
+    /// Collateral requirements and health factors then compare values in the
+    /// feed's quote currency instead of raw token amounts.
+    pub fn set_price_oracle(env: Env, admin: Address, oracle: Address) -> Result<(), LendingError> {
+        Self::require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::PriceOracle, &oracle);
+        env.events().publish(
+            (symbol_short!("ORACLE"), symbol_short!("SET")),
+            PriceOracleSetEvent { admin, oracle },
+        );
+        Ok(())
+    }
+
+    pub fn get_price_oracle(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PriceOracle)
+    }
+
+    /// Set the maximum age in seconds of a feed price (admin only).
+    /// Defaults to `price_oracle::DEFAULT_MAX_PRICE_AGE`; must be non-zero.
+    pub fn set_max_oracle_age(
+        env: Env,
+        admin: Address,
+        max_age_seconds: u64,
+    ) -> Result<(), LendingError> {
+        Self::require_admin(&env, &admin)?;
+        if max_age_seconds == 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxOracleAge, &max_age_seconds);
+        Ok(())
+    }
+
+    pub fn get_max_oracle_age(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxOracleAge)
+            .unwrap_or(price_oracle::DEFAULT_MAX_PRICE_AGE)
+    }
+
+    /// Set the decimals used to value amounts of `token` (admin only).
+    pub fn set_asset_decimals(
+        env: Env,
+        admin: Address,
+        token: Address,
+        decimals: u32,
+    ) -> Result<(), LendingError> {
+        Self::require_admin(&env, &admin)?;
+        if decimals > price_oracle::MAX_ASSET_DECIMALS {
+            return Err(LendingError::InvalidAmount);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::AssetDecimals(token), &decimals);
+        Ok(())
+    }
+
+    pub fn get_asset_decimals(env: Env, token: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AssetDecimals(token))
+            .unwrap_or(price_oracle::DEFAULT_ASSET_DECIMALS)
+    }
+
+    /// Ratio-weighted collateral value over principal of a borrower's loan, in
+    /// basis points
+    pub fn get_health_factor(env: Env, borrower: Address) -> Result<u32, LendingError> {
+        let loan: LoanRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Loan(borrower))
+            .ok_or(LendingError::NoOpenLoan)?;
+        Self::loan_health_factor(&env, &loan)
+    }
+
     pub fn get_flash_loan_fee(env: Env) -> u32 {
         env.storage()
             .instance()
@@ -1893,13 +2759,17 @@ impl LendingContract {
     ///
     /// Security properties:
     /// - The caller (`initiator`) must authorise the call via `require_auth`.
-    /// - `receiver_id` must be a whitelisted contract address.
+    /// - `receiver_id` must be a whitelisted contract implementing
+    ///   `flash_loan::FlashLoanReceiver`.
     /// - A reentrancy guard is held for the entire duration of the call,
     ///   including the external `execute_operation` callback.  Any attempt by
     ///   the receiver to re-enter this contract is rejected with
     ///   `LendingError::ReentrantCall` before any state is modified.
     /// - The contract balance is verified after the callback to ensure the
     ///   principal plus fee has been returned in full.
+    /// - The pool's accounting (deposits, shares, borrows and reserves) must be
+    ///   exactly as it was before the callback, otherwise the loan fails with
+    ///   `LendingError::FlashLoanInvariantViolated`.
     pub fn flash_loan(
         env: Env,
         initiator: Address,
@@ -1914,14 +2784,7 @@ impl LendingContract {
         initiator.require_auth();
 
         // Only whitelisted receiver contracts may be used.
-        let is_whitelisted: bool = env
-            .storage()
-            .persistent()
-            .get(&DataKey::WhitelistedFlashReceiver(receiver_id.clone()))
-            .unwrap_or(false);
-        if !is_whitelisted {
-            return Err(LendingError::Unauthorized);
-        }
+        Self::require_flash_receiver(&env, &receiver_id)?;
 
         // Acquire the reentrancy guard before any external calls.
         // The guard is released via exit_reentrancy_guard on every exit path
@@ -1931,7 +2794,14 @@ impl LendingContract {
 
         // Delegate to the inner implementation so we can unconditionally release
         // the guard regardless of whether the inner logic succeeds or fails.
-        let result = Self::flash_loan_inner(&env, initiator, receiver_id, asset, amount);
+        let result = Self::flash_loan_inner(
+            &env,
+            initiator,
+            receiver_id,
+            vec![&env, asset],
+            vec![&env, amount],
+            false,
+        );
 
         // Always release the guard — Soroban reverts storage on panic/trap anyway,
         // but explicit release keeps the contract usable after a recoverable error.
@@ -1940,85 +2810,173 @@ impl LendingContract {
         result
     }
 
-    /// Inner flash loan logic, called only while the reentrancy guard is held.
-    /// All early returns here are safe because the caller releases the guard.
-    fn flash_loan_inner(
-        env: &Env,
+    /// Flash-borrow several assets in one call. `amounts[i]` of `assets[i]` is
+    /// lent, the receiver's `execute_batch` callback is invoked once with every
+    /// loan, and each must come back with its fee.
+    ///
+    /// The same checks as `flash_loan` apply to every asset. Assets must be
+    /// distinct, and at most `MAX_FLASH_LOAN_ASSETS` may be borrowed at once.
+    pub fn flash_loan_batch(
+        env: Env,
         initiator: Address,
         receiver_id: Address,
-        asset: Address,
-        amount: u64,
+        assets: Vec<Address>,
+        amounts: Vec<u64>,
     ) -> Result<(), LendingError> {
-        if amount == 0 {
+        Self::require_not_paused(&env)?;
+        Self::require_initialized(&env)?;
+        initiator.require_auth();
+        Self::require_flash_receiver(&env, &receiver_id)?;
+
+        if assets.is_empty()
+            || assets.len() != amounts.len()
+            || assets.len() > MAX_FLASH_LOAN_ASSETS
+        {
             return Err(LendingError::InvalidAmount);
         }
-
-        let mut pool = Self::get_pool(env, &asset)?;
-        if pool.is_paused {
-            return Err(LendingError::PoolPaused);
+        for i in 1..assets.len() {
+            let asset = assets.get(i).unwrap();
+            if (0..i).any(|j| assets.get(j).unwrap() == asset) {
+                return Err(LendingError::InvalidAmount);
+            }
         }
 
-        let available = pool.total_deposits.saturating_sub(pool.total_borrowed);
-        if amount > available {
-            return Err(LendingError::InsufficientLiquidity);
-        }
+        Self::enter_reentrancy_guard(&env)?;
+        let result = Self::flash_loan_inner(&env, initiator, receiver_id, assets, amounts, true);
+        Self::exit_reentrancy_guard(&env);
 
-        // Check utilization cap
-        let new_borrowed = pool.total_borrowed + amount;
-        let new_utilization_bps = Self::get_utilization_bps(new_borrowed, pool.total_deposits);
-        if new_utilization_bps > pool.utilization_cap_bps {
-            return Err(LendingError::UtilizationCapExceeded);
+        result
+    }
+
+    fn require_flash_receiver(env: &Env, receiver_id: &Address) -> Result<(), LendingError> {
+        let is_whitelisted: bool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WhitelistedFlashReceiver(receiver_id.clone()))
+            .unwrap_or(false);
+        if !is_whitelisted {
+            return Err(LendingError::Unauthorized);
         }
+        Ok(())
+    }
 
+    /// Inner flash loan logic, called only while the reentrancy guard is held.
+    /// All early returns here are safe because the caller releases the guard.
+    ///
+    /// `batch` selects the receiver callback: `execute_batch` with every loan,
+    /// or `execute_operation` for the single loan.
+    fn flash_loan_inner(
+        env: &Env,
+        initiator: Address,
+        receiver_id: Address,
+        assets: Vec<Address>,
+        amounts: Vec<u64>,
+        batch: bool,
+    ) -> Result<(), LendingError> {
         let fee_bps: u32 = env
             .storage()
             .instance()
             .get(&DataKey::FlashLoanFeeBps)
             .unwrap_or(9u32);
-        let fee = (amount as u128)
-            .checked_mul(fee_bps as u128)
-            .and_then(|v| v.checked_div(10000))
-            .unwrap_or(0) as u64;
-
         let contract_id = env.current_contract_address();
-        let token_client = token::Client::new(env, &asset);
-        let balance_before = token_client.balance(&contract_id);
+
+        let mut loans: Vec<FlashLoan> = Vec::new(env);
+        let mut snapshots: Vec<PoolState> = Vec::new(env);
+        let mut balances_before: Vec<i128> = Vec::new(env);
+        for (asset, amount) in assets.iter().zip(amounts.iter()) {
+            if amount == 0 {
+                return Err(LendingError::InvalidAmount);
+            }
+
+            let mut pool = Self::get_pool(env, &asset)?;
+            if pool.is_paused {
+                return Err(LendingError::PoolPaused);
+            }
+            Self::accrue(env, &asset, &mut pool)?;
+
+            let available = pool.total_deposits.saturating_sub(pool.total_borrowed);
+            if amount > available {
+                return Err(LendingError::InsufficientLiquidity);
+            }
+
+            // Check utilization cap
+            let new_borrowed = pool.total_borrowed + amount;
+            let new_utilization_bps = Self::get_utilization_bps(new_borrowed, pool.total_deposits);
+            if new_utilization_bps > pool.utilization_cap_bps {
+                return Err(LendingError::UtilizationCapExceeded);
+            }
+
+            let fee = (amount as u128)
+                .checked_mul(fee_bps as u128)
+                .and_then(|v| v.checked_div(10000))
+                .unwrap_or(0) as u64;
+
+            // Store the accrued pool so that the snapshot is what the receiver
+            // sees, and what it must leave behind.
+            Self::set_pool(env, &asset, &pool);
+            snapshots.push_back(pool);
+            balances_before.push_back(token::Client::new(env, &asset).balance(&contract_id));
+            loans.push_back(FlashLoan { asset, amount, fee });
+        }
 
         // 1. Transfer funds to the receiver.
-        token_client.transfer(&contract_id, &receiver_id, &(amount as i128));
+        for loan in loans.iter() {
+            token::Client::new(env, &loan.asset).transfer(
+                &contract_id,
+                &receiver_id,
+                &(loan.amount as i128),
+            );
+        }
 
         // 2. Invoke the receiver callback.
         //    The reentrancy guard is already locked, so any attempt by the
-        //    receiver to re-enter this contract will be rejected with
-        //    LendingError::ReentrantCall before any state is modified.
+        //    receiver to re-enter this contract will be rejected.
         //    The true `initiator` address is forwarded so the receiver can
         //    verify who triggered the flash loan.
         let receiver_client = FlashLoanReceiverClient::new(env, &receiver_id);
-        receiver_client.execute_operation(&amount, &fee, &initiator);
+        if batch {
+            receiver_client.execute_batch(&loans, &initiator);
+        } else {
+            let loan = loans.get(0).unwrap();
+            receiver_client.execute_operation(&loan.amount, &loan.fee, &initiator);
+        }
 
-        // 3. Verify the loan plus fee has been repaid in full.
-        //    `balance_after` must be at least `balance_before + fee` — i.e. the
-        //    full principal has been returned and the fee has been added on top.
-        let balance_after = token_client.balance(&contract_id);
-        let required_balance = balance_before + (fee as i128);
+        for (i, loan) in loans.iter().enumerate() {
+            let i = i as u32;
 
-        if balance_after < required_balance {
-            return Err(LendingError::FlashLoanNotRepaid);
-        }
+            // 3. Verify the loan plus fee has been repaid in full.
+            //    `balance_after` must be at least `balance_before + fee` — i.e. the
+            //    full principal has been returned and the fee has been added on top.
+            let balance_after = token::Client::new(env, &loan.asset).balance(&contract_id);
+            let required_balance = balances_before.get(i).unwrap() + (loan.fee as i128);
+            if balance_after < required_balance {
+                return Err(LendingError::FlashLoanNotRepaid);
+            }
 
-        // 4. Credit the fee to the pool.
-        pool.total_deposits += fee;
-        Self::set_pool(env, &asset, &pool);
+            // 4. Verify the callback left the pool's books alone. Funds routed
+            //    back through another entry point (a deposit, a reserve top-up)
+            //    would pass the balance check but leave a claim on them behind.
+            //    The host already refuses re-entry into this contract; this
+            //    keeps the loan sound should the pool ever change some other way.
+            let mut pool = Self::get_pool(env, &loan.asset)?;
+            if pool != snapshots.get(i).unwrap() {
+                return Err(LendingError::FlashLoanInvariantViolated);
+            }
 
-        env.events().publish(
-            (symbol_short!("POOL"), symbol_short!("FLASHL")),
-            FlashLoanEvent {
-                receiver: receiver_id,
-                asset: asset.clone(),
-                amount,
-                fee,
-            },
-        );
+            // 5. Credit the fee to the pool.
+            pool.total_deposits += loan.fee;
+            Self::set_pool(env, &loan.asset, &pool);
+
+            env.events().publish(
+                (symbol_short!("POOL"), symbol_short!("FLASHL")),
+                FlashLoanEvent {
+                    receiver: receiver_id.clone(),
+                    asset: loan.asset.clone(),
+                    amount: loan.amount,
+                    fee: loan.fee,
+                },
+            );
+        }
 
         Ok(())
     }
@@ -2344,7 +3302,9 @@ impl LendingContract {
     }
 
     /// Liquidate an underwater loan by paying part of the debt and seizing collateral
-    /// Only callable if the loan's health factor is below a safe threshold AND grace period has expired
+    /// Only callable if the loan's health factor is below a safe threshold AND grace period has expired.
+    /// The seize bonus and the close factor capping `amount` come from the linked
+    /// governance contract; a liquidation must not leave the loan less healthy.
     pub fn liquidate(
         env: Env,
         liquidator: Address,
@@ -2355,12 +3315,16 @@ impl LendingContract {
         Self::enter_reentrancy_guard(&env)?;
         liquidator.require_auth();
 
-        let loan: LoanRecord = env
+        let mut loan: LoanRecord = env
             .storage()
             .persistent()
             .get(&DataKey::Loan(borrower.clone()))
             .ok_or(LendingError::NoOpenLoan)?;
 
+        // Liquidators repay against the debt including accrued interest
+        Self::accrue_pool(&env, &loan.asset)?;
+        Self::capitalize_interest(&env, &mut loan)?;
+
         if amount == 0 || amount > loan.principal {
             return Err(LendingError::InvalidAmount);
         }
@@ -2370,11 +3334,7 @@ impl LendingContract {
             return Err(LendingError::InvalidAmount);
         }
 
-        // Calculate health factor (collateral / debt ratio)
-        let health_factor = (loan.collateral_amount as u128)
-            .checked_mul(10000)
-            .and_then(|v| v.checked_div(loan.principal as u128))
-            .unwrap_or(0) as u32;
+        let health_factor = Self::loan_health_factor(&env, &loan)?;
 
         // Allow liquidation if health factor is below 150% (15000 basis points)
         let liquidation_threshold_bps = LIQUIDATION_THRESHOLD_BPS;
@@ -2382,20 +3342,106 @@ impl LendingContract {
             return Err(LendingError::InvalidAmount);
         }
 
-        // Calculate collateral to seize (with small penalty/bonus to liquidator)
-        let collateral_to_seize = (amount as u128)
-            .checked_mul(15000) // 150% of the amount repaid
+        let (bonus_bps, close_factor_bps) = Self::liquidation_params(&env, &loan.asset);
+        let max_repay = ((loan.principal as u128)
+            .checked_mul(close_factor_bps as u128)
             .and_then(|v| v.checked_div(10000))
-            .unwrap_or(amount as u128) as u64;
+            .unwrap_or(0) as u64)
+            .max(1);
+        if amount > max_repay {
+            return Err(LendingError::LiquidationExceedsCloseFactor);
+        }
 
-        if collateral_to_seize > loan.collateral_amount {
+        // Collateral worth the repaid amount plus the liquidator's bonus
+        let seize_value = (amount as u128)
+            .checked_mul(10000 + bonus_bps as u128)
+            .and_then(|v| v.checked_div(10000))
+            .ok_or(LendingError::InvalidAmount)? as u64;
+        // Seize from the primary collateral first, then from the basket
+        let primary_value = Self::collateral_in_asset_units(
+            &env,
+            &loan.asset,
+            &loan.collateral_token,
+            loan.collateral_amount,
+        )?;
+        let (collateral_to_seize, mut remaining_value) = if seize_value <= primary_value {
+            let units = Self::asset_in_collateral_units(
+                &env,
+                &loan.asset,
+                &loan.collateral_token,
+                seize_value,
+            )?;
+            (units.min(loan.collateral_amount), 0)
+        } else {
+            (loan.collateral_amount, seize_value - primary_value)
+        };
+        let mut basket = collateral::basket(&env, &borrower);
+        let mut basket_seized = Vec::new(&env);
+        for i in 0..basket.len() {
+            if remaining_value == 0 {
+                break;
+            }
+            let mut position = basket.get(i).unwrap();
+            let value = Self::collateral_in_asset_units(
+                &env,
+                &loan.asset,
+                &position.token,
+                position.amount,
+            )?;
+            let taken = if remaining_value >= value {
+                remaining_value -= value;
+                position.amount
+            } else {
+                let units = Self::asset_in_collateral_units(
+                    &env,
+                    &loan.asset,
+                    &position.token,
+                    remaining_value,
+                )?;
+                remaining_value = 0;
+                units.min(position.amount)
+            };
+            position.amount -= taken;
+            basket_seized.push_back(CollateralPosition {
+                token: position.token.clone(),
+                amount: taken,
+            });
+            basket.set(i, position);
+        }
+        if remaining_value > 0 {
             return Err(LendingError::InvalidAmount);
         }
 
+        loan.principal -= amount;
+        loan.collateral_amount -= collateral_to_seize;
+        let health_factor_after = if loan.principal == 0 {
+            u32::MAX
+        } else {
+            let mut positions = vec![
+                &env,
+                CollateralPosition {
+                    token: loan.collateral_token.clone(),
+                    amount: loan.collateral_amount,
+                },
+            ];
+            positions.append(&basket);
+            Self::health_factor_of(&env, &loan.asset, loan.principal, &positions)?
+        };
+        if health_factor_after < health_factor {
+            return Err(LendingError::LiquidationWorsensHealth);
+        }
+
         let contract_id = env.current_contract_address();
+        let payee = Self::loan_payee(&env, loan.loan_id);
 
-        // Transfer debt payment from liquidator to contract
-        Self::transfer(&env, &loan.asset, &liquidator, &contract_id, amount)?;
+        // Transfer debt payment from liquidator to the claim holder
+        Self::transfer(
+            &env,
+            &loan.asset,
+            &liquidator,
+            payee.as_ref().unwrap_or(&contract_id),
+            amount,
+        )?;
 
         // Transfer collateral from contract to liquidator
         Self::transfer(
@@ -2405,11 +3451,72 @@ impl LendingContract {
             &liquidator,
             collateral_to_seize,
         )?;
+        for seized in basket_seized.iter() {
+            Self::transfer(
+                &env,
+                &seized.token,
+                &contract_id,
+                &liquidator,
+                seized.amount,
+            )?;
+        }
 
-        let mut pool = Self::get_pool(&env, &loan.asset)?;
-        pool.total_borrowed = pool.total_borrowed.saturating_sub(amount);
-        pool.total_deposits += amount;
-        Self::set_pool(&env, &loan.asset, &pool);
+        if payee.is_none() {
+            let mut pool = Self::get_pool(&env, &loan.asset)?;
+            Self::close_debt(&env, &loan.asset, &mut pool, loan.loan_id, amount);
+            pool.total_deposits += amount;
+            Self::set_pool(&env, &loan.asset, &pool);
+        }
+
+        if loan.principal == 0 {
+            // Debt fully covered: return what is left of the collateral and close the loan
+            if loan.collateral_amount > 0 {
+                Self::transfer(
+                    &env,
+                    &loan.collateral_token,
+                    &contract_id,
+                    &borrower,
+                    loan.collateral_amount,
+                )?;
+            }
+            for position in basket.iter() {
+                if position.amount > 0 {
+                    Self::transfer(
+                        &env,
+                        &position.token,
+                        &contract_id,
+                        &borrower,
+                        position.amount,
+                    )?;
+                }
+            }
+            collateral::clear_basket(&env, &borrower);
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Loan(borrower.clone()));
+            env.storage()
+                .persistent()
+                .remove(&DataKey::LoanById(loan.loan_id));
+            Self::remove_user_loan(&env, &borrower, loan.loan_id);
+            env.storage()
+                .persistent()
+                .remove(&DataKey::LateFeesAccrued(loan.loan_id));
+            interest::clear_loan(&env, loan.loan_id);
+            env.storage()
+                .persistent()
+                .remove(&DataKey::LoanPayee(loan.loan_id));
+            if let Some(nft_token) = Self::get_nft_token(&env) {
+                LoanNFTClient::new(&env, &nft_token).burn(&loan.loan_id);
+            }
+        } else {
+            collateral::set_basket(&env, &borrower, &basket);
+            env.storage()
+                .persistent()
+                .set(&DataKey::Loan(borrower.clone()), &loan);
+            env.storage()
+                .persistent()
+                .set(&DataKey::LoanById(loan.loan_id), &loan);
+        }
 
         // Emit liquidation event
         env.events().publish(
@@ -2422,31 +3529,45 @@ impl LendingContract {
                 amount_repaid: amount,
                 collateral_seized: collateral_to_seize,
                 health_factor,
+                health_factor_after,
+                remaining_debt: loan.principal,
+                remaining_collateral: loan.collateral_amount,
+                bonus_bps,
+                basket_seized,
             },
         );
 
         log!(
             &env,
-            "Loan {} liquidated: {} repaid of asset {}, {} collateral seized",
+            "Loan {} liquidated: {} repaid of asset {}, {} collateral seized, {} debt remaining",
             loan.loan_id,
             amount,
             loan.asset,
-            collateral_to_seize
+            collateral_to_seize,
+            loan.principal
         );
 
         Self::exit_reentrancy_guard(&env);
         Ok(collateral_to_seize)
     }
 
+    /// Liquidation bonus and close factor for `asset`, in basis points, read
+    /// from the governance contract when one is linked.
+    fn liquidation_params(env: &Env, asset: &Address) -> (u32, u32) {
+        match Self::get_governance_contract(env.clone()) {
+            Some(governance) => {
+                let client = GovernanceClient::new(env, &governance);
+                (
+                    client.get_liquidation_bonus(asset),
+                    client.get_close_factor().min(10000),
+                )
+            }
+            None => (DEFAULT_LIQUIDATION_BONUS_BPS, DEFAULT_CLOSE_FACTOR_BPS),
+        }
+    }
+
     // ─── Refinancing Functions ───────────────────────
 
-    /// Calculate outstanding balance for a loan (principal + accrued interest)
-    fn calculate_outstanding_balance(env: &Env, loan: &LoanRecord) -> u64 {
-        let elapsed = env.ledger().timestamp().saturating_sub(loan.borrow_time);
-        let interest = Self::calculate_interest(loan.principal, loan.interest_rate_bps, elapsed);
-        loan.principal + interest
-    }
-
     /// Get refinancing terms for an existing loan
     pub fn get_refinance_terms(
         env: Env,
@@ -2461,7 +3582,7 @@ impl LendingContract {
             .get(&DataKey::Loan(borrower.clone()))
             .ok_or(LendingError::NoOpenLoan)?;
 
-        let outstanding_balance = Self::calculate_outstanding_balance(&env, &loan);
+        let outstanding_balance = Self::loan_debt(&env, &loan)?;
         // Compute refinancing fee with checked arithmetic to prevent overflow
         let refinancing_fee_u128 = (outstanding_balance as u128)
             .checked_mul(REFINANCING_FEE_BPS as u128)
@@ -2487,10 +3608,17 @@ impl LendingContract {
         let current_time = env.ledger().timestamp();
         let new_due_date = current_time + new_duration_seconds;
 
+        // The new loan keeps the old one's rate mode; a fixed rate is locked
+        // afresh at the current fixed rate
         let pool = Self::get_pool(&env, &loan.asset)?;
-        let utilization_bps = Self::get_utilization_bps(pool.total_borrowed, pool.total_deposits);
-        let new_interest_rate_bps =
-            Self::calculate_dynamic_rate(pool.base_rate_bps, pool.multiplier_bps, utilization_bps);
+        let new_interest_rate_bps = Self::locked_rate(
+            &env,
+            &loan.asset,
+            &pool,
+            Self::rate_mode_of(&env, loan.loan_id),
+            refinancing_fee,
+        )
+        .unwrap_or_else(|| Self::pool_borrow_rate(&env, &pool));
 
         Ok(RefinanceTerms {
             outstanding_balance,
@@ -2518,6 +3646,7 @@ impl LendingContract {
             .persistent()
             .get(&DataKey::Loan(borrower.clone()))
             .ok_or(LendingError::NoOpenLoan)?;
+        Self::require_pool_claim(&env, old_loan.loan_id)?;
 
         // Cannot refinance if currently in grace period or overdue
         let is_in_grace = Self::is_in_grace_period(env.clone(), borrower.clone())?;
@@ -2525,6 +3654,7 @@ impl LendingContract {
             return Err(LendingError::CannotRefinance);
         }
 
+        Self::accrue_pool(&env, &old_loan.asset)?;
         let terms = Self::get_refinance_terms(env.clone(), borrower.clone(), new_duration_seconds)?;
 
         let contract_id = env.current_contract_address();
@@ -2581,7 +3711,7 @@ impl LendingContract {
         if let Some(nft_token) = Self::get_nft_token(&env) {
             let nft_client = LoanNFTClient::new(&env, &nft_token);
             nft_client.mint(
-                &borrower,
+                &contract_id,
                 &LoanMetadata {
                     borrower: borrower.clone(),
                     asset: new_loan.asset.clone(),
@@ -2601,30 +3731,32 @@ impl LendingContract {
             .checked_add(terms.refinancing_fee)
             .ok_or(LendingError::InvalidRefinanceTerms)?;
 
-        // Update pool borrowed amount and check utilization cap using checked arithmetic
-        if terms.new_principal > old_loan.principal {
-            let additional_principal = terms
-                .new_principal
-                .checked_sub(old_loan.principal)
-                .ok_or(LendingError::InvalidRefinanceTerms)?;
-            let new_borrowed = pool
-                .total_borrowed
-                .checked_add(additional_principal)
-                .ok_or(LendingError::InvalidRefinanceTerms)?;
-            let new_utilization_bps = Self::get_utilization_bps(new_borrowed, pool.total_deposits);
-            if new_utilization_bps > pool.utilization_cap_bps {
-                return Err(LendingError::UtilizationCapExceeded);
-            }
-            pool.total_borrowed = new_borrowed;
-        } else if terms.new_principal < old_loan.principal {
-            let decrease = old_loan
-                .principal
-                .checked_sub(terms.new_principal)
-                .ok_or(LendingError::InvalidRefinanceTerms)?;
-            pool.total_borrowed = pool
-                .total_borrowed
-                .checked_sub(decrease)
-                .ok_or(LendingError::InvalidRefinanceTerms)?;
+        // Replace the old loan's debt with the new principal and check utilization cap
+        let fixed_rate_bps = match Self::rate_mode_of(&env, old_loan.loan_id) {
+            RateMode::Fixed => Some(terms.new_interest_rate_bps),
+            RateMode::Variable => None,
+        };
+        Self::close_debt(
+            &env,
+            &old_loan.asset,
+            &mut pool,
+            old_loan.loan_id,
+            terms.outstanding_balance,
+        );
+        Self::open_debt(
+            &env,
+            &old_loan.asset,
+            &mut pool,
+            new_loan_id,
+            terms.new_principal,
+            fixed_rate_bps,
+        );
+        interest::clear_loan(&env, old_loan.loan_id);
+        if terms.new_principal > terms.outstanding_balance
+            && Self::get_utilization_bps(pool.total_borrowed, pool.total_deposits)
+                > pool.utilization_cap_bps
+        {
+            return Err(LendingError::UtilizationCapExceeded);
         }
 
         Self::set_pool(&env, &old_loan.asset, &pool);
@@ -2679,6 +3811,7 @@ impl LendingContract {
         let mut total_collateral = 0u64;
         let mut collateral_token: Option<Address> = None;
         let mut asset: Option<Address> = None;
+        let mut rate_mode: Option<RateMode> = None;
         let mut old_loans = Vec::new(&env);
 
         // Validate all loans belong to borrower and calculate totals
@@ -2692,6 +3825,7 @@ impl LendingContract {
             if loan.borrower != borrower {
                 return Err(LendingError::Unauthorized);
             }
+            Self::require_pool_claim(&env, loan_id)?;
 
             // Check if this specific loan is overdue (cannot consolidate overdue loans)
             let loan_grace_end = Self::grace_period_end(&env, &loan)?;
@@ -2700,7 +3834,7 @@ impl LendingContract {
                 return Err(LendingError::CannotRefinance);
             }
 
-            let outstanding = Self::calculate_outstanding_balance(&env, &loan);
+            let outstanding = Self::loan_debt(&env, &loan)?;
             total_outstanding += outstanding;
             total_collateral += loan.collateral_amount;
 
@@ -2716,6 +3850,13 @@ impl LendingContract {
                 return Err(LendingError::InvalidRefinanceTerms); // All loan assets must be the same for consolidation
             }
 
+            let mode = Self::rate_mode_of(&env, loan.loan_id);
+            if rate_mode.is_none() {
+                rate_mode = Some(mode);
+            } else if rate_mode != Some(mode) {
+                return Err(LendingError::InvalidRefinanceTerms); // Fixed and variable loans cannot be merged
+            }
+
             old_loans.push_back(loan);
         }
 
@@ -2737,8 +3878,17 @@ impl LendingContract {
             consolidation_fee,
         )?;
 
-        // Remove old loans
+        // Remove old loans, taking their debt out of the pool
+        let mut pool = Self::accrue_pool(&env, &consolidation_asset)?;
         for loan in old_loans.iter() {
+            let outstanding = Self::loan_debt(&env, &loan)?;
+            Self::close_debt(
+                &env,
+                &consolidation_asset,
+                &mut pool,
+                loan.loan_id,
+                outstanding,
+            );
             env.storage()
                 .persistent()
                 .remove(&DataKey::Loan(loan.borrower.clone()));
@@ -2746,6 +3896,7 @@ impl LendingContract {
                 .persistent()
                 .remove(&DataKey::LoanById(loan.loan_id));
             Self::remove_user_loan(&env, &borrower, loan.loan_id);
+            interest::clear_loan(&env, loan.loan_id);
 
             // Burn old NFTs
             if let Some(nft_token) = Self::get_nft_token(&env) {
@@ -2759,10 +3910,25 @@ impl LendingContract {
         let current_time = env.ledger().timestamp();
         let new_due_date = current_time + new_duration_seconds;
 
-        let pool = Self::get_pool(&env, &consolidation_asset)?;
-        let utilization_bps = Self::get_utilization_bps(pool.total_borrowed, pool.total_deposits);
+        // The old loans' debt is replaced by the consolidated principal, in
+        // the loans' common rate mode
+        let fixed_rate_bps = Self::locked_rate(
+            &env,
+            &consolidation_asset,
+            &pool,
+            rate_mode.unwrap_or(RateMode::Variable),
+            new_principal,
+        );
+        Self::open_debt(
+            &env,
+            &consolidation_asset,
+            &mut pool,
+            new_loan_id,
+            new_principal,
+            fixed_rate_bps,
+        );
         let new_interest_rate_bps =
-            Self::calculate_dynamic_rate(pool.base_rate_bps, pool.multiplier_bps, utilization_bps);
+            fixed_rate_bps.unwrap_or_else(|| Self::pool_borrow_rate(&env, &pool));
 
         let new_loan = LoanRecord {
             loan_id: new_loan_id,
@@ -2788,7 +3954,7 @@ impl LendingContract {
         if let Some(nft_token) = Self::get_nft_token(&env) {
             let nft_client = LoanNFTClient::new(&env, &nft_token);
             nft_client.mint(
-                &borrower,
+                &contract_id,
                 &LoanMetadata {
                     borrower: borrower.clone(),
                     asset: consolidation_asset.clone(),
@@ -2802,7 +3968,6 @@ impl LendingContract {
         }
 
         // Add fee to retained yield
-        let mut pool = Self::get_pool(&env, &consolidation_asset)?;
         pool.retained_yield += consolidation_fee;
         Self::set_pool(&env, &consolidation_asset, &pool);
 
@@ -2854,6 +4019,7 @@ impl LendingContract {
             .persistent()
             .get(&DataKey::Loan(borrower.clone()))
             .ok_or(LendingError::NoOpenLoan)?;
+        Self::require_pool_claim(&env, old_loan.loan_id)?;
 
         // Check if loan is in good standing
         let is_in_grace = Self::is_in_grace_period(env.clone(), borrower.clone())?;
@@ -2861,7 +4027,7 @@ impl LendingContract {
             return Err(LendingError::CannotRefinance);
         }
 
-        let outstanding = Self::calculate_outstanding_balance(&env, &old_loan);
+        let outstanding = Self::loan_debt(&env, &old_loan)?;
         let total_split_amount: u64 = split_amounts.iter().sum();
 
         if total_split_amount != outstanding {
@@ -2897,10 +4063,26 @@ impl LendingContract {
         let current_time = env.ledger().timestamp();
         let new_due_date = current_time + new_duration_seconds;
 
-        let pool = Self::get_pool(&env, &old_loan.asset)?;
-        let utilization_bps = Self::get_utilization_bps(pool.total_borrowed, pool.total_deposits);
+        // The old loan's debt is replaced by the split principals, in the
+        // old loan's rate mode
+        let mut pool = Self::accrue_pool(&env, &old_loan.asset)?;
+        let fixed_rate_bps = Self::locked_rate(
+            &env,
+            &old_loan.asset,
+            &pool,
+            Self::rate_mode_of(&env, old_loan.loan_id),
+            0,
+        );
         let new_interest_rate_bps =
-            Self::calculate_dynamic_rate(pool.base_rate_bps, pool.multiplier_bps, utilization_bps);
+            fixed_rate_bps.unwrap_or_else(|| Self::pool_borrow_rate(&env, &pool));
+        Self::close_debt(
+            &env,
+            &old_loan.asset,
+            &mut pool,
+            old_loan.loan_id,
+            outstanding,
+        );
+        interest::clear_loan(&env, old_loan.loan_id);
 
         // Distribute collateral proportionally
         for amount in split_amounts.iter() {
@@ -2914,6 +4096,14 @@ impl LendingContract {
                 .unwrap_or(0)) as u64;
 
             let new_loan_id = Self::increment_loan_id(&env);
+            Self::open_debt(
+                &env,
+                &old_loan.asset,
+                &mut pool,
+                new_loan_id,
+                amount,
+                fixed_rate_bps,
+            );
             let new_loan = LoanRecord {
                 loan_id: new_loan_id,
                 borrower: borrower.clone(),
@@ -2940,7 +4130,7 @@ impl LendingContract {
             if let Some(nft_token) = Self::get_nft_token(&env) {
                 let nft_client = LoanNFTClient::new(&env, &nft_token);
                 nft_client.mint(
-                    &borrower,
+                    &contract_id,
                     &LoanMetadata {
                         borrower: borrower.clone(),
                         asset: old_loan.asset.clone(),
@@ -2957,7 +4147,6 @@ impl LendingContract {
         }
 
         // Add fee to retained yield
-        let mut pool = Self::get_pool(&env, &old_loan.asset)?;
         pool.retained_yield += split_fee;
         Self::set_pool(&env, &old_loan.asset, &pool);
 
@@ -3014,6 +4203,7 @@ impl LendingContract {
         }
 
         let mut pool = Self::get_pool(&env, &asset)?;
+        Self::accrue(&env, &asset, &mut pool)?;
         pool.reserve_factor_bps = reserve_factor_bps;
         Self::set_pool(&env, &asset, &pool);
 
@@ -3058,6 +4248,7 @@ impl LendingContract {
         }
 
         let mut pool = Self::get_pool(&env, &asset)?;
+        Self::accrue(&env, &asset, &mut pool)?;
         if pool.bad_debt_reserve < amount {
             return Err(LendingError::InsufficientLiquidity);
         }
@@ -3094,6 +4285,7 @@ impl LendingContract {
         }
 
         let mut pool = Self::get_pool(&env, &asset)?;
+        Self::accrue(&env, &asset, &mut pool)?;
         let contract_id = env.current_contract_address();
 
         Self::transfer(&env, &asset, &admin, &contract_id, amount)?;
@@ -3142,21 +4334,46 @@ impl LendingContract {
             return Err(LendingError::InvalidAmount);
         }
 
+        Self::accrue_pool(&env, &loan.asset)?;
+        let debt = Self::loan_debt(&env, &loan)?;
         let outstanding_balance = Self::get_repayment_amount(env.clone(), borrower.clone())?;
-        let collateral_seized = loan.collateral_amount;
-        let shortfall = outstanding_balance.saturating_sub(collateral_seized);
+        // Value of all collateral (primary and basket), in units of the loan asset
+        let mut collateral_seized = 0u64;
+        for position in Self::loan_collateral(&env, &loan).iter() {
+            collateral_seized = collateral_seized.saturating_add(Self::collateral_in_asset_units(
+                &env,
+                &loan.asset,
+                &position.token,
+                position.amount,
+            )?);
+        }
+        let mut shortfall = outstanding_balance.saturating_sub(collateral_seized);
 
-        let mut pool = Self::get_pool(&env, &loan.asset)?;
-        if shortfall > 0 {
-            if pool.bad_debt_reserve < shortfall {
-                return Err(LendingError::InsufficientLiquidity);
+        if let Some(payee) = Self::loan_payee(&env, loan.loan_id) {
+            // The claim holder takes the collateral and bears any shortfall
+            let contract_id = env.current_contract_address();
+            for position in Self::loan_collateral(&env, &loan).iter() {
+                Self::transfer(&env, &position.token, &contract_id, &payee, position.amount)?;
+            }
+            shortfall = 0;
+        } else {
+            let mut pool = Self::get_pool(&env, &loan.asset)?;
+            if shortfall > 0 {
+                if pool.bad_debt_reserve < shortfall {
+                    return Err(LendingError::InsufficientLiquidity);
+                }
+                pool.bad_debt_reserve = pool.bad_debt_reserve.saturating_sub(shortfall);
             }
-            pool.bad_debt_reserve = pool.bad_debt_reserve.saturating_sub(shortfall);
-        }
 
-        pool.total_borrowed = pool.total_borrowed.saturating_sub(loan.principal);
-        Self::set_pool(&env, &loan.asset, &pool);
+            Self::close_debt(&env, &loan.asset, &mut pool, loan.loan_id, debt);
+            Self::set_pool(&env, &loan.asset, &pool);
+        }
 
+        collateral::clear_basket(&env, &borrower);
+        interest::clear_loan(&env, loan.loan_id);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::LoanPayee(loan.loan_id));
         env.storage()
             .persistent()
             .remove(&DataKey::Loan(borrower.clone()));
@@ -3209,6 +4426,7 @@ impl LendingContract {
         }
 
         let mut pool = Self::get_pool(&env, &asset)?;
+        Self::accrue(&env, &asset, &mut pool)?;
         if pool.bad_debt_reserve < amount {
             return Err(LendingError::InsufficientLiquidity);
         }
@@ -3242,49 +4460,27 @@ impl LendingContract {
         (depositor_share, protocol_share)
     }
 
-    /// Accrue interest and split between depositors and protocol
+    /// Accrue interest on a loan's pool, splitting it between depositors and
+    /// protocol. Every call that changes a pool accrues it too.
     pub fn accrue_interest_with_reserve(env: Env, loan_id: u64) -> Result<(), LendingError> {
         let loan_key = DataKey::LoanById(loan_id);
         let loan = env
             .storage()
-            .instance()
+            .persistent()
             .get::<_, LoanRecord>(&loan_key)
             .ok_or(LendingError::LoanNotFound)?; // Loan not found
 
-        let elapsed = env.ledger().timestamp().saturating_sub(loan.borrow_time);
-        let total_interest =
-            Self::calculate_interest(loan.principal, loan.interest_rate_bps, elapsed);
-
-        let mut pool = Self::get_pool(&env, &loan.asset)?;
-        let (depositor_interest, protocol_interest) =
-            Self::calculate_interest_split(total_interest, pool.reserve_factor_bps);
-
-        // Split protocol share into reserved and retained portions
-        let reserve_share = ((protocol_interest as u128)
-            .checked_mul(BAD_DEBT_RESERVE_BPS as u128)
-            .and_then(|v| v.checked_div(10000u128))
-            .unwrap_or(0)) as u64;
-        let retained_share = protocol_interest.saturating_sub(reserve_share);
-
-        // Update pool state: depositor share increases pool deposits, protocol keeps retained share, and reserve receives reserve share
-        pool.total_deposits = pool.total_deposits.saturating_add(depositor_interest);
-        pool.retained_yield = pool.retained_yield.saturating_add(retained_share);
-        pool.bad_debt_reserve = pool.bad_debt_reserve.saturating_add(reserve_share);
-        pool.total_protocol_revenue = pool
-            .total_protocol_revenue
-            .saturating_add(protocol_interest);
-
-        Self::set_pool(&env, &loan.asset, &pool);
+        let before = Self::get_pool(&env, &loan.asset)?;
+        let pool = Self::accrue_pool(&env, &loan.asset)?;
 
         log!(
             &env,
-            "InterestAccrued: loan_id={}, total_interest={}, depositor_share={}, protocol_share={}, retained_share={}, reserve_share={}",
+            "InterestAccrued: loan_id={}, depositor_share={}, protocol_share={}, borrow_rate_bps={}",
             loan_id,
-            total_interest,
-            depositor_interest,
-            protocol_interest,
-            retained_share,
-            reserve_share
+            pool.total_deposits.saturating_sub(before.total_deposits),
+            pool.total_protocol_revenue
+                .saturating_sub(before.total_protocol_revenue),
+            Self::pool_borrow_rate(&env, &pool)
         );
 
         Ok(())
@@ -3393,7 +4589,6 @@ impl LendingContract {
         admin: Address,
         premium_rate_bps: u32,
     ) -> Result<(), LendingError> {
-        admin.require_auth();
         Self::require_admin(&env, &admin)?;
 
         if premium_rate_bps > 10000 {
@@ -3427,7 +4622,7 @@ impl LendingContract {
         let loan_key = DataKey::LoanById(loan_id);
         let loan = env
             .storage()
-            .instance()
+            .persistent()
             .get::<_, LoanRecord>(&loan_key)
             .ok_or(LendingError::LoanNotFound)?;
 
@@ -3582,7 +4777,7 @@ impl LendingContract {
         let loan_key = DataKey::LoanById(loan_id);
         let loan = env
             .storage()
-            .instance()
+            .persistent()
             .get::<_, LoanRecord>(&loan_key)
             .ok_or(LendingError::LoanNotFound)?;
 
@@ -3765,7 +4960,6 @@ impl LendingContract {
         admin: Address,
         amount: u64,
     ) -> Result<(), LendingError> {
-        admin.require_auth();
         Self::require_admin(&env, &admin)?;
 
         if amount == 0 {
@@ -3809,7 +5003,6 @@ impl LendingContract {
         admin: Address,
         amount: u64,
     ) -> Result<(), LendingError> {
-        admin.require_auth();
         Self::require_admin(&env, &admin)?;
 
         if amount == 0 {
@@ -3900,6 +5093,126 @@ impl LendingContract {
         env.storage().instance().get(&DataKey::GovernanceContract)
     }
 
+    // ─── Loan Claims ─────────────────────────────────
+
+    /// Sell the lender's claim on a loan to `buyer` at par: what the loan
+    /// owes now, late fees included. The pool books the sale like a
+    /// repayment and hands the loan's NFT to the buyer; from then on the
+    /// loan's repayments and liquidation proceeds go to whoever holds the
+    /// NFT. Returns the price paid.
+    pub fn sell_loan_claim(
+        env: Env,
+        admin: Address,
+        buyer: Address,
+        loan_id: u64,
+    ) -> Result<u64, LendingError> {
+        Self::require_not_paused(&env)?;
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+        Self::enter_reentrancy_guard(&env)?;
+        buyer.require_auth();
+
+        let nft_token = Self::get_nft_token(&env).ok_or(LendingError::NotInitialized)?;
+        let loan: LoanRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LoanById(loan_id))
+            .ok_or(LendingError::LoanNotFound)?;
+        Self::require_pool_claim(&env, loan_id)?;
+
+        let mut pool = Self::get_pool(&env, &loan.asset)?;
+        Self::accrue(&env, &loan.asset, &mut pool)?;
+        let debt = Self::loan_debt(&env, &loan)?;
+        let late_fee = Self::calculate_late_fee(env.clone(), loan.borrower.clone())?;
+        let price = debt + late_fee;
+
+        let contract_id = env.current_contract_address();
+        Self::transfer(&env, &loan.asset, &buyer, &contract_id, price)?;
+
+        // The loan keeps accruing, but no longer as the pool's debt
+        Self::close_debt(&env, &loan.asset, &mut pool, loan_id, debt);
+        pool.retained_yield += late_fee;
+        Self::set_pool(&env, &loan.asset, &pool);
+
+        LoanNFTClient::new(&env, &nft_token).transfer(&contract_id, &buyer, &loan_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::LoanPayee(loan_id), &buyer);
+
+        env.events().publish(
+            (symbol_short!("POOL"), symbol_short!("CLAIMSOLD")),
+            LoanClaimSoldEvent {
+                loan_id,
+                buyer,
+                asset: loan.asset,
+                price,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Self::exit_reentrancy_guard(&env);
+        Ok(price)
+    }
+
+    /// Called by the loan NFT whenever a sold claim changes hands, so that
+    /// the loan's cash flows follow the token. A claim cannot be handed back
+    /// to the pool, which has already been paid for it.
+    pub fn on_loan_claim_transfer(env: Env, loan_id: u64, to: Address) -> Result<(), LendingError> {
+        let nft_token = Self::get_nft_token(&env).ok_or(LendingError::Unauthorized)?;
+        nft_token.require_auth();
+
+        if Self::loan_payee(&env, loan_id).is_none() || to == env.current_contract_address() {
+            return Err(LendingError::Unauthorized);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::LoanPayee(loan_id), &to);
+
+        env.events().publish(
+            (symbol_short!("POOL"), symbol_short!("CLAIMXFER")),
+            LoanClaimTransferredEvent {
+                loan_id,
+                holder: to,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Holder of a sold loan's claim; `None` while the pool holds it.
+    pub fn get_loan_payee(env: Env, loan_id: u64) -> Option<Address> {
+        Self::loan_payee(&env, loan_id)
+    }
+
+    /// What a loan owes now and who it is owed to.
+    pub fn get_loan_claim(env: Env, loan_id: u64) -> Option<LoanClaim> {
+        let loan: LoanRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LoanById(loan_id))?;
+        let debt = Self::loan_debt(&env, &loan).ok()?;
+        let late_fee = Self::calculate_late_fee(env.clone(), loan.borrower.clone()).ok()?;
+        Some(LoanClaim {
+            loan_id,
+            asset: loan.asset,
+            outstanding: debt + late_fee,
+            due_date: loan.due_date,
+            holder: Self::loan_payee(&env, loan_id)
+                .unwrap_or_else(|| env.current_contract_address()),
+        })
+    }
+
+    fn loan_payee(env: &Env, loan_id: u64) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::LoanPayee(loan_id))
+    }
+
+    /// Loans whose claim was sold are no longer the pool's to restructure.
+    fn require_pool_claim(env: &Env, loan_id: u64) -> Result<(), LendingError> {
+        if env.storage().persistent().has(&DataKey::LoanPayee(loan_id)) {
+            return Err(LendingError::LoanClaimSold);
+        }
+        Ok(())
+    }
+
     // ─── Interest Rate Model ─────────────────────────
 
     /// Update the interest rate model parameters. Admin only.
@@ -3921,6 +5234,11 @@ impl LendingContract {
             return Err(LendingError::InvalidRateModel);
         }
 
+        // Interest up to now accrues at the rates of the previous model
+        for asset in Self::get_supported_assets(env.clone()).iter() {
+            Self::accrue_pool(&env, &asset)?;
+        }
+
         let model = RateModel {
             base_rate_bps,
             optimal_utilization_bps,
@@ -4008,57 +5326,23 @@ impl LendingContract {
             .saturating_mul(10))
     }
 
-    /// Get the current borrow rate using the two-slope model if configured,
-    /// or the legacy linear model otherwise.
+    /// Get the current borrow rate of the primary pool: the rate its borrow
+    /// index compounds at, from the two-slope model if configured or the
+    /// legacy linear model otherwise.
     pub fn get_borrow_rate(env: Env) -> Result<u32, LendingError> {
         Self::require_initialized(&env)?;
         let token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
         let pool = Self::get_pool(&env, &token)?;
-        let utilization_bps = Self::get_utilization_bps(pool.total_borrowed, pool.total_deposits);
-
-        if let Some(model) = env
-            .storage()
-            .instance()
-            .get::<DataKey, RateModel>(&DataKey::RateModel)
-        {
-            return Ok(Self::two_slope_rate(&model, utilization_bps));
-        }
-
-        Ok(Self::calculate_dynamic_rate(
-            pool.base_rate_bps,
-            pool.multiplier_bps,
-            utilization_bps,
-        ))
+        Ok(Self::pool_borrow_rate(&env, &pool))
     }
 
-    /// Get the current supply (deposit) rate.
+    /// Get the current supply (deposit) rate of the primary pool.
     /// supply_rate = borrow_rate × utilization × (1 − reserve_factor)
     pub fn get_supply_rate(env: Env) -> Result<u32, LendingError> {
         Self::require_initialized(&env)?;
         let token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
         let pool = Self::get_pool(&env, &token)?;
-        let utilization_bps = Self::get_utilization_bps(pool.total_borrowed, pool.total_deposits);
-        let borrow_rate = Self::get_borrow_rate(env.clone())?;
-
-        let reserve_factor = if let Some(model) = env
-            .storage()
-            .instance()
-            .get::<DataKey, RateModel>(&DataKey::RateModel)
-        {
-            model.reserve_factor_bps
-        } else {
-            pool.reserve_factor_bps
-        };
-
-        // supply_rate = borrow_rate * utilization * (10000 - reserve_factor) / 10000^2
-        let supply_rate = (borrow_rate as u128)
-            .checked_mul(utilization_bps as u128)
-            .unwrap_or(0)
-            .checked_mul((10000u32.saturating_sub(reserve_factor)) as u128)
-            .unwrap_or(0)
-            / (10000u128 * 10000u128);
-
-        Ok(supply_rate as u32)
+        Ok(Self::pool_supply_rate(&env, &pool))
     }
 
     /// Simulate the borrow rate at an arbitrary utilization level (in basis points).
@@ -4081,6 +5365,176 @@ impl LendingContract {
         ))
     }
 
+    // ─── Fixed-Rate Loans ────────────────────────────
+
+    /// Set `asset`'s fixed-rate premium and the utilization from which
+    /// fixed-rate loans may be rebalanced (admin only). Loans already open
+    /// keep the rate they were locked at.
+    pub fn set_fixed_rate_config(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        premium_bps: u32,
+        rebalance_utilization_bps: u32,
+    ) -> Result<(), LendingError> {
+        Self::require_admin(&env, &admin)?;
+        Self::get_pool(&env, &asset)?;
+        if premium_bps > 10000 || rebalance_utilization_bps > 10000 {
+            return Err(LendingError::InvalidRateModel);
+        }
+        env.storage().instance().set(
+            &DataKey::FixedRateConfig(asset),
+            &FixedRateConfig {
+                premium_bps,
+                rebalance_utilization_bps,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get_fixed_rate_config(
+        env: Env,
+        asset: Address,
+    ) -> Result<FixedRateConfig, LendingError> {
+        Self::get_pool(&env, &asset)?;
+        Ok(Self::fixed_rate_config(&env, &asset))
+    }
+
+    /// Rate a fixed-rate loan of `asset` would be locked at now.
+    pub fn get_fixed_borrow_rate(env: Env, asset: Address) -> Result<u32, LendingError> {
+        let pool = Self::get_pool(&env, &asset)?;
+        Ok(Self::fixed_borrow_rate(&env, &asset, &pool, 0))
+    }
+
+    pub fn get_loan_rate_mode(env: Env, borrower: Address) -> Result<RateMode, LendingError> {
+        let loan: LoanRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Loan(borrower))
+            .ok_or(LendingError::NoOpenLoan)?;
+        Ok(Self::rate_mode_of(&env, loan.loan_id))
+    }
+
+    /// Move the borrower's loan to `rate_mode`. Interest accrued so far is
+    /// folded into the principal; a fixed rate is locked at the current
+    /// fixed rate. Returns the loan's new rate.
+    pub fn switch_rate_mode(
+        env: Env,
+        borrower: Address,
+        rate_mode: RateMode,
+    ) -> Result<u32, LendingError> {
+        Self::require_not_paused(&env)?;
+        Self::require_initialized(&env)?;
+        borrower.require_auth();
+
+        let mut loan: LoanRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Loan(borrower.clone()))
+            .ok_or(LendingError::NoOpenLoan)?;
+        Self::require_pool_claim(&env, loan.loan_id)?;
+        let old_mode = Self::rate_mode_of(&env, loan.loan_id);
+        if old_mode == rate_mode {
+            return Err(LendingError::RateModeUnchanged);
+        }
+
+        let mut pool = Self::get_pool(&env, &loan.asset)?;
+        if pool.is_paused {
+            return Err(LendingError::PoolPaused);
+        }
+        Self::accrue(&env, &loan.asset, &mut pool)?;
+        Self::capitalize_interest(&env, &mut loan)?;
+
+        // Move the debt from one bucket of the pool to the other
+        Self::close_debt(&env, &loan.asset, &mut pool, loan.loan_id, loan.principal);
+        interest::clear_loan(&env, loan.loan_id);
+        let fixed_rate_bps = Self::locked_rate(&env, &loan.asset, &pool, rate_mode, loan.principal);
+        Self::open_debt(
+            &env,
+            &loan.asset,
+            &mut pool,
+            loan.loan_id,
+            loan.principal,
+            fixed_rate_bps,
+        );
+        loan.interest_rate_bps =
+            fixed_rate_bps.unwrap_or_else(|| Self::pool_borrow_rate(&env, &pool));
+        Self::set_pool(&env, &loan.asset, &pool);
+        Self::save_loan(&env, &loan);
+
+        env.events().publish(
+            (symbol_short!("POOL"), symbol_short!("RATEMODE")),
+            RateModeSwitchedEvent {
+                loan_id: loan.loan_id,
+                borrower,
+                asset: loan.asset.clone(),
+                old_mode,
+                new_mode: rate_mode,
+                interest_rate_bps: loan.interest_rate_bps,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(loan.interest_rate_bps)
+    }
+
+    /// Reprice the borrower's fixed-rate loan at the current fixed rate.
+    /// Anyone may call this once the pool's utilization has reached the
+    /// asset's rebalancing threshold and the loan pays less than a new
+    /// fixed-rate loan would, so that borrowers who locked in low rates do
+    /// not keep draining depositors while the pool is stressed. Returns the
+    /// loan's new rate.
+    pub fn rebalance_fixed_rate(env: Env, borrower: Address) -> Result<u32, LendingError> {
+        Self::require_not_paused(&env)?;
+        Self::require_initialized(&env)?;
+
+        let mut loan: LoanRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Loan(borrower.clone()))
+            .ok_or(LendingError::NoOpenLoan)?;
+        Self::require_pool_claim(&env, loan.loan_id)?;
+        let fixed =
+            interest::fixed_rate(&env, loan.loan_id).ok_or(LendingError::RebalanceNotAllowed)?;
+
+        let mut pool = Self::get_pool(&env, &loan.asset)?;
+        Self::accrue(&env, &loan.asset, &mut pool)?;
+        let utilization_bps = Self::get_utilization_bps(pool.total_borrowed, pool.total_deposits);
+        let new_rate_bps = Self::fixed_borrow_rate(&env, &loan.asset, &pool, 0);
+        if utilization_bps < Self::fixed_rate_config(&env, &loan.asset).rebalance_utilization_bps
+            || new_rate_bps <= fixed.rate_bps
+        {
+            return Err(LendingError::RebalanceNotAllowed);
+        }
+
+        Self::capitalize_interest(&env, &mut loan)?;
+        Self::close_debt(&env, &loan.asset, &mut pool, loan.loan_id, loan.principal);
+        Self::open_debt(
+            &env,
+            &loan.asset,
+            &mut pool,
+            loan.loan_id,
+            loan.principal,
+            Some(new_rate_bps),
+        );
+        loan.interest_rate_bps = new_rate_bps;
+        Self::set_pool(&env, &loan.asset, &pool);
+        Self::save_loan(&env, &loan);
+
+        env.events().publish(
+            (symbol_short!("POOL"), symbol_short!("REBALANCE")),
+            FixedRateRebalancedEvent {
+                loan_id: loan.loan_id,
+                borrower,
+                asset: loan.asset.clone(),
+                old_rate_bps: fixed.rate_bps,
+                new_rate_bps,
+                utilization_bps,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(new_rate_bps)
+    }
+
     /// Two-slope interest rate calculation.
     fn two_slope_rate(model: &RateModel, utilization_bps: u32) -> u32 {
         let optimal = model.optimal_utilization_bps;
@@ -4121,4 +5575,5 @@ impl LendingContract {
 }
 
 mod cross_contract_test;
+mod flash_loan_test;
 mod test;
@@ -74,7 +74,7 @@ fn test_deposit_mints_shares() {
     assert_eq!(shares, 1000u64);
     assert_eq!(client.get_shares_of(&token_addr, &depositor), 1000u64);
 
-    let pool = client.get_pool_state(&token_addr).unwrap();
+    let pool = client.get_pool_state(&token_addr);
     assert_eq!(pool.total_deposits, 2000);
     assert_eq!(pool.total_shares, 2000);
     assert_eq!(pool.total_borrowed, 0);
@@ -99,7 +99,7 @@ fn test_second_deposit_proportional_shares() {
     let shares2 = client.deposit(&depositor2, &token_addr, &500u64);
     assert_eq!(shares2, 500u64);
 
-    let pool = client.get_pool_state(&token_addr).unwrap();
+    let pool = client.get_pool_state(&token_addr);
     assert_eq!(pool.total_deposits, 2500);
     assert_eq!(pool.total_shares, 2500);
 }
@@ -113,11 +113,11 @@ fn test_withdraw_burns_shares_and_returns_tokens() {
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 10_000);
 
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
     let balance_before = tok_client(&env, &token_addr).balance(&depositor);
 
     // Withdraw 500 shares → should get 500 tokens back
-    let returned = client.withdraw(&depositor, &token_addr, &500u64).unwrap();
+    let returned = client.withdraw(&depositor, &token_addr, &500u64);
     assert_eq!(returned, 500u64);
     assert_eq!(
         tok_client(&env, &token_addr).balance(&depositor),
@@ -125,7 +125,7 @@ fn test_withdraw_burns_shares_and_returns_tokens() {
     );
     assert_eq!(client.get_shares_of(&token_addr, &depositor), 500u64);
 
-    let pool = client.get_pool_state(&token_addr).unwrap();
+    let pool = client.get_pool_state(&token_addr);
     assert_eq!(pool.total_deposits, 1500);
     assert_eq!(pool.total_shares, 1500);
 }
@@ -138,7 +138,7 @@ fn test_withdraw_fails_not_enough_shares() {
 
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 10_000);
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
 
     // Try to withdraw more shares than owned
     let result = client.try_withdraw(&depositor, &token_addr, &2000u64);
@@ -156,20 +156,18 @@ fn test_borrow_reduces_available_liquidity() {
     mint_to(&env, &collateral_addr, &borrower, 100_000);
     mint_to(&env, &token_addr, &depositor, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 10_000);
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
 
     let borrow_amount = 400u64;
     let balance_before = tok_client(&env, &token_addr).balance(&borrower);
-    let loan_id = client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &borrow_amount,
-            &collateral_addr,
-            &600u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap(); // 30 days
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &borrow_amount,
+        &collateral_addr,
+        &600u64,
+        &(30 * 24 * 60 * 60),
+    ); // 30 days
 
     assert!(loan_id > 0);
     assert_eq!(
@@ -177,11 +175,11 @@ fn test_borrow_reduces_available_liquidity() {
         balance_before + 400
     );
 
-    let pool = client.get_pool_state(&token_addr).unwrap();
+    let pool = client.get_pool_state(&token_addr);
     assert_eq!(pool.total_borrowed, 400);
     assert_eq!(pool.total_deposits, 2000);
 
-    assert_eq!(client.available_liquidity(&token_addr).unwrap(), 1600u64);
+    assert_eq!(client.available_liquidity(&token_addr), 1600u64);
 }
 
 #[test]
@@ -192,7 +190,7 @@ fn test_borrow_fails_if_insufficient_liquidity() {
 
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 10_000);
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
 
     let result = client.try_borrow(
         &depositor,
@@ -215,17 +213,15 @@ fn test_borrow_fails_with_existing_loan() {
     let borrower = Address::generate(&env);
     mint_to(&env, &collateral_addr, &borrower, 100_000);
     mint_to(&env, &token_addr, &depositor, 10_000);
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &200u64,
-            &collateral_addr,
-            &300u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &200u64,
+        &collateral_addr,
+        &300u64,
+        &(30 * 24 * 60 * 60),
+    );
 
     // Second borrow should fail
     let result = client.try_borrow(
@@ -251,27 +247,25 @@ fn test_repay_restores_liquidity() {
     mint_to(&env, &token_addr, &depositor, 10_000);
     mint_to(&env, &token_addr, &borrower, 10_000); // pre-fund borrower for repayment
 
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &400u64,
-            &collateral_addr,
-            &600u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &400u64,
+        &collateral_addr,
+        &600u64,
+        &(30 * 24 * 60 * 60),
+    );
 
-    assert_eq!(client.available_liquidity(&token_addr).unwrap(), 1600u64);
+    assert_eq!(client.available_liquidity(&token_addr), 1600u64);
 
-    let repaid = client.repay(&borrower).unwrap();
+    let repaid = client.repay(&borrower);
     assert_eq!(repaid, 400u64);
 
-    let pool = client.get_pool_state(&token_addr).unwrap();
+    let pool = client.get_pool_state(&token_addr);
     assert_eq!(pool.total_borrowed, 0);
     assert_eq!(pool.total_deposits, 2000);
-    assert_eq!(client.available_liquidity(&token_addr).unwrap(), 2000u64);
+    assert_eq!(client.available_liquidity(&token_addr), 2000u64);
 
     // Loan should be gone
     let loan = client.get_loan(&borrower);
@@ -299,17 +293,15 @@ fn test_withdraw_fails_if_funds_are_borrowed() {
     mint_to(&env, &collateral_addr, &borrower, 100_000);
     mint_to(&env, &token_addr, &depositor, 10_000);
 
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1900u64,
-            &collateral_addr,
-            &2850u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap(); // only 100 tokens left un-borrowed
+    client.deposit(&depositor, &token_addr, &2000u64);
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1900u64,
+        &collateral_addr,
+        &2850u64,
+        &(30 * 24 * 60 * 60),
+    ); // only 100 tokens left un-borrowed
 
     // Depositor tries to withdraw 500 → only 100 available
     let result = client.try_withdraw(&depositor, &token_addr, &500u64);
@@ -333,25 +325,23 @@ fn test_available_liquidity_before_and_after() {
     mint_to(&env, &token_addr, &depositor, 10_000);
     mint_to(&env, &token_addr, &borrower, 10_000);
 
-    assert_eq!(client.available_liquidity(&token_addr).unwrap(), 0u64);
+    assert_eq!(client.available_liquidity(&token_addr), 0u64);
 
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
-    assert_eq!(client.available_liquidity(&token_addr).unwrap(), 2000u64);
+    client.deposit(&depositor, &token_addr, &2000u64);
+    assert_eq!(client.available_liquidity(&token_addr), 2000u64);
 
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1500u64,
-            &collateral_addr,
-            &2250u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
-    assert_eq!(client.available_liquidity(&token_addr).unwrap(), 500u64);
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1500u64,
+        &collateral_addr,
+        &2250u64,
+        &(30 * 24 * 60 * 60),
+    );
+    assert_eq!(client.available_liquidity(&token_addr), 500u64);
 
-    client.repay(&borrower).unwrap();
-    assert_eq!(client.available_liquidity(&token_addr).unwrap(), 2000u64);
+    client.repay(&borrower);
+    assert_eq!(client.available_liquidity(&token_addr), 2000u64);
 }
 
 #[test]
@@ -376,17 +366,15 @@ fn test_get_loan_returns_record_when_active() {
     mint_to(&env, &collateral_addr, &borrower, 100_000);
     mint_to(&env, &token_addr, &depositor, 10_000);
 
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
-    let loan_id = client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &300u64,
-            &collateral_addr,
-            &450u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &300u64,
+        &collateral_addr,
+        &450u64,
+        &(30 * 24 * 60 * 60),
+    );
 
     let loan = client.get_loan(&borrower).unwrap();
     assert_eq!(loan.loan_id, loan_id);
@@ -435,7 +423,7 @@ fn test_rounding_loss_exploit_prevented() {
     assert!(client
         .try_deposit(&attacker, &token_addr, &1000u64)
         .is_err());
-    let attack_shares = client.deposit(&attacker, &token_addr, &1001u64).unwrap();
+    let attack_shares = client.deposit(&attacker, &token_addr, &1001u64);
     assert_eq!(attack_shares, 1);
 
     // Victim tries to deposit an amount that would yield 0 shares
@@ -457,49 +445,48 @@ fn test_interest_accrual() {
     mint_to(&env, &token_addr, &borrower, 100_000);
 
     // 1. Deposit 10,000 → 10,000 shares
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // 2. Borrow 5,000
     // Utilization = 5000 / 10000 = 50%.
     // Rate = 5% + (50% * 20%) = 15% (1500 bps)
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &5_000u64,
-            &collateral_addr,
-            &7500u64,
-            &(365 * 24 * 60 * 60),
-        )
-        .unwrap(); // 1 year duration
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &5_000u64,
+        &collateral_addr,
+        &7500u64,
+        &(365 * 24 * 60 * 60),
+    ); // 1 year duration
 
-    let current_rate = client.get_current_interest_rate(&token_addr).unwrap();
+    let current_rate = client.get_current_interest_rate(&token_addr);
     assert_eq!(current_rate, 1500u32);
 
     // 3. Jump time by 1 year (31,536,000 seconds)
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 31_536_000);
 
-    // 4. Expected interest: 5,000 * 0.15 * 1 year = 750
-    let repayment_amount = client.get_repayment_amount(&borrower).unwrap();
-    assert_eq!(repayment_amount, 5_750u64);
+    // 4. Expected interest: 5,000 compounding every second at 15% for 1 year
+    //    = 5,000 * e^0.15 - 5,000 = 809
+    let repayment_amount = client.get_repayment_amount(&borrower);
+    assert_eq!(repayment_amount, 5_809u64);
 
     // 5. Repay
-    client.repay(&borrower).unwrap();
+    client.repay(&borrower);
 
     // 6. Verify pool state
-    let pool = client.get_pool_state(&token_addr).unwrap();
-    // total_deposits should be 10,000 (initial) + 675 (90% of 750 interest) = 10,675
-    assert_eq!(pool.total_deposits, 10_675);
+    let pool = client.get_pool_state(&token_addr);
+    // total_deposits should be 10,000 (initial) + 729 (90% of 809 interest) = 10,729
+    assert_eq!(pool.total_deposits, 10_729);
     assert_eq!(pool.total_borrowed, 0);
-    assert_eq!(pool.retained_yield, 38); // Remaining protocol yield after reserve split
-    assert_eq!(pool.bad_debt_reserve, 37); // Portion of protocol share routed to reserve
+    assert_eq!(pool.retained_yield, 40); // Remaining protocol yield after reserve split
+    assert_eq!(pool.bad_debt_reserve, 40); // Portion of protocol share routed to reserve
 
     // 7. Verify depositor can withdraw more than they put in
-    // shares = 9,000, pool_shares = 10,000, pool_deposits = 10,675
-    // amount = 9,000 * 10,675 / 10,000 = 9,607
-    let withdrawn = client.withdraw(&depositor, &token_addr, &9_000u64).unwrap();
-    assert_eq!(withdrawn, 9_607u64);
+    // shares = 9,000, pool_shares = 10,000, pool_deposits = 10,729
+    // amount = 9,000 * 10,729 / 10,000 = 9,656
+    let withdrawn = client.withdraw(&depositor, &token_addr, &9_000u64);
+    assert_eq!(withdrawn, 9_656u64);
 }
 
 #[test]
@@ -514,29 +501,28 @@ fn test_interest_precision_short_time() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &5_000u64,
-            &collateral_addr,
-            &7500u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap(); // 30 days
+    client.deposit(&depositor, &token_addr, &10_000u64);
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &5_000u64,
+        &collateral_addr,
+        &7500u64,
+        &(30 * 24 * 60 * 60),
+    ); // 30 days
 
     env.ledger().set_timestamp(env.ledger().timestamp() + 3600);
 
-    let repayment_amount = client.get_repayment_amount(&borrower).unwrap();
+    let repayment_amount = client.get_repayment_amount(&borrower);
     assert_eq!(repayment_amount, 5_000u64);
 }
 
 #[test]
 fn test_calculate_interest_rounds_nearest_unit() {
     // 1,000,000 principal at 100% APY for 16 seconds should round to 1 unit of interest.
-    let interest = LendingContract::calculate_interest(1_000_000u64, 10_000u32, 16u64);
-    assert_eq!(interest, 1u64);
+    let index = interest::compound_factor(10_000u32, 16u64).unwrap();
+    let debt = interest::scale(1_000_000u64, interest::INDEX_ONE, index).unwrap();
+    assert_eq!(debt - 1_000_000u64, 1u64);
 }
 
 #[test]
@@ -551,23 +537,19 @@ fn test_small_duration_interest_rounds_up_for_contract_repayment() {
     mint_to(&env, &token_addr, &depositor, 2_000_000);
     mint_to(&env, &token_addr, &borrower, 2_000_000);
 
-    client
-        .deposit(&depositor, &token_addr, &2_000_000u64)
-        .unwrap();
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1_000_000u64,
-            &collateral_addr,
-            &1_500_000u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    client.deposit(&depositor, &token_addr, &2_000_000u64);
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1_000_000u64,
+        &collateral_addr,
+        &1_500_000u64,
+        &(30 * 24 * 60 * 60),
+    );
 
     env.ledger().set_timestamp(env.ledger().timestamp() + 120);
 
-    let repayment_amount = client.get_repayment_amount(&borrower).unwrap();
+    let repayment_amount = client.get_repayment_amount(&borrower);
     assert_eq!(repayment_amount, 1_000_001u64);
 }
 
@@ -579,36 +561,28 @@ fn test_dynamic_interest_rate_increases_with_utilization() {
 
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 100_000);
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // At 0 utilization, rate should be base rate (500)
-    assert_eq!(
-        client.get_current_interest_rate(&token_addr).unwrap(),
-        500u32
-    );
+    assert_eq!(client.get_current_interest_rate(&token_addr), 500u32);
 
     let borrower1 = Address::generate(&env);
     mint_to(&env, &collateral_addr, &borrower1, 100_000);
     // Borrow 2,000 (20% utilization)
     // Dynamic rate should be 500 + (2000 * 2000 / 10000) = 500 + 400 = 900
-    client
-        .borrow(
-            &borrower1,
-            &token_addr,
-            &2_000u64,
-            &collateral_addr,
-            &3000u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    client.borrow(
+        &borrower1,
+        &token_addr,
+        &2_000u64,
+        &collateral_addr,
+        &3000u64,
+        &(30 * 24 * 60 * 60),
+    );
     let loan1 = client.get_loan(&borrower1).unwrap();
     assert_eq!(loan1.interest_rate_bps, 900u32);
 
     // Now utilization is 20%. The *next* borrower will get 900.
-    assert_eq!(
-        client.get_current_interest_rate(&token_addr).unwrap(),
-        900u32
-    );
+    assert_eq!(client.get_current_interest_rate(&token_addr), 900u32);
 
     let borrower2 = Address::generate(&env);
     mint_to(&env, &collateral_addr, &borrower2, 100_000);
@@ -618,16 +592,14 @@ fn test_dynamic_interest_rate_increases_with_utilization() {
     // Let's look at implementation: pool.total_borrowed += amount, THEN get_utilization_bps.
     // So for loan2, total_borrowed becomes 5,000. Utilization = 50%.
     // Rate = 500 + (5000 * 2000 / 10000) = 500 + 1000 = 1500.
-    client
-        .borrow(
-            &borrower2,
-            &token_addr,
-            &3_000u64,
-            &collateral_addr,
-            &4500u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    client.borrow(
+        &borrower2,
+        &token_addr,
+        &3_000u64,
+        &collateral_addr,
+        &4500u64,
+        &(30 * 24 * 60 * 60),
+    );
     let loan2 = client.get_loan(&borrower2).unwrap();
     assert_eq!(loan2.interest_rate_bps, 1500u32);
 }
@@ -640,7 +612,7 @@ fn test_unique_loan_ids() {
 
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 100_000);
-    client.deposit(&depositor, &token_addr, &50_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &50_000u64);
 
     let borrower1 = Address::generate(&env);
     let borrower2 = Address::generate(&env);
@@ -650,32 +622,28 @@ fn test_unique_loan_ids() {
     mint_to(&env, &token_addr, &borrower2, 100_000);
 
     // Create first loan
-    let loan_id_1 = client
-        .borrow(
-            &borrower1,
-            &token_addr,
-            &1_000u64,
-            &collateral_addr,
-            &1500u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    let loan_id_1 = client.borrow(
+        &borrower1,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &1500u64,
+        &(30 * 24 * 60 * 60),
+    );
     assert_eq!(loan_id_1, 1);
 
     // Repay first loan
-    client.repay(&borrower1).unwrap();
+    client.repay(&borrower1);
 
     // Create second loan - should have different ID
-    let loan_id_2 = client
-        .borrow(
-            &borrower2,
-            &token_addr,
-            &2_000u64,
-            &collateral_addr,
-            &3000u64,
-            &(60 * 24 * 60 * 60),
-        )
-        .unwrap();
+    let loan_id_2 = client.borrow(
+        &borrower2,
+        &token_addr,
+        &2_000u64,
+        &collateral_addr,
+        &3000u64,
+        &(60 * 24 * 60 * 60),
+    );
     assert_eq!(loan_id_2, 2);
 
     // Verify loan can be retrieved by ID
@@ -697,21 +665,19 @@ fn test_loan_tracks_due_date() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     let duration = 30 * 24 * 60 * 60u64; // 30 days
     let borrow_time = env.ledger().timestamp();
 
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1_000u64,
-            &collateral_addr,
-            &1_500u64,
-            &duration,
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &1_500u64,
+        &duration,
+    );
 
     let loan = client.get_loan(&borrower).unwrap();
     assert_eq!(loan.borrow_time, borrow_time);
@@ -730,35 +696,33 @@ fn test_repayment_updates_state_correctly() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
-    let loan_id = client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &5_000u64,
-            &collateral_addr,
-            &7500u64,
-            &(365 * 24 * 60 * 60),
-        )
-        .unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &5_000u64,
+        &collateral_addr,
+        &7500u64,
+        &(365 * 24 * 60 * 60),
+    );
 
     // Advance time to accrue interest
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 31_536_000); // 1 year
 
-    let pool_before = client.get_pool_state(&token_addr).unwrap();
+    let pool_before = client.get_pool_state(&token_addr);
     assert_eq!(pool_before.total_borrowed, 5_000);
 
     // Repay
-    let total_repaid = client.repay(&borrower).unwrap();
-    assert_eq!(total_repaid, 5_750); // 5000 + 750 interest
+    let total_repaid = client.repay(&borrower);
+    assert_eq!(total_repaid, 5_809); // 5000 + 809 interest compounded per second
 
     // Verify state updates
-    let pool_after = client.get_pool_state(&token_addr).unwrap();
+    let pool_after = client.get_pool_state(&token_addr);
     assert_eq!(pool_after.total_borrowed, 0);
-    assert_eq!(pool_after.total_deposits, 10_675); // Original + 90% interest
-    assert_eq!(pool_after.retained_yield, 38);
-    assert_eq!(pool_after.bad_debt_reserve, 37);
+    assert_eq!(pool_after.total_deposits, 10_729); // Original + 90% interest
+    assert_eq!(pool_after.retained_yield, 40);
+    assert_eq!(pool_after.bad_debt_reserve, 40);
 
     // Verify loan is removed
     assert!(client.get_loan(&borrower).is_none());
@@ -773,7 +737,7 @@ fn test_repay_decreases_interest_rate() {
 
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 100_000);
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     let borrower = Address::generate(&env);
     mint_to(&env, &collateral_addr, &borrower, 100_000);
@@ -781,29 +745,21 @@ fn test_repay_decreases_interest_rate() {
     mint_to(&env, &collateral_addr, &borrower, 100_000);
 
     // Borrow 50% (5,000). Rate becomes 15% (1500)
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &5_000u64,
-            &collateral_addr,
-            &7500u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
-    assert_eq!(
-        client.get_current_interest_rate(&token_addr).unwrap(),
-        1500u32
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &5_000u64,
+        &collateral_addr,
+        &7500u64,
+        &(30 * 24 * 60 * 60),
     );
+    assert_eq!(client.get_current_interest_rate(&token_addr), 1500u32);
 
     // Repay immediately
-    client.repay(&borrower).unwrap();
+    client.repay(&borrower);
 
     // Utilization goes back to 0. Rate goes back to 5% (500)
-    assert_eq!(
-        client.get_current_interest_rate(&token_addr).unwrap(),
-        500u32
-    );
+    assert_eq!(client.get_current_interest_rate(&token_addr), 500u32);
 }
 
 #[test]
@@ -818,7 +774,7 @@ fn test_collateral_required() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &collateral_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Try to borrow without sufficient collateral (need 150% = 1500 for 1000 borrow)
     let result = client.try_borrow(
@@ -832,16 +788,14 @@ fn test_collateral_required() {
     assert_eq!(result, Err(Ok(LendingError::InsufficientCollateral)));
 
     // With exact collateral should work
-    let loan_id = client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1_000u64,
-            &collateral_addr,
-            &1_500u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &1_500u64,
+        &(30 * 24 * 60 * 60),
+    );
     assert!(loan_id > 0);
 }
 
@@ -859,7 +813,7 @@ fn test_collateral_not_whitelisted() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &bad_collateral, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Try to borrow with non-whitelisted collateral
     let result = client.try_borrow(
@@ -886,20 +840,18 @@ fn test_collateral_returned_on_repay() {
     mint_to(&env, &token_addr, &borrower, 100_000);
     mint_to(&env, &collateral_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     let collateral_balance_before = tok_client(&env, &collateral_addr).balance(&borrower);
 
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1_000u64,
-            &collateral_addr,
-            &1_500u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &1_500u64,
+        &(30 * 24 * 60 * 60),
+    );
 
     // Collateral should be locked
     assert_eq!(
@@ -907,7 +859,7 @@ fn test_collateral_returned_on_repay() {
         collateral_balance_before - 1_500
     );
 
-    client.repay(&borrower).unwrap();
+    client.repay(&borrower);
 
     // Collateral should be returned
     assert_eq!(
@@ -967,7 +919,7 @@ fn test_utilization_cap_enforced() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &collateral_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Try to borrow 8,001 (80.01% utilization) - should fail
     let result = client.try_borrow(
@@ -981,16 +933,14 @@ fn test_utilization_cap_enforced() {
     assert_eq!(result, Err(Ok(LendingError::UtilizationCapExceeded)));
 
     // Borrow exactly 8,000 (80% utilization) - should succeed
-    let loan_id = client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &8_000u64,
-            &collateral_addr,
-            &12_000u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &8_000u64,
+        &collateral_addr,
+        &12_000u64,
+        &(30 * 24 * 60 * 60),
+    );
     assert!(loan_id > 0);
 }
 #[test]
@@ -1013,77 +963,198 @@ fn test_nft_minting_and_burning() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Borrow 1000
-    let loan_id = client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1_000u64,
-            &collateral_addr,
-            &1_500u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &1_500u64,
+        &(30 * 24 * 60 * 60),
+    );
 
-    // Verify NFT is minted
-    assert_eq!(nft_client.owner_of(&loan_id), Some(borrower.clone()));
+    // Verify NFT is minted to the pool, which holds the lender's claim
+    assert_eq!(nft_client.owner_of(&loan_id), Some(client.address.clone()));
     let metadata = nft_client.get_metadata(&loan_id).unwrap();
     assert_eq!(metadata.loan_id, loan_id);
     assert_eq!(metadata.borrower, borrower);
     assert_eq!(metadata.principal, 1_000u64);
 
     // Repay
-    client.repay(&borrower).unwrap();
+    client.repay(&borrower);
 
     // Verify NFT is burned
     assert_eq!(nft_client.owner_of(&loan_id), None);
     assert_eq!(nft_client.get_metadata(&loan_id), None);
 }
 
-// ─────────────────────────────────────────────────
-// Reentrancy Mock & Test
-// ─────────────────────────────────────────────────
-
-#[contract]
-pub struct MaliciousNFT;
-
-#[contractimpl]
-impl MaliciousNFT {
-    pub fn initialize(env: Env, admin: Address) {}
-    pub fn mint(env: Env, to: Address, metadata: LoanMetadata) {
-        let lending_contract = env
-            .storage()
-            .instance()
-            .get::<_, Address>(&symbol_short!("LEND"))
-            .unwrap();
-        let client = LendingContractClient::new(&env, &lending_contract);
-        // Attempt reentrant call to borrow
-        let _ = client.try_borrow(&to, &100, &metadata.collateral_token, &150, &3600);
-    }
-    pub fn burn(env: Env, loan_id: u64) {}
-    pub fn get_metadata(env: Env, loan_id: u64) -> Option<LoanMetadata> {
-        None
-    }
-    pub fn owner_of(env: Env, loan_id: u64) -> Option<Address> {
-        None
-    }
+/// Register a loan NFT that reports transfers to the lending contract.
+fn setup_claim_nft<'a>(
+    env: &'a Env,
+    client: &LendingContractClient<'a>,
+    admin: &Address,
+) -> loan_nft::LoanNFTClient<'a> {
+    let nft_id = env.register_contract(None, loan_nft::LoanNFT);
+    let nft_client = loan_nft::LoanNFTClient::new(env, &nft_id);
+    nft_client.initialize(&client.address);
+    nft_client.set_claim_registry(&client.address);
+    client.set_nft_token(admin, &nft_id);
+    nft_client
 }
 
 #[test]
-fn test_reentrancy_attack_fails() {
+fn test_sold_loan_claim_pays_nft_holder() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, token_addr, collateral_addr, admin) = setup(&env);
+    let nft_client = setup_claim_nft(&env, &client, &admin);
 
-    // Register Malicious NFT contract
-    let mal_nft_id = env.register_contract(None, MaliciousNFT);
-
-    // MaliciousNFT needs to know the lending contract address to call back
-    // We update its internal state directly in the test environment if needed,
-    // but here we just used instance storage of the mock.
-    // Wait, MaliciousNFT is a separate contract, it doesn't share instance storage with the test setup.
+    let depositor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let holder = Address::generate(&env);
+    mint_to(&env, &collateral_addr, &borrower, 100_000);
+    mint_to(&env, &token_addr, &depositor, 100_000);
+    mint_to(&env, &token_addr, &borrower, 100_000);
+    mint_to(&env, &token_addr, &buyer, 100_000);
+    client.deposit(&depositor, &token_addr, &10_000u64);
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &1_500u64,
+        &(30 * 24 * 60 * 60),
+    );
+
+    // The buyer pays par and takes the pool's place as lender
+    let price = client.sell_loan_claim(&admin, &buyer, &loan_id);
+    assert_eq!(price, 1_000);
+    assert_eq!(tok_client(&env, &token_addr).balance(&buyer), 99_000);
+    assert_eq!(nft_client.owner_of(&loan_id), Some(buyer.clone()));
+    assert_eq!(client.get_loan_payee(&loan_id), Some(buyer.clone()));
+    assert_eq!(client.get_pool_state(&token_addr).total_borrowed, 0);
+
+    // Reselling the NFT moves the payee with it
+    nft_client.transfer(&buyer, &holder, &loan_id);
+    assert_eq!(client.get_loan_payee(&loan_id), Some(holder.clone()));
+    let claim = client.get_loan_claim(&loan_id).unwrap();
+    assert_eq!(claim.holder, holder);
+    assert_eq!(claim.outstanding, 1_000);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 10 * 24 * 60 * 60);
+    let owed = client.get_repayment_amount(&borrower);
+    assert!(owed > 1_000);
+    assert_eq!(client.get_loan_claim(&loan_id).unwrap().outstanding, owed);
+
+    let pool_before = client.get_pool_state(&token_addr);
+    client.repay(&borrower);
+    assert_eq!(tok_client(&env, &token_addr).balance(&holder), owed as i128);
+    let pool_after = client.get_pool_state(&token_addr);
+    assert_eq!(pool_after.total_deposits, pool_before.total_deposits);
+    assert_eq!(pool_after.total_borrowed, pool_before.total_borrowed);
+    assert_eq!(pool_after.retained_yield, pool_before.retained_yield);
+    assert_eq!(nft_client.owner_of(&loan_id), None);
+    assert_eq!(client.get_loan_payee(&loan_id), None);
+}
+
+#[test]
+fn test_sold_loan_claim_cannot_be_restructured_or_returned() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    let nft_client = setup_claim_nft(&env, &client, &admin);
+
+    let depositor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint_to(&env, &collateral_addr, &borrower, 100_000);
+    mint_to(&env, &token_addr, &depositor, 100_000);
+    mint_to(&env, &token_addr, &buyer, 100_000);
+    client.deposit(&depositor, &token_addr, &10_000u64);
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &1_500u64,
+        &(30 * 24 * 60 * 60),
+    );
+    client.sell_loan_claim(&admin, &buyer, &loan_id);
+
+    assert_eq!(
+        client.try_sell_loan_claim(&admin, &buyer, &loan_id),
+        Err(Ok(LendingError::LoanClaimSold))
+    );
+    assert_eq!(
+        client.try_switch_rate_mode(&borrower, &RateMode::Fixed),
+        Err(Ok(LendingError::LoanClaimSold))
+    );
+    assert_eq!(
+        client.try_refinance_loan(&borrower, &(60 * 24 * 60 * 60)),
+        Err(Ok(LendingError::LoanClaimSold))
+    );
+
+    // The pool has been paid for the claim and will not take it back
+    assert!(nft_client
+        .try_transfer(&buyer, &client.address, &loan_id)
+        .is_err());
+    assert_eq!(nft_client.owner_of(&loan_id), Some(buyer.clone()));
+    assert_eq!(client.get_loan_payee(&loan_id), Some(buyer));
+}
+
+// ─────────────────────────────────────────────────
+// Reentrancy Mock & Test
+// ─────────────────────────────────────────────────
+
+#[contract]
+pub struct MaliciousNFT;
+
+#[contractimpl]
+impl MaliciousNFT {
+    pub fn initialize(env: Env, admin: Address) {}
+    pub fn mint(env: Env, to: Address, metadata: LoanMetadata) {
+        let lending_contract = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&symbol_short!("LEND"))
+            .unwrap();
+        let client = LendingContractClient::new(&env, &lending_contract);
+        // Attempt reentrant call to borrow
+        let _ = client.try_borrow(
+            &to,
+            &metadata.asset,
+            &100,
+            &metadata.collateral_token,
+            &150,
+            &3600,
+        );
+    }
+    pub fn burn(env: Env, loan_id: u64) {}
+    pub fn get_metadata(env: Env, loan_id: u64) -> Option<LoanMetadata> {
+        None
+    }
+    pub fn owner_of(env: Env, loan_id: u64) -> Option<Address> {
+        None
+    }
+}
+
+#[test]
+fn test_reentrancy_attack_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+
+    // Register Malicious NFT contract
+    let mal_nft_id = env.register_contract(None, MaliciousNFT);
+
+    // MaliciousNFT needs to know the lending contract address to call back
+    // We update its internal state directly in the test environment if needed,
+    // but here we just used instance storage of the mock.
+    // Wait, MaliciousNFT is a separate contract, it doesn't share instance storage with the test setup.
     // I need to set it in MaliciousNFT's storage.
     env.as_contract(&mal_nft_id, || {
         env.storage()
@@ -1099,7 +1170,7 @@ fn test_reentrancy_attack_fails() {
     mint_to(&env, &collateral_addr, &borrower, 100_000);
     mint_to(&env, &token_addr, &depositor, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // This borrow will trigger MaliciousNFT::mint, which calls client.borrow again.
     // The inner borrow should return ReentrantCall error.
@@ -1109,21 +1180,19 @@ fn test_reentrancy_attack_fails() {
     // Let's modify MaliciousNFT to panic on reentrancy failure if we want to catch it specifically,
     // or just check that NO second loan was created.
 
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1_000u64,
-            &collateral_addr,
-            &1_500u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &1_500u64,
+        &(30 * 24 * 60 * 60),
+    );
 
     // If reentrancy was successful, next loan ID would be 3 (1 from first successful, 1 from reentrant).
     // If blocked, next loan ID should be 2.
     // Actually, in our implementation, pool.total_borrowed would be double if successful.
-    let pool = client.get_pool_state(&token_addr).unwrap();
+    let pool = client.get_pool_state(&token_addr);
     assert_eq!(pool.total_borrowed, 1000); // Only the first borrow succeeded
 }
 
@@ -1135,10 +1204,10 @@ fn test_reentrancy_attack_fails() {
 fn test_grace_period_defaults() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _token_addr, _collateral_addr, _admin) = setup(&env);
+    let (client, token_addr, _collateral_addr, _admin) = setup(&env);
 
-    let grace_period = client.get_grace_period();
-    let late_fee_rate = client.get_late_fee_rate();
+    let grace_period = client.get_grace_period(&token_addr);
+    let late_fee_rate = client.get_late_fee_rate(&token_addr);
 
     // Should have default values set
     assert_eq!(grace_period, 259_200u64); // 3 days
@@ -1149,40 +1218,40 @@ fn test_grace_period_defaults() {
 fn test_set_grace_period_admin_only() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _token_addr, _collateral_addr, admin) = setup(&env);
+    let (client, token_addr, _collateral_addr, admin) = setup(&env);
 
     let non_admin = Address::generate(&env);
 
     // Non-admin should fail
-    let result = client.try_set_grace_period(&non_admin, &(5 * 24 * 60 * 60));
+    let result = client.try_set_grace_period(&non_admin, &token_addr, &(5 * 24 * 60 * 60));
     assert!(result.is_err());
 
     // Admin should succeed
-    let result = client.try_set_grace_period(&admin, &(7 * 24 * 60 * 60));
+    let result = client.try_set_grace_period(&admin, &token_addr, &(7 * 24 * 60 * 60));
     assert!(result.is_ok());
 
     // Verify the new grace period
-    assert_eq!(client.get_grace_period(), 7 * 24 * 60 * 60);
+    assert_eq!(client.get_grace_period(&token_addr), 7 * 24 * 60 * 60);
 }
 
 #[test]
 fn test_set_late_fee_rate_admin_only() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _token_addr, _collateral_addr, admin) = setup(&env);
+    let (client, token_addr, _collateral_addr, admin) = setup(&env);
 
     let non_admin = Address::generate(&env);
 
     // Non-admin should fail
-    let result = client.try_set_late_fee_rate(&non_admin, &1000u32);
+    let result = client.try_set_late_fee_rate(&non_admin, &token_addr, &1000u32);
     assert!(result.is_err());
 
     // Admin should succeed
-    let result = client.try_set_late_fee_rate(&admin, &1000u32);
+    let result = client.try_set_late_fee_rate(&admin, &token_addr, &1000u32);
     assert!(result.is_ok());
 
     // Verify the new rate
-    assert_eq!(client.get_late_fee_rate(), 1000u32); // 10% per day
+    assert_eq!(client.get_late_fee_rate(&token_addr), 1000u32); // 10% per day
 }
 
 #[test]
@@ -1197,19 +1266,17 @@ fn test_no_late_fees_during_grace_period() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Borrow with 1 day duration
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1_000u64,
-            &collateral_addr,
-            &1_500u64,
-            &(24 * 60 * 60),
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &1_500u64,
+        &(24 * 60 * 60),
+    );
 
     // Jump to just after due date (within grace period)
     // Grace period is 3 days (259200 seconds), so due_date + grace = due_date + 259200
@@ -1217,15 +1284,15 @@ fn test_no_late_fees_during_grace_period() {
         .set_timestamp(env.ledger().timestamp() + 2 * 24 * 60 * 60); // Jump 2 days
 
     // Should still be in grace period
-    let in_grace = client.is_in_grace_period(&borrower).unwrap();
+    let in_grace = client.is_in_grace_period(&borrower);
     assert!(in_grace);
 
     // Late fees should be 0
-    let late_fee = client.calculate_late_fee(&borrower).unwrap();
+    let late_fee = client.calculate_late_fee(&borrower);
     assert_eq!(late_fee, 0u64);
 
     // Total due should only include principal + interest, no late fees
-    let repayment = client.get_repayment_amount(&borrower).unwrap();
+    let repayment = client.get_repayment_amount(&borrower);
     // 1000 principal at ~15% APY for ~2 days = 1000 + ~8 interest
     assert!(repayment < 1_100u64);
 }
@@ -1242,23 +1309,21 @@ fn test_late_fees_after_grace_period() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Set grace period to 1 day and late fee to 5% per day for easier testing
-    client.set_grace_period(&admin, &(24 * 60 * 60)).unwrap();
-    client.set_late_fee_rate(&admin, &500u32).unwrap(); // 5% per day
+    client.set_grace_period(&admin, &token_addr, &(24 * 60 * 60));
+    client.set_late_fee_rate(&admin, &token_addr, &500u32); // 5% per day
 
     // Borrow 10,000 (so late fees are 500 per day)
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &10_000u64,
-            &collateral_addr,
-            &15_000u64,
-            &(24 * 60 * 60), // 1 day duration
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &10_000u64,
+        &collateral_addr,
+        &15_000u64,
+        &(24 * 60 * 60), // 1 day duration
+    );
 
     // Jump to 3 days after due date (2 days past grace period)
     // late fees = 10000 * 0.05 * 2 = 1000
@@ -1266,15 +1331,15 @@ fn test_late_fees_after_grace_period() {
         .set_timestamp(env.ledger().timestamp() + 4 * 24 * 60 * 60);
 
     // Should be out of grace period
-    let in_grace = client.is_in_grace_period(&borrower).unwrap();
+    let in_grace = client.is_in_grace_period(&borrower);
     assert!(!in_grace);
 
     // Late fee should be ~1000 (2 days * 500 per day = 1000)
-    let late_fee = client.calculate_late_fee(&borrower).unwrap();
+    let late_fee = client.calculate_late_fee(&borrower);
     assert_eq!(late_fee, 1_000u64);
 
     // Total due should include late fees
-    let repayment = client.get_repayment_amount(&borrower).unwrap();
+    let repayment = client.get_repayment_amount(&borrower);
     // 10000 principal + interest (~825 for 4 days at ~15%) + 1000 late fees = ~11825
     assert!(repayment > 11_000u64);
 }
@@ -1292,19 +1357,17 @@ fn test_liquidation_blocked_during_grace_period() {
     mint_to(&env, &token_addr, &depositor, 50_000);
     mint_to(&env, &token_addr, &liquidator, 50_000);
 
-    client.deposit(&depositor, &token_addr, &20_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &20_000u64);
 
     // Borrow with very high collateral (so health factor starts good)
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &5_000u64,
-            &collateral_addr,
-            &7_500u64, // Exactly 150% collateral ratio
-            &(24 * 60 * 60),
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &5_000u64,
+        &collateral_addr,
+        &7_500u64, // Exactly 150% collateral ratio
+        &(24 * 60 * 60),
+    );
 
     // Even though health factor might be bad, liquidation should fail during grace period
     let result = client.try_liquidate(&liquidator, &borrower, &1_000u64);
@@ -1332,41 +1395,41 @@ fn test_bad_debt_reserve_replenishment_and_liquidation() {
     mint_to(&env, &token_addr, &admin, 20_000);
     mint_to(&env, &collateral_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &5_000u64,
-            &collateral_addr,
-            &7_500u64,
-            &(5 * 365 * 24 * 60 * 60),
-        )
-        .unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &5_000u64,
+        &collateral_addr,
+        &7_500u64,
+        &(5 * 365 * 24 * 60 * 60),
+    );
 
+    // Past maturity and the default three-day grace period.
     env.ledger()
-        .set_timestamp(env.ledger().timestamp() + 5 * 365 * 24 * 60 * 60);
+        .set_timestamp(env.ledger().timestamp() + 5 * 365 * 24 * 60 * 60 + 3 * 24 * 60 * 60 + 1);
 
-    let outstanding = client.get_repayment_amount(&borrower).unwrap();
+    let outstanding = client.get_repayment_amount(&borrower);
     assert!(outstanding > 7_500u64);
 
-    let initial_reserve = client.get_reserve_balance(&token_addr).unwrap();
+    let initial_reserve = client.get_reserve_balance(&token_addr);
     assert_eq!(initial_reserve, 0);
 
     let shortfall = outstanding.saturating_sub(7_500u64);
     assert!(shortfall > 0);
 
-    let reserve_balance = client
-        .replenish_bad_debt_reserve(&admin, &token_addr, &(shortfall + 1_000u64))
-        .unwrap();
-    assert_eq!(reserve_balance, shortfall + 1_000u64);
+    let reserve_balance =
+        client.replenish_bad_debt_reserve(&admin, &token_addr, &(shortfall + 1_000u64));
+    // The reserve also received its share of the five years of interest
+    let accrued_reserve = reserve_balance - (shortfall + 1_000u64);
+    assert!(accrued_reserve > 0);
 
-    let covered = client.liquidate_bad_debt(&admin, &borrower).unwrap();
+    let covered = client.liquidate_bad_debt(&admin, &borrower);
     assert_eq!(covered, shortfall);
 
-    let pool = client.get_pool_state(&token_addr).unwrap();
+    let pool = client.get_pool_state(&token_addr);
     assert_eq!(pool.total_borrowed, 0);
-    assert_eq!(pool.bad_debt_reserve, 1_000u64);
+    assert_eq!(pool.bad_debt_reserve, 1_000u64 + accrued_reserve);
 
     assert!(client.get_loan(&borrower).is_none());
     assert!(client.get_user_loan_ids(&borrower).is_empty());
@@ -1384,40 +1447,38 @@ fn test_late_fee_collected_on_repay() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Set simple rates for testing
-    client.set_grace_period(&admin, &(24 * 60 * 60)).unwrap();
-    client.set_late_fee_rate(&admin, &500u32).unwrap(); // 5% per day
+    client.set_grace_period(&admin, &token_addr, &(24 * 60 * 60));
+    client.set_late_fee_rate(&admin, &token_addr, &500u32); // 5% per day
 
     // Borrow 5000
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &5_000u64,
-            &collateral_addr,
-            &7_500u64,
-            &(24 * 60 * 60),
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &5_000u64,
+        &collateral_addr,
+        &7_500u64,
+        &(24 * 60 * 60),
+    );
 
     // Jump 4 days (1 day maturity + 1 day grace + 2 days late)
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 4 * 24 * 60 * 60);
 
     // Late fees should be 5000 * 0.05 * 2 = 500
-    let late_fee = client.calculate_late_fee(&borrower).unwrap();
+    let late_fee = client.calculate_late_fee(&borrower);
     assert_eq!(late_fee, 500u64);
 
     // Get pool state before repay
-    let pool_before = client.get_pool_state(&token_addr).unwrap();
+    let pool_before = client.get_pool_state(&token_addr);
 
     // Repay - should include late fees
-    client.repay(&borrower).unwrap();
+    client.repay(&borrower);
 
     // Get pool state after repay
-    let pool_after = client.get_pool_state(&token_addr).unwrap();
+    let pool_after = client.get_pool_state(&token_addr);
 
     // Late fee (500) should be added to retained_yield
     // Protocol gets 10% of interest, but 100% of late fees
@@ -1440,37 +1501,33 @@ fn test_grace_period_expires_correctly() {
     mint_to(&env, &token_addr, &depositor, 100_000);
     mint_to(&env, &token_addr, &borrower, 100_000);
 
-    client.deposit(&depositor, &token_addr, &10_000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &10_000u64);
 
     // Set 2 day grace period
-    client
-        .set_grace_period(&admin, &(2 * 24 * 60 * 60))
-        .unwrap();
+    client.set_grace_period(&admin, &token_addr, &(2 * 24 * 60 * 60));
 
     // Borrow with 1 day maturity
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1_000u64,
-            &collateral_addr,
-            &1_500u64,
-            &(24 * 60 * 60),
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &1_500u64,
+        &(24 * 60 * 60),
+    );
 
     // At 1.5 days: should be in grace period
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 36 * 60 * 60);
-    assert!(client.is_in_grace_period(&borrower).unwrap());
+    assert!(client.is_in_grace_period(&borrower));
 
     // At 4.5 days: should be out of grace period (3 days) and have at least 1 day overdue
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 3 * 24 * 60 * 60); // Total 4.5 days
-    assert!(!client.is_in_grace_period(&borrower).unwrap());
+    assert!(!client.is_in_grace_period(&borrower));
 
     // Late fees should start accruing
-    assert!(client.calculate_late_fee(&borrower).unwrap() > 0u64);
+    assert!(client.calculate_late_fee(&borrower) > 0u64);
 }
 
 #[test]
@@ -1481,9 +1538,7 @@ fn test_multiple_loans_grace_period() {
 
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 200_000);
-    client
-        .deposit(&depositor, &token_addr, &100_000u64)
-        .unwrap();
+    client.deposit(&depositor, &token_addr, &100_000u64);
 
     let borrower1 = Address::generate(&env);
     let borrower2 = Address::generate(&env);
@@ -1492,44 +1547,40 @@ fn test_multiple_loans_grace_period() {
     mint_to(&env, &token_addr, &borrower1, 100_000);
     mint_to(&env, &token_addr, &borrower2, 100_000);
 
-    client.set_grace_period(&admin, &(24 * 60 * 60)).unwrap();
+    client.set_grace_period(&admin, &token_addr, &(24 * 60 * 60));
 
     // Create two loans with different maturities
-    client
-        .borrow(
-            &borrower1,
-            &token_addr,
-            &1_000u64,
-            &collateral_addr,
-            &1_500u64,
-            &(24 * 60 * 60),
-        )
-        .unwrap();
+    client.borrow(
+        &borrower1,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &1_500u64,
+        &(24 * 60 * 60),
+    );
 
     env.ledger().set_timestamp(env.ledger().timestamp() + 1_000);
 
-    client
-        .borrow(
-            &borrower2,
-            &token_addr,
-            &2_000u64,
-            &collateral_addr,
-            &3_000u64,
-            &(2 * 24 * 60 * 60),
-        )
-        .unwrap();
+    client.borrow(
+        &borrower2,
+        &token_addr,
+        &2_000u64,
+        &collateral_addr,
+        &3_000u64,
+        &(2 * 24 * 60 * 60),
+    );
 
     // Jump 3 days to ensure borrower1 is past grace period
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 3 * 24 * 60 * 60);
 
     // borrower1 should be out of grace period
-    assert!(!client.is_in_grace_period(&borrower1).unwrap());
-    assert!(client.calculate_late_fee(&borrower1).unwrap() > 0u64);
+    assert!(!client.is_in_grace_period(&borrower1));
+    assert!(client.calculate_late_fee(&borrower1) > 0u64);
 
     // borrower2 should still be in grace period (due_date is 2 days after borrow, grace = 1 day, so still in grace)
-    assert!(client.is_in_grace_period(&borrower2).unwrap());
-    assert_eq!(client.calculate_late_fee(&borrower2).unwrap(), 0u64);
+    assert!(client.is_in_grace_period(&borrower2));
+    assert_eq!(client.calculate_late_fee(&borrower2), 0u64);
 }
 
 // ─────────────────────────────────────────────────
@@ -1549,24 +1600,20 @@ fn test_get_refinance_terms() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to provide liquidity
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &5000u64);
 
     // Borrow 1000 with 1500 collateral for 30 days
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1000u64,
-            &collateral_addr,
-            &1500u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1000u64,
+        &collateral_addr,
+        &1500u64,
+        &(30 * 24 * 60 * 60),
+    );
 
     // Get refinancing terms for 60 days
-    let terms = client
-        .get_refinance_terms(&borrower, &(60 * 24 * 60 * 60))
-        .unwrap();
+    let terms = client.get_refinance_terms(&borrower, &(60 * 24 * 60 * 60));
 
     // Should have outstanding balance (principal + accrued interest)
     assert!(terms.outstanding_balance >= 1000u64);
@@ -1600,9 +1647,11 @@ fn test_get_refinance_terms_overflow_rejected() {
     };
 
     // Write loan record directly into contract storage for the test
-    env.storage()
-        .persistent()
-        .set(&DataKey::Loan(borrower.clone()), &loan);
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Loan(borrower.clone()), &loan);
+    });
 
     // Call get_refinance_terms and expect InvalidRefinanceTerms due to overflow checks
     let result = client.try_get_refinance_terms(&borrower, &(60 * 24 * 60 * 60));
@@ -1622,27 +1671,23 @@ fn test_refinance_loan() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to provide liquidity
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &5000u64);
 
     // Borrow 1000 with 1500 collateral for 30 days
-    let old_loan_id = client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1000u64,
-            &collateral_addr,
-            &1500u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    let old_loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &1000u64,
+        &collateral_addr,
+        &1500u64,
+        &(30 * 24 * 60 * 60),
+    );
 
     // Get initial loan details
     let old_loan = client.get_loan(&borrower).unwrap();
 
     // Refinance for 60 days
-    let new_loan_id = client
-        .refinance_loan(&borrower, &(60 * 24 * 60 * 60))
-        .unwrap();
+    let new_loan_id = client.refinance_loan(&borrower, &(60 * 24 * 60 * 60));
 
     // Verify new loan exists with different terms
     let new_loan = client.get_loan(&borrower).unwrap();
@@ -1671,19 +1716,17 @@ fn test_refinance_loan_fails_when_overdue() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to provide liquidity
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &5000u64);
 
     // Borrow 1000 for 1 day
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1000u64,
-            &collateral_addr,
-            &1500u64,
-            &(24 * 60 * 60),
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1000u64,
+        &collateral_addr,
+        &1500u64,
+        &(24 * 60 * 60),
+    );
 
     // Jump past grace period
     env.ledger()
@@ -1707,31 +1750,27 @@ fn test_consolidate_loans() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to provide liquidity
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &5000u64);
 
     // Create multiple loans by using different borrowers first, then transferring
     // For this test, we'll need a different approach since the contract only allows one loan per user
 
     // Let's modify the contract to allow multiple loans for testing consolidation
     // For now, let's test the consolidation logic with a single loan (edge case)
-    let loan_id1 = client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1000u64,
-            &collateral_addr,
-            &1500u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    let loan_id1 = client.borrow(
+        &borrower,
+        &token_addr,
+        &1000u64,
+        &collateral_addr,
+        &1500u64,
+        &(30 * 24 * 60 * 60),
+    );
 
     // Try to consolidate single loan (should work but be similar to refinance)
     let mut loan_ids = Vec::new(&env);
     loan_ids.push_back(loan_id1);
 
-    let new_loan_id = client
-        .consolidate_loans(&borrower, &loan_ids, &(60 * 24 * 60 * 60))
-        .unwrap();
+    let new_loan_id = client.consolidate_loans(&borrower, &loan_ids, &(60 * 24 * 60 * 60));
 
     // Verify consolidation worked
     let new_loan = client.get_loan(&borrower).unwrap();
@@ -1752,26 +1791,24 @@ fn test_split_loan() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to provide liquidity
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &5000u64);
 
     // Borrow 2000 with 3000 collateral
-    let old_loan_id = client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &2000u64,
-            &collateral_addr,
-            &3000u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    let old_loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &2000u64,
+        &collateral_addr,
+        &3000u64,
+        &(30 * 24 * 60 * 60),
+    );
 
     // Jump forward a bit to accrue some interest
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 10 * 24 * 60 * 60);
 
     // Get current outstanding balance
-    let outstanding = client.get_repayment_amount(&borrower).unwrap();
+    let outstanding = client.get_repayment_amount(&borrower);
 
     // Split into two loans: 60% and 40%
     let split1 = (outstanding * 60) / 100;
@@ -1781,9 +1818,7 @@ fn test_split_loan() {
     split_amounts.push_back(split1);
     split_amounts.push_back(split2);
 
-    let new_loan_ids = client
-        .split_loan(&borrower, &split_amounts, &(45 * 24 * 60 * 60))
-        .unwrap();
+    let new_loan_ids = client.split_loan(&borrower, &split_amounts, &(45 * 24 * 60 * 60));
 
     // Verify split worked
     assert_eq!(new_loan_ids.len(), 2);
@@ -1813,19 +1848,17 @@ fn test_split_loan_invalid_amounts() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to provide liquidity
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &5000u64);
 
     // Borrow 1000
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1000u64,
-            &collateral_addr,
-            &1500u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1000u64,
+        &collateral_addr,
+        &1500u64,
+        &(30 * 24 * 60 * 60),
+    );
 
     // Try to split with amounts that don't sum to outstanding
     let mut split_amounts = Vec::new(&env);
@@ -1858,23 +1891,21 @@ fn test_user_loan_tracking() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to provide liquidity
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &5000u64);
 
     // Initially should have no loans
     let user_loans = client.get_user_loan_ids(&borrower);
     assert_eq!(user_loans.len(), 0);
 
     // Borrow a loan
-    let loan_id = client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1000u64,
-            &collateral_addr,
-            &1500u64,
-            &(30 * 24 * 60 * 60),
-        )
-        .unwrap();
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &1000u64,
+        &collateral_addr,
+        &1500u64,
+        &(30 * 24 * 60 * 60),
+    );
 
     // Should have one loan
     let user_loans = client.get_user_loan_ids(&borrower);
@@ -1886,16 +1917,14 @@ fn test_user_loan_tracking() {
     split_amounts.push_back(500u64);
     split_amounts.push_back(500u64);
 
-    client
-        .split_loan(&borrower, &split_amounts, &(30 * 24 * 60 * 60))
-        .unwrap();
+    client.split_loan(&borrower, &split_amounts, &(30 * 24 * 60 * 60));
 
     // Should have two loans
     let user_loans = client.get_user_loan_ids(&borrower);
     assert_eq!(user_loans.len(), 2);
 
     // Repay one loan (by getting the primary loan and repaying)
-    client.repay(&borrower).unwrap();
+    client.repay(&borrower);
 
     // Should have one loan left
     let user_loans = client.get_user_loan_ids(&borrower);
@@ -1939,15 +1968,14 @@ impl MockFlashLoanReceiver {
 fn test_flash_loan_success() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, token_addr, _, _) = setup(&env);
+    let (client, token_addr, _, admin) = setup(&env);
 
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 1_000_000);
-    client
-        .deposit(&depositor, &token_addr, &100_000u64)
-        .unwrap();
+    client.deposit(&depositor, &token_addr, &100_000u64);
 
     let receiver_id = env.register_contract(None, MockFlashLoanReceiver);
+    client.whitelist_flash_loan_receiver(&admin, &receiver_id);
 
     env.as_contract(&receiver_id, || {
         env.storage()
@@ -1963,13 +1991,11 @@ fn test_flash_loan_success() {
     let flash_loan_amount = 50_000u64;
     let expected_fee = (50_000u64 * 9) / 10000;
 
-    let pool_before = client.get_pool_state(&token_addr).unwrap();
+    let pool_before = client.get_pool_state(&token_addr);
 
-    client
-        .flash_loan(&token_addr, &receiver_id, &flash_loan_amount)
-        .unwrap();
+    client.flash_loan(&depositor, &receiver_id, &token_addr, &flash_loan_amount);
 
-    let pool_after = client.get_pool_state(&token_addr).unwrap();
+    let pool_after = client.get_pool_state(&token_addr);
     assert_eq!(
         pool_after.total_deposits,
         pool_before.total_deposits + expected_fee
@@ -1992,8 +2018,8 @@ fn test_stake_lp_tokens() {
     mint_to(&env, &token_addr, &depositor, 10_000);
 
     // Deposit funds to get shares
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
-    client.deposit(&depositor, &token_addr, &5000u64).unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
+    client.deposit(&depositor, &token_addr, &5000u64);
 
     // Check initial state
     assert_eq!(client.get_staked_balance(&user, &token_addr), 0);
@@ -2001,9 +2027,7 @@ fn test_stake_lp_tokens() {
 
     // Stake LP tokens
     let stake_amount = 1000u64;
-    client
-        .stake_lp_tokens(&user, &token_addr, &stake_amount)
-        .unwrap();
+    client.stake_lp_tokens(&user, &token_addr, &stake_amount);
 
     // Verify staking
     assert_eq!(client.get_staked_balance(&user, &token_addr), stake_amount);
@@ -2040,20 +2064,16 @@ fn test_unstake_lp_tokens() {
     mint_to(&env, &token_addr, &user, 10_000);
 
     // Deposit and stake
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
     let stake_amount = 1000u64;
-    client
-        .stake_lp_tokens(&user, &token_addr, &stake_amount)
-        .unwrap();
+    client.stake_lp_tokens(&user, &token_addr, &stake_amount);
 
     // Jump forward in time to accumulate rewards
     env.ledger().set_timestamp(env.ledger().timestamp() + 1000);
 
     // Unstake
     let unstake_amount = 500u64;
-    client
-        .unstake_lp_tokens(&user, &token_addr, &unstake_amount)
-        .unwrap();
+    client.unstake_lp_tokens(&user, &token_addr, &unstake_amount);
 
     // Verify unstaking
     assert_eq!(
@@ -2076,10 +2096,8 @@ fn test_unstake_lp_tokens_insufficient_stake() {
     mint_to(&env, &token_addr, &user, 10_000);
 
     // Deposit and stake small amount
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
-    client
-        .stake_lp_tokens(&user, &token_addr, &1000u64)
-        .unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
+    client.stake_lp_tokens(&user, &token_addr, &1000u64);
 
     // Try to unstake more than staked
     let result = client.try_unstake_lp_tokens(&user, &token_addr, &2000u64);
@@ -2096,10 +2114,8 @@ fn test_claim_rewards() {
     mint_to(&env, &token_addr, &user, 10_000);
 
     // Deposit and stake
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
-    client
-        .stake_lp_tokens(&user, &token_addr, &1000u64)
-        .unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
+    client.stake_lp_tokens(&user, &token_addr, &1000u64);
 
     // Jump forward in time to accumulate rewards
     let time_jump = 10_000u64;
@@ -2111,7 +2127,7 @@ fn test_claim_rewards() {
     assert!(pending_before > 0);
 
     // Claim rewards
-    let claimed = client.claim_rewards(&user, &token_addr).unwrap();
+    let claimed = client.claim_rewards(&user, &token_addr);
     assert!(claimed > 0);
     assert_eq!(claimed, pending_before);
 
@@ -2129,10 +2145,8 @@ fn test_claim_rewards_no_rewards() {
     mint_to(&env, &token_addr, &user, 10_000);
 
     // Deposit and stake
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
-    client
-        .stake_lp_tokens(&user, &token_addr, &1000u64)
-        .unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
+    client.stake_lp_tokens(&user, &token_addr, &1000u64);
 
     // Try to claim immediately (no time passed)
     let result = client.try_claim_rewards(&user, &token_addr);
@@ -2151,9 +2165,7 @@ fn test_set_reward_rate() {
 
     // Set new reward rate as admin
     let new_rate = 2000u64;
-    client
-        .set_reward_rate(&admin, &token_addr, &new_rate)
-        .unwrap();
+    client.set_reward_rate(&admin, &token_addr, &new_rate);
 
     // Verify rate was updated
     assert_eq!(client.get_reward_rate(&token_addr), new_rate);
@@ -2187,14 +2199,10 @@ fn test_multiple_users_staking() {
     mint_to(&env, &token_addr, &user2, 10_000);
 
     // Both users deposit and stake
-    client.deposit(&user1, &token_addr, &5000u64).unwrap();
-    client.deposit(&user2, &token_addr, &5000u64).unwrap();
-    client
-        .stake_lp_tokens(&user1, &token_addr, &1000u64)
-        .unwrap();
-    client
-        .stake_lp_tokens(&user2, &token_addr, &2000u64)
-        .unwrap();
+    client.deposit(&user1, &token_addr, &5000u64);
+    client.deposit(&user2, &token_addr, &5000u64);
+    client.stake_lp_tokens(&user1, &token_addr, &1000u64);
+    client.stake_lp_tokens(&user2, &token_addr, &2000u64);
 
     // Verify total staked
     assert_eq!(client.get_total_staked(&token_addr), 3000u64);
@@ -2223,16 +2231,12 @@ fn test_reward_calculation_accuracy() {
     mint_to(&env, &token_addr, &user, 10_000);
 
     // Deposit and stake known amount
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
     let stake_amount = 1000u64;
-    client
-        .stake_lp_tokens(&user, &token_addr, &stake_amount)
-        .unwrap();
+    client.stake_lp_tokens(&user, &token_addr, &stake_amount);
 
     // Set known reward rate and jump exact time
-    client
-        .set_reward_rate(&admin, &token_addr, &1_000_000_000u64)
-        .unwrap(); // 1 reward per second per token
+    client.set_reward_rate(&admin, &token_addr, &1_000_000_000u64); // 1 reward per second per token
     let time_elapsed = 1000u64; // 1000 seconds
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + time_elapsed);
@@ -2256,10 +2260,8 @@ fn test_partial_unstake_preserves_rewards() {
     mint_to(&env, &token_addr, &user, 10_000);
 
     // Deposit and stake
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
-    client
-        .stake_lp_tokens(&user, &token_addr, &1000u64)
-        .unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
+    client.stake_lp_tokens(&user, &token_addr, &1000u64);
 
     // Jump forward to accumulate rewards
     env.ledger()
@@ -2269,9 +2271,7 @@ fn test_partial_unstake_preserves_rewards() {
     assert!(rewards_before > 0);
 
     // Partial unstake
-    client
-        .unstake_lp_tokens(&user, &token_addr, &500u64)
-        .unwrap();
+    client.unstake_lp_tokens(&user, &token_addr, &500u64);
 
     // Should still have remaining stake and rewards preserved
     assert_eq!(client.get_staked_balance(&user, &token_addr), 500u64);
@@ -2289,19 +2289,15 @@ fn test_full_unstake_resets_tracking() {
     mint_to(&env, &token_addr, &user, 10_000);
 
     // Deposit and stake
-    client.deposit(&user, &token_addr, &5000u64).unwrap();
-    client
-        .stake_lp_tokens(&user, &token_addr, &1000u64)
-        .unwrap();
+    client.deposit(&user, &token_addr, &5000u64);
+    client.stake_lp_tokens(&user, &token_addr, &1000u64);
 
     // Jump forward to accumulate rewards
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + 10_000);
 
     // Full unstake
-    client
-        .unstake_lp_tokens(&user, &token_addr, &1000u64)
-        .unwrap();
+    client.unstake_lp_tokens(&user, &token_addr, &1000u64);
 
     // Should have no stake left
     assert_eq!(client.get_staked_balance(&user, &token_addr), 0);
@@ -2343,15 +2339,15 @@ fn test_insurance_premium_calculation() {
     let (client, token_addr, collateral_addr, admin) = setup(&env);
 
     // Test premium calculation for various loan amounts
-    let premium_100 = client.get_insurance_premium(&100u64).unwrap();
+    let premium_100 = client.get_insurance_premium(&100u64);
     // 100 * 2% = 2
     assert_eq!(premium_100, 2u64);
 
-    let premium_1000 = client.get_insurance_premium(&1000u64).unwrap();
+    let premium_1000 = client.get_insurance_premium(&1000u64);
     // 1000 * 2% = 20
     assert_eq!(premium_1000, 20u64);
 
-    let premium_10000 = client.get_insurance_premium(&10000u64).unwrap();
+    let premium_10000 = client.get_insurance_premium(&10000u64);
     // 10000 * 2% = 200
     assert_eq!(premium_10000, 200u64);
 }
@@ -2363,9 +2359,9 @@ fn test_set_insurance_premium_rate() {
     let (client, token_addr, collateral_addr, admin) = setup(&env);
 
     // Admin can set premium rate
-    client.set_insurance_premium_rate(&admin, &500u32).unwrap(); // 5%
+    client.set_insurance_premium_rate(&admin, &500u32); // 5%
 
-    let premium = client.get_insurance_premium(&1000u64).unwrap();
+    let premium = client.get_insurance_premium(&1000u64);
     // 1000 * 5% = 50
     assert_eq!(premium, 50u64);
 }
@@ -2381,41 +2377,39 @@ fn test_purchase_loan_insurance_success() {
 
     // Setup: lender deposits
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     // Setup: borrower borrows with collateral
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
 
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &5_000u64,
-            &collateral_addr,
-            &10_000u64,
-            &7_200u64, // 2 hours
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &5_000u64,
+        &collateral_addr,
+        &10_000u64,
+        &7_200u64, // 2 hours
+    );
 
     // Borrower has funds for premium
     mint_to(&env, &token_addr, &borrower, 1_000);
 
     // Purchase insurance for loan 0
-    let premium = client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    let premium = client.purchase_loan_insurance(&borrower, &1u64);
     // Premium should be 2% of 5000 = 100
     assert_eq!(premium, 100u64);
 
     // Verify insurance exists
-    assert_eq!(client.is_loan_insured(&0u64), true);
+    assert!(client.is_loan_insured(&1u64));
 
-    let coverage = client.get_insurance_coverage(&0u64);
+    let coverage = client.get_insurance_coverage(&1u64);
     assert_eq!(coverage, 5_000u64); // 100% coverage
 
-    let insurance = client.get_insurance_details(&0u64);
+    let insurance = client.get_insurance_details(&1u64);
     assert!(insurance.is_some());
     let ins = insurance.unwrap();
-    assert_eq!(ins.loan_id, 0);
+    assert_eq!(ins.loan_id, 1);
     assert_eq!(ins.borrower, borrower);
     assert_eq!(ins.coverage_amount, 5_000u64);
     assert_eq!(ins.premium_paid, 100u64);
@@ -2495,7 +2489,7 @@ fn test_get_borrow_rate_at_optimal() {
 
     // Setup: lender deposits and borrower borrows
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &50_000u64);
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
@@ -2512,10 +2506,10 @@ fn test_get_borrow_rate_at_optimal() {
     mint_to(&env, &token_addr, &borrower, 2_000);
 
     // Purchase insurance first time
-    client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    client.purchase_loan_insurance(&borrower, &1u64);
 
     // Try to purchase again - should fail
-    let result = client.try_purchase_loan_insurance(&borrower, &0u64);
+    let result = client.try_purchase_loan_insurance(&borrower, &1u64);
     assert!(result.is_err());
 }
 
@@ -2530,21 +2524,19 @@ fn test_insurance_fund_tracking() {
 
     // Setup
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
 
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &5_000u64,
-            &collateral_addr,
-            &10_000u64,
-            &7_200u64,
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &5_000u64,
+        &collateral_addr,
+        &10_000u64,
+        &7_200u64,
+    );
 
     mint_to(&env, &token_addr, &borrower, 1_000);
 
@@ -2555,7 +2547,7 @@ fn test_insurance_fund_tracking() {
     assert_eq!(fund_before.available_balance, 0);
 
     // Purchase insurance
-    let premium = client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    let premium = client.purchase_loan_insurance(&borrower, &1u64);
 
     // Check fund state after purchase
     let fund_after = client.get_insurance_fund_state();
@@ -2573,7 +2565,7 @@ fn test_deposit_to_insurance_fund() {
     mint_to(&env, &token_addr, &admin, 10_000);
 
     // Deposit to insurance fund
-    client.deposit_to_insurance_fund(&admin, &5_000u64).unwrap();
+    client.deposit_to_insurance_fund(&admin, &5_000u64);
 
     let fund = client.get_insurance_fund_state();
     assert_eq!(fund.available_balance, 5_000u64);
@@ -2588,29 +2580,25 @@ fn test_claim_insurance_after_default() {
     // Deposit 2000 (must exceed MINIMUM_LIQUIDITY=1000 for first deposit)
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 2000);
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
 
     // Borrow 1600 → 80% utilization (1600/2000 = 80%)
     let borrower = Address::generate(&env);
     mint_to(&env, &collateral_addr, &borrower, 3000);
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1600u64,
-            &collateral_addr,
-            &3000u64,
-            &31536000u64,
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1600u64,
+        &collateral_addr,
+        &3000u64,
+        &31536000u64,
+    );
 
     // Configure model: base=200, optimal=80%, slope1=800, slope2=10000
-    client
-        .set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32)
-        .unwrap();
+    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32);
 
     // rate = base + (8000 / 8000) * slope1 = 200 + 800 = 1000
-    let borrow_rate = client.get_borrow_rate().unwrap();
+    let borrow_rate = client.get_borrow_rate();
     assert_eq!(borrow_rate, 1000u32);
 }
 
@@ -2621,12 +2609,10 @@ fn test_simulate_rate_below_optimal() {
     let (client, _token_addr, _collateral_addr, admin) = setup(&env);
 
     // base=200, optimal=8000, slope1=800, slope2=10000
-    client
-        .set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32)
-        .unwrap();
+    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32);
 
     // At 40% utilization: rate = 200 + (4000 / 8000) * 800 = 200 + 400 = 600
-    let rate = client.simulate_rate(&4000u32).unwrap();
+    let rate = client.simulate_rate(&4000u32);
     assert_eq!(rate, 600u32);
 }
 
@@ -2637,14 +2623,12 @@ fn test_simulate_rate_above_optimal() {
     let (client, _token_addr, _collateral_addr, admin) = setup(&env);
 
     // base=200, optimal=8000, slope1=800, slope2=10000
-    client
-        .set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32)
-        .unwrap();
+    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32);
 
     // At 90% utilization (above optimal 80%):
     // excess = 9000 - 8000 = 1000, max_excess = 10000 - 8000 = 2000
     // rate = 200 + 800 + (1000 / 2000) * 10000 = 200 + 800 + 5000 = 6000
-    let rate = client.simulate_rate(&9000u32).unwrap();
+    let rate = client.simulate_rate(&9000u32);
     assert_eq!(rate, 6000u32);
 }
 
@@ -2656,7 +2640,7 @@ fn test_simulate_rate_fallback_to_legacy() {
 
     // No rate model set — uses legacy linear model (base_rate=500, multiplier=2000)
     // At 50% utilization: rate = 500 + (5000 * 2000) / 10000 = 500 + 1000 = 1500
-    let rate = client.simulate_rate(&5000u32).unwrap();
+    let rate = client.simulate_rate(&5000u32);
     assert_eq!(rate, 1500u32);
 }
 
@@ -2671,7 +2655,7 @@ fn test_get_supply_rate() {
 
     // Setup
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
@@ -2680,43 +2664,39 @@ fn test_get_supply_rate() {
     let loan_duration = 7_200u64; // 2 hours
     let due_date = env.ledger().timestamp() + loan_duration;
 
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &loan_amount,
-            &collateral_addr,
-            &10_000u64,
-            &loan_duration,
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &loan_amount,
+        &collateral_addr,
+        &10_000u64,
+        &loan_duration,
+    );
 
     mint_to(&env, &token_addr, &borrower, 1_000);
 
     // Purchase insurance
-    let premium = client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    let premium = client.purchase_loan_insurance(&borrower, &1u64);
 
     // Fund the insurance pool for claims
     mint_to(&env, &token_addr, &admin, 20_000);
-    client
-        .deposit_to_insurance_fund(&admin, &10_000u64)
-        .unwrap();
+    client.deposit_to_insurance_fund(&admin, &10_000u64);
 
     // Jump past the grace period end
     env.ledger()
         .set_timestamp(due_date + (3 * 24 * 60 * 60) + 1);
 
     // Claim insurance
-    let claim_amount = client.claim_insurance(&0u64).unwrap();
+    let claim_amount = client.claim_insurance(&1u64);
     assert_eq!(claim_amount, loan_amount); // 100% coverage
 
     // Verify insurance is marked as claimed
-    assert_eq!(client.is_loan_insured(&0u64), false);
+    assert!(!client.is_loan_insured(&1u64));
 
     // Verify fund was updated
     let fund = client.get_insurance_fund_state();
     assert_eq!(fund.total_claims_paid, claim_amount);
-    assert_eq!(fund.available_balance, 10_000u64 - claim_amount);
+    assert_eq!(fund.available_balance, 10_000u64 + premium - claim_amount);
 }
 
 #[test]
@@ -2730,7 +2710,7 @@ fn test_cannot_claim_expired_insurance() {
 
     // Setup
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
@@ -2739,60 +2719,308 @@ fn test_cannot_claim_expired_insurance() {
     let loan_duration = 7_200u64;
     let due_date = env.ledger().timestamp() + loan_duration;
 
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &loan_amount,
-            &collateral_addr,
-            &10_000u64,
-            &loan_duration,
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &loan_amount,
+        &collateral_addr,
+        &10_000u64,
+        &loan_duration,
+    );
 
     mint_to(&env, &token_addr, &borrower, 1_000);
 
     // Purchase insurance
-    client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    client.purchase_loan_insurance(&borrower, &1u64);
 
     // Jump past the grace period end
     env.ledger()
         .set_timestamp(due_date + (3 * 24 * 60 * 60) + 1);
 
     // Try to claim - should fail because insurance expired
-    let result = client.try_claim_insurance(&0u64);
+    let result = client.try_claim_insurance(&1u64);
     // Deposit 2000 (must exceed MINIMUM_LIQUIDITY=1000 for first deposit)
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 2000);
-    client.deposit(&depositor, &token_addr, &2000u64).unwrap();
+    client.deposit(&depositor, &token_addr, &2000u64);
 
     // Borrow 1000 → 50% utilization (1000/2000 = 50%)
     let borrower = Address::generate(&env);
     mint_to(&env, &collateral_addr, &borrower, 2000);
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &1000u64,
-            &collateral_addr,
-            &2000u64,
-            &31536000u64,
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1000u64,
+        &collateral_addr,
+        &2000u64,
+        &31536000u64,
+    );
 
     // Configure model: base=200, optimal=8000, slope1=800, slope2=10000, reserve=1000
-    client
-        .set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32)
-        .unwrap();
+    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32);
 
     // borrow_rate at 50% util = 200 + (5000/8000)*800 = 200 + 500 = 700
     // supply_rate = 700 * 5000 * (10000-1000) / 10000^2 = 700 * 5000 * 9000 / 100000000 = 315
-    let supply_rate = client.get_supply_rate().unwrap();
+    let supply_rate = client.get_supply_rate();
     // Value may differ slightly due to integer arithmetic — just ensure it's non-zero and less than borrow_rate
     assert!(supply_rate > 0);
     assert!(supply_rate < 700u32);
 }
 
+#[test]
+fn test_interest_index_compounds_pool_debt_and_share_value() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, _admin) = setup(&env);
+
+    let depositor = Address::generate(&env);
+    let late_depositor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    mint_to(&env, &token_addr, &depositor, 10_000);
+    mint_to(&env, &token_addr, &late_depositor, 10_000);
+    mint_to(&env, &collateral_addr, &borrower, 7_500);
+    client.deposit(&depositor, &token_addr, &10_000u64);
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &5_000u64,
+        &collateral_addr,
+        &7_500u64,
+        &(365 * 24 * 60 * 60),
+    );
+
+    // A year at 15% compounded per second grows the borrow index by e^0.15
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 31_536_000);
+    let index = client.get_interest_index(&token_addr);
+    assert_eq!(index.borrow_index / 1_000_000_000_000, 1_161_834);
+    assert!(index.supply_index > interest::INDEX_ONE);
+    assert_eq!(client.get_pool_state(&token_addr).total_borrowed, 5_000); // not yet booked
+
+    // The next deposit books the year's interest before pricing its shares
+    let shares = client.deposit(&late_depositor, &token_addr, &10_000u64);
+    let pool = client.get_pool_state(&token_addr);
+    assert_eq!(pool.total_borrowed, 5_809);
+    assert_eq!(pool.total_deposits, 20_729);
+    assert_eq!(pool.total_protocol_revenue, 80);
+    assert_eq!(shares, 9_320); // 10,000 * 10,000 / 10,729
+    assert_eq!(client.get_interest_index(&token_addr), index);
+    assert_eq!(client.get_repayment_amount(&borrower), 5_809);
+}
+
+#[test]
+fn test_rate_model_drives_interest_accrual() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32);
+
+    let depositor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    mint_to(&env, &token_addr, &depositor, 10_000);
+    mint_to(&env, &collateral_addr, &borrower, 13_500);
+    client.deposit(&depositor, &token_addr, &10_000u64);
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &9_000u64,
+        &collateral_addr,
+        &13_500u64,
+        &(60 * 24 * 60 * 60),
+    );
+
+    // 90% utilization is past the kink: 200 + 800 + (1000 / 2000) * 10000 = 6000
+    assert_eq!(client.get_borrow_rate(), 6000u32);
+    assert_eq!(client.get_current_interest_rate(&token_addr), 6000u32);
+    assert_eq!(
+        client.get_loan(&borrower).unwrap().interest_rate_bps,
+        6000u32
+    );
+    // supply_rate = 6000 * 90% * (1 - 10%) = 4860
+    assert_eq!(client.get_supply_rate(), 4860u32);
+    assert_eq!(client.get_asset_supply_rate(&token_addr), 4860u32);
+
+    // 30 days at 60%: 9,000 * e^(0.6 * 30 / 365) = 9,455
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 30 * 24 * 60 * 60);
+    assert_eq!(client.get_repayment_amount(&borrower), 9_455);
+}
+
+#[test]
+fn test_fixed_rate_loan_keeps_its_rate_as_utilization_moves() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32);
+    assert_eq!(
+        client.get_fixed_rate_config(&token_addr),
+        FixedRateConfig {
+            premium_bps: 200,
+            rebalance_utilization_bps: 9500,
+        }
+    );
+
+    let depositor = Address::generate(&env);
+    let fixed_borrower = Address::generate(&env);
+    let variable_borrower = Address::generate(&env);
+    mint_to(&env, &token_addr, &depositor, 10_000);
+    mint_to(&env, &collateral_addr, &fixed_borrower, 7_500);
+    mint_to(&env, &collateral_addr, &variable_borrower, 6_000);
+    client.deposit(&depositor, &token_addr, &10_000u64);
+
+    // 50% utilization: 200 + (5000 / 8000) * 800 = 700, plus the 200 premium
+    client.borrow_with_rate_mode(
+        &fixed_borrower,
+        &token_addr,
+        &5_000u64,
+        &collateral_addr,
+        &7_500u64,
+        &(400 * 24 * 60 * 60),
+        &RateMode::Fixed,
+    );
+    let loan = client.get_loan(&fixed_borrower).unwrap();
+    assert_eq!(loan.interest_rate_bps, 900);
+    assert_eq!(client.get_loan_rate_mode(&fixed_borrower), RateMode::Fixed);
+
+    // A variable loan pushes utilization to 90% and the variable rate to 6000
+    client.borrow(
+        &variable_borrower,
+        &token_addr,
+        &4_000u64,
+        &collateral_addr,
+        &6_000u64,
+        &(400 * 24 * 60 * 60),
+    );
+    assert_eq!(client.get_borrow_rate(), 6000u32);
+    assert_eq!(client.get_fixed_borrow_rate(&token_addr), 6200u32);
+    assert_eq!(
+        client.get_loan_rate_mode(&variable_borrower),
+        RateMode::Variable
+    );
+    let index = client.get_interest_index(&token_addr);
+    assert_eq!(index.fixed_debt, 5_000 * interest::INDEX_ONE);
+    assert_eq!(index.fixed_rate_bps, 900);
+    assert_eq!(index.debt, 4_000 * interest::INDEX_ONE);
+
+    // A year at 9% fixed: 5,000 * e^0.09 = 5,471
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + SECONDS_IN_YEAR);
+    assert_eq!(client.get_repayment_amount(&fixed_borrower), 5_471);
+
+    mint_to(&env, &token_addr, &fixed_borrower, 471);
+    assert_eq!(client.repay(&fixed_borrower), 5_471);
+    let index = client.get_interest_index(&token_addr);
+    assert_eq!((index.fixed_debt, index.fixed_rate_bps), (0, 0));
+}
+
+#[test]
+fn test_switch_rate_mode_moves_loan_between_buckets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32);
+
+    let depositor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    mint_to(&env, &token_addr, &depositor, 10_000);
+    mint_to(&env, &collateral_addr, &borrower, 7_500);
+    client.deposit(&depositor, &token_addr, &10_000u64);
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &5_000u64,
+        &collateral_addr,
+        &7_500u64,
+        &(60 * 24 * 60 * 60),
+    );
+    assert_eq!(
+        client.try_switch_rate_mode(&borrower, &RateMode::Variable),
+        Err(Ok(LendingError::RateModeUnchanged))
+    );
+
+    assert_eq!(client.switch_rate_mode(&borrower, &RateMode::Fixed), 900);
+    assert_eq!(client.get_loan_rate_mode(&borrower), RateMode::Fixed);
+    assert_eq!(client.get_loan(&borrower).unwrap().interest_rate_bps, 900);
+    let index = client.get_interest_index(&token_addr);
+    assert_eq!(index.fixed_debt, 5_000 * interest::INDEX_ONE);
+    assert_eq!(index.debt, 0);
+
+    // 30 days at 9%: 5,000 * e^(0.09 * 30 / 365) = 5,037, carried over as principal
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 30 * 24 * 60 * 60);
+    client.switch_rate_mode(&borrower, &RateMode::Variable);
+    let loan = client.get_loan(&borrower).unwrap();
+    assert_eq!(loan.principal, 5_037);
+    assert_eq!(client.get_loan_rate_mode(&borrower), RateMode::Variable);
+    let index = client.get_interest_index(&token_addr);
+    assert_eq!((index.fixed_debt, index.fixed_rate_bps), (0, 0));
+    assert_eq!(client.get_pool_state(&token_addr).total_borrowed, 5_037);
+}
+
+#[test]
+fn test_fixed_rate_rebalances_only_above_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    client.set_rate_model(&admin, &200u32, &8000u32, &800u32, &10000u32, &1000u32);
+    client.set_fixed_rate_config(&admin, &token_addr, &200u32, &8500u32);
+    assert_eq!(
+        client.try_set_fixed_rate_config(&admin, &token_addr, &200u32, &10_001u32),
+        Err(Ok(LendingError::InvalidRateModel))
+    );
+
+    let depositor = Address::generate(&env);
+    let fixed_borrower = Address::generate(&env);
+    let variable_borrower = Address::generate(&env);
+    mint_to(&env, &token_addr, &depositor, 10_000);
+    mint_to(&env, &collateral_addr, &fixed_borrower, 7_500);
+    mint_to(&env, &collateral_addr, &variable_borrower, 6_000);
+    client.deposit(&depositor, &token_addr, &10_000u64);
+    client.borrow_with_rate_mode(
+        &fixed_borrower,
+        &token_addr,
+        &5_000u64,
+        &collateral_addr,
+        &7_500u64,
+        &(60 * 24 * 60 * 60),
+        &RateMode::Fixed,
+    );
+
+    // 50% utilization is below the threshold
+    assert_eq!(
+        client.try_rebalance_fixed_rate(&fixed_borrower),
+        Err(Ok(LendingError::RebalanceNotAllowed))
+    );
+
+    client.borrow(
+        &variable_borrower,
+        &token_addr,
+        &4_000u64,
+        &collateral_addr,
+        &6_000u64,
+        &(60 * 24 * 60 * 60),
+    );
+    // Variable loans have no fixed rate to rebalance
+    assert_eq!(
+        client.try_rebalance_fixed_rate(&variable_borrower),
+        Err(Ok(LendingError::RebalanceNotAllowed))
+    );
+
+    // 90% utilization: the loan is repriced at 6000 + 200
+    assert_eq!(client.rebalance_fixed_rate(&fixed_borrower), 6200);
+    assert_eq!(
+        client.get_loan(&fixed_borrower).unwrap().interest_rate_bps,
+        6200
+    );
+    assert_eq!(client.get_interest_index(&token_addr).fixed_rate_bps, 6200);
+
+    // Already at the current fixed rate
+    assert_eq!(
+        client.try_rebalance_fixed_rate(&fixed_borrower),
+        Err(Ok(LendingError::RebalanceNotAllowed))
+    );
+}
+
 // ─────────────────────────────────────────────────
 // Access Control (RBAC) Tests
 // ─────────────────────────────────────────────────
@@ -2816,14 +3044,10 @@ fn test_admin_can_assign_and_revoke_roles() {
 
     assert!(!client.has_role(&user, &access_control::Role::Beneficiary));
 
-    client
-        .assign_role(&admin, &user, &access_control::Role::Beneficiary)
-        .unwrap();
+    client.assign_role(&admin, &user, &access_control::Role::Beneficiary);
     assert!(client.has_role(&user, &access_control::Role::Beneficiary));
 
-    client
-        .revoke_role(&admin, &user, &access_control::Role::Beneficiary)
-        .unwrap();
+    client.revoke_role(&admin, &user, &access_control::Role::Beneficiary);
     assert!(!client.has_role(&user, &access_control::Role::Beneficiary));
 }
 
@@ -2850,7 +3074,7 @@ fn test_cancel_insurance_with_refund() {
 
     // Setup
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
@@ -2859,21 +3083,19 @@ fn test_cancel_insurance_with_refund() {
     let loan_duration = 7_200u64;
     let due_date = env.ledger().timestamp() + loan_duration;
 
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &loan_amount,
-            &collateral_addr,
-            &10_000u64,
-            &loan_duration,
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &loan_amount,
+        &collateral_addr,
+        &10_000u64,
+        &loan_duration,
+    );
 
     mint_to(&env, &token_addr, &borrower, 1_000);
 
     // Purchase insurance
-    let premium = client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    let premium = client.purchase_loan_insurance(&borrower, &1u64);
 
     let fund_after_purchase = client.get_insurance_fund_state();
     let initial_balance = fund_after_purchase.available_balance;
@@ -2883,14 +3105,14 @@ fn test_cancel_insurance_with_refund() {
     env.ledger()
         .set_timestamp(env.ledger().timestamp() + half_duration);
 
-    let refund = client.cancel_insurance(&borrower, &0u64).unwrap();
+    let refund = client.cancel_insurance(&borrower, &1u64);
 
     // Refund should be approximately half the premium (pro-rata)
     assert!(refund > 0);
     assert!(refund < premium);
 
     // Verify insurance is removed
-    assert_eq!(client.is_loan_insured(&0u64), false);
+    assert!(!client.is_loan_insured(&1u64));
 
     // Verify fund was updated
     let fund_after_cancel = client.get_insurance_fund_state();
@@ -2911,7 +3133,7 @@ fn test_cancel_insurance_no_refund_after_expiry() {
 
     // Setup
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
@@ -2920,21 +3142,19 @@ fn test_cancel_insurance_no_refund_after_expiry() {
     let loan_duration = 7_200u64;
     let due_date = env.ledger().timestamp() + loan_duration;
 
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &loan_amount,
-            &collateral_addr,
-            &10_000u64,
-            &loan_duration,
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &loan_amount,
+        &collateral_addr,
+        &10_000u64,
+        &loan_duration,
+    );
 
     mint_to(&env, &token_addr, &borrower, 1_000);
 
     // Purchase insurance
-    client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    client.purchase_loan_insurance(&borrower, &1u64);
 
     let fund_after_purchase = client.get_insurance_fund_state();
     let initial_balance = fund_after_purchase.available_balance;
@@ -2944,7 +3164,7 @@ fn test_cancel_insurance_no_refund_after_expiry() {
         .set_timestamp(due_date + (3 * 24 * 60 * 60) + 1);
 
     // Cancel insurance after expiry
-    let refund = client.cancel_insurance(&borrower, &0u64).unwrap();
+    let refund = client.cancel_insurance(&borrower, &1u64);
 
     // No refund after expiry
     assert_eq!(refund, 0);
@@ -2966,29 +3186,27 @@ fn test_unauthorized_cancel_insurance() {
 
     // Setup
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     mint_to(&env, &token_addr, &borrower, 10_000);
     mint_to(&env, &collateral_addr, &borrower, 50_000);
 
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &5_000u64,
-            &collateral_addr,
-            &10_000u64,
-            &7_200u64,
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &5_000u64,
+        &collateral_addr,
+        &10_000u64,
+        &7_200u64,
+    );
 
     mint_to(&env, &token_addr, &borrower, 1_000);
 
     // Purchase insurance
-    client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    client.purchase_loan_insurance(&borrower, &1u64);
 
     // Try to cancel as unauthorized user
-    let result = client.try_cancel_insurance(&unauthorized_user, &0u64);
+    let result = client.try_cancel_insurance(&unauthorized_user, &1u64);
     assert!(result.is_err());
 }
 
@@ -3022,7 +3240,7 @@ fn test_pause_blocks_deposit() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, token_addr, _collateral_addr, admin) = setup(&env);
-    client.pause(&admin).unwrap();
+    client.pause(&admin);
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 1000);
     let result = client.try_deposit(&depositor, &token_addr, &500u64);
@@ -3038,15 +3256,13 @@ fn test_withdraw_from_insurance_fund() {
     mint_to(&env, &token_addr, &admin, 10_000);
 
     // Deposit to insurance fund
-    client.deposit_to_insurance_fund(&admin, &5_000u64).unwrap();
+    client.deposit_to_insurance_fund(&admin, &5_000u64);
 
     let fund_before = client.get_insurance_fund_state();
     assert_eq!(fund_before.available_balance, 5_000u64);
 
     // Withdraw from insurance fund
-    client
-        .withdraw_from_insurance_fund(&admin, &2_000u64)
-        .unwrap();
+    client.withdraw_from_insurance_fund(&admin, &2_000u64);
 
     let fund_after = client.get_insurance_fund_state();
     assert_eq!(fund_after.available_balance, 3_000u64);
@@ -3063,7 +3279,7 @@ fn test_insurance_lifecycle_complete() {
 
     // Step 1: Lender deposits
     mint_to(&env, &token_addr, &lender, 100_000);
-    client.deposit(&lender, &token_addr, &50_000u64).unwrap();
+    client.deposit(&lender, &token_addr, &50_000u64);
 
     // Step 2: Borrower borrows
     mint_to(&env, &token_addr, &borrower, 10_000);
@@ -3072,28 +3288,24 @@ fn test_insurance_lifecycle_complete() {
     let loan_amount = 5_000u64;
     let loan_duration = 7_200u64;
 
-    client
-        .borrow(
-            &borrower,
-            &token_addr,
-            &loan_amount,
-            &collateral_addr,
-            &10_000u64,
-            &loan_duration,
-        )
-        .unwrap();
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &loan_amount,
+        &collateral_addr,
+        &10_000u64,
+        &loan_duration,
+    );
 
     // Step 3: Borrower purchases insurance
     mint_to(&env, &token_addr, &borrower, 1_000);
-    let premium = client.purchase_loan_insurance(&borrower, &0u64).unwrap();
+    let premium = client.purchase_loan_insurance(&borrower, &1u64);
 
-    assert_eq!(client.is_loan_insured(&0u64), true);
+    assert!(client.is_loan_insured(&1u64));
 
     // Step 4: Fund insurance for potential claims
     mint_to(&env, &token_addr, &admin, 20_000);
-    client
-        .deposit_to_insurance_fund(&admin, &10_000u64)
-        .unwrap();
+    client.deposit_to_insurance_fund(&admin, &10_000u64);
 
     // Step 5: Verify fund state
     let fund = client.get_insurance_fund_state();
@@ -3105,11 +3317,11 @@ fn test_insurance_lifecycle_complete() {
         .set_timestamp(env.ledger().timestamp() + loan_duration + (3 * 24 * 60 * 60) + 1);
 
     // Step 7: Claim insurance
-    let claim_amount = client.claim_insurance(&0u64).unwrap();
+    let claim_amount = client.claim_insurance(&1u64);
     assert_eq!(claim_amount, loan_amount);
 
     // Step 8: Verify final state
-    assert_eq!(client.is_loan_insured(&0u64), false);
+    assert!(!client.is_loan_insured(&1u64));
 
     let final_fund = client.get_insurance_fund_state();
     assert_eq!(final_fund.total_claims_paid, claim_amount);
@@ -3124,11 +3336,11 @@ fn test_unpause_restores_deposit() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, token_addr, _collateral_addr, admin) = setup(&env);
-    client.pause(&admin).unwrap();
-    client.unpause(&admin).unwrap();
+    client.pause(&admin);
+    client.unpause(&admin);
     let depositor = Address::generate(&env);
     mint_to(&env, &token_addr, &depositor, 2000);
-    let shares = client.deposit(&depositor, &token_addr, &2000u64).unwrap();
+    let shares = client.deposit(&depositor, &token_addr, &2000u64);
     assert!(shares > 0);
 }
 
@@ -3153,3 +3365,546 @@ fn test_is_paused_reflects_state_lending() {
     client.unpause(&admin);
     assert!(!client.is_paused());
 }
+
+// ─────────────────────────────────────────────────
+// Price feed
+// ─────────────────────────────────────────────────
+
+const ONE: i128 = 100_000_000_000_000; // 1.00 at the feed's 14 decimals
+
+fn setup_price_feed(
+    env: &Env,
+    client: &LendingContractClient<'_>,
+    admin: &Address,
+) -> mock_oracle::MockOracleClient<'static> {
+    let oracle_id = env.register_contract(None, mock_oracle::MockOracle);
+    client.set_price_oracle(admin, &oracle_id);
+    mock_oracle::MockOracleClient::new(env, &oracle_id)
+}
+
+/// Zero every rate so debt stays at principal while liquidation math is tested.
+fn without_interest(client: &LendingContractClient<'_>, admin: &Address) {
+    client.set_rate_model(admin, &0u32, &8000u32, &0u32, &0u32, &0u32);
+}
+
+#[test]
+fn test_price_feed_values_collateral() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    let oracle = setup_price_feed(&env, &client, &admin);
+    let now = env.ledger().timestamp();
+    oracle.set_price(&token_addr, &ONE, &now);
+    oracle.set_price(&collateral_addr, &(2 * ONE), &now);
+    assert_eq!(client.get_price_oracle(), Some(oracle.address.clone()));
+
+    let depositor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    mint_to(&env, &token_addr, &depositor, 100_000);
+    mint_to(&env, &collateral_addr, &borrower, 100_000);
+    client.deposit(&depositor, &token_addr, &10_000u64);
+
+    // 700 collateral is worth 1400, short of the 1500 required
+    let result = client.try_borrow(
+        &borrower,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &700u64,
+        &(30 * 24 * 60 * 60),
+    );
+    assert_eq!(result, Err(Ok(LendingError::InsufficientCollateral)));
+
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &750u64,
+        &(30 * 24 * 60 * 60),
+    );
+    assert_eq!(client.get_health_factor(&borrower), 15000);
+}
+
+#[test]
+fn test_price_drop_allows_liquidation_at_market_value() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    let oracle = setup_price_feed(&env, &client, &admin);
+    without_interest(&client, &admin);
+    let now = env.ledger().timestamp();
+    oracle.set_price(&token_addr, &ONE, &now);
+    oracle.set_price(&collateral_addr, &ONE, &now);
+
+    let depositor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+    mint_to(&env, &token_addr, &depositor, 100_000);
+    mint_to(&env, &token_addr, &liquidator, 100_000);
+    mint_to(&env, &collateral_addr, &borrower, 100_000);
+    client.deposit(&depositor, &token_addr, &10_000u64);
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &1_500u64,
+        &(24 * 60 * 60),
+    );
+
+    // Past maturity and the grace period, but still fully collateralized
+    env.ledger().set_timestamp(now + 5 * 24 * 60 * 60);
+    oracle.set_price(&token_addr, &ONE, &env.ledger().timestamp());
+    oracle.set_price(&collateral_addr, &ONE, &env.ledger().timestamp());
+    assert_eq!(
+        client.try_liquidate(&liquidator, &borrower, &100u64),
+        Err(Ok(LendingError::InvalidAmount))
+    );
+
+    // Collateral loses a fifth of its value
+    oracle.set_price(&collateral_addr, &(ONE * 4 / 5), &env.ledger().timestamp());
+    assert_eq!(client.get_health_factor(&borrower), 12000);
+
+    // Repaying 100 earns 105 of value (5% bonus), i.e. 131 collateral units
+    let seized = client.liquidate(&liquidator, &borrower, &100u64);
+    assert_eq!(seized, 131);
+    assert_eq!(tok_client(&env, &collateral_addr).balance(&liquidator), 131);
+}
+
+#[test]
+fn test_stale_or_missing_price_blocks_borrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    let oracle = setup_price_feed(&env, &client, &admin);
+    env.ledger().set_timestamp(10_000);
+    oracle.set_price(&token_addr, &ONE, &10_000);
+    oracle.set_price(&collateral_addr, &ONE, &9_000);
+    client.set_max_oracle_age(&admin, &600u64);
+    assert_eq!(client.get_max_oracle_age(), 600);
+
+    let depositor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    mint_to(&env, &token_addr, &depositor, 100_000);
+    mint_to(&env, &collateral_addr, &borrower, 100_000);
+    client.deposit(&depositor, &token_addr, &10_000u64);
+
+    let borrow = |collateral: u64| {
+        client.try_borrow(
+            &borrower,
+            &token_addr,
+            &1_000u64,
+            &collateral_addr,
+            &collateral,
+            &(30 * 24 * 60 * 60),
+        )
+    };
+    assert_eq!(borrow(1_500), Err(Ok(LendingError::OraclePriceStale)));
+
+    oracle.remove_price(&collateral_addr);
+    assert_eq!(borrow(1_500), Err(Ok(LendingError::OraclePriceUnavailable)));
+
+    oracle.set_price(&collateral_addr, &ONE, &10_000);
+    assert!(borrow(1_500).is_ok());
+}
+
+#[test]
+fn test_feed_prices_are_aged_by_default() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    let oracle = setup_price_feed(&env, &client, &admin);
+    assert_eq!(
+        client.get_max_oracle_age(),
+        price_oracle::DEFAULT_MAX_PRICE_AGE
+    );
+    assert_eq!(
+        client.try_set_max_oracle_age(&admin, &0u64),
+        Err(Ok(LendingError::InvalidAmount))
+    );
+
+    env.ledger().set_timestamp(10_000);
+    oracle.set_price(&token_addr, &ONE, &10_000);
+    oracle.set_price(
+        &collateral_addr,
+        &ONE,
+        &(10_000 - price_oracle::DEFAULT_MAX_PRICE_AGE - 1),
+    );
+
+    let depositor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    mint_to(&env, &token_addr, &depositor, 100_000);
+    mint_to(&env, &collateral_addr, &borrower, 100_000);
+    client.deposit(&depositor, &token_addr, &10_000u64);
+
+    assert_eq!(
+        client.try_borrow(
+            &borrower,
+            &token_addr,
+            &1_000u64,
+            &collateral_addr,
+            &1_500u64,
+            &(30 * 24 * 60 * 60),
+        ),
+        Err(Ok(LendingError::OraclePriceStale))
+    );
+}
+
+// ─────────────────────────────────────────────────
+// Governance liquidation parameters
+// ─────────────────────────────────────────────────
+
+#[contract]
+pub struct MockGovernance;
+
+#[contractimpl]
+impl MockGovernance {
+    pub fn set_params(env: Env, bonus: u32, close_factor: u32) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BONUS"), &bonus);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CLOSE"), &close_factor);
+    }
+    pub fn get_liquidation_bonus(env: Env, _asset: Address) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("BONUS"))
+            .unwrap()
+    }
+    pub fn get_close_factor(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("CLOSE"))
+            .unwrap()
+    }
+}
+
+/// Borrow 1000 against 1500 collateral, then move past the grace period and
+/// drop the collateral price so the loan's health factor is `health_bps`.
+fn setup_underwater_loan(
+    env: &Env,
+    health_bps: i128,
+) -> (
+    LendingContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    let (client, token_addr, collateral_addr, admin) = setup(env);
+    let oracle = setup_price_feed(env, &client, &admin);
+    without_interest(&client, &admin);
+    let now = env.ledger().timestamp();
+    oracle.set_price(&token_addr, &ONE, &now);
+    oracle.set_price(&collateral_addr, &ONE, &now);
+
+    let depositor = Address::generate(env);
+    let borrower = Address::generate(env);
+    let liquidator = Address::generate(env);
+    mint_to(env, &token_addr, &depositor, 100_000);
+    mint_to(env, &token_addr, &liquidator, 100_000);
+    mint_to(env, &collateral_addr, &borrower, 1_500);
+    client.deposit(&depositor, &token_addr, &10_000u64);
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &1_500u64,
+        &(24 * 60 * 60),
+    );
+
+    env.ledger().set_timestamp(now + 5 * 24 * 60 * 60);
+    let now = env.ledger().timestamp();
+    oracle.set_price(&token_addr, &ONE, &now);
+    oracle.set_price(&collateral_addr, &(ONE * health_bps / 15000), &now);
+    (client, collateral_addr, admin, borrower, liquidator)
+}
+
+#[test]
+fn test_liquidation_uses_governance_bonus_and_close_factor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, admin, borrower, liquidator) = setup_underwater_loan(&env, 12000);
+    let governance = env.register_contract(None, MockGovernance);
+    MockGovernanceClient::new(&env, &governance).set_params(&1000, &5000);
+    client.set_governance_contract(&admin, &governance);
+
+    // Close factor of 50% caps the repayment at 500
+    assert_eq!(
+        client.try_liquidate(&liquidator, &borrower, &600u64),
+        Err(Ok(LendingError::LiquidationExceedsCloseFactor))
+    );
+
+    // 500 repaid plus a 10% bonus is 550 of value, i.e. 687 collateral units
+    let seized = client.liquidate(&liquidator, &borrower, &500u64);
+    assert_eq!(seized, 687);
+    assert_eq!(tok_client(&env, &collateral_addr).balance(&liquidator), 687);
+
+    let loan = client.get_loan(&borrower).unwrap();
+    assert_eq!(loan.principal, 500);
+    assert_eq!(loan.collateral_amount, 813);
+    assert!(client.get_health_factor(&borrower) > 12000);
+}
+
+#[test]
+fn test_liquidation_rejected_when_it_worsens_health() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, borrower, liquidator) = setup_underwater_loan(&env, 10200);
+
+    // Below 105% health, a 5% bonus takes more value than it repays
+    assert_eq!(
+        client.try_liquidate(&liquidator, &borrower, &100u64),
+        Err(Ok(LendingError::LiquidationWorsensHealth))
+    );
+    assert_eq!(client.get_loan(&borrower).unwrap().principal, 1_000);
+}
+
+#[test]
+fn test_full_liquidation_closes_loan_and_returns_surplus() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, collateral_addr, _, borrower, liquidator) = setup_underwater_loan(&env, 12000);
+
+    // 1050 of value at 0.80 is 1312 units; the remaining 188 go back to the borrower
+    let seized = client.liquidate(&liquidator, &borrower, &1_000u64);
+    assert_eq!(seized, 1_312);
+    assert!(client.get_loan(&borrower).is_none());
+    assert!(client.get_user_loan_ids(&borrower).is_empty());
+    assert_eq!(tok_client(&env, &collateral_addr).balance(&borrower), 188);
+}
+
+#[test]
+fn test_liquidation_of_sold_loan_pays_nft_holder() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    let oracle = setup_price_feed(&env, &client, &admin);
+    without_interest(&client, &admin);
+    setup_claim_nft(&env, &client, &admin);
+    let now = env.ledger().timestamp();
+    oracle.set_price(&token_addr, &ONE, &now);
+    oracle.set_price(&collateral_addr, &ONE, &now);
+
+    let depositor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+    mint_to(&env, &token_addr, &depositor, 100_000);
+    mint_to(&env, &token_addr, &buyer, 100_000);
+    mint_to(&env, &token_addr, &liquidator, 100_000);
+    mint_to(&env, &collateral_addr, &borrower, 1_500);
+    client.deposit(&depositor, &token_addr, &10_000u64);
+    let loan_id = client.borrow(
+        &borrower,
+        &token_addr,
+        &1_000u64,
+        &collateral_addr,
+        &1_500u64,
+        &(24 * 60 * 60),
+    );
+    client.sell_loan_claim(&admin, &buyer, &loan_id);
+
+    env.ledger().set_timestamp(now + 5 * 24 * 60 * 60);
+    let now = env.ledger().timestamp();
+    oracle.set_price(&token_addr, &ONE, &now);
+    oracle.set_price(&collateral_addr, &(ONE * 12000 / 15000), &now);
+
+    let pool_before = client.get_pool_state(&token_addr);
+    client.liquidate(&liquidator, &borrower, &1_000u64);
+    assert_eq!(tok_client(&env, &token_addr).balance(&buyer), 100_000);
+    assert_eq!(client.get_pool_state(&token_addr), pool_before);
+    assert!(client.get_loan(&borrower).is_none());
+    assert_eq!(client.get_loan_payee(&loan_id), None);
+}
+
+/// Borrow 600 against 1000 of the primary collateral plus 1000 of a second,
+/// riskier token (200% ratio) posted into the basket. Both tokens are priced
+/// at par.
+fn setup_basket_loan(
+    env: &Env,
+) -> (
+    LendingContractClient<'_>,
+    mock_oracle::MockOracleClient<'static>,
+    Address,
+    Address,
+    Address,
+) {
+    let (client, token_addr, collateral_addr, admin) = setup(env);
+    let oracle = setup_price_feed(env, &client, &admin);
+    without_interest(&client, &admin);
+    let second = create_token_addr(env);
+    client.whitelist_collateral(&admin, &second);
+    client.set_asset_collateral_ratio(&admin, &second, &20000u32);
+    let now = env.ledger().timestamp();
+    oracle.set_price(&token_addr, &ONE, &now);
+    oracle.set_price(&collateral_addr, &ONE, &now);
+    oracle.set_price(&second, &ONE, &now);
+
+    let depositor = Address::generate(env);
+    let borrower = Address::generate(env);
+    mint_to(env, &token_addr, &depositor, 100_000);
+    mint_to(env, &collateral_addr, &borrower, 10_000);
+    mint_to(env, &second, &borrower, 10_000);
+    client.deposit(&depositor, &token_addr, &10_000u64);
+    client.borrow(
+        &borrower,
+        &token_addr,
+        &600u64,
+        &collateral_addr,
+        &1_000u64,
+        &(30 * 24 * 60 * 60),
+    );
+    client.add_collateral(&borrower, &second, &1_000u64);
+    (client, oracle, collateral_addr, second, borrower)
+}
+
+#[test]
+fn test_collateral_basket_counts_each_asset_at_its_ratio() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _oracle, collateral_addr, second, borrower) = setup_basket_loan(&env);
+
+    assert_eq!(client.get_asset_collateral_ratio_bps(&second), 20000);
+    assert_eq!(
+        client.get_asset_collateral_ratio_bps(&collateral_addr),
+        15000
+    );
+    let basket = client.get_collateral_basket(&borrower);
+    assert_eq!(basket.len(), 2);
+    assert_eq!(basket.get(1).unwrap().token, second);
+    assert_eq!(basket.get(1).unwrap().amount, 1_000);
+
+    // The second token counts at 1.5/2.0 of its value: 1000 + 750 of
+    // weighted collateral against 600 of debt
+    assert_eq!(client.get_health_factor(&borrower), 29166);
+
+    // Capacity is 1000/1.5 + 1000/2.0 = 1166; 566 of it is spare, so
+    // 1132 units of the second token may be withdrawn, capped by the 1000 held
+    assert_eq!(
+        client.get_max_withdrawable_collateral(&borrower, &second),
+        1_000
+    );
+    // Without the second token the primary alone must cover 600 * 1.5 = 900
+    client.withdraw_collateral(&borrower, &second, &1_000u64);
+    assert_eq!(client.get_collateral_basket(&borrower).len(), 1);
+    assert_eq!(
+        client.get_max_withdrawable_collateral(&borrower, &collateral_addr),
+        100
+    );
+    assert_eq!(
+        client.try_withdraw_collateral(&borrower, &collateral_addr, &101u64),
+        Err(Ok(LendingError::InsufficientCollateral))
+    );
+    client.withdraw_collateral(&borrower, &collateral_addr, &100u64);
+    assert_eq!(client.get_loan(&borrower).unwrap().collateral_amount, 900);
+}
+
+#[test]
+fn test_add_collateral_rejects_unlisted_token_and_missing_loan() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _oracle, collateral_addr, _second, borrower) = setup_basket_loan(&env);
+
+    let unlisted = create_token_addr(&env);
+    assert_eq!(
+        client.try_add_collateral(&borrower, &unlisted, &10u64),
+        Err(Ok(LendingError::CollateralNotWhitelisted))
+    );
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_add_collateral(&stranger, &collateral_addr, &10u64),
+        Err(Ok(LendingError::NoOpenLoan))
+    );
+
+    // Topping up the primary token grows the loan's own collateral
+    client.add_collateral(&borrower, &collateral_addr, &500u64);
+    assert_eq!(client.get_loan(&borrower).unwrap().collateral_amount, 1_500);
+    assert_eq!(client.get_collateral_basket(&borrower).len(), 2);
+}
+
+#[test]
+fn test_liquidation_seizes_basket_after_primary_collateral() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, oracle, collateral_addr, second, borrower) = setup_basket_loan(&env);
+    let token_addr = client.get_loan(&borrower).unwrap().asset;
+    let liquidator = Address::generate(&env);
+    mint_to(&env, &token_addr, &liquidator, 100_000);
+
+    // Primary collateral crashes to 0.10, the second token falls to 0.62:
+    // 100 + 620 * 0.75 = 565 of weighted collateral against 600 of debt
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 35 * 24 * 60 * 60);
+    let now = env.ledger().timestamp();
+    oracle.set_price(&token_addr, &ONE, &now);
+    oracle.set_price(&collateral_addr, &(ONE / 10), &now);
+    oracle.set_price(&second, &(ONE * 62 / 100), &now);
+    assert_eq!(client.get_health_factor(&borrower), 9416);
+
+    // 300 repaid plus the 5% bonus is 315 of value: all 100 of primary value,
+    // then 215 of value (346 units) from the basket
+    client.liquidate(&liquidator, &borrower, &300u64);
+    assert_eq!(
+        tok_client(&env, &collateral_addr).balance(&liquidator),
+        1_000
+    );
+    assert_eq!(tok_client(&env, &second).balance(&liquidator), 346);
+
+    let loan = client.get_loan(&borrower).unwrap();
+    assert_eq!(loan.principal, 300);
+    assert_eq!(loan.collateral_amount, 0);
+    let basket = client.get_collateral_basket(&borrower);
+    assert_eq!(basket.get(1).unwrap().amount, 654);
+    assert!(client.get_health_factor(&borrower) > 9416);
+}
+
+#[test]
+fn test_asset_collateral_ratio_governs_borrow_and_liquidation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_addr, collateral_addr, admin) = setup(&env);
+    let oracle = setup_price_feed(&env, &client, &admin);
+    without_interest(&client, &admin);
+    client.set_asset_collateral_ratio(&admin, &collateral_addr, &20000u32);
+    let now = env.ledger().timestamp();
+    oracle.set_price(&token_addr, &ONE, &now);
+    oracle.set_price(&collateral_addr, &ONE, &now);
+
+    let depositor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    mint_to(&env, &token_addr, &depositor, 100_000);
+    mint_to(&env, &collateral_addr, &borrower, 10_000);
+    client.deposit(&depositor, &token_addr, &10_000u64);
+    let borrow = |collateral: u64| {
+        client.try_borrow(
+            &borrower,
+            &token_addr,
+            &1_000u64,
+            &collateral_addr,
+            &collateral,
+            &(30 * 24 * 60 * 60),
+        )
+    };
+    // The global 150% would accept 1500; the token's own 200% needs 2000
+    assert_eq!(borrow(1_500), Err(Ok(LendingError::InsufficientCollateral)));
+    assert!(borrow(2_000).is_ok());
+    assert_eq!(client.get_health_factor(&borrower), 15000);
+
+    // At 0.90 the collateral is still worth 180% of the debt, but weighted
+    // at 1.5/2.0 it falls below the liquidation threshold
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 35 * 24 * 60 * 60);
+    let now = env.ledger().timestamp();
+    oracle.set_price(&token_addr, &ONE, &now);
+    oracle.set_price(&collateral_addr, &(ONE * 9 / 10), &now);
+    assert_eq!(client.get_health_factor(&borrower), 13500);
+    let liquidator = Address::generate(&env);
+    mint_to(&env, &token_addr, &liquidator, 100_000);
+    client.liquidate(&liquidator, &borrower, &500u64);
+    assert_eq!(client.get_loan(&borrower).unwrap().principal, 500);
+}
@@ -1,13 +1,18 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Symbol};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, contracttype, Address, Env, String, Symbol,
+};
 
 const DEFAULT_APPROVAL_EXPIRATION_SECONDS: u64 = 60 * 60 * 24 * 7; // 7 days
+const MAX_ROYALTY_BPS: u32 = 10_000;
+const TOKEN_URI_BUFFER_LEN: usize = 320;
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LoanMetadata {
     pub loan_id: u64,
     pub borrower: Address,
+    pub asset: Address,
     pub principal: u64,
     pub collateral_amount: u64,
     pub collateral_token: Address,
@@ -28,6 +33,35 @@ pub enum DataKey {
     ReentrancyGuard,
     Transferable(u64),   // Loan ID -> bool
     MetadataLocked(u64), // Loan ID -> bool; set true after mint to enforce immutability
+    ClaimRegistry,       // Lending contract told about every holder change
+    Royalty,             // -> RoyaltyConfig
+}
+
+/// Live state of the loan behind an NFT, as reported by the claim registry.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanClaim {
+    pub loan_id: u64,
+    pub asset: Address,
+    pub outstanding: u64,
+    pub due_date: u64,
+    pub holder: Address,
+}
+
+/// Royalty owed on secondary sales, in basis points of the sale price.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoyaltyConfig {
+    pub receiver: Address,
+    pub bps: u32,
+}
+
+/// The lending contract that pays a loan's lender-side cash flows to
+/// whoever holds its NFT.
+#[contractclient(name = "ClaimRegistryClient")]
+pub trait ClaimRegistry {
+    fn on_loan_claim_transfer(env: Env, loan_id: u64, to: Address);
+    fn get_loan_claim(env: Env, loan_id: u64) -> Option<LoanClaim>;
 }
 
 #[contracttype]
@@ -67,10 +101,10 @@ impl LoanNFT {
         env.storage()
             .persistent()
             .set(&DataKey::Owner(loan_id), &to);
-        // Active loan NFTs are non-transferable by default; admin must explicitly unlock after settlement
+        // Loan NFTs are tradable claims; admin can freeze one with `set_transferable`
         env.storage()
             .persistent()
-            .set(&DataKey::Transferable(loan_id), &false);
+            .set(&DataKey::Transferable(loan_id), &true);
 
         // Lock metadata to enforce immutability after minting
         env.storage()
@@ -166,6 +200,7 @@ impl LoanNFT {
         }
 
         Self::do_transfer(&env, from.clone(), to.clone(), loan_id);
+        Self::notify_claim_registry(&env, &from, &to, loan_id);
         Self::exit_reentrancy_guard(&env);
     }
 
@@ -203,6 +238,7 @@ impl LoanNFT {
         }
 
         Self::do_transfer(&env, from.clone(), to.clone(), loan_id);
+        Self::notify_claim_registry(&env, &from, &to, loan_id);
         Self::exit_reentrancy_guard(&env);
     }
 
@@ -287,10 +323,15 @@ impl LoanNFT {
             .unwrap_or(0)
     }
 
+    /// An explicitly set URI wins; otherwise, with a claim registry set, a
+    /// JSON data URI describing the loan as it stands now.
     pub fn token_uri(env: Env, loan_id: u64) -> String {
-        env.storage()
-            .persistent()
-            .get(&DataKey::TokenUri(loan_id))
+        if let Some(uri) = env.storage().persistent().get(&DataKey::TokenUri(loan_id)) {
+            return uri;
+        }
+        Self::get_claim_registry(env.clone())
+            .and_then(|registry| ClaimRegistryClient::new(&env, &registry).get_loan_claim(&loan_id))
+            .map(|claim| Self::claim_uri(&env, &claim))
             .unwrap_or_else(|| String::from_str(&env, ""))
     }
 
@@ -319,6 +360,48 @@ impl LoanNFT {
         env.storage().persistent().get(&DataKey::Owner(loan_id))
     }
 
+    // --- Lending Integration ---
+
+    /// Register the lending contract whose lender-side payee follows the NFT
+    /// holder. Transfers out of the registry itself are not reported back to
+    /// it, since it initiates those and records the new holder on its own.
+    pub fn set_claim_registry(env: Env, registry: Address) {
+        Self::check_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::ClaimRegistry, &registry);
+    }
+
+    pub fn get_claim_registry(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::ClaimRegistry)
+    }
+
+    // --- Royalties ---
+
+    pub fn set_royalty(env: Env, receiver: Address, bps: u32) {
+        Self::check_admin(&env);
+        if bps > MAX_ROYALTY_BPS {
+            panic!("Royalty exceeds sale price");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Royalty, &RoyaltyConfig { receiver, bps });
+    }
+
+    /// Receiver and amount of the royalty due on selling `loan_id` for
+    /// `sale_price`, or `None` if no royalty is configured.
+    pub fn royalty_info(env: Env, loan_id: u64, sale_price: i128) -> Option<(Address, i128)> {
+        if !env.storage().persistent().has(&DataKey::Metadata(loan_id)) {
+            panic!("NFT does not exist");
+        }
+        let config: RoyaltyConfig = env.storage().instance().get(&DataKey::Royalty)?;
+        let amount = sale_price
+            .checked_mul(config.bps as i128)
+            .expect("Royalty overflow")
+            / MAX_ROYALTY_BPS as i128;
+        Some((config.receiver, amount))
+    }
+
     // --- Transfer Restrictions ---
     pub fn set_transferable(env: Env, loan_id: u64, is_transferable: bool) {
         Self::check_admin(&env);
@@ -373,6 +456,30 @@ impl LoanNFT {
         Self::emit_transfer_event(env, from, to, loan_id);
     }
 
+    fn notify_claim_registry(env: &Env, from: &Address, to: &Address, loan_id: u64) {
+        if let Some(registry) = Self::get_claim_registry(env.clone()) {
+            if *from != registry {
+                ClaimRegistryClient::new(env, &registry).on_loan_claim_transfer(&loan_id, to);
+            }
+        }
+    }
+
+    fn claim_uri(env: &Env, claim: &LoanClaim) -> String {
+        let mut uri = UriBuffer::new();
+        uri.push_str("data:application/json,{\"loan_id\":");
+        uri.push_u64(claim.loan_id);
+        uri.push_str(",\"asset\":\"");
+        uri.push_string(&claim.asset.to_string());
+        uri.push_str("\",\"outstanding\":");
+        uri.push_u64(claim.outstanding);
+        uri.push_str(",\"due_date\":");
+        uri.push_u64(claim.due_date);
+        uri.push_str(",\"holder\":\"");
+        uri.push_string(&claim.holder.to_string());
+        uri.push_str("\"}");
+        String::from_bytes(env, uri.as_bytes())
+    }
+
     fn check_admin(env: &Env) {
         let admin: Address = env
             .storage()
@@ -423,5 +530,61 @@ impl LoanNFT {
     }
 }
 
+/// Fixed-size buffer for assembling `token_uri` strings without an allocator.
+struct UriBuffer {
+    bytes: [u8; TOKEN_URI_BUFFER_LEN],
+    len: usize,
+}
+
+impl UriBuffer {
+    fn new() -> Self {
+        UriBuffer {
+            bytes: [0; TOKEN_URI_BUFFER_LEN],
+            len: 0,
+        }
+    }
+
+    fn push_bytes(&mut self, bytes: &[u8]) {
+        let end = self.len + bytes.len();
+        if end > TOKEN_URI_BUFFER_LEN {
+            panic!("Token URI too long");
+        }
+        self.bytes[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+    }
+
+    fn push_str(&mut self, s: &str) {
+        self.push_bytes(s.as_bytes());
+    }
+
+    fn push_string(&mut self, s: &String) {
+        let len = s.len() as usize;
+        let end = self.len + len;
+        if end > TOKEN_URI_BUFFER_LEN {
+            panic!("Token URI too long");
+        }
+        s.copy_into_slice(&mut self.bytes[self.len..end]);
+        self.len = end;
+    }
+
+    fn push_u64(&mut self, mut value: u64) {
+        let mut digits = [0u8; 20];
+        let mut start = digits.len();
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (value % 10) as u8;
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+        self.push_bytes(&digits[start..]);
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
 #[cfg(test)]
 mod test;
@@ -1,9 +1,63 @@
-use crate::{LoanMetadata, LoanNFT, LoanNFTClient};
+extern crate std;
+use std::string::ToString;
+
+use crate::{LoanClaim, LoanMetadata, LoanNFT, LoanNFTClient};
 use soroban_sdk::{
+    contract, contractimpl,
     testutils::{Address as _, Events as _},
     Address, Env, IntoVal, String, Symbol,
 };
 
+/// Stands in for the lending contract: remembers the last reported holder
+/// and reports a fixed outstanding balance.
+#[contract]
+struct MockClaimRegistry;
+
+#[contractimpl]
+impl MockClaimRegistry {
+    pub fn set_asset(env: Env, asset: Address) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "asset"), &asset);
+    }
+
+    pub fn on_loan_claim_transfer(env: Env, loan_id: u64, to: Address) {
+        env.storage().persistent().set(&loan_id, &to);
+    }
+
+    pub fn holder(env: Env, loan_id: u64) -> Option<Address> {
+        env.storage().persistent().get(&loan_id)
+    }
+
+    pub fn get_loan_claim(env: Env, loan_id: u64) -> Option<LoanClaim> {
+        let holder: Address = env.storage().persistent().get(&loan_id)?;
+        Some(LoanClaim {
+            loan_id,
+            asset: env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "asset"))
+                .unwrap(),
+            outstanding: 1050,
+            due_date: 86400,
+            holder,
+        })
+    }
+}
+
+fn metadata(env: &Env, loan_id: u64, borrower: &Address) -> LoanMetadata {
+    let token = Address::generate(env);
+    LoanMetadata {
+        loan_id,
+        borrower: borrower.clone(),
+        asset: token.clone(),
+        principal: 1000,
+        collateral_amount: 500,
+        collateral_token: token,
+        due_date: 86400,
+    }
+}
+
 #[test]
 fn test_mint_and_burn() {
     let env = Env::default();
@@ -21,6 +75,7 @@ fn test_mint_and_burn() {
     let metadata = LoanMetadata {
         loan_id: 1,
         borrower: user.clone(),
+        asset: token.clone(),
         principal: 1000,
         collateral_amount: 500,
         collateral_token: token.clone(),
@@ -58,6 +113,7 @@ fn test_transfer() {
     let metadata = LoanMetadata {
         loan_id: 2,
         borrower: user1.clone(),
+        asset: token.clone(),
         principal: 1000,
         collateral_amount: 500,
         collateral_token: token.clone(),
@@ -90,6 +146,7 @@ fn test_approve_and_transfer_from() {
     let metadata = LoanMetadata {
         loan_id: 3,
         borrower: user1.clone(),
+        asset: token.clone(),
         principal: 1000,
         collateral_amount: 500,
         collateral_token: token.clone(),
@@ -126,6 +183,7 @@ fn test_approval_for_all() {
     let metadata = LoanMetadata {
         loan_id: 4,
         borrower: user1.clone(),
+        asset: token.clone(),
         principal: 100,
         collateral_amount: 50,
         collateral_token: token.clone(),
@@ -158,6 +216,7 @@ fn test_operator_can_approve_on_behalf_of_owner() {
     let metadata = LoanMetadata {
         loan_id: 44,
         borrower: owner.clone(),
+        asset: token.clone(),
         principal: 100,
         collateral_amount: 50,
         collateral_token: token,
@@ -204,6 +263,7 @@ fn test_approve_rejects_current_owner() {
     let metadata = LoanMetadata {
         loan_id: 45,
         borrower: owner.clone(),
+        asset: token.clone(),
         principal: 100,
         collateral_amount: 50,
         collateral_token: token,
@@ -232,6 +292,7 @@ fn test_transfer_events_use_standard_names() {
     let metadata = LoanMetadata {
         loan_id: 46,
         borrower: owner.clone(),
+        asset: token.clone(),
         principal: 100,
         collateral_amount: 50,
         collateral_token: token,
@@ -285,6 +346,7 @@ fn test_transfer_restriction() {
     let metadata = LoanMetadata {
         loan_id: 5,
         borrower: user1.clone(),
+        asset: token.clone(),
         principal: 100,
         collateral_amount: 50,
         collateral_token: token.clone(),
@@ -312,6 +374,7 @@ fn test_token_uri() {
     let metadata = LoanMetadata {
         loan_id: 6,
         borrower: user1.clone(),
+        asset: token.clone(),
         principal: 100,
         collateral_amount: 50,
         collateral_token: token.clone(),
@@ -324,3 +387,107 @@ fn test_token_uri() {
 
     assert_eq!(client.token_uri(&6), uri);
 }
+
+#[test]
+fn test_transfers_report_new_holder_to_claim_registry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, LoanNFT);
+    let client = LoanNFTClient::new(&env, &contract_id);
+    let registry_id = env.register_contract(None, MockClaimRegistry);
+    let registry = MockClaimRegistryClient::new(&env, &registry_id);
+
+    let admin = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let next_buyer = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.set_claim_registry(&registry_id);
+    assert_eq!(client.get_claim_registry(), Some(registry_id.clone()));
+    client.mint(&registry_id, &metadata(&env, 7, &borrower));
+
+    // The registry hands the claim out itself and is not called back.
+    client.transfer(&registry_id, &buyer, &7);
+    assert_eq!(registry.holder(&7), None);
+
+    client.transfer(&buyer, &next_buyer, &7);
+    assert_eq!(registry.holder(&7), Some(next_buyer.clone()));
+
+    client.approve(&next_buyer, &operator, &7);
+    client.transfer_from(&operator, &next_buyer, &buyer, &7);
+    assert_eq!(registry.holder(&7), Some(buyer));
+}
+
+#[test]
+fn test_token_uri_reflects_live_loan_state() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, LoanNFT);
+    let client = LoanNFTClient::new(&env, &contract_id);
+    let registry_id = env.register_contract(None, MockClaimRegistry);
+    let registry = MockClaimRegistryClient::new(&env, &registry_id);
+
+    let admin = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    let holder = Address::generate(&env);
+    let asset = Address::generate(&env);
+    registry.set_asset(&asset);
+
+    client.initialize(&admin);
+    client.mint(&registry_id, &metadata(&env, 8, &borrower));
+    assert_eq!(client.token_uri(&8), String::from_str(&env, ""));
+
+    client.set_claim_registry(&registry_id);
+    client.transfer(&registry_id, &holder, &8);
+    registry.on_loan_claim_transfer(&8, &holder);
+
+    let mut expected =
+        std::string::String::from("data:application/json,{\"loan_id\":8,\"asset\":\"");
+    expected.push_str(&asset.to_string().to_string());
+    expected.push_str("\",\"outstanding\":1050,\"due_date\":86400,\"holder\":\"");
+    expected.push_str(&holder.to_string().to_string());
+    expected.push_str("\"}");
+    assert_eq!(client.token_uri(&8), String::from_str(&env, &expected));
+
+    // An explicit URI still takes precedence.
+    let uri = String::from_str(&env, "https://example.com/nft/8");
+    client.set_token_uri(&8, &uri);
+    assert_eq!(client.token_uri(&8), uri);
+}
+
+#[test]
+fn test_royalty_info() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, LoanNFT);
+    let client = LoanNFTClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let receiver = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.mint(&owner, &metadata(&env, 9, &owner));
+    assert_eq!(client.royalty_info(&9, &10_000), None);
+
+    client.set_royalty(&receiver, &250);
+    assert_eq!(client.royalty_info(&9, &10_000), Some((receiver, 250)));
+}
+
+#[test]
+#[should_panic(expected = "Royalty exceeds sale price")]
+fn test_royalty_capped_at_sale_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, LoanNFT);
+    let client = LoanNFTClient::new(&env, &contract_id);
+
+    client.initialize(&Address::generate(&env));
+    client.set_royalty(&Address::generate(&env), &10_001);
+}
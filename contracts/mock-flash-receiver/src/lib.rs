@@ -0,0 +1,173 @@
+#![no_std]
+
+//! Reference flash-loan receiver for tests.
+//!
+//! Repays honestly by default; the other modes misbehave in the ways a
+//! hostile receiver would, so the lending contract's safety checks can be
+//! exercised.
+
+use flash_loan::FlashLoan;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, token, vec, Address, Env, IntoVal, Symbol, Vec,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReceiverMode {
+    /// Return principal and fee.
+    Repay,
+    /// Return the principal but keep the fee.
+    RepayPrincipalOnly,
+    /// Return principal and fee in another token.
+    RepayWith(Address),
+    /// Deposit the borrowed funds back into the pool instead of repaying.
+    Redeposit,
+    /// Book the borrowed funds into the pool's bad-debt reserve and pay only
+    /// the fee. Requires the receiver to be the lending contract's admin.
+    ReplenishReserve,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MockFlashReceiverDataKey {
+    Lender,
+    Asset,
+    Mode,
+}
+
+#[contract]
+pub struct MockFlashReceiver;
+
+#[contractimpl]
+impl MockFlashReceiver {
+    /// `asset` is the asset single-asset callbacks are for.
+    pub fn initialize(env: Env, lender: Address, asset: Address) {
+        env.storage()
+            .instance()
+            .set(&MockFlashReceiverDataKey::Lender, &lender);
+        env.storage()
+            .instance()
+            .set(&MockFlashReceiverDataKey::Asset, &asset);
+    }
+
+    pub fn set_mode(env: Env, mode: ReceiverMode) {
+        env.storage()
+            .instance()
+            .set(&MockFlashReceiverDataKey::Mode, &mode);
+    }
+
+    pub fn execute_operation(env: Env, amount: u64, fee: u64, _initiator: Address) {
+        let asset: Address = env
+            .storage()
+            .instance()
+            .get(&MockFlashReceiverDataKey::Asset)
+            .unwrap();
+        settle(&env, &asset, amount, fee);
+    }
+
+    pub fn execute_batch(env: Env, loans: Vec<FlashLoan>, _initiator: Address) {
+        for loan in loans.iter() {
+            settle(&env, &loan.asset, loan.amount, loan.fee);
+        }
+    }
+}
+
+/// Hand `amount` of `asset`, due back with `fee`, to the lender as the
+/// current mode dictates.
+fn settle(env: &Env, asset: &Address, amount: u64, fee: u64) {
+    let lender: Address = env
+        .storage()
+        .instance()
+        .get(&MockFlashReceiverDataKey::Lender)
+        .unwrap();
+    let mode = env
+        .storage()
+        .instance()
+        .get(&MockFlashReceiverDataKey::Mode)
+        .unwrap_or(ReceiverMode::Repay);
+    let this = env.current_contract_address();
+
+    match mode {
+        ReceiverMode::Repay => pay(env, asset, &lender, amount + fee),
+        ReceiverMode::RepayPrincipalOnly => pay(env, asset, &lender, amount),
+        ReceiverMode::RepayWith(token) => pay(env, &token, &lender, amount + fee),
+        ReceiverMode::Redeposit => {
+            env.invoke_contract::<u64>(
+                &lender,
+                &Symbol::new(env, "deposit"),
+                vec![
+                    env,
+                    this.into_val(env),
+                    asset.into_val(env),
+                    amount.into_val(env),
+                ],
+            );
+            pay(env, asset, &lender, fee);
+        }
+        ReceiverMode::ReplenishReserve => {
+            env.invoke_contract::<u64>(
+                &lender,
+                &Symbol::new(env, "replenish_bad_debt_reserve"),
+                vec![
+                    env,
+                    this.into_val(env),
+                    asset.into_val(env),
+                    amount.into_val(env),
+                ],
+            );
+            pay(env, asset, &lender, fee);
+        }
+    }
+}
+
+fn pay(env: &Env, token: &Address, to: &Address, amount: u64) {
+    token::Client::new(env, token).transfer(&env.current_contract_address(), to, &(amount as i128));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flash_loan::FlashLoanReceiverClient;
+    use soroban_sdk::testutils::Address as _;
+
+    #[test]
+    fn repays_each_loan_through_the_receiver_client() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let lender = Address::generate(&env);
+        let asset = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let other = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+
+        let receiver = env.register_contract(None, MockFlashReceiver);
+        MockFlashReceiverClient::new(&env, &receiver).initialize(&lender, &asset);
+        token::StellarAssetClient::new(&env, &asset).mint(&receiver, &1_000);
+        token::StellarAssetClient::new(&env, &other).mint(&receiver, &1_000);
+
+        let client = FlashLoanReceiverClient::new(&env, &receiver);
+        client.execute_operation(&100, &1, &lender);
+        assert_eq!(token::Client::new(&env, &asset).balance(&lender), 101);
+
+        client.execute_batch(
+            &vec![
+                &env,
+                FlashLoan {
+                    asset: asset.clone(),
+                    amount: 200,
+                    fee: 2,
+                },
+                FlashLoan {
+                    asset: other.clone(),
+                    amount: 300,
+                    fee: 3,
+                },
+            ],
+            &lender,
+        );
+        assert_eq!(token::Client::new(&env, &asset).balance(&lender), 303);
+        assert_eq!(token::Client::new(&env, &other).balance(&lender), 303);
+    }
+}
@@ -0,0 +1,115 @@
+#![no_std]
+
+//! Settable SEP-40 price feed for tests.
+
+use price_oracle::{Asset, PriceData};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+/// Feed decimals used until `initialize` is called (Reflector's default).
+const DEFAULT_DECIMALS: u32 = 14;
+/// Seconds between price updates reported by `resolution`.
+const RESOLUTION: u32 = 300;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MockOracleDataKey {
+    Base,
+    Decimals,
+    Price(Asset),
+}
+
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    pub fn initialize(env: Env, base: Asset, decimals: u32) {
+        env.storage()
+            .instance()
+            .set(&MockOracleDataKey::Base, &base);
+        env.storage()
+            .instance()
+            .set(&MockOracleDataKey::Decimals, &decimals);
+    }
+
+    /// Set the price of a Stellar token, observed at `timestamp`.
+    pub fn set_price(env: Env, token: Address, price: i128, timestamp: u64) {
+        env.storage().instance().set(
+            &MockOracleDataKey::Price(Asset::Stellar(token)),
+            &PriceData { price, timestamp },
+        );
+    }
+
+    pub fn remove_price(env: Env, token: Address) {
+        env.storage()
+            .instance()
+            .remove(&MockOracleDataKey::Price(Asset::Stellar(token)));
+    }
+
+    pub fn base(env: Env) -> Asset {
+        env.storage()
+            .instance()
+            .get(&MockOracleDataKey::Base)
+            .unwrap_or(Asset::Other(soroban_sdk::symbol_short!("USD")))
+    }
+
+    pub fn decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&MockOracleDataKey::Decimals)
+            .unwrap_or(DEFAULT_DECIMALS)
+    }
+
+    pub fn resolution(_env: Env) -> u32 {
+        RESOLUTION
+    }
+
+    pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+        env.storage()
+            .instance()
+            .get(&MockOracleDataKey::Price(asset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use price_oracle::PriceOracleClient;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+
+    #[test]
+    fn serves_prices_through_the_sep40_client() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000);
+        let oracle = env.register_contract(None, MockOracle);
+        let mock = MockOracleClient::new(&env, &oracle);
+        let token = Address::generate(&env);
+
+        let feed = PriceOracleClient::new(&env, &oracle);
+        assert_eq!(feed.decimals(), DEFAULT_DECIMALS);
+        assert_eq!(feed.lastprice(&Asset::Stellar(token.clone())), None);
+
+        mock.set_price(&token, &5_000, &900);
+        assert_eq!(
+            feed.lastprice(&Asset::Stellar(token.clone())),
+            Some(PriceData {
+                price: 5_000,
+                timestamp: 900
+            })
+        );
+        assert_eq!(
+            price_oracle::fresh_price(&env, &oracle, &token, 100),
+            Ok(5_000)
+        );
+        assert_eq!(
+            price_oracle::fresh_price(&env, &oracle, &token, 50),
+            Err(price_oracle::PriceError::Stale)
+        );
+
+        mock.remove_price(&token);
+        assert_eq!(
+            price_oracle::fresh_price(&env, &oracle, &token, 0),
+            Err(price_oracle::PriceError::Unavailable)
+        );
+    }
+}
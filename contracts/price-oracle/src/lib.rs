@@ -0,0 +1,169 @@
+#![no_std]
+
+//! SEP-40 price feed interface shared by the InheritX lending contracts.
+//!
+//! A feed quotes every asset in a single base currency (e.g. USD) with a
+//! fixed number of decimals. Token amounts are converted into that quote
+//! currency using the token's own decimals, so collateral and debt in
+//! different tokens can be compared by value rather than by raw amount.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+/// Decimals assumed for a token that has not been configured (the Stellar
+/// asset default).
+pub const DEFAULT_ASSET_DECIMALS: u32 = 7;
+
+/// Largest token decimals accepted.
+pub const MAX_ASSET_DECIMALS: u32 = 18;
+
+/// Oldest feed price, in seconds, accepted until an admin configures
+/// another maximum.
+pub const DEFAULT_MAX_PRICE_AGE: u64 = 3600;
+
+/// SEP-40 asset identifier.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// SEP-40 price record. `price` is scaled by the feed's `decimals()`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// The subset of SEP-40 the contracts rely on.
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn base(env: Env) -> Asset;
+    fn decimals(env: Env) -> u32;
+    fn resolution(env: Env) -> u32;
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PriceError {
+    /// The feed has no positive price for the asset, or the call failed.
+    Unavailable,
+    /// The latest price is older than the allowed age.
+    Stale,
+}
+
+/// A token's oracle price together with the token's decimals.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Price {
+    pub price: i128,
+    pub decimals: u32,
+}
+
+/// Latest price of `token` from `oracle`. Prices older than `max_age`
+/// seconds, or stamped in the future, are rejected.
+pub fn fresh_price(
+    env: &Env,
+    oracle: &Address,
+    token: &Address,
+    max_age: u64,
+) -> Result<i128, PriceError> {
+    let client = PriceOracleClient::new(env, oracle);
+    let data = match client.try_lastprice(&Asset::Stellar(token.clone())) {
+        Ok(Ok(Some(data))) => data,
+        _ => return Err(PriceError::Unavailable),
+    };
+    if data.price <= 0 {
+        return Err(PriceError::Unavailable);
+    }
+    let now = env.ledger().timestamp();
+    if data.timestamp > now || now - data.timestamp > max_age {
+        return Err(PriceError::Stale);
+    }
+    Ok(data.price)
+}
+
+/// Value of `amount` token units in the quote currency, scaled by the
+/// feed's decimals.
+pub fn to_quote(amount: i128, price: Price) -> Option<i128> {
+    amount
+        .checked_mul(price.price)?
+        .checked_div(10i128.checked_pow(price.decimals)?)
+}
+
+/// Amount of the `to` token worth the same as `amount` of the `from` token.
+pub fn convert(amount: i128, from: Price, to: Price) -> Option<i128> {
+    if to.price <= 0 {
+        return None;
+    }
+    let scale_from = 10i128.checked_pow(from.decimals)?;
+    let scale_to = 10i128.checked_pow(to.decimals)?;
+    let value = amount.checked_mul(from.price)?;
+    match value.checked_mul(scale_to) {
+        Some(scaled) => scaled.checked_div(to.price.checked_mul(scale_from)?),
+        // Very large amounts: divide first, giving up sub-unit precision.
+        None => value
+            .checked_div(scale_from)?
+            .checked_mul(scale_to)?
+            .checked_div(to.price),
+    }
+}
+
+/// Collateral value over debt value in basis points; 10000 when there is
+/// no debt.
+pub fn health_factor_bps(collateral_value: i128, debt_value: i128) -> u32 {
+    if debt_value <= 0 {
+        return 10000;
+    }
+    (collateral_value.max(0) as u128)
+        .checked_mul(10000)
+        .and_then(|v| v.checked_div(debt_value as u128))
+        .map(|v| v.min(u32::MAX as u128) as u32)
+        .unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XLM: Price = Price {
+        price: 12_000_000_000_000, // 0.12 at 14 feed decimals
+        decimals: 7,
+    };
+    const USDC: Price = Price {
+        price: 100_000_000_000_000, // 1.00
+        decimals: 6,
+    };
+
+    #[test]
+    fn converts_between_tokens_by_value() {
+        // 1000 XLM is worth 120 USDC.
+        assert_eq!(convert(10_000_000_000, XLM, USDC), Some(120_000_000));
+        assert_eq!(convert(120_000_000, USDC, XLM), Some(10_000_000_000));
+        assert_eq!(to_quote(10_000_000_000, XLM), to_quote(120_000_000, USDC));
+    }
+
+    #[test]
+    fn conversion_survives_large_amounts() {
+        let huge = i128::MAX / 1_000_000_000_000_000;
+        assert!(convert(huge, XLM, USDC).is_some());
+        assert_eq!(
+            convert(
+                1,
+                XLM,
+                Price {
+                    price: 0,
+                    decimals: 7
+                }
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn health_factor_compares_values() {
+        assert_eq!(health_factor_bps(150, 100), 15000);
+        assert_eq!(health_factor_bps(150, 0), 10000);
+        assert_eq!(health_factor_bps(0, 100), 0);
+    }
+}